@@ -36,7 +36,6 @@ const Configuration = {
             'JSON-RPC',
             'load_test',
             'mempool_infra',
-            'mempool_node',
             'mempool',
             'monitoring',
             'native_blockifier',
@@ -44,6 +43,7 @@ const Configuration = {
             'node',
             'protobuf',
             'release',
+            'sequencer_node',
             'skeleton',
             'starknet_api',
             'starknet_client',
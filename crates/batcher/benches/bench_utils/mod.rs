@@ -0,0 +1,88 @@
+// The state and the block builder shared by the batcher benchmarks: an account and a test contract
+// deployed in a dictionary state.
+
+use std::sync::Arc;
+
+use blockifier::context::ChainInfo;
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::state_api::{StateReader, StateResult};
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::dict_state_reader::DictStateReader;
+use blockifier::test_utils::initial_test_state::test_state;
+use blockifier::test_utils::{CairoVersion, BALANCE};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_batcher::block_builder::{
+    BatcherStateReader,
+    BlockBuilder,
+    BlockBuilderConfig,
+    StateReaderFactory,
+};
+use starknet_batcher::clock::TokioClock;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+use starknet_types_core::felt::Felt;
+
+pub fn account_contract() -> FeatureContract {
+    FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0)
+}
+
+pub fn test_contract() -> FeatureContract {
+    FeatureContract::TestContract(CairoVersion::Cairo0)
+}
+
+// The batcher state reader traits are implemented for a local type, as both the traits and the
+// test state reader are foreign to the benchmarks.
+struct BenchStateReader(DictStateReader);
+
+impl StateReader for BenchStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.0.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.0.get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.0.get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.0.get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.0.get_compiled_class_hash(class_hash)
+    }
+}
+
+impl BatcherStateReader for BenchStateReader {}
+
+struct BenchStateReaderFactory(DictStateReader);
+
+impl StateReaderFactory for BenchStateReaderFactory {
+    fn get_state_reader_for_next_block(
+        &self,
+    ) -> StateResult<(BlockNumber, Box<dyn BatcherStateReader>)> {
+        Ok((BlockNumber(1), Box::new(BenchStateReader(self.0.clone()))))
+    }
+}
+
+pub fn block_builder() -> BlockBuilder {
+    let chain_info = ChainInfo::create_for_testing();
+    let state = test_state(&chain_info, BALANCE, &[(account_contract(), 1), (test_contract(), 1)]);
+    BlockBuilder::new(
+        SharedDynamicConfig::new(BlockBuilderConfig::default()),
+        Arc::new(BenchStateReaderFactory(state.state)),
+        chain_info,
+        SharedL2GasPrice::default(),
+        None,
+        Arc::new(TokioClock),
+    )
+}
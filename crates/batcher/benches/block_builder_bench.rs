@@ -0,0 +1,103 @@
+#![allow(clippy::unwrap_used)]
+
+// Benchmarks building a block of invoke transactions. With the `allocation_counting` feature, the
+// benchmark first asserts that the number of allocations per transaction stays within its budget,
+// so allocation regressions of the block builder hot path fail the benchmark.
+
+mod bench_utils;
+
+use bench_utils::{account_contract, block_builder, test_contract};
+use blockifier::test_utils::{create_trivial_calldata, MAX_FEE, TEST_SEQUENCER_ADDRESS};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use starknet_api::block::BlockTimestamp;
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::test_utils::invoke::invoke_tx;
+use starknet_api::transaction::{Fee, TransactionHash, TransactionVersion};
+use starknet_api::{contract_address, felt, invoke_tx_args, patricia_key};
+use starknet_batcher::block_builder::{BlockBuilder, BlockBuilderTrait};
+use starknet_batcher_types::batcher_types::ProposalBlockInfo;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+
+// Fits in a block of the default capacity.
+const N_TXS: u64 = 100;
+// The budget of allocations per transaction, including its execution by the blockifier; about
+// 1300 at the time of writing.
+#[cfg(feature = "allocation_counting")]
+const MAX_ALLOCATIONS_PER_TX: usize = 1500;
+
+fn txs() -> Vec<Transaction> {
+    (0..N_TXS)
+        .map(|nonce| {
+            let tx = invoke_tx(invoke_tx_args! {
+                sender_address: account_contract().get_instance_address(0),
+                calldata: create_trivial_calldata(test_contract().get_instance_address(0)),
+                version: TransactionVersion::ONE,
+                max_fee: Fee(MAX_FEE),
+                nonce: Nonce(felt!(nonce)),
+            });
+            Transaction::Invoke(InvokeTransaction { tx, tx_hash: TransactionHash(felt!(nonce)) })
+        })
+        .collect()
+}
+
+// Builds a block of the given transactions, returning the transactions added to it.
+async fn build_block(block_builder: &BlockBuilder, txs: Vec<Transaction>) -> Vec<Transaction> {
+    let (output_content_sender, output_content_receiver) = tokio::sync::mpsc::channel(txs.len());
+    let (result, output_txs) = tokio::join!(
+        block_builder.build_block(
+            ProposalBlockInfo {
+                timestamp: BlockTimestamp(0),
+                sequencer_address: contract_address!(TEST_SEQUENCER_ADDRESS),
+            },
+            tokio::time::Instant::now() + tokio::time::Duration::from_secs(60),
+            Box::pin(futures::stream::iter(txs)),
+            output_content_sender,
+        ),
+        ReceiverStream::new(output_content_receiver).collect::<Vec<_>>()
+    );
+    result.unwrap();
+    output_txs
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap()
+}
+
+#[cfg(feature = "allocation_counting")]
+fn assert_allocations_within_budget() {
+    use starknet_mempool_infra::allocation_counter::AllocationScope;
+
+    let (runtime, block_builder, txs) = (runtime(), block_builder(), txs());
+    let allocation_scope = AllocationScope::start();
+    let n_txs = runtime.block_on(build_block(&block_builder, txs)).len();
+    let n_allocations_per_tx = allocation_scope.n_allocations() / n_txs;
+
+    println!("Block builder allocations per transaction: {n_allocations_per_tx}");
+    assert!(
+        n_allocations_per_tx <= MAX_ALLOCATIONS_PER_TX,
+        "The block builder made {n_allocations_per_tx} allocations per transaction, above the \
+         budget of {MAX_ALLOCATIONS_PER_TX}."
+    );
+}
+
+fn build_block_benchmark(criterion: &mut Criterion) {
+    #[cfg(feature = "allocation_counting")]
+    assert_allocations_within_budget();
+
+    let runtime = runtime();
+    let mut group = criterion.benchmark_group("build_block");
+    group.throughput(Throughput::Elements(N_TXS));
+    group.bench_function("invoke", |bencher| {
+        bencher.iter_batched(
+            || (block_builder(), txs()),
+            |(block_builder, txs)| runtime.block_on(build_block(&block_builder, txs)),
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, build_block_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,261 @@
+#![allow(clippy::unwrap_used)]
+
+// Benchmarks generating proposals end to end: the proposals manager takes transactions from a
+// synthetic mempool and builds blocks of them with the block builder, for several transaction mixes
+// and mempool latencies. Reports, per scenario, the throughput of the transactions added to the
+// proposals, the time to the first transaction of a proposal, and how the proposals adhere to their
+// deadlines, as a baseline for performance work on the build loop.
+
+mod bench_utils;
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bench_utils::{account_contract, block_builder, test_contract};
+use blockifier::test_utils::{
+    create_calldata,
+    create_trivial_calldata,
+    MAX_FEE,
+    TEST_SEQUENCER_ADDRESS,
+};
+use futures::StreamExt;
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::test_utils::invoke::invoke_tx;
+use starknet_api::transaction::{Fee, TransactionHash, TransactionVersion};
+use starknet_api::{contract_address, felt, invoke_tx_args, patricia_key};
+use starknet_batcher::clock::TokioClock;
+use starknet_batcher::proposals_manager::{ProposalsManager, ProposalsManagerConfig};
+use starknet_batcher_types::batcher_types::{ProposalBlockInfo, ProposalRound};
+use starknet_mempool_types::communication::{MempoolClient, MempoolClientResult};
+use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::{
+    CommitBlockArgs,
+    DropAccountTxsArgs,
+    MempoolInput,
+    RejectedTransaction,
+    TxBudget,
+};
+use tokio::time::Instant;
+
+// More than a proposal takes, so that the mempool doesn't run out of transactions.
+const N_MEMPOOL_TXS: u64 = 2000;
+const N_RUNS: usize = 10;
+const PROPOSAL_TIMEOUT: Duration = Duration::from_millis(500);
+
+struct Scenario {
+    name: &'static str,
+    // The percentage of the transactions that write to the storage; the others only return a
+    // value.
+    storage_tx_percentage: u64,
+    // The time the mempool takes to answer a request for transactions.
+    mempool_latency: Duration,
+}
+
+const SCENARIOS: &[Scenario] = &[
+    Scenario { name: "light_txs", storage_tx_percentage: 0, mempool_latency: Duration::ZERO },
+    Scenario { name: "mixed_txs", storage_tx_percentage: 50, mempool_latency: Duration::ZERO },
+    Scenario { name: "storage_txs", storage_tx_percentage: 100, mempool_latency: Duration::ZERO },
+    Scenario {
+        name: "mixed_txs_slow_mempool",
+        storage_tx_percentage: 50,
+        mempool_latency: Duration::from_millis(20),
+    },
+];
+
+fn txs(storage_tx_percentage: u64) -> VecDeque<Transaction> {
+    let test_contract_address = test_contract().get_instance_address(0);
+    (0..N_MEMPOOL_TXS)
+        .map(|nonce| {
+            let calldata = if nonce % 100 < storage_tx_percentage {
+                create_calldata(
+                    test_contract_address,
+                    "test_storage_read_write",
+                    &[felt!(nonce), felt!(nonce)],
+                )
+            } else {
+                create_trivial_calldata(test_contract_address)
+            };
+            let tx = invoke_tx(invoke_tx_args! {
+                sender_address: account_contract().get_instance_address(0),
+                calldata,
+                version: TransactionVersion::ONE,
+                max_fee: Fee(MAX_FEE),
+                nonce: Nonce(felt!(nonce)),
+            });
+            Transaction::Invoke(InvokeTransaction { tx, tx_hash: TransactionHash(felt!(nonce)) })
+        })
+        .collect()
+}
+
+// A mempool that answers requests for transactions after a fixed latency, out of a queue of
+// transactions. Requests that may not wait for the latency are answered with no transactions.
+struct SyntheticMempool {
+    txs: Mutex<VecDeque<Transaction>>,
+    latency: Duration,
+}
+
+impl SyntheticMempool {
+    async fn take_txs(&self, n_txs: usize, max_wait: Duration) -> Vec<Transaction> {
+        tokio::time::sleep(self.latency.min(max_wait)).await;
+        if self.latency > max_wait {
+            return Vec::new();
+        }
+        let mut txs = self.txs.lock().unwrap();
+        let n_txs = n_txs.min(txs.len());
+        txs.drain(..n_txs).collect()
+    }
+}
+
+#[async_trait]
+impl MempoolClient for SyntheticMempool {
+    async fn add_tx(&self, _mempool_input: MempoolInput) -> MempoolClientResult<()> {
+        Ok(())
+    }
+
+    async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<Transaction>> {
+        Ok(self.take_txs(n_txs, Duration::MAX).await)
+    }
+
+    async fn get_txs_by_budget(&self, _budget: TxBudget) -> MempoolClientResult<Vec<Transaction>> {
+        unimplemented!("Proposals take transactions by number.")
+    }
+
+    async fn get_txs_within(
+        &self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolClientResult<Vec<Transaction>> {
+        Ok(self.take_txs(n_txs, max_wait).await)
+    }
+
+    // Proposals poll the mempool instead.
+    async fn open_tx_stream(&self) -> MempoolClientResult<()> {
+        Err(MempoolError::TransactionStreamUnavailable.into())
+    }
+
+    async fn close_tx_stream(&self) -> MempoolClientResult<()> {
+        Ok(())
+    }
+
+    async fn evict_txs(&self, _n_txs: usize) -> MempoolClientResult<usize> {
+        Ok(0)
+    }
+
+    async fn return_txs(&self, txs: Vec<Transaction>) -> MempoolClientResult<()> {
+        let mut mempool_txs = self.txs.lock().unwrap();
+        for tx in txs.into_iter().rev() {
+            mempool_txs.push_front(tx);
+        }
+        Ok(())
+    }
+
+    async fn reject_txs(&self, _rejected_txs: Vec<RejectedTransaction>) -> MempoolClientResult<()> {
+        Ok(())
+    }
+
+    async fn commit_block(&self, _args: CommitBlockArgs) -> MempoolClientResult<()> {
+        Ok(())
+    }
+
+    async fn drop_account_txs(
+        &self,
+        _args: DropAccountTxsArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>> {
+        Ok(Vec::new())
+    }
+
+    async fn flush(&self) -> MempoolClientResult<Vec<TransactionHash>> {
+        Ok(Vec::new())
+    }
+}
+
+struct RunStats {
+    n_txs: usize,
+    // From the request to generate the proposal to the end of its content.
+    duration: Duration,
+    time_to_first_tx: Option<Duration>,
+    // How long after its deadline the content of the proposal ended, if it did.
+    overrun: Duration,
+}
+
+// Generates a single proposal, streaming its content as consensus would.
+async fn run(scenario: &Scenario) -> RunStats {
+    let mempool_client = Arc::new(SyntheticMempool {
+        txs: Mutex::new(txs(scenario.storage_tx_percentage)),
+        latency: scenario.mempool_latency,
+    });
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        mempool_client,
+        None,
+        Arc::new(block_builder()),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let start = Instant::now();
+    let deadline = start + PROPOSAL_TIMEOUT;
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            deadline,
+            ProposalRound { height: BlockNumber(0), round: 0 },
+            ProposalBlockInfo {
+                timestamp: BlockTimestamp(0),
+                sequencer_address: contract_address!(TEST_SEQUENCER_ADDRESS),
+            },
+        )
+        .await
+        .unwrap();
+    let mut n_txs = 0;
+    let mut time_to_first_tx = None;
+    while output_tx_stream.next().await.is_some() {
+        time_to_first_tx.get_or_insert_with(|| start.elapsed());
+        n_txs += 1;
+    }
+    let end = Instant::now();
+
+    RunStats {
+        n_txs,
+        duration: end - start,
+        time_to_first_tx,
+        overrun: end.saturating_duration_since(deadline),
+    }
+}
+
+fn median(mut values: Vec<Duration>) -> Duration {
+    values.sort();
+    values.get(values.len() / 2).copied().unwrap_or_default()
+}
+
+#[allow(clippy::as_conversions)]
+fn report(scenario: &Scenario, runs: &[RunStats]) {
+    let n_txs: usize = runs.iter().map(|run| run.n_txs).sum();
+    let duration: Duration = runs.iter().map(|run| run.duration).sum();
+    let time_to_first_tx = median(runs.iter().filter_map(|run| run.time_to_first_tx).collect());
+    let n_overrun_runs = runs.iter().filter(|run| !run.overrun.is_zero()).count();
+    let max_overrun = runs.iter().map(|run| run.overrun).max().unwrap_or_default();
+
+    println!("{}:", scenario.name);
+    println!("  txs per proposal:        {}", n_txs / runs.len());
+    println!("  txs/sec:                 {:.0}", n_txs as f64 / duration.as_secs_f64());
+    println!("  time to first tx:        {time_to_first_tx:?} (median)");
+    println!("  proposals past deadline: {n_overrun_runs}/{}", runs.len());
+    println!("  max deadline overrun:    {max_overrun:?}");
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    println!(
+        "Proposal pipeline, {N_RUNS} proposals per scenario, each with a timeout of \
+         {PROPOSAL_TIMEOUT:?}."
+    );
+    for scenario in SCENARIOS {
+        let runs: Vec<RunStats> = (0..N_RUNS).map(|_| runtime.block_on(run(scenario))).collect();
+        report(scenario, &runs);
+    }
+}
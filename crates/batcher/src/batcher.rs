@@ -1,24 +1,1048 @@
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroU128;
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use starknet_mempool_infra::component_runner::ComponentStarter;
+#[cfg(test)]
+use mockall::automock;
+use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::skipped_height::SkippedHeightStorageWriter;
+use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
+use papyrus_storage::{StorageReader, StorageWriter};
+use starknet_api::block::{BlockHeader, BlockNumber};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::{
+    AddSyncedBlockInput,
+    BatcherResult,
+    BlockCommitments,
+    BuildProposalInput,
+    BuildProposalReturnValue,
+    CommittedBlock,
+    DecisionReachedInput,
+    EnvironmentFingerprint,
+    ForceAbortAndResyncInput,
+    ForceAbortAndResyncReturnValue,
+    GetCommittedBlockInput,
+    GetProposalContentInput,
+    GetProposalContentReturnValue,
+    GetProposalMetadataInput,
+    GetProposalProgressInput,
+    GetValidationStatusInput,
+    ImportBlockInput,
+    ImportBlockStatus,
+    PendingState,
+    ProposalBlockInfo,
+    ProposalCommitment,
+    ProposalContent,
+    ProposalId,
+    ProposalMetadata,
+    ProposalProgress,
+    ProposalRound,
+    ProposalState,
+    ProposalStatus,
+    ReproposeProposalInput,
+    ReproposeProposalReturnValue,
+    SkipHeightInput,
+    StartHeightInput,
+    ValidateProposalInput,
+    ValidationStatus,
+};
+use starknet_batcher_types::deadline::Deadline;
+use starknet_batcher_types::errors::BatcherError;
+use starknet_class_manager_types::communication::SharedClassManagerClient;
+use starknet_error_codes::count_error;
+use starknet_l1_gas_price_types::communication::SharedL1GasPriceProviderClient;
+use starknet_l1_provider_types::communication::SharedL1ProviderClient;
+use starknet_mempool_infra::component_definitions::ComponentHealth;
+use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
 use starknet_mempool_types::communication::SharedMempoolClient;
+use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::oneshot::error::TryRecvError;
+use tokio::sync::Mutex;
+use tracing::{error, info, instrument, warn};
 
+use crate::block_builder::{
+    BlockBuilder,
+    BlockBuilderConfig,
+    BlockBuilderTrait,
+    ProposedBlockHeader,
+    SharedBuildProgress,
+    SharedPendingState,
+    StateReaderFactory,
+};
+use crate::block_feed::{BlockFeedPublisher, BlockSummary};
+use crate::block_info_policy::BlockInfoPolicy;
+use crate::block_replay::BlockReplayRecorder;
+use crate::cached_state_reader::CachingStateReaderFactory;
+use crate::clock::{SharedClock, TokioClock};
 use crate::config::BatcherConfig;
+use crate::environment_fingerprint::ExecutionEnvironment;
+use crate::fee_market::FeeMarket;
+use crate::papyrus_state_reader::PapyrusReaderFactory;
+use crate::proposal_commitment::calculate_proposal_commitment;
+use crate::proposal_content::PagedProposalContent;
+use crate::proposal_journal::{JournalRecovery, ProposalJournal};
+use crate::proposals_manager::{
+    ProposalValidationReceiver,
+    ProposalValidationResult,
+    ProposalsManager,
+    ProposalsManagerError,
+};
+
+#[cfg(test)]
+#[path = "batcher_test.rs"]
+mod batcher_test;
 
-// TODO(Tsabary/Yael/Dafna): Replace with actual batcher code.
 pub struct Batcher {
     pub config: BatcherConfig,
     pub mempool_client: SharedMempoolClient,
+    // Notified of the committed blocks, to stop providing the L1 handler transactions they
+    // include.
+    l1_provider_client: Option<SharedL1ProviderClient>,
+    proposals_manager: ProposalsManager,
+    storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+    storage_writer: Box<dyn BatcherStorageWriterTrait>,
+    // The height the batcher currently works on, unknown until a height is started or handled.
+    active_height: Option<BlockNumber>,
+    // The content of the generated proposals of the active height. Locked only to make the batcher
+    // `Sync`, as the content is accessed through `&mut self`.
+    proposal_contents: Mutex<HashMap<ProposalId, PagedProposalContent>>,
+    // The validations of the active height, polled by the clients that started them.
+    validations: HashMap<ProposalId, ProposalValidation>,
+    // Fingerprinted with a random seed for each generated proposal, and with the seed of the
+    // proposer for each validated one.
+    environment: ExecutionEnvironment,
+    // Pushes the committed blocks to an external endpoint, if configured.
+    block_feed: Option<BlockFeedPublisher>,
+    // Sets the L2 gas price of the next block by the gas usage of the committed ones.
+    fee_market: FeeMarket,
+    // Chooses the timestamps and the sequencer address of the proposals, and checks those of the
+    // validated ones.
+    // TODO: Resume from the timestamp of the latest block after a restart, once it is read from
+    // the storage.
+    block_info_policy: BlockInfoPolicy,
+    // The clock the deadlines of the requests are converted to.
+    clock: SharedClock,
+    // The state changes of the block being built, published by the block builder.
+    pending_state: SharedPendingState,
+    // The progress of the proposals left by the previous run, recovered once the batcher starts.
+    journal_recovery: Option<JournalRecovery>,
+    // The latest blocks built and committed by the batcher, oldest first, served to state sync and
+    // to the feeder API. Synced blocks are not kept.
+    committed_blocks: VecDeque<CommittedBlock>,
+}
+
+// A validation started by a client, kept until the active height ends.
+enum ProposalValidation {
+    Running {
+        result_receiver: ProposalValidationReceiver,
+        proposer_fingerprint: Option<EnvironmentFingerprint>,
+    },
+    Done(ProposalStatus),
 }
 
 impl Batcher {
-    pub fn new(config: BatcherConfig, mempool_client: SharedMempoolClient) -> Self {
-        Self { config, mempool_client }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: BatcherConfig,
+        mempool_client: SharedMempoolClient,
+        l1_provider_client: Option<SharedL1ProviderClient>,
+        mempool_tx_stream: Option<Receiver<Transaction>>,
+        storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+        storage_writer: Box<dyn BatcherStorageWriterTrait>,
+        block_builder: Arc<dyn BlockBuilderTrait>,
+        l2_gas_price: SharedL2GasPrice,
+        clock: SharedClock,
+    ) -> Self {
+        let proposals_manager = ProposalsManager::new(
+            config.proposals_manager.clone(),
+            mempool_client.clone(),
+            l1_provider_client.clone(),
+            block_builder,
+            mempool_tx_stream,
+            clock.clone(),
+        );
+        let environment = ExecutionEnvironment::local(&config.chain_info, &config.block_builder);
+        let block_feed = config.block_feed.clone().map(BlockFeedPublisher::new);
+        let fee_market = FeeMarket::new(config.fee_market.clone(), l2_gas_price);
+        let block_info_policy =
+            BlockInfoPolicy::new(config.block_info_policy.clone(), config.sequencer_address);
+        Self {
+            config,
+            mempool_client,
+            l1_provider_client,
+            proposals_manager,
+            storage_reader,
+            storage_writer,
+            active_height: None,
+            proposal_contents: Mutex::new(HashMap::new()),
+            validations: HashMap::new(),
+            environment,
+            block_feed,
+            fee_market,
+            block_info_policy,
+            clock,
+            pending_state: SharedPendingState::default(),
+            journal_recovery: None,
+            committed_blocks: VecDeque::new(),
+        }
+    }
+
+    pub fn with_pending_state(mut self, pending_state: SharedPendingState) -> Self {
+        self.pending_state = pending_state;
+        self
+    }
+
+    /// Reports the progress of the active proposal by the given progress, published by the block
+    /// builder.
+    pub fn with_build_progress(mut self, build_progress: SharedBuildProgress) -> Self {
+        self.proposals_manager = self.proposals_manager.with_build_progress(build_progress);
+        self
+    }
+
+    /// Records the progress of the generated proposals in the given journal, and recovers the
+    /// progress of the previous run once the batcher starts.
+    pub fn with_proposal_journal(
+        mut self,
+        journal: ProposalJournal,
+        recovery: JournalRecovery,
+    ) -> Self {
+        self.proposals_manager = self.proposals_manager.with_journal(Arc::new(journal));
+        self.journal_recovery = Some(recovery);
+        self
+    }
+
+    /// Starts generating a proposal for the active height with transactions from the mempool, with
+    /// the block info chosen by the block info policy.
+    #[instrument(skip(self))]
+    pub async fn build_proposal(
+        &mut self,
+        input: BuildProposalInput,
+    ) -> BatcherResult<BuildProposalReturnValue> {
+        let BuildProposalInput { proposal_id, deadline, height, round, timestamp } = input;
+        self.verify_proposal_height(proposal_id, height)?;
+        let deadline = self.deadline_instant(deadline)?;
+        let round = ProposalRound { height, round };
+
+        let block_info =
+            self.block_info_policy.proposed_block_info(timestamp, self.clock.block_timestamp());
+        let tx_stream = self
+            .proposals_manager
+            .generate_block_proposal(proposal_id, deadline, round, block_info)
+            .await
+            .map_err(to_batcher_error)?;
+        let content = PagedProposalContent::new(tx_stream, self.config.proposal_content_page_size);
+        self.proposal_contents.get_mut().insert(proposal_id, content);
+        Ok(BuildProposalReturnValue {
+            environment_fingerprint: self.environment.fingerprint(rand::random()),
+            block_info,
+        })
+    }
+
+    /// Re-proposes a proposal generated earlier in the active height, e.g., once consensus locked
+    /// on it in a previous round. Its cached content is served as is and its commitment is
+    /// returned, without rebuilding its block; a proposal that is no longer cached is rejected.
+    #[instrument(skip(self))]
+    pub async fn repropose_proposal(
+        &mut self,
+        input: ReproposeProposalInput,
+    ) -> BatcherResult<ReproposeProposalReturnValue> {
+        let ReproposeProposalInput { proposal_id, height, round } = input;
+        self.verify_proposal_height(proposal_id, height)?;
+        let round = ProposalRound { height, round };
+
+        let (tx_stream, commitment, block_info) =
+            self.proposals_manager.repropose(proposal_id, round).map_err(to_batcher_error)?;
+        let content = PagedProposalContent::new(tx_stream, self.config.proposal_content_page_size);
+        self.proposal_contents.get_mut().insert(proposal_id, content);
+        Ok(ReproposeProposalReturnValue {
+            commitment,
+            environment_fingerprint: self.environment.fingerprint(rand::random()),
+            block_info,
+        })
+    }
+
+    /// Starts validating a proposal of another node for the active height, by building a block
+    /// out of its transactions in the background; its status is then returned by
+    /// `get_validation_status`. The block info of the proposal, if known, is checked by the block
+    /// info policy first.
+    #[instrument(skip(self, input), fields(proposal_id = input.proposal_id))]
+    pub async fn validate_proposal(&mut self, input: ValidateProposalInput) -> BatcherResult<()> {
+        let ValidateProposalInput {
+            proposal_id,
+            deadline,
+            height,
+            round,
+            txs,
+            environment_fingerprint: proposer_fingerprint,
+            block_info,
+        } = input;
+        self.verify_proposal_height(proposal_id, height)?;
+        let block_info = match block_info {
+            Some(block_info) => {
+                self.block_info_policy
+                    .validate(&block_info, self.clock.block_timestamp())
+                    .map_err(|error| {
+                        warn!("Proposal {} has an invalid block info: {}", proposal_id, error);
+                        BatcherError::InvalidBlockInfo { proposal_id, error }
+                    })?;
+                block_info
+            }
+            None => self.block_info_policy.proposed_block_info(None, self.clock.block_timestamp()),
+        };
+
+        let round = ProposalRound { height, round };
+        let result_receiver =
+            self.start_validation(proposal_id, deadline, round, block_info, txs).await?;
+        self.validations.insert(
+            proposal_id,
+            ProposalValidation::Running { result_receiver, proposer_fingerprint },
+        );
+        Ok(())
+    }
+
+    /// Returns the status of a validation started by `validate_proposal`. A rejected proposal that
+    /// was generated in a different environment is reported as an environment mismatch.
+    #[instrument(skip(self))]
+    pub async fn get_validation_status(
+        &mut self,
+        input: GetValidationStatusInput,
+    ) -> BatcherResult<ValidationStatus> {
+        let GetValidationStatusInput { proposal_id } = input;
+        let environment = self.environment;
+        let validation = self
+            .validations
+            .get_mut(&proposal_id)
+            .ok_or(BatcherError::ProposalNotFound { proposal_id })?;
+        let status = match validation {
+            ProposalValidation::Done(status) => return Ok(ValidationStatus::Done(status.clone())),
+            ProposalValidation::Running { result_receiver, proposer_fingerprint } => {
+                match result_receiver.try_recv() {
+                    Err(TryRecvError::Empty) => return Ok(ValidationStatus::InProgress),
+                    Err(TryRecvError::Closed) => {
+                        error!(
+                            "The validation of proposal {} ended without a result.",
+                            proposal_id
+                        );
+                        Err(BatcherError::InternalError)
+                    }
+                    Ok(result) => result.map_err(to_batcher_error).map(|validation_result| {
+                        proposal_status(
+                            proposal_id,
+                            validation_result,
+                            *proposer_fingerprint,
+                            &environment,
+                        )
+                    }),
+                }
+            }
+        };
+        match status {
+            Ok(status) => {
+                *validation = ProposalValidation::Done(status.clone());
+                Ok(ValidationStatus::Done(status))
+            }
+            Err(err) => {
+                self.validations.remove(&proposal_id);
+                Err(err)
+            }
+        }
+    }
+
+    /// Imports a block built by an external builder, e.g., a backup builder service, as the local
+    /// proposal for the active height. The block is validated by building a block out of its
+    /// transactions, and is then served by `get_proposal_content` as if it was generated locally,
+    /// provided its commitments match the expected ones.
+    #[instrument(skip(self, input), fields(proposal_id = input.proposal_id))]
+    pub async fn import_block(
+        &mut self,
+        input: ImportBlockInput,
+    ) -> BatcherResult<ImportBlockStatus> {
+        let ImportBlockInput { proposal_id, deadline, height, round, txs, expected_commitments } =
+            input;
+        self.verify_proposal_height(proposal_id, height)?;
+
+        let round = ProposalRound { height, round };
+        // The block info of an imported block isn't known, so it's built with the block info this
+        // node would have proposed.
+        let block_info =
+            self.block_info_policy.proposed_block_info(None, self.clock.block_timestamp());
+        let validation_result =
+            self.run_validation(proposal_id, deadline, round, block_info, txs.clone()).await?;
+        let commitment = match validation_result {
+            ProposalValidationResult::Valid { commitment } => commitment,
+            ProposalValidationResult::ContentMismatch { n_proposed_txs, n_executed_txs, error } => {
+                warn!(
+                    "Imported block {} was rejected: {} of its {} transactions were executed: \
+                     {:?}.",
+                    proposal_id, n_executed_txs, n_proposed_txs, error
+                );
+                return Ok(ImportBlockStatus::ContentMismatch { n_proposed_txs, n_executed_txs });
+            }
+        };
+
+        let commitments =
+            BlockCommitments { state_diff_commitment: commitment.state_diff_commitment };
+        if commitments != expected_commitments {
+            warn!(
+                "Imported block {} was rejected: its commitments {:?} differ from the expected \
+                 {:?}.",
+                proposal_id, commitments, expected_commitments
+            );
+            self.proposals_manager.discard_completed_proposal(proposal_id).await;
+            return Ok(ImportBlockStatus::CommitmentMismatch {
+                expected: expected_commitments,
+                actual: commitments,
+            });
+        }
+
+        let content = PagedProposalContent::new(
+            Box::pin(futures::stream::iter(txs)),
+            self.config.proposal_content_page_size,
+        );
+        self.proposal_contents.get_mut().insert(proposal_id, content);
+        info!("Imported block {} as the local proposal of height {}.", proposal_id, height);
+        Ok(ImportBlockStatus::Imported {
+            environment_fingerprint: self.environment.fingerprint(rand::random()),
+        })
+    }
+
+    /// Returns the given page of the transactions of the proposal, waiting for them to be added,
+    /// or `Finished` with the commitment of the block if the proposal ended before the page. Pages
+    /// are numbered from 0, and can be requested again until a decision is reached.
+    #[instrument(skip(self))]
+    pub async fn get_proposal_content(
+        &mut self,
+        input: GetProposalContentInput,
+    ) -> BatcherResult<GetProposalContentReturnValue> {
+        let GetProposalContentInput { proposal_id, page } = input;
+        let content = self
+            .proposal_contents
+            .get_mut()
+            .get_mut(&proposal_id)
+            .ok_or(BatcherError::ProposalNotFound { proposal_id })?;
+
+        if let Some(txs) = content.page(page).await {
+            return Ok(GetProposalContentReturnValue {
+                content: ProposalContent::Txs(txs.to_vec()),
+            });
+        }
+        // Only the proposals whose block was built have a commitment.
+        let commitment = self.proposals_manager.commitment(proposal_id).await.map_err(|err| {
+            warn!("Proposal {} ended without a block: {}", proposal_id, err);
+            BatcherError::ProposalFailed { proposal_id }
+        })?;
+        Ok(GetProposalContentReturnValue { content: ProposalContent::Finished { commitment } })
+    }
+
+    /// Starts working on the given height, before consensus makes its proposals. Proposals are
+    /// executed on top of the storage, so a height later than the active one can only be started
+    /// once the storage reached it, e.g., after the node synced the blocks it missed from its
+    /// peers. The proposals of the previous height are discarded. Starting the active height again
+    /// has no effect.
+    #[instrument(skip(self))]
+    pub async fn start_height(&mut self, input: StartHeightInput) -> BatcherResult<()> {
+        let StartHeightInput { height } = input;
+        let active_height = self.active_height()?;
+        if height == active_height {
+            info!("Height {} is already active.", height);
+            return Ok(());
+        }
+        if height < active_height || height != self.storage_height()? {
+            return Err(BatcherError::HeightMismatch { active_height, requested_height: height });
+        }
+
+        self.proposals_manager.start_height(height).await;
+        self.proposal_contents.get_mut().clear();
+        self.validations.clear();
+        self.active_height = Some(height);
+        info!("Started height {}.", height);
+        Ok(())
+    }
+
+    /// Admin action: aborts the active proposal and discards the proposals of the active height,
+    /// which starts over from its first round. None of its proposals is committed, so the height
+    /// is completed by a block synced from peers, see [`Self::add_synced_block`], or by a new
+    /// decision.
+    #[instrument(skip(self))]
+    pub async fn force_abort_and_resync(
+        &mut self,
+        _input: ForceAbortAndResyncInput,
+    ) -> BatcherResult<ForceAbortAndResyncReturnValue> {
+        if !self.config.enable_admin_actions {
+            return Err(BatcherError::AdminActionsDisabled);
+        }
+
+        let aborted_proposal_id = self.proposals_manager.restart_height().await;
+        self.proposal_contents.get_mut().clear();
+        self.validations.clear();
+        info!("Discarded the proposals of the active height, to resync it.");
+
+        Ok(ForceAbortAndResyncReturnValue { aborted_proposal_id })
+    }
+
+    /// Returns the state of the proposals of the active height, for operators.
+    #[instrument(skip(self))]
+    pub async fn get_proposal_state(&self) -> BatcherResult<ProposalState> {
+        Ok(ProposalState {
+            active_height: self.active_height,
+            active_proposal_id: self.proposals_manager.active_proposal_id().await,
+            completed_proposal_ids: self.proposals_manager.completed_proposal_ids().await,
+        })
+    }
+
+    /// Returns the resources used by the block built for a proposal of the active height, generated
+    /// or validated, for operators to tune the block limits with.
+    #[instrument(skip(self))]
+    pub async fn get_proposal_metadata(
+        &self,
+        input: GetProposalMetadataInput,
+    ) -> BatcherResult<ProposalMetadata> {
+        let GetProposalMetadataInput { proposal_id } = input;
+        self.proposals_manager.metadata(proposal_id).await.map_err(to_batcher_error)
+    }
+
+    /// Returns the progress of building the block of a proposal of the active height, for the
+    /// proposer's consensus to decide whether to keep waiting for a long build or abort it.
+    #[instrument(skip(self))]
+    pub async fn get_proposal_progress(
+        &self,
+        input: GetProposalProgressInput,
+    ) -> BatcherResult<ProposalProgress> {
+        let GetProposalProgressInput { proposal_id } = input;
+        self.proposals_manager.progress(proposal_id).await.map_err(to_batcher_error)
+    }
+
+    /// Returns the state changes of the block being built at the active height, for the gateway to
+    /// validate transactions on top of. The state changes of blocks of previous heights, e.g., of
+    /// proposals that were not decided on, are not returned.
+    #[instrument(skip(self))]
+    pub async fn get_pending_state(&self) -> BatcherResult<Option<PendingState>> {
+        Ok(self
+            .pending_state
+            .get()
+            .filter(|pending_state| Some(pending_state.block_number) == self.active_height))
+    }
+
+    /// Stops the active proposal before the node shuts down, returning its unused transactions
+    /// to the mempool. Returns the id of the stopped proposal, if any.
+    #[instrument(skip(self))]
+    pub async fn shutdown(&mut self) -> Option<ProposalId> {
+        self.proposals_manager.shutdown().await
+    }
+
+    /// Handles a consensus decision of no block for the given height: aborts the proposal of the
+    /// height, if any, records the skipped height in the storage, so that the numbers of the next
+    /// blocks match the storage, and advances to the next height.
+    #[instrument(skip(self))]
+    pub async fn skip_height(&mut self, input: SkipHeightInput) -> BatcherResult<()> {
+        let SkipHeightInput { height } = input;
+        let active_height = self.active_height()?;
+        if active_height != height {
+            return Err(BatcherError::HeightMismatch { active_height, requested_height: height });
+        }
+
+        self.storage_writer.commit_skipped_height(height).map_err(|err| {
+            error!("Failed to commit the skipped height {} to the storage: {}", height, err);
+            BatcherError::InternalError
+        })?;
+        self.proposals_manager.start_height(height.unchecked_next()).await;
+        self.proposal_contents.get_mut().clear();
+        self.validations.clear();
+        self.active_height = Some(height.unchecked_next());
+        info!("Skipped height {}.", height);
+        Ok(())
+    }
+
+    /// Commits the block of the proposal consensus decided on to the storage, and advances to the
+    /// next height.
+    #[instrument(skip(self))]
+    pub async fn decision_reached(&mut self, input: DecisionReachedInput) -> BatcherResult<()> {
+        let DecisionReachedInput { proposal_id } = input;
+        let height = self.active_height()?;
+
+        let artifacts =
+            self.proposals_manager.decision_reached(proposal_id).await.map_err(to_batcher_error)?;
+        self.proposal_contents.get_mut().clear();
+        self.validations.clear();
+        let tx_hashes = artifacts.tx_hashes();
+        let n_txs = tx_hashes.len();
+        let n_events = artifacts.n_events();
+        let l2_gas_used = artifacts.gas_consumed.l2_gas;
+        let commit_block_args = commit_block_args(&tx_hashes, &artifacts.state_diff);
+        let ProposalCommitment { state_diff_commitment, transaction_commitment } =
+            calculate_proposal_commitment(&artifacts);
+        // TODO: Fill the state root of the header, and store the L2 gas price once the header has
+        // it.
+        let ProposedBlockHeader {
+            timestamp,
+            sequencer,
+            l1_gas_price,
+            l1_data_gas_price,
+            l2_gas_price: _,
+            starknet_version,
+        } = artifacts.header;
+        let header = BlockHeader {
+            block_number: height,
+            timestamp,
+            sequencer,
+            l1_gas_price,
+            l1_data_gas_price,
+            state_diff_commitment: Some(state_diff_commitment),
+            transaction_commitment: Some(transaction_commitment),
+            n_transactions: n_txs,
+            n_events,
+            starknet_version,
+            ..Default::default()
+        };
+        let receipts = artifacts.receipts();
+        self.storage_writer.commit_proposal(header.clone(), artifacts.state_diff.clone()).map_err(
+            |err| {
+                error!("Failed to commit proposal {} to the storage: {}", proposal_id, err);
+                BatcherError::InternalError
+            },
+        )?;
+        self.keep_committed_block(CommittedBlock {
+            header: header.clone(),
+            txs: artifacts.txs,
+            receipts,
+            state_diff: artifacts.state_diff,
+        });
+        self.active_height = Some(height.unchecked_next());
+        self.fee_market.on_block_committed(l2_gas_used);
+        self.block_info_policy.record_block(header.timestamp);
+        // The block is already committed, so failing to notify the mempool or the L1 provider
+        // doesn't fail the decision.
+        self.notify_block_committed(
+            commit_block_args,
+            BlockSummary { header, tx_hashes, n_events },
+        )
+        .await;
+
+        info!(
+            "Committed proposal {} as block {} with {} transactions.",
+            proposal_id, height, n_txs
+        );
+        Ok(())
     }
+
+    /// Returns a block the batcher built and committed, with its transactions, their receipts and
+    /// the state diff of the block. Only the latest committed blocks are kept; `None` is returned
+    /// for older blocks, for blocks that weren't committed yet, and for synced blocks.
+    #[instrument(skip(self))]
+    pub async fn get_committed_block(
+        &self,
+        input: GetCommittedBlockInput,
+    ) -> BatcherResult<Option<Box<CommittedBlock>>> {
+        let GetCommittedBlockInput { block_number } = input;
+        Ok(self
+            .committed_blocks
+            .iter()
+            .find(|block| block.header.block_number == block_number)
+            .map(|block| Box::new(block.clone())))
+    }
+
+    // Keeps the committed block, dropping the oldest kept block beyond the configured capacity.
+    fn keep_committed_block(&mut self, block: CommittedBlock) {
+        if self.config.committed_blocks_capacity == 0 {
+            return;
+        }
+        if self.committed_blocks.len() == self.config.committed_blocks_capacity {
+            self.committed_blocks.pop_front();
+        }
+        self.committed_blocks.push_back(block);
+    }
+
+    /// Applies a block the node synced from its peers to the storage, without building or
+    /// validating it, e.g., while the node catches up with the network. The block must follow the
+    /// storage. If the batcher didn't move past the height of the block, it advances to the next
+    /// height, discarding the proposals of the height.
+    #[instrument(skip(self, input), fields(height = %input.header.block_number))]
+    pub async fn add_synced_block(&mut self, input: AddSyncedBlockInput) -> BatcherResult<()> {
+        let AddSyncedBlockInput { header, state_diff, tx_hashes } = input;
+        let height = header.block_number;
+        let storage_height = self.storage_height()?;
+        if height != storage_height {
+            return Err(BatcherError::SyncedBlockHeightMismatch {
+                storage_height,
+                block_height: height,
+            });
+        }
+
+        let commit_block_args = commit_block_args(&tx_hashes, &state_diff);
+        self.storage_writer.commit_proposal(header.clone(), state_diff).map_err(|err| {
+            error!("Failed to commit synced block {} to the storage: {}", height, err);
+            BatcherError::InternalError
+        })?;
+        // A skipped height may be synced after the batcher moved past it.
+        if self.active_height.map_or(true, |active_height| active_height <= height) {
+            self.proposals_manager.start_height(height.unchecked_next()).await;
+            self.proposal_contents.get_mut().clear();
+            self.validations.clear();
+            self.active_height = Some(height.unchecked_next());
+            self.block_info_policy.record_block(header.timestamp);
+        }
+        // TODO: Update the fee market once synced blocks carry their L2 gas usage.
+        let n_events = header.n_events;
+        self.notify_block_committed(
+            commit_block_args,
+            BlockSummary { header, tx_hashes, n_events },
+        )
+        .await;
+
+        info!("Added synced block {}.", height);
+        Ok(())
+    }
+
+    // Notifies the mempool, the L1 provider and the block feed of a block committed to the
+    // storage. Failures are only logged, as the block is already committed.
+    async fn notify_block_committed(
+        &self,
+        commit_block_args: CommitBlockArgs,
+        block_summary: BlockSummary,
+    ) {
+        let height = block_summary.header.block_number;
+        if let Err(err) = self.mempool_client.commit_block(commit_block_args).await {
+            error!("Failed to notify the mempool of the commit of block {}: {}", height, err);
+        }
+        if let Some(l1_provider_client) = &self.l1_provider_client {
+            if let Err(err) = l1_provider_client.commit_block(block_summary.tx_hashes.clone()).await
+            {
+                error!(
+                    "Failed to notify the L1 provider of the commit of block {}: {}",
+                    height, err
+                );
+            }
+        }
+        if let Some(block_feed) = &self.block_feed {
+            block_feed.publish(block_summary);
+        }
+    }
+
+    // Builds a block out of the given transactions, and waits for the result of the validation.
+    async fn run_validation(
+        &mut self,
+        proposal_id: ProposalId,
+        deadline: Deadline,
+        round: ProposalRound,
+        block_info: ProposalBlockInfo,
+        txs: Vec<Transaction>,
+    ) -> BatcherResult<ProposalValidationResult> {
+        self.start_validation(proposal_id, deadline, round, block_info, txs)
+            .await?
+            .await
+            .map_err(|_| {
+                error!("The validation of proposal {} ended without a result.", proposal_id);
+                BatcherError::InternalError
+            })?
+            .map_err(to_batcher_error)
+    }
+
+    // Starts building a block out of the given transactions, returning the receiver of the result
+    // of the validation.
+    async fn start_validation(
+        &mut self,
+        proposal_id: ProposalId,
+        deadline: Deadline,
+        round: ProposalRound,
+        block_info: ProposalBlockInfo,
+        txs: Vec<Transaction>,
+    ) -> BatcherResult<ProposalValidationReceiver> {
+        let deadline = self.deadline_instant(deadline)?;
+        self.proposals_manager
+            .validate_block_proposal(
+                proposal_id,
+                deadline,
+                round,
+                block_info,
+                Box::pin(futures::stream::iter(txs)),
+            )
+            .await
+            .map_err(to_batcher_error)
+    }
+
+    // Converts the deadline of a request to the clock of the batcher.
+    fn deadline_instant(&self, deadline: Deadline) -> BatcherResult<tokio::time::Instant> {
+        let time_left = deadline.time_left_within(self.config.max_time_to_deadline)?;
+        Ok(self.clock.now() + time_left)
+    }
+
+    // The height the batcher works on: the started height, or the height following the last
+    // handled one, or the height of the storage if no height was handled yet.
+    fn active_height(&self) -> BatcherResult<BlockNumber> {
+        match self.active_height {
+            Some(active_height) => Ok(active_height),
+            None => self.storage_height(),
+        }
+    }
+
+    // The number of the next block to commit to the storage.
+    fn storage_height(&self) -> BatcherResult<BlockNumber> {
+        self.storage_reader.height().map_err(|err| {
+            error!("Failed to read the height from the storage: {}", err);
+            BatcherError::InternalError
+        })
+    }
+
+    // If no height was started, the first proposal starts the height of the storage.
+    fn verify_proposal_height(
+        &mut self,
+        proposal_id: ProposalId,
+        height: BlockNumber,
+    ) -> BatcherResult<()> {
+        let active_height = self.active_height()?;
+        self.active_height = Some(active_height);
+        if active_height != height {
+            return Err(BatcherError::ProposalHeightMismatch {
+                proposal_id,
+                active_height,
+                proposal_height: height,
+            });
+        }
+        Ok(())
+    }
+}
+
+// The mempool is notified of the committed transactions, and of the next nonce of every account
+// that committed transactions.
+fn commit_block_args(tx_hashes: &[TransactionHash], state_diff: &ThinStateDiff) -> CommitBlockArgs {
+    CommitBlockArgs {
+        committed_txs: tx_hashes.to_vec(),
+        nonces: state_diff.nonces.iter().map(|(&address, &nonce)| (address, nonce)).collect(),
+    }
+}
+
+fn to_batcher_error(err: ProposalsManagerError) -> BatcherError {
+    count_error(&err);
+    match err {
+        ProposalsManagerError::AlreadyGeneratingProposal {
+            current_generating_proposal_id,
+            new_proposal_id,
+        } => BatcherError::ServerBusy {
+            active_proposal_id: current_generating_proposal_id,
+            new_proposal_id,
+        },
+        ProposalsManagerError::AlreadyValidatingRound {
+            validating_proposal_id,
+            proposal_id,
+            ..
+        } => BatcherError::ServerBusy {
+            active_proposal_id: validating_proposal_id,
+            new_proposal_id: proposal_id,
+        },
+        ProposalsManagerError::TooManyConcurrentValidations {
+            oldest_validating_proposal_id,
+            proposal_id,
+            ..
+        } => BatcherError::ServerBusy {
+            active_proposal_id: oldest_validating_proposal_id,
+            new_proposal_id: proposal_id,
+        },
+        ProposalsManagerError::ProposalNotFound { proposal_id } => {
+            BatcherError::ProposalNotFound { proposal_id }
+        }
+        ProposalsManagerError::StaleProposal { proposal_id, round, latest_round } => {
+            BatcherError::StaleProposal { proposal_id, round, latest_round }
+        }
+        ProposalsManagerError::ProposalNotCached { proposal_id, evicted } => {
+            BatcherError::ProposalNotCached { proposal_id, evicted }
+        }
+        ProposalsManagerError::DuplicateProposalId { proposal_id } => {
+            BatcherError::DuplicateProposalId { proposal_id }
+        }
+        err => {
+            error!("Proposal handling failed: {}", err);
+            BatcherError::InternalError
+        }
+    }
+}
+
+// Reports a rejected proposal that was generated in a different environment as an environment
+// mismatch.
+fn proposal_status(
+    proposal_id: ProposalId,
+    validation_result: ProposalValidationResult,
+    proposer_fingerprint: Option<EnvironmentFingerprint>,
+    environment: &ExecutionEnvironment,
+) -> ProposalStatus {
+    // Set only if the proposal was generated in a different environment, along with the
+    // fingerprint of the local environment for the seed of the proposal.
+    let mismatch = proposer_fingerprint.and_then(|proposer_fingerprint| {
+        let validator_fingerprint = environment.fingerprint(proposer_fingerprint.seed);
+        (proposer_fingerprint != validator_fingerprint)
+            .then_some((proposer_fingerprint, validator_fingerprint))
+    });
+    match (validation_result, mismatch) {
+        (ProposalValidationResult::Valid { commitment }, None) => {
+            ProposalStatus::Valid { commitment }
+        }
+        (
+            ProposalValidationResult::Valid { commitment },
+            Some((proposer_fingerprint, validator_fingerprint)),
+        ) => {
+            warn!(
+                "Proposal {} is valid, but was generated in a different environment: {} (local: \
+                 {}).",
+                proposal_id, proposer_fingerprint, validator_fingerprint
+            );
+            ProposalStatus::Valid { commitment }
+        }
+        (
+            ProposalValidationResult::ContentMismatch { n_proposed_txs, n_executed_txs, error },
+            None,
+        ) => ProposalStatus::ContentMismatch { n_proposed_txs, n_executed_txs, error },
+        (
+            ProposalValidationResult::ContentMismatch { n_proposed_txs, n_executed_txs, error },
+            Some((proposer_fingerprint, validator_fingerprint)),
+        ) => ProposalStatus::EnvironmentMismatch {
+            proposer_fingerprint,
+            validator_fingerprint,
+            n_proposed_txs,
+            n_executed_txs,
+            error,
+        },
+    }
+}
+
+/// Opens the storage of the batcher. Its reader can be shared with the other components of the
+/// node.
+pub fn open_batcher_storage(config: &BatcherConfig) -> (StorageReader, StorageWriter) {
+    papyrus_storage::open_storage(config.storage.clone())
+        .expect("Failed to open the batcher's storage.")
+}
+
+/// Creates the batcher of the node over its opened storage. The limits of the blocks it builds are
+/// read from `block_builder_config`, so that they can be changed at runtime.
+#[allow(clippy::too_many_arguments)]
+pub fn create_batcher(
+    config: BatcherConfig,
+    (storage_reader, storage_writer): (StorageReader, StorageWriter),
+    mempool_client: SharedMempoolClient,
+    l1_provider_client: Option<SharedL1ProviderClient>,
+    l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient>,
+    class_manager_client: Option<SharedClassManagerClient>,
+    mempool_tx_stream: Option<Receiver<Transaction>>,
+    l2_gas_price: SharedL2GasPrice,
+    block_builder_config: SharedDynamicConfig<BlockBuilderConfig>,
+) -> Batcher {
+    let clock: SharedClock = Arc::new(TokioClock);
+    let pending_state = SharedPendingState::default();
+    let build_progress = SharedBuildProgress::default();
+    let mut state_reader_factory: Arc<dyn StateReaderFactory> = Arc::new(PapyrusReaderFactory {
+        storage_reader: storage_reader.clone(),
+        class_manager_client,
+    });
+    if config.state_cache.enabled {
+        state_reader_factory =
+            Arc::new(CachingStateReaderFactory::new(state_reader_factory, &config.state_cache));
+    }
+    let block_builder = BlockBuilder::new(
+        block_builder_config,
+        state_reader_factory,
+        config.chain_info.clone(),
+        l2_gas_price.clone(),
+        l1_gas_price_provider_client,
+        clock.clone(),
+    )
+    .with_fee_config(config.fee_config)
+    .with_pending_state(pending_state.clone())
+    .with_build_progress(build_progress.clone());
+    let block_builder = match config.strk_per_eth_rate {
+        Some(strk_per_eth_rate) => block_builder.with_strk_per_eth_rate(
+            NonZeroU128::new(strk_per_eth_rate.into())
+                .expect("The STRK/ETH rate should be validated to be positive."),
+        ),
+        None => block_builder,
+    };
+    let block_builder = match &config.block_replay {
+        Some(replay_config) => block_builder.with_replay_recorder(
+            BlockReplayRecorder::new(replay_config)
+                .expect("Failed to create the block replay directory."),
+        ),
+        None => block_builder,
+    };
+    let proposal_journal = config.proposal_journal.as_ref().map(|journal_config| {
+        ProposalJournal::open(&journal_config.path).expect("Failed to open the proposal journal.")
+    });
+    let batcher = Batcher::new(
+        config,
+        mempool_client,
+        l1_provider_client,
+        mempool_tx_stream,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        Arc::new(block_builder),
+        l2_gas_price,
+        clock,
+    )
+    .with_pending_state(pending_state)
+    .with_build_progress(build_progress);
+    match proposal_journal {
+        Some((journal, recovery)) => batcher.with_proposal_journal(journal, recovery),
+        None => batcher,
+    }
+}
+
+#[cfg_attr(test, automock)]
+pub trait BatcherStorageReaderTrait: Send + Sync {
+    /// The number of the next block to commit.
+    fn height(&self) -> papyrus_storage::StorageResult<BlockNumber>;
 }
 
-pub fn create_batcher(config: BatcherConfig, mempool_client: SharedMempoolClient) -> Batcher {
-    Batcher::new(config, mempool_client)
+impl BatcherStorageReaderTrait for papyrus_storage::StorageReader {
+    fn height(&self) -> papyrus_storage::StorageResult<BlockNumber> {
+        self.begin_ro_txn()?.get_state_marker()
+    }
+}
+
+#[cfg_attr(test, automock)]
+pub trait BatcherStorageWriterTrait: Send + Sync {
+    fn commit_proposal(
+        &mut self,
+        header: BlockHeader,
+        state_diff: ThinStateDiff,
+    ) -> papyrus_storage::StorageResult<()>;
+
+    /// Records a height consensus decided on no block for, moving the storage past it without a
+    /// block.
+    fn commit_skipped_height(&mut self, height: BlockNumber) -> papyrus_storage::StorageResult<()>;
+}
+
+impl BatcherStorageWriterTrait for papyrus_storage::StorageWriter {
+    fn commit_proposal(
+        &mut self,
+        header: BlockHeader,
+        state_diff: ThinStateDiff,
+    ) -> papyrus_storage::StorageResult<()> {
+        let height = header.block_number;
+        // TODO: Store the block body once the block builder outputs the execution results.
+        self.begin_rw_txn()?
+            .append_header(height, &header)?
+            .append_state_diff(height, state_diff)?
+            .commit()
+    }
+
+    fn commit_skipped_height(&mut self, height: BlockNumber) -> papyrus_storage::StorageResult<()> {
+        self.begin_rw_txn()?.append_skipped_height(height)?.commit()
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for Batcher {
+    async fn start(&mut self) -> Result<(), ComponentStartError> {
+        if let Some(recovery) = self.journal_recovery.take() {
+            let height =
+                self.storage_height().map_err(|_| ComponentStartError::InternalComponentError)?;
+            self.proposals_manager.recover(recovery, height).await;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
-impl ComponentStarter for Batcher {}
+impl ComponentHealth for Batcher {}
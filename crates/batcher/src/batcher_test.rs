@@ -0,0 +1,1008 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use blockifier::context::ChainInfo;
+use mockall::predicate::eq;
+use mockall::Sequence;
+use rstest::rstest;
+use starknet_api::block::{BlockHeader, BlockNumber, BlockTimestamp};
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::core::{ContractAddress, Nonce, StateDiffCommitment};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::felt;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::{
+    AddSyncedBlockInput,
+    BatcherResult,
+    BlockCommitments,
+    BlockInfoValidationError,
+    BuildProposalInput,
+    BuildProposalReturnValue,
+    DecisionReachedInput,
+    EnvironmentFingerprint,
+    ForceAbortAndResyncInput,
+    ForceAbortAndResyncReturnValue,
+    GetCommittedBlockInput,
+    GetProposalContentInput,
+    GetProposalContentReturnValue,
+    GetProposalMetadataInput,
+    GetValidationStatusInput,
+    ImportBlockInput,
+    ImportBlockStatus,
+    PendingState,
+    ProposalBlockInfo,
+    ProposalContent,
+    ProposalId,
+    ProposalState,
+    ProposalStatus,
+    ProposalValidationError,
+    ReproposeProposalInput,
+    SkipHeightInput,
+    StartHeightInput,
+    ValidateProposalInput,
+    ValidationStatus,
+};
+use starknet_batcher_types::deadline::{Deadline, DeadlineTooFarError};
+use starknet_batcher_types::errors::BatcherError;
+use starknet_mempool_types::communication::{
+    MempoolClientError,
+    MempoolClientResult,
+    MockMempoolClient,
+};
+use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+
+use crate::batcher::{Batcher, MockBatcherStorageReaderTrait, MockBatcherStorageWriterTrait};
+use crate::block_builder::{
+    BlockBuilderConfig,
+    BlockBuilderError,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    MockBlockBuilderTrait,
+    SharedPendingState,
+};
+use crate::clock::TokioClock;
+use crate::config::BatcherConfig;
+use crate::environment_fingerprint::ExecutionEnvironment;
+use crate::test_utils::{passthrough_proposal_commitment, PassthroughBlockBuilder};
+
+const STORAGE_HEIGHT: BlockNumber = BlockNumber(5);
+
+fn batcher(enable_admin_actions: bool) -> Batcher {
+    create_test_batcher(
+        enable_admin_actions,
+        MockMempoolClient::new(),
+        MockBatcherStorageWriterTrait::new(),
+        Arc::new(PassthroughBlockBuilder),
+    )
+}
+
+fn batcher_with_mempool_client(mempool_client: MockMempoolClient) -> Batcher {
+    create_test_batcher(
+        false,
+        mempool_client,
+        MockBatcherStorageWriterTrait::new(),
+        Arc::new(PassthroughBlockBuilder),
+    )
+}
+
+fn batcher_with_block_builder(block_builder: Arc<dyn BlockBuilderTrait>) -> Batcher {
+    create_test_batcher(
+        false,
+        MockMempoolClient::new(),
+        MockBatcherStorageWriterTrait::new(),
+        block_builder,
+    )
+}
+
+fn create_test_batcher(
+    enable_admin_actions: bool,
+    mempool_client: MockMempoolClient,
+    storage_writer: MockBatcherStorageWriterTrait,
+    block_builder: Arc<dyn BlockBuilderTrait>,
+) -> Batcher {
+    let config = BatcherConfig { enable_admin_actions, ..Default::default() };
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    storage_reader.expect_height().returning(|| Ok(STORAGE_HEIGHT));
+    Batcher::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        None,
+        Arc::new(storage_reader),
+        Box::new(storage_writer),
+        block_builder,
+        SharedL2GasPrice::default(),
+        Arc::new(TokioClock),
+    )
+}
+
+// The environment of the test batcher.
+fn local_environment() -> ExecutionEnvironment {
+    ExecutionEnvironment::local(&ChainInfo::default(), &BlockBuilderConfig::default())
+}
+
+fn local_environment_fingerprint() -> EnvironmentFingerprint {
+    local_environment().fingerprint(0)
+}
+
+// A fingerprint of the same seed as the local one, of another environment.
+fn other_environment_fingerprint() -> EnvironmentFingerprint {
+    EnvironmentFingerprint { seed: 0, digest: [1; 32] }
+}
+
+// A block builder that executes none of the proposed transactions.
+fn rejecting_block_builder() -> Arc<dyn BlockBuilderTrait> {
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder
+        .expect_build_block()
+        .returning(|_, _, _, _| Ok(BlockExecutionArtifacts::default()));
+    Arc::new(block_builder)
+}
+
+fn proposal_deadline() -> Deadline {
+    Deadline::after(Duration::from_millis(100))
+}
+
+fn proposed_txs(n_txs: u64) -> Vec<Transaction> {
+    (0..n_txs)
+        .map(|i| {
+            Transaction::Invoke(InvokeTransaction {
+                tx: invoke_tx(InvokeTxArgs::default()),
+                tx_hash: TransactionHash(felt!(i)),
+            })
+        })
+        .collect()
+}
+
+async fn validate_proposal(batcher: &mut Batcher, proposal_id: u64, n_txs: u64) {
+    let input = ValidateProposalInput {
+        proposal_id,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        txs: proposed_txs(n_txs),
+        environment_fingerprint: Some(local_environment_fingerprint()),
+        block_info: None,
+    };
+    let commitment = passthrough_proposal_commitment(&proposed_txs(n_txs));
+    batcher.validate_proposal(input).await.unwrap();
+    assert_eq!(
+        await_validation(batcher, proposal_id).await,
+        Ok(ProposalStatus::Valid { commitment })
+    );
+}
+
+// Polls the status of a started validation until it is done.
+async fn await_validation(
+    batcher: &mut Batcher,
+    proposal_id: ProposalId,
+) -> BatcherResult<ProposalStatus> {
+    loop {
+        match batcher.get_validation_status(GetValidationStatusInput { proposal_id }).await? {
+            ValidationStatus::InProgress => tokio::time::sleep(Duration::from_millis(1)).await,
+            ValidationStatus::Done(status) => return Ok(status),
+        }
+    }
+}
+
+async fn get_proposal_content_page(
+    batcher: &mut Batcher,
+    proposal_id: u64,
+    page: usize,
+) -> BatcherResult<ProposalContent> {
+    batcher
+        .get_proposal_content(GetProposalContentInput { proposal_id, page })
+        .await
+        .map(|GetProposalContentReturnValue { content }| content)
+}
+
+// Collects the pages of the given proposal until its generation ends.
+async fn get_all_proposal_content(batcher: &mut Batcher, proposal_id: u64) -> Vec<Transaction> {
+    let mut txs = vec![];
+    for page in 0.. {
+        match get_proposal_content_page(batcher, proposal_id, page).await.unwrap() {
+            ProposalContent::Txs(page_txs) => txs.extend(page_txs),
+            ProposalContent::Finished { .. } => break,
+        }
+    }
+    txs
+}
+
+#[tokio::test]
+async fn force_abort_and_resync_is_rejected_when_disabled() {
+    let mut batcher = batcher(false);
+    assert_eq!(
+        batcher.force_abort_and_resync(ForceAbortAndResyncInput).await,
+        Err(BatcherError::AdminActionsDisabled)
+    );
+}
+
+#[tokio::test]
+async fn force_abort_and_resync_discards_the_proposals_of_the_height() {
+    let mut batcher = batcher(true);
+    validate_proposal(&mut batcher, 0, 2).await;
+
+    // No proposal was in progress.
+    assert_eq!(
+        batcher.force_abort_and_resync(ForceAbortAndResyncInput).await,
+        Ok(ForceAbortAndResyncReturnValue { aborted_proposal_id: None })
+    );
+
+    assert_eq!(
+        batcher.get_validation_status(GetValidationStatusInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+    // A proposal built before the abort is not committed.
+    assert_eq!(
+        batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn get_proposal_state_lists_the_proposals_of_the_height() {
+    let mut batcher = batcher(false);
+    assert_eq!(batcher.get_proposal_state().await, Ok(ProposalState::default()));
+
+    validate_proposal(&mut batcher, 1, 3).await;
+    validate_proposal(&mut batcher, 0, 2).await;
+
+    assert_eq!(
+        batcher.get_proposal_state().await,
+        Ok(ProposalState {
+            active_height: Some(STORAGE_HEIGHT),
+            active_proposal_id: None,
+            completed_proposal_ids: vec![0, 1],
+        })
+    );
+}
+
+#[tokio::test]
+async fn get_proposal_metadata_reports_the_built_block() {
+    let mut batcher = batcher(false);
+    validate_proposal(&mut batcher, 0, 3).await;
+
+    let metadata =
+        batcher.get_proposal_metadata(GetProposalMetadataInput { proposal_id: 0 }).await.unwrap();
+    assert_eq!(metadata.n_txs, 3);
+    assert_eq!(metadata.n_reverted_txs, 0);
+    assert_eq!(
+        batcher.get_proposal_metadata(GetProposalMetadataInput { proposal_id: 1 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 1 })
+    );
+}
+
+#[tokio::test]
+async fn get_pending_state_returns_the_block_of_the_active_height() {
+    let pending_state = SharedPendingState::default();
+    let mut batcher = batcher(false).with_pending_state(pending_state.clone());
+    let state_diff = ThinStateDiff {
+        nonces: [(ContractAddress::from(1_u8), Nonce(felt!(1_u8)))].into(),
+        ..Default::default()
+    };
+    let block = PendingState { block_number: STORAGE_HEIGHT, state_diff };
+    pending_state.set(Some(block.clone()));
+    assert_eq!(batcher.get_pending_state().await, Ok(None));
+
+    batcher.start_height(StartHeightInput { height: STORAGE_HEIGHT }).await.unwrap();
+    assert_eq!(batcher.get_pending_state().await, Ok(Some(block.clone())));
+
+    pending_state.set(Some(PendingState { block_number: BlockNumber(0), ..block }));
+    assert_eq!(batcher.get_pending_state().await, Ok(None));
+}
+
+// A batcher whose storage expects the given heights to be skipped, in order.
+fn batcher_skipping_heights(heights: &[BlockNumber]) -> Batcher {
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    let mut sequence = Sequence::new();
+    for &height in heights {
+        storage_writer
+            .expect_commit_skipped_height()
+            .with(eq(height))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(()));
+    }
+    create_test_batcher(
+        false,
+        MockMempoolClient::new(),
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    )
+}
+
+#[tokio::test]
+async fn skip_height_advances_to_next_height() {
+    let next_height = STORAGE_HEIGHT.unchecked_next();
+    let mut batcher = batcher_skipping_heights(&[STORAGE_HEIGHT, next_height]);
+
+    assert_eq!(batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await, Ok(()));
+    assert_eq!(batcher.skip_height(SkipHeightInput { height: next_height }).await, Ok(()));
+    assert_eq!(
+        batcher.skip_height(SkipHeightInput { height: next_height }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: next_height.unchecked_next(),
+            requested_height: next_height
+        })
+    );
+}
+
+#[tokio::test]
+async fn skip_height_rejects_heights_other_than_storage_height() {
+    let mut batcher = batcher(false);
+
+    assert_eq!(
+        batcher.skip_height(SkipHeightInput { height: BlockNumber(3) }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: STORAGE_HEIGHT,
+            requested_height: BlockNumber(3)
+        })
+    );
+}
+
+#[tokio::test]
+async fn skip_height_fails_if_storage_fails() {
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    storage_writer.expect_commit_skipped_height().returning(|_| {
+        Err(papyrus_storage::StorageError::DBInconsistency { msg: "test".to_owned() })
+    });
+    let mut batcher = create_test_batcher(
+        false,
+        MockMempoolClient::new(),
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    );
+
+    assert_eq!(
+        batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await,
+        Err(BatcherError::InternalError)
+    );
+    // The height is not skipped.
+    assert_eq!(batcher.start_height(StartHeightInput { height: STORAGE_HEIGHT }).await, Ok(()));
+}
+
+// The block decided on after a skipped height is committed at the height following it.
+#[tokio::test]
+async fn decision_reached_after_skipped_height_commits_next_height() {
+    let next_height = STORAGE_HEIGHT.unchecked_next();
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    let mut sequence = Sequence::new();
+    storage_writer
+        .expect_commit_skipped_height()
+        .with(eq(STORAGE_HEIGHT))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_| Ok(()));
+    storage_writer
+        .expect_commit_proposal()
+        .withf(move |header, _| header.block_number == next_height)
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_, _| Ok(()));
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_commit_block().times(1).returning(|_| Ok(()));
+    let mut batcher = create_test_batcher(
+        false,
+        mempool_client,
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    );
+
+    batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await.unwrap();
+    let input = ValidateProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: next_height,
+        round: 0,
+        txs: proposed_txs(1),
+        environment_fingerprint: Some(local_environment_fingerprint()),
+        block_info: None,
+    };
+    batcher.validate_proposal(input).await.unwrap();
+    assert_matches!(await_validation(&mut batcher, 0).await, Ok(ProposalStatus::Valid { .. }));
+
+    assert_eq!(batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await, Ok(()));
+}
+
+// A failure to notify the mempool doesn't fail the decision, as the block is already committed.
+#[rstest]
+#[case::mempool_notified(Ok(()))]
+#[case::mempool_failure(Err(MempoolClientError::MempoolError(MempoolError::FeltOutOfRange)))]
+#[tokio::test]
+async fn decision_reached_commits_proposal_and_advances_height(
+    #[case] mempool_result: MempoolClientResult<()>,
+) {
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    storage_writer
+        .expect_commit_proposal()
+        .withf(|header, _| header.block_number == STORAGE_HEIGHT && header.n_transactions == 3)
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client
+        .expect_commit_block()
+        .with(eq(CommitBlockArgs {
+            committed_txs: proposed_txs(3).iter().map(Transaction::tx_hash).collect(),
+            nonces: HashMap::new(),
+        }))
+        .times(1)
+        .return_once(|_| mempool_result);
+    let mut batcher = create_test_batcher(
+        false,
+        mempool_client,
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    );
+    validate_proposal(&mut batcher, 0, 3).await;
+
+    assert_eq!(batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await, Ok(()));
+    assert_eq!(
+        batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: STORAGE_HEIGHT.unchecked_next(),
+            requested_height: STORAGE_HEIGHT
+        })
+    );
+}
+
+#[tokio::test]
+async fn decided_block_is_served_as_committed_block() {
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    storage_writer.expect_commit_proposal().returning(|_, _| Ok(()));
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_commit_block().returning(|_| Ok(()));
+    let mut batcher = create_test_batcher(
+        false,
+        mempool_client,
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    );
+    validate_proposal(&mut batcher, 0, 2).await;
+    batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await.unwrap();
+
+    let committed_block = batcher
+        .get_committed_block(GetCommittedBlockInput { block_number: STORAGE_HEIGHT })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(committed_block.header.block_number, STORAGE_HEIGHT);
+    assert_eq!(committed_block.txs, proposed_txs(2));
+    assert_eq!(
+        committed_block.receipts.iter().map(|receipt| receipt.tx_hash).collect::<Vec<_>>(),
+        proposed_txs(2).iter().map(Transaction::tx_hash).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        batcher
+            .get_committed_block(GetCommittedBlockInput {
+                block_number: STORAGE_HEIGHT.unchecked_next()
+            })
+            .await,
+        Ok(None)
+    );
+}
+
+#[tokio::test]
+async fn decision_reached_on_unknown_proposal_fails() {
+    let mut batcher = batcher(false);
+    validate_proposal(&mut batcher, 0, 1).await;
+
+    assert_eq!(
+        batcher.decision_reached(DecisionReachedInput { proposal_id: 1 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 1 })
+    );
+}
+
+#[tokio::test]
+async fn build_proposal_streams_mempool_txs() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(3)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut batcher = batcher_with_mempool_client(mempool_client);
+
+    let input = BuildProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+    assert_matches!(
+        batcher.build_proposal(input).await,
+        Ok(BuildProposalReturnValue { environment_fingerprint, .. })
+            if environment_fingerprint
+                == local_environment().fingerprint(environment_fingerprint.seed)
+    );
+
+    let txs = get_all_proposal_content(&mut batcher, 0).await;
+    assert_eq!(txs, proposed_txs(3));
+    // The content of a finished proposal remains available, e.g., for retransmission.
+    assert_matches!(
+        get_proposal_content_page(&mut batcher, 0, 0).await,
+        Ok(ProposalContent::Txs(page_txs)) if txs.starts_with(&page_txs)
+    );
+}
+
+#[tokio::test]
+async fn repropose_proposal_streams_cached_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(3)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut batcher = batcher_with_mempool_client(mempool_client);
+    let input = BuildProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+    batcher.build_proposal(input.clone()).await.unwrap();
+    assert_eq!(get_all_proposal_content(&mut batcher, 0).await, proposed_txs(3));
+
+    // A proposal id is built once; a later round re-proposes the block explicitly.
+    assert_eq!(
+        batcher.build_proposal(input).await,
+        Err(BatcherError::DuplicateProposalId { proposal_id: 0 })
+    );
+    let reproposal = batcher
+        .repropose_proposal(ReproposeProposalInput {
+            proposal_id: 0,
+            height: STORAGE_HEIGHT,
+            round: 1,
+        })
+        .await
+        .unwrap();
+    assert_eq!(reproposal.commitment, passthrough_proposal_commitment(&proposed_txs(3)));
+    assert_eq!(get_all_proposal_content(&mut batcher, 0).await, proposed_txs(3));
+}
+
+#[tokio::test]
+async fn proposal_content_is_paged() {
+    let mut batcher = batcher(false);
+    batcher.config.proposal_content_page_size = 2;
+    batcher.import_block(import_block_input(passthrough_block_commitments())).await.unwrap();
+    let finished =
+        ProposalContent::Finished { commitment: passthrough_proposal_commitment(&proposed_txs(3)) };
+
+    // Pages are requested out of order, and again.
+    assert_eq!(
+        get_proposal_content_page(&mut batcher, 0, 1).await,
+        Ok(ProposalContent::Txs(proposed_txs(3)[2..].to_vec()))
+    );
+    assert_eq!(get_proposal_content_page(&mut batcher, 0, 3).await, Ok(finished.clone()));
+    assert_eq!(
+        get_proposal_content_page(&mut batcher, 0, 0).await,
+        Ok(ProposalContent::Txs(proposed_txs(3)[..2].to_vec()))
+    );
+    assert_eq!(get_proposal_content_page(&mut batcher, 0, 2).await, Ok(finished));
+}
+
+#[tokio::test]
+async fn failed_proposal_content_has_no_commitment() {
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder.expect_build_block().returning(|_, _, _, _| {
+        Err(BlockBuilderError::InternalError("Execution failed.".to_string()))
+    });
+    block_builder.expect_build_block_sequentially().returning(|_, _, _, _| {
+        Err(BlockBuilderError::InternalError("Execution failed.".to_string()))
+    });
+    let mut batcher = batcher_with_block_builder(Arc::new(block_builder));
+    let input = BuildProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+    batcher.build_proposal(input).await.unwrap();
+
+    assert_eq!(
+        get_proposal_content_page(&mut batcher, 0, 0).await,
+        Err(BatcherError::ProposalFailed { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn build_proposal_while_generating_fails() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut batcher = batcher_with_mempool_client(mempool_client);
+    let build_proposal_input = |proposal_id| BuildProposalInput {
+        proposal_id,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+
+    assert_matches!(batcher.build_proposal(build_proposal_input(0)).await, Ok(_));
+    assert_eq!(
+        batcher.build_proposal(build_proposal_input(1)).await,
+        Err(BatcherError::ServerBusy { active_proposal_id: 0, new_proposal_id: 1 })
+    );
+}
+
+#[rstest]
+#[case::build(true)]
+#[case::validate(false)]
+#[tokio::test]
+async fn proposal_for_wrong_height_fails(#[case] build: bool) {
+    let mut batcher = batcher(false);
+    let height = STORAGE_HEIGHT.unchecked_next();
+    let deadline = proposal_deadline();
+
+    let result = if build {
+        batcher
+            .build_proposal(BuildProposalInput {
+                proposal_id: 0,
+                deadline,
+                height,
+                round: 0,
+                timestamp: None,
+            })
+            .await
+            .map(|_| ())
+    } else {
+        batcher
+            .validate_proposal(ValidateProposalInput {
+                proposal_id: 0,
+                deadline,
+                height,
+                round: 0,
+                txs: vec![],
+                environment_fingerprint: None,
+                block_info: None,
+            })
+            .await
+            .map(|_| ())
+    };
+    assert_eq!(
+        result,
+        Err(BatcherError::ProposalHeightMismatch {
+            proposal_id: 0,
+            active_height: STORAGE_HEIGHT,
+            proposal_height: height
+        })
+    );
+}
+
+#[rstest]
+#[case::valid_in_other_environment(
+    Arc::new(PassthroughBlockBuilder),
+    Some(other_environment_fingerprint()),
+    ProposalStatus::Valid { commitment: passthrough_proposal_commitment(&proposed_txs(2)) }
+)]
+#[case::rejected_in_same_environment(
+    rejecting_block_builder(),
+    Some(local_environment_fingerprint()),
+    ProposalStatus::ContentMismatch {
+        n_proposed_txs: 0,
+        n_executed_txs: 0,
+        error: ProposalValidationError::Incomplete { n_received_txs: 0 },
+    }
+)]
+#[case::rejected_in_same_environment_with_other_seed(
+    rejecting_block_builder(),
+    Some(local_environment().fingerprint(7)),
+    ProposalStatus::ContentMismatch {
+        n_proposed_txs: 0,
+        n_executed_txs: 0,
+        error: ProposalValidationError::Incomplete { n_received_txs: 0 },
+    }
+)]
+#[case::rejected_in_unknown_environment(
+    rejecting_block_builder(),
+    None,
+    ProposalStatus::ContentMismatch {
+        n_proposed_txs: 0,
+        n_executed_txs: 0,
+        error: ProposalValidationError::Incomplete { n_received_txs: 0 },
+    }
+)]
+#[case::rejected_in_other_environment(
+    rejecting_block_builder(),
+    Some(other_environment_fingerprint()),
+    ProposalStatus::EnvironmentMismatch {
+        proposer_fingerprint: other_environment_fingerprint(),
+        validator_fingerprint: local_environment_fingerprint(),
+        n_proposed_txs: 0,
+        n_executed_txs: 0,
+        error: ProposalValidationError::Incomplete { n_received_txs: 0 },
+    }
+)]
+#[tokio::test]
+async fn validate_proposal_reports_environment_mismatch(
+    #[case] block_builder: Arc<dyn BlockBuilderTrait>,
+    #[case] environment_fingerprint: Option<EnvironmentFingerprint>,
+    #[case] expected_status: ProposalStatus,
+) {
+    let mut batcher = batcher_with_block_builder(block_builder);
+    let input = ValidateProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        txs: proposed_txs(2),
+        environment_fingerprint,
+        block_info: None,
+    };
+    batcher.validate_proposal(input).await.unwrap();
+    assert_eq!(await_validation(&mut batcher, 0).await, Ok(expected_status));
+}
+
+#[tokio::test]
+async fn validation_status_is_kept_once_done() {
+    let mut batcher = batcher(false);
+    validate_proposal(&mut batcher, 0, 2).await;
+
+    let commitment = passthrough_proposal_commitment(&proposed_txs(2));
+    assert_eq!(
+        batcher.get_validation_status(GetValidationStatusInput { proposal_id: 0 }).await,
+        Ok(ValidationStatus::Done(ProposalStatus::Valid { commitment }))
+    );
+}
+
+#[tokio::test]
+async fn validation_status_of_unknown_proposal_is_not_found() {
+    let mut batcher = batcher(false);
+
+    assert_eq!(
+        batcher.get_validation_status(GetValidationStatusInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn validate_proposal_with_invalid_block_info_fails() {
+    let mut batcher = batcher(false);
+    let input = ValidateProposalInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        txs: proposed_txs(2),
+        environment_fingerprint: Some(local_environment_fingerprint()),
+        block_info: Some(ProposalBlockInfo {
+            timestamp: BlockTimestamp(u64::MAX),
+            sequencer_address: ContractAddress::from(1_u8),
+        }),
+    };
+
+    assert_matches!(
+        batcher.validate_proposal(input).await,
+        Err(BatcherError::InvalidBlockInfo {
+            proposal_id: 0,
+            error: BlockInfoValidationError::TimestampTooFarInFuture { .. },
+        })
+    );
+}
+
+// The commitments of the blocks built by the passthrough block builder.
+fn passthrough_block_commitments() -> BlockCommitments {
+    BlockCommitments { state_diff_commitment: calculate_state_diff_hash(&ThinStateDiff::default()) }
+}
+
+fn import_block_input(expected_commitments: BlockCommitments) -> ImportBlockInput {
+    ImportBlockInput {
+        proposal_id: 0,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        txs: proposed_txs(3),
+        expected_commitments,
+    }
+}
+
+#[tokio::test]
+async fn imported_block_is_served_as_local_proposal() {
+    let mut batcher = batcher(false);
+
+    assert_matches!(
+        batcher.import_block(import_block_input(passthrough_block_commitments())).await,
+        Ok(ImportBlockStatus::Imported { environment_fingerprint })
+            if environment_fingerprint
+                == local_environment().fingerprint(environment_fingerprint.seed)
+    );
+    assert_eq!(get_all_proposal_content(&mut batcher, 0).await, proposed_txs(3));
+}
+
+#[tokio::test]
+async fn imported_block_with_mismatching_commitments_is_discarded() {
+    let mut batcher = batcher(false);
+    let expected_commitments =
+        BlockCommitments { state_diff_commitment: StateDiffCommitment::default() };
+
+    assert_eq!(
+        batcher.import_block(import_block_input(expected_commitments.clone())).await,
+        Ok(ImportBlockStatus::CommitmentMismatch {
+            expected: expected_commitments,
+            actual: passthrough_block_commitments(),
+        })
+    );
+    assert_eq!(
+        get_proposal_content_page(&mut batcher, 0, 0).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+    assert_eq!(
+        batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn imported_block_with_mismatching_content_is_rejected() {
+    let mut batcher = batcher_with_block_builder(rejecting_block_builder());
+
+    assert_eq!(
+        batcher.import_block(import_block_input(passthrough_block_commitments())).await,
+        Ok(ImportBlockStatus::ContentMismatch { n_proposed_txs: 0, n_executed_txs: 0 })
+    );
+}
+
+#[tokio::test]
+async fn proposal_with_too_far_deadline_fails() {
+    let mut batcher = batcher(false);
+    let max_time_to_deadline = BatcherConfig::default().max_time_to_deadline;
+    let input = BuildProposalInput {
+        proposal_id: 0,
+        deadline: Deadline::after(max_time_to_deadline * 2),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+
+    assert_matches!(
+        batcher.build_proposal(input).await,
+        Err(BatcherError::DeadlineTooFar(DeadlineTooFarError { max_time_left, .. }))
+            if max_time_left == max_time_to_deadline
+    );
+}
+
+#[tokio::test]
+async fn start_height_rejects_unsynced_and_stale_heights() {
+    let mut batcher = batcher_skipping_heights(&[STORAGE_HEIGHT]);
+    let next_height = STORAGE_HEIGHT.unchecked_next();
+
+    // The storage has not reached the next height.
+    assert_eq!(
+        batcher.start_height(StartHeightInput { height: next_height }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: STORAGE_HEIGHT,
+            requested_height: next_height
+        })
+    );
+    assert_eq!(batcher.start_height(StartHeightInput { height: STORAGE_HEIGHT }).await, Ok(()));
+
+    batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await.unwrap();
+    assert_eq!(
+        batcher.start_height(StartHeightInput { height: STORAGE_HEIGHT }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: next_height,
+            requested_height: STORAGE_HEIGHT
+        })
+    );
+}
+
+#[tokio::test]
+async fn start_height_moves_to_synced_storage_height() {
+    let storage_height = Arc::new(AtomicU64::new(STORAGE_HEIGHT.0));
+    let mut storage_reader = MockBatcherStorageReaderTrait::new();
+    let reported_height = storage_height.clone();
+    storage_reader
+        .expect_height()
+        .returning(move || Ok(BlockNumber(reported_height.load(Ordering::SeqCst))));
+    let mut batcher = Batcher::new(
+        BatcherConfig::default(),
+        Arc::new(MockMempoolClient::new()),
+        None,
+        None,
+        Arc::new(storage_reader),
+        Box::new(MockBatcherStorageWriterTrait::new()),
+        Arc::new(PassthroughBlockBuilder),
+        SharedL2GasPrice::default(),
+        Arc::new(TokioClock),
+    );
+    validate_proposal(&mut batcher, 0, 3).await;
+
+    // The node synced two blocks from its peers.
+    let synced_height = BlockNumber(STORAGE_HEIGHT.0 + 2);
+    storage_height.store(synced_height.0, Ordering::SeqCst);
+    assert_eq!(batcher.start_height(StartHeightInput { height: synced_height }).await, Ok(()));
+
+    // The proposals of the previous height are discarded.
+    assert_eq!(
+        batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+    let input = BuildProposalInput {
+        proposal_id: 1,
+        deadline: proposal_deadline(),
+        height: STORAGE_HEIGHT,
+        round: 0,
+        timestamp: None,
+    };
+    assert_eq!(
+        batcher.build_proposal(input).await,
+        Err(BatcherError::ProposalHeightMismatch {
+            proposal_id: 1,
+            active_height: synced_height,
+            proposal_height: STORAGE_HEIGHT
+        })
+    );
+}
+
+fn synced_block(height: BlockNumber) -> AddSyncedBlockInput {
+    AddSyncedBlockInput {
+        header: BlockHeader { block_number: height, n_transactions: 2, ..Default::default() },
+        state_diff: ThinStateDiff {
+            nonces: [(ContractAddress::from(1_u8), Nonce(felt!(3_u8)))].into(),
+            ..Default::default()
+        },
+        tx_hashes: vec![TransactionHash(felt!(1_u8)), TransactionHash(felt!(2_u8))],
+    }
+}
+
+#[tokio::test]
+async fn add_synced_block_commits_block_and_advances_height() {
+    let mut storage_writer = MockBatcherStorageWriterTrait::new();
+    storage_writer
+        .expect_commit_proposal()
+        .withf(|header, state_diff| {
+            header.block_number == STORAGE_HEIGHT && state_diff.nonces.len() == 1
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client
+        .expect_commit_block()
+        .with(eq(CommitBlockArgs {
+            committed_txs: vec![TransactionHash(felt!(1_u8)), TransactionHash(felt!(2_u8))],
+            nonces: [(ContractAddress::from(1_u8), Nonce(felt!(3_u8)))].into(),
+        }))
+        .times(1)
+        .returning(|_| Ok(()));
+    let mut batcher = create_test_batcher(
+        false,
+        mempool_client,
+        storage_writer,
+        Arc::new(PassthroughBlockBuilder),
+    );
+    validate_proposal(&mut batcher, 0, 3).await;
+
+    assert_eq!(batcher.add_synced_block(synced_block(STORAGE_HEIGHT)).await, Ok(()));
+
+    // The proposals of the synced height are discarded.
+    assert_eq!(
+        batcher.decision_reached(DecisionReachedInput { proposal_id: 0 }).await,
+        Err(BatcherError::ProposalNotFound { proposal_id: 0 })
+    );
+    assert_eq!(
+        batcher.skip_height(SkipHeightInput { height: STORAGE_HEIGHT }).await,
+        Err(BatcherError::HeightMismatch {
+            active_height: STORAGE_HEIGHT.unchecked_next(),
+            requested_height: STORAGE_HEIGHT
+        })
+    );
+}
+
+#[rstest]
+#[case::already_stored(BlockNumber(STORAGE_HEIGHT.0 - 1))]
+#[case::not_following_storage(STORAGE_HEIGHT.unchecked_next())]
+#[tokio::test]
+async fn add_synced_block_rejects_blocks_not_following_storage(#[case] height: BlockNumber) {
+    let mut batcher = batcher(false);
+
+    assert_eq!(
+        batcher.add_synced_block(synced_block(height)).await,
+        Err(BatcherError::SyncedBlockHeightMismatch {
+            storage_height: STORAGE_HEIGHT,
+            block_height: height
+        })
+    );
+}
@@ -0,0 +1,1176 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroU128;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blockifier::blockifier::block::GasPrices;
+use blockifier::blockifier::config::{ConcurrencyConfig, TransactionExecutorConfig};
+use blockifier::blockifier::transaction_executor::{TransactionExecutor, TransactionExecutorError};
+use blockifier::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount};
+use blockifier::context::{BlockContext, BlockContextBuilder, ChainInfo};
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::cached_state::{CachedState, CommitmentStateDiff};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use blockifier::transaction::errors::{
+    TransactionExecutionError,
+    TransactionFeeError,
+    TransactionPreValidationError,
+};
+use blockifier::transaction::objects::GasVector;
+use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
+use futures::FutureExt;
+#[cfg(test)]
+use mockall::automock;
+use papyrus_config::converters::deserialize_milliseconds_to_duration;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_dynamic_param,
+    ser_param,
+    SerializeConfig,
+};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{
+    BlockNumber,
+    BlockTimestamp,
+    GasPrice,
+    GasPricePerToken,
+    StarknetVersion,
+};
+use starknet_api::core::{
+    ClassHash,
+    CompiledClassHash,
+    ContractAddress,
+    Nonce,
+    SequencerContractAddress,
+};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::state::{StateDiff, StorageKey, ThinStateDiff};
+use starknet_api::transaction::{Fee, TransactionHash, TransactionSignature};
+use starknet_batcher_types::batcher_types::{
+    PendingState,
+    ProposalBlockInfo,
+    ProposalMetadata,
+    TransactionReceipt,
+    TxExclusionReason,
+};
+use starknet_l1_gas_price_types::communication::SharedL1GasPriceProviderClient;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceInfo;
+#[cfg(feature = "allocation_counting")]
+use starknet_mempool_infra::allocation_counter::AllocationScope;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_types::mempool_types::{RejectedTransaction, RejectionReason};
+use starknet_mempool_types::resource_bounds::{
+    validate_l1_gas_bounds,
+    validate_l2_gas_price,
+    FeeConfig,
+    SharedL2GasPrice,
+    MIN_GAS_PRICE,
+};
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, info, warn};
+use validator::Validate;
+
+use crate::block_replay::{BlockReplayRecord, BlockReplayRecorder};
+use crate::clock::{timeout_at, SharedClock};
+use crate::protocol_version::{
+    default_protocol_versions,
+    deserialize_protocol_versions,
+    protocol_version_at,
+    serialize_protocol_versions,
+    versioned_constants_of,
+    ProtocolVersions,
+};
+
+#[cfg(test)]
+#[path = "block_builder_test.rs"]
+mod block_builder_test;
+
+/// The stream of transactions the block builder consumes. Boxed so that adapters (e.g.,
+/// deduplication, throttling, recording) can be composed on top of any source.
+pub type InputTxStream = Pin<Box<dyn Stream<Item = Transaction> + Send>>;
+/// The stream of transactions that were added to the block, in execution order.
+pub type OutputTxStream = Pin<Box<dyn Stream<Item = Transaction> + Send>>;
+
+/// The number of allocations made while executing a transaction that was added to a block.
+/// Transactions executed concurrently aren't recorded, as their allocations can't be told apart.
+#[cfg(feature = "allocation_counting")]
+pub const TX_ALLOCATIONS: &str = "block_builder_tx_allocations";
+
+#[derive(Debug, Error)]
+pub enum BlockBuilderError {
+    #[error("Internal block builder error: {0}")]
+    InternalError(String),
+    #[error("The output transaction stream was closed by the receiver.")]
+    OutputStreamClosed,
+    #[error(
+        "The state is at block {state_block_number}, not at the replayed block \
+         {replayed_block_number}."
+    )]
+    ReplayBlockNumberMismatch {
+        state_block_number: BlockNumber,
+        replayed_block_number: BlockNumber,
+    },
+    #[error(transparent)]
+    StateError(#[from] StateError),
+}
+
+pub type BlockBuilderResult<T> = Result<T, BlockBuilderError>;
+
+/// The header fields of a built block that are chosen by its proposer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedBlockHeader {
+    pub timestamp: BlockTimestamp,
+    pub sequencer: SequencerContractAddress,
+    pub l1_gas_price: GasPricePerToken,
+    pub l1_data_gas_price: GasPricePerToken,
+    // Set by the fee market of the batcher.
+    pub l2_gas_price: GasPricePerToken,
+    // The protocol version the block is executed with, by its height.
+    pub starknet_version: StarknetVersion,
+}
+
+/// The receipt of a transaction that was added to a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutedTransaction {
+    pub tx_hash: TransactionHash,
+    // Unset for L1 handler transactions, which are not signed.
+    pub signature: Option<TransactionSignature>,
+    pub fee: Fee,
+    pub gas_consumed: GasVector,
+    pub n_events: usize,
+    pub is_reverted: bool,
+}
+
+/// The output of building a block, kept until consensus decides on the block.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlockExecutionArtifacts {
+    pub header: ProposedBlockHeader,
+    /// The transactions of the block, in execution order.
+    pub txs: Vec<Transaction>,
+    /// The execution results of the transactions of the block, in the order of `txs`.
+    pub executed_txs: Vec<ExecutedTransaction>,
+    pub state_diff: ThinStateDiff,
+    /// The gas consumed by the transactions of the block.
+    pub gas_consumed: GasVector,
+    /// The transactions that were left out of the block for failing to execute, reported back to
+    /// the mempool.
+    pub rejected_txs: Vec<RejectedTransaction>,
+    /// The transactions of the input stream that were left out of the block, in stream order.
+    pub excluded_txs: Vec<ExcludedTransaction>,
+    pub concurrency_stats: ConcurrencyStats,
+    /// The resources the bouncer counted for the transactions of the block.
+    pub bouncer_weights: BouncerWeights,
+}
+
+/// A transaction that was left out of a block, reported to validators when a proposal diverges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExcludedTransaction {
+    pub tx_hash: TransactionHash,
+    pub reason: TxExclusionReason,
+}
+
+// The transactions left out of a block in the making.
+#[derive(Default)]
+struct LeftOutTxs {
+    excluded_txs: Vec<ExcludedTransaction>,
+    rejected_txs: Vec<RejectedTransaction>,
+}
+
+impl LeftOutTxs {
+    fn exclude(&mut self, tx: &Transaction, reason: TxExclusionReason) {
+        self.excluded_txs.push(ExcludedTransaction { tx_hash: tx.tx_hash(), reason });
+    }
+
+    // Excludes the transaction and reports it to the mempool as well.
+    fn reject(
+        &mut self,
+        tx: &Transaction,
+        reason: TxExclusionReason,
+        rejection_reason: RejectionReason,
+    ) {
+        self.exclude(tx, reason);
+        self.rejected_txs.extend(rejected_tx(tx, rejection_reason));
+    }
+}
+
+/// How the transactions of a block were executed in concurrency mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConcurrencyStats {
+    /// The number of transactions executed in concurrent chunks.
+    pub n_concurrent_txs: usize,
+    /// The number of executions repeated for conflicts between transactions of a chunk.
+    pub n_reexecutions: usize,
+    /// Whether the block builder fell back to sequential execution for too many conflicts.
+    pub fell_back_to_sequential: bool,
+}
+
+impl BlockExecutionArtifacts {
+    pub fn tx_hashes(&self) -> Vec<TransactionHash> {
+        self.executed_txs.iter().map(|executed_tx| executed_tx.tx_hash).collect()
+    }
+
+    /// The number of events emitted by the transactions of the block.
+    pub fn n_events(&self) -> usize {
+        self.executed_txs.iter().map(|executed_tx| executed_tx.n_events).sum()
+    }
+
+    /// The receipts of the transactions of the block, in execution order.
+    pub fn receipts(&self) -> Vec<TransactionReceipt> {
+        self.executed_txs
+            .iter()
+            .map(|executed_tx| TransactionReceipt {
+                tx_hash: executed_tx.tx_hash,
+                actual_fee: executed_tx.fee,
+                l1_gas_used: executed_tx.gas_consumed.l1_gas,
+                l1_data_gas_used: executed_tx.gas_consumed.l1_data_gas,
+                l2_gas_used: executed_tx.gas_consumed.l2_gas,
+                n_events: executed_tx.n_events,
+                is_reverted: executed_tx.is_reverted,
+            })
+            .collect()
+    }
+
+    /// The resources used by the block, which took `build_duration` to build.
+    pub fn metadata(&self, build_duration: Duration) -> ProposalMetadata {
+        ProposalMetadata {
+            n_txs: self.executed_txs.len(),
+            n_reverted_txs: self
+                .executed_txs
+                .iter()
+                .filter(|executed_tx| executed_tx.is_reverted)
+                .count(),
+            l1_gas_used: self.gas_consumed.l1_gas,
+            l1_data_gas_used: self.gas_consumed.l1_data_gas,
+            l2_gas_used: self.gas_consumed.l2_gas,
+            n_steps: self.bouncer_weights.n_steps,
+            builtin_counts: builtin_counts(&self.bouncer_weights.builtin_count),
+            build_duration,
+        }
+    }
+}
+
+/// The capacity of the built blocks. A block is closed once it is full, even before the deadline.
+/// The limits on the number of transactions and on their execution time can be changed without
+/// restarting the node.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct BlockBuilderConfig {
+    #[validate(range(min = 1))]
+    pub max_n_transactions: usize,
+    pub bouncer_config: BouncerConfig,
+    // A transaction that takes longer to execute is left out of the block, so that a single
+    // transaction can't use up the time of the proposal.
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub max_tx_execution_time: Duration,
+    #[validate]
+    pub concurrency_config: ExecutionConcurrencyConfig,
+    // If set, a block that no transaction was added to is sealed empty once `min_empty_block_wait`
+    // passed since its building started, instead of waiting for transactions until the deadline.
+    pub seal_empty_blocks_early: bool,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub min_empty_block_wait: Duration,
+    // Selects the versioned constants the blocks are executed with, so that protocol upgrades are
+    // scheduled by the config.
+    #[serde(deserialize_with = "deserialize_protocol_versions")]
+    pub protocol_versions: ProtocolVersions,
+}
+
+impl Default for BlockBuilderConfig {
+    fn default() -> Self {
+        // TODO: Get correct values for the default block capacity.
+        Self {
+            max_n_transactions: 5000,
+            bouncer_config: BouncerConfig::max(),
+            max_tx_execution_time: Duration::from_secs(1),
+            concurrency_config: ExecutionConcurrencyConfig::default(),
+            seal_empty_blocks_early: false,
+            min_empty_block_wait: Duration::from_secs(1),
+            protocol_versions: default_protocol_versions(),
+        }
+    }
+}
+
+impl SerializeConfig for BlockBuilderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let members = BTreeMap::from_iter([
+            ser_dynamic_param(
+                "max_n_transactions",
+                &self.max_n_transactions,
+                "Maximum number of transactions in a block",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "max_tx_execution_time",
+                &u64::try_from(self.max_tx_execution_time.as_millis())
+                    .expect("Execution time should fit u64."),
+                "Maximum execution time of a transaction, in milliseconds; a transaction that \
+                 takes longer is left out of the block",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "seal_empty_blocks_early",
+                &self.seal_empty_blocks_early,
+                "If true, a block that no transaction was added to is sealed empty once \
+                 'min_empty_block_wait' passed since its building started, instead of at the \
+                 deadline",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "min_empty_block_wait",
+                &u64::try_from(self.min_empty_block_wait.as_millis())
+                    .expect("Empty block wait should fit u64."),
+                "The time the block builder waits for transactions, in milliseconds, before \
+                 sealing an empty block, if 'seal_empty_blocks_early' is set",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "protocol_versions",
+                &serialize_protocol_versions(&self.protocol_versions),
+                "Comma-separated 'height:version' pairs of the Starknet protocol versions of the \
+                 built blocks, each applying from its height until the next one. Must have an \
+                 entry for height 0.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![
+            members,
+            append_sub_config_name(self.bouncer_config.dump(), "bouncer_config"),
+            append_sub_config_name(self.concurrency_config.dump(), "concurrency_config"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Optimistic concurrent execution of the transactions of a block: the transactions ready in the
+/// input stream are executed in chunks by several workers, and transactions that conflict with
+/// earlier transactions of their chunk are re-executed. The execution time of a transaction isn't
+/// limited in a concurrent chunk.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ExecutionConcurrencyConfig {
+    pub enabled: bool,
+    #[validate(range(min = 1))]
+    pub n_workers: usize,
+    #[validate(range(min = 1))]
+    pub chunk_size: usize,
+    // Once the re-executions of a chunk exceed this percentage of its transactions, the rest of
+    // the block is executed sequentially, as the workers mostly redo each other's work.
+    pub max_reexecutions_percentage: usize,
+}
+
+impl Default for ExecutionConcurrencyConfig {
+    fn default() -> Self {
+        Self { enabled: false, n_workers: 4, chunk_size: 64, max_reexecutions_percentage: 50 }
+    }
+}
+
+impl SerializeConfig for ExecutionConcurrencyConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "Whether to execute the transactions of a block concurrently",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "n_workers",
+                &self.n_workers,
+                "Number of workers executing a chunk of transactions concurrently",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "chunk_size",
+                &self.chunk_size,
+                "Maximum number of transactions executed concurrently as a chunk",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_reexecutions_percentage",
+                &self.max_reexecutions_percentage,
+                "Percentage of the transactions of a chunk re-executed for conflicts above which \
+                 the rest of the block is executed sequentially",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl ExecutionConcurrencyConfig {
+    fn executor_config(&self) -> TransactionExecutorConfig {
+        TransactionExecutorConfig {
+            concurrency_config: ConcurrencyConfig {
+                enabled: self.enabled,
+                n_workers: self.n_workers,
+                chunk_size: self.chunk_size,
+            },
+        }
+    }
+
+    fn too_many_reexecutions(&self, n_txs: usize, n_reexecutions: usize) -> bool {
+        n_reexecutions.saturating_mul(100) > self.max_reexecutions_percentage.saturating_mul(n_txs)
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlockBuilderTrait: Send + Sync {
+    /// Adds transactions from `tx_stream` to the block with the given block info until the deadline
+    /// is reached, the block is full or the stream ends. Every transaction added to the block is
+    /// sent to `output_content_sender`.
+    async fn build_block(
+        &self,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts>;
+
+    /// Like [`Self::build_block`], but executes the transactions sequentially whatever the
+    /// configured concurrency, e.g., to retry a block whose concurrent execution failed.
+    async fn build_block_sequentially(
+        &self,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        self.build_block(block_info, deadline, tx_stream, output_content_sender).await
+    }
+}
+
+pub trait BatcherStateReader: BlockifierStateReader + Send + Sync {}
+
+#[cfg_attr(test, automock)]
+pub trait StateReaderFactory: Send + Sync {
+    /// Returns the number of the next block, and a reader of the state the block is built on.
+    fn get_state_reader_for_next_block(
+        &self,
+    ) -> StateResult<(BlockNumber, Box<dyn BatcherStateReader>)>;
+}
+
+// By default, a Box<dyn Trait> does not implement the trait of the object it contains.
+// Therefore, for using the Box<dyn BatcherStateReader>, that the StateReaderFactory creates,
+// we need to implement the BlockifierStateReader trait for Box<dyn BatcherStateReader>.
+impl BlockifierStateReader for Box<dyn BatcherStateReader> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.as_ref().get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.as_ref().get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.as_ref().get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.as_ref().get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.as_ref().get_compiled_class_hash(class_hash)
+    }
+}
+
+// Computing the state diff of a block takes time linear in its size, so the pending state is not
+// published after every chunk.
+const PENDING_STATE_PUBLISH_INTERVAL: Duration = Duration::from_millis(100);
+// A replayed block ends with its recorded transactions; the deadline only bounds a stuck replay.
+const REPLAY_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// The state changes of the block being built. Published by the block builder while it builds a
+/// block, and read by the batcher to serve the gateway.
+#[derive(Clone, Debug, Default)]
+pub struct SharedPendingState(Arc<RwLock<Option<PendingState>>>);
+
+impl SharedPendingState {
+    pub fn get(&self) -> Option<PendingState> {
+        self.0.read().expect("Pending state lock should not be poisoned.").clone()
+    }
+
+    pub fn set(&self, pending_state: Option<PendingState>) {
+        *self.0.write().expect("Pending state lock should not be poisoned.") = pending_state;
+    }
+}
+
+/// The transactions executed into the block being built so far, and the gas they consumed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuildProgress {
+    pub n_executed_txs: usize,
+    pub n_reverted_txs: usize,
+    pub gas_consumed: GasVector,
+}
+
+impl BuildProgress {
+    fn add(&mut self, executed_tx: &ExecutedTransaction) {
+        self.n_executed_txs += 1;
+        self.n_reverted_txs += usize::from(executed_tx.is_reverted);
+        self.gas_consumed = self.gas_consumed + executed_tx.gas_consumed;
+    }
+}
+
+/// The progress of the block being built. Published by the block builder after every chunk of
+/// transactions, and read by the proposals manager to report the progress of the active proposal.
+#[derive(Clone, Debug, Default)]
+pub struct SharedBuildProgress(Arc<RwLock<BuildProgress>>);
+
+impl SharedBuildProgress {
+    pub fn get(&self) -> BuildProgress {
+        *self.0.read().expect("Build progress lock should not be poisoned.")
+    }
+
+    pub fn set(&self, progress: BuildProgress) {
+        *self.0.write().expect("Build progress lock should not be poisoned.") = progress;
+    }
+}
+
+/// Builds blocks by executing the transactions of the input stream with the blockifier, on top of
+/// the latest state.
+pub struct BlockBuilder {
+    // Each block is built with the config set when its building starts.
+    config: SharedDynamicConfig<BlockBuilderConfig>,
+    state_reader_factory: Arc<dyn StateReaderFactory>,
+    chain_info: ChainInfo,
+    fee_config: FeeConfig,
+    // If set, the gas prices of the built blocks are converted between the fee tokens by this
+    // STRK/ETH rate. Otherwise, the prices in both fee tokens are equal.
+    strk_per_eth_rate: Option<NonZeroU128>,
+    l2_gas_price: SharedL2GasPrice,
+    // Provides the L1 gas prices of the built blocks, if set.
+    l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient>,
+    clock: SharedClock,
+    // If set, the state changes of the block being built are published to it.
+    pending_state: Option<SharedPendingState>,
+    // If set, the progress of the block being built is published to it.
+    build_progress: Option<SharedBuildProgress>,
+    // If set, the inputs of the built blocks are recorded, for replaying them offline.
+    replay_recorder: Option<BlockReplayRecorder>,
+}
+
+impl BlockBuilder {
+    pub fn new(
+        config: SharedDynamicConfig<BlockBuilderConfig>,
+        state_reader_factory: Arc<dyn StateReaderFactory>,
+        chain_info: ChainInfo,
+        l2_gas_price: SharedL2GasPrice,
+        l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient>,
+        clock: SharedClock,
+    ) -> Self {
+        Self {
+            config,
+            state_reader_factory,
+            chain_info,
+            fee_config: FeeConfig::default(),
+            strk_per_eth_rate: None,
+            l2_gas_price,
+            l1_gas_price_provider_client,
+            clock,
+            pending_state: None,
+            build_progress: None,
+            replay_recorder: None,
+        }
+    }
+
+    pub fn with_fee_config(mut self, fee_config: FeeConfig) -> Self {
+        self.fee_config = fee_config;
+        self
+    }
+
+    pub fn with_strk_per_eth_rate(mut self, strk_per_eth_rate: NonZeroU128) -> Self {
+        self.strk_per_eth_rate = Some(strk_per_eth_rate);
+        self
+    }
+
+    pub fn with_pending_state(mut self, pending_state: SharedPendingState) -> Self {
+        self.pending_state = Some(pending_state);
+        self
+    }
+
+    pub fn with_build_progress(mut self, build_progress: SharedBuildProgress) -> Self {
+        self.build_progress = Some(build_progress);
+        self
+    }
+
+    pub fn with_replay_recorder(mut self, replay_recorder: BlockReplayRecorder) -> Self {
+        self.replay_recorder = Some(replay_recorder);
+        self
+    }
+
+    /// Executes the transactions of a recorded block again, with its recorded header, on top of
+    /// the latest state, which must be the state the block was built on. The transactions are
+    /// executed sequentially and without a time limit, so that the replay doesn't depend on the
+    /// config or the speed of the replaying node; check the result with
+    /// [`BlockReplayRecord::check`].
+    pub async fn replay_block(
+        &self,
+        record: &BlockReplayRecord,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let (block_number, state_reader) =
+            self.state_reader_factory.get_state_reader_for_next_block()?;
+        if block_number != record.block_number {
+            return Err(BlockBuilderError::ReplayBlockNumberMismatch {
+                state_block_number: block_number,
+                replayed_block_number: record.block_number,
+            });
+        }
+        let config = BlockBuilderConfig {
+            max_n_transactions: usize::MAX,
+            bouncer_config: BouncerConfig::max(),
+            max_tx_execution_time: Duration::MAX,
+            concurrency_config: ExecutionConcurrencyConfig { enabled: false, ..Default::default() },
+            seal_empty_blocks_early: false,
+            ..self.config.get()
+        };
+        let (output_content_sender, _output_content_receiver) =
+            tokio::sync::mpsc::channel(record.txs.len().max(1));
+        self.execute_block(
+            block_number,
+            state_reader,
+            record.header.clone(),
+            &config,
+            self.clock.now() + REPLAY_TIME_LIMIT,
+            Box::pin(tokio_stream::iter(record.txs.clone())),
+            output_content_sender,
+        )
+        .await
+    }
+
+    // Publishes the state changes of the transactions executed so far, if the pending state is
+    // set.
+    fn publish_pending_state<S: BlockifierStateReader>(
+        &self,
+        block_number: BlockNumber,
+        executor: &mut TransactionExecutor<S>,
+    ) -> BlockBuilderResult<()> {
+        let Some(pending_state) = &self.pending_state else {
+            return Ok(());
+        };
+        let block_state = executor.block_state.as_mut().ok_or_else(|| {
+            BlockBuilderError::InternalError("The block state was taken.".to_string())
+        })?;
+        let state_maps = block_state.to_state_diff()?;
+        let state_diff = to_state_diff(state_maps.into(), &block_state.state)?;
+        pending_state.set(Some(PendingState { block_number, state_diff: state_diff.into() }));
+        Ok(())
+    }
+
+    // Builds a block with the given config, rather than the one currently set.
+    async fn build_block_with_config(
+        &self,
+        config: BlockBuilderConfig,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let (block_number, state_reader) =
+            self.state_reader_factory.get_state_reader_for_next_block()?;
+        let header = self.proposed_header(block_number, block_info, &config).await;
+        let artifacts = self
+            .execute_block(
+                block_number,
+                state_reader,
+                header,
+                &config,
+                deadline,
+                tx_stream,
+                output_content_sender,
+            )
+            .await?;
+        if let Some(replay_recorder) = &self.replay_recorder {
+            replay_recorder.record(&BlockReplayRecord::new(block_number, &artifacts));
+        }
+        Ok(artifacts)
+    }
+
+    async fn proposed_header(
+        &self,
+        block_number: BlockNumber,
+        block_info: ProposalBlockInfo,
+        config: &BlockBuilderConfig,
+    ) -> ProposedBlockHeader {
+        // TODO: Take the L1 gas prices from the proposal.
+        let ProposalBlockInfo { timestamp, sequencer_address } = block_info;
+        let price_info = self.l1_price_info(timestamp).await;
+        let l2_gas_price = self.fee_config.l2_gas_price(self.l2_gas_price.get()).get();
+        let l1_gas_price = self.fee_config.l1_gas_price(price_info.base_fee_per_gas).get();
+        ProposedBlockHeader {
+            timestamp,
+            sequencer: SequencerContractAddress(sequencer_address),
+            l1_gas_price: gas_price_from_wei(l1_gas_price, self.strk_per_eth_rate),
+            l1_data_gas_price: gas_price_from_wei(price_info.blob_fee, self.strk_per_eth_rate),
+            l2_gas_price: gas_price_from_fri(l2_gas_price, self.strk_per_eth_rate),
+            starknet_version: protocol_version_at(&config.protocol_versions, block_number).clone(),
+        }
+    }
+
+    // The L1 gas prices the block is sealed with. Without an L1 gas price provider, or if it
+    // fails, the minimal gas price is used.
+    async fn l1_price_info(&self, timestamp: BlockTimestamp) -> PriceInfo {
+        let Some(l1_gas_price_provider_client) = &self.l1_gas_price_provider_client else {
+            return PriceInfo::default();
+        };
+        l1_gas_price_provider_client.get_price_info(timestamp).await.unwrap_or_else(|err| {
+            warn!("Failed to get the L1 gas prices, using the minimal gas price: {}", err);
+            PriceInfo::default()
+        })
+    }
+
+    // Returns the transaction along with its form for execution, unless it doesn't pass the checks
+    // it was admitted with or can't be converted.
+    fn executable_tx(
+        &self,
+        tx: Transaction,
+        l1_gas_price: NonZeroU128,
+        l2_gas_price: NonZeroU128,
+        left_out_txs: &mut LeftOutTxs,
+    ) -> Option<(Transaction, BlockifierTransaction)> {
+        let tx_hash = tx.tx_hash();
+        // The checks the gateway admitted the transaction with, against the fee policy of the
+        // chain and the gas prices of the block. Such transactions aren't rejected, as they may be
+        // valid at later gas prices or under a later policy.
+        if let Some(resource_bounds) = tx.resource_bounds() {
+            if let Err(err) = self
+                .fee_config
+                .validate_resource_bounds(resource_bounds)
+                .and(validate_l1_gas_bounds(resource_bounds.get_l1_bounds(), l1_gas_price))
+                .and(validate_l2_gas_price(resource_bounds.get_l2_bounds(), l2_gas_price))
+            {
+                debug!(%tx_hash, "Transaction has invalid resource bounds: {}", err);
+                left_out_txs
+                    .exclude(&tx, TxExclusionReason::InvalidResourceBounds(err.to_string()));
+                return None;
+            }
+        }
+        match BlockifierTransaction::try_from(tx.clone()) {
+            Ok(blockifier_tx) => Some((tx, blockifier_tx)),
+            Err(err) => {
+                warn!(%tx_hash, "Failed to convert transaction for execution: {}", err);
+                left_out_txs.reject(
+                    &tx,
+                    TxExclusionReason::ExecutionFailed(err.to_string()),
+                    RejectionReason::ExecutionFailed,
+                );
+                None
+            }
+        }
+    }
+
+    fn block_context(
+        &self,
+        block_number: BlockNumber,
+        header: &ProposedBlockHeader,
+        bouncer_config: BouncerConfig,
+    ) -> BlockContext {
+        let gas_prices = GasPrices::new(
+            gas_price_of(header.l1_gas_price.price_in_wei),
+            gas_price_of(header.l1_gas_price.price_in_fri),
+            gas_price_of(header.l1_data_gas_price.price_in_wei),
+            gas_price_of(header.l1_data_gas_price.price_in_fri),
+            gas_price_of(header.l2_gas_price.price_in_wei),
+            gas_price_of(header.l2_gas_price.price_in_fri),
+        );
+        BlockContextBuilder::new()
+            .chain_info(self.chain_info.clone())
+            .block_number(block_number)
+            .block_timestamp(header.timestamp)
+            .gas_prices(gas_prices)
+            .sequencer_address(header.sequencer.0)
+            .versioned_constants(versioned_constants_of(&header.starknet_version).clone())
+            .bouncer_config(bouncer_config)
+            .build()
+    }
+
+    // Executes the transactions of the stream into a block with the given header, until the
+    // deadline.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_block(
+        &self,
+        block_number: BlockNumber,
+        state_reader: Box<dyn BatcherStateReader>,
+        header: ProposedBlockHeader,
+        config: &BlockBuilderConfig,
+        deadline: tokio::time::Instant,
+        mut tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let l1_gas_price = gas_price_of(header.l1_gas_price.price_in_fri);
+        let l2_gas_price = gas_price_of(header.l2_gas_price.price_in_fri);
+        let mut executor = TransactionExecutor::new(
+            CachedState::new(state_reader),
+            self.block_context(block_number, &header, config.bouncer_config.clone()),
+            config.concurrency_config.executor_config(),
+        );
+
+        // The deadline at which an empty block is sealed, if earlier than the deadline.
+        let empty_block_deadline = config
+            .seal_empty_blocks_early
+            .then(|| self.clock.now() + config.min_empty_block_wait)
+            .filter(|empty_block_deadline| *empty_block_deadline < deadline);
+
+        if let Some(pending_state) = &self.pending_state {
+            pending_state.set(None);
+        }
+        let mut next_pending_state_publish = self.clock.now();
+        let mut progress = BuildProgress::default();
+        if let Some(build_progress) = &self.build_progress {
+            build_progress.set(progress);
+        }
+
+        let concurrency_config = &config.concurrency_config;
+        let mut concurrent = concurrency_config.enabled;
+        let mut concurrency_stats = ConcurrencyStats::default();
+        let mut executed_txs = Vec::new();
+        let mut block_txs = Vec::new();
+        let mut left_out_txs = LeftOutTxs::default();
+        let mut stream_ended = false;
+        while !stream_ended {
+            let n_remaining_txs = config.max_n_transactions - executed_txs.len();
+            if n_remaining_txs == 0 {
+                info!("Block reached the maximal number of transactions.");
+                break;
+            }
+            let tx_deadline = match empty_block_deadline {
+                Some(empty_block_deadline) if executed_txs.is_empty() => empty_block_deadline,
+                _ => deadline,
+            };
+            let tx = match timeout_at(self.clock.as_ref(), tx_deadline, tx_stream.next()).await {
+                None if tx_deadline < deadline => {
+                    info!("No transaction was added to the block in time, sealing it empty.");
+                    break;
+                }
+                None => {
+                    info!("Block builder reached the deadline.");
+                    break;
+                }
+                Some(None) => {
+                    debug!("Input transaction stream ended.");
+                    break;
+                }
+                Some(Some(tx)) => tx,
+            };
+            let mut txs = vec![tx];
+            if concurrent {
+                // Only the transactions already in the stream join the chunk, so that its execution
+                // doesn't wait for the stream.
+                let chunk_size = concurrency_config.chunk_size.min(n_remaining_txs);
+                while txs.len() < chunk_size {
+                    match tx_stream.next().now_or_never() {
+                        Some(Some(tx)) => txs.push(tx),
+                        Some(None) => {
+                            debug!("Input transaction stream ended.");
+                            stream_ended = true;
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            #[cfg(feature = "allocation_counting")]
+            let allocation_scope = AllocationScope::start();
+            let (txs, blockifier_txs): (Vec<_>, Vec<_>) = txs
+                .into_iter()
+                .filter_map(|tx| {
+                    self.executable_tx(tx, l1_gas_price, l2_gas_price, &mut left_out_txs)
+                })
+                .unzip();
+            let n_chunk_txs = blockifier_txs.len();
+            let executed_sequentially = n_chunk_txs <= 1;
+            let n_reexecutions_before_chunk = executor.n_reexecutions;
+            let max_tx_execution_time = config.max_tx_execution_time;
+            // Execution is CPU bound and may take a while, so it runs off the async threads.
+            let (returned_executor, results) = tokio::task::spawn_blocking(move || {
+                let results = if executed_sequentially {
+                    blockifier_txs
+                        .iter()
+                        .map(|tx| executor.execute_with_time_limit(tx, max_tx_execution_time))
+                        .collect()
+                } else {
+                    executor.execute_chunk(&blockifier_txs)
+                };
+                (executor, results)
+            })
+            .await
+            .map_err(|err| BlockBuilderError::InternalError(err.to_string()))?;
+            executor = returned_executor;
+            if !executed_sequentially {
+                let n_reexecutions = executor.n_reexecutions - n_reexecutions_before_chunk;
+                concurrency_stats.n_concurrent_txs += results.len();
+                concurrency_stats.n_reexecutions += n_reexecutions;
+                if concurrency_config.too_many_reexecutions(n_chunk_txs, n_reexecutions) {
+                    warn!(
+                        "{} re-executions in a chunk of {} transactions, executing the rest of \
+                         the block sequentially.",
+                        n_reexecutions, n_chunk_txs
+                    );
+                    concurrent = false;
+                    concurrency_stats.fell_back_to_sequential = true;
+                }
+            }
+
+            // A chunk stops at the first transaction that doesn't fit in the block.
+            let mut block_full = false;
+            if let Some(unfit_tx) = txs.get(results.len()) {
+                left_out_txs.exclude(unfit_tx, TxExclusionReason::BlockFull);
+                block_full = true;
+            }
+            for (tx, execution_result) in txs.into_iter().zip(results) {
+                let tx_hash = tx.tx_hash();
+                let executed_tx = match execution_result {
+                    Ok(execution_info) => {
+                        if execution_info.is_reverted() {
+                            debug!(%tx_hash, "Transaction was reverted.");
+                        }
+                        ExecutedTransaction {
+                            tx_hash,
+                            signature: tx.signature(),
+                            fee: execution_info.receipt.fee,
+                            gas_consumed: execution_info.receipt.gas,
+                            n_events: execution_info.summarize().n_events,
+                            is_reverted: execution_info.is_reverted(),
+                        }
+                    }
+                    Err(TransactionExecutorError::BlockFull) => {
+                        left_out_txs.exclude(&tx, TxExclusionReason::BlockFull);
+                        block_full = true;
+                        break;
+                    }
+                    Err(err @ TransactionExecutorError::ExecutionTimeout { .. }) => {
+                        warn!(%tx_hash, "Transaction was left out of the block: {}", err);
+                        left_out_txs.reject(
+                            &tx,
+                            TxExclusionReason::ExecutionTimeout,
+                            RejectionReason::ExecutionTimeout,
+                        );
+                        continue;
+                    }
+                    Err(err) => {
+                        debug!(%tx_hash, "Transaction failed to execute: {}", err);
+                        left_out_txs.reject(
+                            &tx,
+                            TxExclusionReason::ExecutionFailed(err.to_string()),
+                            rejection_reason(&err),
+                        );
+                        continue;
+                    }
+                };
+                #[cfg(feature = "allocation_counting")]
+                if executed_sequentially {
+                    allocation_scope.record(TX_ALLOCATIONS);
+                }
+                block_txs.push(tx.clone());
+                output_content_sender
+                    .send(tx)
+                    .await
+                    .map_err(|_| BlockBuilderError::OutputStreamClosed)?;
+                progress.add(&executed_tx);
+                executed_txs.push(executed_tx);
+            }
+            if let Some(build_progress) = &self.build_progress {
+                build_progress.set(progress);
+            }
+            if self.clock.now() >= next_pending_state_publish {
+                self.publish_pending_state(block_number, &mut executor)?;
+                next_pending_state_publish = self.clock.now() + PENDING_STATE_PUBLISH_INTERVAL;
+            }
+            if block_full {
+                info!("Block is full.");
+                break;
+            }
+        }
+
+        let (commitment_state_diff, _visited_segments, bouncer_weights) =
+            executor.finalize().map_err(|err| BlockBuilderError::InternalError(err.to_string()))?;
+        let block_state = executor.block_state.as_ref().ok_or_else(|| {
+            BlockBuilderError::InternalError("The block state was taken.".to_string())
+        })?;
+        let state_diff: ThinStateDiff =
+            to_state_diff(commitment_state_diff, &block_state.state)?.into();
+        if let Some(pending_state) = &self.pending_state {
+            pending_state.set(Some(PendingState { block_number, state_diff: state_diff.clone() }));
+        }
+        let gas_consumed = executed_txs.iter().map(|executed_tx| executed_tx.gas_consumed).sum();
+        Ok(BlockExecutionArtifacts {
+            header,
+            txs: block_txs,
+            executed_txs,
+            state_diff,
+            gas_consumed,
+            rejected_txs: left_out_txs.rejected_txs,
+            excluded_txs: left_out_txs.excluded_txs,
+            concurrency_stats,
+            bouncer_weights,
+        })
+    }
+}
+
+#[async_trait]
+impl BlockBuilderTrait for BlockBuilder {
+    async fn build_block(
+        &self,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        self.build_block_with_config(
+            self.config.get(),
+            block_info,
+            deadline,
+            tx_stream,
+            output_content_sender,
+        )
+        .await
+    }
+
+    async fn build_block_sequentially(
+        &self,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let config = self.config.get();
+        let config = BlockBuilderConfig {
+            concurrency_config: ExecutionConcurrencyConfig {
+                enabled: false,
+                ..config.concurrency_config
+            },
+            ..config
+        };
+        self.build_block_with_config(config, block_info, deadline, tx_stream, output_content_sender)
+            .await
+    }
+}
+
+fn builtin_counts(builtin_count: &BuiltinCount) -> BTreeMap<String, usize> {
+    let BuiltinCount {
+        add_mod,
+        bitwise,
+        ecdsa,
+        ec_op,
+        keccak,
+        mul_mod,
+        pedersen,
+        poseidon,
+        range_check,
+        range_check96,
+    } = *builtin_count;
+    BTreeMap::from_iter(
+        [
+            ("add_mod", add_mod),
+            ("bitwise", bitwise),
+            ("ecdsa", ecdsa),
+            ("ec_op", ec_op),
+            ("keccak", keccak),
+            ("mul_mod", mul_mod),
+            ("pedersen", pedersen),
+            ("poseidon", poseidon),
+            ("range_check", range_check),
+            ("range_check96", range_check96),
+        ]
+        .map(|(name, count)| (name.to_string(), count)),
+    )
+}
+
+// L1 handler transactions aren't in the mempool, so their failures aren't reported to it.
+fn rejected_tx(tx: &Transaction, reason: RejectionReason) -> Option<RejectedTransaction> {
+    if matches!(tx, Transaction::L1Handler(_)) {
+        return None;
+    }
+    Some(RejectedTransaction {
+        tx_hash: tx.tx_hash(),
+        address: tx.contract_address(),
+        nonce: tx.nonce(),
+        reason,
+    })
+}
+
+fn rejection_reason(err: &TransactionExecutorError) -> RejectionReason {
+    let TransactionExecutorError::TransactionExecutionError(err) = err else {
+        return RejectionReason::ExecutionFailed;
+    };
+    let fee_error = match err {
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::InvalidNonce { .. },
+        ) => return RejectionReason::InvalidNonce,
+        TransactionExecutionError::TransactionPreValidationError(
+            TransactionPreValidationError::TransactionFeeError(fee_error),
+        )
+        | TransactionExecutionError::TransactionFeeError(fee_error) => fee_error,
+        _ => return RejectionReason::ExecutionFailed,
+    };
+    match fee_error {
+        TransactionFeeError::L1GasBoundsExceedBalance { .. }
+        | TransactionFeeError::MaxFeeExceedsBalance { .. } => RejectionReason::InsufficientBalance,
+        _ => RejectionReason::ExecutionFailed,
+    }
+}
+
+// The prices in both fee tokens of an L1 gas price, given in wei, the fee token of the base layer.
+fn gas_price_from_wei(
+    price_in_wei: u128,
+    strk_per_eth_rate: Option<NonZeroU128>,
+) -> GasPricePerToken {
+    let price_in_fri =
+        strk_per_eth_rate.map_or(price_in_wei, |rate| price_in_wei.saturating_mul(rate.get()));
+    GasPricePerToken {
+        price_in_fri: at_least_min_gas_price(price_in_fri),
+        price_in_wei: at_least_min_gas_price(price_in_wei),
+    }
+}
+
+// The prices in both fee tokens of the L2 gas price, given in fri, the fee token of the fee market.
+fn gas_price_from_fri(
+    price_in_fri: u128,
+    strk_per_eth_rate: Option<NonZeroU128>,
+) -> GasPricePerToken {
+    let price_in_wei = strk_per_eth_rate.map_or(price_in_fri, |rate| price_in_fri / rate);
+    GasPricePerToken {
+        price_in_fri: at_least_min_gas_price(price_in_fri),
+        // Raised, as a price in fri below the rate is converted to zero wei.
+        price_in_wei: at_least_min_gas_price(price_in_wei),
+    }
+}
+
+fn at_least_min_gas_price(price: u128) -> GasPrice {
+    GasPrice(price.max(MIN_GAS_PRICE.get()))
+}
+
+fn gas_price_of(price: GasPrice) -> NonZeroU128 {
+    NonZeroU128::new(price.0).expect("The proposed gas prices should be non-zero.")
+}
+
+// Splits the contracts whose class changed into deployed and replaced ones, by their class in the
+// state the block was built on.
+// TODO: Add the declared classes, which requires the Sierra of declare transactions.
+fn to_state_diff(
+    commitment_state_diff: CommitmentStateDiff,
+    state_reader: &impl BlockifierStateReader,
+) -> BlockBuilderResult<StateDiff> {
+    let mut state_diff = StateDiff {
+        storage_diffs: commitment_state_diff.storage_updates,
+        nonces: commitment_state_diff.address_to_nonce,
+        ..Default::default()
+    };
+    for (address, class_hash) in commitment_state_diff.address_to_class_hash {
+        if state_reader.get_class_hash_at(address)? == ClassHash::default() {
+            state_diff.deployed_contracts.insert(address, class_hash);
+        } else {
+            state_diff.replaced_classes.insert(address, class_hash);
+        }
+    }
+    Ok(state_diff)
+}
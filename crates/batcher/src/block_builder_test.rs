@@ -0,0 +1,818 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroU128;
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use blockifier::blockifier::config::TransactionExecutorConfig;
+use blockifier::blockifier::transaction_executor::TransactionExecutor;
+use blockifier::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount};
+use blockifier::context::ChainInfo;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::dict_state_reader::DictStateReader;
+use blockifier::test_utils::initial_test_state::test_state;
+use blockifier::test_utils::{
+    create_trivial_calldata,
+    CairoVersion,
+    BALANCE,
+    MAX_FEE,
+    MAX_L1_GAS_AMOUNT,
+    TEST_SEQUENCER_ADDRESS,
+};
+use blockifier::transaction::account_transaction::AccountTransaction;
+use blockifier::transaction::objects::FeeType;
+use blockifier::transaction::transaction_execution::Transaction as BlockifierTransaction;
+use rstest::{fixture, rstest};
+use starknet_api::block::{BlockNumber, BlockTimestamp, GasPrice, StarknetVersion};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey, SequencerContractAddress};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::test_utils::invoke::invoke_tx;
+use starknet_api::transaction::{
+    Fee,
+    ResourceBounds,
+    TransactionHash,
+    TransactionVersion,
+    ValidResourceBounds,
+};
+use starknet_api::{contract_address, felt, invoke_tx_args, patricia_key};
+use starknet_batcher_types::batcher_types::{PendingState, ProposalBlockInfo, TxExclusionReason};
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderClientError,
+    L1GasPriceProviderClientResult,
+    MockL1GasPriceProviderClient,
+    SharedL1GasPriceProviderClient,
+};
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceInfo;
+use starknet_mempool_infra::component_client::ClientError;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_types::mempool_types::{RejectedTransaction, RejectionReason};
+use starknet_mempool_types::resource_bounds::{
+    validate_l1_gas_bounds,
+    FeeConfig,
+    SharedL2GasPrice,
+    MIN_GAS_PRICE,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+
+use crate::block_builder::{
+    BatcherStateReader,
+    BlockBuilder,
+    BlockBuilderConfig,
+    BlockBuilderError,
+    BlockBuilderResult,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    BuildProgress,
+    ExcludedTransaction,
+    ExecutionConcurrencyConfig,
+    InputTxStream,
+    MockStateReaderFactory,
+    ProposedBlockHeader,
+    SharedBuildProgress,
+    SharedPendingState,
+};
+use crate::block_replay::{BlockReplayConfig, BlockReplayRecord, BlockReplayRecorder};
+use crate::clock::{Clock, SharedClock, TokioClock};
+use crate::protocol_version::LATEST_STARKNET_VERSION;
+use crate::test_utils::FakeClock;
+
+const BLOCK_NUMBER: BlockNumber = BlockNumber(1);
+const TEST_TIMESTAMP: u64 = 1_700_000_000;
+const BUILD_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+// The number of range check builtin instances used by a test transaction.
+const TX_N_RANGE_CHECKS: usize = 86;
+
+impl BatcherStateReader for DictStateReader {}
+
+fn account_contract() -> FeatureContract {
+    FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0)
+}
+
+fn test_contract() -> FeatureContract {
+    FeatureContract::TestContract(CairoVersion::Cairo0)
+}
+
+// An invoke transaction of the test account, calling the test contract.
+fn account_invoke_tx(nonce: u64) -> Transaction {
+    let tx = invoke_tx(invoke_tx_args! {
+        sender_address: account_contract().get_instance_address(0),
+        calldata: create_trivial_calldata(test_contract().get_instance_address(0)),
+        version: TransactionVersion::ONE,
+        max_fee: Fee(MAX_FEE),
+        nonce: Nonce(felt!(nonce)),
+    });
+    Transaction::Invoke(InvokeTransaction { tx, tx_hash: TransactionHash(felt!(nonce)) })
+}
+
+// A V3 invoke transaction of the test account with the given L1 gas bounds.
+fn account_invoke_v3_tx(nonce: u64, l1_bounds: ResourceBounds) -> Transaction {
+    let tx = invoke_tx(invoke_tx_args! {
+        sender_address: account_contract().get_instance_address(0),
+        calldata: create_trivial_calldata(test_contract().get_instance_address(0)),
+        resource_bounds: ValidResourceBounds::L1Gas(l1_bounds),
+        nonce: Nonce(felt!(nonce)),
+    });
+    Transaction::Invoke(InvokeTransaction { tx, tx_hash: TransactionHash(felt!(nonce)) })
+}
+
+fn block_builder_with_config(config: BlockBuilderConfig) -> BlockBuilder {
+    create_block_builder(config, SharedL2GasPrice::default(), None, Arc::new(TokioClock))
+}
+
+fn create_block_builder(
+    config: BlockBuilderConfig,
+    l2_gas_price: SharedL2GasPrice,
+    l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient>,
+    clock: SharedClock,
+) -> BlockBuilder {
+    let chain_info = ChainInfo::create_for_testing();
+    let state = test_state(&chain_info, BALANCE, &[(account_contract(), 1), (test_contract(), 1)]);
+    let mut state_reader_factory = MockStateReaderFactory::new();
+    state_reader_factory
+        .expect_get_state_reader_for_next_block()
+        .return_once(move || Ok((BLOCK_NUMBER, Box::new(state.state))));
+    BlockBuilder::new(
+        SharedDynamicConfig::new(config),
+        Arc::new(state_reader_factory),
+        chain_info,
+        l2_gas_price,
+        l1_gas_price_provider_client,
+        clock,
+    )
+}
+
+fn test_block_info() -> ProposalBlockInfo {
+    ProposalBlockInfo {
+        timestamp: BlockTimestamp(TEST_TIMESTAMP),
+        sequencer_address: contract_address!(TEST_SEQUENCER_ADDRESS),
+    }
+}
+
+#[fixture]
+fn block_builder() -> BlockBuilder {
+    block_builder_with_config(BlockBuilderConfig::default())
+}
+
+async fn build_block(
+    block_builder: &BlockBuilder,
+    deadline: tokio::time::Instant,
+    tx_stream: InputTxStream,
+) -> (BlockBuilderResult<BlockExecutionArtifacts>, Vec<Transaction>) {
+    let (output_content_sender, output_content_receiver) = tokio::sync::mpsc::channel(100);
+    tokio::join!(
+        block_builder.build_block(test_block_info(), deadline, tx_stream, output_content_sender),
+        ReceiverStream::new(output_content_receiver).collect::<Vec<_>>()
+    )
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_executes_txs(block_builder: BlockBuilder) {
+    let valid_txs: Vec<_> = (0..3).map(account_invoke_tx).collect();
+    // A transaction with a nonce that is too high fails and is not added to the block.
+    let invalid_tx = account_invoke_tx(10);
+    let input_txs =
+        vec![valid_txs[0].clone(), valid_txs[1].clone(), invalid_tx, valid_txs[2].clone()];
+
+    let (result, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs)),
+    )
+    .await;
+
+    assert_eq!(output_txs, valid_txs);
+    let state_diff = result.unwrap().state_diff;
+    assert_eq!(
+        state_diff.nonces.get(&account_contract().get_instance_address(0)),
+        Some(&Nonce(felt!(3_u8)))
+    );
+    assert!(state_diff.deployed_contracts.is_empty());
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_outputs_receipts_and_header(block_builder: BlockBuilder) {
+    let input_txs: Vec<_> = (0..2).map(account_invoke_tx).collect();
+
+    let (result, _) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs.clone())),
+    )
+    .await;
+
+    let artifacts = result.unwrap();
+    assert_eq!(
+        artifacts.tx_hashes(),
+        input_txs.iter().map(Transaction::tx_hash).collect::<Vec<_>>()
+    );
+    for executed_tx in &artifacts.executed_txs {
+        assert!(!executed_tx.is_reverted);
+        assert!(executed_tx.fee > Fee(0));
+    }
+    assert_eq!(
+        artifacts.gas_consumed,
+        artifacts.executed_txs.iter().map(|executed_tx| executed_tx.gas_consumed).sum()
+    );
+    assert_eq!(artifacts.header.timestamp, BlockTimestamp(TEST_TIMESTAMP));
+    assert_eq!(
+        artifacts.header.sequencer,
+        SequencerContractAddress(contract_address!(TEST_SEQUENCER_ADDRESS))
+    );
+    assert_eq!(artifacts.header.l1_gas_price.price_in_wei, GasPrice(MIN_GAS_PRICE.get()));
+}
+
+#[tokio::test]
+async fn replay_block_reproduces_the_recorded_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let replay_config = BlockReplayConfig { directory: dir.path().to_path_buf() };
+    let block_builder = block_builder_with_config(BlockBuilderConfig::default())
+        .with_replay_recorder(BlockReplayRecorder::new(&replay_config).unwrap());
+    // The transaction with a nonce that is too high is not added to the block, nor recorded.
+    let input_txs = vec![account_invoke_tx(0), account_invoke_tx(10), account_invoke_tx(1)];
+    let (result, _) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs)),
+    )
+    .await;
+    let artifacts = result.unwrap();
+    let replay_path = std::fs::read_dir(dir.path()).unwrap().next().unwrap().unwrap().path();
+    let record = BlockReplayRecord::read(&replay_path).unwrap();
+    assert_eq!(record.txs, vec![account_invoke_tx(0), account_invoke_tx(1)]);
+
+    // The replay takes the gas prices of the recorded header, not the current ones.
+    let l2_gas_price = SharedL2GasPrice::default();
+    l2_gas_price.set(NonZeroU128::new(3 * MIN_GAS_PRICE.get()).unwrap());
+    let replaying_block_builder = create_block_builder(
+        BlockBuilderConfig::default(),
+        l2_gas_price,
+        None,
+        Arc::new(TokioClock),
+    );
+    let replayed_artifacts = replaying_block_builder.replay_block(&record).await.unwrap();
+
+    assert_eq!(record.check(&replayed_artifacts), Ok(()));
+    assert_eq!(replayed_artifacts.header, artifacts.header);
+    assert_eq!(replayed_artifacts.state_diff, artifacts.state_diff);
+}
+
+#[tokio::test]
+async fn replay_block_fails_on_state_of_another_block() {
+    let record = BlockReplayRecord {
+        block_number: BLOCK_NUMBER.unchecked_next(),
+        header: ProposedBlockHeader::default(),
+        txs: vec![],
+        state_diff: Default::default(),
+    };
+
+    let result =
+        block_builder_with_config(BlockBuilderConfig::default()).replay_block(&record).await;
+
+    assert_matches!(result, Err(BlockBuilderError::ReplayBlockNumberMismatch { .. }));
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_publishes_the_pending_state(block_builder: BlockBuilder) {
+    let pending_state = SharedPendingState::default();
+    let block_builder = block_builder.with_pending_state(pending_state.clone());
+    let (tx_sender, tx_receiver) = tokio::sync::mpsc::channel(1);
+
+    let build = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(ReceiverStream::new(tx_receiver)),
+    );
+    let published_pending_state = pending_state.clone();
+    let add_tx = async move {
+        tx_sender.send(account_invoke_tx(0)).await.unwrap();
+        // The pending state is published while the block is still open.
+        while published_pending_state.get().is_none() {
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+        let published = published_pending_state.get().unwrap();
+        assert_eq!(published.block_number, BLOCK_NUMBER);
+        assert_eq!(
+            published.state_diff.nonces.get(&account_contract().get_instance_address(0)),
+            Some(&Nonce(felt!(1_u8)))
+        );
+    };
+    let ((result, _), ()) = tokio::join!(build, add_tx);
+
+    assert_eq!(
+        pending_state.get(),
+        Some(PendingState { block_number: BLOCK_NUMBER, state_diff: result.unwrap().state_diff })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_publishes_its_progress(block_builder: BlockBuilder) {
+    let build_progress = SharedBuildProgress::default();
+    let block_builder = block_builder.with_build_progress(build_progress.clone());
+    let input_txs: Vec<_> = (0..2).map(account_invoke_tx).collect();
+
+    let (result, _) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs)),
+    )
+    .await;
+
+    assert_eq!(
+        build_progress.get(),
+        BuildProgress {
+            n_executed_txs: 2,
+            n_reverted_txs: 0,
+            gas_consumed: result.unwrap().gas_consumed
+        }
+    );
+}
+
+#[rstest]
+#[case::max_n_transactions(BlockBuilderConfig { max_n_transactions: 2, ..Default::default() })]
+#[case::max_builtin_count(BlockBuilderConfig {
+    bouncer_config: BouncerConfig {
+        block_max_capacity: BouncerWeights {
+            builtin_count: BuiltinCount { range_check: 2 * TX_N_RANGE_CHECKS, ..BuiltinCount::max() },
+            ..BouncerWeights::max()
+        },
+    },
+    ..Default::default()
+})]
+#[tokio::test]
+async fn build_block_stops_when_full(#[case] config: BlockBuilderConfig) {
+    let input_txs: Vec<_> = (0..3).map(account_invoke_tx).collect();
+
+    let (result, output_txs) = build_block(
+        &block_builder_with_config(config),
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs.clone())),
+    )
+    .await;
+
+    assert_eq!(output_txs, input_txs[..2]);
+    assert_eq!(
+        result.unwrap().state_diff.nonces.get(&account_contract().get_instance_address(0)),
+        Some(&Nonce(felt!(2_u8)))
+    );
+}
+
+#[tokio::test]
+async fn build_block_uses_the_current_config() {
+    let block_builder = block_builder_with_config(BlockBuilderConfig::default());
+    block_builder.config.set(BlockBuilderConfig { max_n_transactions: 2, ..Default::default() });
+    let input_txs: Vec<_> = (0..3).map(account_invoke_tx).collect();
+
+    let (_, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs.clone())),
+    )
+    .await;
+
+    assert_eq!(output_txs, input_txs[..2]);
+}
+
+#[tokio::test]
+async fn build_block_executes_txs_concurrently() {
+    let config = BlockBuilderConfig {
+        concurrency_config: ExecutionConcurrencyConfig {
+            enabled: true,
+            // Conflicts between the transactions of the account don't stop the concurrent
+            // execution.
+            max_reexecutions_percentage: usize::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let input_txs: Vec<_> = (0..3).map(account_invoke_tx).collect();
+
+    let (result, output_txs) = build_block(
+        &block_builder_with_config(config),
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs.clone())),
+    )
+    .await;
+
+    assert_eq!(output_txs, input_txs);
+    let artifacts = result.unwrap();
+    assert_eq!(
+        artifacts.state_diff.nonces.get(&account_contract().get_instance_address(0)),
+        Some(&Nonce(felt!(3_u8)))
+    );
+    assert_eq!(artifacts.concurrency_stats.n_concurrent_txs, 3);
+    assert!(!artifacts.concurrency_stats.fell_back_to_sequential);
+}
+
+#[rstest]
+#[case::no_reexecutions(4, 0, false)]
+#[case::at_the_limit(4, 2, false)]
+#[case::above_the_limit(4, 3, true)]
+fn too_many_reexecutions(
+    #[case] n_txs: usize,
+    #[case] n_reexecutions: usize,
+    #[case] expected: bool,
+) {
+    let config =
+        ExecutionConcurrencyConfig { max_reexecutions_percentage: 50, ..Default::default() };
+    assert_eq!(config.too_many_reexecutions(n_txs, n_reexecutions), expected);
+}
+
+#[tokio::test]
+async fn build_block_leaves_out_txs_exceeding_the_execution_time() {
+    let config = BlockBuilderConfig {
+        max_tx_execution_time: tokio::time::Duration::ZERO,
+        ..Default::default()
+    };
+    let input_txs: Vec<_> = (0..2).map(account_invoke_tx).collect();
+
+    let (result, output_txs) = build_block(
+        &block_builder_with_config(config),
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(input_txs.clone())),
+    )
+    .await;
+
+    assert!(output_txs.is_empty());
+    let artifacts = result.unwrap();
+    let rejected_txs: Vec<_> = input_txs
+        .iter()
+        .map(|tx| RejectedTransaction {
+            tx_hash: tx.tx_hash(),
+            address: tx.contract_address(),
+            nonce: tx.nonce(),
+            reason: RejectionReason::ExecutionTimeout,
+        })
+        .collect();
+    assert_eq!(artifacts.rejected_txs, rejected_txs);
+    assert!(artifacts
+        .excluded_txs
+        .iter()
+        .all(|excluded_tx| excluded_tx.reason == TxExclusionReason::ExecutionTimeout));
+    assert!(artifacts.state_diff.nonces.is_empty());
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_rejects_txs_with_invalid_nonces(block_builder: BlockBuilder) {
+    let tx = account_invoke_tx(1);
+
+    let (result, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(vec![tx.clone()])),
+    )
+    .await;
+
+    assert!(output_txs.is_empty());
+    assert_eq!(
+        result.unwrap().rejected_txs,
+        vec![RejectedTransaction {
+            tx_hash: tx.tx_hash(),
+            address: tx.contract_address(),
+            nonce: tx.nonce(),
+            reason: RejectionReason::InvalidNonce,
+        }]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_stops_at_deadline(block_builder: BlockBuilder) {
+    let tx_stream =
+        futures::stream::iter(vec![account_invoke_tx(0)]).chain(futures::stream::pending());
+
+    let (result, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + tokio::time::Duration::from_millis(100),
+        Box::pin(tx_stream),
+    )
+    .await;
+
+    assert_eq!(output_txs, vec![account_invoke_tx(0)]);
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn build_block_stops_when_the_clock_reaches_the_deadline() {
+    let clock = Arc::new(FakeClock::default());
+    let block_builder = create_block_builder(
+        BlockBuilderConfig::default(),
+        SharedL2GasPrice::default(),
+        None,
+        clock.clone(),
+    );
+    let tx_stream =
+        futures::stream::iter(vec![account_invoke_tx(0)]).chain(futures::stream::pending());
+    let mut build =
+        Box::pin(build_block(&block_builder, clock.now() + BUILD_TIMEOUT, Box::pin(tx_stream)));
+
+    // The block stays open until the clock reaches the deadline, regardless of the time passed.
+    assert!(futures::poll!(&mut build).is_pending());
+    clock.advance(BUILD_TIMEOUT - tokio::time::Duration::from_millis(1));
+    assert!(futures::poll!(&mut build).is_pending());
+    clock.advance(tokio::time::Duration::from_millis(1));
+
+    let (result, output_txs) = build.await;
+    assert_eq!(output_txs, vec![account_invoke_tx(0)]);
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn build_block_seals_an_empty_block_after_the_minimal_wait() {
+    let clock = Arc::new(FakeClock::default());
+    let min_empty_block_wait = tokio::time::Duration::from_millis(500);
+    let block_builder = create_block_builder(
+        BlockBuilderConfig {
+            seal_empty_blocks_early: true,
+            min_empty_block_wait,
+            ..Default::default()
+        },
+        SharedL2GasPrice::default(),
+        None,
+        clock.clone(),
+    );
+    let mut build = Box::pin(build_block(
+        &block_builder,
+        clock.now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::pending::<Transaction>()),
+    ));
+
+    assert!(futures::poll!(&mut build).is_pending());
+    clock.advance(min_empty_block_wait);
+
+    let (result, output_txs) = build.await;
+    assert!(output_txs.is_empty());
+    assert!(result.unwrap().executed_txs.is_empty());
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_fails_on_closed_output_stream(block_builder: BlockBuilder) {
+    let (output_content_sender, output_content_receiver) = tokio::sync::mpsc::channel(1);
+    drop(output_content_receiver);
+
+    let result = block_builder
+        .build_block(
+            test_block_info(),
+            tokio::time::Instant::now() + BUILD_TIMEOUT,
+            Box::pin(futures::stream::iter(vec![account_invoke_tx(0)])),
+            output_content_sender,
+        )
+        .await;
+
+    assert_matches!(result, Err(BlockBuilderError::OutputStreamClosed));
+}
+
+#[rstest]
+#[tokio::test]
+async fn build_block_skips_txs_with_invalid_resource_bounds(block_builder: BlockBuilder) {
+    let valid_tx = account_invoke_v3_tx(
+        0,
+        ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: MIN_GAS_PRICE.get() },
+    );
+    let zero_bounds_tx = account_invoke_v3_tx(1, ResourceBounds::default());
+
+    let (result, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(vec![valid_tx.clone(), zero_bounds_tx.clone()])),
+    )
+    .await;
+
+    assert_eq!(output_txs, vec![valid_tx]);
+    let artifacts = result.unwrap();
+    assert_matches!(
+        artifacts.excluded_txs.as_slice(),
+        [ExcludedTransaction { tx_hash, reason: TxExclusionReason::InvalidResourceBounds(_) }]
+            if *tx_hash == zero_bounds_tx.tx_hash()
+    );
+    // Such transactions may be valid at later gas prices.
+    assert!(artifacts.rejected_txs.is_empty());
+}
+
+#[tokio::test]
+async fn build_block_skips_txs_exceeding_the_max_fee() {
+    let max_fee = u128::from(MAX_L1_GAS_AMOUNT) * MIN_GAS_PRICE.get();
+    let block_builder = block_builder_with_config(BlockBuilderConfig::default())
+        .with_fee_config(FeeConfig { max_fee: Some(max_fee), ..Default::default() });
+    let valid_tx = account_invoke_v3_tx(
+        0,
+        ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: MIN_GAS_PRICE.get() },
+    );
+    let expensive_tx = account_invoke_v3_tx(
+        1,
+        ResourceBounds {
+            max_amount: MAX_L1_GAS_AMOUNT,
+            max_price_per_unit: 2 * MIN_GAS_PRICE.get(),
+        },
+    );
+
+    let (result, output_txs) = build_block(
+        &block_builder,
+        tokio::time::Instant::now() + BUILD_TIMEOUT,
+        Box::pin(futures::stream::iter(vec![valid_tx.clone(), expensive_tx.clone()])),
+    )
+    .await;
+
+    assert_eq!(output_txs, vec![valid_tx]);
+    assert_matches!(
+        result.unwrap().excluded_txs.as_slice(),
+        [ExcludedTransaction { tx_hash, reason: TxExclusionReason::InvalidResourceBounds(_) }]
+            if *tx_hash == expensive_tx.tx_hash()
+    );
+}
+
+#[tokio::test]
+async fn proposed_header_is_raised_to_the_minimal_gas_prices() {
+    let min_l1_gas_price = NonZeroU128::new(50).unwrap();
+    let min_l2_gas_price = NonZeroU128::new(70).unwrap();
+    let block_builder = block_builder_with_config(BlockBuilderConfig::default())
+        .with_fee_config(FeeConfig { min_l1_gas_price, min_l2_gas_price, ..Default::default() });
+
+    let header = block_builder
+        .proposed_header(BLOCK_NUMBER, test_block_info(), &BlockBuilderConfig::default())
+        .await;
+
+    assert_eq!(header.l1_gas_price.price_in_wei, GasPrice(min_l1_gas_price.get()));
+    assert_eq!(header.l2_gas_price.price_in_fri, GasPrice(min_l2_gas_price.get()));
+}
+
+#[rstest]
+#[case::without_rate(None, GasPrice(30), GasPrice(70), GasPrice(70))]
+#[case::with_rate(
+    NonZeroU128::new(100),
+    GasPrice(3000),
+    GasPrice(70),
+    GasPrice(MIN_GAS_PRICE.get())
+)]
+#[tokio::test]
+async fn proposed_header_converts_the_gas_prices_by_the_strk_per_eth_rate(
+    #[case] strk_per_eth_rate: Option<NonZeroU128>,
+    #[case] expected_l1_gas_price_in_fri: GasPrice,
+    #[case] expected_l2_gas_price_in_fri: GasPrice,
+    #[case] expected_l2_gas_price_in_wei: GasPrice,
+) {
+    let mut l1_gas_price_provider_client = MockL1GasPriceProviderClient::new();
+    l1_gas_price_provider_client
+        .expect_get_price_info()
+        .return_once(|_| Ok(PriceInfo { base_fee_per_gas: 30, blob_fee: 0 }));
+    let block_builder = create_block_builder(
+        BlockBuilderConfig::default(),
+        SharedL2GasPrice::new(NonZeroU128::new(70).unwrap()),
+        Some(Arc::new(l1_gas_price_provider_client)),
+        Arc::new(TokioClock),
+    );
+    let block_builder = match strk_per_eth_rate {
+        Some(strk_per_eth_rate) => block_builder.with_strk_per_eth_rate(strk_per_eth_rate),
+        None => block_builder,
+    };
+
+    let header = block_builder
+        .proposed_header(BLOCK_NUMBER, test_block_info(), &BlockBuilderConfig::default())
+        .await;
+
+    assert_eq!(header.l1_gas_price.price_in_wei, GasPrice(30));
+    assert_eq!(header.l1_gas_price.price_in_fri, expected_l1_gas_price_in_fri);
+    assert_eq!(header.l2_gas_price.price_in_fri, expected_l2_gas_price_in_fri);
+    // An L2 gas price in fri below the rate is raised to the minimal price in wei.
+    assert_eq!(header.l2_gas_price.price_in_wei, expected_l2_gas_price_in_wei);
+}
+
+// The fee market of the batcher sets the L2 gas price between blocks.
+#[tokio::test]
+async fn proposed_header_follows_the_l2_gas_price() {
+    let l2_gas_price = SharedL2GasPrice::default();
+    let block_builder = create_block_builder(
+        BlockBuilderConfig::default(),
+        l2_gas_price.clone(),
+        None,
+        Arc::new(TokioClock),
+    );
+    let next_price = NonZeroU128::new(3 * MIN_GAS_PRICE.get()).unwrap();
+
+    l2_gas_price.set(next_price);
+
+    let header = block_builder
+        .proposed_header(BLOCK_NUMBER, test_block_info(), &BlockBuilderConfig::default())
+        .await;
+    assert_eq!(header.l2_gas_price.price_in_fri, GasPrice(next_price.get()));
+    let block_context = block_builder.block_context(BLOCK_NUMBER, &header, BouncerConfig::max());
+    assert_eq!(
+        block_context.block_info().gas_prices.get_l2_gas_price_by_fee_type(&FeeType::Strk),
+        next_price
+    );
+}
+
+// Zero prices, e.g., of base layer blocks before EIP-4844, are raised to the minimal gas price.
+#[rstest]
+#[case::provided(
+    Ok(PriceInfo { base_fee_per_gas: 30, blob_fee: 0 }),
+    GasPrice(30),
+    GasPrice(MIN_GAS_PRICE.get())
+)]
+#[case::provider_failure(
+    Err(L1GasPriceProviderClientError::ClientError(ClientError::CircuitOpen)),
+    GasPrice(MIN_GAS_PRICE.get()),
+    GasPrice(MIN_GAS_PRICE.get())
+)]
+#[tokio::test]
+async fn proposed_header_takes_the_l1_gas_prices_from_the_provider(
+    #[case] price_info: L1GasPriceProviderClientResult<PriceInfo>,
+    #[case] expected_l1_gas_price: GasPrice,
+    #[case] expected_l1_data_gas_price: GasPrice,
+) {
+    let mut l1_gas_price_provider_client = MockL1GasPriceProviderClient::new();
+    l1_gas_price_provider_client.expect_get_price_info().times(1).return_once(|_| price_info);
+    let block_builder = create_block_builder(
+        BlockBuilderConfig::default(),
+        SharedL2GasPrice::default(),
+        Some(Arc::new(l1_gas_price_provider_client)),
+        Arc::new(TokioClock),
+    );
+
+    let header = block_builder
+        .proposed_header(BLOCK_NUMBER, test_block_info(), &BlockBuilderConfig::default())
+        .await;
+
+    assert_eq!(header.l1_gas_price.price_in_wei, expected_l1_gas_price);
+    assert_eq!(header.l1_data_gas_price.price_in_wei, expected_l1_data_gas_price);
+}
+
+#[rstest]
+#[case::before_upgrade(BlockNumber(0), "0.13.2")]
+#[case::at_upgrade(BLOCK_NUMBER, LATEST_STARKNET_VERSION)]
+#[case::after_upgrade(BLOCK_NUMBER.unchecked_next(), LATEST_STARKNET_VERSION)]
+#[tokio::test]
+async fn proposed_header_takes_the_protocol_version_of_the_height(
+    #[case] block_number: BlockNumber,
+    #[case] expected_version: &str,
+) {
+    let config = BlockBuilderConfig {
+        protocol_versions: BTreeMap::from([
+            (BlockNumber(0), StarknetVersion("0.13.2".to_string())),
+            (BLOCK_NUMBER, StarknetVersion(LATEST_STARKNET_VERSION.to_string())),
+        ]),
+        ..Default::default()
+    };
+    let block_builder = create_block_builder(
+        config.clone(),
+        SharedL2GasPrice::default(),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let header = block_builder.proposed_header(block_number, test_block_info(), &config).await;
+
+    assert_eq!(header.starknet_version, StarknetVersion(expected_version.to_string()));
+}
+
+// The gateway admits transactions with the same resource bounds checks the block builder runs
+// before execution. The checks must accept exactly the transactions the execution charges a fee for
+// (with enough L1 gas for the transaction): the execution itself skips the fee for transactions
+// with zero L1 bounds, which the checks reject in both stages.
+#[rstest]
+#[case::zero_amount(ResourceBounds { max_amount: 0, max_price_per_unit: MIN_GAS_PRICE.get() })]
+#[case::zero_price(ResourceBounds { max_amount: MAX_L1_GAS_AMOUNT, max_price_per_unit: 0 })]
+#[case::zero_bounds(ResourceBounds::default())]
+#[case::min_gas_price(ResourceBounds {
+    max_amount: MAX_L1_GAS_AMOUNT,
+    max_price_per_unit: MIN_GAS_PRICE.get(),
+})]
+#[case::high_gas_price(ResourceBounds {
+    max_amount: MAX_L1_GAS_AMOUNT,
+    max_price_per_unit: 10 * MIN_GAS_PRICE.get(),
+})]
+#[tokio::test]
+async fn resource_bounds_checks_agree_with_execution(
+    block_builder: BlockBuilder,
+    #[case] l1_bounds: ResourceBounds,
+) {
+    let chain_info = ChainInfo::create_for_testing();
+    let state = test_state(&chain_info, BALANCE, &[(account_contract(), 1), (test_contract(), 1)]);
+    let mut executor = TransactionExecutor::new(
+        state,
+        block_builder.block_context(
+            BLOCK_NUMBER,
+            &block_builder
+                .proposed_header(BLOCK_NUMBER, test_block_info(), &BlockBuilderConfig::default())
+                .await,
+            BouncerConfig::max(),
+        ),
+        TransactionExecutorConfig::default(),
+    );
+    let tx = AccountTransaction::try_from(account_invoke_v3_tx(0, l1_bounds)).unwrap();
+
+    let execution_result = executor.execute(&BlockifierTransaction::AccountTransaction(tx));
+
+    let is_charged =
+        matches!(&execution_result, Ok(execution_info) if execution_info.receipt.fee > Fee(0));
+    assert_eq!(
+        validate_l1_gas_bounds(l1_bounds, MIN_GAS_PRICE).is_ok(),
+        is_charged,
+        "Execution result: {execution_result:?}"
+    );
+}
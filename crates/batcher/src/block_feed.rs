@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use papyrus_config::converters::deserialize_milliseconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockHeader;
+use starknet_api::transaction::TransactionHash;
+use tokio_retry::strategy::ExponentialBackoff;
+use tokio_retry::Retry;
+use tracing::{debug, error};
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "block_feed_test.rs"]
+mod block_feed_test;
+
+/// The configuration of the push feed of committed blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct BlockFeedConfig {
+    // The endpoint the summaries of the committed blocks are posted to.
+    pub url: String,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub request_timeout: Duration,
+    // The waiting time after a failed post, in milliseconds, which increases exponentially.
+    pub retry_base_millis: u64,
+    pub retry_max_delay_millis: u64,
+    pub max_retries: usize,
+}
+
+impl Default for BlockFeedConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8080/blocks".to_string(),
+            request_timeout: Duration::from_millis(1000),
+            retry_base_millis: 50,
+            retry_max_delay_millis: 1000,
+            max_retries: 5,
+        }
+    }
+}
+
+impl SerializeConfig for BlockFeedConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "url",
+                &self.url,
+                "The endpoint the summaries of the committed blocks are posted to.",
+                ParamPrivacyInput::Private,
+            ),
+            ser_param(
+                "request_timeout",
+                &u64::try_from(self.request_timeout.as_millis()).expect("Timeout should fit u64."),
+                "The timeout of a single post of a block summary, in milliseconds.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "retry_base_millis",
+                &self.retry_base_millis,
+                "Base waiting time after a failed post. After that, the time increases \
+                 exponentially.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "retry_max_delay_millis",
+                &self.retry_max_delay_millis,
+                "Max waiting time after a failed post.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_retries",
+                &self.max_retries,
+                "Maximum number of retries of a post before the block summary is dropped.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// A compact summary of a committed block, as posted to the feed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockSummary {
+    pub header: BlockHeader,
+    pub tx_hashes: Vec<TransactionHash>,
+    // The number of events emitted by the transactions of the block.
+    pub n_events: usize,
+}
+
+/// Posts the summaries of the committed blocks to the configured endpoint, so that consumers such
+/// as block explorers and indexers are notified without polling the storage.
+pub struct BlockFeedPublisher {
+    config: BlockFeedConfig,
+    client: reqwest::Client,
+}
+
+impl BlockFeedPublisher {
+    pub fn new(config: BlockFeedConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .build()
+            .expect("Failed to build the block feed client.");
+        Self { config, client }
+    }
+
+    /// Posts the summary in the background, retrying on failure. The block is already committed,
+    /// so a failure to post it is only logged.
+    pub fn publish(&self, summary: BlockSummary) -> tokio::task::JoinHandle<()> {
+        let client = self.client.clone();
+        let url = self.config.url.clone();
+        let strategy = ExponentialBackoff::from_millis(self.config.retry_base_millis)
+            .max_delay(Duration::from_millis(self.config.retry_max_delay_millis))
+            .take(self.config.max_retries);
+        tokio::spawn(async move {
+            let block_number = summary.header.block_number;
+            let result = Retry::spawn(strategy, || async {
+                client
+                    .post(&url)
+                    .json(&summary)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .inspect_err(|err| debug!("Failed to post block {}: {}", block_number, err))
+            })
+            .await;
+            if let Err(err) = result {
+                error!("Failed to post block {} to the block feed: {}", block_number, err);
+            }
+        })
+    }
+}
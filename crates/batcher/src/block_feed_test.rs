@@ -0,0 +1,61 @@
+use starknet_api::block::{BlockHeader, BlockNumber};
+use starknet_api::felt;
+use starknet_api::transaction::TransactionHash;
+
+use crate::block_feed::{BlockFeedConfig, BlockFeedPublisher, BlockSummary};
+
+const MAX_RETRIES: usize = 2;
+
+fn block_summary() -> BlockSummary {
+    BlockSummary {
+        header: BlockHeader {
+            block_number: BlockNumber(1),
+            n_transactions: 2,
+            ..Default::default()
+        },
+        tx_hashes: vec![TransactionHash(felt!(1_u8)), TransactionHash(felt!(2_u8))],
+        n_events: 3,
+    }
+}
+
+fn publisher(server: &mockito::ServerGuard) -> BlockFeedPublisher {
+    BlockFeedPublisher::new(BlockFeedConfig {
+        url: format!("{}/blocks", server.url()),
+        retry_base_millis: 1,
+        retry_max_delay_millis: 1,
+        max_retries: MAX_RETRIES,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn block_summary_is_posted() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/blocks")
+        .match_header("Content-Type", "application/json")
+        .match_body(mockito::Matcher::Json(serde_json::to_value(block_summary()).unwrap()))
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    publisher(&server).publish(block_summary()).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn failed_post_is_retried() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/blocks")
+        .with_status(500)
+        .expect(1 + MAX_RETRIES)
+        .create_async()
+        .await;
+
+    publisher(&server).publish(block_summary()).await.unwrap();
+
+    mock.assert_async().await;
+}
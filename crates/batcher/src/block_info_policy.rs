@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_api::core::ContractAddress;
+use starknet_batcher_types::batcher_types::{BlockInfoValidationError, ProposalBlockInfo};
+use tracing::warn;
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "block_info_policy_test.rs"]
+mod block_info_policy_test;
+
+const DEFAULT_MAX_TIMESTAMP_DRIFT: Duration = Duration::from_secs(15);
+
+/// How the timestamp of a generated proposal is chosen.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TimestampStrategy {
+    /// The wall clock of the proposer, never earlier than the timestamp of the previous block.
+    #[default]
+    WallClock,
+    /// The timestamp supplied by consensus along with the request; the wall clock is used if
+    /// consensus doesn't supply one.
+    Consensus,
+}
+
+/// The policy the timestamps of the generated proposals are chosen by, and the timestamps of the
+/// validated proposals are checked by.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct BlockInfoPolicyConfig {
+    pub timestamp_strategy: TimestampStrategy,
+    // Validated proposals with timestamps later than the wall clock by more than this are
+    // rejected, bounding the clock skew between the proposer and the validator.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub max_timestamp_drift: Duration,
+}
+
+impl Default for BlockInfoPolicyConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_strategy: TimestampStrategy::default(),
+            max_timestamp_drift: DEFAULT_MAX_TIMESTAMP_DRIFT,
+        }
+    }
+}
+
+impl SerializeConfig for BlockInfoPolicyConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "timestamp_strategy",
+                &self.timestamp_strategy,
+                "How the timestamp of a generated proposal is chosen: 'WallClock' takes the wall \
+                 clock, never earlier than the previous block, and 'Consensus' takes the \
+                 timestamp supplied by consensus",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_timestamp_drift",
+                &self.max_timestamp_drift.as_secs(),
+                "The maximal time, in seconds, the timestamp of a validated proposal may be ahead \
+                 of the wall clock",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Chooses the block info of the generated proposals, and checks the block info of the validated
+/// ones, relative to the previous block.
+#[derive(Debug)]
+pub struct BlockInfoPolicy {
+    config: BlockInfoPolicyConfig,
+    // The address the fees of the generated proposals are paid to.
+    sequencer_address: ContractAddress,
+    // The timestamp of the latest committed block, unknown until a block is committed.
+    previous_timestamp: Option<BlockTimestamp>,
+}
+
+impl BlockInfoPolicy {
+    pub fn new(config: BlockInfoPolicyConfig, sequencer_address: ContractAddress) -> Self {
+        Self { config, sequencer_address, previous_timestamp: None }
+    }
+
+    /// The block info of a proposal generated at the given wall clock time, with the timestamp
+    /// supplied by consensus, if any.
+    pub fn proposed_block_info(
+        &self,
+        consensus_timestamp: Option<BlockTimestamp>,
+        now: BlockTimestamp,
+    ) -> ProposalBlockInfo {
+        let timestamp = match (self.config.timestamp_strategy, consensus_timestamp) {
+            (TimestampStrategy::Consensus, Some(timestamp)) => timestamp,
+            (TimestampStrategy::Consensus, None) => {
+                warn!("Consensus supplied no timestamp for the proposal, using the wall clock.");
+                self.wall_clock_timestamp(now)
+            }
+            (TimestampStrategy::WallClock, _) => self.wall_clock_timestamp(now),
+        };
+        ProposalBlockInfo { timestamp, sequencer_address: self.sequencer_address }
+    }
+
+    /// Checks the block info of a proposal validated at the given wall clock time.
+    pub fn validate(
+        &self,
+        block_info: &ProposalBlockInfo,
+        now: BlockTimestamp,
+    ) -> Result<(), BlockInfoValidationError> {
+        let timestamp = block_info.timestamp;
+        if let Some(previous_timestamp) = self.previous_timestamp {
+            if timestamp < previous_timestamp {
+                return Err(BlockInfoValidationError::TimestampBeforePreviousBlock {
+                    timestamp,
+                    previous_timestamp,
+                });
+            }
+        }
+        let max_timestamp =
+            BlockTimestamp(now.0.saturating_add(self.config.max_timestamp_drift.as_secs()));
+        if timestamp > max_timestamp {
+            return Err(BlockInfoValidationError::TimestampTooFarInFuture {
+                timestamp,
+                max_timestamp,
+            });
+        }
+        if block_info.sequencer_address == ContractAddress::default() {
+            return Err(BlockInfoValidationError::MissingSequencerAddress);
+        }
+        Ok(())
+    }
+
+    /// Records the timestamp of a committed block, which the timestamps of the next block are
+    /// checked against.
+    pub fn record_block(&mut self, timestamp: BlockTimestamp) {
+        self.previous_timestamp = Some(timestamp);
+    }
+
+    // Keeps the timestamps monotonic when the wall clock is behind the previous block, e.g., after
+    // the clock was set back.
+    fn wall_clock_timestamp(&self, now: BlockTimestamp) -> BlockTimestamp {
+        match self.previous_timestamp {
+            Some(previous_timestamp) => now.max(previous_timestamp),
+            None => now,
+        }
+    }
+}
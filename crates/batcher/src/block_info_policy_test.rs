@@ -0,0 +1,86 @@
+use assert_matches::assert_matches;
+use rstest::rstest;
+use starknet_api::block::BlockTimestamp;
+use starknet_api::contract_address;
+use starknet_api::core::ContractAddress;
+use starknet_batcher_types::batcher_types::{BlockInfoValidationError, ProposalBlockInfo};
+
+use crate::block_info_policy::{BlockInfoPolicy, BlockInfoPolicyConfig, TimestampStrategy};
+
+const PREVIOUS_TIMESTAMP: BlockTimestamp = BlockTimestamp(1000);
+
+fn policy(timestamp_strategy: TimestampStrategy) -> BlockInfoPolicy {
+    let config = BlockInfoPolicyConfig { timestamp_strategy, ..Default::default() };
+    let mut policy = BlockInfoPolicy::new(config, contract_address!("0x7"));
+    policy.record_block(PREVIOUS_TIMESTAMP);
+    policy
+}
+
+fn block_info(timestamp: u64) -> ProposalBlockInfo {
+    ProposalBlockInfo {
+        timestamp: BlockTimestamp(timestamp),
+        sequencer_address: contract_address!("0x8"),
+    }
+}
+
+#[rstest]
+#[case::clock_ahead(1010, 1010)]
+#[case::clock_behind_previous_block(990, 1000)]
+fn wall_clock_timestamp_is_monotonic(#[case] now: u64, #[case] expected_timestamp: u64) {
+    let block_info = policy(TimestampStrategy::WallClock)
+        .proposed_block_info(Some(BlockTimestamp(2000)), BlockTimestamp(now));
+
+    assert_eq!(block_info.timestamp, BlockTimestamp(expected_timestamp));
+    assert_eq!(block_info.sequencer_address, contract_address!("0x7"));
+}
+
+#[rstest]
+#[case::supplied(Some(BlockTimestamp(1005)), 1005)]
+#[case::missing(None, 1010)]
+fn consensus_timestamp_is_proposed(
+    #[case] consensus_timestamp: Option<BlockTimestamp>,
+    #[case] expected_timestamp: u64,
+) {
+    let block_info = policy(TimestampStrategy::Consensus)
+        .proposed_block_info(consensus_timestamp, BlockTimestamp(1010));
+
+    assert_eq!(block_info.timestamp, BlockTimestamp(expected_timestamp));
+}
+
+#[test]
+fn valid_block_info_is_accepted() {
+    let policy = policy(TimestampStrategy::WallClock);
+
+    assert_eq!(policy.validate(&block_info(1000), BlockTimestamp(990)), Ok(()));
+}
+
+#[test]
+fn invalid_block_info_is_rejected() {
+    let policy = policy(TimestampStrategy::WallClock);
+    let now = BlockTimestamp(1010);
+
+    assert_matches!(
+        policy.validate(&block_info(999), now),
+        Err(BlockInfoValidationError::TimestampBeforePreviousBlock {
+            previous_timestamp: PREVIOUS_TIMESTAMP,
+            ..
+        })
+    );
+    assert_matches!(
+        policy.validate(&block_info(1026), now),
+        Err(BlockInfoValidationError::TimestampTooFarInFuture {
+            max_timestamp: BlockTimestamp(1025),
+            ..
+        })
+    );
+    assert_matches!(
+        policy.validate(
+            &ProposalBlockInfo {
+                sequencer_address: ContractAddress::default(),
+                ..block_info(1005)
+            },
+            now
+        ),
+        Err(BlockInfoValidationError::MissingSequencerAddress)
+    );
+}
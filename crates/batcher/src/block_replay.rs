@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starknet_api::block::BlockNumber;
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::TransactionHash;
+use thiserror::Error;
+use tracing::{error, info};
+use validator::Validate;
+
+use crate::block_builder::{BlockExecutionArtifacts, ProposedBlockHeader};
+
+#[cfg(test)]
+#[path = "block_replay_test.rs"]
+mod block_replay_test;
+
+/// The configuration of the recording of the inputs of the built blocks, for replaying them
+/// offline.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct BlockReplayConfig {
+    pub directory: PathBuf,
+}
+
+impl Default for BlockReplayConfig {
+    fn default() -> Self {
+        Self { directory: PathBuf::from("block_replays") }
+    }
+}
+
+impl SerializeConfig for BlockReplayConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "directory",
+            &self.directory,
+            "The directory the replay files of the built blocks are written to.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// The inputs a block was built from, enough to execute it again and get the same state diff: the
+/// header it was executed with, and the transactions that were added to it, in execution order.
+/// The transactions that were left out of the block are not recorded, as they didn't change the
+/// state.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockReplayRecord {
+    pub block_number: BlockNumber,
+    pub header: ProposedBlockHeader,
+    pub txs: Vec<Transaction>,
+    /// The state diff the block was built with, which a replay is checked against.
+    pub state_diff: ThinStateDiff,
+}
+
+/// How a replayed block differs from the recorded one.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ReplayDivergence {
+    #[error("The replayed block has transactions {replayed:?}, instead of {recorded:?}.")]
+    TxHashes { recorded: Vec<TransactionHash>, replayed: Vec<TransactionHash> },
+    #[error("The replayed block has a different state diff than the recorded one.")]
+    StateDiff,
+}
+
+impl BlockReplayRecord {
+    pub fn new(block_number: BlockNumber, artifacts: &BlockExecutionArtifacts) -> Self {
+        Self {
+            block_number,
+            header: artifacts.header.clone(),
+            txs: artifacts.txs.clone(),
+            state_diff: artifacts.state_diff.clone(),
+        }
+    }
+
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Checks the artifacts of a replay of the block against the recorded block.
+    pub fn check(&self, artifacts: &BlockExecutionArtifacts) -> Result<(), ReplayDivergence> {
+        let recorded: Vec<_> = self.txs.iter().map(Transaction::tx_hash).collect();
+        let replayed = artifacts.tx_hashes();
+        if recorded != replayed {
+            return Err(ReplayDivergence::TxHashes { recorded, replayed });
+        }
+        if self.state_diff != artifacts.state_diff {
+            return Err(ReplayDivergence::StateDiff);
+        }
+        Ok(())
+    }
+
+    // Named by the block number and a hash of the content, so that the different blocks built for
+    // a height, e.g., by different rounds, get different files.
+    fn file_name(&self, content: &[u8]) -> String {
+        let hash = Sha256::digest(content);
+        let hash_prefix: String = hash[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+        format!("block_{}_{}.json", self.block_number, hash_prefix)
+    }
+}
+
+/// Writes the replay records of the built blocks to files of a directory.
+#[derive(Clone, Debug)]
+pub struct BlockReplayRecorder {
+    directory: PathBuf,
+}
+
+impl BlockReplayRecorder {
+    /// Creates the directory of the replay files, if missing.
+    pub fn new(config: &BlockReplayConfig) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&config.directory)?;
+        Ok(Self { directory: config.directory.clone() })
+    }
+
+    // A block is built regardless of its recording; a failed write is only logged.
+    pub fn record(&self, record: &BlockReplayRecord) {
+        match self.write(record) {
+            Ok(path) => info!("Recorded block {} for replay at {:?}.", record.block_number, path),
+            Err(err) => {
+                error!("Failed to record block {} for replay: {}", record.block_number, err)
+            }
+        }
+    }
+
+    fn write(&self, record: &BlockReplayRecord) -> std::io::Result<PathBuf> {
+        let content = serde_json::to_vec(record)?;
+        let path = self.directory.join(record.file_name(&content));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writer.write_all(&content)?;
+        writer.flush()?;
+        Ok(path)
+    }
+}
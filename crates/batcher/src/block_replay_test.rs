@@ -0,0 +1,82 @@
+use assert_matches::assert_matches;
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::felt;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
+
+use crate::block_builder::{BlockExecutionArtifacts, ExecutedTransaction, ProposedBlockHeader};
+use crate::block_replay::{
+    BlockReplayConfig,
+    BlockReplayRecord,
+    BlockReplayRecorder,
+    ReplayDivergence,
+};
+
+fn tx(tx_hash: u64) -> Transaction {
+    Transaction::Invoke(InvokeTransaction {
+        tx: invoke_tx(InvokeTxArgs::default()),
+        tx_hash: TransactionHash(felt!(tx_hash)),
+    })
+}
+
+fn state_diff(nonce: u64) -> ThinStateDiff {
+    ThinStateDiff {
+        nonces: [(ContractAddress::default(), Nonce(felt!(nonce)))].into_iter().collect(),
+        ..Default::default()
+    }
+}
+
+fn record() -> BlockReplayRecord {
+    BlockReplayRecord {
+        block_number: BlockNumber(3),
+        header: ProposedBlockHeader { timestamp: BlockTimestamp(1000), ..Default::default() },
+        txs: vec![tx(1), tx(2)],
+        state_diff: state_diff(2),
+    }
+}
+
+fn artifacts(tx_hashes: &[u64], state_diff: ThinStateDiff) -> BlockExecutionArtifacts {
+    BlockExecutionArtifacts {
+        executed_txs: tx_hashes
+            .iter()
+            .map(|tx_hash| ExecutedTransaction {
+                tx_hash: TransactionHash(felt!(*tx_hash)),
+                ..Default::default()
+            })
+            .collect(),
+        state_diff,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn recorded_block_is_read_back() {
+    let dir = tempfile::tempdir().unwrap();
+    let directory = dir.path().join("replays");
+    let recorder =
+        BlockReplayRecorder::new(&BlockReplayConfig { directory: directory.clone() }).unwrap();
+
+    recorder.record(&record());
+
+    let paths: Vec<_> =
+        std::fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().path()).collect();
+    assert_eq!(paths.len(), 1);
+    let file_name = paths[0].file_name().unwrap().to_str().unwrap();
+    assert!(file_name.starts_with("block_3_"), "Unexpected replay file name: {file_name}");
+    assert_eq!(BlockReplayRecord::read(&paths[0]).unwrap(), record());
+}
+
+#[test]
+fn check_reports_the_divergence_of_a_replay() {
+    let record = record();
+
+    assert_eq!(record.check(&artifacts(&[1, 2], state_diff(2))), Ok(()));
+    assert_matches!(
+        record.check(&artifacts(&[1], state_diff(1))),
+        Err(ReplayDivergence::TxHashes { .. })
+    );
+    assert_eq!(record.check(&artifacts(&[1, 2], state_diff(3))), Err(ReplayDivergence::StateDiff));
+}
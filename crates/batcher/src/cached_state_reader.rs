@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use cached::{Cached, SizedCache};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+use validator::Validate;
+
+use crate::block_builder::{BatcherStateReader, StateReaderFactory};
+use crate::metrics::record_state_cache_lookup;
+
+#[cfg(test)]
+#[path = "cached_state_reader_test.rs"]
+mod cached_state_reader_test;
+
+/// The version of the compiled classes kept by the state caches. Bumped whenever the classes are
+/// compiled differently, e.g., on upgrading the compiler, as it may change their execution.
+pub const COMPILED_CLASS_CACHE_VERSION: u32 = 1;
+
+/// The caches of the state the blocks are executed on, shared by the generated and the validated
+/// proposals.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct StateCacheConfig {
+    pub enabled: bool,
+    #[validate(range(min = 1))]
+    pub storage_cache_size: usize,
+    #[validate(range(min = 1))]
+    pub nonce_cache_size: usize,
+    // The size of the caches of the class hashes of contracts, and of the compiled class hashes of
+    // classes.
+    #[validate(range(min = 1))]
+    pub class_hash_cache_size: usize,
+    #[validate(range(min = 1))]
+    pub compiled_class_cache_size: usize,
+}
+
+impl Default for StateCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            storage_cache_size: 100_000,
+            nonce_cache_size: 10_000,
+            class_hash_cache_size: 10_000,
+            compiled_class_cache_size: 500,
+        }
+    }
+}
+
+impl SerializeConfig for StateCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, the state reads of the block builder are cached across the proposals of \
+                 a height.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "storage_cache_size",
+                &self.storage_cache_size,
+                "The maximal number of contract storage values kept in the state cache.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "nonce_cache_size",
+                &self.nonce_cache_size,
+                "The maximal number of contract nonces kept in the state cache.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "class_hash_cache_size",
+                &self.class_hash_cache_size,
+                "The maximal number of contract class hashes, and of compiled class hashes, kept \
+                 in the state cache.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "compiled_class_cache_size",
+                &self.compiled_class_cache_size,
+                "The maximal number of compiled classes kept in the state cache.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+struct StateCaches {
+    // The block whose starting state the caches hold. Unset until the first reader is created.
+    block_number: Option<BlockNumber>,
+    storage: SizedCache<(ContractAddress, StorageKey), Felt>,
+    nonces: SizedCache<ContractAddress, Nonce>,
+    class_hashes: SizedCache<ContractAddress, ClassHash>,
+    compiled_class_hashes: SizedCache<ClassHash, CompiledClassHash>,
+    // A compiled class doesn't change once declared, so the compiled classes are kept across
+    // blocks.
+    compiled_classes: SizedCache<ClassHash, ContractClass>,
+}
+
+impl StateCaches {
+    fn new(config: &StateCacheConfig) -> Self {
+        Self {
+            block_number: None,
+            storage: SizedCache::with_size(config.storage_cache_size),
+            nonces: SizedCache::with_size(config.nonce_cache_size),
+            class_hashes: SizedCache::with_size(config.class_hash_cache_size),
+            compiled_class_hashes: SizedCache::with_size(config.class_hash_cache_size),
+            compiled_classes: SizedCache::with_size(config.compiled_class_cache_size),
+        }
+    }
+
+    // Clears the caches of the state of the previous block once the storage advanced, i.e., once
+    // a block was committed.
+    fn start_block(&mut self, block_number: BlockNumber) {
+        if self.block_number == Some(block_number) {
+            return;
+        }
+        self.block_number = Some(block_number);
+        self.storage.cache_clear();
+        self.nonces.cache_clear();
+        self.class_hashes.cache_clear();
+        self.compiled_class_hashes.cache_clear();
+    }
+}
+
+/// Creates state readers that serve repeated reads of the state of a block from caches shared by
+/// all the readers of the block, e.g., of a generated proposal and of the validated proposals of
+/// later rounds.
+pub struct CachingStateReaderFactory {
+    state_reader_factory: Arc<dyn StateReaderFactory>,
+    caches: Arc<Mutex<StateCaches>>,
+}
+
+impl CachingStateReaderFactory {
+    pub fn new(
+        state_reader_factory: Arc<dyn StateReaderFactory>,
+        config: &StateCacheConfig,
+    ) -> Self {
+        Self { state_reader_factory, caches: Arc::new(Mutex::new(StateCaches::new(config))) }
+    }
+}
+
+impl StateReaderFactory for CachingStateReaderFactory {
+    fn get_state_reader_for_next_block(
+        &self,
+    ) -> StateResult<(BlockNumber, Box<dyn BatcherStateReader>)> {
+        let (block_number, state_reader) =
+            self.state_reader_factory.get_state_reader_for_next_block()?;
+        lock(&self.caches).start_block(block_number);
+        Ok((
+            block_number,
+            Box::new(CachingStateReader {
+                state_reader,
+                block_number,
+                caches: self.caches.clone(),
+            }),
+        ))
+    }
+}
+
+/// Reads the state of a block through the caches of its factory.
+pub struct CachingStateReader {
+    state_reader: Box<dyn BatcherStateReader>,
+    block_number: BlockNumber,
+    caches: Arc<Mutex<StateCaches>>,
+}
+
+impl CachingStateReader {
+    // Returns the cached value, or reads it and caches it. The caches of a later block, which a
+    // reader still executing a block of an earlier height must not use, are bypassed.
+    fn get_or_read<K, V>(
+        &self,
+        item: &'static str,
+        key: K,
+        cache_of: impl Fn(&mut StateCaches) -> &mut SizedCache<K, V>,
+        read: impl FnOnce() -> StateResult<V>,
+    ) -> StateResult<V>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+    {
+        let is_current_block = {
+            let mut caches = lock(&self.caches);
+            let is_current_block = caches.block_number == Some(self.block_number);
+            if is_current_block {
+                if let Some(value) = cache_of(&mut *caches).cache_get(&key) {
+                    record_state_cache_lookup(item, true);
+                    return Ok(value.clone());
+                }
+            }
+            is_current_block
+        };
+        if !is_current_block {
+            return read();
+        }
+        record_state_cache_lookup(item, false);
+        // The lock isn't held while reading, so that concurrent readers don't wait on the storage.
+        let value = read()?;
+        let mut caches = lock(&self.caches);
+        if caches.block_number == Some(self.block_number) {
+            cache_of(&mut *caches).cache_set(key, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+impl BlockifierStateReader for CachingStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.get_or_read(
+            "storage",
+            (contract_address, key),
+            |caches| &mut caches.storage,
+            || self.state_reader.get_storage_at(contract_address, key),
+        )
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.get_or_read(
+            "nonce",
+            contract_address,
+            |caches| &mut caches.nonces,
+            || self.state_reader.get_nonce_at(contract_address),
+        )
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.get_or_read(
+            "class_hash",
+            contract_address,
+            |caches| &mut caches.class_hashes,
+            || self.state_reader.get_class_hash_at(contract_address),
+        )
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.get_or_read(
+            "compiled_class",
+            class_hash,
+            |caches| &mut caches.compiled_classes,
+            || self.state_reader.get_compiled_contract_class(class_hash),
+        )
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.get_or_read(
+            "compiled_class_hash",
+            class_hash,
+            |caches| &mut caches.compiled_class_hashes,
+            || self.state_reader.get_compiled_class_hash(class_hash),
+        )
+    }
+}
+
+impl BatcherStateReader for CachingStateReader {}
+
+fn lock(caches: &Mutex<StateCaches>) -> MutexGuard<'_, StateCaches> {
+    caches.lock().expect("State cache lock should not be poisoned.")
+}
@@ -0,0 +1,136 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::CairoVersion;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::block_builder::{BatcherStateReader, StateReaderFactory};
+use crate::cached_state_reader::{CachingStateReaderFactory, StateCacheConfig};
+
+// Reads values that tell the block they were read for, and counts the reads.
+struct CountingStateReader {
+    block_number: BlockNumber,
+    n_reads: Arc<AtomicUsize>,
+}
+
+impl CountingStateReader {
+    fn read(&self) -> Felt {
+        self.n_reads.fetch_add(1, Ordering::Relaxed);
+        Felt::from(self.block_number.0)
+    }
+}
+
+impl BlockifierStateReader for CountingStateReader {
+    fn get_storage_at(&self, _: ContractAddress, _: StorageKey) -> StateResult<Felt> {
+        Ok(self.read())
+    }
+
+    fn get_nonce_at(&self, _: ContractAddress) -> StateResult<Nonce> {
+        Ok(Nonce(self.read()))
+    }
+
+    fn get_class_hash_at(&self, _: ContractAddress) -> StateResult<ClassHash> {
+        Ok(ClassHash(self.read()))
+    }
+
+    fn get_compiled_contract_class(&self, _: ClassHash) -> StateResult<ContractClass> {
+        self.read();
+        Ok(FeatureContract::TestContract(CairoVersion::Cairo0).get_class())
+    }
+
+    fn get_compiled_class_hash(&self, _: ClassHash) -> StateResult<CompiledClassHash> {
+        Ok(CompiledClassHash(self.read()))
+    }
+}
+
+impl BatcherStateReader for CountingStateReader {}
+
+#[derive(Default)]
+struct CountingStateReaderFactory {
+    block_number: Mutex<BlockNumber>,
+    n_reads: Arc<AtomicUsize>,
+}
+
+impl CountingStateReaderFactory {
+    fn commit_block(&self) {
+        let mut block_number = self.block_number.lock().unwrap();
+        *block_number = block_number.unchecked_next();
+    }
+}
+
+impl StateReaderFactory for CountingStateReaderFactory {
+    fn get_state_reader_for_next_block(
+        &self,
+    ) -> StateResult<(BlockNumber, Box<dyn BatcherStateReader>)> {
+        let block_number = *self.block_number.lock().unwrap();
+        Ok((
+            block_number,
+            Box::new(CountingStateReader { block_number, n_reads: self.n_reads.clone() }),
+        ))
+    }
+}
+
+fn caching_factory() -> (Arc<CountingStateReaderFactory>, CachingStateReaderFactory) {
+    let factory = Arc::new(CountingStateReaderFactory::default());
+    let caching_factory =
+        CachingStateReaderFactory::new(factory.clone(), &StateCacheConfig::default());
+    (factory, caching_factory)
+}
+
+fn new_reader(factory: &CachingStateReaderFactory) -> Box<dyn BatcherStateReader> {
+    factory.get_state_reader_for_next_block().unwrap().1
+}
+
+fn read_storage(reader: &dyn BatcherStateReader) -> Felt {
+    reader.get_storage_at(ContractAddress::default(), StorageKey::default()).unwrap()
+}
+
+#[test]
+fn reads_of_a_block_are_shared_by_its_readers() {
+    let (factory, caching_factory) = caching_factory();
+    let proposal_reader = new_reader(&caching_factory);
+    let validation_reader = new_reader(&caching_factory);
+
+    for reader in [&proposal_reader, &validation_reader] {
+        read_storage(reader.as_ref());
+        reader.get_nonce_at(ContractAddress::default()).unwrap();
+        reader.get_class_hash_at(ContractAddress::default()).unwrap();
+    }
+
+    assert_eq!(factory.n_reads.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn caches_are_invalidated_when_a_block_is_committed() {
+    let (factory, caching_factory) = caching_factory();
+    let reader = new_reader(&caching_factory);
+    read_storage(reader.as_ref());
+    reader.get_compiled_contract_class(ClassHash::default()).unwrap();
+
+    factory.commit_block();
+    let next_block_reader = new_reader(&caching_factory);
+
+    assert_eq!(read_storage(next_block_reader.as_ref()), Felt::ONE);
+    // Compiled classes are kept across blocks.
+    next_block_reader.get_compiled_contract_class(ClassHash::default()).unwrap();
+    assert_eq!(factory.n_reads.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn reader_of_an_earlier_block_bypasses_the_caches() {
+    let (factory, caching_factory) = caching_factory();
+    let stale_reader = new_reader(&caching_factory);
+    factory.commit_block();
+    let reader = new_reader(&caching_factory);
+
+    assert_eq!(read_storage(stale_reader.as_ref()), Felt::ZERO);
+    assert_eq!(read_storage(reader.as_ref()), Felt::ONE);
+    assert_eq!(read_storage(stale_reader.as_ref()), Felt::ZERO);
+    assert_eq!(factory.n_reads.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,72 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use starknet_api::block::BlockTimestamp;
+use tokio::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "clock_test.rs"]
+mod clock_test;
+
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The source of time the batcher measures deadlines and timeouts by.
+#[async_trait]
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+
+    /// The current wall clock time, as a block timestamp.
+    fn block_timestamp(&self) -> BlockTimestamp;
+
+    /// Waits until the clock reaches the given instant.
+    async fn sleep_until(&self, deadline: Instant);
+}
+
+/// The clock of the tokio runtime.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioClock;
+
+#[async_trait]
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn block_timestamp(&self) -> BlockTimestamp {
+        BlockTimestamp(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("The system time should be after the UNIX epoch.")
+                .as_secs(),
+        )
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        tokio::time::sleep_until(deadline).await
+    }
+}
+
+/// Awaits the given future until the clock reaches the deadline. Returns None if the deadline was
+/// reached first. As with `tokio::time::timeout_at`, a ready future wins over a passed deadline.
+pub async fn timeout_at<F: Future>(
+    clock: &dyn Clock,
+    deadline: Instant,
+    future: F,
+) -> Option<F::Output> {
+    tokio::select! {
+        biased;
+        output = future => Some(output),
+        _ = clock.sleep_until(deadline) => None,
+    }
+}
+
+/// Awaits the given future for at most `duration` of the clock.
+pub async fn timeout<F: Future>(
+    clock: &dyn Clock,
+    duration: Duration,
+    future: F,
+) -> Option<F::Output> {
+    timeout_at(clock, clock.now() + duration, future).await
+}
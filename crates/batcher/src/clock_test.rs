@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use tokio::time::Duration;
+
+use crate::clock::{timeout, timeout_at, Clock};
+use crate::test_utils::FakeClock;
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[tokio::test]
+async fn timeout_at_resolves_once_the_clock_reaches_the_deadline() {
+    let clock = Arc::new(FakeClock::default());
+    let deadline = clock.now() + TIMEOUT;
+    let mut timeout = Box::pin(timeout_at(clock.as_ref(), deadline, std::future::pending::<()>()));
+
+    assert!(futures::poll!(&mut timeout).is_pending());
+    clock.advance(TIMEOUT);
+    assert_eq!(timeout.await, None);
+}
+
+#[tokio::test]
+async fn ready_future_wins_over_a_passed_deadline() {
+    let clock = FakeClock::default();
+    let deadline = clock.now();
+
+    assert_eq!(timeout_at(&clock, deadline, async { 1 }).await, Some(1));
+    assert_eq!(timeout(&clock, Duration::ZERO, async { 1 }).await, Some(1));
+}
@@ -1,19 +1,29 @@
 use std::net::IpAddr;
 
 use async_trait::async_trait;
+use starknet_batcher_types::batcher_types::{
+    BatcherResult,
+    GetValidationStatusInput,
+    ProposalId,
+    ValidationStatus,
+};
 use starknet_batcher_types::communication::{
     BatcherRequest,
     BatcherRequestAndResponseSender,
     BatcherResponse,
+    VALIDATION_STATUS_POLL_INTERVAL,
 };
-use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
+use starknet_batcher_types::versioning::{VersionedBatcherRequest, VersionedBatcherResponse};
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
 use starknet_mempool_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
 use tokio::sync::mpsc::Receiver;
 
 use crate::batcher::Batcher;
 
 pub type LocalBatcherServer = LocalComponentServer<Batcher, BatcherRequest, BatcherResponse>;
-pub type RemoteBatcherServer = RemoteComponentServer<Batcher, BatcherRequest, BatcherResponse>;
+pub type RemoteBatcherServer =
+    RemoteComponentServer<Batcher, VersionedBatcherRequest, VersionedBatcherResponse>;
 
 pub fn create_local_batcher_server(
     batcher: Batcher,
@@ -26,25 +36,112 @@ pub fn create_remote_batcher_server(
     batcher: Batcher,
     ip_address: IpAddr,
     port: u16,
+    serialization_format: SerializationFormat,
 ) -> RemoteBatcherServer {
     RemoteComponentServer::new(batcher, ip_address, port)
+        .with_serialization_format(serialization_format)
 }
 
 #[async_trait]
 impl ComponentRequestHandler<BatcherRequest, BatcherResponse> for Batcher {
     async fn handle_request(&mut self, request: BatcherRequest) -> BatcherResponse {
         match request {
-            BatcherRequest::BatcherFnOne(_batcher_input) => {
-                // TODO(Tsabary/Yael/Dafna): Invoke a function that returns a
-                // BatcherResult<BatcherFnOneReturnValue>, and return
-                // the BatcherResponse::BatcherFnOneInput accordingly.
-                unimplemented!()
-            }
-            BatcherRequest::BatcherFnTwo(_batcher_input) => {
-                // TODO(Tsabary/Yael/Dafna): Invoke a function that returns a
-                // BatcherResult<BatcherFnTwoReturnValue>, and return
-                // the BatcherResponse::BatcherFnTwoInput accordingly.
-                unimplemented!()
+            BatcherRequest::BuildProposal(input) => {
+                BatcherResponse::BuildProposal(self.build_proposal(input).await)
+            }
+            BatcherRequest::ValidateProposal(input) => {
+                BatcherResponse::ValidateProposal(self.validate_proposal(input).await)
+            }
+            BatcherRequest::GetProposalContent(input) => {
+                BatcherResponse::GetProposalContent(self.get_proposal_content(input).await)
+            }
+            BatcherRequest::ForceAbortAndResync(input) => {
+                BatcherResponse::ForceAbortAndResync(self.force_abort_and_resync(input).await)
+            }
+            BatcherRequest::SkipHeight(input) => {
+                BatcherResponse::SkipHeight(self.skip_height(input).await)
+            }
+            BatcherRequest::DecisionReached(input) => {
+                BatcherResponse::DecisionReached(self.decision_reached(input).await)
+            }
+            BatcherRequest::ImportBlock(input) => {
+                BatcherResponse::ImportBlock(self.import_block(input).await)
+            }
+            BatcherRequest::IsAlive => BatcherResponse::IsAlive(self.is_alive().await),
+            BatcherRequest::IsReady => BatcherResponse::IsReady(self.is_ready().await),
+            BatcherRequest::StartHeight(input) => {
+                BatcherResponse::StartHeight(self.start_height(input).await)
+            }
+            BatcherRequest::AddSyncedBlock(input) => {
+                BatcherResponse::AddSyncedBlock(self.add_synced_block(*input).await)
+            }
+            BatcherRequest::GetProposalState => {
+                BatcherResponse::GetProposalState(self.get_proposal_state().await)
+            }
+            BatcherRequest::GetProposalMetadata(input) => {
+                BatcherResponse::GetProposalMetadata(self.get_proposal_metadata(input).await)
+            }
+            BatcherRequest::GetPendingState => {
+                BatcherResponse::GetPendingState(self.get_pending_state().await)
+            }
+            BatcherRequest::GetProposalProgress(input) => {
+                BatcherResponse::GetProposalProgress(self.get_proposal_progress(input).await)
+            }
+            BatcherRequest::ReproposeProposal(input) => {
+                BatcherResponse::ReproposeProposal(self.repropose_proposal(input).await)
+            }
+            BatcherRequest::GetCommittedBlock(input) => {
+                BatcherResponse::GetCommittedBlock(self.get_committed_block(input).await)
+            }
+            BatcherRequest::GetValidationStatus(input) => {
+                BatcherResponse::GetValidationStatus(self.get_validation_status(input).await)
+            }
+        }
+    }
+}
+
+// Remote requests are answered in the API version they were sent with. Validations of clients of
+// the previous version are answered once done, with their status.
+#[async_trait]
+impl ComponentRequestHandler<VersionedBatcherRequest, VersionedBatcherResponse> for Batcher {
+    async fn handle_request(
+        &mut self,
+        versioned_request: VersionedBatcherRequest,
+    ) -> VersionedBatcherResponse {
+        let awaited_validation = match &versioned_request.request {
+            BatcherRequest::ValidateProposal(input) if versioned_request.awaits_validation() => {
+                Some(input.proposal_id)
+            }
+            _ => None,
+        };
+        let VersionedBatcherRequest { version, request } = versioned_request;
+        let mut response =
+            ComponentRequestHandler::<BatcherRequest, BatcherResponse>::handle_request(
+                self, request,
+            )
+            .await;
+        if let (Some(proposal_id), BatcherResponse::ValidateProposal(Ok(()))) =
+            (awaited_validation, &response)
+        {
+            response =
+                BatcherResponse::GetValidationStatus(self.await_validation(proposal_id).await);
+        }
+        VersionedBatcherResponse { version, response }
+    }
+}
+
+impl Batcher {
+    // Polls the status of a started validation until it is done.
+    async fn await_validation(
+        &mut self,
+        proposal_id: ProposalId,
+    ) -> BatcherResult<ValidationStatus> {
+        loop {
+            match self.get_validation_status(GetValidationStatusInput { proposal_id }).await {
+                Ok(ValidationStatus::InProgress) => {
+                    tokio::time::sleep(VALIDATION_STATUS_POLL_INTERVAL).await
+                }
+                result => return result,
             }
         }
     }
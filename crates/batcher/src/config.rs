@@ -1,30 +1,202 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use blockifier::context::ChainInfo;
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use papyrus_storage::db::DbConfig;
+use papyrus_storage::StorageConfig;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use starknet_api::core::ContractAddress;
+use starknet_mempool_types::resource_bounds::FeeConfig;
+use validator::{Validate, ValidationError};
+
+use crate::block_builder::BlockBuilderConfig;
+use crate::block_feed::BlockFeedConfig;
+use crate::block_info_policy::BlockInfoPolicyConfig;
+use crate::block_replay::BlockReplayConfig;
+use crate::cached_state_reader::StateCacheConfig;
+use crate::fee_market::FeeMarketConfig;
+use crate::proposal_journal::ProposalJournalConfig;
+use crate::proposals_manager::ProposalsManagerConfig;
+
+const DEFAULT_PROPOSAL_CONTENT_PAGE_SIZE: usize = 100;
+const DEFAULT_COMMITTED_BLOCKS_CAPACITY: usize = 100;
+const DEFAULT_STRK_PER_ETH_RATE: u64 = 3000;
 
 /// The batcher related configuration.
 /// TODO(Lev/Tsabary/Yael/Dafna): Define actual configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_deadline_margin", skip_on_field_errors = false))]
 pub struct BatcherConfig {
     pub batcher_config_param_1: usize,
+    pub enable_admin_actions: bool,
+    #[validate]
+    pub proposals_manager: ProposalsManagerConfig,
+    #[validate]
+    pub storage: StorageConfig,
+    #[validate]
+    pub block_builder: BlockBuilderConfig,
+    #[validate]
+    pub state_cache: StateCacheConfig,
+    pub chain_info: ChainInfo,
+    // The fee policy of the chain, which the gateway enforces too.
+    pub fee_config: FeeConfig,
+    // If set, the gas prices of the built blocks are converted between the fee tokens by this
+    // rate, so transactions pay the same fee whichever token they pay in.
+    #[validate(range(min = 1))]
+    pub strk_per_eth_rate: Option<u64>,
+    #[validate]
+    pub fee_market: FeeMarketConfig,
+    // The address fees of the built blocks are paid to.
+    pub sequencer_address: ContractAddress,
+    #[validate]
+    pub block_info_policy: BlockInfoPolicyConfig,
+    // Requests with later deadlines are rejected, as they indicate a skew between the clocks of
+    // the caller and the batcher.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub max_time_to_deadline: Duration,
+    // The maximal number of transactions in a page of the content of a proposal.
+    #[validate(range(min = 1))]
+    pub proposal_content_page_size: usize,
+    // The number of latest committed blocks kept with their transactions and receipts, for state
+    // sync and the feeder API. Zero disables keeping them.
+    pub committed_blocks_capacity: usize,
+    // If set, a summary of every committed block is pushed to the configured endpoint.
+    #[validate]
+    pub block_feed: Option<BlockFeedConfig>,
+    // If set, the progress of the generated proposals is journaled, for recovery after a crash.
+    #[validate]
+    pub proposal_journal: Option<ProposalJournalConfig>,
+    // If set, the inputs of the built blocks are recorded, for replaying them offline.
+    #[validate]
+    pub block_replay: Option<BlockReplayConfig>,
+}
+
+// A proposal stops taking transactions at its deadline minus the margin, so a margin that isn't
+// shorter than the longest accepted time to the deadline leaves every proposal empty.
+fn validate_deadline_margin(config: &BatcherConfig) -> Result<(), ValidationError> {
+    let deadline_margin = config.proposals_manager.deadline_margin;
+    if deadline_margin < config.max_time_to_deadline {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("Invalid batcher configuration.");
+    error.message = Some(
+        format!(
+            "The deadline margin of the proposals, {deadline_margin:?}, isn't shorter than the \
+             maximal time to a deadline, {:?}.",
+            config.max_time_to_deadline
+        )
+        .into(),
+    );
+    Err(error)
 }
 
 impl SerializeConfig for BatcherConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
-            "batcher_config_param_1",
-            &self.batcher_config_param_1,
-            "The first batcher configuration parameter",
-            ParamPrivacyInput::Public,
-        )])
+        let members = BTreeMap::from_iter([
+            ser_param(
+                "batcher_config_param_1",
+                &self.batcher_config_param_1,
+                "The first batcher configuration parameter",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "enable_admin_actions",
+                &self.enable_admin_actions,
+                "If true, the batcher serves admin actions, e.g., force-aborting the active \
+                 proposal. Admin requests are authenticated by the admin endpoint and by the auth \
+                 token of the batcher's remote server.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "sequencer_address",
+                &self.sequencer_address,
+                "The address of the sequencer, which the fees of the built blocks are paid to.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_time_to_deadline",
+                &self.max_time_to_deadline.as_secs(),
+                "The maximal time, in seconds, from the arrival of a request to its deadline. \
+                 Requests with later deadlines are rejected, as they indicate a clock skew \
+                 between the caller and the batcher.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "proposal_content_page_size",
+                &self.proposal_content_page_size,
+                "The maximal number of transactions in a page of the content of a proposal.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "committed_blocks_capacity",
+                &self.committed_blocks_capacity,
+                "The number of latest committed blocks kept with their transactions and receipts, \
+                 for state sync and the feeder API. Zero disables keeping them.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![
+            members,
+            append_sub_config_name(self.proposals_manager.dump(), "proposals_manager"),
+            append_sub_config_name(self.storage.dump(), "storage"),
+            append_sub_config_name(self.block_builder.dump(), "block_builder"),
+            append_sub_config_name(self.state_cache.dump(), "state_cache"),
+            append_sub_config_name(self.chain_info.dump(), "chain_info"),
+            append_sub_config_name(self.fee_config.dump(), "fee_config"),
+            ser_optional_param(
+                &self.strk_per_eth_rate,
+                DEFAULT_STRK_PER_ETH_RATE,
+                "strk_per_eth_rate",
+                "The STRK/ETH rate the gas prices of the built blocks are converted between the \
+                 fee tokens by. If not set, the prices in both fee tokens are equal.",
+                ParamPrivacyInput::Public,
+            ),
+            append_sub_config_name(self.fee_market.dump(), "fee_market"),
+            append_sub_config_name(self.block_info_policy.dump(), "block_info_policy"),
+            ser_optional_sub_config(&self.block_feed, "block_feed"),
+            ser_optional_sub_config(&self.proposal_journal, "proposal_journal"),
+            ser_optional_sub_config(&self.block_replay, "block_replay"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
     }
 }
 
 impl Default for BatcherConfig {
     fn default() -> Self {
-        Self { batcher_config_param_1: 1 }
+        Self {
+            batcher_config_param_1: 1,
+            enable_admin_actions: false,
+            proposals_manager: ProposalsManagerConfig::default(),
+            storage: StorageConfig {
+                db_config: DbConfig { path_prefix: "./batcher".into(), ..Default::default() },
+                ..Default::default()
+            },
+            block_builder: BlockBuilderConfig::default(),
+            state_cache: StateCacheConfig::default(),
+            chain_info: ChainInfo::default(),
+            fee_config: FeeConfig::default(),
+            strk_per_eth_rate: None,
+            fee_market: FeeMarketConfig::default(),
+            sequencer_address: ContractAddress::default(),
+            block_info_policy: BlockInfoPolicyConfig::default(),
+            max_time_to_deadline: Duration::from_secs(60),
+            proposal_content_page_size: DEFAULT_PROPOSAL_CONTENT_PAGE_SIZE,
+            committed_blocks_capacity: DEFAULT_COMMITTED_BLOCKS_CAPACITY,
+            block_feed: None,
+            proposal_journal: None,
+            block_replay: None,
+        }
     }
 }
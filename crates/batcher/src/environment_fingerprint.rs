@@ -0,0 +1,79 @@
+use blockifier::context::ChainInfo;
+use blockifier::versioned_constants::StarknetVersion;
+use sha2::{Digest, Sha256};
+use starknet_batcher_types::batcher_types::EnvironmentFingerprint;
+use strum::IntoEnumIterator;
+
+use crate::block_builder::BlockBuilderConfig;
+use crate::cached_state_reader::COMPILED_CLASS_CACHE_VERSION;
+
+#[cfg(test)]
+#[path = "environment_fingerprint_test.rs"]
+mod environment_fingerprint_test;
+
+// Bumped whenever the hashed content changes, so that nodes computing fingerprints differently
+// never report matching fingerprints.
+const FINGERPRINT_VERSION: u32 = 2;
+
+/// The parts of the node's environment that affect the execution of proposals, hashed once when
+/// the batcher is created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionEnvironment {
+    digest: [u8; 32],
+}
+
+impl ExecutionEnvironment {
+    /// Hashes the given versioned constants JSONs, the version of the compiled class cache, the
+    /// chain info and the block builder config. The sequencer address is excluded, as it differs
+    /// between nodes without affecting the validation of another node's proposal.
+    pub fn new(
+        versioned_constants_jsons: &[&str],
+        chain_info: &ChainInfo,
+        block_builder_config: &BlockBuilderConfig,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(FINGERPRINT_VERSION.to_be_bytes());
+        hasher.update(COMPILED_CLASS_CACHE_VERSION.to_be_bytes());
+        hasher.update(length_bytes(versioned_constants_jsons.len()));
+        for versioned_constants_json in versioned_constants_jsons {
+            hash_field(&mut hasher, versioned_constants_json);
+        }
+        hash_field(
+            &mut hasher,
+            &serde_json::to_string(chain_info).expect("Failed to serialize the chain info."),
+        );
+        hash_field(
+            &mut hasher,
+            &serde_json::to_string(block_builder_config)
+                .expect("Failed to serialize the block builder config."),
+        );
+        Self { digest: hasher.finalize().into() }
+    }
+
+    /// The environment of this node, with the versioned constants of every Starknet version, as
+    /// the versioned constants of each height are selected by its protocol version.
+    pub fn local(chain_info: &ChainInfo, block_builder_config: &BlockBuilderConfig) -> Self {
+        let versioned_constants_jsons: Vec<_> =
+            StarknetVersion::iter().map(|version| version.versioned_constants_json()).collect();
+        Self::new(&versioned_constants_jsons, chain_info, block_builder_config)
+    }
+
+    /// The fingerprint of the environment for the proposal of the given seed. The seed is chosen
+    /// at random by the proposer, and reused by the validators to recompute the fingerprint.
+    pub fn fingerprint(&self, seed: u64) -> EnvironmentFingerprint {
+        let mut hasher = Sha256::new();
+        hasher.update(self.digest);
+        hasher.update(seed.to_be_bytes());
+        EnvironmentFingerprint { seed, digest: hasher.finalize().into() }
+    }
+}
+
+// Fields are length-prefixed, so that moving bytes between adjacent fields changes the hash.
+fn hash_field(hasher: &mut Sha256, field: &str) {
+    hasher.update(length_bytes(field.len()));
+    hasher.update(field.as_bytes());
+}
+
+fn length_bytes(length: usize) -> [u8; 8] {
+    u64::try_from(length).expect("Length fits in u64.").to_be_bytes()
+}
@@ -0,0 +1,49 @@
+use blockifier::context::ChainInfo;
+use blockifier::versioned_constants::StarknetVersion;
+use starknet_api::core::ChainId;
+
+use crate::block_builder::BlockBuilderConfig;
+use crate::environment_fingerprint::ExecutionEnvironment;
+
+#[test]
+fn same_environment_has_same_fingerprint() {
+    let fingerprint = |seed| {
+        ExecutionEnvironment::local(&ChainInfo::default(), &BlockBuilderConfig::default())
+            .fingerprint(seed)
+    };
+    assert_eq!(fingerprint(0), fingerprint(0));
+    assert_ne!(fingerprint(0), fingerprint(1));
+}
+
+#[test]
+fn execution_relevant_changes_change_the_fingerprint() {
+    let versioned_constants = [StarknetVersion::Latest.versioned_constants_json()];
+    let chain_info = ChainInfo::default();
+    let block_builder_config = BlockBuilderConfig::default();
+    let fingerprint =
+        ExecutionEnvironment::new(&versioned_constants, &chain_info, &block_builder_config)
+            .fingerprint(0);
+
+    let other_versioned_constants = [StarknetVersion::V0_13_0.versioned_constants_json()];
+    assert_ne!(
+        ExecutionEnvironment::new(&other_versioned_constants, &chain_info, &block_builder_config)
+            .fingerprint(0),
+        fingerprint
+    );
+
+    let other_chain_info =
+        ChainInfo { chain_id: ChainId::Other("other_chain".to_string()), ..chain_info.clone() };
+    assert_ne!(
+        ExecutionEnvironment::new(&versioned_constants, &other_chain_info, &block_builder_config)
+            .fingerprint(0),
+        fingerprint
+    );
+
+    let other_block_builder_config =
+        BlockBuilderConfig { max_n_transactions: 1, ..block_builder_config };
+    assert_ne!(
+        ExecutionEnvironment::new(&versioned_constants, &chain_info, &other_block_builder_config)
+            .fingerprint(0),
+        fingerprint
+    );
+}
@@ -1,4 +1,13 @@
 use std::cmp::max;
+use std::collections::BTreeMap;
+use std::num::NonZeroU128;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+use tracing::debug;
+use validator::Validate;
 
 #[cfg(test)]
 #[path = "fee_market_test.rs"]
@@ -7,25 +16,112 @@ pub mod fee_market_test;
 //  This constant is used to calculate the base gas price for the next block according to EIP-1559
 // and serves as a sensitivity parameter that limits the maximum rate of change of the gas price
 // between consecutive blocks.
-const GAS_PRICE_MAX_CHANGE_DENOMINATOR: u128 = 48;
+const DEFAULT_GAS_PRICE_MAX_CHANGE_DENOMINATOR: u64 = 48;
+// Setting the target at 50% of the max block size balances the rate of gas price changes, helping
+// to prevent sudden spikes, particularly during increases, for a better user experience.
+const DEFAULT_GAS_TARGET_UTILIZATION_PERCENT: u64 = 50;
 const MIN_GAS_PRICE: u64 = 100000; // In fri.
 // TODO(Mohammad): Check the exact value for maximum block size in StarkNet.
 const MAX_BLOCK_SIZE: u64 = 4000000000; // In gas units. It's equivalent to 40M gas steps, with 100 gas units per step.
 
+/// The EIP-1559 fee market setting the L2 gas price of the built blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct FeeMarketConfig {
+    // The L2 gas usage of blocks the price steers to, in percents of the maximal block size: the
+    // price rises after blocks above it, and falls after blocks below it.
+    #[validate(range(min = 1, max = 100))]
+    pub gas_target_utilization_percent: u64,
+    // Limits the rate of change of the price: between consecutive blocks, the price changes by the
+    // deviation of the gas usage from the target, relative to the target, divided by this value.
+    #[validate(range(min = 1))]
+    pub gas_price_max_change_denominator: u64,
+}
+
+impl Default for FeeMarketConfig {
+    fn default() -> Self {
+        Self {
+            gas_target_utilization_percent: DEFAULT_GAS_TARGET_UTILIZATION_PERCENT,
+            gas_price_max_change_denominator: DEFAULT_GAS_PRICE_MAX_CHANGE_DENOMINATOR,
+        }
+    }
+}
+
+impl SerializeConfig for FeeMarketConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "gas_target_utilization_percent",
+                &self.gas_target_utilization_percent,
+                "The L2 gas usage of blocks the L2 gas price steers to, in percents of the \
+                 maximal block size",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "gas_price_max_change_denominator",
+                &self.gas_price_max_change_denominator,
+                "Limits the change of the L2 gas price between consecutive blocks: the price \
+                 changes by the relative deviation of the gas usage from the target, divided by \
+                 this value",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Sets the L2 gas price of the next block, following the L2 gas usage of the committed blocks.
+// TODO: Resume from the price of the latest block after a restart, once it is stored.
+pub struct FeeMarket {
+    config: FeeMarketConfig,
+    l2_gas_price: SharedL2GasPrice,
+}
+
+impl FeeMarket {
+    /// Starts the given price at the minimal gas price.
+    pub fn new(config: FeeMarketConfig, l2_gas_price: SharedL2GasPrice) -> Self {
+        l2_gas_price.set(to_non_zero_gas_price(MIN_GAS_PRICE));
+        Self { config, l2_gas_price }
+    }
+
+    pub fn gas_target(&self) -> u64 {
+        MAX_BLOCK_SIZE / 100 * self.config.gas_target_utilization_percent
+    }
+
+    /// Moves the price after a block that consumed the given L2 gas, and returns the new price.
+    pub fn on_block_committed(&self, l2_gas_used: u128) -> NonZeroU128 {
+        let price = u64::try_from(self.l2_gas_price.get().get()).unwrap_or(u64::MAX);
+        let gas_used = u64::try_from(l2_gas_used).unwrap_or(u64::MAX).min(MAX_BLOCK_SIZE);
+        let next_price = to_non_zero_gas_price(calculate_next_base_gas_price(
+            price,
+            gas_used,
+            self.gas_target(),
+            self.config.gas_price_max_change_denominator,
+        ));
+        debug!("The L2 gas price changed from {} to {}.", price, next_price);
+        self.l2_gas_price.set(next_price);
+        next_price
+    }
+}
+
+fn to_non_zero_gas_price(price: u64) -> NonZeroU128 {
+    NonZeroU128::new(u128::from(price)).expect("Gas prices are at least the minimal gas price.")
+}
+
 /// Calculate the base gas price for the next block according to EIP-1559.
 ///
 /// # Parameters
 /// - `price`: The base fee of the current block.
 /// - `gas_used`: The total gas used in the current block.
 /// - `gas_target`: The target gas usage per block (usually half of the gas limit).
-pub fn calculate_next_base_gas_price(price: u64, gas_used: u64, gas_target: u64) -> u64 {
-    // Setting the target at 50% of the max block size balances the rate of gas price changes,
-    // helping to prevent sudden spikes, particularly during increases, for a better user
-    // experience.
-    assert_eq!(
-        gas_target,
-        MAX_BLOCK_SIZE / 2,
-        "Gas target must be 50% of max block size to balance price changes."
+/// - `max_change_denominator`: The sensitivity of the price to the deviation from the target.
+pub fn calculate_next_base_gas_price(
+    price: u64,
+    gas_used: u64,
+    gas_target: u64,
+    max_change_denominator: u64,
+) -> u64 {
+    assert!(
+        0 < gas_target && gas_target <= MAX_BLOCK_SIZE,
+        "Gas target must be positive and at most the max block size."
     );
     // To prevent precision loss during multiplication and division, we set a minimum gas price.
     // Additionally, a minimum gas price is established to prevent prolonged periods before the
@@ -53,17 +149,18 @@ pub fn calculate_next_base_gas_price(price: u64, gas_used: u64, gas_target: u64)
     // Calculate the price change, maintaining precision by dividing after scaling up.
     // This avoids significant precision loss that would occur if dividing before
     // multiplication.
-    let price_change_u128 = gas_delta_cost / gas_target_u128 / GAS_PRICE_MAX_CHANGE_DENOMINATOR;
+    let price_change_u128 = gas_delta_cost / gas_target_u128 / u128::from(max_change_denominator);
 
-    // Convert back to u64, as the price change should fit within the u64 range.
-    // Since the target is half the maximum block size (which fits within a u64), the gas delta
-    // is bounded by half the maximum block size. Therefore, after dividing by the gas target
-    // (which is half the maximum block size), the result is guaranteed to fit within a u64.
-    let price_change = u64::try_from(price_change_u128)
-        .expect("Result fits u64 after division of a bounded gas delta");
+    // Below the target, the gas delta is bounded by the target, so the price change is bounded by
+    // the price. Above a target lower than half the maximum block size, the gas delta may exceed
+    // the target, so the price saturates instead of overflowing.
+    let price_change = u64::try_from(price_change_u128).unwrap_or(u64::MAX);
 
-    let adjusted_price =
-        if gas_used > gas_target { price + price_change } else { price - price_change };
+    let adjusted_price = if gas_used > gas_target {
+        price.saturating_add(price_change)
+    } else {
+        price - price_change
+    };
 
     assert!(
         gas_used > gas_target && adjusted_price >= price
@@ -1,10 +1,18 @@
+use std::num::NonZeroU128;
+
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+
 use crate::fee_market::{
     calculate_next_base_gas_price,
-    GAS_PRICE_MAX_CHANGE_DENOMINATOR,
+    FeeMarket,
+    FeeMarketConfig,
+    DEFAULT_GAS_PRICE_MAX_CHANGE_DENOMINATOR,
     MAX_BLOCK_SIZE,
     MIN_GAS_PRICE,
 };
 
+const DENOMINATOR: u64 = DEFAULT_GAS_PRICE_MAX_CHANGE_DENOMINATOR;
+
 #[test]
 fn test_price_calculation_snapshot() {
     // Setup: using realistic arbitrary values.
@@ -20,15 +28,25 @@ fn test_price_calculation_snapshot() {
 
     // Assert.
     assert_eq!(
-        calculate_next_base_gas_price(INIT_PRICE, HIGH_CONGESTION_GAS_USED, GAS_TARGET),
+        calculate_next_base_gas_price(
+            INIT_PRICE,
+            HIGH_CONGESTION_GAS_USED,
+            GAS_TARGET,
+            DENOMINATOR
+        ),
         increased_price
     );
     assert_eq!(
-        calculate_next_base_gas_price(INIT_PRICE, LOW_CONGESTION_GAS_USED, GAS_TARGET),
+        calculate_next_base_gas_price(INIT_PRICE, LOW_CONGESTION_GAS_USED, GAS_TARGET, DENOMINATOR),
         decreased_price
     );
     assert_eq!(
-        calculate_next_base_gas_price(INIT_PRICE, STABLE_CONGESTION_GAS_USED, GAS_TARGET),
+        calculate_next_base_gas_price(
+            INIT_PRICE,
+            STABLE_CONGESTION_GAS_USED,
+            GAS_TARGET,
+            DENOMINATOR
+        ),
         INIT_PRICE
     );
 }
@@ -39,24 +57,82 @@ fn test_gas_price_with_extreme_values() {
     let price = MIN_GAS_PRICE;
     let gas_target = MAX_BLOCK_SIZE / 2;
     let gas_used = 0;
-    assert_eq!(calculate_next_base_gas_price(price, gas_used, gas_target), MIN_GAS_PRICE);
+    assert_eq!(
+        calculate_next_base_gas_price(price, gas_used, gas_target, DENOMINATOR),
+        MIN_GAS_PRICE
+    );
 
     let price = MIN_GAS_PRICE;
     let gas_target = MAX_BLOCK_SIZE / 2;
     let gas_used = MAX_BLOCK_SIZE;
-    assert!(calculate_next_base_gas_price(price, gas_used, gas_target) > MIN_GAS_PRICE);
+    assert!(
+        calculate_next_base_gas_price(price, gas_used, gas_target, DENOMINATOR) > MIN_GAS_PRICE
+    );
 
     let price = u64::MAX;
     let gas_target = MAX_BLOCK_SIZE / 2;
     let gas_used = 0;
-    calculate_next_base_gas_price(price, gas_used, gas_target); // Should not panic.
+    calculate_next_base_gas_price(price, gas_used, gas_target, DENOMINATOR); // Should not panic.
 
     // To avoid overflow when updating the price, the value is set below a certain threshold so that
     // the new price does not exceed u64::MAX.
     let max_u128 = u128::from(u64::MAX);
-    let price_u128 =
-        max_u128 * GAS_PRICE_MAX_CHANGE_DENOMINATOR / (GAS_PRICE_MAX_CHANGE_DENOMINATOR + 1);
+    let denominator = u128::from(DENOMINATOR);
+    let price_u128 = max_u128 * denominator / (denominator + 1);
     let gas_target = MAX_BLOCK_SIZE / 2;
     let gas_used = MAX_BLOCK_SIZE;
-    calculate_next_base_gas_price(u64::try_from(price_u128).unwrap(), gas_used, gas_target); // Should not panic.
+    calculate_next_base_gas_price(
+        u64::try_from(price_u128).unwrap(),
+        gas_used,
+        gas_target,
+        DENOMINATOR,
+    ); // Should not panic.
+}
+
+#[test]
+fn test_price_calculation_with_configured_target_and_change_rate() {
+    const INIT_PRICE: u64 = 1_000_000;
+    const GAS_TARGET: u64 = MAX_BLOCK_SIZE / 4;
+
+    // 1000000 + (1000000 * 1 / 4 * MAX_BLOCK_SIZE) / (0.25 * MAX_BLOCK_SIZE * 8).
+    assert_eq!(
+        calculate_next_base_gas_price(INIT_PRICE, MAX_BLOCK_SIZE / 2, GAS_TARGET, 8),
+        1125000
+    );
+    // 1000000 - (1000000 * 1 / 8 * MAX_BLOCK_SIZE) / (0.25 * MAX_BLOCK_SIZE * 8).
+    assert_eq!(
+        calculate_next_base_gas_price(INIT_PRICE, MAX_BLOCK_SIZE / 8, GAS_TARGET, 8),
+        937500
+    );
+
+    // Above a low target, the price saturates rather than overflowing.
+    assert_eq!(calculate_next_base_gas_price(u64::MAX, MAX_BLOCK_SIZE, 1, 1), u64::MAX);
+}
+
+#[test]
+fn fee_market_follows_committed_blocks() {
+    let l2_gas_price = SharedL2GasPrice::new(NonZeroU128::new(1).unwrap());
+    let config =
+        FeeMarketConfig { gas_target_utilization_percent: 25, gas_price_max_change_denominator: 8 };
+    let fee_market = FeeMarket::new(config, l2_gas_price.clone());
+    assert_eq!(fee_market.gas_target(), MAX_BLOCK_SIZE / 4);
+    assert_eq!(l2_gas_price.get().get(), u128::from(MIN_GAS_PRICE));
+
+    let gas_used = u128::from(MAX_BLOCK_SIZE / 2);
+    let next_price = fee_market.on_block_committed(gas_used);
+    let expected_price =
+        calculate_next_base_gas_price(MIN_GAS_PRICE, MAX_BLOCK_SIZE / 2, MAX_BLOCK_SIZE / 4, 8);
+    assert_eq!(next_price.get(), u128::from(expected_price));
+    assert_eq!(l2_gas_price.get(), next_price);
+
+    // Gas usage beyond the maximal block size counts as a full block.
+    let price = l2_gas_price.get().get();
+    let next_price = fee_market.on_block_committed(u128::MAX);
+    let expected_price = calculate_next_base_gas_price(
+        u64::try_from(price).unwrap(),
+        MAX_BLOCK_SIZE,
+        MAX_BLOCK_SIZE / 4,
+        8,
+    );
+    assert_eq!(next_price.get(), u128::from(expected_price));
 }
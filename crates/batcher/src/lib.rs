@@ -1,7 +1,24 @@
 pub mod batcher;
+pub mod block_builder;
+pub mod block_feed;
+pub mod block_info_policy;
+pub mod block_replay;
+pub mod cached_state_reader;
+pub mod clock;
 pub mod communication;
 pub mod config;
+pub mod environment_fingerprint;
 pub mod fee_market;
+pub mod metrics;
+pub mod outstream;
+pub mod papyrus_state_reader;
+pub mod proposal_commitment;
+pub mod proposal_content;
+pub mod proposal_journal;
 pub mod proposals_manager;
 #[cfg(test)]
 mod proposals_manager_test;
+pub mod protocol_version;
+pub mod system_transactions;
+#[cfg(test)]
+mod test_utils;
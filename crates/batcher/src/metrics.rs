@@ -0,0 +1,175 @@
+//! The metrics of the batcher. Proposals are labeled by their kind: generated by this node, or
+//! validated for another node.
+
+use starknet_batcher_types::batcher_types::ProposalMetadata;
+
+use crate::block_builder::ConcurrencyStats;
+use crate::outstream::OutstreamBackpressure;
+
+/// Counts the proposals started, labeled by their kind.
+pub const PROPOSALS_STARTED: &str = "batcher_proposals_started";
+/// Counts the proposals whose block was built, labeled by their kind.
+pub const PROPOSALS_FINISHED: &str = "batcher_proposals_finished";
+/// Counts the proposals whose block failed to build, labeled by their kind.
+pub const PROPOSALS_FAILED: &str = "batcher_proposals_failed";
+/// Counts the proposals aborted while in generation or validation.
+pub const PROPOSALS_ABORTED: &str = "batcher_proposals_aborted";
+/// Counts the proposals closed early because the connection to the mempool was lost.
+pub const DEGRADED_PROPOSALS: &str = "batcher_degraded_proposals";
+/// The number of transactions fed to the block builder per finished proposal, labeled by kind.
+pub const PROPOSAL_TXS: &str = "batcher_proposal_txs";
+/// The time it took to build the block of a finished proposal, in seconds, labeled by kind.
+pub const BLOCK_BUILD_DURATION_SECS: &str = "batcher_block_build_duration_seconds";
+/// The number of reverted transactions per finished proposal, labeled by kind.
+pub const PROPOSAL_REVERTED_TXS: &str = "batcher_proposal_reverted_txs";
+/// The gas used per finished proposal, labeled by kind and by gas resource.
+pub const PROPOSAL_GAS_USED: &str = "batcher_proposal_gas_used";
+/// The number of Cairo steps executed per finished proposal, labeled by kind.
+pub const PROPOSAL_STEPS: &str = "batcher_proposal_steps";
+/// The number of builtin instances used per finished proposal, labeled by kind and by builtin.
+pub const PROPOSAL_BUILTINS: &str = "batcher_proposal_builtins";
+/// The number of transactions executed concurrently per finished proposal, labeled by kind.
+pub const PROPOSAL_CONCURRENT_TXS: &str = "batcher_proposal_concurrent_txs";
+/// The number of transaction re-executions for conflicts per finished proposal, labeled by kind.
+pub const PROPOSAL_REEXECUTIONS: &str = "batcher_proposal_reexecutions";
+/// Counts the finished proposals whose block builder fell back to sequential execution for too
+/// many conflicts, labeled by kind.
+pub const CONCURRENCY_FALLBACKS: &str = "batcher_concurrency_fallbacks";
+/// Counts the state reads of the block builder served from the state cache, labeled by the read
+/// item.
+pub const STATE_CACHE_HITS: &str = "batcher_state_cache_hits";
+/// Counts the state reads of the block builder that missed the state cache, labeled by the read
+/// item.
+pub const STATE_CACHE_MISSES: &str = "batcher_state_cache_misses";
+/// Counts the times the consumer of a generated proposal stalled with its outstream buffer full.
+pub const OUTSTREAM_STALLS: &str = "batcher_outstream_stalls";
+/// The time the generation of a proposal waited for the consumer of its content, in seconds.
+pub const OUTSTREAM_STALL_DURATION_SECS: &str = "batcher_outstream_stall_duration_seconds";
+/// The maximal number of transactions spilled to memory per generated proposal, by the spill
+/// overflow policy.
+pub const OUTSTREAM_SPILLED_TXS: &str = "batcher_outstream_spilled_txs";
+/// Counts the generated proposals aborted for their content overflowing the outstream buffer.
+pub const OUTSTREAM_OVERFLOWS: &str = "batcher_outstream_overflows";
+
+pub const PROPOSAL_KIND_LABEL: &str = "kind";
+pub const GAS_RESOURCE_LABEL: &str = "resource";
+pub const BUILTIN_LABEL: &str = "builtin";
+pub const STATE_ITEM_LABEL: &str = "item";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalKind {
+    Generation,
+    Validation,
+}
+
+impl ProposalKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ProposalKind::Generation => "generation",
+            ProposalKind::Validation => "validation",
+        }
+    }
+}
+
+pub(crate) fn record_proposal_started(kind: ProposalKind) {
+    metrics::increment_counter!(PROPOSALS_STARTED, PROPOSAL_KIND_LABEL => kind.as_str());
+}
+
+pub(crate) fn record_proposal_finished(kind: ProposalKind, metadata: &ProposalMetadata) {
+    let kind = kind.as_str();
+    metrics::increment_counter!(PROPOSALS_FINISHED, PROPOSAL_KIND_LABEL => kind);
+    metrics::histogram!(
+        PROPOSAL_TXS,
+        to_histogram_value(metadata.n_txs),
+        PROPOSAL_KIND_LABEL => kind
+    );
+    metrics::histogram!(
+        PROPOSAL_REVERTED_TXS,
+        to_histogram_value(metadata.n_reverted_txs),
+        PROPOSAL_KIND_LABEL => kind
+    );
+    metrics::histogram!(
+        BLOCK_BUILD_DURATION_SECS,
+        metadata.build_duration.as_secs_f64(),
+        PROPOSAL_KIND_LABEL => kind
+    );
+    for (resource, gas_used) in [
+        ("l1_gas", metadata.l1_gas_used),
+        ("l1_data_gas", metadata.l1_data_gas_used),
+        ("l2_gas", metadata.l2_gas_used),
+    ] {
+        metrics::histogram!(
+            PROPOSAL_GAS_USED,
+            gas_to_histogram_value(gas_used),
+            PROPOSAL_KIND_LABEL => kind,
+            GAS_RESOURCE_LABEL => resource
+        );
+    }
+    metrics::histogram!(
+        PROPOSAL_STEPS,
+        to_histogram_value(metadata.n_steps),
+        PROPOSAL_KIND_LABEL => kind
+    );
+    for (builtin, count) in &metadata.builtin_counts {
+        metrics::histogram!(
+            PROPOSAL_BUILTINS,
+            to_histogram_value(*count),
+            PROPOSAL_KIND_LABEL => kind,
+            BUILTIN_LABEL => builtin.clone()
+        );
+    }
+}
+
+pub(crate) fn record_concurrency_stats(kind: ProposalKind, stats: &ConcurrencyStats) {
+    metrics::histogram!(
+        PROPOSAL_CONCURRENT_TXS,
+        to_histogram_value(stats.n_concurrent_txs),
+        PROPOSAL_KIND_LABEL => kind.as_str()
+    );
+    metrics::histogram!(
+        PROPOSAL_REEXECUTIONS,
+        to_histogram_value(stats.n_reexecutions),
+        PROPOSAL_KIND_LABEL => kind.as_str()
+    );
+    if stats.fell_back_to_sequential {
+        metrics::increment_counter!(CONCURRENCY_FALLBACKS, PROPOSAL_KIND_LABEL => kind.as_str());
+    }
+}
+
+pub(crate) fn record_outstream_backpressure(backpressure: &OutstreamBackpressure) {
+    let n_stalls = u64::try_from(backpressure.n_stalls).expect("Number of stalls should fit u64.");
+    metrics::counter!(OUTSTREAM_STALLS, n_stalls);
+    metrics::histogram!(OUTSTREAM_STALL_DURATION_SECS, backpressure.stall_duration.as_secs_f64());
+    metrics::histogram!(OUTSTREAM_SPILLED_TXS, to_histogram_value(backpressure.max_spilled_txs));
+    if backpressure.overflowed {
+        metrics::increment_counter!(OUTSTREAM_OVERFLOWS);
+    }
+}
+
+pub(crate) fn record_proposal_failed(kind: ProposalKind) {
+    metrics::increment_counter!(PROPOSALS_FAILED, PROPOSAL_KIND_LABEL => kind.as_str());
+}
+
+pub(crate) fn record_proposal_aborted() {
+    metrics::increment_counter!(PROPOSALS_ABORTED);
+}
+
+pub(crate) fn record_state_cache_lookup(item: &'static str, hit: bool) {
+    if hit {
+        metrics::increment_counter!(STATE_CACHE_HITS, STATE_ITEM_LABEL => item);
+    } else {
+        metrics::increment_counter!(STATE_CACHE_MISSES, STATE_ITEM_LABEL => item);
+    }
+}
+
+// Transaction and execution counts are far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_histogram_value(n: usize) -> f64 {
+    n as f64
+}
+
+// Block gas amounts are far below the precision limit of f64 as well.
+#[allow(clippy::as_conversions)]
+fn gas_to_histogram_value(gas: u128) -> f64 {
+    gas as f64
+}
@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::executable_transaction::Transaction;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "outstream_test.rs"]
+mod outstream_test;
+
+/// How a proposal generation handles a stalled consumer of its content, i.e., an outstream buffer
+/// that is full.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutstreamOverflowPolicy {
+    /// Wait for the consumer, stalling the block builder, which may miss its deadline.
+    #[default]
+    Block,
+    /// Fail the proposal.
+    AbortProposal,
+    /// Keep taking the transactions of the block builder into an in-memory buffer, and wait for
+    /// the consumer once the buffer is full.
+    SpillToBuffer,
+}
+
+/// The backpressure the consumer of a proposal content put on its generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutstreamBackpressure {
+    /// The number of times the outstream buffer was found full.
+    pub n_stalls: usize,
+    /// The total time the forwarding waited for the consumer.
+    pub stall_duration: Duration,
+    /// The maximal number of transactions held in the spill buffer at once.
+    pub max_spilled_txs: usize,
+    /// Set if the proposal was aborted for its content overflowing the outstream buffer.
+    pub overflowed: bool,
+}
+
+/// The transactions forwarded to the consumer of a proposal content, and how the consumer kept up
+/// with them.
+#[derive(Debug, Default)]
+pub struct ForwardedContent {
+    pub txs: Vec<Transaction>,
+    pub backpressure: OutstreamBackpressure,
+}
+
+/// Forwards the transactions added to a block by its builder, read from `receiver`, to the
+/// consumer of the proposal content, handling a stalled consumer by the given policy. Returns once
+/// the block builder is done, the consumer closed the content, or the content overflowed; in the
+/// latter cases, `receiver` is dropped, notifying the block builder that its output was closed.
+pub async fn forward_content(
+    receiver: Receiver<Transaction>,
+    sender: &Sender<Transaction>,
+    policy: OutstreamOverflowPolicy,
+    spill_buffer_size: usize,
+) -> ForwardedContent {
+    match policy {
+        // A single transaction is held while waiting for the consumer.
+        OutstreamOverflowPolicy::Block => forward_buffered(receiver, sender, 1).await,
+        OutstreamOverflowPolicy::AbortProposal => forward_or_overflow(receiver, sender).await,
+        OutstreamOverflowPolicy::SpillToBuffer => {
+            forward_buffered(receiver, sender, spill_buffer_size.max(1)).await
+        }
+    }
+}
+
+async fn forward_buffered(
+    mut receiver: Receiver<Transaction>,
+    sender: &Sender<Transaction>,
+    max_pending_txs: usize,
+) -> ForwardedContent {
+    let mut content = ForwardedContent::default();
+    // Taken from the block builder and not yet accepted by the consumer.
+    let mut pending_txs = VecDeque::new();
+    let mut is_build_done = false;
+    let mut stalled_since = None;
+    loop {
+        if !pending_txs.is_empty() && sender.capacity() == 0 && stalled_since.is_none() {
+            content.backpressure.n_stalls += 1;
+            stalled_since = Some(Instant::now());
+        }
+        tokio::select! {
+            // The consumer is fed before more transactions are taken.
+            biased;
+            permit = sender.reserve(), if !pending_txs.is_empty() => {
+                let Ok(permit) = permit else {
+                    // The consumer closed the content.
+                    break;
+                };
+                if let Some(stalled_since) = stalled_since.take() {
+                    content.backpressure.stall_duration += stalled_since.elapsed();
+                }
+                let tx = pending_txs.pop_front().expect("A transaction should be pending.");
+                permit.send(tx.clone());
+                content.txs.push(tx);
+            }
+            tx = receiver.recv(), if !is_build_done && pending_txs.len() < max_pending_txs => {
+                match tx {
+                    Some(tx) => {
+                        pending_txs.push_back(tx);
+                        // The transaction in hand is not spilled.
+                        content.backpressure.max_spilled_txs = content
+                            .backpressure
+                            .max_spilled_txs
+                            .max(pending_txs.len() - 1);
+                    }
+                    None => is_build_done = true,
+                }
+            }
+            else => break,
+        }
+    }
+    content
+}
+
+async fn forward_or_overflow(
+    mut receiver: Receiver<Transaction>,
+    sender: &Sender<Transaction>,
+) -> ForwardedContent {
+    let mut content = ForwardedContent::default();
+    while let Some(tx) = receiver.recv().await {
+        match sender.try_send(tx.clone()) {
+            Ok(()) => content.txs.push(tx),
+            Err(TrySendError::Full(_)) => {
+                content.backpressure.n_stalls += 1;
+                content.backpressure.overflowed = true;
+                break;
+            }
+            Err(TrySendError::Closed(_)) => break,
+        }
+    }
+    content
+}
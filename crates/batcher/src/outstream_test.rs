@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::felt;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
+use tokio::sync::mpsc::channel;
+
+use crate::outstream::{forward_content, ForwardedContent, OutstreamOverflowPolicy};
+
+const N_TXS: u64 = 5;
+// Long enough for a send that isn't blocked on the consumer to complete.
+const SEND_TIMEOUT: Duration = Duration::from_millis(100);
+
+fn txs() -> Vec<Transaction> {
+    (0..N_TXS)
+        .map(|i| {
+            Transaction::Invoke(InvokeTransaction {
+                tx: invoke_tx(InvokeTxArgs::default()),
+                tx_hash: TransactionHash(felt!(i)),
+            })
+        })
+        .collect()
+}
+
+fn tx_hashes(txs: &[Transaction]) -> Vec<TransactionHash> {
+    txs.iter().map(|tx| tx.tx_hash()).collect()
+}
+
+// Runs the forwarding of the content of a block builder, with buffers of a single transaction, to
+// a consumer that doesn't read it until the block builder is done. Returns whether all the
+// transactions were added by then, the transactions read by the consumer, and the forwarded
+// content.
+async fn forward_to_stalled_consumer(
+    policy: OutstreamOverflowPolicy,
+    spill_buffer_size: usize,
+) -> (bool, Vec<Transaction>, ForwardedContent) {
+    let (builder_sender, builder_receiver) = channel(1);
+    let (consumer_sender, mut consumer_receiver) = channel(1);
+    let forwarding = tokio::spawn(async move {
+        forward_content(builder_receiver, &consumer_sender, policy, spill_buffer_size).await
+    });
+
+    let mut is_build_done = true;
+    for tx in txs() {
+        // The forwarding may be done once the content overflowed.
+        match tokio::time::timeout(SEND_TIMEOUT, builder_sender.send(tx)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => break,
+            Err(_) => {
+                is_build_done = false;
+                break;
+            }
+        }
+    }
+    drop(builder_sender);
+
+    let mut consumed_txs = Vec::new();
+    while let Some(tx) = consumer_receiver.recv().await {
+        consumed_txs.push(tx);
+    }
+    (is_build_done, consumed_txs, forwarding.await.unwrap())
+}
+
+#[tokio::test]
+async fn block_policy_stalls_the_block_builder() {
+    let (is_build_done, consumed_txs, content) =
+        forward_to_stalled_consumer(OutstreamOverflowPolicy::Block, 0).await;
+
+    assert!(!is_build_done);
+    // The transactions added before the stall are forwarded once the consumer reads them.
+    assert_eq!(tx_hashes(&consumed_txs), tx_hashes(&content.txs));
+    assert!(content.backpressure.n_stalls > 0);
+    assert!(!content.backpressure.overflowed);
+}
+
+#[tokio::test]
+async fn abort_policy_overflows_the_content() {
+    let (_, consumed_txs, content) =
+        forward_to_stalled_consumer(OutstreamOverflowPolicy::AbortProposal, 0).await;
+
+    assert!(content.backpressure.overflowed);
+    // Only the buffered transaction was forwarded.
+    assert_eq!(tx_hashes(&content.txs), tx_hashes(&txs()[..1]));
+    assert_eq!(tx_hashes(&consumed_txs), tx_hashes(&content.txs));
+}
+
+#[tokio::test]
+async fn spill_policy_keeps_the_block_builder_going() {
+    let (is_build_done, consumed_txs, content) =
+        forward_to_stalled_consumer(OutstreamOverflowPolicy::SpillToBuffer, 10).await;
+
+    assert!(is_build_done);
+    assert_eq!(tx_hashes(&consumed_txs), tx_hashes(&txs()));
+    assert_eq!(tx_hashes(&content.txs), tx_hashes(&txs()));
+    assert!(content.backpressure.max_spilled_txs > 0);
+    assert!(!content.backpressure.overflowed);
+}
+
+#[tokio::test]
+async fn content_is_forwarded_in_order_to_a_ready_consumer() {
+    let (builder_sender, builder_receiver) = channel(1);
+    let (consumer_sender, mut consumer_receiver) = channel(N_TXS.try_into().unwrap());
+    let build = async move {
+        for tx in txs() {
+            builder_sender.send(tx).await.unwrap();
+        }
+    };
+
+    let ((), content) = tokio::join!(
+        build,
+        forward_content(builder_receiver, &consumer_sender, OutstreamOverflowPolicy::Block, 0)
+    );
+    drop(consumer_sender);
+
+    let mut consumed_txs = Vec::new();
+    while let Some(tx) = consumer_receiver.recv().await {
+        consumed_txs.push(tx);
+    }
+    assert_eq!(tx_hashes(&consumed_txs), tx_hashes(&txs()));
+    assert_eq!(content.backpressure, Default::default());
+}
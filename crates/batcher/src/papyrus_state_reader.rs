@@ -0,0 +1,187 @@
+use blockifier::execution::contract_class::{ContractClass, ContractClassV0, ContractClassV1};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use papyrus_storage::compiled_class::CasmStorageReader;
+use papyrus_storage::db::RO;
+use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::{StorageReader, StorageTxn};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::{StateNumber, StorageKey};
+use starknet_class_manager_types::communication::SharedClassManagerClient;
+use starknet_types_core::felt::Felt;
+use tokio::runtime::Handle;
+
+use crate::block_builder::{BatcherStateReader, StateReaderFactory};
+
+/// Reads the state at the start of a block from the node's storage.
+pub struct PapyrusReader {
+    storage_reader: StorageReader,
+    block_number: BlockNumber,
+    // Provides the compiled classes of the classes declared in the blocks built by this node,
+    // which the storage doesn't hold.
+    class_manager_client: Option<SharedClassManagerClient>,
+}
+
+impl PapyrusReader {
+    pub fn new(
+        storage_reader: StorageReader,
+        block_number: BlockNumber,
+        class_manager_client: Option<SharedClassManagerClient>,
+    ) -> Self {
+        Self { storage_reader, block_number, class_manager_client }
+    }
+
+    fn state_number(&self) -> StateNumber {
+        StateNumber(self.block_number)
+    }
+
+    fn reader(&self) -> StateResult<StorageTxn<'_, RO>> {
+        self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)
+    }
+
+    // Returns the block the given class was declared in, if it was declared before the state.
+    fn get_class_declaration_block_number(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<Option<BlockNumber>> {
+        let declaration_block_number = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| state_reader.get_class_definition_block_number(&class_hash))
+            .map_err(storage_err_to_state_err)?;
+        Ok(declaration_block_number.filter(|block_number| *block_number < self.block_number))
+    }
+
+    // The state reader is called synchronously by the executor, on a thread of the runtime, so
+    // the thread is handed over to the other tasks while the class manager is called.
+    fn get_casm_from_class_manager(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<Option<CasmContractClass>> {
+        let Some(class_manager_client) = &self.class_manager_client else {
+            return Ok(None);
+        };
+        tokio::task::block_in_place(|| {
+            Handle::current().block_on(class_manager_client.get_executable(class_hash))
+        })
+        .map_err(|err| StateError::StateReadError(err.to_string()))
+    }
+}
+
+impl BlockifierStateReader for PapyrusReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_storage_at(self.state_number(), &contract_address, &key)
+            })
+            .map_err(storage_err_to_state_err)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let nonce = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_nonce_at(self.state_number(), &contract_address)
+            })
+            .map_err(storage_err_to_state_err)?;
+        Ok(nonce.unwrap_or_default())
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let class_hash = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_class_hash_at(self.state_number(), &contract_address)
+            })
+            .map_err(storage_err_to_state_err)?;
+        Ok(class_hash.unwrap_or_default())
+    }
+
+    /// Returns the compiled class of a declared Cairo 1 class, or of a deprecated (Cairo 0) class.
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        if self.get_class_declaration_block_number(class_hash)?.is_some() {
+            let mut casm =
+                self.reader()?.get_casm(&class_hash).map_err(storage_err_to_state_err)?;
+            if casm.is_none() {
+                casm = self.get_casm_from_class_manager(class_hash)?;
+            }
+            let casm = casm.ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "Missing the compiled class of declared class {class_hash}."
+                ))
+            })?;
+            return Ok(ContractClass::V1(ContractClassV1::try_from(casm)?));
+        }
+
+        let deprecated_class = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_deprecated_class_definition_at(self.state_number(), &class_hash)
+            })
+            .map_err(storage_err_to_state_err)?;
+        match deprecated_class {
+            Some(deprecated_class) => Ok(ContractClassV0::try_from(deprecated_class)?.into()),
+            None => Err(StateError::UndeclaredClassHash(class_hash)),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let Some(declaration_block_number) = self.get_class_declaration_block_number(class_hash)?
+        else {
+            return Ok(CompiledClassHash::default());
+        };
+        let state_diff = self
+            .reader()?
+            .get_state_diff(declaration_block_number)
+            .map_err(storage_err_to_state_err)?;
+        state_diff
+            .and_then(|state_diff| state_diff.declared_classes.get(&class_hash).copied())
+            .ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "Missing the compiled class hash of declared class {class_hash}."
+                ))
+            })
+    }
+}
+
+impl BatcherStateReader for PapyrusReader {}
+
+/// Creates readers of the state at the start of the block following the latest one in the storage.
+pub struct PapyrusReaderFactory {
+    pub storage_reader: StorageReader,
+    pub class_manager_client: Option<SharedClassManagerClient>,
+}
+
+impl StateReaderFactory for PapyrusReaderFactory {
+    fn get_state_reader_for_next_block(
+        &self,
+    ) -> StateResult<(BlockNumber, Box<dyn BatcherStateReader>)> {
+        let block_number = self
+            .storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_state_marker())
+            .map_err(storage_err_to_state_err)?;
+        Ok((
+            block_number,
+            Box::new(PapyrusReader::new(
+                self.storage_reader.clone(),
+                block_number,
+                self.class_manager_client.clone(),
+            )),
+        ))
+    }
+}
+
+fn storage_err_to_state_err(err: papyrus_storage::StorageError) -> StateError {
+    StateError::StateReadError(err.to_string())
+}
@@ -0,0 +1,31 @@
+use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::block_hash::transaction_commitment::{
+    calculate_transaction_commitment,
+    TransactionLeafElement,
+};
+use starknet_batcher_types::batcher_types::ProposalCommitment;
+use starknet_types_core::hash::Poseidon;
+
+use crate::block_builder::BlockExecutionArtifacts;
+
+#[cfg(test)]
+#[path = "proposal_commitment_test.rs"]
+mod proposal_commitment_test;
+
+/// Computes the commitment of a built block, which consensus votes on: the hash of its state diff,
+/// and the commitment of its transactions, in execution order, as in the block hash.
+pub fn calculate_proposal_commitment(artifacts: &BlockExecutionArtifacts) -> ProposalCommitment {
+    let transaction_leaf_elements: Vec<_> = artifacts
+        .executed_txs
+        .iter()
+        .map(|executed_tx| {
+            TransactionLeafElement::new(executed_tx.tx_hash, executed_tx.signature.clone())
+        })
+        .collect();
+    ProposalCommitment {
+        state_diff_commitment: calculate_state_diff_hash(&artifacts.state_diff),
+        transaction_commitment: calculate_transaction_commitment::<Poseidon>(
+            &transaction_leaf_elements,
+        ),
+    }
+}
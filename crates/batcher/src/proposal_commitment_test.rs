@@ -0,0 +1,43 @@
+use starknet_api::felt;
+use starknet_api::transaction::{TransactionHash, TransactionSignature};
+
+use crate::block_builder::{BlockExecutionArtifacts, ExecutedTransaction};
+use crate::proposal_commitment::calculate_proposal_commitment;
+
+fn executed_tx(tx_hash: u8, signature: u8) -> ExecutedTransaction {
+    ExecutedTransaction {
+        tx_hash: TransactionHash(felt!(tx_hash)),
+        signature: Some(TransactionSignature(vec![felt!(signature)])),
+        ..Default::default()
+    }
+}
+
+fn artifacts(executed_txs: Vec<ExecutedTransaction>) -> BlockExecutionArtifacts {
+    BlockExecutionArtifacts { executed_txs, ..Default::default() }
+}
+
+#[test]
+fn commitment_is_deterministic() {
+    let block = || artifacts(vec![executed_tx(1, 1), executed_tx(2, 2)]);
+
+    assert_eq!(calculate_proposal_commitment(&block()), calculate_proposal_commitment(&block()));
+}
+
+#[test]
+fn commitment_depends_on_transaction_order() {
+    let commitment =
+        calculate_proposal_commitment(&artifacts(vec![executed_tx(1, 1), executed_tx(2, 2)]));
+    let reordered_commitment =
+        calculate_proposal_commitment(&artifacts(vec![executed_tx(2, 2), executed_tx(1, 1)]));
+
+    assert_eq!(commitment.state_diff_commitment, reordered_commitment.state_diff_commitment);
+    assert_ne!(commitment.transaction_commitment, reordered_commitment.transaction_commitment);
+}
+
+#[test]
+fn commitment_depends_on_signatures() {
+    let commitment = calculate_proposal_commitment(&artifacts(vec![executed_tx(1, 1)]));
+    let resigned_commitment = calculate_proposal_commitment(&artifacts(vec![executed_tx(1, 2)]));
+
+    assert_ne!(commitment.transaction_commitment, resigned_commitment.transaction_commitment);
+}
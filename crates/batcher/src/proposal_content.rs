@@ -0,0 +1,58 @@
+use futures::FutureExt;
+use starknet_api::executable_transaction::Transaction;
+use tokio_stream::StreamExt;
+
+use crate::block_builder::OutputTxStream;
+
+/// The transactions of a proposal, split into pages as they are added to the block. Pages are
+/// kept once formed, so that consensus can retrieve them at its own pace, and again to retransmit
+/// them.
+pub(crate) struct PagedProposalContent {
+    tx_stream: OutputTxStream,
+    page_size: usize,
+    pages: Vec<Vec<Transaction>>,
+    // Set once the stream ended, i.e., no more transactions will be added to the proposal.
+    is_finished: bool,
+}
+
+impl PagedProposalContent {
+    pub(crate) fn new(tx_stream: OutputTxStream, page_size: usize) -> Self {
+        Self { tx_stream, page_size, pages: vec![], is_finished: false }
+    }
+
+    /// Returns the given page, waiting for the transactions of the pages up to it to be added, or
+    /// `None` if the proposal ended before the page.
+    pub(crate) async fn page(&mut self, index: usize) -> Option<&[Transaction]> {
+        while self.pages.len() <= index {
+            if !self.form_next_page().await {
+                return None;
+            }
+        }
+        Some(&self.pages[index])
+    }
+
+    // A page holds the transactions available once the first one of it is added, up to the page
+    // size. Returns false if the stream ended before the first one.
+    async fn form_next_page(&mut self) -> bool {
+        if self.is_finished {
+            return false;
+        }
+        let Some(tx) = self.tx_stream.next().await else {
+            self.is_finished = true;
+            return false;
+        };
+        let mut txs = vec![tx];
+        while txs.len() < self.page_size {
+            match self.tx_stream.next().now_or_never() {
+                Some(Some(tx)) => txs.push(tx),
+                Some(None) => {
+                    self.is_finished = true;
+                    break;
+                }
+                None => break,
+            }
+        }
+        self.pages.push(txs);
+        true
+    }
+}
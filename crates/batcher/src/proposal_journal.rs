@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::executable_transaction::Transaction;
+use starknet_batcher_types::batcher_types::{ProposalBlockInfo, ProposalCommitment, ProposalId};
+use tracing::{error, warn};
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "proposal_journal_test.rs"]
+mod proposal_journal_test;
+
+/// The configuration of the journal of the proposals generated by the batcher.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ProposalJournalConfig {
+    pub path: PathBuf,
+}
+
+impl Default for ProposalJournalConfig {
+    fn default() -> Self {
+        Self { path: PathBuf::from("proposal_journal.jsonl") }
+    }
+}
+
+impl SerializeConfig for ProposalJournalConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "path",
+            &self.path,
+            "The path of the proposal journal file.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// A single line of the proposal journal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum JournalEntry {
+    /// The generation of a proposal started.
+    BuildStarted { proposal_id: ProposalId, height: BlockNumber },
+    /// Transactions taken from the mempool to feed the block builder of a proposal.
+    TxsFed { proposal_id: ProposalId, txs: Vec<Transaction> },
+    /// The block of a proposal was built, with the given content.
+    BuildFinished {
+        proposal_id: ProposalId,
+        height: BlockNumber,
+        txs: Vec<Transaction>,
+        commitment: ProposalCommitment,
+        // Missing from the entries of journals written before the block info was recorded.
+        #[serde(default)]
+        block_info: ProposalBlockInfo,
+    },
+    /// The generation of a proposal ended without a block, and its unused transactions were
+    /// returned to the mempool.
+    BuildAborted { proposal_id: ProposalId },
+}
+
+/// A proposal whose generation was interrupted by a crash of the batcher.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterruptedProposal {
+    pub proposal_id: ProposalId,
+    pub height: BlockNumber,
+    /// The transactions taken from the mempool for the proposal, which the mempool still holds as
+    /// taken.
+    pub txs: Vec<Transaction>,
+}
+
+/// The latest proposal whose block was built.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuiltProposal {
+    pub proposal_id: ProposalId,
+    pub height: BlockNumber,
+    pub txs: Vec<Transaction>,
+    pub commitment: ProposalCommitment,
+    pub block_info: ProposalBlockInfo,
+}
+
+/// The progress of the proposals of the journal, as left by the previous run of the batcher.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JournalRecovery {
+    pub interrupted_proposals: Vec<InterruptedProposal>,
+    pub last_built_proposal: Option<BuiltProposal>,
+}
+
+impl JournalRecovery {
+    pub fn from_entries(entries: Vec<JournalEntry>) -> Self {
+        let mut in_progress: HashMap<ProposalId, InterruptedProposal> = HashMap::new();
+        let mut last_built_proposal = None;
+        for entry in entries {
+            match entry {
+                JournalEntry::BuildStarted { proposal_id, height } => {
+                    in_progress.insert(
+                        proposal_id,
+                        InterruptedProposal { proposal_id, height, txs: vec![] },
+                    );
+                }
+                JournalEntry::TxsFed { proposal_id, txs } => {
+                    if let Some(proposal) = in_progress.get_mut(&proposal_id) {
+                        proposal.txs.extend(txs);
+                    }
+                }
+                JournalEntry::BuildFinished {
+                    proposal_id,
+                    height,
+                    txs,
+                    commitment,
+                    block_info,
+                } => {
+                    in_progress.remove(&proposal_id);
+                    last_built_proposal =
+                        Some(BuiltProposal { proposal_id, height, txs, commitment, block_info });
+                }
+                JournalEntry::BuildAborted { proposal_id } => {
+                    in_progress.remove(&proposal_id);
+                }
+            }
+        }
+        let mut interrupted_proposals: Vec<_> = in_progress.into_values().collect();
+        interrupted_proposals.sort_by_key(|proposal| proposal.proposal_id);
+        Self { interrupted_proposals, last_built_proposal }
+    }
+}
+
+/// An append-only journal of the progress of the proposals generated by the batcher, stored as
+/// JSON lines, so that a batcher restarted after a crash can return the transactions of a
+/// half-built proposal to the mempool, and re-propose the last built proposal as is. The journal
+/// holds the proposals of the current height only.
+#[derive(Debug)]
+pub struct ProposalJournal {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl ProposalJournal {
+    /// Opens the journal, returning the progress recorded by the previous run. The journal is
+    /// compacted to the last built proposal, as the interrupted proposals are recovered once.
+    pub fn open(path: &Path) -> std::io::Result<(Self, JournalRecovery)> {
+        let entries = match read_journal(path) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err),
+        };
+        let recovery = JournalRecovery::from_entries(entries);
+        let journal = Self { path: path.to_path_buf(), writer: Mutex::new(create(path)?) };
+        if let Some(BuiltProposal { proposal_id, height, txs, commitment, block_info }) =
+            recovery.last_built_proposal.clone()
+        {
+            journal.append(&JournalEntry::BuildFinished {
+                proposal_id,
+                height,
+                txs,
+                commitment,
+                block_info,
+            })?;
+        }
+        Ok((journal, recovery))
+    }
+
+    pub fn record_build_started(&self, proposal_id: ProposalId, height: BlockNumber) {
+        self.record(&JournalEntry::BuildStarted { proposal_id, height });
+    }
+
+    pub fn record_txs_fed(&self, proposal_id: ProposalId, txs: &[Transaction]) {
+        self.record(&JournalEntry::TxsFed { proposal_id, txs: txs.to_vec() });
+    }
+
+    pub fn record_build_finished(
+        &self,
+        proposal_id: ProposalId,
+        height: BlockNumber,
+        txs: &[Transaction],
+        commitment: &ProposalCommitment,
+        block_info: &ProposalBlockInfo,
+    ) {
+        self.record(&JournalEntry::BuildFinished {
+            proposal_id,
+            height,
+            txs: txs.to_vec(),
+            commitment: commitment.clone(),
+            block_info: *block_info,
+        });
+    }
+
+    pub fn record_build_aborted(&self, proposal_id: ProposalId) {
+        self.record(&JournalEntry::BuildAborted { proposal_id });
+    }
+
+    /// Discards the recorded proposals, once their height is decided or skipped.
+    pub fn clear(&self) {
+        let mut writer = self.lock_writer();
+        match create(&self.path) {
+            Ok(new_writer) => *writer = new_writer,
+            Err(err) => error!("Failed to clear the proposal journal: {}", err),
+        }
+    }
+
+    // A proposal is generated regardless of the journal; a failed write is only logged.
+    fn record(&self, entry: &JournalEntry) {
+        if let Err(err) = self.append(entry) {
+            error!("Failed to record proposal progress in the journal: {}", err);
+        }
+    }
+
+    fn append(&self, entry: &JournalEntry) -> std::io::Result<()> {
+        let mut writer = self.lock_writer();
+        serde_json::to_writer(&mut *writer, entry)?;
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
+
+    fn lock_writer(&self) -> std::sync::MutexGuard<'_, BufWriter<File>> {
+        self.writer.lock().expect("Proposal journal lock should not be poisoned.")
+    }
+}
+
+// Creates an empty journal file, truncating the existing one.
+fn create(path: &Path) -> std::io::Result<BufWriter<File>> {
+    let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+pub fn read_journal(path: &Path) -> std::io::Result<Vec<JournalEntry>> {
+    let mut entries = vec![];
+    for line in BufReader::new(File::open(path)?).lines() {
+        match serde_json::from_str(&line?) {
+            Ok(entry) => entries.push(entry),
+            // The last line is torn if the batcher crashed while writing it.
+            Err(err) => {
+                warn!("Dropping a malformed line of the proposal journal: {}", err);
+                break;
+            }
+        }
+    }
+    Ok(entries)
+}
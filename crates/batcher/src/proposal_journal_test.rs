@@ -0,0 +1,117 @@
+use std::io::Write;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::felt;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::{ProposalBlockInfo, ProposalCommitment};
+
+use crate::proposal_journal::{
+    read_journal,
+    BuiltProposal,
+    InterruptedProposal,
+    JournalEntry,
+    JournalRecovery,
+    ProposalJournal,
+};
+
+fn tx(tx_hash: u64) -> Transaction {
+    Transaction::Invoke(InvokeTransaction {
+        tx: invoke_tx(InvokeTxArgs::default()),
+        tx_hash: TransactionHash(felt!(tx_hash)),
+    })
+}
+
+#[test]
+fn recovers_the_txs_of_an_interrupted_proposal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+
+    let (journal, recovery) = ProposalJournal::open(&path).unwrap();
+    assert_eq!(recovery, JournalRecovery::default());
+    journal.record_build_started(0, BlockNumber(1));
+    journal.record_txs_fed(0, &[tx(1)]);
+    journal.record_build_aborted(0);
+    journal.record_build_started(1, BlockNumber(1));
+    journal.record_txs_fed(1, &[tx(2)]);
+    journal.record_txs_fed(1, &[tx(3)]);
+    drop(journal);
+
+    let (_, recovery) = ProposalJournal::open(&path).unwrap();
+    assert_eq!(
+        recovery,
+        JournalRecovery {
+            interrupted_proposals: vec![InterruptedProposal {
+                proposal_id: 1,
+                height: BlockNumber(1),
+                txs: vec![tx(2), tx(3)],
+            }],
+            last_built_proposal: None,
+        }
+    );
+}
+
+#[test]
+fn journal_is_compacted_to_the_last_built_proposal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let built_proposal = BuiltProposal {
+        proposal_id: 0,
+        height: BlockNumber(1),
+        txs: vec![tx(1)],
+        commitment: ProposalCommitment::default(),
+        block_info: ProposalBlockInfo::default(),
+    };
+
+    let (journal, _) = ProposalJournal::open(&path).unwrap();
+    journal.record_build_started(0, BlockNumber(1));
+    journal.record_txs_fed(0, &[tx(1), tx(2)]);
+    journal.record_build_finished(
+        0,
+        BlockNumber(1),
+        &[tx(1)],
+        &ProposalCommitment::default(),
+        &ProposalBlockInfo::default(),
+    );
+    journal.record_build_started(1, BlockNumber(1));
+    drop(journal);
+
+    let (_, recovery) = ProposalJournal::open(&path).unwrap();
+    assert_eq!(recovery.last_built_proposal, Some(built_proposal.clone()));
+    assert_eq!(recovery.interrupted_proposals.len(), 1);
+
+    // The interrupted proposal is recovered once.
+    let (journal, recovery) = ProposalJournal::open(&path).unwrap();
+    assert_eq!(
+        recovery,
+        JournalRecovery {
+            interrupted_proposals: vec![],
+            last_built_proposal: Some(built_proposal)
+        }
+    );
+
+    journal.clear();
+    assert_eq!(read_journal(&path).unwrap(), vec![]);
+}
+
+#[test]
+fn torn_last_line_is_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let (journal, _) = ProposalJournal::open(&path).unwrap();
+    journal.record_build_started(0, BlockNumber(1));
+    drop(journal);
+
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap()
+        .write_all(br#"{"TxsFed":{"proposal_id":0,"#)
+        .unwrap();
+
+    assert_eq!(
+        read_journal(&path).unwrap(),
+        vec![JournalEntry::BuildStarted { proposal_id: 0, height: BlockNumber(1) }]
+    );
+}
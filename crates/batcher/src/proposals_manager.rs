@@ -1,48 +1,298 @@
-use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex as StdMutex};
 
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use futures::StreamExt;
+use papyrus_config::converters::deserialize_milliseconds_to_duration;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_api::StarknetApiError;
+use starknet_batcher_types::batcher_types::{
+    ProposalBlockInfo,
+    ProposalCommitment,
+    ProposalMetadata,
+    ProposalProgress,
+    ProposalValidationError,
+};
+pub use starknet_batcher_types::batcher_types::{ProposalId, ProposalRound};
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
+use starknet_l1_provider_types::communication::SharedL1ProviderClient;
+use starknet_mempool_infra::channel_metrics::monitored_channel;
+use starknet_mempool_infra::component_client::ClientError;
+use starknet_mempool_infra::request_context::RequestContext;
 use starknet_mempool_types::communication::{MempoolClientError, SharedMempoolClient};
+use starknet_mempool_types::mempool_types::RejectedTransaction;
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::{oneshot, Mutex, OwnedMutexGuard};
+use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::{debug, error, info, instrument};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+use validator::{Validate, ValidationError};
 
-// TODO: Should be defined in SN_API probably (shared with the consensus).
-pub type ProposalId = u64;
+use crate::block_builder::{
+    BlockBuilderError,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    BuildProgress,
+    ExcludedTransaction,
+    InputTxStream,
+    OutputTxStream,
+    SharedBuildProgress,
+};
+use crate::clock::{timeout, SharedClock};
+use crate::metrics::{
+    record_concurrency_stats,
+    record_outstream_backpressure,
+    record_proposal_aborted,
+    record_proposal_failed,
+    record_proposal_finished,
+    record_proposal_started,
+    ProposalKind,
+    DEGRADED_PROPOSALS,
+};
+use crate::outstream::{forward_content, OutstreamOverflowPolicy};
+use crate::proposal_commitment::calculate_proposal_commitment;
+use crate::proposal_journal::{JournalRecovery, ProposalJournal};
+use crate::system_transactions::{SystemTransactionConfig, SystemTransactionGenerator};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+const DEFAULT_PROPOSAL_CACHE_SIZE: usize = 10;
+const DEFAULT_MAX_L1_HANDLER_TXS_PER_PROPOSAL: usize = 10;
+const DEFAULT_DEADLINE_MARGIN: tokio::time::Duration = tokio::time::Duration::from_millis(100);
+const DEFAULT_MAX_MEMPOOL_REQUEST_WAIT: tokio::time::Duration =
+    tokio::time::Duration::from_millis(50);
+const DEFAULT_MAX_CONCURRENT_VALIDATIONS: usize = 4;
+const DEFAULT_OUTSTREAM_SPILL_BUFFER_SIZE: usize = 1000;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_proposals_manager_config", skip_on_field_errors = false))]
 pub struct ProposalsManagerConfig {
     pub max_txs_per_mempool_request: usize,
+    // The time a mempool request of a generated proposal may take; the mempool answers with the
+    // transactions it took by then, so that a slow mempool doesn't eat the proposal deadline.
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub max_mempool_request_wait: tokio::time::Duration,
+    // The maximal number of pending L1 handler transactions prepended to a generated proposal.
+    pub max_l1_handler_txs_per_proposal: usize,
     pub outstream_content_buffer_size: usize,
+    pub outstream_overflow_policy: OutstreamOverflowPolicy,
+    // The maximal number of transactions of a generated proposal held in memory while its consumer
+    // stalls, by the spill policy.
+    pub outstream_spill_buffer_size: usize,
+    pub use_mempool_tx_stream: bool,
+    #[validate]
+    pub system_transactions: SystemTransactionConfig,
+    #[validate]
+    pub generation_fallback: GenerationFallbackConfig,
+    pub mempool_disconnect_policy: MempoolDisconnectPolicy,
+    // The maximal number of generated proposals kept for re-proposal, 0 disables the cache.
+    pub proposal_cache_size: usize,
+    // The time before the deadline of a generated proposal at which its block builder stops taking
+    // transactions, to leave time for closing the block and streaming its content.
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub deadline_margin: tokio::time::Duration,
+    // The maximal number of proposals of different rounds of a height validated concurrently.
+    #[validate(range(min = 1))]
+    pub max_concurrent_validations: usize,
 }
 
 impl Default for ProposalsManagerConfig {
     fn default() -> Self {
         // TODO: Get correct value for default max_txs_per_mempool_request.
-        Self { max_txs_per_mempool_request: 10, outstream_content_buffer_size: 100 }
+        Self {
+            max_txs_per_mempool_request: 10,
+            max_mempool_request_wait: DEFAULT_MAX_MEMPOOL_REQUEST_WAIT,
+            max_l1_handler_txs_per_proposal: DEFAULT_MAX_L1_HANDLER_TXS_PER_PROPOSAL,
+            outstream_content_buffer_size: 100,
+            outstream_overflow_policy: OutstreamOverflowPolicy::default(),
+            outstream_spill_buffer_size: DEFAULT_OUTSTREAM_SPILL_BUFFER_SIZE,
+            use_mempool_tx_stream: true,
+            system_transactions: SystemTransactionConfig::default(),
+            generation_fallback: GenerationFallbackConfig::default(),
+            mempool_disconnect_policy: MempoolDisconnectPolicy::default(),
+            proposal_cache_size: DEFAULT_PROPOSAL_CACHE_SIZE,
+            deadline_margin: DEFAULT_DEADLINE_MARGIN,
+            max_concurrent_validations: DEFAULT_MAX_CONCURRENT_VALIDATIONS,
+        }
     }
 }
 
+// The transactions of a mempool request are sent to the outstream of the proposal content as
+// they're executed, so a buffer smaller than a request stalls the block builder on its consumer.
+fn validate_proposals_manager_config(
+    config: &ProposalsManagerConfig,
+) -> Result<(), ValidationError> {
+    let max_txs_per_mempool_request = if config.generation_fallback.enable {
+        config
+            .max_txs_per_mempool_request
+            .max(config.generation_fallback.max_txs_per_mempool_request)
+    } else {
+        config.max_txs_per_mempool_request
+    };
+    if config.outstream_content_buffer_size >= max_txs_per_mempool_request {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("Invalid proposals manager configuration.");
+    error.message = Some(
+        format!(
+            "The outstream content buffer size, {}, is smaller than the maximal number of \
+             transactions per mempool request, {max_txs_per_mempool_request}.",
+            config.outstream_content_buffer_size
+        )
+        .into(),
+    );
+    Err(error)
+}
+
 impl SerializeConfig for ProposalsManagerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let members = BTreeMap::from_iter([
             ser_param(
                 "max_txs_per_mempool_request",
                 &self.max_txs_per_mempool_request,
                 "Maximum transactions to get from the mempool per iteration of proposal generation",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_mempool_request_wait",
+                &u64::try_from(self.max_mempool_request_wait.as_millis())
+                    .expect("Mempool request wait should fit u64."),
+                "The time, in milliseconds, a request for transactions of a generated proposal may \
+                 take. Past it, the mempool answers with the transactions it took so far",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_l1_handler_txs_per_proposal",
+                &self.max_l1_handler_txs_per_proposal,
+                "Maximum pending L1 handler transactions to get from the L1 provider at the start \
+                 of a proposal generation",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "outstream_content_buffer_size",
                 &self.outstream_content_buffer_size,
                 "Maximum items to add to the outstream buffer before blocking",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "outstream_overflow_policy",
+                &self.outstream_overflow_policy,
+                "What to do once the consumer of a generated proposal stalls with the outstream \
+                 buffer full: 'Block' waits for it, 'AbortProposal' fails the proposal, and \
+                 'SpillToBuffer' keeps building into an in-memory buffer, waiting once it is full",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "outstream_spill_buffer_size",
+                &self.outstream_spill_buffer_size,
+                "Maximum transactions of a generated proposal held in memory while its consumer \
+                 stalls, by the 'SpillToBuffer' overflow policy",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "use_mempool_tx_stream",
+                &self.use_mempool_tx_stream,
+                "If true, the mempool pushes transactions to proposals over a stream, falling \
+                 back to polling if the stream is unavailable",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "mempool_disconnect_policy",
+                &self.mempool_disconnect_policy,
+                "What to do with a proposal whose generation lost the connection to the mempool: \
+                 'AbortProposal' fails it, and 'FinalizeWithCurrentTxs' closes the block with the \
+                 transactions executed so far",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "proposal_cache_size",
+                &self.proposal_cache_size,
+                "Maximum generated proposals of the current height kept for re-proposal in later \
+                 rounds, evicting the oldest. 0 disables the cache",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "deadline_margin",
+                &u64::try_from(self.deadline_margin.as_millis())
+                    .expect("Deadline margin should fit u64."),
+                "The time before the deadline of a generated proposal, in milliseconds, at which \
+                 its block builder stops adding transactions, leaving time to close the block and \
+                 stream its content",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_concurrent_validations",
+                &self.max_concurrent_validations,
+                "Maximum proposals of different rounds of a height validated concurrently. A \
+                 proposal is generated only while no other proposal is being built",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![
+            members,
+            append_sub_config_name(self.system_transactions.dump(), "system_transactions"),
+            append_sub_config_name(self.generation_fallback.dump(), "generation_fallback"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// How a proposal generation handles losing the connection to the mempool.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MempoolDisconnectPolicy {
+    /// Fail the proposal.
+    #[default]
+    AbortProposal,
+    /// Close the block with the transactions executed so far, keeping the chain live during
+    /// mempool outages.
+    FinalizeWithCurrentTxs,
+}
+
+/// Conservative settings of a single retry of a proposal generation that failed due to an internal
+/// error, to improve liveness under partial faults.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct GenerationFallbackConfig {
+    pub enable: bool,
+    pub max_txs_per_mempool_request: usize,
+    pub max_txs: usize,
+}
+
+impl Default for GenerationFallbackConfig {
+    fn default() -> Self {
+        Self { enable: true, max_txs_per_mempool_request: 1, max_txs: 100 }
+    }
+}
+
+impl SerializeConfig for GenerationFallbackConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, a proposal generation that fails due to an internal error before adding \
+                 any transaction is retried once with the fallback settings",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_txs_per_mempool_request",
+                &self.max_txs_per_mempool_request,
+                "Maximum transactions to get from the mempool per iteration of the retried \
+                 proposal generation",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_txs",
+                &self.max_txs,
+                "Maximum mempool transactions in the retried proposal",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -57,14 +307,332 @@ pub enum ProposalsManagerError {
         current_generating_proposal_id: ProposalId,
         new_proposal_id: ProposalId,
     },
+    #[error(transparent)]
+    BlockBuilderError(#[from] BlockBuilderError),
+    #[error(
+        "Proposal with id {proposal_id} is not active. Active proposal id: {active_proposal_id:?}."
+    )]
+    ProposalNotActive { proposal_id: ProposalId, active_proposal_id: Option<ProposalId> },
     #[error("Internal error.")]
     InternalError,
     #[error(transparent)]
     MempoolError(#[from] MempoolClientError),
+    #[error("Proposal with id {proposal_id} was not found.")]
+    ProposalNotFound { proposal_id: ProposalId },
+    #[error("Failed to generate the system transactions: {0}")]
+    SystemTransactionError(#[from] StarknetApiError),
+    #[error(
+        "Proposal {proposal_id} of {round} is stale, a proposal of {latest_round} was already \
+         started."
+    )]
+    StaleProposal { proposal_id: ProposalId, round: ProposalRound, latest_round: ProposalRound },
+    #[error(
+        "Proposal {proposal_id} can't be re-proposed: {}.",
+        if *.evicted {
+            "it was evicted from the proposal cache"
+        } else {
+            "it was not generated at the current height"
+        }
+    )]
+    ProposalNotCached { proposal_id: ProposalId, evicted: bool },
+    #[error(
+        "Received proposal validation request with id {proposal_id} of {round} while already \
+         validating proposal with id {validating_proposal_id} of the same round."
+    )]
+    AlreadyValidatingRound {
+        validating_proposal_id: ProposalId,
+        proposal_id: ProposalId,
+        round: ProposalRound,
+    },
+    #[error(
+        "Received proposal validation request with id {proposal_id} while already validating \
+         {max_concurrent_validations} proposals, the oldest with id {oldest_validating_proposal_id}."
+    )]
+    TooManyConcurrentValidations {
+        oldest_validating_proposal_id: ProposalId,
+        proposal_id: ProposalId,
+        max_concurrent_validations: usize,
+    },
+    #[error("Proposal id {proposal_id} was already used at the current height.")]
+    DuplicateProposalId { proposal_id: ProposalId },
+    #[error(
+        "The consumer of the proposal content stalled, overflowing its outstream buffer of \
+         {buffer_size} transactions."
+    )]
+    OutstreamOverflow { buffer_size: usize },
+}
+
+impl CodedError for ProposalsManagerError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ProposalsManagerError::AlreadyGeneratingProposal { .. } => {
+                codes::PROPOSALS_MANAGER_ALREADY_GENERATING_PROPOSAL
+            }
+            ProposalsManagerError::BlockBuilderError(_) => {
+                codes::PROPOSALS_MANAGER_BLOCK_BUILDER_ERROR
+            }
+            ProposalsManagerError::ProposalNotActive { .. } => {
+                codes::PROPOSALS_MANAGER_PROPOSAL_NOT_ACTIVE
+            }
+            ProposalsManagerError::InternalError => codes::PROPOSALS_MANAGER_INTERNAL_ERROR,
+            ProposalsManagerError::MempoolError(err) => err.error_code(),
+            ProposalsManagerError::ProposalNotFound { .. } => {
+                codes::PROPOSALS_MANAGER_PROPOSAL_NOT_FOUND
+            }
+            ProposalsManagerError::SystemTransactionError(_) => {
+                codes::PROPOSALS_MANAGER_SYSTEM_TRANSACTION_ERROR
+            }
+            ProposalsManagerError::StaleProposal { .. } => codes::PROPOSALS_MANAGER_STALE_PROPOSAL,
+            ProposalsManagerError::ProposalNotCached { .. } => {
+                codes::PROPOSALS_MANAGER_PROPOSAL_NOT_CACHED
+            }
+            ProposalsManagerError::AlreadyValidatingRound { .. } => {
+                codes::PROPOSALS_MANAGER_ALREADY_VALIDATING_ROUND
+            }
+            ProposalsManagerError::TooManyConcurrentValidations { .. } => {
+                codes::PROPOSALS_MANAGER_TOO_MANY_CONCURRENT_VALIDATIONS
+            }
+            ProposalsManagerError::DuplicateProposalId { .. } => {
+                codes::PROPOSALS_MANAGER_DUPLICATE_PROPOSAL_ID
+            }
+            ProposalsManagerError::OutstreamOverflow { .. } => {
+                codes::PROPOSALS_MANAGER_OUTSTREAM_OVERFLOW
+            }
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            // Consensus may race the batcher, e.g., by asking for a proposal it already aborted.
+            ProposalsManagerError::AlreadyGeneratingProposal { .. }
+            | ProposalsManagerError::ProposalNotActive { .. }
+            | ProposalsManagerError::ProposalNotFound { .. }
+            | ProposalsManagerError::StaleProposal { .. }
+            | ProposalsManagerError::ProposalNotCached { .. }
+            | ProposalsManagerError::AlreadyValidatingRound { .. }
+            | ProposalsManagerError::TooManyConcurrentValidations { .. }
+            | ProposalsManagerError::DuplicateProposalId { .. } => Severity::Warning,
+            ProposalsManagerError::MempoolError(err) => err.severity(),
+            ProposalsManagerError::BlockBuilderError(_)
+            | ProposalsManagerError::InternalError
+            | ProposalsManagerError::SystemTransactionError(_)
+            | ProposalsManagerError::OutstreamOverflow { .. } => Severity::Error,
+        }
+    }
 }
 
 pub type ProposalsManagerResult<T> = Result<T, ProposalsManagerError>;
 
+// Creates the transaction stream of a retried proposal generation.
+type TxStreamFactory = Box<dyn FnOnce() -> InputTxStream + Send>;
+
+// Transactions taken from the mempool for the proposal in generation, and not yet fed to the block
+// builder. Returned to the mempool once the generation ends.
+type InFlightTxs = Arc<StdMutex<VecDeque<Transaction>>>;
+
+// Receives the transactions the mempool pushes over its transaction stream. Locked by the proposal
+// in generation while the stream is open for it.
+type MempoolTxStream = Arc<Mutex<Receiver<Transaction>>>;
+
+// The error that disconnected the proposal in generation from the mempool, if any.
+type MempoolDisconnect = Arc<StdMutex<Option<MempoolClientError>>>;
+
+/// The outcome of validating a proposal received from another node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProposalValidationResult {
+    /// Every proposed transaction was executed, in the proposed order, into a block of the given
+    /// commitment.
+    Valid { commitment: ProposalCommitment },
+    /// The executed content differs from the proposed one, e.g., a proposed transaction was not
+    /// executed, or the deadline was reached before the whole proposal was received.
+    ContentMismatch { n_proposed_txs: usize, n_executed_txs: usize, error: ProposalValidationError },
+}
+
+// A block built for a proposal generated or validated successfully, pending the decision.
+#[derive(Debug)]
+struct CompletedProposal {
+    artifacts: BlockExecutionArtifacts,
+    commitment: ProposalCommitment,
+    metadata: ProposalMetadata,
+}
+
+impl CompletedProposal {
+    // The commitment is computed as part of finishing the build, so that it is ready once the
+    // proposer or the validators ask for it.
+    fn new(artifacts: BlockExecutionArtifacts, build_duration: tokio::time::Duration) -> Self {
+        let commitment = calculate_proposal_commitment(&artifacts);
+        let metadata = artifacts.metadata(build_duration);
+        Self { artifacts, commitment, metadata }
+    }
+}
+
+type CompletedProposals = Arc<Mutex<HashMap<ProposalId, CompletedProposal>>>;
+
+/// The content and commitment of a block generated for a proposal of this node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedProposal {
+    pub txs: Vec<Transaction>,
+    pub commitment: ProposalCommitment,
+    pub block_info: ProposalBlockInfo,
+}
+
+/// The latest proposals generated for the current height, so that consensus can re-propose them
+/// in later rounds without rebuilding them. Holds up to `capacity` proposals, evicting the oldest.
+#[derive(Debug)]
+struct ProposalCache {
+    capacity: usize,
+    proposals: HashMap<ProposalId, Arc<CachedProposal>>,
+    // The cached proposal ids, oldest first.
+    insertion_order: VecDeque<ProposalId>,
+    // The proposals of the current height that were evicted, so that a re-proposal of one of them
+    // is told apart from a re-proposal of an unknown proposal.
+    evicted: HashSet<ProposalId>,
+}
+
+impl ProposalCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            proposals: HashMap::new(),
+            insertion_order: VecDeque::new(),
+            evicted: HashSet::new(),
+        }
+    }
+
+    fn insert(&mut self, proposal_id: ProposalId, proposal: CachedProposal) {
+        if self.capacity == 0 {
+            self.evicted.insert(proposal_id);
+            return;
+        }
+        self.evicted.remove(&proposal_id);
+        if self.proposals.insert(proposal_id, Arc::new(proposal)).is_some() {
+            self.insertion_order.retain(|cached_id| *cached_id != proposal_id);
+        }
+        self.insertion_order.push_back(proposal_id);
+        while self.insertion_order.len() > self.capacity {
+            let evicted_id =
+                self.insertion_order.pop_front().expect("The cache should not be empty.");
+            self.proposals.remove(&evicted_id);
+            self.evicted.insert(evicted_id);
+            debug!("Evicted proposal {} from the proposal cache.", evicted_id);
+        }
+    }
+
+    fn get(&self, proposal_id: ProposalId) -> Option<Arc<CachedProposal>> {
+        self.proposals.get(&proposal_id).cloned()
+    }
+
+    fn is_evicted(&self, proposal_id: ProposalId) -> bool {
+        self.evicted.contains(&proposal_id)
+    }
+
+    fn clear(&mut self) {
+        self.proposals.clear();
+        self.insertion_order.clear();
+        self.evicted.clear();
+    }
+}
+
+type SharedProposalCache = Arc<StdMutex<ProposalCache>>;
+
+// The proposals whose blocks are being built. Either a single proposal is generated, or proposals
+// of different rounds are validated concurrently, so that a proposal of a later round can be
+// validated before the validation of an earlier one is done.
+#[derive(Clone, Debug, Default)]
+struct ActiveProposals {
+    generation: Option<ProposalId>,
+    validations: BTreeMap<ProposalId, ProposalRound>,
+}
+
+impl ActiveProposals {
+    fn contains(&self, proposal_id: ProposalId) -> bool {
+        self.generation == Some(proposal_id) || self.validations.contains_key(&proposal_id)
+    }
+
+    fn proposal_ids(&self) -> Vec<ProposalId> {
+        self.generation.into_iter().chain(self.validations.keys().copied()).collect()
+    }
+
+    // The proposal being generated, or else the validated proposal of the latest round.
+    fn latest(&self) -> Option<ProposalId> {
+        self.generation.or_else(|| {
+            self.validations
+                .iter()
+                .max_by_key(|(_, round)| **round)
+                .map(|(&proposal_id, _)| proposal_id)
+        })
+    }
+
+    // The validated proposal of the earliest round.
+    fn oldest_validation(&self) -> Option<ProposalId> {
+        self.validations
+            .iter()
+            .min_by_key(|(_, round)| **round)
+            .map(|(&proposal_id, _)| proposal_id)
+    }
+
+    fn remove(&mut self, proposal_id: ProposalId) {
+        if self.generation == Some(proposal_id) {
+            self.generation = None;
+        }
+        self.validations.remove(&proposal_id);
+    }
+
+    // A proposal is generated only while no other proposal is built.
+    fn start_generation(&mut self, proposal_id: ProposalId) -> ProposalsManagerResult<()> {
+        if let Some(active_proposal_id) = self.latest() {
+            return Err(ProposalsManagerError::AlreadyGeneratingProposal {
+                current_generating_proposal_id: active_proposal_id,
+                new_proposal_id: proposal_id,
+            });
+        }
+        self.generation = Some(proposal_id);
+        Ok(())
+    }
+
+    // A proposal is validated while no proposal is generated, no other proposal of its round is
+    // validated, and fewer than the maximal number of proposals are validated.
+    fn start_validation(
+        &mut self,
+        proposal_id: ProposalId,
+        round: ProposalRound,
+        max_concurrent_validations: usize,
+    ) -> ProposalsManagerResult<()> {
+        if let Some(generated_proposal_id) = self.generation {
+            return Err(ProposalsManagerError::AlreadyGeneratingProposal {
+                current_generating_proposal_id: generated_proposal_id,
+                new_proposal_id: proposal_id,
+            });
+        }
+        let same_round_validation =
+            self.validations.iter().find(|(_, validated_round)| **validated_round == round);
+        if let Some((&validating_proposal_id, _)) = same_round_validation {
+            return Err(ProposalsManagerError::AlreadyValidatingRound {
+                validating_proposal_id,
+                proposal_id,
+                round,
+            });
+        }
+        if let Some(oldest_validating_proposal_id) = self.oldest_validation() {
+            if self.validations.len() >= max_concurrent_validations {
+                return Err(ProposalsManagerError::TooManyConcurrentValidations {
+                    oldest_validating_proposal_id,
+                    proposal_id,
+                    max_concurrent_validations,
+                });
+            }
+        }
+        self.validations.insert(proposal_id, round);
+        Ok(())
+    }
+}
+
+type SharedActiveProposals = Arc<Mutex<ActiveProposals>>;
+
+/// Receives the result of a proposal validation once it is done.
+pub type ProposalValidationReceiver =
+    oneshot::Receiver<ProposalsManagerResult<ProposalValidationResult>>;
+
 /// Main struct for handling block proposals.
 /// Taking care of:
 /// - Proposing new blocks.
@@ -72,150 +640,1298 @@ pub type ProposalsManagerResult<T> = Result<T, ProposalsManagerError>;
 /// - Commiting accepted proposals to the storage.
 ///
 /// Triggered by the consensus.
-// TODO: Remove dead_code attribute.
-#[allow(dead_code)]
-pub(crate) struct ProposalsManager {
+pub struct ProposalsManager {
     config: ProposalsManagerConfig,
     mempool_client: SharedMempoolClient,
-    /// The block proposal that is currently being proposed, if any.
-    /// At any given time, there can be only one proposal being actively executed (either proposed
-    /// or validated).
-    proposal_in_generation: Arc<Mutex<Option<ProposalId>>>,
+    /// Provides the L1 handler transactions of generated proposals, if available.
+    l1_provider_client: Option<SharedL1ProviderClient>,
+    block_builder: Arc<dyn BlockBuilderTrait>,
+    system_tx_generator: SystemTransactionGenerator,
+    /// Receives the transactions the mempool pushes while its transaction stream is open, if
+    /// available. Transactions left over from a proposal are returned to the mempool once the
+    /// stream is closed.
+    mempool_tx_stream: Option<MempoolTxStream>,
+    /// The proposals whose blocks are being built: either a single generated proposal, or
+    /// validated proposals of different rounds.
+    active_proposals: SharedActiveProposals,
+    /// The tasks building the blocks of the active proposals.
+    active_tasks: HashMap<ProposalId, ActiveProposalTask>,
+    in_flight_txs: InFlightTxs,
+    /// The blocks built for the proposals of the current height, until a decision is reached.
+    completed_proposals: CompletedProposals,
+    /// The latest proposals generated for the current height, for re-proposal.
+    proposal_cache: SharedProposalCache,
+    /// The ids of the proposals started at the current height. Ids are chosen by consensus, so a
+    /// proposal started with a used id, e.g., by a retried request, is rejected instead of
+    /// replacing the block of the earlier proposal.
+    used_proposal_ids: HashSet<ProposalId>,
+    /// The latest round a proposal was started in. Proposals of earlier rounds, including the
+    /// rounds of earlier heights, are stale.
+    latest_round: Option<ProposalRound>,
+    /// The clock the deadlines and timeouts of the proposals are measured by.
+    clock: SharedClock,
+    /// Records the progress of the generated proposals, for recovery after a crash, if enabled.
+    journal: Option<Arc<ProposalJournal>>,
+    /// The progress of the block being built for the active proposal, published by the block
+    /// builder.
+    build_progress: SharedBuildProgress,
 }
 
 impl ProposalsManager {
-    // TODO: Remove dead_code attribute.
-    #[allow(dead_code)]
-    pub fn new(config: ProposalsManagerConfig, mempool_client: SharedMempoolClient) -> Self {
-        Self { config, mempool_client, proposal_in_generation: Arc::new(Mutex::new(None)) }
+    pub fn new(
+        config: ProposalsManagerConfig,
+        mempool_client: SharedMempoolClient,
+        l1_provider_client: Option<SharedL1ProviderClient>,
+        block_builder: Arc<dyn BlockBuilderTrait>,
+        mempool_tx_stream: Option<Receiver<Transaction>>,
+        clock: SharedClock,
+    ) -> Self {
+        let system_tx_generator = SystemTransactionGenerator::new(&config.system_transactions);
+        let proposal_cache =
+            Arc::new(StdMutex::new(ProposalCache::new(config.proposal_cache_size)));
+        Self {
+            config,
+            mempool_client,
+            l1_provider_client,
+            block_builder,
+            system_tx_generator,
+            mempool_tx_stream: mempool_tx_stream.map(|receiver| Arc::new(Mutex::new(receiver))),
+            active_proposals: Default::default(),
+            active_tasks: HashMap::new(),
+            in_flight_txs: Default::default(),
+            completed_proposals: Default::default(),
+            proposal_cache,
+            used_proposal_ids: HashSet::new(),
+            latest_round: None,
+            clock,
+            journal: None,
+            build_progress: SharedBuildProgress::default(),
+        }
+    }
+
+    pub fn with_journal(mut self, journal: Arc<ProposalJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    pub fn with_build_progress(mut self, build_progress: SharedBuildProgress) -> Self {
+        self.build_progress = build_progress;
+        self
+    }
+
+    /// Recovers the proposals of the journal of a crashed batcher: the transactions of the
+    /// interrupted proposals are returned to the mempool, and the last built proposal is cached
+    /// for re-proposal if it is of the given height, so that its content and commitment are
+    /// answered as before the crash. Its block is not recovered, so it can't be decided on.
+    #[instrument(skip(self, recovery))]
+    pub async fn recover(&mut self, recovery: JournalRecovery, height: BlockNumber) {
+        let JournalRecovery { interrupted_proposals, last_built_proposal } = recovery;
+        for interrupted_proposal in interrupted_proposals {
+            warn!(
+                "Proposal {} of height {} was interrupted, returning its {} transactions to the \
+                 mempool.",
+                interrupted_proposal.proposal_id,
+                interrupted_proposal.height,
+                interrupted_proposal.txs.len()
+            );
+            self.in_flight_txs
+                .lock()
+                .expect("In-flight transactions lock is poisoned.")
+                .extend(interrupted_proposal.txs);
+            return_in_flight_txs(&self.mempool_client, &self.in_flight_txs).await;
+        }
+        let Some(built_proposal) = last_built_proposal else {
+            return;
+        };
+        if built_proposal.height != height {
+            debug!(
+                "Dropping the last built proposal {} of height {}.",
+                built_proposal.proposal_id, built_proposal.height
+            );
+            return;
+        }
+        info!("Recovered the last built proposal {}.", built_proposal.proposal_id);
+        self.lock_proposal_cache().insert(
+            built_proposal.proposal_id,
+            CachedProposal {
+                txs: built_proposal.txs,
+                commitment: built_proposal.commitment,
+                block_info: built_proposal.block_info,
+            },
+        );
     }
 
-    /// Starts a new block proposal generation task for the given proposal_id and height with
-    /// transactions from the mempool.
+    /// Starts a new block proposal generation task for the given proposal_id and round with
+    /// the pending L1 handler transactions, followed by transactions from the mempool. The block is
+    /// built with the given block info.
     #[instrument(skip(self))]
     pub async fn generate_block_proposal(
         &mut self,
         proposal_id: ProposalId,
         timeout: tokio::time::Instant,
-        _height: BlockNumber,
-    ) -> ProposalsManagerResult<ReceiverStream<Transaction>> {
+        round: ProposalRound,
+        block_info: ProposalBlockInfo,
+    ) -> ProposalsManagerResult<OutputTxStream> {
         info!("Starting generation of new proposal.");
-        self.set_proposal_in_generation(proposal_id).await?;
+        let mut prefix_txs =
+            self.system_tx_generator.generate(round.height, block_info.timestamp)?;
+        self.set_proposal_active(proposal_id, round, ProposalKind::Generation).await?;
+        record_proposal_started(ProposalKind::Generation);
+        if let Some(journal) = &self.journal {
+            journal.record_build_started(proposal_id, round.height);
+        }
+        prefix_txs.extend(self.get_l1_handler_txs().await);
+        let build_deadline = self.build_deadline(timeout);
+
+        let pushed_txs = self.open_mempool_tx_stream().await;
+        let open_mempool_tx_stream =
+            if pushed_txs.is_some() { self.mempool_tx_stream.clone() } else { None };
+        let mempool_disconnect = MempoolDisconnect::default();
+        let tx_stream = MempoolTxSource {
+            mempool_client: self.mempool_client.clone(),
+            max_txs_per_mempool_request: self.config.max_txs_per_mempool_request,
+            max_mempool_request_wait: self.config.max_mempool_request_wait,
+            deadline: build_deadline,
+            pushed_txs,
+            in_flight_txs: self.in_flight_txs.clone(),
+            mempool_disconnect: mempool_disconnect.clone(),
+            clock: self.clock.clone(),
+            proposal_id,
+            journal: self.journal.clone(),
+        }
+        .into_stream();
+        let fallback_tx_stream = self.fallback_tx_stream_factory(
+            proposal_id,
+            prefix_txs.clone(),
+            build_deadline,
+            mempool_disconnect.clone(),
+        );
+        // System transactions are executed first, then L1 handler transactions, then user
+        // transactions.
+        let tx_stream = Box::pin(futures::stream::iter(prefix_txs).chain(tx_stream));
+        Ok(self.spawn_proposal_generation(
+            proposal_id,
+            round.height,
+            block_info,
+            build_deadline,
+            tx_stream,
+            fallback_tx_stream,
+            open_mempool_tx_stream,
+            mempool_disconnect,
+        ))
+    }
+
+    /// Starts validating the proposal with the given id and round, by building a block with the
+    /// given block info out of the proposed transactions in `tx_stream` instead of the mempool. The
+    /// result is reported over the returned receiver once the stream ends or the deadline is
+    /// reached. Proposals of different rounds are validated concurrently, up to the configured
+    /// maximum, but not while a proposal is generated.
+    #[instrument(skip(self, tx_stream))]
+    pub async fn validate_block_proposal(
+        &mut self,
+        proposal_id: ProposalId,
+        deadline: tokio::time::Instant,
+        round: ProposalRound,
+        block_info: ProposalBlockInfo,
+        tx_stream: InputTxStream,
+    ) -> ProposalsManagerResult<ProposalValidationReceiver> {
+        info!("Starting validation of proposal.");
+        let system_tx_hashes = self
+            .system_tx_generator
+            .generate(round.height, block_info.timestamp)?
+            .iter()
+            .map(Transaction::tx_hash)
+            .collect();
+        self.set_proposal_active(proposal_id, round, ProposalKind::Validation).await?;
+        record_proposal_started(ProposalKind::Validation);
+
+        let (result_sender, result_receiver) = oneshot::channel();
+        let cancellation_token = CancellationToken::new();
+        let handle = tokio::spawn(
+            ProposalValidationTask {
+                proposal_id,
+                block_info,
+                deadline,
+                block_builder: self.block_builder.clone(),
+                tx_stream,
+                system_tx_hashes,
+                outstream_content_buffer_size: self.config.outstream_content_buffer_size,
+                active_proposals: self.active_proposals.clone(),
+                completed_proposals: self.completed_proposals.clone(),
+                result_sender,
+                cancellation_token: cancellation_token.clone(),
+                clock: self.clock.clone(),
+            }
+            .run(),
+        );
+        self.active_tasks
+            .insert(proposal_id, ActiveProposalTask { handle, cancellation_token, deadline });
+        Ok(result_receiver)
+    }
+
+    /// Takes the block built for the proposal consensus decided on, discarding the other proposals
+    /// of the height. The proposals of the height still being built, e.g., the validations of
+    /// other rounds, are aborted.
+    #[instrument(skip(self))]
+    pub async fn decision_reached(
+        &mut self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<BlockExecutionArtifacts> {
+        let CompletedProposal { artifacts, .. } = self
+            .completed_proposals
+            .lock()
+            .await
+            .remove(&proposal_id)
+            .ok_or(ProposalsManagerError::ProposalNotFound { proposal_id })?;
+        // Aborted before the completed proposals are discarded, so that no block of the decided
+        // height is completed afterwards.
+        let aborted_proposals = self.abort_active_proposals().await;
+        if aborted_proposals.generation.is_some() {
+            self.return_unused_mempool_txs().await;
+        }
+        if !aborted_proposals.proposal_ids().is_empty() {
+            warn!(
+                "Aborted proposals {:?}, as a decision was reached.",
+                aborted_proposals.proposal_ids()
+            );
+        }
+        self.completed_proposals.lock().await.clear();
+        self.used_proposal_ids.clear();
+        self.discard_cached_proposals();
+        self.clear_journal();
+        // The proposals of the decided height are stale from now on.
+        if let Some(latest_round) = &mut self.latest_round {
+            *latest_round =
+                ProposalRound { height: latest_round.height.unchecked_next(), round: 0 };
+        }
+        Ok(artifacts)
+    }
 
-        let (sender, receiver) =
-            tokio::sync::mpsc::channel::<Transaction>(self.config.outstream_content_buffer_size);
+    /// Streams the content of a proposal generated earlier in the height, e.g., to re-propose it
+    /// in a later round, without rebuilding its block. Returned along with the block info the
+    /// proposal was generated with.
+    #[instrument(skip(self))]
+    pub fn stream_cached_proposal(
+        &self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<(OutputTxStream, ProposalBlockInfo)> {
+        let cached_proposal = self
+            .lock_proposal_cache()
+            .get(proposal_id)
+            .ok_or(ProposalsManagerError::ProposalNotFound { proposal_id })?;
+        debug!("Streaming the {} cached transactions.", cached_proposal.txs.len());
+        Ok((
+            Box::pin(futures::stream::iter(cached_proposal.txs.clone())),
+            cached_proposal.block_info,
+        ))
+    }
+
+    /// Re-proposes a proposal generated earlier in the height in the given round, e.g., once
+    /// consensus locked on it in a previous round: streams its cached content and returns its
+    /// commitment, without rebuilding its block. Fails if the proposal is not cached, e.g., it was
+    /// evicted or not generated by this node.
+    #[instrument(skip(self))]
+    pub fn repropose(
+        &mut self,
+        proposal_id: ProposalId,
+        round: ProposalRound,
+    ) -> ProposalsManagerResult<(OutputTxStream, ProposalCommitment, ProposalBlockInfo)> {
+        if let Some(latest_round) = self.latest_round {
+            if round < latest_round {
+                return Err(ProposalsManagerError::StaleProposal {
+                    proposal_id,
+                    round,
+                    latest_round,
+                });
+            }
+        }
+        let cached_proposal = {
+            let proposal_cache = self.lock_proposal_cache();
+            proposal_cache.get(proposal_id).ok_or_else(|| {
+                ProposalsManagerError::ProposalNotCached {
+                    proposal_id,
+                    evicted: proposal_cache.is_evicted(proposal_id),
+                }
+            })?
+        };
+        self.latest_round = Some(round);
+        info!("Re-proposing the {} cached transactions.", cached_proposal.txs.len());
+        Ok((
+            Box::pin(futures::stream::iter(cached_proposal.txs.clone())),
+            cached_proposal.commitment.clone(),
+            cached_proposal.block_info,
+        ))
+    }
+
+    /// Discards the cached proposals, once they can no longer be re-proposed.
+    pub fn discard_cached_proposals(&self) {
+        self.lock_proposal_cache().clear();
+    }
+
+    /// Returns the commitment of the block built for the given proposal.
+    pub async fn commitment(
+        &self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<ProposalCommitment> {
+        if let Some(cached_proposal) = self.lock_proposal_cache().get(proposal_id) {
+            return Ok(cached_proposal.commitment.clone());
+        }
+        let completed_proposals = self.completed_proposals.lock().await;
+        let completed_proposal = completed_proposals
+            .get(&proposal_id)
+            .ok_or(ProposalsManagerError::ProposalNotFound { proposal_id })?;
+        Ok(completed_proposal.commitment.clone())
+    }
+
+    /// Returns the resources used by the block built for the given proposal.
+    pub async fn metadata(
+        &self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<ProposalMetadata> {
+        let completed_proposals = self.completed_proposals.lock().await;
+        let completed_proposal = completed_proposals
+            .get(&proposal_id)
+            .ok_or(ProposalsManagerError::ProposalNotFound { proposal_id })?;
+        Ok(completed_proposal.metadata.clone())
+    }
+
+    /// Returns the progress of building the block of the given proposal: of the block being built,
+    /// if it is an active proposal, or of the built block, if it is completed. The block builder
+    /// publishes the progress of a single block, so while proposals are validated concurrently,
+    /// only their time remaining is reported.
+    pub async fn progress(
+        &self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<ProposalProgress> {
+        let (is_active, is_built_alone) = {
+            let active_proposals = self.active_proposals.lock().await;
+            let is_active = active_proposals.contains(proposal_id);
+            (is_active, is_active && active_proposals.validations.len() <= 1)
+        };
+        if is_active {
+            if let Some(active_task) = self.active_tasks.get(&proposal_id) {
+                let BuildProgress { n_executed_txs, n_reverted_txs, gas_consumed } =
+                    if is_built_alone { self.build_progress.get() } else { Default::default() };
+                return Ok(ProposalProgress {
+                    n_executed_txs,
+                    n_reverted_txs,
+                    l1_gas_used: gas_consumed.l1_gas,
+                    l1_data_gas_used: gas_consumed.l1_data_gas,
+                    l2_gas_used: gas_consumed.l2_gas,
+                    time_remaining: active_task
+                        .deadline
+                        .saturating_duration_since(self.clock.now()),
+                    is_done: false,
+                });
+            }
+        }
+        let metadata = self.metadata(proposal_id).await?;
+        Ok(ProposalProgress {
+            n_executed_txs: metadata.n_txs,
+            n_reverted_txs: metadata.n_reverted_txs,
+            l1_gas_used: metadata.l1_gas_used,
+            l1_data_gas_used: metadata.l1_data_gas_used,
+            l2_gas_used: metadata.l2_gas_used,
+            time_remaining: tokio::time::Duration::ZERO,
+            is_done: true,
+        })
+    }
+
+    /// Returns the proposal currently being generated, or else the validated proposal of the
+    /// latest round, if any.
+    pub async fn active_proposal_id(&self) -> Option<ProposalId> {
+        self.active_proposals.lock().await.latest()
+    }
+
+    /// Returns the proposals whose blocks were built, in ascending order.
+    pub async fn completed_proposal_ids(&self) -> Vec<ProposalId> {
+        let mut proposal_ids: Vec<_> =
+            self.completed_proposals.lock().await.keys().copied().collect();
+        proposal_ids.sort();
+        proposal_ids
+    }
+
+    /// Discards the block built for the given proposal, so that it can't be decided on.
+    #[instrument(skip(self))]
+    pub async fn discard_completed_proposal(&mut self, proposal_id: ProposalId) {
+        self.completed_proposals.lock().await.remove(&proposal_id);
+    }
+
+    /// Moves on to the given height: aborts the proposal in progress, if any, and discards the
+    /// proposals of the previous height. Proposals of earlier heights are stale from now on.
+    #[instrument(skip(self))]
+    pub async fn start_height(&mut self, height: BlockNumber) {
+        self.discard_height().await;
+        self.latest_round = Some(ProposalRound { height, round: 0 });
+    }
+
+    /// Aborts the proposals in progress and discards the proposals of the active height, which
+    /// starts over from its first round, e.g., once an admin forced the node to resync it. Returns
+    /// the id of the aborted proposal, as [`Self::force_abort`].
+    #[instrument(skip(self))]
+    pub async fn restart_height(&mut self) -> Option<ProposalId> {
+        let aborted_proposal_id = self.discard_height().await;
+        if let Some(latest_round) = &mut self.latest_round {
+            latest_round.round = 0;
+        }
+        aborted_proposal_id
+    }
+
+    /// Aborts the given proposal, which must be the one currently being generated or one of the
+    /// proposals being validated, e.g., when its consensus round times out or a higher-priority
+    /// proposal arrives. The validations of other rounds go on.
+    #[instrument(skip(self))]
+    pub async fn abort_active_proposal(
+        &mut self,
+        proposal_id: ProposalId,
+    ) -> ProposalsManagerResult<()> {
+        let (is_active, is_generated, active_proposal_id) = {
+            let active_proposals = self.active_proposals.lock().await;
+            (
+                active_proposals.contains(proposal_id),
+                active_proposals.generation == Some(proposal_id),
+                active_proposals.latest(),
+            )
+        };
+        if !is_active {
+            return Err(ProposalsManagerError::ProposalNotActive {
+                proposal_id,
+                active_proposal_id,
+            });
+        }
+        if is_generated {
+            self.force_abort().await;
+            return Ok(());
+        }
+        if let Some(active_task) = self.active_tasks.remove(&proposal_id) {
+            active_task.abort().await;
+        }
+        self.active_proposals.lock().await.remove(proposal_id);
+        warn!("Aborted the validation of proposal {}.", proposal_id);
+        record_proposal_aborted();
+        Ok(())
+    }
+
+    /// Aborts the proposals currently being generated or validated, if any, and resets the manager
+    /// so a new proposal can be started. Transactions taken from the mempool and not yet fed to the
+    /// block builder are returned to it. Returns the id of the aborted generated proposal, or else
+    /// of the aborted validated proposal of the latest round.
+    #[instrument(skip(self))]
+    pub async fn force_abort(&mut self) -> Option<ProposalId> {
+        let aborted_proposals = self.abort_active_proposals().await;
+        self.return_unused_mempool_txs().await;
+        match aborted_proposals.proposal_ids().as_slice() {
+            [] => info!("Force abort requested while no proposal is being generated."),
+            proposal_ids => warn!("Force aborted proposals {:?}.", proposal_ids),
+        }
+        aborted_proposals.latest()
+    }
+
+    /// Stops the proposals currently being generated or validated, if any, before the node shuts
+    /// down. Unlike [`Self::force_abort`], the block builders are signaled to stop taking
+    /// transactions and are waited for to unwind, so no half-built block is left behind; the
+    /// proposals are neither stored nor reported. Transactions taken from the mempool and not yet
+    /// fed to the block builder are returned to it. Returns the id of the stopped generated
+    /// proposal, or else of the stopped validated proposal of the latest round.
+    #[instrument(skip(self))]
+    pub async fn shutdown(&mut self) -> Option<ProposalId> {
+        // Taken before the tasks unwind, as the tasks clear their proposals once done.
+        let stopped_proposals = self.active_proposals.lock().await.clone();
+        for (_, ActiveProposalTask { handle, cancellation_token, .. }) in self.active_tasks.drain()
+        {
+            cancellation_token.cancel();
+            if let Err(err) = handle.await {
+                error!("Proposal task failed to unwind: {}", err);
+            }
+        }
+        if let Some(mempool_tx_stream) = &self.mempool_tx_stream {
+            close_mempool_tx_stream(&self.mempool_client, mempool_tx_stream, &self.in_flight_txs)
+                .await;
+        }
+        return_in_flight_txs(&self.mempool_client, &self.in_flight_txs).await;
+        *self.active_proposals.lock().await = ActiveProposals::default();
+        let stopped_proposal_ids = stopped_proposals.proposal_ids();
+        if !stopped_proposal_ids.is_empty() {
+            info!("Stopped proposals {:?} on shutdown.", stopped_proposal_ids);
+        }
+        self.record_proposals_aborted(&stopped_proposals);
+        stopped_proposals.latest()
+    }
+
+    // Takes the mempool transaction stream and asks the mempool to push transactions over it.
+    // Returns None if the proposal should poll the mempool instead.
+    async fn open_mempool_tx_stream(&self) -> Option<OwnedMutexGuard<Receiver<Transaction>>> {
+        if !self.config.use_mempool_tx_stream {
+            return None;
+        }
+        let pushed_txs = self.mempool_tx_stream.clone()?.try_lock_owned().ok()?;
+        if let Err(err) = self.mempool_client.open_tx_stream().await {
+            warn!("Failed to open the mempool transaction stream, polling instead: {}", err);
+            return None;
+        }
+        Some(pushed_txs)
+    }
+
+    // The fallback polls the mempool for a limited number of transactions, in small chunks.
+    fn fallback_tx_stream_factory(
+        &self,
+        proposal_id: ProposalId,
+        prefix_txs: Vec<Transaction>,
+        deadline: tokio::time::Instant,
+        mempool_disconnect: MempoolDisconnect,
+    ) -> Option<TxStreamFactory> {
+        let fallback_config = self.config.generation_fallback.clone();
+        if !fallback_config.enable {
+            return None;
+        }
+        let max_mempool_request_wait = self.config.max_mempool_request_wait;
+        let mempool_client = self.mempool_client.clone();
+        let in_flight_txs = self.in_flight_txs.clone();
+        let clock = self.clock.clone();
+        let journal = self.journal.clone();
+        Some(Box::new(move || -> InputTxStream {
+            let tx_stream = MempoolTxSource {
+                mempool_client,
+                max_txs_per_mempool_request: fallback_config.max_txs_per_mempool_request,
+                max_mempool_request_wait,
+                deadline,
+                pushed_txs: None,
+                in_flight_txs,
+                mempool_disconnect,
+                clock,
+                proposal_id,
+                journal,
+            }
+            .into_stream()
+            .take(fallback_config.max_txs);
+            Box::pin(futures::stream::iter(prefix_txs).chain(tx_stream))
+        }))
+    }
+
+    // The deadline of the block builder of a proposal generated by the given deadline, which is
+    // earlier by the configured margin. A margin longer than the time left closes the block at
+    // once.
+    fn build_deadline(&self, deadline: tokio::time::Instant) -> tokio::time::Instant {
+        deadline.checked_sub(self.config.deadline_margin).unwrap_or_else(|| self.clock.now())
+    }
+
+    // The pending L1 handler transactions to prepend to a generated proposal. A proposal is
+    // generated without them if the L1 provider is unreachable.
+    async fn get_l1_handler_txs(&self) -> Vec<Transaction> {
+        let Some(l1_provider_client) = &self.l1_provider_client else {
+            return vec![];
+        };
+        match l1_provider_client.get_txs(self.config.max_l1_handler_txs_per_proposal).await {
+            Ok(txs) => {
+                debug!("Prepending {} L1 handler transactions.", txs.len());
+                txs.into_iter().map(Transaction::L1Handler).collect()
+            }
+            Err(err) => {
+                warn!("Failed to get the L1 handler transactions of the proposal: {}", err);
+                vec![]
+            }
+        }
+    }
+
+    // Spawns a task building a block out of `tx_stream` and returns the stream of the transactions
+    // added to the block.
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_proposal_generation(
+        &mut self,
+        proposal_id: ProposalId,
+        height: BlockNumber,
+        block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        tx_stream: InputTxStream,
+        fallback_tx_stream: Option<TxStreamFactory>,
+        open_mempool_tx_stream: Option<MempoolTxStream>,
+        mempool_disconnect: MempoolDisconnect,
+    ) -> OutputTxStream {
+        let (output_content_sender, output_content_receiver) = monitored_channel::<Transaction>(
+            "proposal_output_content",
+            self.config.outstream_content_buffer_size,
+        );
+        let cancellation_token = CancellationToken::new();
         // TODO: Find where to join the task - needed to make sure it starts immediatly.
-        let _handle = tokio::spawn(
+        let handle = tokio::spawn(
             ProposalGenerationTask {
-                timeout,
+                proposal_id,
+                height,
+                block_info,
+                deadline,
+                block_builder: self.block_builder.clone(),
+                output_content_sender,
+                outstream_content_buffer_size: self.config.outstream_content_buffer_size,
+                outstream_overflow_policy: self.config.outstream_overflow_policy,
+                outstream_spill_buffer_size: self.config.outstream_spill_buffer_size,
                 mempool_client: self.mempool_client.clone(),
-                max_txs_per_mempool_request: self.config.max_txs_per_mempool_request,
-                sender,
-                proposal_in_generation: self.proposal_in_generation.clone(),
+                open_mempool_tx_stream,
+                in_flight_txs: self.in_flight_txs.clone(),
+                mempool_disconnect,
+                mempool_disconnect_policy: self.config.mempool_disconnect_policy,
+                active_proposals: self.active_proposals.clone(),
+                completed_proposals: self.completed_proposals.clone(),
+                proposal_cache: self.proposal_cache.clone(),
+                cancellation_token: cancellation_token.clone(),
+                clock: self.clock.clone(),
+                journal: self.journal.clone(),
             }
-            .run(),
+            .run(tx_stream, fallback_tx_stream),
         );
+        self.active_tasks
+            .insert(proposal_id, ActiveProposalTask { handle, cancellation_token, deadline });
 
-        Ok(ReceiverStream::new(receiver))
+        Box::pin(ReceiverStream::new(output_content_receiver))
     }
 
-    // Checks if there is already a proposal being generated, and if not, sets the given proposal_id
-    // as the one being generated.
-    async fn set_proposal_in_generation(
+    // Checks that the given round is not stale and that the proposal doesn't conflict with the
+    // active proposals, and if so, sets it as active. A proposal is generated only while no other
+    // proposal is active, while proposals of different rounds are validated concurrently.
+    async fn set_proposal_active(
         &mut self,
         proposal_id: ProposalId,
+        round: ProposalRound,
+        kind: ProposalKind,
     ) -> ProposalsManagerResult<()> {
-        let mut lock = self.proposal_in_generation.lock().await;
-
-        if let Some(proposal_in_generation) = *lock {
-            return Err(ProposalsManagerError::AlreadyGeneratingProposal {
-                current_generating_proposal_id: proposal_in_generation,
-                new_proposal_id: proposal_id,
-            });
+        if let Some(latest_round) = self.latest_round {
+            if round < latest_round {
+                return Err(ProposalsManagerError::StaleProposal {
+                    proposal_id,
+                    round,
+                    latest_round,
+                });
+            }
+        }
+        if self.used_proposal_ids.contains(&proposal_id) {
+            return Err(ProposalsManagerError::DuplicateProposalId { proposal_id });
+        }
+        let mut active_proposals = self.active_proposals.lock().await;
+        match kind {
+            ProposalKind::Generation => active_proposals.start_generation(proposal_id)?,
+            ProposalKind::Validation => active_proposals.start_validation(
+                proposal_id,
+                round,
+                self.config.max_concurrent_validations,
+            )?,
         }
+        // The tasks of the proposals that are done are no longer needed.
+        self.active_tasks.retain(|proposal_id, _| active_proposals.contains(*proposal_id));
 
-        *lock = Some(proposal_id);
-        debug!("Set proposal {} as the one being generated.", proposal_id);
+        self.used_proposal_ids.insert(proposal_id);
+        self.latest_round = Some(round);
+        debug!("Set proposal {} of {} as active.", proposal_id, round);
         Ok(())
     }
-}
 
-// TODO: Should be defined elsewhere.
-#[allow(dead_code)]
-mod block_builder {
-    use starknet_api::executable_transaction::Transaction;
-    use starknet_api::state::StateDiff;
+    // Aborts the proposals in progress and discards every proposal of the height, so that none of
+    // them is committed. Returns the id of the aborted proposal, as [`Self::force_abort`].
+    async fn discard_height(&mut self) -> Option<ProposalId> {
+        let aborted_proposal_id = self.force_abort().await;
+        self.completed_proposals.lock().await.clear();
+        self.used_proposal_ids.clear();
+        self.discard_cached_proposals();
+        self.clear_journal();
+        aborted_proposal_id
+    }
+
+    // Aborts the tasks of the active proposals and clears them. Returns the aborted proposals.
+    async fn abort_active_proposals(&mut self) -> ActiveProposals {
+        for (_, active_task) in self.active_tasks.drain() {
+            // Waits for the task to stop, so that it no longer takes in-flight transactions.
+            active_task.abort().await;
+        }
+        let aborted_proposals = std::mem::take(&mut *self.active_proposals.lock().await);
+        self.record_proposals_aborted(&aborted_proposals);
+        aborted_proposals
+    }
 
-    #[derive(Debug, PartialEq)]
-    pub enum Status {
-        Building,
-        Ready,
-        Timeout,
+    // Returns the transactions taken from the mempool and not yet fed to the block builder.
+    async fn return_unused_mempool_txs(&self) {
+        if let Some(mempool_tx_stream) = &self.mempool_tx_stream {
+            close_mempool_tx_stream(&self.mempool_client, mempool_tx_stream, &self.in_flight_txs)
+                .await;
+        }
+        return_in_flight_txs(&self.mempool_client, &self.in_flight_txs).await;
     }
 
-    pub struct BlockBuilder {}
+    fn lock_proposal_cache(&self) -> std::sync::MutexGuard<'_, ProposalCache> {
+        self.proposal_cache.lock().expect("Proposal cache lock is poisoned.")
+    }
 
-    impl BlockBuilder {
-        pub fn status(&self) -> Status {
-            Status::Building
+    fn record_build_aborted(&self, proposal_id: ProposalId) {
+        if let Some(journal) = &self.journal {
+            journal.record_build_aborted(proposal_id);
         }
+    }
 
-        /// Returning true if the block is ready to be proposed.
-        pub fn add_txs_and_stream(
-            &self,
-            _txs: &[Transaction],
-            _sender: &tokio::sync::mpsc::Sender<Transaction>,
-        ) -> bool {
-            false
+    // Only generated proposals are journaled.
+    fn record_proposals_aborted(&self, aborted_proposals: &ActiveProposals) {
+        for _ in aborted_proposals.proposal_ids() {
+            record_proposal_aborted();
+        }
+        if let Some(proposal_id) = aborted_proposals.generation {
+            self.record_build_aborted(proposal_id);
         }
+    }
 
-        pub fn close_block(&self) -> StateDiff {
-            StateDiff::default()
+    // The proposals of the journal are of the height that was just decided or skipped.
+    fn clear_journal(&self) {
+        if let Some(journal) = &self.journal {
+            journal.clear();
         }
     }
 }
 
-#[allow(dead_code)]
-struct ProposalGenerationTask {
-    pub timeout: tokio::time::Instant,
-    pub mempool_client: SharedMempoolClient,
-    pub max_txs_per_mempool_request: usize,
-    pub sender: tokio::sync::mpsc::Sender<Transaction>,
-    pub proposal_in_generation: Arc<Mutex<Option<ProposalId>>>,
+// A dropped manager signals its active tasks to stop; a generation task returns its in-flight
+// transactions to the mempool as it unwinds, instead of building a block no one will take.
+impl Drop for ProposalsManager {
+    fn drop(&mut self) {
+        for active_task in self.active_tasks.values() {
+            active_task.cancellation_token.cancel();
+        }
+    }
 }
 
-impl ProposalGenerationTask {
-    #[allow(dead_code)]
-    async fn run(self) -> ProposalsManagerResult<()> {
-        let block_builder = block_builder::BlockBuilder {};
+// The task generating or validating an active proposal.
+struct ActiveProposalTask {
+    handle: JoinHandle<ProposalsManagerResult<()>>,
+    // Cancelled to make the block builder stop taking transactions and close the block.
+    cancellation_token: CancellationToken,
+    // The deadline the block builder closes the block by.
+    deadline: tokio::time::Instant,
+}
+
+impl ActiveProposalTask {
+    // Stops the task at once, without waiting for the block builder to close the block.
+    async fn abort(self) {
+        self.cancellation_token.cancel();
+        self.handle.abort();
+        let _ = self.handle.await;
+    }
+}
+
+// Ends the given stream once the token is cancelled, so that the block builder stops at a
+// transaction boundary.
+fn cancellable(tx_stream: InputTxStream, cancellation_token: CancellationToken) -> InputTxStream {
+    Box::pin(tx_stream.take_until(cancellation_token.cancelled_owned()))
+}
+
+// Interval after which a proposal polls the mempool when no transaction was pushed over the
+// mempool transaction stream, as transactions that become eligible over time aren't pushed until
+// transactions are added or returned to the mempool.
+const MEMPOOL_TX_STREAM_IDLE_TIMEOUT: tokio::time::Duration =
+    tokio::time::Duration::from_millis(100);
+
+// The source of the mempool transactions of a proposal: transactions pushed over the mempool
+// transaction stream if it is open, and transactions polled from the mempool otherwise.
+struct MempoolTxSource {
+    mempool_client: SharedMempoolClient,
+    max_txs_per_mempool_request: usize,
+    max_mempool_request_wait: tokio::time::Duration,
+    // The deadline of the proposal, past which the mempool doesn't answer its requests.
+    deadline: tokio::time::Instant,
+    pushed_txs: Option<OwnedMutexGuard<Receiver<Transaction>>>,
+    in_flight_txs: InFlightTxs,
+    mempool_disconnect: MempoolDisconnect,
+    clock: SharedClock,
+    proposal_id: ProposalId,
+    journal: Option<Arc<ProposalJournal>>,
+}
+
+impl MempoolTxSource {
+    fn into_stream(self) -> InputTxStream {
+        let stream = futures::stream::unfold(self, |mut source| async move {
+            let tx = source.next_tx().await?;
+            Some((tx, source))
+        });
+        Box::pin(stream)
+    }
+
+    // Transactions are buffered as in-flight until the block builder takes them.
+    async fn next_tx(&mut self) -> Option<Transaction> {
+        if let Some(tx) = self.pop_in_flight_tx() {
+            return Some(tx);
+        }
+        let mempool_txs = self.next_txs().await?;
+        // Recorded as they're taken from the mempool, as the mempool holds them as taken from now
+        // on, whether or not the block builder gets to them.
+        if let Some(journal) = &self.journal {
+            journal.record_txs_fed(self.proposal_id, &mempool_txs);
+        }
+        self.in_flight_txs
+            .lock()
+            .expect("In-flight transactions lock is poisoned.")
+            .extend(mempool_txs);
+        self.pop_in_flight_tx()
+    }
+
+    fn pop_in_flight_tx(&self) -> Option<Transaction> {
+        self.in_flight_txs.lock().expect("In-flight transactions lock is poisoned.").pop_front()
+    }
+
+    // Returns None once no more transactions can be received from the mempool.
+    async fn next_txs(&mut self) -> Option<Vec<Transaction>> {
         loop {
-            if tokio::time::Instant::now() > self.timeout {
-                info!("Proposal reached timeout.");
-                break;
+            if let Some(pushed_txs) = &mut self.pushed_txs {
+                let recv = pushed_txs.recv();
+                match timeout(self.clock.as_ref(), MEMPOOL_TX_STREAM_IDLE_TIMEOUT, recv).await {
+                    Some(Some(tx)) => return Some(vec![tx]),
+                    Some(None) => {
+                        warn!("Mempool transaction stream was closed, polling instead.");
+                        self.pushed_txs = None;
+                    }
+                    None => {}
+                }
             }
-            let mempool_txs = self.mempool_client.get_txs(self.max_txs_per_mempool_request).await?;
-            if mempool_txs.is_empty() {
-                // TODO: check if sleep is needed here.
-                tokio::task::yield_now().await;
-                continue;
+
+            // Bounded by the time left for the proposal, so that the mempool answers with the
+            // transactions it has ready while the block builder can still add them.
+            let max_wait = self
+                .max_mempool_request_wait
+                .min(self.deadline.saturating_duration_since(self.clock.now()));
+            let get_txs =
+                self.mempool_client.get_txs_within(self.max_txs_per_mempool_request, max_wait);
+            match RequestContext::current_or_new().with_deadline(self.deadline).scope(get_txs).await
+            {
+                Ok(mempool_txs) if mempool_txs.is_empty() => {
+                    if self.pushed_txs.is_none() {
+                        // TODO: check if sleep is needed here.
+                        tokio::task::yield_now().await;
+                    }
+                }
+                Ok(mempool_txs) => {
+                    debug!(
+                        "Feeding {} mempool transactions to the block builder.",
+                        mempool_txs.len()
+                    );
+                    return Some(mempool_txs);
+                }
+                // The block is closed at the deadline regardless; the mempool is still connected.
+                Err(MempoolClientError::ClientError(ClientError::DeadlineExceeded)) => {
+                    debug!("The proposal deadline passed while waiting for mempool transactions.");
+                    return None;
+                }
+                Err(err) => {
+                    error!("Failed to get transactions from the mempool: {}", err);
+                    *self
+                        .mempool_disconnect
+                        .lock()
+                        .expect("Mempool disconnect lock is poisoned.") = Some(err);
+                    return None;
+                }
             }
+        }
+    }
+}
+
+// Closes the mempool transaction stream, and moves the transactions pushed over it that the block
+// builder didn't take to the in-flight transactions, to be returned to the mempool rather than be
+// left in the stream until the next proposal.
+async fn close_mempool_tx_stream(
+    mempool_client: &SharedMempoolClient,
+    mempool_tx_stream: &Mutex<Receiver<Transaction>>,
+    in_flight_txs: &InFlightTxs,
+) {
+    if let Err(err) = mempool_client.close_tx_stream().await {
+        error!("Failed to close the mempool transaction stream: {}", err);
+    }
+    // Locked once the proposal stream that received from it is dropped.
+    let mut pushed_txs = mempool_tx_stream.lock().await;
+    let mut in_flight_txs = in_flight_txs.lock().expect("In-flight transactions lock is poisoned.");
+    while let Ok(tx) = pushed_txs.try_recv() {
+        in_flight_txs.push_back(tx);
+    }
+}
+
+// Reports the transactions the block builder left out of the block to the mempool, so that it
+// evicts them instead of offering them again.
+async fn reject_txs(mempool_client: &SharedMempoolClient, rejected_txs: &[RejectedTransaction]) {
+    if rejected_txs.is_empty() {
+        return;
+    }
+    if let Err(err) = mempool_client.reject_txs(rejected_txs.to_vec()).await {
+        error!("Failed to report {} rejected transactions: {}", rejected_txs.len(), err);
+    }
+}
+
+async fn return_in_flight_txs(mempool_client: &SharedMempoolClient, in_flight_txs: &InFlightTxs) {
+    let txs: Vec<_> =
+        in_flight_txs.lock().expect("In-flight transactions lock is poisoned.").drain(..).collect();
+    if txs.is_empty() {
+        return;
+    }
+    debug!("Returning {} unused transactions to the mempool.", txs.len());
+    if let Err(err) = mempool_client.return_txs(txs).await {
+        error!("Failed to return transactions to the mempool: {}", err);
+    }
+}
+
+struct ProposalGenerationTask {
+    pub proposal_id: ProposalId,
+    pub height: BlockNumber,
+    pub block_info: ProposalBlockInfo,
+    pub deadline: tokio::time::Instant,
+    pub block_builder: Arc<dyn BlockBuilderTrait>,
+    pub output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    pub outstream_content_buffer_size: usize,
+    pub outstream_overflow_policy: OutstreamOverflowPolicy,
+    pub outstream_spill_buffer_size: usize,
+    pub mempool_client: SharedMempoolClient,
+    // The mempool transaction stream, if opened for the proposal.
+    pub open_mempool_tx_stream: Option<MempoolTxStream>,
+    pub in_flight_txs: InFlightTxs,
+    pub mempool_disconnect: MempoolDisconnect,
+    pub mempool_disconnect_policy: MempoolDisconnectPolicy,
+    pub active_proposals: SharedActiveProposals,
+    pub completed_proposals: CompletedProposals,
+    pub proposal_cache: SharedProposalCache,
+    pub cancellation_token: CancellationToken,
+    pub clock: SharedClock,
+    pub journal: Option<Arc<ProposalJournal>>,
+}
 
-            // TODO: Get L1 transactions.
-            debug!("Adding {} mempool transactions to proposal in generation.", mempool_txs.len());
-            // TODO: This is cpu bound operation, should use spawn_blocking / Rayon / std::thread
-            // here or from inside the function.
-            let is_block_ready =
-                block_builder.add_txs_and_stream(mempool_txs.as_slice(), &self.sender);
-            if is_block_ready {
-                break;
+impl ProposalGenerationTask {
+    // The fallback transaction stream, if given, is used to retry a failed generation once.
+    async fn run(
+        self,
+        tx_stream: InputTxStream,
+        fallback_tx_stream: Option<TxStreamFactory>,
+    ) -> ProposalsManagerResult<()> {
+        let build_start = self.clock.now();
+        let (mut result, mut output_txs) = build_block_and_forward(
+            self.block_builder.as_ref(),
+            false,
+            self.block_info,
+            self.deadline,
+            cancellable(tx_stream, self.cancellation_token.clone()),
+            &self.output_content_sender,
+            self.outstream_content_buffer_size,
+            self.outstream_overflow_policy,
+            self.outstream_spill_buffer_size,
+        )
+        .await;
+        if let Some(mempool_tx_stream) = &self.open_mempool_tx_stream {
+            close_mempool_tx_stream(&self.mempool_client, mempool_tx_stream, &self.in_flight_txs)
+                .await;
+        }
+        return_in_flight_txs(&self.mempool_client, &self.in_flight_txs).await;
+
+        // A retry is possible only while the proposal content is empty, as transactions already
+        // sent to consensus cannot be taken back.
+        if let (Err(err), Some(fallback_tx_stream)) = (&result, fallback_tx_stream) {
+            if output_txs.is_empty()
+                && !matches!(
+                    err,
+                    ProposalsManagerError::BlockBuilderError(BlockBuilderError::OutputStreamClosed)
+                        | ProposalsManagerError::OutstreamOverflow { .. }
+                )
+                && !self.cancellation_token.is_cancelled()
+            {
+                warn!("Proposal generation failed: {}. Retrying once with reduced scope.", err);
+                // Executed sequentially, in case the failure is due to concurrent execution.
+                (result, output_txs) = build_block_and_forward(
+                    self.block_builder.as_ref(),
+                    true,
+                    self.block_info,
+                    self.deadline,
+                    cancellable(fallback_tx_stream(), self.cancellation_token.clone()),
+                    &self.output_content_sender,
+                    self.outstream_content_buffer_size,
+                    self.outstream_overflow_policy,
+                    self.outstream_spill_buffer_size,
+                )
+                .await;
+                return_in_flight_txs(&self.mempool_client, &self.in_flight_txs).await;
             }
         }
 
         info!("Closing block.");
-        // TODO: Get state diff.
-        let mut proposal_id = self.proposal_in_generation.lock().await;
-        *proposal_id = None;
+        let completed_proposal = self.close_block(result, output_txs, build_start).await;
+        // Held until the proposal is completed, so that it is either active or completed.
+        let mut active_proposals = self.active_proposals.lock().await;
+        active_proposals.remove(self.proposal_id);
+        if let Some(completed_proposal) = completed_proposal? {
+            self.completed_proposals.lock().await.insert(self.proposal_id, completed_proposal);
+        }
+        Ok(())
+    }
+
+    // Reports the rejected transactions to the mempool, journals and caches the block of the
+    // proposal. Returns the completed proposal, or `None` if the proposal was cancelled. Done
+    // before the proposal is completed, so that the active proposals aren't locked meanwhile.
+    async fn close_block(
+        &self,
+        result: ProposalsManagerResult<BlockExecutionArtifacts>,
+        output_txs: Vec<Transaction>,
+        build_start: tokio::time::Instant,
+    ) -> ProposalsManagerResult<Option<CompletedProposal>> {
+        if self.cancellation_token.is_cancelled() {
+            info!("Proposal {} was cancelled, discarding its block.", self.proposal_id);
+            self.record_build_aborted();
+            return Ok(None);
+        }
+
+        let artifacts = result.inspect_err(|err| {
+            error!("Proposal generation failed: {}", err);
+            record_proposal_failed(ProposalKind::Generation);
+            self.record_build_aborted();
+        })?;
+        reject_txs(&self.mempool_client, &artifacts.rejected_txs).await;
+        let mempool_disconnect =
+            self.mempool_disconnect.lock().expect("Mempool disconnect lock is poisoned.").take();
+        if let Some(err) = mempool_disconnect {
+            match self.mempool_disconnect_policy {
+                MempoolDisconnectPolicy::AbortProposal => {
+                    error!("Proposal generation lost the connection to the mempool: {}", err);
+                    record_proposal_failed(ProposalKind::Generation);
+                    self.record_build_aborted();
+                    return Err(err.into());
+                }
+                MempoolDisconnectPolicy::FinalizeWithCurrentTxs => {
+                    warn!(
+                        "Proposal {} lost the connection to the mempool, closing it with {} \
+                         transactions.",
+                        self.proposal_id,
+                        output_txs.len()
+                    );
+                    metrics::increment_counter!(DEGRADED_PROPOSALS);
+                }
+            }
+        }
+        record_concurrency_stats(ProposalKind::Generation, &artifacts.concurrency_stats);
+        let completed_proposal = CompletedProposal::new(artifacts, self.clock.now() - build_start);
+        record_proposal_finished(ProposalKind::Generation, &completed_proposal.metadata);
+        if let Some(journal) = &self.journal {
+            journal.record_build_finished(
+                self.proposal_id,
+                self.height,
+                &output_txs,
+                &completed_proposal.commitment,
+                &self.block_info,
+            );
+        }
+        // Cached before the output stream is closed, so that the proposal can be re-proposed once
+        // its content was streamed.
+        self.proposal_cache.lock().expect("Proposal cache lock is poisoned.").insert(
+            self.proposal_id,
+            CachedProposal {
+                txs: output_txs,
+                commitment: completed_proposal.commitment.clone(),
+                block_info: self.block_info,
+            },
+        );
+        Ok(Some(completed_proposal))
+    }
+
+    // Recorded once the unused transactions of the proposal were returned to the mempool.
+    fn record_build_aborted(&self) {
+        if let Some(journal) = &self.journal {
+            journal.record_build_aborted(self.proposal_id);
+        }
+    }
+}
+
+// Builds a block, forwarding the transactions added to it to `output_content_sender` by the
+// overflow policy. Returns the build result and the forwarded transactions.
+#[allow(clippy::too_many_arguments)]
+async fn build_block_and_forward(
+    block_builder: &dyn BlockBuilderTrait,
+    execute_sequentially: bool,
+    block_info: ProposalBlockInfo,
+    deadline: tokio::time::Instant,
+    tx_stream: InputTxStream,
+    output_content_sender: &tokio::sync::mpsc::Sender<Transaction>,
+    buffer_size: usize,
+    overflow_policy: OutstreamOverflowPolicy,
+    spill_buffer_size: usize,
+) -> (ProposalsManagerResult<BlockExecutionArtifacts>, Vec<Transaction>) {
+    let (sender, receiver) =
+        monitored_channel::<Transaction>("block_builder_output_content", buffer_size);
+    let build = async {
+        if execute_sequentially {
+            block_builder.build_block_sequentially(block_info, deadline, tx_stream, sender).await
+        } else {
+            block_builder.build_block(block_info, deadline, tx_stream, sender).await
+        }
+    };
+    let (result, forwarded_content) = tokio::join!(
+        build,
+        forward_content(receiver, output_content_sender, overflow_policy, spill_buffer_size)
+    );
+    let backpressure = forwarded_content.backpressure;
+    record_outstream_backpressure(&backpressure);
+    if backpressure.overflowed {
+        warn!(
+            "The consumer of the proposal content stalled after {} transactions, aborting the \
+             proposal.",
+            forwarded_content.txs.len()
+        );
+        return (
+            Err(ProposalsManagerError::OutstreamOverflow { buffer_size }),
+            forwarded_content.txs,
+        );
+    }
+    (result.map_err(Into::into), forwarded_content.txs)
+}
+
+// The transactions taken from a proposal stream so far.
+#[derive(Debug, Default)]
+pub(crate) struct ProposedContent {
+    // The system transactions of the block, which the proposal must start with.
+    pub(crate) system_tx_hashes: Vec<TransactionHash>,
+    pub(crate) tx_hashes: Vec<TransactionHash>,
+    // Set once the proposal stream ended, i.e., the whole proposal was received.
+    pub(crate) is_complete: bool,
+}
+
+impl ProposedContent {
+    // The first divergence of the executed transactions, a subsequence of the proposed ones, from
+    // the proposed content, if any.
+    pub(crate) fn validation_error(
+        &self,
+        executed_tx_hashes: &[TransactionHash],
+        excluded_txs: &[ExcludedTransaction],
+    ) -> Option<ProposalValidationError> {
+        // A system transaction not received yet is missing only once the proposal is complete.
+        let unexpected_system_tx =
+            self.system_tx_hashes.iter().enumerate().find(|&(tx_index, expected_tx_hash)| {
+                self.tx_hashes
+                    .get(tx_index)
+                    .map_or(self.is_complete, |tx_hash| tx_hash != expected_tx_hash)
+            });
+        if let Some((tx_index, &expected_tx_hash)) = unexpected_system_tx {
+            return Some(ProposalValidationError::UnexpectedSystemTx {
+                tx_index,
+                expected_tx_hash,
+            });
+        }
+        let diverging_tx = self
+            .tx_hashes
+            .iter()
+            .enumerate()
+            .find(|&(tx_index, tx_hash)| executed_tx_hashes.get(tx_index) != Some(tx_hash));
+        let Some((tx_index, &tx_hash)) = diverging_tx else {
+            return (!self.is_complete).then_some(ProposalValidationError::Incomplete {
+                n_received_txs: self.tx_hashes.len(),
+            });
+        };
+        let excluded_tx = excluded_txs.iter().find(|excluded_tx| excluded_tx.tx_hash == tx_hash);
+        Some(match excluded_tx {
+            Some(excluded_tx) => ProposalValidationError::TxExcluded {
+                tx_index,
+                tx_hash,
+                reason: excluded_tx.reason.clone(),
+            },
+            None => ProposalValidationError::BlockClosed { tx_index, tx_hash },
+        })
+    }
+}
+
+struct ProposalValidationTask {
+    proposal_id: ProposalId,
+    block_info: ProposalBlockInfo,
+    deadline: tokio::time::Instant,
+    block_builder: Arc<dyn BlockBuilderTrait>,
+    tx_stream: InputTxStream,
+    // The system transactions of the block, which the proposal must start with.
+    system_tx_hashes: Vec<TransactionHash>,
+    outstream_content_buffer_size: usize,
+    active_proposals: SharedActiveProposals,
+    completed_proposals: CompletedProposals,
+    result_sender: oneshot::Sender<ProposalsManagerResult<ProposalValidationResult>>,
+    cancellation_token: CancellationToken,
+    clock: SharedClock,
+}
 
+impl ProposalValidationTask {
+    async fn run(self) -> ProposalsManagerResult<()> {
+        let build_start = self.clock.now();
+        // Record the proposed transactions as the block builder takes them from the stream.
+        let proposed_content = Arc::new(StdMutex::new(ProposedContent {
+            system_tx_hashes: self.system_tx_hashes,
+            ..Default::default()
+        }));
+        let recorded_content = proposed_content.clone();
+        let tx_stream = futures::stream::unfold(
+            (cancellable(self.tx_stream, self.cancellation_token.clone()), recorded_content),
+            |(mut tx_stream, recorded_content)| async move {
+                let tx = tx_stream.next().await;
+                {
+                    let mut content =
+                        recorded_content.lock().expect("Proposed content lock is poisoned.");
+                    match &tx {
+                        Some(tx) => content.tx_hashes.push(tx.tx_hash()),
+                        None => content.is_complete = true,
+                    }
+                }
+                Some((tx?, (tx_stream, recorded_content)))
+            },
+        );
+
+        let (output_content_sender, output_content_receiver) = monitored_channel::<Transaction>(
+            "validation_output_content",
+            self.outstream_content_buffer_size,
+        );
+        let executed_tx_hashes =
+            ReceiverStream::new(output_content_receiver).map(|tx| tx.tx_hash()).collect::<Vec<_>>();
+        let (build_result, executed_tx_hashes) = tokio::join!(
+            self.block_builder.build_block(
+                self.block_info,
+                self.deadline,
+                Box::pin(tx_stream),
+                output_content_sender
+            ),
+            executed_tx_hashes
+        );
+
+        info!("Finished validating proposal.");
+        self.active_proposals.lock().await.remove(self.proposal_id);
+        // A cancelled validation has no result, as its proposal was not fully received.
+        if self.cancellation_token.is_cancelled() {
+            info!("Validation of proposal {} was cancelled.", self.proposal_id);
+            return Ok(());
+        }
+
+        let result = match build_result {
+            Ok(artifacts) => {
+                let build_duration = self.clock.now() - build_start;
+                record_proposal_finished(
+                    ProposalKind::Validation,
+                    &artifacts.metadata(build_duration),
+                );
+                record_concurrency_stats(ProposalKind::Validation, &artifacts.concurrency_stats);
+                let content_mismatch = {
+                    let proposed_content =
+                        proposed_content.lock().expect("Proposed content lock is poisoned.");
+                    proposed_content
+                        .validation_error(&executed_tx_hashes, &artifacts.excluded_txs)
+                        .map(|error| ProposalValidationResult::ContentMismatch {
+                            n_proposed_txs: proposed_content.tx_hashes.len(),
+                            n_executed_txs: executed_tx_hashes.len(),
+                            error,
+                        })
+                };
+                match content_mismatch {
+                    Some(content_mismatch) => Ok(content_mismatch),
+                    None => {
+                        let completed_proposal = CompletedProposal::new(artifacts, build_duration);
+                        let commitment = completed_proposal.commitment.clone();
+                        self.completed_proposals
+                            .lock()
+                            .await
+                            .insert(self.proposal_id, completed_proposal);
+                        Ok(ProposalValidationResult::Valid { commitment })
+                    }
+                }
+            }
+            Err(err) => {
+                record_proposal_failed(ProposalKind::Validation);
+                Err(err.into())
+            }
+        };
+        if self.result_sender.send(result).is_err() {
+            warn!("Proposal validation result receiver was dropped.");
+        }
         Ok(())
     }
 }
@@ -1,24 +1,102 @@
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
+use async_trait::async_trait;
+use futures::StreamExt;
+use mockall::predicate::eq;
+use rstest::rstest;
 use starknet_api::block::BlockNumber;
-use starknet_mempool_types::communication::MockMempoolClient;
+use starknet_api::executable_transaction::{InvokeTransaction, L1HandlerTransaction, Transaction};
+use starknet_api::felt;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::{Fee, TransactionHash};
+use starknet_batcher_types::batcher_types::{
+    ProposalBlockInfo,
+    ProposalProgress,
+    ProposalValidationError,
+    TxExclusionReason,
+};
+use starknet_l1_provider_types::communication::{L1ProviderClientError, MockL1ProviderClient};
+use starknet_mempool_infra::component_client::ClientError;
+use starknet_mempool_types::communication::{
+    MempoolClientError,
+    MempoolClientResult,
+    MockMempoolClient,
+};
+use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::{RejectedTransaction, RejectionReason};
+use tokio_stream::wrappers::ReceiverStream;
 
-use crate::proposals_manager::{ProposalsManager, ProposalsManagerConfig, ProposalsManagerError};
+use crate::block_builder::{
+    BlockBuilderError,
+    BlockBuilderResult,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    BuildProgress,
+    ExcludedTransaction,
+    InputTxStream,
+    MockBlockBuilderTrait,
+    SharedBuildProgress,
+};
+use crate::clock::TokioClock;
+use crate::proposal_journal::ProposalJournal;
+use crate::proposals_manager::{
+    GenerationFallbackConfig,
+    MempoolDisconnectPolicy,
+    ProposalRound,
+    ProposalValidationReceiver,
+    ProposalValidationResult,
+    ProposalsManager,
+    ProposalsManagerConfig,
+    ProposalsManagerError,
+    ProposedContent,
+};
+use crate::system_transactions::SystemTransactionConfig;
+use crate::test_utils::{passthrough_proposal_commitment, PassthroughBlockBuilder};
 
 const GENERATION_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(1);
+const SHORT_GENERATION_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_millis(10);
+
+fn proposals_manager() -> ProposalsManager {
+    ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(MockMempoolClient::new()),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    )
+}
+
+fn proposed_txs(n_txs: u64) -> Vec<Transaction> {
+    (0..n_txs)
+        .map(|i| {
+            Transaction::Invoke(InvokeTransaction {
+                tx: invoke_tx(InvokeTxArgs::default()),
+                tx_hash: TransactionHash(felt!(i)),
+            })
+        })
+        .collect()
+}
 
 #[tokio::test]
 async fn multiple_proposals_generation_fails() {
     let mut mempool_client = MockMempoolClient::new();
-    mempool_client.expect_get_txs().returning(|_| Ok(vec![]));
-    let mut proposals_manager =
-        ProposalsManager::new(ProposalsManagerConfig::default(), Arc::new(mempool_client));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
     let _ = proposals_manager
         .generate_block_proposal(
             0,
             tokio::time::Instant::now() + GENERATION_TIMEOUT,
-            BlockNumber::default(),
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
         )
         .await
         .unwrap();
@@ -27,15 +105,1308 @@ async fn multiple_proposals_generation_fails() {
         .generate_block_proposal(
             1,
             tokio::time::Instant::now() + GENERATION_TIMEOUT,
-            BlockNumber::default(),
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
         )
-        .await;
+        .await
+        .err();
 
     assert_matches!(
         another_generate_request,
-        Err(ProposalsManagerError::AlreadyGeneratingProposal {
+        Some(ProposalsManagerError::AlreadyGeneratingProposal {
             current_generating_proposal_id,
             new_proposal_id
         }) if current_generating_proposal_id == 0 && new_proposal_id == 1
     );
 }
+
+#[tokio::test]
+async fn force_abort_allows_new_proposal_generation() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let _ = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(proposals_manager.force_abort().await, Some(0));
+    assert_eq!(proposals_manager.force_abort().await, None);
+
+    let _ = proposals_manager
+        .generate_block_proposal(
+            1,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn abort_active_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_matches!(
+        proposals_manager.abort_active_proposal(1).await,
+        Err(ProposalsManagerError::ProposalNotActive {
+            proposal_id: 1,
+            active_proposal_id: Some(0)
+        })
+    );
+    proposals_manager.abort_active_proposal(0).await.unwrap();
+
+    // The aborted proposal stream ends, and a proposal of the next round can be generated.
+    assert!(output_tx_stream.next().await.is_none());
+    assert_matches!(
+        proposals_manager.abort_active_proposal(0).await,
+        Err(ProposalsManagerError::ProposalNotActive { proposal_id: 0, active_proposal_id: None })
+    );
+    let _output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            1,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound { height: BlockNumber::default(), round: 1 },
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn shutdown_discards_the_generated_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(proposals_manager.shutdown().await, Some(0));
+    assert_eq!(proposals_manager.shutdown().await, None);
+
+    // The block builder unwound without storing the proposal.
+    assert!(output_tx_stream.next().await.is_none());
+    assert_matches!(
+        proposals_manager.commitment(0).await,
+        Err(ProposalsManagerError::ProposalNotFound { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn shutdown_stops_proposal_validation_without_a_result() {
+    let mut proposals_manager = proposals_manager();
+    let validation_result_receiver = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::pending()),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(proposals_manager.shutdown().await, Some(0));
+    assert!(validation_result_receiver.await.is_err());
+}
+
+// Starts validating a proposal whose stream stays open until the deadline.
+async fn start_pending_validation(
+    proposals_manager: &mut ProposalsManager,
+    proposal_id: u64,
+    round: u64,
+) -> Result<ProposalValidationReceiver, ProposalsManagerError> {
+    proposals_manager
+        .validate_block_proposal(
+            proposal_id,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound { height: BlockNumber::default(), round },
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::pending()),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn proposals_of_different_rounds_are_validated_concurrently() {
+    let mut proposals_manager = proposals_manager();
+    let _pending_validation = start_pending_validation(&mut proposals_manager, 0, 0).await.unwrap();
+
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            1,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound { height: BlockNumber::default(), round: 1 },
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::iter(proposed_txs(2))),
+        )
+        .await
+        .unwrap();
+    assert_eq!(proposals_manager.active_proposal_id().await, Some(1));
+    assert_eq!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::Valid {
+            commitment: passthrough_proposal_commitment(&proposed_txs(2))
+        }
+    );
+
+    // The validation of the earlier round goes on until it is aborted.
+    assert_eq!(proposals_manager.active_proposal_id().await, Some(0));
+    assert!(!proposals_manager.progress(0).await.unwrap().is_done);
+    proposals_manager.abort_active_proposal(0).await.unwrap();
+    assert_eq!(proposals_manager.active_proposal_id().await, None);
+    assert_eq!(proposals_manager.completed_proposal_ids().await, vec![1]);
+}
+
+#[tokio::test]
+async fn aborting_a_validation_keeps_the_validations_of_other_rounds() {
+    let mut proposals_manager = proposals_manager();
+    let aborted_validation = start_pending_validation(&mut proposals_manager, 0, 0).await.unwrap();
+    let _pending_validation = start_pending_validation(&mut proposals_manager, 1, 1).await.unwrap();
+
+    proposals_manager.abort_active_proposal(0).await.unwrap();
+
+    assert!(aborted_validation.await.is_err());
+    assert_eq!(proposals_manager.active_proposal_id().await, Some(1));
+    assert_eq!(proposals_manager.force_abort().await, Some(1));
+    assert_eq!(proposals_manager.active_proposal_id().await, None);
+}
+
+#[tokio::test]
+async fn conflicting_proposals_are_rejected() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let config = ProposalsManagerConfig { max_concurrent_validations: 2, ..Default::default() };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let round = |round| ProposalRound { height: BlockNumber::default(), round };
+    let _pending_validation = start_pending_validation(&mut proposals_manager, 0, 0).await.unwrap();
+
+    // A single proposal of a round is validated at a time.
+    assert_matches!(
+        start_pending_validation(&mut proposals_manager, 1, 0).await,
+        Err(ProposalsManagerError::AlreadyValidatingRound {
+            validating_proposal_id: 0,
+            proposal_id: 1,
+            round: validated_round,
+        }) if validated_round == round(0)
+    );
+    // A proposal isn't generated while proposals are validated.
+    assert_matches!(
+        proposals_manager
+            .generate_block_proposal(
+                2,
+                tokio::time::Instant::now() + GENERATION_TIMEOUT,
+                round(1),
+                ProposalBlockInfo::default(),
+            )
+            .await,
+        Err(ProposalsManagerError::AlreadyGeneratingProposal {
+            current_generating_proposal_id: 0,
+            new_proposal_id: 2,
+        })
+    );
+    // The number of concurrent validations is bounded.
+    let _pending_validation = start_pending_validation(&mut proposals_manager, 3, 1).await.unwrap();
+    assert_matches!(
+        start_pending_validation(&mut proposals_manager, 4, 2).await,
+        Err(ProposalsManagerError::TooManyConcurrentValidations {
+            oldest_validating_proposal_id: 0,
+            proposal_id: 4,
+            max_concurrent_validations: 2,
+        })
+    );
+
+    // A proposal isn't validated while a proposal is generated.
+    proposals_manager.force_abort().await;
+    let _output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            5,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            round(2),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+    assert_matches!(
+        start_pending_validation(&mut proposals_manager, 6, 3).await,
+        Err(ProposalsManagerError::AlreadyGeneratingProposal {
+            current_generating_proposal_id: 5,
+            new_proposal_id: 6,
+        })
+    );
+}
+
+#[rstest]
+#[case::stream_opened(Ok(()))]
+#[case::fallback_to_polling(Err(MempoolClientError::MempoolError(
+    MempoolError::TransactionStreamUnavailable
+)))]
+#[tokio::test]
+async fn proposal_generation_opens_mempool_tx_stream(#[case] open_result: MempoolClientResult<()>) {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_open_tx_stream().times(1).return_once(|| open_result);
+    mempool_client.expect_close_tx_stream().returning(|| Ok(()));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let (_mempool_tx_sender, mempool_tx_receiver) = tokio::sync::mpsc::channel(1);
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        Some(mempool_tx_receiver),
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // No transactions are available, the stream ends when the proposal reaches its deadline.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+async fn validate_empty_proposal(
+    proposals_manager: &mut ProposalsManager,
+    proposal_id: u64,
+    round: ProposalRound,
+) -> Result<ProposalValidationResult, ProposalsManagerError> {
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            proposal_id,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            round,
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::empty()),
+        )
+        .await?;
+    validation_result.await.unwrap()
+}
+
+#[tokio::test]
+async fn proposals_of_stale_rounds_are_rejected() {
+    let mut proposals_manager = proposals_manager();
+    let round = |height, round| ProposalRound { height: BlockNumber(height), round };
+    validate_empty_proposal(&mut proposals_manager, 0, round(5, 1)).await.unwrap();
+
+    assert_matches!(
+        validate_empty_proposal(&mut proposals_manager, 1, round(5, 0)).await,
+        Err(ProposalsManagerError::StaleProposal { proposal_id: 1, round: stale, latest_round })
+            if stale == round(5, 0) && latest_round == round(5, 1)
+    );
+    // Another proposal of the latest round is not stale.
+    validate_empty_proposal(&mut proposals_manager, 2, round(5, 1)).await.unwrap();
+
+    // Once a decision is reached, the proposals of the decided height are stale.
+    proposals_manager.decision_reached(2).await.unwrap();
+    assert_matches!(
+        validate_empty_proposal(&mut proposals_manager, 3, round(5, 2)).await,
+        Err(ProposalsManagerError::StaleProposal { latest_round, .. }) if latest_round == round(6, 0)
+    );
+    validate_empty_proposal(&mut proposals_manager, 4, round(6, 0)).await.unwrap();
+}
+
+#[tokio::test]
+async fn validate_proposal_success() {
+    let mut proposals_manager = proposals_manager();
+    let tx_stream: InputTxStream = Box::pin(futures::stream::iter(proposed_txs(3)));
+
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            tx_stream,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::Valid {
+            commitment: passthrough_proposal_commitment(&proposed_txs(3))
+        }
+    );
+}
+
+#[tokio::test]
+async fn progress_is_reported_while_and_after_building_the_block() {
+    let build_progress = SharedBuildProgress::default();
+    let mut proposals_manager = proposals_manager().with_build_progress(build_progress.clone());
+    let (tx_sender, tx_receiver) = tokio::sync::mpsc::channel(3);
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            Box::pin(ReceiverStream::new(tx_receiver)),
+        )
+        .await
+        .unwrap();
+
+    // The progress of the active proposal is the one published by the block builder.
+    build_progress.set(BuildProgress { n_executed_txs: 2, ..Default::default() });
+    let progress = proposals_manager.progress(0).await.unwrap();
+    assert_eq!(progress.n_executed_txs, 2);
+    assert!(!progress.is_done);
+    assert!(progress.time_remaining > tokio::time::Duration::ZERO);
+    assert!(progress.time_remaining <= GENERATION_TIMEOUT);
+
+    // Once the block is built, the progress is of the built block.
+    for tx in proposed_txs(3) {
+        tx_sender.send(tx).await.unwrap();
+    }
+    drop(tx_sender);
+    validation_result.await.unwrap().unwrap();
+    assert_eq!(
+        proposals_manager.progress(0).await.unwrap(),
+        ProposalProgress { n_executed_txs: 3, is_done: true, ..Default::default() }
+    );
+    assert_matches!(
+        proposals_manager.progress(1).await,
+        Err(ProposalsManagerError::ProposalNotFound { proposal_id: 1 })
+    );
+}
+
+#[tokio::test]
+async fn decision_reached_takes_proposal_output() {
+    let mut proposals_manager = proposals_manager();
+    let txs = proposed_txs(3);
+    let tx_stream: InputTxStream = Box::pin(futures::stream::iter(txs.clone()));
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            tx_stream,
+        )
+        .await
+        .unwrap();
+    assert_matches!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::Valid { .. }
+    );
+
+    let output = proposals_manager.decision_reached(0).await.unwrap();
+    assert_eq!(output.tx_hashes(), txs.iter().map(Transaction::tx_hash).collect::<Vec<_>>());
+    // The output is taken, so a decision can't be reached on the same proposal twice.
+    assert_matches!(
+        proposals_manager.decision_reached(0).await,
+        Err(ProposalsManagerError::ProposalNotFound { proposal_id: 0 })
+    );
+}
+
+#[tokio::test]
+async fn decision_reached_aborts_the_validations_of_other_rounds() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    validate_empty_proposal(&mut proposals_manager, 0, ProposalRound::default()).await.unwrap();
+    let pending_validation = start_pending_validation(&mut proposals_manager, 1, 1).await.unwrap();
+
+    proposals_manager.decision_reached(0).await.unwrap();
+
+    // The validation of the other round has no result, and leaves no block behind.
+    assert!(pending_validation.await.is_err());
+    assert_eq!(proposals_manager.active_proposal_id().await, None);
+    assert!(proposals_manager.completed_proposal_ids().await.is_empty());
+    // A proposal of the next height is generated.
+    proposals_manager
+        .generate_block_proposal(
+            2,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound { height: BlockNumber(1), round: 0 },
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn validate_proposal_incomplete_before_deadline() {
+    let mut proposals_manager = proposals_manager();
+    // The proposal stream never ends, so the deadline is reached before the whole proposal is
+    // received.
+    let tx_stream: InputTxStream =
+        Box::pin(futures::stream::iter(proposed_txs(2)).chain(futures::stream::pending()));
+
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            tx_stream,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::ContentMismatch {
+            n_proposed_txs: 2,
+            n_executed_txs: 2,
+            error: ProposalValidationError::Incomplete { n_received_txs: 2 },
+        }
+    );
+}
+
+fn tx_hash(i: u8) -> TransactionHash {
+    TransactionHash(felt!(i))
+}
+
+#[rstest]
+#[case::valid(&[0, 1], true, None)]
+#[case::excluded(
+    &[0],
+    true,
+    Some(ProposalValidationError::TxExcluded {
+        tx_index: 1,
+        tx_hash: tx_hash(1),
+        reason: TxExclusionReason::ExecutionFailed("invalid nonce".to_string()),
+    })
+)]
+#[case::block_closed(
+    &[],
+    true,
+    Some(ProposalValidationError::BlockClosed { tx_index: 0, tx_hash: tx_hash(0) })
+)]
+#[case::incomplete(&[0, 1], false, Some(ProposalValidationError::Incomplete { n_received_txs: 2 }))]
+fn validation_error_names_the_first_divergence(
+    #[case] executed_txs: &[u8],
+    #[case] is_complete: bool,
+    #[case] expected_error: Option<ProposalValidationError>,
+) {
+    let proposed_content = ProposedContent {
+        tx_hashes: vec![tx_hash(0), tx_hash(1)],
+        is_complete,
+        ..Default::default()
+    };
+    let executed_tx_hashes: Vec<_> = executed_txs.iter().copied().map(tx_hash).collect();
+    // The second transaction failed to execute whenever it was not executed.
+    let excluded_txs = [ExcludedTransaction {
+        tx_hash: tx_hash(1),
+        reason: TxExclusionReason::ExecutionFailed("invalid nonce".to_string()),
+    }];
+
+    assert_eq!(
+        proposed_content.validation_error(&executed_tx_hashes, &excluded_txs),
+        expected_error
+    );
+}
+
+#[rstest]
+#[case::expected(&[9, 0], true, None)]
+#[case::other_tx(
+    &[0, 1],
+    true,
+    Some(ProposalValidationError::UnexpectedSystemTx { tx_index: 0, expected_tx_hash: tx_hash(9) })
+)]
+#[case::missing(
+    &[],
+    true,
+    Some(ProposalValidationError::UnexpectedSystemTx { tx_index: 0, expected_tx_hash: tx_hash(9) })
+)]
+#[case::not_received_yet(
+    &[],
+    false,
+    Some(ProposalValidationError::Incomplete { n_received_txs: 0 })
+)]
+fn validation_error_names_an_unexpected_system_tx(
+    #[case] proposed_txs: &[u8],
+    #[case] is_complete: bool,
+    #[case] expected_error: Option<ProposalValidationError>,
+) {
+    let tx_hashes: Vec<_> = proposed_txs.iter().copied().map(tx_hash).collect();
+    let proposed_content = ProposedContent {
+        system_tx_hashes: vec![tx_hash(9)],
+        tx_hashes: tx_hashes.clone(),
+        is_complete,
+    };
+
+    // All the proposed transactions were executed.
+    assert_eq!(proposed_content.validation_error(&tx_hashes, &[]), expected_error);
+}
+
+#[tokio::test]
+async fn validation_of_proposal_without_system_transactions_fails() {
+    let config = ProposalsManagerConfig {
+        system_transactions: SystemTransactionConfig { enable: true, ..Default::default() },
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(MockMempoolClient::new()),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let validation_result = proposals_manager
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::iter(proposed_txs(1))),
+        )
+        .await
+        .unwrap();
+
+    assert_matches!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::ContentMismatch {
+            error: ProposalValidationError::UnexpectedSystemTx { tx_index: 0, .. },
+            ..
+        }
+    );
+}
+
+#[tokio::test]
+async fn proposal_generation_starts_with_system_transactions() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let config = ProposalsManagerConfig {
+        system_transactions: SystemTransactionConfig { enable: true, ..Default::default() },
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound { height: BlockNumber(7), round: 0 },
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    let output_txs: Vec<Transaction> = output_tx_stream.collect().await;
+    let [Transaction::Invoke(system_tx)] = output_txs.as_slice() else {
+        panic!("Expected only the system transaction, got {output_txs:?}.");
+    };
+    assert_eq!(system_tx.tx.calldata().0[0], felt!(7_u8));
+}
+
+fn l1_handler_tx(tx_hash: u64) -> L1HandlerTransaction {
+    L1HandlerTransaction {
+        tx: Default::default(),
+        tx_hash: TransactionHash(felt!(tx_hash)),
+        paid_fee_on_l1: Fee(1),
+    }
+}
+
+#[tokio::test]
+async fn proposal_generation_prepends_l1_handler_transactions() {
+    let mut mempool_client = MockMempoolClient::new();
+    let mut mempool_txs = Some(proposed_txs(1));
+    mempool_client
+        .expect_get_txs_within()
+        .returning(move |_, _| Ok(mempool_txs.take().unwrap_or_default()));
+    let config = ProposalsManagerConfig {
+        system_transactions: SystemTransactionConfig { enable: true, ..Default::default() },
+        ..Default::default()
+    };
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client
+        .expect_get_txs()
+        .with(eq(config.max_l1_handler_txs_per_proposal))
+        .times(1)
+        .returning(|_| Ok(vec![l1_handler_tx(100)]));
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        Some(Arc::new(l1_provider_client)),
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // L1 handler transactions follow the system transactions, and precede the mempool ones.
+    let output_txs: Vec<Transaction> = output_tx_stream.collect().await;
+    let [Transaction::Invoke(_), Transaction::L1Handler(l1_handler), mempool_tx] =
+        output_txs.as_slice()
+    else {
+        panic!("Expected a system, an L1 handler and a mempool transaction, got {output_txs:?}.");
+    };
+    assert_eq!(*l1_handler, l1_handler_tx(100));
+    assert_eq!(*mempool_tx, proposed_txs(1)[0]);
+}
+
+#[tokio::test]
+async fn proposal_generation_proceeds_without_an_unreachable_l1_provider() {
+    let mut mempool_client = MockMempoolClient::new();
+    let mut mempool_txs = Some(proposed_txs(2));
+    mempool_client
+        .expect_get_txs_within()
+        .returning(move |_, _| Ok(mempool_txs.take().unwrap_or_default()));
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client
+        .expect_get_txs()
+        .returning(|_| Err(L1ProviderClientError::ClientError(ClientError::DeadlineExceeded)));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        Some(Arc::new(l1_provider_client)),
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(output_tx_stream.collect::<Vec<_>>().await, proposed_txs(2));
+}
+
+// The retry executes the transactions sequentially, in case the failure is due to concurrent
+// execution.
+#[rstest]
+#[case::fallback_enabled(true, 1)]
+#[case::fallback_disabled(false, 0)]
+#[tokio::test]
+async fn failed_proposal_generation_is_retried_once_sequentially(
+    #[case] enable_fallback: bool,
+    #[case] expected_n_retries: usize,
+) {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder.expect_build_block().times(1).returning(|_, _, _, _| {
+        Err(BlockBuilderError::InternalError("Execution failed.".to_string()))
+    });
+    block_builder.expect_build_block_sequentially().times(expected_n_retries).returning(
+        |_, _, _, _| Err(BlockBuilderError::InternalError("Execution failed.".to_string())),
+    );
+    let config = ProposalsManagerConfig {
+        generation_fallback: GenerationFallbackConfig {
+            enable: enable_fallback,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(block_builder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // The stream ends once generation is done, and the mock verifies the number of retries.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn block_builder_stops_before_the_proposal_deadline() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let config = ProposalsManagerConfig::default();
+    let deadline = tokio::time::Instant::now() + GENERATION_TIMEOUT;
+    let build_deadline = deadline - config.deadline_margin;
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder
+        .expect_build_block()
+        .withf(move |_, deadline, _, _| *deadline == build_deadline)
+        .times(1)
+        .returning(|_, _, _, _| Ok(BlockExecutionArtifacts::default()));
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(block_builder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            deadline,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // The stream ends once generation is done, and the mock verifies the deadline of the build.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn rejected_txs_are_reported_to_the_mempool() {
+    let tx = proposed_txs(1).remove(0);
+    let rejected_tx = RejectedTransaction {
+        tx_hash: tx.tx_hash(),
+        address: tx.contract_address(),
+        nonce: tx.nonce(),
+        reason: RejectionReason::InsufficientBalance,
+    };
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    mempool_client.expect_reject_txs().with(eq(vec![rejected_tx])).times(1).returning(|_| Ok(()));
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder.expect_build_block().times(1).return_once(move |_, _, _, _| {
+        Ok(BlockExecutionArtifacts { rejected_txs: vec![rejected_tx], ..Default::default() })
+    });
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(block_builder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // The stream ends once generation is done, and the mock verifies the rejection.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+// Takes a single transaction from the stream and fails before adding it to the block.
+struct FailingBlockBuilder;
+
+#[async_trait]
+impl BlockBuilderTrait for FailingBlockBuilder {
+    async fn build_block(
+        &self,
+        _block_info: ProposalBlockInfo,
+        _deadline: tokio::time::Instant,
+        mut tx_stream: InputTxStream,
+        _output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        tx_stream.next().await;
+        Err(BlockBuilderError::InternalError("Execution failed.".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn unused_mempool_txs_are_returned_after_failed_generation() {
+    let mut mempool_client = MockMempoolClient::new();
+    let mut mempool_txs = Some(proposed_txs(3));
+    mempool_client
+        .expect_get_txs_within()
+        .returning(move |_, _| Ok(mempool_txs.take().unwrap_or_default()));
+    mempool_client
+        .expect_return_txs()
+        .withf(|txs| txs.as_slice() == &proposed_txs(3)[1..])
+        .times(1)
+        .returning(|_| Ok(()));
+    let config = ProposalsManagerConfig {
+        generation_fallback: GenerationFallbackConfig { enable: false, ..Default::default() },
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(FailingBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // The stream ends once generation is done, and the mock verifies the returned transactions.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn unused_pushed_txs_are_returned_after_generation() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_open_tx_stream().times(1).returning(|| Ok(()));
+    mempool_client.expect_close_tx_stream().returning(|| Ok(()));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    mempool_client
+        .expect_return_txs()
+        .withf(|txs| txs.as_slice() == &proposed_txs(3)[1..])
+        .times(1)
+        .returning(|_| Ok(()));
+    let (mempool_tx_sender, mempool_tx_receiver) = tokio::sync::mpsc::channel(3);
+    for tx in proposed_txs(3) {
+        mempool_tx_sender.try_send(tx).unwrap();
+    }
+    let config = ProposalsManagerConfig {
+        generation_fallback: GenerationFallbackConfig { enable: false, ..Default::default() },
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(FailingBlockBuilder),
+        Some(mempool_tx_receiver),
+        Arc::new(TokioClock),
+    );
+
+    let mut output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+
+    // The block builder takes a single pushed transaction, the mock verifies the rest are returned
+    // rather than left in the stream.
+    assert!(output_tx_stream.next().await.is_none());
+}
+
+#[rstest]
+#[case::abort_proposal(MempoolDisconnectPolicy::AbortProposal, None)]
+#[case::finalize_with_current_txs(MempoolDisconnectPolicy::FinalizeWithCurrentTxs, Some(2))]
+#[tokio::test]
+async fn proposal_generation_handles_mempool_disconnect(
+    #[case] mempool_disconnect_policy: MempoolDisconnectPolicy,
+    #[case] expected_n_txs: Option<usize>,
+) {
+    let mut mempool_client = MockMempoolClient::new();
+    let mut mempool_txs = Some(proposed_txs(2));
+    mempool_client.expect_get_txs_within().returning(move |_, _| match mempool_txs.take() {
+        Some(txs) => Ok(txs),
+        None => Err(MempoolClientError::ClientError(ClientError::UnexpectedResponse(
+            "Connection closed.".to_string(),
+        ))),
+    });
+    let config = ProposalsManagerConfig { mempool_disconnect_policy, ..Default::default() };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+    // The stream ends once generation is done, long before the deadline.
+    assert_eq!(output_tx_stream.count().await, 2);
+
+    let output = proposals_manager.decision_reached(0).await;
+    assert_eq!(output.ok().map(|output| output.executed_txs.len()), expected_n_txs);
+}
+
+#[tokio::test]
+async fn mempool_deadline_exceeded_closes_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    let mut mempool_txs = Some(proposed_txs(2));
+    mempool_client.expect_get_txs_within().returning(move |_, _| match mempool_txs.take() {
+        Some(txs) => Ok(txs),
+        None => Err(MempoolClientError::ClientError(ClientError::DeadlineExceeded)),
+    });
+    // Missing the deadline is not a disconnect, so the proposal is not aborted.
+    let config = ProposalsManagerConfig {
+        mempool_disconnect_policy: MempoolDisconnectPolicy::AbortProposal,
+        ..Default::default()
+    };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(output_tx_stream.count().await, 2);
+
+    let output = proposals_manager.decision_reached(0).await.unwrap();
+    assert_eq!(output.executed_txs.len(), 2);
+}
+
+async fn generate_proposal(
+    proposals_manager: &mut ProposalsManager,
+    proposal_id: u64,
+) -> Vec<Transaction> {
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            proposal_id,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap();
+    output_tx_stream.collect().await
+}
+
+#[tokio::test]
+async fn mempool_requests_are_time_boxed() {
+    let config = ProposalsManagerConfig {
+        max_mempool_request_wait: tokio::time::Duration::from_millis(5),
+        ..Default::default()
+    };
+    let max_wait = config.max_mempool_request_wait;
+    let mut mempool_client = MockMempoolClient::new();
+    // The mempool answers each request with the transactions it had ready within the wait.
+    mempool_client
+        .expect_get_txs_within()
+        .withf(move |_, wait| *wait <= max_wait)
+        .times(1)
+        .return_once(|_, _| Ok(proposed_txs(1)));
+    mempool_client
+        .expect_get_txs_within()
+        .withf(move |_, wait| *wait <= max_wait)
+        .returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    assert_eq!(generate_proposal(&mut proposals_manager, 0).await, proposed_txs(1));
+}
+
+#[tokio::test]
+async fn proposal_ids_are_not_reused_within_a_height() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    generate_proposal(&mut proposals_manager, 0).await;
+
+    // Retried requests with the id of a proposal of the height are rejected, whatever their kind.
+    let next_round = ProposalRound { height: BlockNumber(0), round: 1 };
+    assert_matches!(
+        proposals_manager
+            .generate_block_proposal(
+                0,
+                tokio::time::Instant::now() + GENERATION_TIMEOUT,
+                next_round,
+                ProposalBlockInfo::default(),
+            )
+            .await,
+        Err(ProposalsManagerError::DuplicateProposalId { proposal_id: 0 })
+    );
+    assert_matches!(
+        start_pending_validation(&mut proposals_manager, 0, 1).await,
+        Err(ProposalsManagerError::DuplicateProposalId { proposal_id: 0 })
+    );
+
+    // Ids are used again at the next height.
+    proposals_manager.start_height(BlockNumber(1)).await;
+    let next_height = ProposalRound { height: BlockNumber(1), round: 0 };
+    let output_tx_stream = proposals_manager
+        .generate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            next_height,
+            ProposalBlockInfo::default(),
+        )
+        .await;
+    assert!(output_tx_stream.is_ok());
+}
+
+#[tokio::test]
+async fn generated_proposals_are_cached_for_re_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(2)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let config = ProposalsManagerConfig { proposal_cache_size: 1, ..Default::default() };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+
+    let txs = generate_proposal(&mut proposals_manager, 0).await;
+    assert_eq!(txs, proposed_txs(2));
+    let cached_txs: Vec<_> = proposals_manager.stream_cached_proposal(0).unwrap().0.collect().await;
+    assert_eq!(cached_txs, txs);
+
+    // The oldest proposal is evicted once the cache is full.
+    assert_eq!(generate_proposal(&mut proposals_manager, 1).await, vec![]);
+    assert_matches!(
+        proposals_manager.stream_cached_proposal(0).err(),
+        Some(ProposalsManagerError::ProposalNotFound { proposal_id: 0 })
+    );
+
+    // Cached proposals are discarded once a decision is reached.
+    proposals_manager.decision_reached(1).await.unwrap();
+    assert_matches!(
+        proposals_manager.stream_cached_proposal(1).err(),
+        Some(ProposalsManagerError::ProposalNotFound { proposal_id: 1 })
+    );
+}
+
+#[tokio::test]
+async fn reproposal_streams_the_cached_proposal() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(2)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let config = ProposalsManagerConfig { proposal_cache_size: 1, ..Default::default() };
+    let mut proposals_manager = ProposalsManager::new(
+        config,
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let txs = generate_proposal(&mut proposals_manager, 0).await;
+    let commitment = proposals_manager.commitment(0).await.unwrap();
+    let next_round = ProposalRound { height: BlockNumber::default(), round: 1 };
+
+    let (tx_stream, reproposed_commitment, _) = proposals_manager.repropose(0, next_round).unwrap();
+    assert_eq!(tx_stream.collect::<Vec<_>>().await, txs);
+    assert_eq!(reproposed_commitment, commitment);
+    assert_matches!(
+        proposals_manager.repropose(0, ProposalRound::default()).err(),
+        Some(ProposalsManagerError::StaleProposal { proposal_id: 0, .. })
+    );
+
+    // Generating another proposal evicts the first one from the cache.
+    proposals_manager
+        .generate_block_proposal(
+            1,
+            tokio::time::Instant::now() + SHORT_GENERATION_TIMEOUT,
+            next_round,
+            ProposalBlockInfo::default(),
+        )
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    assert_matches!(
+        proposals_manager.repropose(0, next_round).err(),
+        Some(ProposalsManagerError::ProposalNotCached { proposal_id: 0, evicted: true })
+    );
+    assert_matches!(
+        proposals_manager.repropose(2, next_round).err(),
+        Some(ProposalsManagerError::ProposalNotCached { proposal_id: 2, evicted: false })
+    );
+}
+
+#[tokio::test]
+async fn proposals_are_recovered_from_the_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(2)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let (journal, _) = ProposalJournal::open(&path).unwrap();
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    )
+    .with_journal(Arc::new(journal));
+    let txs = generate_proposal(&mut proposals_manager, 0).await;
+    drop(proposals_manager);
+    // A crash in the middle of the next proposal leaves its transactions taken from the mempool.
+    let (journal, _) = ProposalJournal::open(&path).unwrap();
+    journal.record_build_started(1, BlockNumber(0));
+    journal.record_txs_fed(1, &proposed_txs(1));
+    drop(journal);
+
+    let (_, recovery) = ProposalJournal::open(&path).unwrap();
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_return_txs().with(eq(proposed_txs(1))).times(1).returning(|_| Ok(()));
+    let mut proposals_manager = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    proposals_manager.recover(recovery, BlockNumber(0)).await;
+
+    let recovered_txs: Vec<_> =
+        proposals_manager.stream_cached_proposal(0).unwrap().0.collect().await;
+    assert_eq!(recovered_txs, txs);
+    assert_eq!(
+        proposals_manager.commitment(0).await.unwrap(),
+        passthrough_proposal_commitment(&txs)
+    );
+}
+
+#[tokio::test]
+async fn proposer_and_validator_compute_the_same_commitment() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_get_txs_within().times(1).return_once(|_, _| Ok(proposed_txs(2)));
+    mempool_client.expect_get_txs_within().returning(|_, _| Ok(vec![]));
+    let mut proposer = ProposalsManager::new(
+        ProposalsManagerConfig::default(),
+        Arc::new(mempool_client),
+        None,
+        Arc::new(PassthroughBlockBuilder),
+        None,
+        Arc::new(TokioClock),
+    );
+    let txs = generate_proposal(&mut proposer, 0).await;
+
+    let mut validator = proposals_manager();
+    let validation_result = validator
+        .validate_block_proposal(
+            0,
+            tokio::time::Instant::now() + GENERATION_TIMEOUT,
+            ProposalRound::default(),
+            ProposalBlockInfo::default(),
+            Box::pin(futures::stream::iter(txs)),
+        )
+        .await
+        .unwrap();
+
+    let commitment = proposer.commitment(0).await.unwrap();
+    assert_eq!(
+        validation_result.await.unwrap().unwrap(),
+        ProposalValidationResult::Valid { commitment: commitment.clone() }
+    );
+    assert_eq!(validator.commitment(0).await.unwrap(), commitment);
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use blockifier::versioned_constants::{
+    StarknetVersion as BlockifierStarknetVersion,
+    VersionedConstants,
+};
+use serde::{Deserialize, Deserializer};
+use starknet_api::block::{BlockNumber, StarknetVersion};
+
+#[cfg(test)]
+#[path = "protocol_version_test.rs"]
+mod protocol_version_test;
+
+/// The protocol version of the latest versioned constants of the blockifier.
+pub const LATEST_STARKNET_VERSION: &str = "0.13.3";
+
+/// The Starknet protocol versions of the built blocks, by the height each version takes effect at.
+/// A version applies from its height until the height of the next version.
+pub type ProtocolVersions = BTreeMap<BlockNumber, StarknetVersion>;
+
+pub fn default_protocol_versions() -> ProtocolVersions {
+    BTreeMap::from([(BlockNumber(0), StarknetVersion(LATEST_STARKNET_VERSION.to_string()))])
+}
+
+/// Returns the protocol version of the block at the given height. The versions always have an
+/// entry for the genesis block, which covers the heights before the first upgrade.
+pub fn protocol_version_at(
+    protocol_versions: &ProtocolVersions,
+    block_number: BlockNumber,
+) -> &StarknetVersion {
+    protocol_versions
+        .range(..=block_number)
+        .next_back()
+        .map(|(_, version)| version)
+        .expect("The protocol versions should have an entry for the genesis block.")
+}
+
+/// Returns the versioned constants blocks of the given protocol version are executed with.
+pub fn versioned_constants_of(version: &StarknetVersion) -> &'static VersionedConstants {
+    let blockifier_version = blockifier_starknet_version(version)
+        .expect("The protocol versions should be validated when the config is loaded.");
+    VersionedConstants::get(blockifier_version)
+}
+
+// The blockifier keeps versioned constants only for the versions that changed them; versions
+// that didn't are not supported, to avoid executing blocks with wrong constants.
+fn blockifier_starknet_version(version: &StarknetVersion) -> Option<BlockifierStarknetVersion> {
+    match version.0.as_str() {
+        "0.13.0" => Some(BlockifierStarknetVersion::V0_13_0),
+        "0.13.1" => Some(BlockifierStarknetVersion::V0_13_1),
+        "0.13.1.1" => Some(BlockifierStarknetVersion::V0_13_1_1),
+        "0.13.2" => Some(BlockifierStarknetVersion::V0_13_2),
+        "0.13.2.1" => Some(BlockifierStarknetVersion::V0_13_2_1),
+        LATEST_STARKNET_VERSION => Some(BlockifierStarknetVersion::Latest),
+        _ => None,
+    }
+}
+
+pub fn serialize_protocol_versions(protocol_versions: &ProtocolVersions) -> String {
+    protocol_versions
+        .iter()
+        .map(|(block_number, version)| format!("{block_number}:{version}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn deserialize_protocol_versions<'de, D>(de: D) -> Result<ProtocolVersions, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str: String = Deserialize::deserialize(de)?;
+    let mut protocol_versions = ProtocolVersions::new();
+    for raw_entry in raw_str.split(',') {
+        let invalid_entry = || -> D::Error {
+            serde::de::Error::custom(format!("Invalid protocol version entry: {raw_entry}."))
+        };
+        let (raw_block_number, raw_version) =
+            raw_entry.trim().split_once(':').ok_or_else(invalid_entry)?;
+        let block_number =
+            BlockNumber(raw_block_number.trim().parse().map_err(|_| invalid_entry())?);
+        let version = StarknetVersion(raw_version.trim().to_string());
+        if blockifier_starknet_version(&version).is_none() {
+            return Err(serde::de::Error::custom(format!(
+                "Unsupported protocol version: {version}."
+            )));
+        }
+        if protocol_versions.insert(block_number, version).is_some() {
+            return Err(serde::de::Error::custom(format!(
+                "Multiple protocol versions for block {block_number}."
+            )));
+        }
+    }
+    if !protocol_versions.contains_key(&BlockNumber(0)) {
+        return Err(serde::de::Error::custom("Missing the protocol version of the genesis block."));
+    }
+    Ok(protocol_versions)
+}
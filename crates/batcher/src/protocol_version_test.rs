@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use assert_matches::assert_matches;
+use rstest::rstest;
+use serde::Deserialize;
+use starknet_api::block::{BlockNumber, StarknetVersion};
+
+use crate::protocol_version::{
+    deserialize_protocol_versions,
+    protocol_version_at,
+    serialize_protocol_versions,
+    ProtocolVersions,
+};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(deserialize_with = "deserialize_protocol_versions")]
+    protocol_versions: ProtocolVersions,
+}
+
+fn parse(raw_protocol_versions: &str) -> Result<ProtocolVersions, serde_json::Error> {
+    serde_json::from_value::<Config>(
+        serde_json::json!({ "protocol_versions": raw_protocol_versions }),
+    )
+    .map(|config| config.protocol_versions)
+}
+
+fn version(version: &str) -> StarknetVersion {
+    StarknetVersion(version.to_string())
+}
+
+#[test]
+fn protocol_version_applies_until_the_next_upgrade() {
+    let protocol_versions =
+        BTreeMap::from([(BlockNumber(0), version("0.13.2")), (BlockNumber(10), version("0.13.3"))]);
+
+    assert_eq!(protocol_version_at(&protocol_versions, BlockNumber(0)), &version("0.13.2"));
+    assert_eq!(protocol_version_at(&protocol_versions, BlockNumber(9)), &version("0.13.2"));
+    assert_eq!(protocol_version_at(&protocol_versions, BlockNumber(10)), &version("0.13.3"));
+    assert_eq!(protocol_version_at(&protocol_versions, BlockNumber(100)), &version("0.13.3"));
+}
+
+#[test]
+fn protocol_versions_serialization_round_trip() {
+    let protocol_versions = BTreeMap::from([
+        (BlockNumber(0), version("0.13.2.1")),
+        (BlockNumber(500), version("0.13.3")),
+    ]);
+
+    let raw_protocol_versions = serialize_protocol_versions(&protocol_versions);
+
+    assert_eq!(raw_protocol_versions, "0:0.13.2.1,500:0.13.3");
+    assert_eq!(parse(&raw_protocol_versions).unwrap(), protocol_versions);
+}
+
+#[rstest]
+#[case::unsupported_version("0:0.12.0")]
+#[case::missing_genesis_version("5:0.13.3")]
+#[case::duplicate_height("0:0.13.2,0:0.13.3")]
+#[case::malformed_entry("0.13.3")]
+fn invalid_protocol_versions_are_rejected(#[case] raw_protocol_versions: &str) {
+    assert_matches!(parse(raw_protocol_versions), Err(_));
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ChainId, ContractAddress, Nonce};
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::transaction::{
+    Calldata,
+    InvokeTransactionV3,
+    TransactionHasher,
+    TransactionVersion,
+    ValidResourceBounds,
+};
+use starknet_api::StarknetApiError;
+use starknet_types_core::felt::Felt;
+use validator::{Validate, ValidationError};
+
+#[cfg(test)]
+#[path = "system_transactions_test.rs"]
+mod system_transactions_test;
+
+const BLOCK_NUMBER_PLACEHOLDER: &str = "{block_number}";
+const TIMESTAMP_PLACEHOLDER: &str = "{timestamp}";
+
+/// Configuration of the system transaction inserted at the start of every block, e.g., to register
+/// block hashes. Values of the template are either felts or the placeholders `{block_number}` and
+/// `{timestamp}`, resolved per block.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_system_transaction_config"))]
+pub struct SystemTransactionConfig {
+    pub enable: bool,
+    pub chain_id: ChainId,
+    pub sender_address: ContractAddress,
+    pub nonce_template: String,
+    // Comma-separated calldata values.
+    pub calldata_template: String,
+}
+
+impl Default for SystemTransactionConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            chain_id: ChainId::Other("0x0".to_string()),
+            sender_address: ContractAddress::default(),
+            nonce_template: BLOCK_NUMBER_PLACEHOLDER.to_string(),
+            calldata_template: format!("{BLOCK_NUMBER_PLACEHOLDER},{TIMESTAMP_PLACEHOLDER}"),
+        }
+    }
+}
+
+impl SerializeConfig for SystemTransactionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, a system transaction is executed at the start of every block, before \
+                 user transactions",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "chain_id",
+                &self.chain_id,
+                "The chain ID used to compute the system transaction hash",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "sender_address",
+                &self.sender_address,
+                "The address of the account sending the system transaction",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "nonce_template",
+                &self.nonce_template,
+                "The nonce of the system transaction; a felt, {block_number} or {timestamp}",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "calldata_template",
+                &self.calldata_template,
+                "Comma-separated calldata of the system transaction; each value is a felt, \
+                 {block_number} or {timestamp}",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+fn validate_system_transaction_config(
+    config: &SystemTransactionConfig,
+) -> Result<(), ValidationError> {
+    if let Err(error_message) = SystemTransactionTemplate::try_from(config) {
+        let mut error = ValidationError::new("Invalid system transaction template.");
+        error.message = Some(error_message.into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// A value of the system transaction template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateValue {
+    Constant(Felt),
+    BlockNumber,
+    Timestamp,
+}
+
+impl TemplateValue {
+    fn resolve(&self, block_number: BlockNumber, timestamp: BlockTimestamp) -> Felt {
+        match self {
+            TemplateValue::Constant(value) => *value,
+            TemplateValue::BlockNumber => Felt::from(block_number.0),
+            TemplateValue::Timestamp => Felt::from(timestamp.0),
+        }
+    }
+}
+
+impl FromStr for TemplateValue {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            BLOCK_NUMBER_PLACEHOLDER => Ok(TemplateValue::BlockNumber),
+            TIMESTAMP_PLACEHOLDER => Ok(TemplateValue::Timestamp),
+            constant => {
+                let felt = match constant.strip_prefix("0x") {
+                    Some(_) => Felt::from_hex(constant).ok(),
+                    None => Felt::from_dec_str(constant).ok(),
+                };
+                felt.map(TemplateValue::Constant)
+                    .ok_or_else(|| format!("Invalid system transaction template value: {value}."))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SystemTransactionTemplate {
+    nonce: TemplateValue,
+    calldata: Vec<TemplateValue>,
+}
+
+impl TryFrom<&SystemTransactionConfig> for SystemTransactionTemplate {
+    type Error = String;
+
+    fn try_from(config: &SystemTransactionConfig) -> Result<Self, Self::Error> {
+        let calldata = match config.calldata_template.trim() {
+            "" => vec![],
+            calldata_template => calldata_template
+                .split(',')
+                .map(TemplateValue::from_str)
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(Self { nonce: config.nonce_template.parse()?, calldata })
+    }
+}
+
+/// Generates the system transactions executed at the start of every block, before user
+/// transactions. The transactions are part of the proposal content, and hence of the block
+/// commitments.
+#[derive(Clone, Debug, Default)]
+pub struct SystemTransactionGenerator {
+    chain_id: Option<ChainId>,
+    sender_address: ContractAddress,
+    template: Option<SystemTransactionTemplate>,
+}
+
+impl SystemTransactionGenerator {
+    /// Expects a validated config.
+    pub fn new(config: &SystemTransactionConfig) -> Self {
+        if !config.enable {
+            return Self::default();
+        }
+        let template = SystemTransactionTemplate::try_from(config)
+            .expect("System transaction config should be validated.");
+        Self {
+            chain_id: Some(config.chain_id.clone()),
+            sender_address: config.sender_address,
+            template: Some(template),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        block_number: BlockNumber,
+        timestamp: BlockTimestamp,
+    ) -> Result<Vec<Transaction>, StarknetApiError> {
+        let (Some(chain_id), Some(template)) = (&self.chain_id, &self.template) else {
+            return Ok(vec![]);
+        };
+
+        let calldata = template
+            .calldata
+            .iter()
+            .map(|value| value.resolve(block_number, timestamp))
+            .collect::<Vec<_>>();
+        // System transactions are not charged.
+        let tx = starknet_api::transaction::InvokeTransaction::V3(InvokeTransactionV3 {
+            resource_bounds: ValidResourceBounds::AllResources(Default::default()),
+            tip: Default::default(),
+            signature: Default::default(),
+            nonce: Nonce(template.nonce.resolve(block_number, timestamp)),
+            sender_address: self.sender_address,
+            calldata: Calldata(calldata.into()),
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+            paymaster_data: Default::default(),
+            account_deployment_data: Default::default(),
+        });
+        let tx_hash = tx.calculate_transaction_hash(chain_id, &TransactionVersion::THREE)?;
+
+        Ok(vec![Transaction::Invoke(InvokeTransaction { tx, tx_hash })])
+    }
+}
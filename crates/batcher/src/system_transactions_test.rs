@@ -0,0 +1,62 @@
+use assert_matches::assert_matches;
+use rstest::rstest;
+use starknet_api::block::{BlockNumber, BlockTimestamp};
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::{Calldata, InvokeTransaction};
+use starknet_api::{calldata, contract_address, felt, patricia_key};
+use validator::Validate;
+
+use crate::system_transactions::{
+    SystemTransactionConfig,
+    SystemTransactionGenerator,
+    TemplateValue,
+};
+
+#[rstest]
+#[case::block_number("{block_number}", TemplateValue::BlockNumber)]
+#[case::timestamp(" {timestamp}", TemplateValue::Timestamp)]
+#[case::hex("0x1f", TemplateValue::Constant(felt!(31_u8)))]
+#[case::decimal("31", TemplateValue::Constant(felt!(31_u8)))]
+fn parse_template_value(#[case] value: &str, #[case] expected: TemplateValue) {
+    assert_eq!(value.parse::<TemplateValue>().unwrap(), expected);
+}
+
+#[test]
+fn invalid_template_is_rejected() {
+    let config = SystemTransactionConfig {
+        calldata_template: "{block_number},{height}".to_string(),
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn disabled_generator_generates_nothing() {
+    let generator = SystemTransactionGenerator::new(&SystemTransactionConfig::default());
+    assert_eq!(generator.generate(BlockNumber(1), BlockTimestamp(2)).unwrap(), vec![]);
+}
+
+#[test]
+fn generate_templated_system_transaction() {
+    let config = SystemTransactionConfig {
+        enable: true,
+        sender_address: contract_address!("0x1"),
+        calldata_template: "{block_number},0x7,{timestamp}".to_string(),
+        ..Default::default()
+    };
+    let generator = SystemTransactionGenerator::new(&config);
+
+    let system_txs = generator.generate(BlockNumber(5), BlockTimestamp(1000)).unwrap();
+
+    let [Transaction::Invoke(system_tx)] = system_txs.as_slice() else {
+        panic!("Expected a single invoke system transaction, got {system_txs:?}.");
+    };
+    let tx = assert_matches!(&system_tx.tx, InvokeTransaction::V3(tx) => tx);
+    assert_eq!(tx.sender_address, contract_address!("0x1"));
+    assert_eq!(tx.nonce.0, felt!(5_u8));
+    assert_eq!(tx.calldata, calldata![felt!(5_u8), felt!(7_u8), felt!(1000_u16)]);
+
+    // Generation is deterministic, so validators derive the same transaction.
+    assert_eq!(generator.generate(BlockNumber(5), BlockTimestamp(1000)).unwrap(), system_txs);
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use starknet_api::block::BlockTimestamp;
+use starknet_api::executable_transaction::Transaction;
+use starknet_batcher_types::batcher_types::{ProposalBlockInfo, ProposalCommitment};
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
+use tokio_stream::StreamExt;
+
+use crate::block_builder::{
+    BlockBuilderError,
+    BlockBuilderResult,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    ExecutedTransaction,
+    InputTxStream,
+};
+use crate::clock::Clock;
+use crate::proposal_commitment::calculate_proposal_commitment;
+
+/// A block builder that adds all the transactions of the stream to the block, without executing
+/// them, until the deadline is reached or the stream ends. The transactions have default receipts,
+/// and the block has an empty state diff.
+pub struct PassthroughBlockBuilder;
+
+#[async_trait]
+impl BlockBuilderTrait for PassthroughBlockBuilder {
+    async fn build_block(
+        &self,
+        _block_info: ProposalBlockInfo,
+        deadline: tokio::time::Instant,
+        mut tx_stream: InputTxStream,
+        output_content_sender: tokio::sync::mpsc::Sender<Transaction>,
+    ) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let mut txs = Vec::new();
+        let mut executed_txs = Vec::new();
+        while let Ok(Some(tx)) = tokio::time::timeout_at(deadline, tx_stream.next()).await {
+            executed_txs.push(passthrough_executed_tx(&tx));
+            txs.push(tx.clone());
+            output_content_sender
+                .send(tx)
+                .await
+                .map_err(|_| BlockBuilderError::OutputStreamClosed)?;
+        }
+        Ok(BlockExecutionArtifacts { txs, executed_txs, ..Default::default() })
+    }
+}
+
+fn passthrough_executed_tx(tx: &Transaction) -> ExecutedTransaction {
+    ExecutedTransaction { tx_hash: tx.tx_hash(), signature: tx.signature(), ..Default::default() }
+}
+
+/// The commitment of the block the passthrough block builder builds out of the given transactions.
+pub fn passthrough_proposal_commitment(txs: &[Transaction]) -> ProposalCommitment {
+    calculate_proposal_commitment(&BlockExecutionArtifacts {
+        executed_txs: txs.iter().map(passthrough_executed_tx).collect(),
+        ..Default::default()
+    })
+}
+
+/// The block timestamp a fake clock starts at.
+pub const FAKE_CLOCK_START_TIMESTAMP: u64 = 1_700_000_000;
+
+/// A clock that moves only when advanced, so that deadlines are reached exactly when a test says.
+pub struct FakeClock {
+    start: Instant,
+    now: watch::Sender<Instant>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        let start = Instant::now();
+        Self { start, now: watch::Sender::new(start) }
+    }
+}
+
+impl FakeClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now.send_modify(|now| *now += duration);
+    }
+}
+
+#[async_trait]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.now.borrow()
+    }
+
+    fn block_timestamp(&self) -> BlockTimestamp {
+        BlockTimestamp(FAKE_CLOCK_START_TIMESTAMP + (self.now() - self.start).as_secs())
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        self.now
+            .subscribe()
+            .wait_for(|now| *now >= deadline)
+            .await
+            .expect("The clock outlives the sleeps on it.");
+    }
+}
@@ -1,21 +1,402 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockHeader, BlockNumber, BlockTimestamp};
+use starknet_api::core::{ContractAddress, StateDiffCommitment, TransactionCommitment};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::{Fee, TransactionHash};
+use thiserror::Error;
 
+use crate::deadline::Deadline;
 use crate::errors::BatcherError;
 
-// TODO(Tsabary/Yael/Dafna): Populate the data structure used to invoke the batcher.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BatcherFnOneInput {}
+// TODO: Should be defined in SN_API probably (shared with the consensus).
+pub type ProposalId = u64;
+
+// TODO: Should be defined in SN_API probably (shared with the consensus).
+pub type Round = u32;
+
+/// The consensus round a proposal is made in. Consensus may make a proposal in every round of a
+/// height until it decides on a block, so rounds are ordered by height and then by round.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct ProposalRound {
+    pub height: BlockNumber,
+    pub round: Round,
+}
+
+impl fmt::Display for ProposalRound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round {} of height {}", self.round, self.height)
+    }
+}
+
+/// A digest of the node's environment that affects the execution of proposals: the versioned
+/// constants, the version of the compiled class cache, the chain info and the block building
+/// configuration, salted with the random seed of the proposal it is attached to. Nodes with
+/// different fingerprints for the same seed may execute the same proposal differently.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EnvironmentFingerprint {
+    pub seed: u64,
+    pub digest: [u8; 32],
+}
+
+impl fmt::Display for EnvironmentFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x")?;
+        self.digest.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        write!(f, " (seed {})", self.seed)
+    }
+}
+
+/// The commitment of the block built for a proposal, which consensus votes on: the proposer and
+/// the validators of a proposal agree on it if they compute the same commitment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalCommitment {
+    pub state_diff_commitment: StateDiffCommitment,
+    pub transaction_commitment: TransactionCommitment,
+}
+
+/// The timestamp and sequencer address of the block built for a proposal. Chosen by the proposer,
+/// and checked by the validators of the proposal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalBlockInfo {
+    pub timestamp: BlockTimestamp,
+    /// The address the fees of the block are paid to.
+    pub sequencer_address: ContractAddress,
+}
+
+/// The reason the block info of a proposal was rejected by a validator.
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockInfoValidationError {
+    #[error(
+        "The timestamp {} is earlier than the timestamp of the previous block, {}.",
+        timestamp.0,
+        previous_timestamp.0
+    )]
+    TimestampBeforePreviousBlock { timestamp: BlockTimestamp, previous_timestamp: BlockTimestamp },
+    #[error(
+        "The timestamp {} is too far in the future, the latest accepted timestamp is {}.",
+        timestamp.0,
+        max_timestamp.0
+    )]
+    TimestampTooFarInFuture { timestamp: BlockTimestamp, max_timestamp: BlockTimestamp },
+    #[error("The sequencer address is unset.")]
+    MissingSequencerAddress,
+}
+
+/// Input of the request to start generating a proposal for the given round, with transactions from
+/// the mempool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BuildProposalInput {
+    pub proposal_id: ProposalId,
+    pub deadline: Deadline,
+    pub height: BlockNumber,
+    pub round: Round,
+    /// The timestamp of the block chosen by consensus, used if the batcher takes the timestamps of
+    /// its blocks from consensus.
+    pub timestamp: Option<BlockTimestamp>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BuildProposalReturnValue {
+    /// The environment the proposal is generated in, sent along with the proposal to validators.
+    pub environment_fingerprint: EnvironmentFingerprint,
+    /// The block info the proposal is generated with, sent along with the proposal to validators.
+    pub block_info: ProposalBlockInfo,
+}
+
+/// Input of the request re-proposing a proposal generated earlier in the height, e.g., once
+/// consensus locked on it in a previous round.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReproposeProposalInput {
+    pub proposal_id: ProposalId,
+    pub height: BlockNumber,
+    /// The round the proposal is re-proposed in.
+    pub round: Round,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReproposeProposalReturnValue {
+    /// The commitment of the block built for the proposal when it was generated.
+    pub commitment: ProposalCommitment,
+    pub environment_fingerprint: EnvironmentFingerprint,
+    pub block_info: ProposalBlockInfo,
+}
+
+/// Input of the request to validate a proposal received from another node, by building a block
+/// out of its transactions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ValidateProposalInput {
+    pub proposal_id: ProposalId,
+    pub deadline: Deadline,
+    pub height: BlockNumber,
+    pub round: Round,
+    pub txs: Vec<Transaction>,
+    /// The environment the proposal was generated in, if known.
+    pub environment_fingerprint: Option<EnvironmentFingerprint>,
+    /// The block info the proposal was generated with, if known. Checked by the block info policy
+    /// of the validator, and used to build the block; otherwise, the block is built with the block
+    /// info the validator would have proposed.
+    pub block_info: Option<ProposalBlockInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    /// Every proposed transaction was executed, in the proposed order, into a block of the given
+    /// commitment.
+    Valid { commitment: ProposalCommitment },
+    /// The executed content differs from the proposed one, first as described by `error`.
+    ContentMismatch { n_proposed_txs: usize, n_executed_txs: usize, error: ProposalValidationError },
+    /// The executed content differs from the proposed one, and the proposal was generated in a
+    /// different environment than the one it was validated in.
+    EnvironmentMismatch {
+        proposer_fingerprint: EnvironmentFingerprint,
+        validator_fingerprint: EnvironmentFingerprint,
+        n_proposed_txs: usize,
+        n_executed_txs: usize,
+        error: ProposalValidationError,
+    },
+}
+
+/// Input of the request for the status of a proposal validation started by `validate_proposal`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetValidationStatusInput {
+    pub proposal_id: ProposalId,
+}
+
+/// The status of a proposal validation. Once done, the validation keeps its status until the
+/// active height ends.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationStatus {
+    InProgress,
+    Done(ProposalStatus),
+}
+
+/// The first divergence of the block built out of a proposal from the proposed content.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalValidationError {
+    /// The proposed transaction at `tx_index` was left out of the block.
+    TxExcluded { tx_index: usize, tx_hash: TransactionHash, reason: TxExclusionReason },
+    /// The block was closed, at the deadline or at its maximal number of transactions, before the
+    /// proposed transaction at `tx_index` was executed.
+    BlockClosed { tx_index: usize, tx_hash: TransactionHash },
+    /// The proposal was not fully received by the deadline; all the `n_received_txs` transactions
+    /// that were received were executed.
+    Incomplete { n_received_txs: usize },
+    /// The proposal doesn't start with the system transactions of its block: the proposed
+    /// transaction at `tx_index`, if any, isn't the expected system transaction.
+    UnexpectedSystemTx { tx_index: usize, expected_tx_hash: TransactionHash },
+}
+
+/// Why the block builder left a transaction out of the block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxExclusionReason {
+    /// The resource bounds of the transaction don't cover the gas prices of the block.
+    InvalidResourceBounds(String),
+    /// The transaction failed to execute, e.g., for an invalid nonce or an insufficient balance.
+    ExecutionFailed(String),
+    /// The transaction took longer to execute than allowed.
+    ExecutionTimeout,
+    /// The transaction does not fit in the block.
+    BlockFull,
+}
+
+/// Input of the request for a page of the transactions of a generated proposal. Pages are numbered
+/// from 0, and consensus retrieves them at its own pace, possibly more than once, e.g., to
+/// retransmit them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetProposalContentInput {
+    pub proposal_id: ProposalId,
+    pub page: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalContent {
+    /// The transactions of the requested page.
+    Txs(Vec<Transaction>),
+    /// The proposal generation ended before the requested page: all the transactions of the
+    /// proposal are in the previous pages, and were built into a block of the given commitment.
+    Finished { commitment: ProposalCommitment },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetProposalContentReturnValue {
+    pub content: ProposalContent,
+}
+
+/// The commitments of a block, as computed by executing its transactions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCommitments {
+    pub state_diff_commitment: StateDiffCommitment,
+}
+
+/// Input of the request to import a block built by an external builder, e.g., a backup builder
+/// service, as the local proposal for the given height. The block is validated by executing its
+/// transactions, and its commitments are compared to the expected ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImportBlockInput {
+    pub proposal_id: ProposalId,
+    pub deadline: Deadline,
+    pub height: BlockNumber,
+    pub round: Round,
+    pub txs: Vec<Transaction>,
+    pub expected_commitments: BlockCommitments,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportBlockStatus {
+    /// The block was validated, and is served as a generated proposal by `get_proposal_content`.
+    Imported { environment_fingerprint: EnvironmentFingerprint },
+    /// The executed content differs from the imported one.
+    ContentMismatch { n_proposed_txs: usize, n_executed_txs: usize },
+    /// The content was executed, but the commitments differ from the expected ones.
+    CommitmentMismatch { expected: BlockCommitments, actual: BlockCommitments },
+}
+
+/// Input of the admin action that aborts the active proposal and discards the proposals of the
+/// active height, so that the node resyncs it from peers. The action is authenticated by the admin
+/// endpoint, and by the auth token of the remote batcher server, if any.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForceAbortAndResyncInput;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForceAbortAndResyncReturnValue {
+    /// The proposal that was being generated when the action was invoked, if any.
+    pub aborted_proposal_id: Option<ProposalId>,
+}
+
+/// The state of the proposals of the active height, dumped for operators.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalState {
+    /// The height the batcher works on, unknown until a height is started or handled.
+    pub active_height: Option<BlockNumber>,
+    /// The proposal currently being generated, or else the validated proposal of the latest round,
+    /// if any.
+    pub active_proposal_id: Option<ProposalId>,
+    /// The proposals of the height whose blocks were built, and can be decided on.
+    pub completed_proposal_ids: Vec<ProposalId>,
+}
+
+/// Input of the request for the resources used by the block built for a proposal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetProposalMetadataInput {
+    pub proposal_id: ProposalId,
+}
+
+/// The resources used by the block built for a proposal, generated or validated, for operators to
+/// tune the block limits with.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalMetadata {
+    /// The number of transactions added to the block, including the reverted ones.
+    pub n_txs: usize,
+    pub n_reverted_txs: usize,
+    pub l1_gas_used: u128,
+    pub l1_data_gas_used: u128,
+    pub l2_gas_used: u128,
+    /// The number of Cairo steps executed by the transactions of the block.
+    pub n_steps: usize,
+    /// The number of instances of each builtin used by the transactions of the block, by builtin
+    /// name.
+    pub builtin_counts: BTreeMap<String, usize>,
+    /// The time it took to build the block.
+    pub build_duration: Duration,
+}
+
+/// Input of the request for the progress of the block being built for a proposal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetProposalProgressInput {
+    pub proposal_id: ProposalId,
+}
+
+/// The progress of building the block of a proposal of the active height, generated or validated.
+/// Lets the proposer's consensus decide whether to keep waiting for a long build or abort it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalProgress {
+    /// The number of transactions added to the block so far, including the reverted ones.
+    pub n_executed_txs: usize,
+    pub n_reverted_txs: usize,
+    pub l1_gas_used: u128,
+    pub l1_data_gas_used: u128,
+    pub l2_gas_used: u128,
+    /// The time left until the block is closed by the deadline of the build, zero once the block
+    /// is built.
+    pub time_remaining: Duration,
+    /// Whether the block was built, and can be decided on.
+    pub is_done: bool,
+}
+
+/// The state changes of the block the batcher is building, on top of the state of the previous
+/// block. Lets the gateway validate transactions that follow the ones already in the block, e.g.,
+/// the next transactions of an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingState {
+    pub block_number: BlockNumber,
+    /// The changes made by the transactions added to the block so far. Declared classes are not
+    /// included.
+    pub state_diff: ThinStateDiff,
+}
+
+/// Input of the request informing the batcher that consensus decided on the block of a proposal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionReachedInput {
+    pub proposal_id: ProposalId,
+}
+
+/// Input of the request to start working on a height, before consensus makes its proposals.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StartHeightInput {
+    pub height: BlockNumber,
+}
+
+/// Input of the request to apply a block the node synced from its peers, e.g., while catching up
+/// with the network, without building or validating it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddSyncedBlockInput {
+    pub header: BlockHeader,
+    pub state_diff: ThinStateDiff,
+    pub tx_hashes: Vec<TransactionHash>,
+}
+
+/// Input of the request for a block the batcher built and committed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetCommittedBlockInput {
+    pub block_number: BlockNumber,
+}
 
-// TODO(Tsabary/Yael/Dafna): Populate the data structure used to invoke the batcher.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BatcherFnTwoInput {}
+/// The execution result of a transaction of a committed block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub tx_hash: TransactionHash,
+    pub actual_fee: Fee,
+    pub l1_gas_used: u128,
+    pub l1_data_gas_used: u128,
+    pub l2_gas_used: u128,
+    pub n_events: usize,
+    pub is_reverted: bool,
+}
 
-// TODO(Tsabary/Yael/Dafna): Replace with the actual return type of the batcher function.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BatcherFnOneReturnValue {}
+/// A block the batcher built and committed to the storage, as the sync and feeder layers serve it:
+/// the storage holds the header and the state diff of the block, but not its transactions and
+/// their receipts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommittedBlock {
+    pub header: BlockHeader,
+    /// The transactions of the block, in execution order.
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions of the block, in the order of `txs`.
+    pub receipts: Vec<TransactionReceipt>,
+    pub state_diff: ThinStateDiff,
+}
 
-// TODO(Tsabary/Yael/Dafna): Replace with the actual return type of the batcher function.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BatcherFnTwoReturnValue {}
+/// Input of the request informing the batcher that consensus decided on no block for a height.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkipHeightInput {
+    pub height: BlockNumber,
+}
 
 pub type BatcherResult<T> = Result<T, BatcherError>;
@@ -1,60 +1,218 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use mockall::predicate::*;
 use mockall::*;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
 use starknet_mempool_infra::component_client::{
     ClientError,
     LocalComponentClient,
     RemoteComponentClient,
 };
-use starknet_mempool_infra::component_definitions::ComponentRequestAndResponseSender;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use starknet_mempool_infra::component_error::{Component, ComponentError};
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use crate::batcher_types::{
-    BatcherFnOneInput,
-    BatcherFnOneReturnValue,
-    BatcherFnTwoInput,
-    BatcherFnTwoReturnValue,
+    AddSyncedBlockInput,
     BatcherResult,
+    BuildProposalInput,
+    BuildProposalReturnValue,
+    CommittedBlock,
+    DecisionReachedInput,
+    ForceAbortAndResyncInput,
+    ForceAbortAndResyncReturnValue,
+    GetCommittedBlockInput,
+    GetProposalContentInput,
+    GetProposalContentReturnValue,
+    GetProposalMetadataInput,
+    GetProposalProgressInput,
+    GetValidationStatusInput,
+    ImportBlockInput,
+    ImportBlockStatus,
+    PendingState,
+    ProposalId,
+    ProposalMetadata,
+    ProposalProgress,
+    ProposalState,
+    ProposalStatus,
+    ReproposeProposalInput,
+    ReproposeProposalReturnValue,
+    SkipHeightInput,
+    StartHeightInput,
+    ValidateProposalInput,
+    ValidationStatus,
 };
 use crate::errors::BatcherError;
+use crate::versioning::{VersionedBatcherRequest, VersionedBatcherResponse};
 
 pub type LocalBatcherClientImpl = LocalComponentClient<BatcherRequest, BatcherResponse>;
-pub type RemoteBatcherClientImpl = RemoteComponentClient<BatcherRequest, BatcherResponse>;
+pub type RemoteBatcherClientImpl =
+    RemoteComponentClient<VersionedBatcherRequest, VersionedBatcherResponse>;
 pub type BatcherClientResult<T> = Result<T, BatcherClientError>;
 pub type BatcherRequestAndResponseSender =
     ComponentRequestAndResponseSender<BatcherRequest, BatcherResponse>;
 pub type SharedBatcherClient = Arc<dyn BatcherClient>;
 
+/// The interval at which clients poll the status of the proposal validations they started.
+pub const VALIDATION_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
 /// Serves as the batcher's shared interface. Requires `Send + Sync` to allow transferring and
 /// sharing resources (inputs, futures) across threads.
 #[automock]
 #[async_trait]
 pub trait BatcherClient: Send + Sync {
-    async fn batcher_fn_one(
+    /// Starts generating a proposal. Its transactions are retrieved with `get_proposal_content`.
+    async fn build_proposal(
+        &self,
+        input: BuildProposalInput,
+    ) -> BatcherClientResult<BuildProposalReturnValue>;
+
+    /// Validates a proposal received from another node, returning once the validation is done.
+    /// The validation runs in the background of the batcher, and its status is polled meanwhile, so
+    /// that the batcher keeps serving other requests.
+    async fn validate_proposal(
+        &self,
+        input: ValidateProposalInput,
+    ) -> BatcherClientResult<ProposalStatus>;
+
+    /// Returns the status of a proposal validation started by `validate_proposal`.
+    async fn get_validation_status(
+        &self,
+        input: GetValidationStatusInput,
+    ) -> BatcherClientResult<ValidationStatus>;
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentReturnValue>;
+
+    async fn force_abort_and_resync(
+        &self,
+        input: ForceAbortAndResyncInput,
+    ) -> BatcherClientResult<ForceAbortAndResyncReturnValue>;
+
+    async fn skip_height(&self, input: SkipHeightInput) -> BatcherClientResult<()>;
+
+    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()>;
+
+    /// Imports a block built by an external builder as the local proposal, returning once it is
+    /// validated. Its transactions are then retrieved with `get_proposal_content`.
+    async fn import_block(&self, input: ImportBlockInput)
+        -> BatcherClientResult<ImportBlockStatus>;
+
+    /// Starts working on a height, before its proposals are made.
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()>;
+
+    /// Applies a block the node synced from its peers to the storage, and advances past its
+    /// height.
+    async fn add_synced_block(&self, input: AddSyncedBlockInput) -> BatcherClientResult<()>;
+
+    /// Returns the state of the proposals of the active height, for operators.
+    async fn get_proposal_state(&self) -> BatcherClientResult<ProposalState>;
+
+    /// Returns the resources used by the block built for a proposal of the active height.
+    async fn get_proposal_metadata(
         &self,
-        batcher_fn_one_input: BatcherFnOneInput,
-    ) -> BatcherClientResult<BatcherFnOneReturnValue>;
+        input: GetProposalMetadataInput,
+    ) -> BatcherClientResult<ProposalMetadata>;
+
+    /// Returns the state changes of the block being built at the active height, if any.
+    async fn get_pending_state(&self) -> BatcherClientResult<Option<PendingState>>;
 
-    async fn batcher_fn_two(
+    /// Returns the progress of building the block of the given proposal.
+    async fn get_proposal_progress(
         &self,
-        batcher_fn_two_input: BatcherFnTwoInput,
-    ) -> BatcherClientResult<BatcherFnTwoReturnValue>;
+        input: GetProposalProgressInput,
+    ) -> BatcherClientResult<ProposalProgress>;
+
+    /// Re-proposes a proposal generated earlier in the active height, without rebuilding its
+    /// block. Its content is then served by `get_proposal_content`.
+    async fn repropose_proposal(
+        &self,
+        input: ReproposeProposalInput,
+    ) -> BatcherClientResult<ReproposeProposalReturnValue>;
+
+    /// Returns a block the batcher built and committed, with its transactions and their receipts,
+    /// if it is still kept by the batcher.
+    async fn get_committed_block(
+        &self,
+        input: GetCommittedBlockInput,
+    ) -> BatcherClientResult<Option<CommittedBlock>>;
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
 pub enum BatcherRequest {
-    BatcherFnOne(BatcherFnOneInput),
-    BatcherFnTwo(BatcherFnTwoInput),
+    BuildProposal(BuildProposalInput),
+    // Answered once the validation started; its status is then polled with `GetValidationStatus`.
+    ValidateProposal(ValidateProposalInput),
+    GetProposalContent(GetProposalContentInput),
+    ForceAbortAndResync(ForceAbortAndResyncInput),
+    SkipHeight(SkipHeightInput),
+    DecisionReached(DecisionReachedInput),
+    ImportBlock(ImportBlockInput),
+    IsAlive,
+    IsReady,
+    StartHeight(StartHeightInput),
+    // Boxed, as synced blocks are much larger than the other requests.
+    AddSyncedBlock(Box<AddSyncedBlockInput>),
+    GetProposalState,
+    GetProposalMetadata(GetProposalMetadataInput),
+    GetPendingState,
+    GetProposalProgress(GetProposalProgressInput),
+    ReproposeProposal(ReproposeProposalInput),
+    GetCommittedBlock(GetCommittedBlockInput),
+    GetValidationStatus(GetValidationStatusInput),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum BatcherResponse {
-    BatcherFnOne(BatcherResult<BatcherFnOneReturnValue>),
-    BatcherFnTwo(BatcherResult<BatcherFnTwoReturnValue>),
+    BuildProposal(BatcherResult<BuildProposalReturnValue>),
+    ValidateProposal(BatcherResult<()>),
+    GetProposalContent(BatcherResult<GetProposalContentReturnValue>),
+    ForceAbortAndResync(BatcherResult<ForceAbortAndResyncReturnValue>),
+    SkipHeight(BatcherResult<()>),
+    DecisionReached(BatcherResult<()>),
+    ImportBlock(BatcherResult<ImportBlockStatus>),
+    IsAlive(bool),
+    IsReady(bool),
+    StartHeight(BatcherResult<()>),
+    AddSyncedBlock(BatcherResult<()>),
+    GetProposalState(BatcherResult<ProposalState>),
+    GetProposalMetadata(BatcherResult<ProposalMetadata>),
+    GetPendingState(BatcherResult<Option<PendingState>>),
+    GetProposalProgress(BatcherResult<ProposalProgress>),
+    ReproposeProposal(BatcherResult<ReproposeProposalReturnValue>),
+    // Boxed, as committed blocks are much larger than the other responses.
+    GetCommittedBlock(BatcherResult<Option<Box<CommittedBlock>>>),
+    GetValidationStatus(BatcherResult<ValidationStatus>),
+}
+
+impl HealthCheckRequest for BatcherRequest {
+    fn is_alive() -> Self {
+        BatcherRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        BatcherRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for BatcherResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            BatcherResponse::IsAlive(health) | BatcherResponse::IsReady(health) => Some(*health),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -65,44 +223,420 @@ pub enum BatcherClientError {
     BatcherError(#[from] BatcherError),
 }
 
+impl ComponentError for BatcherClientError {
+    fn component(&self) -> Component {
+        Component::Batcher
+    }
+
+    fn is_retriable(&self) -> bool {
+        match self {
+            BatcherClientError::ClientError(err) => err.is_retriable(),
+            BatcherClientError::BatcherError(err) => err.is_retriable(),
+        }
+    }
+}
+
+impl CodedError for BatcherClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            BatcherClientError::ClientError(err) => err.error_code(),
+            BatcherClientError::BatcherError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            BatcherClientError::ClientError(err) => err.severity(),
+            BatcherClientError::BatcherError(err) => err.severity(),
+        }
+    }
+}
+
 #[async_trait]
 impl BatcherClient for LocalBatcherClientImpl {
-    async fn batcher_fn_one(
+    async fn build_proposal(
+        &self,
+        input: BuildProposalInput,
+    ) -> BatcherClientResult<BuildProposalReturnValue> {
+        let request = BatcherRequest::BuildProposal(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, BuildProposal, BatcherClientError, BatcherError)
+    }
+
+    async fn validate_proposal(
+        &self,
+        input: ValidateProposalInput,
+    ) -> BatcherClientResult<ProposalStatus> {
+        let proposal_id = input.proposal_id;
+        let request = BatcherRequest::ValidateProposal(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            ValidateProposal,
+            BatcherClientError,
+            BatcherError
+        )?;
+        await_validation(self, proposal_id).await
+    }
+
+    async fn get_validation_status(
+        &self,
+        input: GetValidationStatusInput,
+    ) -> BatcherClientResult<ValidationStatus> {
+        let request = BatcherRequest::GetValidationStatus(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetValidationStatus,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_content(
         &self,
-        batcher_fn_one_input: BatcherFnOneInput,
-    ) -> BatcherClientResult<BatcherFnOneReturnValue> {
-        let request = BatcherRequest::BatcherFnOne(batcher_fn_one_input);
-        let response = self.send(request).await;
-        handle_response_variants!(BatcherResponse, BatcherFnOne, BatcherClientError, BatcherError)
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentReturnValue> {
+        let request = BatcherRequest::GetProposalContent(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalContent,
+            BatcherClientError,
+            BatcherError
+        )
     }
 
-    async fn batcher_fn_two(
+    async fn force_abort_and_resync(
         &self,
-        batcher_fn_two_input: BatcherFnTwoInput,
-    ) -> BatcherClientResult<BatcherFnTwoReturnValue> {
-        let request = BatcherRequest::BatcherFnTwo(batcher_fn_two_input);
-        let response = self.send(request).await;
-        handle_response_variants!(BatcherResponse, BatcherFnTwo, BatcherClientError, BatcherError)
+        input: ForceAbortAndResyncInput,
+    ) -> BatcherClientResult<ForceAbortAndResyncReturnValue> {
+        let request = BatcherRequest::ForceAbortAndResync(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            ForceAbortAndResync,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn skip_height(&self, input: SkipHeightInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::SkipHeight(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, SkipHeight, BatcherClientError, BatcherError)
+    }
+
+    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::DecisionReached(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            DecisionReached,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn import_block(
+        &self,
+        input: ImportBlockInput,
+    ) -> BatcherClientResult<ImportBlockStatus> {
+        let request = BatcherRequest::ImportBlock(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, ImportBlock, BatcherClientError, BatcherError)
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::StartHeight(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, StartHeight, BatcherClientError, BatcherError)
+    }
+
+    async fn add_synced_block(&self, input: AddSyncedBlockInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::AddSyncedBlock(Box::new(input));
+        let response = self.send(request).await?;
+        handle_response_variants!(BatcherResponse, AddSyncedBlock, BatcherClientError, BatcherError)
+    }
+
+    async fn get_proposal_state(&self) -> BatcherClientResult<ProposalState> {
+        let request = BatcherRequest::GetProposalState;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalState,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_metadata(
+        &self,
+        input: GetProposalMetadataInput,
+    ) -> BatcherClientResult<ProposalMetadata> {
+        let request = BatcherRequest::GetProposalMetadata(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalMetadata,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_pending_state(&self) -> BatcherClientResult<Option<PendingState>> {
+        let request = BatcherRequest::GetPendingState;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetPendingState,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_progress(
+        &self,
+        input: GetProposalProgressInput,
+    ) -> BatcherClientResult<ProposalProgress> {
+        let request = BatcherRequest::GetProposalProgress(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalProgress,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn repropose_proposal(
+        &self,
+        input: ReproposeProposalInput,
+    ) -> BatcherClientResult<ReproposeProposalReturnValue> {
+        let request = BatcherRequest::ReproposeProposal(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            BatcherResponse,
+            ReproposeProposal,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_committed_block(
+        &self,
+        input: GetCommittedBlockInput,
+    ) -> BatcherClientResult<Option<CommittedBlock>> {
+        let request = BatcherRequest::GetCommittedBlock(input);
+        let response = self.send(request).await?;
+        let committed_block: Option<Box<CommittedBlock>> = handle_response_variants!(
+            BatcherResponse,
+            GetCommittedBlock,
+            BatcherClientError,
+            BatcherError
+        )?;
+        Ok(committed_block.map(|committed_block| *committed_block))
     }
 }
 
 #[async_trait]
 impl BatcherClient for RemoteBatcherClientImpl {
-    async fn batcher_fn_one(
+    async fn build_proposal(
         &self,
-        batcher_fn_one_input: BatcherFnOneInput,
-    ) -> BatcherClientResult<BatcherFnOneReturnValue> {
-        let request = BatcherRequest::BatcherFnOne(batcher_fn_one_input);
-        let response = self.send(request).await?;
-        handle_response_variants!(BatcherResponse, BatcherFnOne, BatcherClientError, BatcherError)
+        input: BuildProposalInput,
+    ) -> BatcherClientResult<BuildProposalReturnValue> {
+        let request = BatcherRequest::BuildProposal(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(BatcherResponse, BuildProposal, BatcherClientError, BatcherError)
     }
 
-    async fn batcher_fn_two(
+    async fn validate_proposal(
         &self,
-        batcher_fn_two_input: BatcherFnTwoInput,
-    ) -> BatcherClientResult<BatcherFnTwoReturnValue> {
-        let request = BatcherRequest::BatcherFnTwo(batcher_fn_two_input);
-        let response = self.send(request).await?;
-        handle_response_variants!(BatcherResponse, BatcherFnTwo, BatcherClientError, BatcherError)
+        input: ValidateProposalInput,
+    ) -> BatcherClientResult<ProposalStatus> {
+        let proposal_id = input.proposal_id;
+        let request = BatcherRequest::ValidateProposal(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            ValidateProposal,
+            BatcherClientError,
+            BatcherError
+        )?;
+        await_validation(self, proposal_id).await
+    }
+
+    async fn get_validation_status(
+        &self,
+        input: GetValidationStatusInput,
+    ) -> BatcherClientResult<ValidationStatus> {
+        let request = BatcherRequest::GetValidationStatus(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetValidationStatus,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_content(
+        &self,
+        input: GetProposalContentInput,
+    ) -> BatcherClientResult<GetProposalContentReturnValue> {
+        let request = BatcherRequest::GetProposalContent(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalContent,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn force_abort_and_resync(
+        &self,
+        input: ForceAbortAndResyncInput,
+    ) -> BatcherClientResult<ForceAbortAndResyncReturnValue> {
+        let request = BatcherRequest::ForceAbortAndResync(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            ForceAbortAndResync,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn skip_height(&self, input: SkipHeightInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::SkipHeight(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(BatcherResponse, SkipHeight, BatcherClientError, BatcherError)
+    }
+
+    async fn decision_reached(&self, input: DecisionReachedInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::DecisionReached(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            DecisionReached,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn import_block(
+        &self,
+        input: ImportBlockInput,
+    ) -> BatcherClientResult<ImportBlockStatus> {
+        let request = BatcherRequest::ImportBlock(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(BatcherResponse, ImportBlock, BatcherClientError, BatcherError)
+    }
+
+    async fn start_height(&self, input: StartHeightInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::StartHeight(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(BatcherResponse, StartHeight, BatcherClientError, BatcherError)
+    }
+
+    async fn add_synced_block(&self, input: AddSyncedBlockInput) -> BatcherClientResult<()> {
+        let request = BatcherRequest::AddSyncedBlock(Box::new(input));
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(BatcherResponse, AddSyncedBlock, BatcherClientError, BatcherError)
+    }
+
+    async fn get_proposal_state(&self) -> BatcherClientResult<ProposalState> {
+        let request = BatcherRequest::GetProposalState;
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalState,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_metadata(
+        &self,
+        input: GetProposalMetadataInput,
+    ) -> BatcherClientResult<ProposalMetadata> {
+        let request = BatcherRequest::GetProposalMetadata(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalMetadata,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_pending_state(&self) -> BatcherClientResult<Option<PendingState>> {
+        let request = BatcherRequest::GetPendingState;
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetPendingState,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_proposal_progress(
+        &self,
+        input: GetProposalProgressInput,
+    ) -> BatcherClientResult<ProposalProgress> {
+        let request = BatcherRequest::GetProposalProgress(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            GetProposalProgress,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn repropose_proposal(
+        &self,
+        input: ReproposeProposalInput,
+    ) -> BatcherClientResult<ReproposeProposalReturnValue> {
+        let request = BatcherRequest::ReproposeProposal(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        handle_response_variants!(
+            BatcherResponse,
+            ReproposeProposal,
+            BatcherClientError,
+            BatcherError
+        )
+    }
+
+    async fn get_committed_block(
+        &self,
+        input: GetCommittedBlockInput,
+    ) -> BatcherClientResult<Option<CommittedBlock>> {
+        let request = BatcherRequest::GetCommittedBlock(input);
+        let response = self.send(VersionedBatcherRequest::new(request)).await?.response;
+        let committed_block: Option<Box<CommittedBlock>> = handle_response_variants!(
+            BatcherResponse,
+            GetCommittedBlock,
+            BatcherClientError,
+            BatcherError
+        )?;
+        Ok(committed_block.map(|committed_block| *committed_block))
+    }
+}
+
+// Polls the status of a started validation until it is done.
+async fn await_validation(
+    client: &(impl BatcherClient + ?Sized),
+    proposal_id: ProposalId,
+) -> BatcherClientResult<ProposalStatus> {
+    loop {
+        match client.get_validation_status(GetValidationStatusInput { proposal_id }).await? {
+            ValidationStatus::InProgress => {
+                tokio::time::sleep(VALIDATION_STATUS_POLL_INTERVAL).await
+            }
+            ValidationStatus::Done(status) => return Ok(status),
+        }
     }
 }
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(test)]
+#[path = "deadline_test.rs"]
+mod deadline_test;
+
+/// A deadline of a request, as an absolute wall-clock time so that it keeps its meaning across
+/// processes, unlike an instant of the monotonic clock of a single process. It is converted to the
+/// monotonic clock of the process that waits on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Deadline(pub DateTime<Utc>);
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+#[error(
+    "Deadline {deadline} is {time_left:?} away, more than the allowed {max_time_left:?}; the \
+     clocks of the caller and the callee may be skewed."
+)]
+pub struct DeadlineTooFarError {
+    pub deadline: DateTime<Utc>,
+    pub time_left: Duration,
+    pub max_time_left: Duration,
+}
+
+impl Deadline {
+    /// The deadline that is the given time away from now.
+    pub fn after(time_left: Duration) -> Self {
+        Self(Utc::now() + time_left)
+    }
+
+    /// Converts an instant of the monotonic clock of this process.
+    pub fn from_instant(instant: tokio::time::Instant) -> Self {
+        Self::after(instant.saturating_duration_since(tokio::time::Instant::now()))
+    }
+
+    /// Converts the deadline to the monotonic clock of this process. A deadline that passed is
+    /// converted to now. A deadline that is more than `max_time_left` away is rejected, as it
+    /// indicates a skew between the clock of the caller and the clock of this process.
+    pub fn to_instant(
+        &self,
+        max_time_left: Duration,
+    ) -> Result<tokio::time::Instant, DeadlineTooFarError> {
+        Ok(tokio::time::Instant::now() + self.time_left_within(max_time_left)?)
+    }
+
+    /// The time left until the deadline, zero if it passed. A deadline that is more than
+    /// `max_time_left` away is rejected, as in [`Self::to_instant`].
+    pub fn time_left_within(
+        &self,
+        max_time_left: Duration,
+    ) -> Result<Duration, DeadlineTooFarError> {
+        let time_left = self.time_left();
+        if time_left > max_time_left {
+            return Err(DeadlineTooFarError { deadline: self.0, time_left, max_time_left });
+        }
+        Ok(time_left)
+    }
+
+    /// The time left until the deadline, zero if it passed.
+    pub fn time_left(&self) -> Duration {
+        (self.0 - Utc::now()).to_std().unwrap_or_default()
+    }
+}
+
+impl From<DateTime<Utc>> for Deadline {
+    fn from(deadline: DateTime<Utc>) -> Self {
+        Self(deadline)
+    }
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use bincode::{deserialize, serialize};
+use chrono::{DateTime, Utc};
+
+use crate::deadline::{Deadline, DeadlineTooFarError};
+
+const MAX_TIME_LEFT: Duration = Duration::from_secs(60);
+
+#[test]
+fn deadline_is_encoded_as_wall_clock_time() {
+    let wall_clock_deadline = Utc::now();
+
+    let encoded_deadline = serialize(&Deadline(wall_clock_deadline)).unwrap();
+
+    assert_eq!(deserialize::<DateTime<Utc>>(&encoded_deadline).unwrap(), wall_clock_deadline);
+}
+
+#[test]
+fn instant_round_trip() {
+    let instant = tokio::time::Instant::now() + Duration::from_secs(10);
+
+    let converted_instant = Deadline::from_instant(instant).to_instant(MAX_TIME_LEFT).unwrap();
+
+    // The conversions only lose the time that passed between the clock readings.
+    let difference = if converted_instant > instant {
+        converted_instant - instant
+    } else {
+        instant - converted_instant
+    };
+    assert!(difference < Duration::from_secs(1));
+}
+
+#[test]
+fn passed_deadline_is_converted_to_now() {
+    let deadline = Deadline(Utc::now() - Duration::from_secs(10));
+    let before_conversion = tokio::time::Instant::now();
+
+    let converted_instant = deadline.to_instant(MAX_TIME_LEFT).unwrap();
+
+    assert!(before_conversion <= converted_instant);
+    assert!(converted_instant <= tokio::time::Instant::now());
+}
+
+#[test]
+fn far_deadline_is_rejected() {
+    let deadline = Deadline::after(MAX_TIME_LEFT * 2);
+
+    assert_matches!(
+        deadline.to_instant(MAX_TIME_LEFT),
+        Err(DeadlineTooFarError { max_time_left: MAX_TIME_LEFT, .. })
+    );
+}
@@ -1,9 +1,122 @@
 use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
 use thiserror::Error;
 
+use crate::batcher_types::{BlockInfoValidationError, ProposalId, ProposalRound};
+use crate::deadline::DeadlineTooFarError;
+
+#[cfg(test)]
+#[path = "errors_test.rs"]
+mod errors_test;
+
 // TODO(Tsabary/Yael/Dafna): Populate with actual errors.
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BatcherError {
+    #[error("Admin actions are disabled.")]
+    AdminActionsDisabled,
+    #[error(
+        "Requested height {requested_height} does not match the active height {active_height}."
+    )]
+    HeightMismatch { active_height: BlockNumber, requested_height: BlockNumber },
+    #[error("Internal server error.")]
+    InternalError,
     #[error("Placeholder error message")]
     Placeholder,
+    #[error("Proposal with id {proposal_id} was not found.")]
+    ProposalNotFound { proposal_id: ProposalId },
+    #[error(
+        "Received a request for proposal {new_proposal_id} while proposal {active_proposal_id} is \
+         being generated."
+    )]
+    ServerBusy { active_proposal_id: ProposalId, new_proposal_id: ProposalId },
+    // Appended, so that the encoding of the other variants is kept across API versions.
+    #[error(transparent)]
+    DeadlineTooFar(#[from] DeadlineTooFarError),
+    #[error("Proposal {proposal_id} ended without a block, e.g., it failed or was aborted.")]
+    ProposalFailed { proposal_id: ProposalId },
+    #[error(
+        "Proposal {proposal_id} of {round} is stale, a proposal of {latest_round} was already \
+         started."
+    )]
+    StaleProposal { proposal_id: ProposalId, round: ProposalRound, latest_round: ProposalRound },
+    #[error(
+        "Proposal {proposal_id} is for height {proposal_height}, but the active height is \
+         {active_height}."
+    )]
+    ProposalHeightMismatch {
+        proposal_id: ProposalId,
+        active_height: BlockNumber,
+        proposal_height: BlockNumber,
+    },
+    #[error(
+        "Synced block {block_height} does not follow the storage, the next block to store is \
+         {storage_height}."
+    )]
+    SyncedBlockHeightMismatch { storage_height: BlockNumber, block_height: BlockNumber },
+    #[error(
+        "Proposal {proposal_id} can't be re-proposed: {}.",
+        if *.evicted {
+            "it was evicted from the proposal cache"
+        } else {
+            "it was not generated at the active height"
+        }
+    )]
+    ProposalNotCached { proposal_id: ProposalId, evicted: bool },
+    #[error("Proposal {proposal_id} has an invalid block info: {error}")]
+    InvalidBlockInfo { proposal_id: ProposalId, error: BlockInfoValidationError },
+    #[error(
+        "Proposal id {proposal_id} was already used at the active height, proposal ids may not be \
+         reused within a height."
+    )]
+    DuplicateProposalId { proposal_id: ProposalId },
+}
+
+impl BatcherError {
+    /// Whether the request may succeed if sent again as is, once the batcher finishes its active
+    /// proposal.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, BatcherError::ServerBusy { .. })
+    }
+}
+
+impl CodedError for BatcherError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            BatcherError::AdminActionsDisabled => codes::BATCHER_ADMIN_ACTIONS_DISABLED,
+            BatcherError::HeightMismatch { .. } => codes::BATCHER_HEIGHT_MISMATCH,
+            BatcherError::InternalError => codes::BATCHER_INTERNAL_ERROR,
+            BatcherError::Placeholder => codes::BATCHER_PLACEHOLDER,
+            BatcherError::ProposalNotFound { .. } => codes::BATCHER_PROPOSAL_NOT_FOUND,
+            BatcherError::ServerBusy { .. } => codes::BATCHER_SERVER_BUSY,
+            BatcherError::DeadlineTooFar(_) => codes::BATCHER_DEADLINE_TOO_FAR,
+            BatcherError::ProposalFailed { .. } => codes::BATCHER_PROPOSAL_FAILED,
+            BatcherError::StaleProposal { .. } => codes::BATCHER_STALE_PROPOSAL,
+            BatcherError::ProposalHeightMismatch { .. } => codes::BATCHER_PROPOSAL_HEIGHT_MISMATCH,
+            BatcherError::SyncedBlockHeightMismatch { .. } => {
+                codes::BATCHER_SYNCED_BLOCK_HEIGHT_MISMATCH
+            }
+            BatcherError::ProposalNotCached { .. } => codes::BATCHER_PROPOSAL_NOT_CACHED,
+            BatcherError::InvalidBlockInfo { .. } => codes::BATCHER_INVALID_BLOCK_INFO,
+            BatcherError::DuplicateProposalId { .. } => codes::BATCHER_DUPLICATE_PROPOSAL_ID,
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            BatcherError::AdminActionsDisabled
+            | BatcherError::DeadlineTooFar(_)
+            | BatcherError::ProposalFailed { .. }
+            | BatcherError::StaleProposal { .. } => Severity::Info,
+            BatcherError::HeightMismatch { .. }
+            | BatcherError::ProposalNotFound { .. }
+            | BatcherError::ServerBusy { .. }
+            | BatcherError::ProposalHeightMismatch { .. }
+            | BatcherError::SyncedBlockHeightMismatch { .. }
+            | BatcherError::ProposalNotCached { .. }
+            | BatcherError::InvalidBlockInfo { .. }
+            | BatcherError::DuplicateProposalId { .. } => Severity::Warning,
+            BatcherError::InternalError | BatcherError::Placeholder => Severity::Error,
+        }
+    }
 }
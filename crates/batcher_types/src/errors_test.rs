@@ -0,0 +1,50 @@
+use starknet_error_codes::{codes, CodedError};
+use starknet_mempool_infra::component_error::{Component, ComponentError};
+
+use crate::communication::BatcherClientError;
+use crate::errors::BatcherError;
+
+#[test]
+fn busy_batcher_is_retriable() {
+    let err = BatcherClientError::BatcherError(BatcherError::ServerBusy {
+        active_proposal_id: 0,
+        new_proposal_id: 1,
+    });
+
+    assert!(err.is_retriable());
+    assert_eq!(err.component(), Component::Batcher);
+    assert_eq!(err.error_code(), codes::BATCHER_SERVER_BUSY);
+}
+
+#[test]
+fn rejected_request_is_not_retriable() {
+    let err = BatcherClientError::BatcherError(BatcherError::ProposalNotFound { proposal_id: 0 });
+
+    assert!(!err.is_retriable());
+    assert_eq!(err.error_code(), codes::BATCHER_PROPOSAL_NOT_FOUND);
+}
+
+#[test]
+fn reproposal_error_tells_evicted_proposals_apart() {
+    let not_generated = BatcherError::ProposalNotCached { proposal_id: 1, evicted: false };
+    let evicted = BatcherError::ProposalNotCached { proposal_id: 1, evicted: true };
+
+    assert_eq!(
+        not_generated.to_string(),
+        "Proposal 1 can't be re-proposed: it was not generated at the active height."
+    );
+    assert_eq!(
+        evicted.to_string(),
+        "Proposal 1 can't be re-proposed: it was evicted from the proposal cache."
+    );
+    assert_eq!(evicted.error_code(), codes::BATCHER_PROPOSAL_NOT_CACHED);
+}
+
+#[test]
+fn duplicate_proposal_id_is_not_retriable() {
+    let err =
+        BatcherClientError::BatcherError(BatcherError::DuplicateProposalId { proposal_id: 3 });
+
+    assert!(!err.is_retriable());
+    assert_eq!(err.error_code(), codes::BATCHER_DUPLICATE_PROPOSAL_ID);
+}
@@ -1,3 +1,5 @@
 pub mod batcher_types;
 pub mod communication;
+pub mod deadline;
 pub mod errors;
+pub mod versioning;
@@ -0,0 +1,556 @@
+//! Versioned encoding of the batcher requests and responses sent between processes.
+//!
+//! Every request is encoded as its API version followed by the request, and answered in the same
+//! version, so that a batcher can serve clients that were not upgraded yet. Requests of the
+//! previous version are converted to the current [`BatcherRequest`] when decoded, and their
+//! responses are converted back when encoded; other versions are rejected.
+//!
+//! When changing the layout of [`BatcherRequest`] or [`BatcherResponse`], bump
+//! [`BATCHER_API_VERSION`] and replace the `V<n>` types with the layout of the version before it.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::{self, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet_api::block::BlockNumber;
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_infra::component_definitions::{HealthCheckRequest, HealthCheckResponse};
+
+use crate::batcher_types::{
+    AddSyncedBlockInput,
+    BatcherResult,
+    BlockCommitments,
+    BuildProposalInput,
+    BuildProposalReturnValue,
+    CommittedBlock,
+    DecisionReachedInput,
+    EnvironmentFingerprint,
+    ForceAbortAndResyncInput,
+    ForceAbortAndResyncReturnValue,
+    GetCommittedBlockInput,
+    GetProposalContentInput,
+    GetProposalContentReturnValue,
+    GetProposalMetadataInput,
+    GetProposalProgressInput,
+    ImportBlockInput,
+    ImportBlockStatus,
+    PendingState,
+    ProposalBlockInfo,
+    ProposalCommitment,
+    ProposalId,
+    ProposalMetadata,
+    ProposalProgress,
+    ProposalState,
+    ProposalStatus,
+    ProposalValidationError,
+    ReproposeProposalInput,
+    ReproposeProposalReturnValue,
+    Round,
+    SkipHeightInput,
+    StartHeightInput,
+    TxExclusionReason,
+    ValidateProposalInput,
+    ValidationStatus,
+};
+use crate::communication::{BatcherRequest, BatcherResponse};
+use crate::deadline::Deadline;
+
+#[cfg(test)]
+#[path = "versioning_test.rs"]
+mod versioning_test;
+
+/// The version of the batcher API that requests are encoded with.
+pub const BATCHER_API_VERSION: u32 = 2;
+
+/// Version 1, before proposal validations were polled and the environment fingerprints were
+/// salted with the seed of their proposal.
+pub const BATCHER_API_VERSION_1: u32 = 1;
+
+/// The environment fingerprint of version 1, before it was salted with the seed of its proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentFingerprintV1(pub [u8; 32]);
+
+impl From<EnvironmentFingerprint> for EnvironmentFingerprintV1 {
+    fn from(fingerprint: EnvironmentFingerprint) -> Self {
+        Self(fingerprint.digest)
+    }
+}
+
+/// The input of a proposal validation in version 1.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateProposalInputV1 {
+    pub proposal_id: ProposalId,
+    pub deadline: Deadline,
+    pub height: BlockNumber,
+    pub round: Round,
+    pub txs: Vec<Transaction>,
+    pub environment_fingerprint: Option<EnvironmentFingerprintV1>,
+    pub block_info: Option<ProposalBlockInfo>,
+}
+
+// Fingerprints of version 1 are not salted with the seed of their proposal, so they can't be
+// compared with the fingerprint of the validator; the environment of their proposals is unknown.
+impl From<ValidateProposalInputV1> for ValidateProposalInput {
+    fn from(input: ValidateProposalInputV1) -> Self {
+        let ValidateProposalInputV1 {
+            proposal_id, deadline, height, round, txs, block_info, ..
+        } = input;
+        Self {
+            proposal_id,
+            deadline,
+            height,
+            round,
+            txs,
+            environment_fingerprint: None,
+            block_info,
+        }
+    }
+}
+
+/// The requests of version 1, before the validation status request was appended.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BatcherRequestV1 {
+    BuildProposal(BuildProposalInput),
+    // Answered once the validation is done.
+    ValidateProposal(ValidateProposalInputV1),
+    GetProposalContent(GetProposalContentInput),
+    ForceAbortAndResync(ForceAbortAndResyncInput),
+    SkipHeight(SkipHeightInput),
+    DecisionReached(DecisionReachedInput),
+    ImportBlock(ImportBlockInput),
+    IsAlive,
+    IsReady,
+    StartHeight(StartHeightInput),
+    AddSyncedBlock(Box<AddSyncedBlockInput>),
+    GetProposalState,
+    GetProposalMetadata(GetProposalMetadataInput),
+    GetPendingState,
+    GetProposalProgress(GetProposalProgressInput),
+    ReproposeProposal(ReproposeProposalInput),
+    GetCommittedBlock(GetCommittedBlockInput),
+}
+
+impl From<BatcherRequestV1> for BatcherRequest {
+    fn from(request: BatcherRequestV1) -> Self {
+        match request {
+            BatcherRequestV1::BuildProposal(input) => BatcherRequest::BuildProposal(input),
+            BatcherRequestV1::ValidateProposal(input) => {
+                BatcherRequest::ValidateProposal(input.into())
+            }
+            BatcherRequestV1::GetProposalContent(input) => {
+                BatcherRequest::GetProposalContent(input)
+            }
+            BatcherRequestV1::ForceAbortAndResync(input) => {
+                BatcherRequest::ForceAbortAndResync(input)
+            }
+            BatcherRequestV1::SkipHeight(input) => BatcherRequest::SkipHeight(input),
+            BatcherRequestV1::DecisionReached(input) => BatcherRequest::DecisionReached(input),
+            BatcherRequestV1::ImportBlock(input) => BatcherRequest::ImportBlock(input),
+            BatcherRequestV1::IsAlive => BatcherRequest::IsAlive,
+            BatcherRequestV1::IsReady => BatcherRequest::IsReady,
+            BatcherRequestV1::StartHeight(input) => BatcherRequest::StartHeight(input),
+            BatcherRequestV1::AddSyncedBlock(input) => BatcherRequest::AddSyncedBlock(input),
+            BatcherRequestV1::GetProposalState => BatcherRequest::GetProposalState,
+            BatcherRequestV1::GetProposalMetadata(input) => {
+                BatcherRequest::GetProposalMetadata(input)
+            }
+            BatcherRequestV1::GetPendingState => BatcherRequest::GetPendingState,
+            BatcherRequestV1::GetProposalProgress(input) => {
+                BatcherRequest::GetProposalProgress(input)
+            }
+            BatcherRequestV1::ReproposeProposal(input) => BatcherRequest::ReproposeProposal(input),
+            BatcherRequestV1::GetCommittedBlock(input) => BatcherRequest::GetCommittedBlock(input),
+        }
+    }
+}
+
+/// The return value of a proposal generation in version 1.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BuildProposalReturnValueV1 {
+    pub environment_fingerprint: EnvironmentFingerprintV1,
+    pub block_info: ProposalBlockInfo,
+}
+
+impl From<&BuildProposalReturnValue> for BuildProposalReturnValueV1 {
+    fn from(return_value: &BuildProposalReturnValue) -> Self {
+        Self {
+            environment_fingerprint: return_value.environment_fingerprint.into(),
+            block_info: return_value.block_info,
+        }
+    }
+}
+
+/// The return value of a re-proposal in version 1.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReproposeProposalReturnValueV1 {
+    pub commitment: ProposalCommitment,
+    pub environment_fingerprint: EnvironmentFingerprintV1,
+    pub block_info: ProposalBlockInfo,
+}
+
+impl From<&ReproposeProposalReturnValue> for ReproposeProposalReturnValueV1 {
+    fn from(return_value: &ReproposeProposalReturnValue) -> Self {
+        Self {
+            commitment: return_value.commitment.clone(),
+            environment_fingerprint: return_value.environment_fingerprint.into(),
+            block_info: return_value.block_info,
+        }
+    }
+}
+
+/// The proposal validation error of version 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalValidationErrorV1 {
+    TxExcluded { tx_index: usize, tx_hash: TransactionHash, reason: TxExclusionReason },
+    BlockClosed { tx_index: usize, tx_hash: TransactionHash },
+    Incomplete { n_received_txs: usize },
+}
+
+impl From<ProposalValidationError> for ProposalValidationErrorV1 {
+    fn from(error: ProposalValidationError) -> Self {
+        match error {
+            ProposalValidationError::TxExcluded { tx_index, tx_hash, reason } => {
+                ProposalValidationErrorV1::TxExcluded { tx_index, tx_hash, reason }
+            }
+            ProposalValidationError::BlockClosed { tx_index, tx_hash } => {
+                ProposalValidationErrorV1::BlockClosed { tx_index, tx_hash }
+            }
+            ProposalValidationError::Incomplete { n_received_txs } => {
+                ProposalValidationErrorV1::Incomplete { n_received_txs }
+            }
+            // Version 1 has no such error, so the expected system transaction is reported as left
+            // out of the block.
+            ProposalValidationError::UnexpectedSystemTx { tx_index, expected_tx_hash } => {
+                ProposalValidationErrorV1::TxExcluded {
+                    tx_index,
+                    tx_hash: expected_tx_hash,
+                    reason: TxExclusionReason::ExecutionFailed(
+                        "The proposal doesn't start with the expected system transaction."
+                            .to_string(),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// The proposal status of version 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatusV1 {
+    Valid {
+        commitment: ProposalCommitment,
+    },
+    ContentMismatch {
+        n_proposed_txs: usize,
+        n_executed_txs: usize,
+        error: ProposalValidationErrorV1,
+    },
+    EnvironmentMismatch {
+        proposer_fingerprint: EnvironmentFingerprintV1,
+        validator_fingerprint: EnvironmentFingerprintV1,
+        n_proposed_txs: usize,
+        n_executed_txs: usize,
+        error: ProposalValidationErrorV1,
+    },
+}
+
+impl From<&ProposalStatus> for ProposalStatusV1 {
+    fn from(status: &ProposalStatus) -> Self {
+        match status.clone() {
+            ProposalStatus::Valid { commitment } => ProposalStatusV1::Valid { commitment },
+            ProposalStatus::ContentMismatch { n_proposed_txs, n_executed_txs, error } => {
+                ProposalStatusV1::ContentMismatch {
+                    n_proposed_txs,
+                    n_executed_txs,
+                    error: error.into(),
+                }
+            }
+            ProposalStatus::EnvironmentMismatch {
+                proposer_fingerprint,
+                validator_fingerprint,
+                n_proposed_txs,
+                n_executed_txs,
+                error,
+            } => ProposalStatusV1::EnvironmentMismatch {
+                proposer_fingerprint: proposer_fingerprint.into(),
+                validator_fingerprint: validator_fingerprint.into(),
+                n_proposed_txs,
+                n_executed_txs,
+                error: error.into(),
+            },
+        }
+    }
+}
+
+/// The block import status of version 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportBlockStatusV1 {
+    Imported { environment_fingerprint: EnvironmentFingerprintV1 },
+    ContentMismatch { n_proposed_txs: usize, n_executed_txs: usize },
+    CommitmentMismatch { expected: BlockCommitments, actual: BlockCommitments },
+}
+
+impl From<&ImportBlockStatus> for ImportBlockStatusV1 {
+    fn from(status: &ImportBlockStatus) -> Self {
+        match status.clone() {
+            ImportBlockStatus::Imported { environment_fingerprint } => {
+                ImportBlockStatusV1::Imported {
+                    environment_fingerprint: environment_fingerprint.into(),
+                }
+            }
+            ImportBlockStatus::ContentMismatch { n_proposed_txs, n_executed_txs } => {
+                ImportBlockStatusV1::ContentMismatch { n_proposed_txs, n_executed_txs }
+            }
+            ImportBlockStatus::CommitmentMismatch { expected, actual } => {
+                ImportBlockStatusV1::CommitmentMismatch { expected, actual }
+            }
+        }
+    }
+}
+
+/// The responses of version 1, in which a validation is answered with its status once done.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BatcherResponseV1 {
+    BuildProposal(BatcherResult<BuildProposalReturnValueV1>),
+    ValidateProposal(BatcherResult<ProposalStatusV1>),
+    GetProposalContent(BatcherResult<GetProposalContentReturnValue>),
+    ForceAbortAndResync(BatcherResult<ForceAbortAndResyncReturnValue>),
+    SkipHeight(BatcherResult<()>),
+    DecisionReached(BatcherResult<()>),
+    ImportBlock(BatcherResult<ImportBlockStatusV1>),
+    IsAlive(bool),
+    IsReady(bool),
+    StartHeight(BatcherResult<()>),
+    AddSyncedBlock(BatcherResult<()>),
+    GetProposalState(BatcherResult<ProposalState>),
+    GetProposalMetadata(BatcherResult<ProposalMetadata>),
+    GetPendingState(BatcherResult<Option<PendingState>>),
+    GetProposalProgress(BatcherResult<ProposalProgress>),
+    ReproposeProposal(BatcherResult<ReproposeProposalReturnValueV1>),
+    GetCommittedBlock(BatcherResult<Option<Box<CommittedBlock>>>),
+}
+
+// The validation of a request of version 1 is answered by the status of the validation once it is
+// done, see [`VersionedBatcherRequest::awaits_validation`].
+impl TryFrom<&BatcherResponse> for BatcherResponseV1 {
+    type Error = String;
+
+    fn try_from(response: &BatcherResponse) -> Result<Self, Self::Error> {
+        Ok(match response {
+            BatcherResponse::BuildProposal(result) => BatcherResponseV1::BuildProposal(
+                result.as_ref().map(BuildProposalReturnValueV1::from).map_err(Clone::clone),
+            ),
+            BatcherResponse::ValidateProposal(Err(err))
+            | BatcherResponse::GetValidationStatus(Err(err)) => {
+                BatcherResponseV1::ValidateProposal(Err(err.clone()))
+            }
+            BatcherResponse::GetValidationStatus(Ok(ValidationStatus::Done(status))) => {
+                BatcherResponseV1::ValidateProposal(Ok(status.into()))
+            }
+            BatcherResponse::GetProposalContent(result) => {
+                BatcherResponseV1::GetProposalContent(result.clone())
+            }
+            BatcherResponse::ForceAbortAndResync(result) => {
+                BatcherResponseV1::ForceAbortAndResync(result.clone())
+            }
+            BatcherResponse::SkipHeight(result) => BatcherResponseV1::SkipHeight(result.clone()),
+            BatcherResponse::DecisionReached(result) => {
+                BatcherResponseV1::DecisionReached(result.clone())
+            }
+            BatcherResponse::ImportBlock(result) => BatcherResponseV1::ImportBlock(
+                result.as_ref().map(ImportBlockStatusV1::from).map_err(Clone::clone),
+            ),
+            BatcherResponse::IsAlive(health) => BatcherResponseV1::IsAlive(*health),
+            BatcherResponse::IsReady(health) => BatcherResponseV1::IsReady(*health),
+            BatcherResponse::StartHeight(result) => BatcherResponseV1::StartHeight(result.clone()),
+            BatcherResponse::AddSyncedBlock(result) => {
+                BatcherResponseV1::AddSyncedBlock(result.clone())
+            }
+            BatcherResponse::GetProposalState(result) => {
+                BatcherResponseV1::GetProposalState(result.clone())
+            }
+            BatcherResponse::GetProposalMetadata(result) => {
+                BatcherResponseV1::GetProposalMetadata(result.clone())
+            }
+            BatcherResponse::GetPendingState(result) => {
+                BatcherResponseV1::GetPendingState(result.clone())
+            }
+            BatcherResponse::GetProposalProgress(result) => {
+                BatcherResponseV1::GetProposalProgress(result.clone())
+            }
+            BatcherResponse::ReproposeProposal(result) => BatcherResponseV1::ReproposeProposal(
+                result.as_ref().map(ReproposeProposalReturnValueV1::from).map_err(Clone::clone),
+            ),
+            BatcherResponse::GetCommittedBlock(result) => {
+                BatcherResponseV1::GetCommittedBlock(result.clone())
+            }
+            BatcherResponse::ValidateProposal(Ok(()))
+            | BatcherResponse::GetValidationStatus(Ok(ValidationStatus::InProgress)) => {
+                return Err(format!(
+                    "Response {response:?} does not exist in version 1, where validations are \
+                     answered once done."
+                ));
+            }
+        })
+    }
+}
+
+/// A batcher request, together with the API version it was encoded with.
+#[derive(Debug)]
+pub struct VersionedBatcherRequest {
+    pub version: u32,
+    pub request: BatcherRequest,
+}
+
+impl VersionedBatcherRequest {
+    /// Wraps a request to be encoded with the current API version.
+    pub fn new(request: BatcherRequest) -> Self {
+        Self { version: BATCHER_API_VERSION, request }
+    }
+
+    /// Whether the request is a validation of a version that is answered with the status of the
+    /// validation once it is done, rather than once it started. Such a request is answered by a
+    /// [`BatcherResponse::GetValidationStatus`] response.
+    pub fn awaits_validation(&self) -> bool {
+        self.version == BATCHER_API_VERSION_1
+            && matches!(self.request, BatcherRequest::ValidateProposal(_))
+    }
+}
+
+// Clients encode their requests with the current version only.
+impl Serialize for VersionedBatcherRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.version != BATCHER_API_VERSION {
+            return Err(ser::Error::custom(unsupported_version(self.version)));
+        }
+        serialize_versioned(serializer, self.version, &self.request)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedBatcherRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (version, request) = deserializer.deserialize_tuple(
+            2,
+            VersionedVisitor::<BatcherRequest, BatcherRequestV1>::new("a batcher request"),
+        )?;
+        Ok(Self { version, request })
+    }
+}
+
+// Labeled in the metrics and the request priorities by the variant of the wrapped request.
+impl From<&VersionedBatcherRequest> for &'static str {
+    fn from(versioned_request: &VersionedBatcherRequest) -> Self {
+        (&versioned_request.request).into()
+    }
+}
+
+impl HealthCheckRequest for VersionedBatcherRequest {
+    fn is_alive() -> Self {
+        Self::new(BatcherRequest::IsAlive)
+    }
+
+    fn is_ready() -> Self {
+        Self::new(BatcherRequest::IsReady)
+    }
+}
+
+/// A batcher response, encoded with the API version of the request it answers.
+#[derive(Debug)]
+pub struct VersionedBatcherResponse {
+    pub version: u32,
+    pub response: BatcherResponse,
+}
+
+impl Serialize for VersionedBatcherResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.version {
+            BATCHER_API_VERSION => serialize_versioned(serializer, self.version, &self.response),
+            BATCHER_API_VERSION_1 => serialize_versioned(
+                serializer,
+                self.version,
+                &BatcherResponseV1::try_from(&self.response).map_err(ser::Error::custom)?,
+            ),
+            version => Err(ser::Error::custom(unsupported_version(version))),
+        }
+    }
+}
+
+// Clients send requests with the current version, so only responses of the current version are
+// expected.
+impl<'de> Deserialize<'de> for VersionedBatcherResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (version, response) = deserializer.deserialize_tuple(
+            2,
+            VersionedVisitor::<BatcherResponse, BatcherResponse>::current_only(
+                "a batcher response",
+            ),
+        )?;
+        Ok(Self { version, response })
+    }
+}
+
+impl HealthCheckResponse for VersionedBatcherResponse {
+    fn health(&self) -> Option<bool> {
+        self.response.health()
+    }
+}
+
+fn serialize_versioned<S: Serializer, T: Serialize>(
+    serializer: S,
+    version: u32,
+    message: &T,
+) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&version)?;
+    tuple.serialize_element(message)?;
+    tuple.end()
+}
+
+// Decodes an API version followed by a message of that version: a message of the current version
+// as `T`, and, if supported, a message of version 1 as `V1`, converted to `T`.
+struct VersionedVisitor<T, V1> {
+    expected_message: &'static str,
+    supports_version_1: bool,
+    _message: PhantomData<(T, V1)>,
+}
+
+impl<T, V1> VersionedVisitor<T, V1> {
+    fn new(expected_message: &'static str) -> Self {
+        Self { expected_message, supports_version_1: true, _message: PhantomData }
+    }
+
+    fn current_only(expected_message: &'static str) -> Self {
+        Self { expected_message, supports_version_1: false, _message: PhantomData }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, V1: Deserialize<'de> + Into<T>> Visitor<'de>
+    for VersionedVisitor<T, V1>
+{
+    type Value = (u32, T);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "an API version followed by {}", self.expected_message)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let version: u32 =
+            seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let message = match version {
+            BATCHER_API_VERSION => seq.next_element::<T>()?,
+            BATCHER_API_VERSION_1 if self.supports_version_1 => {
+                seq.next_element::<V1>()?.map(Into::into)
+            }
+            _ => return Err(de::Error::custom(unsupported_version(version))),
+        };
+        let message = message.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok((version, message))
+    }
+}
+
+fn unsupported_version(version: u32) -> String {
+    format!("Unsupported batcher API version {version}.")
+}
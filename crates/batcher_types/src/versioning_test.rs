@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use bincode::{deserialize, serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_infra::payload_metrics::RequestVariant;
+
+use crate::batcher_types::{
+    DecisionReachedInput,
+    GetProposalContentInput,
+    ProposalStatus,
+    ProposalValidationError,
+    TxExclusionReason,
+    ValidateProposalInput,
+    ValidationStatus,
+};
+use crate::communication::{BatcherRequest, BatcherResponse};
+use crate::deadline::Deadline;
+use crate::errors::BatcherError;
+use crate::versioning::{
+    BatcherRequestV1,
+    BatcherResponseV1,
+    EnvironmentFingerprintV1,
+    ProposalStatusV1,
+    ProposalValidationErrorV1,
+    ValidateProposalInputV1,
+    VersionedBatcherRequest,
+    VersionedBatcherResponse,
+    BATCHER_API_VERSION,
+    BATCHER_API_VERSION_1,
+};
+
+#[test]
+fn current_version_request_round_trip() {
+    let request =
+        VersionedBatcherRequest::new(BatcherRequest::GetProposalContent(GetProposalContentInput {
+            proposal_id: 3,
+            page: 1,
+        }));
+
+    let decoded: VersionedBatcherRequest = deserialize(&serialize(&request).unwrap()).unwrap();
+
+    assert_eq!(decoded.version, BATCHER_API_VERSION);
+    assert_matches!(
+        decoded.request,
+        BatcherRequest::GetProposalContent(GetProposalContentInput { proposal_id: 3, page: 1 })
+    );
+}
+
+#[test]
+fn unknown_version_request_is_rejected() {
+    let encoded_request = serialize(&(
+        BATCHER_API_VERSION + 1,
+        BatcherRequest::DecisionReached(DecisionReachedInput { proposal_id: 0 }),
+    ))
+    .unwrap();
+
+    assert!(deserialize::<VersionedBatcherRequest>(&encoded_request).is_err());
+}
+
+#[test]
+fn request_is_not_encoded_with_unknown_version() {
+    let request = VersionedBatcherRequest {
+        version: BATCHER_API_VERSION + 1,
+        request: BatcherRequest::IsAlive,
+    };
+
+    assert!(serialize(&request).is_err());
+}
+
+#[test]
+fn current_version_response_round_trip() {
+    let response = VersionedBatcherResponse {
+        version: BATCHER_API_VERSION,
+        response: BatcherResponse::SkipHeight(Err(BatcherError::InternalError)),
+    };
+
+    let decoded: VersionedBatcherResponse = deserialize(&serialize(&response).unwrap()).unwrap();
+
+    assert_eq!(decoded.version, BATCHER_API_VERSION);
+    assert_matches!(
+        decoded.response,
+        BatcherResponse::SkipHeight(Err(BatcherError::InternalError))
+    );
+}
+
+#[test]
+fn unknown_version_response_is_rejected() {
+    let encoded_response =
+        serialize(&(BATCHER_API_VERSION + 1, BatcherResponse::IsReady(true))).unwrap();
+
+    assert!(deserialize::<VersionedBatcherResponse>(&encoded_response).is_err());
+}
+
+#[test]
+fn previous_version_request_is_decoded() {
+    let encoded_request = serialize(&(
+        BATCHER_API_VERSION_1,
+        BatcherRequestV1::ValidateProposal(ValidateProposalInputV1 {
+            proposal_id: 3,
+            deadline: Deadline::after(Duration::from_secs(1)),
+            height: BlockNumber(5),
+            round: 1,
+            txs: vec![],
+            environment_fingerprint: Some(EnvironmentFingerprintV1([1; 32])),
+            block_info: None,
+        }),
+    ))
+    .unwrap();
+
+    let decoded: VersionedBatcherRequest = deserialize(&encoded_request).unwrap();
+
+    assert_eq!(decoded.version, BATCHER_API_VERSION_1);
+    assert!(decoded.awaits_validation());
+    // The unsalted fingerprint of the proposer is dropped.
+    assert_matches!(
+        decoded.request,
+        BatcherRequest::ValidateProposal(ValidateProposalInput {
+            proposal_id: 3,
+            height: BlockNumber(5),
+            round: 1,
+            environment_fingerprint: None,
+            ..
+        })
+    );
+}
+
+#[test]
+fn previous_version_validation_is_answered_with_its_status() {
+    let response = VersionedBatcherResponse {
+        version: BATCHER_API_VERSION_1,
+        response: BatcherResponse::GetValidationStatus(Ok(ValidationStatus::Done(
+            ProposalStatus::ContentMismatch {
+                n_proposed_txs: 2,
+                n_executed_txs: 1,
+                error: ProposalValidationError::Incomplete { n_received_txs: 1 },
+            },
+        ))),
+    };
+
+    let (version, decoded): (u32, BatcherResponseV1) =
+        deserialize(&serialize(&response).unwrap()).unwrap();
+
+    assert_eq!(version, BATCHER_API_VERSION_1);
+    assert_matches!(
+        decoded,
+        BatcherResponseV1::ValidateProposal(Ok(ProposalStatusV1::ContentMismatch {
+            n_proposed_txs: 2,
+            n_executed_txs: 1,
+            error: ProposalValidationErrorV1::Incomplete { n_received_txs: 1 },
+        }))
+    );
+    // A validation that was only started can't be answered in version 1.
+    let started_validation = VersionedBatcherResponse {
+        version: BATCHER_API_VERSION_1,
+        response: BatcherResponse::ValidateProposal(Ok(())),
+    };
+    assert!(serialize(&started_validation).is_err());
+}
+
+#[test]
+fn unexpected_system_tx_is_reported_to_previous_version_as_excluded() {
+    let error = ProposalValidationError::UnexpectedSystemTx {
+        tx_index: 0,
+        expected_tx_hash: TransactionHash::default(),
+    };
+
+    assert_matches!(
+        ProposalValidationErrorV1::from(error),
+        ProposalValidationErrorV1::TxExcluded {
+            tx_index: 0,
+            reason: TxExclusionReason::ExecutionFailed(_),
+            ..
+        }
+    );
+}
+
+#[test]
+fn versioned_request_is_named_by_its_variant() {
+    let request = VersionedBatcherRequest::new(BatcherRequest::IsAlive);
+
+    assert_eq!(request.variant_name(), "IsAlive");
+}
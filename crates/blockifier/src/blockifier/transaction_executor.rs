@@ -3,9 +3,12 @@ use std::collections::{HashMap, HashSet};
 #[cfg(feature = "concurrency")]
 use std::panic::{self, catch_unwind, AssertUnwindSafe};
 #[cfg(feature = "concurrency")]
+use std::sync::atomic::Ordering;
+#[cfg(feature = "concurrency")]
 use std::sync::Arc;
 #[cfg(feature = "concurrency")]
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
@@ -39,6 +42,11 @@ pub enum TransactionExecutorError {
     StateError(#[from] StateError),
     #[error(transparent)]
     TransactionExecutionError(#[from] TransactionExecutionError),
+    #[error(
+        "Transaction execution took {execution_time:?}, more than the allowed \
+         {max_execution_time:?}."
+    )]
+    ExecutionTimeout { execution_time: Duration, max_execution_time: Duration },
 }
 
 pub type TransactionExecutorResult<T> = Result<T, TransactionExecutorError>;
@@ -57,6 +65,10 @@ pub struct TransactionExecutor<S: StateReader> {
     // committing the chunk. The block state is wrapped with an Option<_> to allow setting it to
     // `None` while it is moved to the worker executor.
     pub block_state: Option<CachedState<S>>,
+
+    // The number of transaction executions repeated in concurrency mode, for conflicts with
+    // earlier transactions of their chunk.
+    pub n_reexecutions: usize,
 }
 
 impl<S: StateReader> TransactionExecutor<S> {
@@ -74,6 +86,7 @@ impl<S: StateReader> TransactionExecutor<S> {
             bouncer: Bouncer::new(bouncer_config),
             config,
             block_state: Some(block_state),
+            n_reexecutions: 0,
         };
         log::debug!("Initialized Transaction Executor.");
 
@@ -87,6 +100,26 @@ impl<S: StateReader> TransactionExecutor<S> {
         &mut self,
         tx: &Transaction,
     ) -> TransactionExecutorResult<TransactionExecutionInfo> {
+        self.execute_inner(tx, None)
+    }
+
+    /// Executes the given transaction like `execute`, but rejects it, leaving the state
+    /// unchanged, if its execution took longer than `max_execution_time`. The execution itself is
+    /// not interrupted.
+    pub fn execute_with_time_limit(
+        &mut self,
+        tx: &Transaction,
+        max_execution_time: Duration,
+    ) -> TransactionExecutorResult<TransactionExecutionInfo> {
+        self.execute_inner(tx, Some(max_execution_time))
+    }
+
+    fn execute_inner(
+        &mut self,
+        tx: &Transaction,
+        max_execution_time: Option<Duration>,
+    ) -> TransactionExecutorResult<TransactionExecutionInfo> {
+        let execution_start = Instant::now();
         let mut transactional_state = TransactionalState::create_transactional(
             self.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR),
         );
@@ -95,6 +128,16 @@ impl<S: StateReader> TransactionExecutor<S> {
             ExecutionFlags { charge_fee: true, validate: true, concurrency_mode: false };
         let tx_execution_result =
             tx.execute_raw(&mut transactional_state, &self.block_context, execution_flags);
+        let execution_time = execution_start.elapsed();
+        if let Some(max_execution_time) = max_execution_time {
+            if execution_time > max_execution_time {
+                transactional_state.abort();
+                return Err(TransactionExecutorError::ExecutionTimeout {
+                    execution_time,
+                    max_execution_time,
+                });
+            }
+        }
         match tx_execution_result {
             Ok(tx_execution_info) => {
                 let tx_state_changes_keys =
@@ -263,6 +306,7 @@ impl<S: StateReader + Send + Sync> TransactionExecutor<S> {
         });
 
         let n_committed_txs = worker_executor.scheduler.get_n_committed_txs();
+        self.n_reexecutions += worker_executor.n_reexecutions.load(Ordering::Relaxed);
         let mut tx_execution_results = Vec::new();
         let mut visited_pcs: HashMap<ClassHash, HashSet<usize>> = HashMap::new();
         for execution_output in worker_executor.execution_outputs.iter() {
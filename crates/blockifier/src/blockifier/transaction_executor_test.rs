@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
@@ -235,6 +237,28 @@ fn test_l1_handler(block_context: BlockContext) {
     tx_executor_test_body(state, block_context, tx, expected_bouncer_weights);
 }
 
+#[rstest]
+fn test_execution_timeout(block_context: BlockContext) {
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo1);
+    let state = test_state(&block_context.chain_info, BALANCE, &[(test_contract, 1)]);
+    let tx = Transaction::L1HandlerTransaction(L1HandlerTransaction::create_for_testing(
+        Fee(1908000000000000),
+        test_contract.get_instance_address(0),
+    ));
+    let mut tx_executor =
+        TransactionExecutor::new(state, block_context, TransactionExecutorConfig::default());
+
+    let result = tx_executor.execute_with_time_limit(&tx, Duration::ZERO);
+
+    assert_matches!(
+        result,
+        Err(TransactionExecutorError::ExecutionTimeout { max_execution_time, .. })
+        if max_execution_time == Duration::ZERO
+    );
+    // The rejected transaction is not counted in the block.
+    assert_eq!(tx_executor.bouncer.get_accumulated_weights(), &BouncerWeights::default());
+}
+
 #[rstest]
 #[case::happy_flow(BouncerWeights::default(), 10)]
 #[should_panic(expected = "BlockFull: Transaction cannot be added to the current block, block \
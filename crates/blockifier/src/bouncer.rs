@@ -1,7 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::core::ClassHash;
 
@@ -40,11 +42,17 @@ macro_rules! impl_checked_sub {
 
 pub type HashMapWrapper = HashMap<BuiltinName, usize>;
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct BouncerConfig {
     pub block_max_capacity: BouncerWeights,
 }
 
+impl SerializeConfig for BouncerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        append_sub_config_name(self.block_max_capacity.dump(), "block_max_capacity")
+    }
+}
+
 impl BouncerConfig {
     pub fn max() -> Self {
         Self { block_max_capacity: BouncerWeights::max() }
@@ -107,6 +115,47 @@ impl BouncerWeights {
     }
 }
 
+impl SerializeConfig for BouncerWeights {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let members = BTreeMap::from_iter([
+            ser_param(
+                "gas",
+                &self.gas,
+                "The maximal L1 gas of a block.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "message_segment_length",
+                &self.message_segment_length,
+                "The maximal length of the L2-to-L1 message segment of a block.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "n_events",
+                &self.n_events,
+                "The maximal number of events in a block.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "n_steps",
+                &self.n_steps,
+                "The maximal number of Cairo steps in a block.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "state_diff_size",
+                &self.state_diff_size,
+                "The maximal size of the state diff of a block.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![members, append_sub_config_name(self.builtin_count.dump(), "builtin_count")]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -177,6 +226,33 @@ impl BuiltinCount {
     }
 }
 
+impl SerializeConfig for BuiltinCount {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        [
+            ("add_mod", self.add_mod),
+            ("bitwise", self.bitwise),
+            ("ecdsa", self.ecdsa),
+            ("ec_op", self.ec_op),
+            ("keccak", self.keccak),
+            ("mul_mod", self.mul_mod),
+            ("pedersen", self.pedersen),
+            ("poseidon", self.poseidon),
+            ("range_check", self.range_check),
+            ("range_check96", self.range_check96),
+        ]
+        .into_iter()
+        .map(|(builtin_name, max_count)| {
+            ser_param(
+                builtin_name,
+                &max_count,
+                &format!("The maximal number of {builtin_name} builtin instances in a block."),
+                ParamPrivacyInput::Public,
+            )
+        })
+        .collect()
+    }
+}
+
 impl From<HashMapWrapper> for BuiltinCount {
     fn from(mut data: HashMapWrapper) -> Self {
         // TODO(yael 24/3/24): replace the unwrap_or_default with expect, once the
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
@@ -48,6 +49,9 @@ pub struct WorkerExecutor<'a, S: StateReader> {
     pub execution_outputs: Box<[Mutex<Option<ExecutionTaskOutput>>]>,
     pub block_context: &'a BlockContext,
     pub bouncer: Mutex<&'a mut Bouncer>,
+    // The number of executions of transactions that were already executed, i.e., the executions
+    // discarded for conflicts with earlier transactions of the chunk.
+    pub n_reexecutions: AtomicUsize,
 }
 impl<'a, S: StateReader> WorkerExecutor<'a, S> {
     pub fn new(
@@ -60,7 +64,15 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
         let execution_outputs =
             std::iter::repeat_with(|| Mutex::new(None)).take(chunk.len()).collect();
 
-        WorkerExecutor { scheduler, state, chunk, execution_outputs, block_context, bouncer }
+        WorkerExecutor {
+            scheduler,
+            state,
+            chunk,
+            execution_outputs,
+            block_context,
+            bouncer,
+            n_reexecutions: AtomicUsize::new(0),
+        }
     }
 
     // TODO(barak, 01/08/2024): Remove the `new` method or move it to test utils.
@@ -83,6 +95,7 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
             execution_outputs,
             block_context,
             bouncer,
+            n_reexecutions: AtomicUsize::new(0),
         }
     }
 
@@ -153,6 +166,9 @@ impl<'a, S: StateReader> WorkerExecutor<'a, S> {
             Err(_) => (StateMaps::default(), HashMap::default(), HashMap::default()),
         };
         let mut execution_output = lock_mutex_in_array(&self.execution_outputs, tx_index);
+        if execution_output.is_some() {
+            self.n_reexecutions.fetch_add(1, Ordering::Relaxed);
+        }
         *execution_output = Some(ExecutionTaskOutput {
             reads: tx_reads_writes.initial_reads,
             writes,
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Mutex;
 
 use rstest::rstest;
@@ -184,6 +185,8 @@ pub fn test_commit_tx() {
             expected_sequencer_balance_low,
         );
     }
+    // tx1 and tx2 were re-executed on commit.
+    assert_eq!(executor.n_reexecutions.load(Ordering::Relaxed), 2);
 }
 
 #[test]
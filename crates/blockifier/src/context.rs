@@ -3,10 +3,11 @@ use std::collections::BTreeMap;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockNumber, BlockTimestamp};
 use starknet_api::core::{ChainId, ContractAddress};
 use starknet_api::transaction::ValidResourceBounds;
 
-use crate::blockifier::block::BlockInfo;
+use crate::blockifier::block::{BlockInfo, GasPrices};
 use crate::bouncer::BouncerConfig;
 use crate::transaction::objects::{
     FeeType,
@@ -84,6 +85,193 @@ impl BlockContext {
     }
 }
 
+/// Marks a required field of a [`BlockContextBuilder`] that was not set yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unset;
+
+/// Assembles a [`BlockContext`], checking at compile time that all the required fields are set:
+/// the chain info, block number, timestamp, gas prices and sequencer address. The versioned
+/// constants default to the latest ones, the bouncer config to an unlimited block, and the data
+/// availability mode to calldata.
+///
+/// ```compile_fail
+/// # use blockifier::context::{BlockContextBuilder, ChainInfo};
+/// # use starknet_api::block::BlockNumber;
+/// // Fails to compile: the timestamp, gas prices and sequencer address are not set.
+/// let block_context = BlockContextBuilder::new()
+///     .chain_info(ChainInfo::default())
+///     .block_number(BlockNumber(1))
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlockContextBuilder<Chain, Number, Timestamp, Prices, Sequencer> {
+    chain_info: Chain,
+    block_number: Number,
+    block_timestamp: Timestamp,
+    gas_prices: Prices,
+    sequencer_address: Sequencer,
+    use_kzg_da: bool,
+    versioned_constants: Option<VersionedConstants>,
+    bouncer_config: BouncerConfig,
+}
+
+impl BlockContextBuilder<Unset, Unset, Unset, Unset, Unset> {
+    pub fn new() -> Self {
+        Self {
+            chain_info: Unset,
+            block_number: Unset,
+            block_timestamp: Unset,
+            gas_prices: Unset,
+            sequencer_address: Unset,
+            use_kzg_da: false,
+            versioned_constants: None,
+            bouncer_config: BouncerConfig::max(),
+        }
+    }
+}
+
+impl Default for BlockContextBuilder<Unset, Unset, Unset, Unset, Unset> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Number, Timestamp, Prices, Sequencer>
+    BlockContextBuilder<Unset, Number, Timestamp, Prices, Sequencer>
+{
+    pub fn chain_info(
+        self,
+        chain_info: ChainInfo,
+    ) -> BlockContextBuilder<ChainInfo, Number, Timestamp, Prices, Sequencer> {
+        BlockContextBuilder {
+            chain_info,
+            block_number: self.block_number,
+            block_timestamp: self.block_timestamp,
+            gas_prices: self.gas_prices,
+            sequencer_address: self.sequencer_address,
+            use_kzg_da: self.use_kzg_da,
+            versioned_constants: self.versioned_constants,
+            bouncer_config: self.bouncer_config,
+        }
+    }
+}
+
+impl<Chain, Timestamp, Prices, Sequencer>
+    BlockContextBuilder<Chain, Unset, Timestamp, Prices, Sequencer>
+{
+    pub fn block_number(
+        self,
+        block_number: BlockNumber,
+    ) -> BlockContextBuilder<Chain, BlockNumber, Timestamp, Prices, Sequencer> {
+        BlockContextBuilder {
+            chain_info: self.chain_info,
+            block_number,
+            block_timestamp: self.block_timestamp,
+            gas_prices: self.gas_prices,
+            sequencer_address: self.sequencer_address,
+            use_kzg_da: self.use_kzg_da,
+            versioned_constants: self.versioned_constants,
+            bouncer_config: self.bouncer_config,
+        }
+    }
+}
+
+impl<Chain, Number, Prices, Sequencer>
+    BlockContextBuilder<Chain, Number, Unset, Prices, Sequencer>
+{
+    pub fn block_timestamp(
+        self,
+        block_timestamp: BlockTimestamp,
+    ) -> BlockContextBuilder<Chain, Number, BlockTimestamp, Prices, Sequencer> {
+        BlockContextBuilder {
+            chain_info: self.chain_info,
+            block_number: self.block_number,
+            block_timestamp,
+            gas_prices: self.gas_prices,
+            sequencer_address: self.sequencer_address,
+            use_kzg_da: self.use_kzg_da,
+            versioned_constants: self.versioned_constants,
+            bouncer_config: self.bouncer_config,
+        }
+    }
+}
+
+impl<Chain, Number, Timestamp, Sequencer>
+    BlockContextBuilder<Chain, Number, Timestamp, Unset, Sequencer>
+{
+    pub fn gas_prices(
+        self,
+        gas_prices: GasPrices,
+    ) -> BlockContextBuilder<Chain, Number, Timestamp, GasPrices, Sequencer> {
+        BlockContextBuilder {
+            chain_info: self.chain_info,
+            block_number: self.block_number,
+            block_timestamp: self.block_timestamp,
+            gas_prices,
+            sequencer_address: self.sequencer_address,
+            use_kzg_da: self.use_kzg_da,
+            versioned_constants: self.versioned_constants,
+            bouncer_config: self.bouncer_config,
+        }
+    }
+}
+
+impl<Chain, Number, Timestamp, Prices>
+    BlockContextBuilder<Chain, Number, Timestamp, Prices, Unset>
+{
+    pub fn sequencer_address(
+        self,
+        sequencer_address: ContractAddress,
+    ) -> BlockContextBuilder<Chain, Number, Timestamp, Prices, ContractAddress> {
+        BlockContextBuilder {
+            chain_info: self.chain_info,
+            block_number: self.block_number,
+            block_timestamp: self.block_timestamp,
+            gas_prices: self.gas_prices,
+            sequencer_address,
+            use_kzg_da: self.use_kzg_da,
+            versioned_constants: self.versioned_constants,
+            bouncer_config: self.bouncer_config,
+        }
+    }
+}
+
+impl<Chain, Number, Timestamp, Prices, Sequencer>
+    BlockContextBuilder<Chain, Number, Timestamp, Prices, Sequencer>
+{
+    /// Whether the state diff of the block is published in blobs (KZG) rather than calldata.
+    pub fn use_kzg_da(mut self, use_kzg_da: bool) -> Self {
+        self.use_kzg_da = use_kzg_da;
+        self
+    }
+
+    pub fn versioned_constants(mut self, versioned_constants: VersionedConstants) -> Self {
+        self.versioned_constants = Some(versioned_constants);
+        self
+    }
+
+    pub fn bouncer_config(mut self, bouncer_config: BouncerConfig) -> Self {
+        self.bouncer_config = bouncer_config;
+        self
+    }
+}
+
+impl BlockContextBuilder<ChainInfo, BlockNumber, BlockTimestamp, GasPrices, ContractAddress> {
+    pub fn build(self) -> BlockContext {
+        let block_info = BlockInfo {
+            block_number: self.block_number,
+            block_timestamp: self.block_timestamp,
+            sequencer_address: self.sequencer_address,
+            gas_prices: self.gas_prices,
+            use_kzg_da: self.use_kzg_da,
+        };
+        let versioned_constants = self
+            .versioned_constants
+            .unwrap_or_else(|| VersionedConstants::latest_constants().clone());
+        BlockContext::new(block_info, self.chain_info, versioned_constants, self.bouncer_config)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ChainInfo {
     pub chain_id: ChainId,
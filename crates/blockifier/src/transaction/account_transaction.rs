@@ -95,6 +95,11 @@ impl TryFrom<starknet_api::executable_transaction::Transaction> for AccountTrans
             starknet_api::executable_transaction::Transaction::Invoke(invoke_tx) => {
                 Ok(Self::Invoke(InvokeTransaction { tx: invoke_tx, only_query: false }))
             }
+            starknet_api::executable_transaction::Transaction::L1Handler(l1_handler_tx) => {
+                Err(TransactionExecutionError::NotAccountTransaction {
+                    tx_hash: l1_handler_tx.tx_hash,
+                })
+            }
         }
     }
 }
@@ -642,7 +647,11 @@ impl AccountTransaction {
     /// Returns 0 on non-declare transactions; for declare transactions, returns the class code
     /// size.
     pub(crate) fn declare_code_size(&self) -> usize {
-        if let Self::Declare(tx) = self { tx.class_info.code_size() } else { 0 }
+        if let Self::Declare(tx) = self {
+            tx.class_info.code_size()
+        } else {
+            0
+        }
     }
 
     fn is_non_revertible(&self, tx_info: &TransactionInfo) -> bool {
@@ -12,7 +12,7 @@ use crate::fee::actual_cost::TransactionReceipt;
 use crate::state::cached_state::TransactionalState;
 use crate::state::state_api::UpdatableState;
 use crate::transaction::account_transaction::AccountTransaction;
-use crate::transaction::errors::TransactionFeeError;
+use crate::transaction::errors::{TransactionExecutionError, TransactionFeeError};
 use crate::transaction::objects::{
     TransactionExecutionInfo,
     TransactionExecutionResult,
@@ -99,6 +99,25 @@ impl Transaction {
     }
 }
 
+impl TryFrom<starknet_api::executable_transaction::Transaction> for Transaction {
+    type Error = TransactionExecutionError;
+
+    fn try_from(
+        executable_transaction: starknet_api::executable_transaction::Transaction,
+    ) -> TransactionExecutionResult<Self> {
+        match executable_transaction {
+            starknet_api::executable_transaction::Transaction::L1Handler(l1_handler_tx) => {
+                Ok(Self::L1HandlerTransaction(L1HandlerTransaction {
+                    tx: l1_handler_tx.tx,
+                    tx_hash: l1_handler_tx.tx_hash,
+                    paid_fee_on_l1: l1_handler_tx.paid_fee_on_l1,
+                }))
+            }
+            account_tx => Ok(Self::AccountTransaction(account_tx.try_into()?)),
+        }
+    }
+}
+
 impl TransactionInfoCreator for Transaction {
     fn create_tx_info(&self) -> TransactionInfo {
         match self {
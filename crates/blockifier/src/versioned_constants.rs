@@ -62,6 +62,19 @@ macro_rules! define_versioned_constants {
                 }
             }
         }
+
+        impl StarknetVersion {
+            /// The JSON the versioned constants of this version are loaded from.
+            pub fn versioned_constants_json(&self) -> &'static str {
+                match self {
+                    $(
+                        StarknetVersion::$variant => {
+                            paste! { [<VERSIONED_CONSTANTS_ $variant:upper _JSON>] }
+                        }
+                    )*
+                }
+            }
+        }
     };
 }
 
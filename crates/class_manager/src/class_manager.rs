@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
+use papyrus_config::{ParamPath, SerializedParam};
+use papyrus_storage::class::{ClassStorageReader, ClassStorageWriter};
+use papyrus_storage::compiled_class::{CasmStorageReader, CasmStorageWriter};
+use papyrus_storage::db::DbConfig;
+use papyrus_storage::{StorageConfig, StorageError, StorageReader, StorageWriter};
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ClassHash;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_class_manager_types::class_manager_types::{Class, ClassManagerResult};
+use starknet_class_manager_types::errors::ClassManagerError;
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "class_manager_test.rs"]
+pub mod class_manager_test;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ClassManagerConfig {
+    // Separate from the storage of the batcher, which holds the classes of synced blocks only.
+    #[validate]
+    pub storage: StorageConfig,
+}
+
+impl Default for ClassManagerConfig {
+    fn default() -> Self {
+        Self {
+            storage: StorageConfig {
+                db_config: DbConfig { path_prefix: "./class_manager".into(), ..Default::default() },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl SerializeConfig for ClassManagerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        append_sub_config_name(self.storage.dump(), "storage")
+    }
+}
+
+/// Stores the classes declared through the gateway, so that the block builder can execute the
+/// transactions that follow their declaration, and so that state sync can serve them.
+pub struct ClassManager {
+    storage_reader: StorageReader,
+    storage_writer: StorageWriter,
+}
+
+impl ClassManager {
+    pub fn new(config: ClassManagerConfig) -> Self {
+        let (storage_reader, storage_writer) = papyrus_storage::open_storage(config.storage)
+            .expect("Failed to open the class manager's storage.");
+        Self { storage_reader, storage_writer }
+    }
+
+    pub fn add_class(&mut self, class_hash: ClassHash, class: Class) -> ClassManagerResult<()> {
+        // Classes are declared repeatedly, e.g., by declare transactions that are rejected later.
+        if self.get_executable(class_hash)?.is_some() {
+            return Ok(());
+        }
+
+        let txn = self.storage_writer.begin_rw_txn().map_err(storage_error)?;
+        // The storage indexes the classes by the block they were declared in; the class manager
+        // stores each class as a block of its own, as the block it is declared in is not known
+        // yet.
+        let class_marker = txn.get_class_marker().map_err(storage_error)?;
+        txn.append_classes(class_marker, &[(class_hash, &class.sierra)], &[])
+            .and_then(|txn| txn.append_casm(&class_hash, &class.casm))
+            .and_then(|txn| txn.commit())
+            .map_err(storage_error)
+    }
+
+    pub fn get_sierra(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerResult<Option<SierraContractClass>> {
+        self.storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_class(&class_hash))
+            .map_err(storage_error)
+    }
+
+    pub fn get_executable(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerResult<Option<CasmContractClass>> {
+        self.storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_casm(&class_hash))
+            .map_err(storage_error)
+    }
+}
+
+fn storage_error(err: StorageError) -> ClassManagerError {
+    ClassManagerError::StorageError(err.to_string())
+}
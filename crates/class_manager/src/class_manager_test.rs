@@ -0,0 +1,49 @@
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use papyrus_storage::test_utils::get_test_config;
+use starknet_api::core::ClassHash;
+use starknet_api::felt;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_class_manager_types::class_manager_types::Class;
+
+use crate::class_manager::{ClassManager, ClassManagerConfig};
+
+fn class(sierra_program_length: usize) -> Class {
+    Class {
+        sierra: SierraContractClass {
+            sierra_program: vec![felt!(1_u8); sierra_program_length],
+            ..Default::default()
+        },
+        casm: CasmContractClass {
+            compiler_version: format!("{sierra_program_length}"),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn added_classes_are_read_by_their_class_hash() {
+    let (storage, _temp_dir) = get_test_config(None);
+    let mut class_manager = ClassManager::new(ClassManagerConfig { storage });
+    let (class_hash, other_class_hash) = (ClassHash(felt!(1_u8)), ClassHash(felt!(2_u8)));
+
+    class_manager.add_class(class_hash, class(1)).unwrap();
+    class_manager.add_class(other_class_hash, class(2)).unwrap();
+
+    assert_eq!(class_manager.get_sierra(class_hash), Ok(Some(class(1).sierra)));
+    assert_eq!(class_manager.get_executable(class_hash), Ok(Some(class(1).casm)));
+    assert_eq!(class_manager.get_executable(other_class_hash), Ok(Some(class(2).casm)));
+    assert_eq!(class_manager.get_sierra(ClassHash(felt!(3_u8))), Ok(None));
+    assert_eq!(class_manager.get_executable(ClassHash(felt!(3_u8))), Ok(None));
+}
+
+#[test]
+fn adding_a_stored_class_is_a_noop() {
+    let (storage, _temp_dir) = get_test_config(None);
+    let mut class_manager = ClassManager::new(ClassManagerConfig { storage });
+    let class_hash = ClassHash(felt!(1_u8));
+
+    class_manager.add_class(class_hash, class(1)).unwrap();
+    class_manager.add_class(class_hash, class(2)).unwrap();
+
+    assert_eq!(class_manager.get_executable(class_hash), Ok(Some(class(1).casm)));
+}
@@ -0,0 +1,62 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use starknet_class_manager_types::communication::{
+    ClassManagerRequest,
+    ClassManagerRequestAndResponseSender,
+    ClassManagerResponse,
+};
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
+use tokio::sync::mpsc::Receiver;
+
+use crate::class_manager::ClassManager;
+
+pub type LocalClassManagerServer =
+    LocalComponentServer<ClassManager, ClassManagerRequest, ClassManagerResponse>;
+pub type RemoteClassManagerServer =
+    RemoteComponentServer<ClassManager, ClassManagerRequest, ClassManagerResponse>;
+
+pub fn create_local_class_manager_server(
+    class_manager: ClassManager,
+    rx_class_manager: Receiver<ClassManagerRequestAndResponseSender>,
+) -> LocalClassManagerServer {
+    LocalComponentServer::new(class_manager, rx_class_manager)
+}
+
+pub fn create_remote_class_manager_server(
+    class_manager: ClassManager,
+    ip_address: IpAddr,
+    port: u16,
+    serialization_format: SerializationFormat,
+) -> RemoteClassManagerServer {
+    RemoteComponentServer::new(class_manager, ip_address, port)
+        .with_serialization_format(serialization_format)
+}
+
+#[async_trait]
+impl ComponentRequestHandler<ClassManagerRequest, ClassManagerResponse> for ClassManager {
+    async fn handle_request(&mut self, request: ClassManagerRequest) -> ClassManagerResponse {
+        match request {
+            ClassManagerRequest::AddClass(class_hash, class) => {
+                ClassManagerResponse::AddClass(self.add_class(class_hash, class))
+            }
+            ClassManagerRequest::GetSierra(class_hash) => {
+                ClassManagerResponse::GetSierra(self.get_sierra(class_hash))
+            }
+            ClassManagerRequest::GetExecutable(class_hash) => {
+                ClassManagerResponse::GetExecutable(self.get_executable(class_hash))
+            }
+            ClassManagerRequest::IsAlive => ClassManagerResponse::IsAlive(self.is_alive().await),
+            ClassManagerRequest::IsReady => ClassManagerResponse::IsReady(self.is_ready().await),
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for ClassManager {}
+
+#[async_trait]
+impl ComponentHealth for ClassManager {}
@@ -0,0 +1,2 @@
+pub mod class_manager;
+pub mod communication;
@@ -0,0 +1,14 @@
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use serde::{Deserialize, Serialize};
+use starknet_api::state::ContractClass as SierraContractClass;
+
+use crate::errors::ClassManagerError;
+
+pub type ClassManagerResult<T> = Result<T, ClassManagerError>;
+
+/// A declared class: its Sierra class, and the Cairo assembly it was compiled to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Class {
+    pub sierra: SierraContractClass,
+    pub casm: CasmContractClass,
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use mockall::predicate::*;
+use mockall::*;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ClassHash;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use starknet_mempool_infra::component_error::{Component, ComponentError};
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use crate::class_manager_types::{Class, ClassManagerResult};
+use crate::errors::ClassManagerError;
+
+pub type LocalClassManagerClientImpl =
+    LocalComponentClient<ClassManagerRequest, ClassManagerResponse>;
+pub type RemoteClassManagerClientImpl =
+    RemoteComponentClient<ClassManagerRequest, ClassManagerResponse>;
+pub type ClassManagerClientResult<T> = Result<T, ClassManagerClientError>;
+pub type ClassManagerRequestAndResponseSender =
+    ComponentRequestAndResponseSender<ClassManagerRequest, ClassManagerResponse>;
+pub type SharedClassManagerClient = Arc<dyn ClassManagerClient>;
+
+/// Serves as the class manager's shared interface. Requires `Send + Sync` to allow transferring
+/// and sharing resources (inputs, futures) across threads.
+#[automock]
+#[async_trait]
+pub trait ClassManagerClient: Send + Sync {
+    /// Stores a declared class by its class hash. Called by the gateway once a declare
+    /// transaction is validated, before it is added to the mempool. Storing a class again is a
+    /// no-op.
+    async fn add_class(&self, class_hash: ClassHash, class: Class) -> ClassManagerClientResult<()>;
+    /// Returns the Sierra class of the given class hash, if it was stored.
+    async fn get_sierra(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<SierraContractClass>>;
+    /// Returns the Cairo assembly of the given class hash, if it was stored. Called by the state
+    /// reader of the block builder, to execute transactions against classes declared in blocks
+    /// it built.
+    async fn get_executable(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<CasmContractClass>>;
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
+pub enum ClassManagerRequest {
+    AddClass(ClassHash, Class),
+    GetSierra(ClassHash),
+    GetExecutable(ClassHash),
+    IsAlive,
+    IsReady,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ClassManagerResponse {
+    AddClass(ClassManagerResult<()>),
+    GetSierra(ClassManagerResult<Option<SierraContractClass>>),
+    GetExecutable(ClassManagerResult<Option<CasmContractClass>>),
+    IsAlive(bool),
+    IsReady(bool),
+}
+
+impl HealthCheckRequest for ClassManagerRequest {
+    fn is_alive() -> Self {
+        ClassManagerRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        ClassManagerRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for ClassManagerResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            ClassManagerResponse::IsAlive(health) | ClassManagerResponse::IsReady(health) => {
+                Some(*health)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum ClassManagerClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    ClassManagerError(#[from] ClassManagerError),
+}
+
+impl ComponentError for ClassManagerClientError {
+    fn component(&self) -> Component {
+        Component::ClassManager
+    }
+
+    // Failures of the class storage are not expected to resolve by themselves.
+    fn is_retriable(&self) -> bool {
+        match self {
+            ClassManagerClientError::ClientError(err) => err.is_retriable(),
+            ClassManagerClientError::ClassManagerError(_) => false,
+        }
+    }
+}
+
+impl CodedError for ClassManagerClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ClassManagerClientError::ClientError(err) => err.error_code(),
+            ClassManagerClientError::ClassManagerError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            ClassManagerClientError::ClientError(err) => err.severity(),
+            ClassManagerClientError::ClassManagerError(err) => err.severity(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClassManagerClient for LocalClassManagerClientImpl {
+    async fn add_class(&self, class_hash: ClassHash, class: Class) -> ClassManagerClientResult<()> {
+        let request = ClassManagerRequest::AddClass(class_hash, class);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            AddClass,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+
+    async fn get_sierra(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<SierraContractClass>> {
+        let request = ClassManagerRequest::GetSierra(class_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            GetSierra,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+
+    async fn get_executable(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<CasmContractClass>> {
+        let request = ClassManagerRequest::GetExecutable(class_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            GetExecutable,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+}
+
+#[async_trait]
+impl ClassManagerClient for RemoteClassManagerClientImpl {
+    async fn add_class(&self, class_hash: ClassHash, class: Class) -> ClassManagerClientResult<()> {
+        let request = ClassManagerRequest::AddClass(class_hash, class);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            AddClass,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+
+    async fn get_sierra(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<SierraContractClass>> {
+        let request = ClassManagerRequest::GetSierra(class_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            GetSierra,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+
+    async fn get_executable(
+        &self,
+        class_hash: ClassHash,
+    ) -> ClassManagerClientResult<Option<CasmContractClass>> {
+        let request = ClassManagerRequest::GetExecutable(class_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            ClassManagerResponse,
+            GetExecutable,
+            ClassManagerClientError,
+            ClassManagerError
+        )
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClassManagerError {
+    #[error("Failed to access the class storage: {0}")]
+    StorageError(String),
+}
+
+impl CodedError for ClassManagerError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ClassManagerError::StorageError(_) => codes::CLASS_MANAGER_STORAGE_ERROR,
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            ClassManagerError::StorageError(_) => Severity::Error,
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod class_manager_types;
+pub mod communication;
+pub mod errors;
+pub mod transaction_converter;
@@ -0,0 +1,142 @@
+use starknet_api::contract_class::ClassInfo;
+use starknet_api::core::{ChainId, ClassHash};
+use starknet_api::executable_transaction::{
+    DeclareTransaction as ExecutableDeclareTransaction,
+    DeployAccountTransaction as ExecutableDeployAccountTransaction,
+    InvokeTransaction as ExecutableInvokeTransaction,
+    L1HandlerTransaction as ExecutableL1HandlerTransaction,
+    Transaction as ExecutableTransaction,
+};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{Fee, Transaction};
+use starknet_api::StarknetApiError;
+use thiserror::Error;
+
+use crate::communication::{ClassManagerClientError, SharedClassManagerClient};
+
+#[cfg(test)]
+#[path = "transaction_converter_test.rs"]
+mod transaction_converter_test;
+
+// TODO(Matan): Take the fee paid on L1 from the proposal once it is streamed with the L1 handler
+// transactions.
+const PROPOSED_L1_HANDLER_PAID_FEE: Fee = Fee(1);
+
+#[derive(Debug, Error)]
+pub enum TransactionConverterError {
+    #[error(transparent)]
+    ClassManagerClientError(#[from] ClassManagerClientError),
+    #[error("The class of hash {class_hash} is not stored.")]
+    ClassNotFound { class_hash: ClassHash },
+    #[error("Deploy transactions are not included in blocks anymore.")]
+    DeployTransaction,
+    #[error("A declare transaction is missing the class info of its compiled class.")]
+    MissingClassInfo,
+    #[error(transparent)]
+    StarknetApiError(#[from] StarknetApiError),
+}
+
+pub type TransactionConverterResult<T> = Result<T, TransactionConverterError>;
+
+/// Converts an RPC transaction to an executable transaction, computing its hash.
+/// Declare transactions require the class info of their Sierra class compiled to Casm, which the
+/// gateway compiles before the conversion.
+pub fn rpc_tx_to_executable_tx(
+    rpc_tx: RpcTransaction,
+    optional_class_info: Option<ClassInfo>,
+    chain_id: &ChainId,
+) -> TransactionConverterResult<ExecutableTransaction> {
+    Ok(match rpc_tx {
+        RpcTransaction::Declare(rpc_declare_tx) => {
+            let class_info =
+                optional_class_info.ok_or(TransactionConverterError::MissingClassInfo)?;
+            ExecutableTransaction::Declare(ExecutableDeclareTransaction::create(
+                rpc_declare_tx.into(),
+                class_info,
+                chain_id,
+            )?)
+        }
+        RpcTransaction::DeployAccount(rpc_deploy_account_tx) => {
+            ExecutableTransaction::DeployAccount(ExecutableDeployAccountTransaction::from_rpc_tx(
+                rpc_deploy_account_tx,
+                chain_id,
+            )?)
+        }
+        RpcTransaction::Invoke(rpc_invoke_tx) => ExecutableTransaction::Invoke(
+            ExecutableInvokeTransaction::from_rpc_tx(rpc_invoke_tx, chain_id)?,
+        ),
+    })
+}
+
+/// Returns a transaction of a proposal as sent over the consensus network, without its hash and
+/// class, which the receiving peers derive.
+pub fn executable_tx_to_consensus_tx(tx: ExecutableTransaction) -> Transaction {
+    match tx {
+        ExecutableTransaction::Declare(tx) => Transaction::Declare(tx.tx),
+        ExecutableTransaction::DeployAccount(tx) => Transaction::DeployAccount(tx.tx),
+        ExecutableTransaction::Invoke(tx) => Transaction::Invoke(tx.tx),
+        ExecutableTransaction::L1Handler(tx) => Transaction::L1Handler(tx.tx),
+    }
+}
+
+/// Converts the transactions of proposals received from peers to executable transactions. The
+/// classes of declare transactions are read from the class manager, which stored them when the
+/// transactions were added through the gateway.
+#[derive(Clone)]
+pub struct TransactionConverter {
+    class_manager_client: SharedClassManagerClient,
+    chain_id: ChainId,
+}
+
+impl TransactionConverter {
+    pub fn new(class_manager_client: SharedClassManagerClient, chain_id: ChainId) -> Self {
+        Self { class_manager_client, chain_id }
+    }
+
+    /// Converts a transaction of a proposal streamed by a peer, computing its hash, and resolving
+    /// the class of a declare transaction.
+    pub async fn consensus_tx_to_executable_tx(
+        &self,
+        tx: Transaction,
+    ) -> TransactionConverterResult<ExecutableTransaction> {
+        Ok(match tx {
+            Transaction::Declare(tx) => {
+                let class_info = self.class_info(tx.class_hash()).await?;
+                ExecutableTransaction::Declare(ExecutableDeclareTransaction::create(
+                    tx,
+                    class_info,
+                    &self.chain_id,
+                )?)
+            }
+            Transaction::DeployAccount(tx) => ExecutableTransaction::DeployAccount(
+                ExecutableDeployAccountTransaction::create(tx, &self.chain_id)?,
+            ),
+            Transaction::Invoke(tx) => ExecutableTransaction::Invoke(
+                ExecutableInvokeTransaction::create(tx, &self.chain_id)?,
+            ),
+            Transaction::L1Handler(tx) => {
+                ExecutableTransaction::L1Handler(ExecutableL1HandlerTransaction::create(
+                    tx,
+                    &self.chain_id,
+                    PROPOSED_L1_HANDLER_PAID_FEE,
+                )?)
+            }
+            Transaction::Deploy(_) => return Err(TransactionConverterError::DeployTransaction),
+        })
+    }
+
+    // The class info of a stored class, as compiled when it was declared.
+    async fn class_info(&self, class_hash: ClassHash) -> TransactionConverterResult<ClassInfo> {
+        let (Some(sierra), Some(casm_contract_class)) = (
+            self.class_manager_client.get_sierra(class_hash).await?,
+            self.class_manager_client.get_executable(class_hash).await?,
+        ) else {
+            return Err(TransactionConverterError::ClassNotFound { class_hash });
+        };
+        Ok(ClassInfo {
+            casm_contract_class,
+            sierra_program_length: sierra.sierra_program.len(),
+            abi_length: sierra.abi.len(),
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use starknet_api::core::{ChainId, ClassHash};
+use starknet_api::executable_transaction::{
+    InvokeTransaction as ExecutableInvokeTransaction,
+    Transaction as ExecutableTransaction,
+};
+use starknet_api::felt;
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_api::test_utils::declare::{declare_tx, DeclareTxArgs};
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::Transaction;
+
+use crate::communication::MockClassManagerClient;
+use crate::transaction_converter::{
+    executable_tx_to_consensus_tx,
+    TransactionConverter,
+    TransactionConverterError,
+};
+
+fn class_hash() -> ClassHash {
+    ClassHash(felt!(3_u8))
+}
+
+fn sierra() -> SierraContractClass {
+    SierraContractClass {
+        sierra_program: vec![felt!(1_u8), felt!(2_u8)],
+        abi: "[]".to_string(),
+        ..Default::default()
+    }
+}
+
+fn declare_consensus_tx() -> Transaction {
+    Transaction::Declare(declare_tx(DeclareTxArgs {
+        class_hash: class_hash(),
+        ..Default::default()
+    }))
+}
+
+fn transaction_converter(class_manager_client: MockClassManagerClient) -> TransactionConverter {
+    TransactionConverter::new(Arc::new(class_manager_client), ChainId::create_for_testing())
+}
+
+#[tokio::test]
+async fn consensus_tx_round_trip() {
+    let executable_tx = ExecutableTransaction::Invoke(
+        ExecutableInvokeTransaction::create(
+            invoke_tx(InvokeTxArgs::default()),
+            &ChainId::create_for_testing(),
+        )
+        .unwrap(),
+    );
+    let converter = transaction_converter(MockClassManagerClient::new());
+
+    let consensus_tx = executable_tx_to_consensus_tx(executable_tx.clone());
+
+    assert_eq!(converter.consensus_tx_to_executable_tx(consensus_tx).await.unwrap(), executable_tx);
+}
+
+#[tokio::test]
+async fn declare_consensus_tx_takes_the_stored_class() {
+    let mut class_manager_client = MockClassManagerClient::new();
+    class_manager_client
+        .expect_get_sierra()
+        .withf(|&requested_class_hash| requested_class_hash == class_hash())
+        .returning(|_| Ok(Some(sierra())));
+    class_manager_client.expect_get_executable().returning(|_| Ok(Some(Default::default())));
+    let converter = transaction_converter(class_manager_client);
+
+    let executable_tx =
+        converter.consensus_tx_to_executable_tx(declare_consensus_tx()).await.unwrap();
+
+    let ExecutableTransaction::Declare(executable_declare_tx) = executable_tx else {
+        panic!("Expected a declare transaction, got {executable_tx:?}.");
+    };
+    assert_eq!(executable_declare_tx.class_info.sierra_program_length, 2);
+    assert_eq!(executable_declare_tx.class_info.abi_length, 2);
+}
+
+#[tokio::test]
+async fn declare_consensus_tx_of_an_unknown_class_is_rejected() {
+    let mut class_manager_client = MockClassManagerClient::new();
+    class_manager_client.expect_get_sierra().returning(|_| Ok(None));
+    class_manager_client.expect_get_executable().returning(|_| Ok(None));
+    let converter = transaction_converter(class_manager_client);
+
+    assert_matches!(
+        converter.consensus_tx_to_executable_tx(declare_consensus_tx()).await,
+        Err(TransactionConverterError::ClassNotFound { class_hash: unknown_class_hash })
+            if unknown_class_hash == class_hash()
+    );
+}
@@ -8,6 +8,7 @@ use starknet_consensus_manager_types::communication::{
 };
 use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
 use starknet_mempool_infra::component_server::{LocalActiveComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
 use tokio::sync::mpsc::Receiver;
 
 use crate::consensus_manager::ConsensusManager;
@@ -28,8 +29,10 @@ pub fn create_remote_consensus_manager_server(
     consensus_manager: ConsensusManager,
     ip_address: IpAddr,
     port: u16,
+    serialization_format: SerializationFormat,
 ) -> RemoteConsensusManagerServer {
     RemoteComponentServer::new(consensus_manager, ip_address, port)
+        .with_serialization_format(serialization_format)
 }
 
 #[async_trait]
@@ -11,6 +11,7 @@ use starknet_mempool_infra::component_client::{
     RemoteComponentClient,
 };
 use starknet_mempool_infra::component_definitions::ComponentRequestAndResponseSender;
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use crate::consensus_manager_types::{
@@ -47,7 +48,7 @@ pub trait ConsensusManagerClient: Send + Sync {
     ) -> ConsensusManagerClientResult<ConsensusManagerFnTwoReturnValue>;
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
 pub enum ConsensusManagerRequest {
     ConsensusManagerFnOne(ConsensusManagerFnOneInput),
     ConsensusManagerFnTwo(ConsensusManagerFnTwoInput),
@@ -75,7 +76,7 @@ impl ConsensusManagerClient for LocalConsensusManagerClientImpl {
     ) -> ConsensusManagerClientResult<ConsensusManagerFnOneReturnValue> {
         let request =
             ConsensusManagerRequest::ConsensusManagerFnOne(consensus_manager_fn_one_input);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(
             ConsensusManagerResponse,
             ConsensusManagerFnOne,
@@ -90,7 +91,7 @@ impl ConsensusManagerClient for LocalConsensusManagerClientImpl {
     ) -> ConsensusManagerClientResult<ConsensusManagerFnTwoReturnValue> {
         let request =
             ConsensusManagerRequest::ConsensusManagerFnTwo(consensus_manager_fn_two_input);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(
             ConsensusManagerResponse,
             ConsensusManagerFnTwo,
@@ -0,0 +1,99 @@
+//! The registry of error codes. Codes are never reused or renumbered: a retired error keeps its
+//! code reserved, and new errors are appended to the range of their component.
+//!
+//! | Range     | Component         |
+//! |-----------|-------------------|
+//! | 1000-1999 | Component infra   |
+//! | 2000-2999 | Gateway           |
+//! | 3000-3999 | Mempool           |
+//! | 4000-4999 | Batcher           |
+//! | 5000-5999 | L1 provider       |
+//! | 6000-6999 | L1 gas price      |
+//! | 7000-7999 | Class manager     |
+
+use crate::ErrorCode;
+
+#[cfg(test)]
+#[path = "codes_test.rs"]
+mod codes_test;
+
+macro_rules! error_codes {
+    ($($name:ident = $code:literal,)*) => {
+        $(pub const $name: ErrorCode = ErrorCode($code);)*
+
+        /// All the registered codes, by name.
+        pub const ALL_CODES: &[(&str, ErrorCode)] = &[$((stringify!($name), $name),)*];
+    };
+}
+
+error_codes! {
+    // Component infra.
+    CLIENT_COMMUNICATION_FAILURE = 1000,
+    CLIENT_RESPONSE_DESERIALIZATION_FAILURE = 1001,
+    CLIENT_RESPONSE_PARSING_FAILURE = 1002,
+    CLIENT_SERVER_ERROR_RESPONSE = 1003,
+    CLIENT_UNEXPECTED_RESPONSE = 1004,
+    CLIENT_DEADLINE_EXCEEDED = 1005,
+    CLIENT_CIRCUIT_OPEN = 1006,
+    CLIENT_TIMEOUT = 1007,
+    CLIENT_GRPC_FAILURE = 1008,
+
+    // Gateway.
+    GATEWAY_TRANSACTION_REJECTED = 2000,
+    GATEWAY_INTERNAL_ERROR = 2001,
+    GATEWAY_TOO_MANY_REQUESTS = 2002,
+    GATEWAY_CONFIG_RELOAD_FAILED = 2003,
+    GATEWAY_SERVICE_UNAVAILABLE = 2004,
+
+    // Mempool.
+    MEMPOOL_DUPLICATE_NONCE = 3000,
+    MEMPOOL_DUPLICATE_TRANSACTION = 3001,
+    MEMPOOL_FULL = 3002,
+    MEMPOOL_NONCE_TOO_FAR_IN_FUTURE = 3003,
+    MEMPOOL_TOO_MANY_PENDING_TRANSACTIONS = 3004,
+    MEMPOOL_REPLACEMENT_TIP_TOO_LOW = 3005,
+    MEMPOOL_TRANSACTION_STREAM_UNAVAILABLE = 3006,
+    MEMPOOL_TRANSACTION_NOT_FOUND = 3007,
+    MEMPOOL_FELT_OUT_OF_RANGE = 3008,
+    MEMPOOL_TOO_MANY_SCHEDULED_TRANSACTIONS = 3009,
+
+    // Batcher.
+    PROPOSALS_MANAGER_ALREADY_GENERATING_PROPOSAL = 4000,
+    PROPOSALS_MANAGER_BLOCK_BUILDER_ERROR = 4001,
+    PROPOSALS_MANAGER_PROPOSAL_NOT_ACTIVE = 4002,
+    PROPOSALS_MANAGER_INTERNAL_ERROR = 4003,
+    PROPOSALS_MANAGER_PROPOSAL_NOT_FOUND = 4004,
+    PROPOSALS_MANAGER_SYSTEM_TRANSACTION_ERROR = 4005,
+    PROPOSALS_MANAGER_STALE_PROPOSAL = 4006,
+    BATCHER_ADMIN_ACTIONS_DISABLED = 4007,
+    BATCHER_HEIGHT_MISMATCH = 4008,
+    BATCHER_INTERNAL_ERROR = 4009,
+    BATCHER_PLACEHOLDER = 4010,
+    BATCHER_PROPOSAL_NOT_FOUND = 4011,
+    BATCHER_SERVER_BUSY = 4012,
+    // Retired: admin actions are authenticated by the admin endpoint and the component tokens.
+    BATCHER_UNAUTHORIZED = 4013,
+    BATCHER_DEADLINE_TOO_FAR = 4014,
+    BATCHER_PROPOSAL_FAILED = 4015,
+    BATCHER_STALE_PROPOSAL = 4016,
+    BATCHER_PROPOSAL_HEIGHT_MISMATCH = 4017,
+    BATCHER_SYNCED_BLOCK_HEIGHT_MISMATCH = 4018,
+    PROPOSALS_MANAGER_PROPOSAL_NOT_CACHED = 4019,
+    BATCHER_PROPOSAL_NOT_CACHED = 4020,
+    BATCHER_INVALID_BLOCK_INFO = 4021,
+    PROPOSALS_MANAGER_ALREADY_VALIDATING_ROUND = 4022,
+    PROPOSALS_MANAGER_TOO_MANY_CONCURRENT_VALIDATIONS = 4023,
+    PROPOSALS_MANAGER_DUPLICATE_PROPOSAL_ID = 4024,
+    BATCHER_DUPLICATE_PROPOSAL_ID = 4025,
+    PROPOSALS_MANAGER_OUTSTREAM_OVERFLOW = 4026,
+
+    // L1 provider.
+    L1_PROVIDER_DUPLICATE_TRANSACTION = 5000,
+
+    // L1 gas price.
+    GAS_PRICE_PROVIDER_UNEXPECTED_HEIGHT = 6000,
+    GAS_PRICE_PROVIDER_NOT_ENOUGH_SAMPLES = 6001,
+
+    // Class manager.
+    CLASS_MANAGER_STORAGE_ERROR = 7000,
+}
@@ -0,0 +1,28 @@
+use std::collections::HashSet;
+
+use super::ALL_CODES;
+
+#[test]
+fn codes_are_unique() {
+    let mut codes = HashSet::new();
+    for (name, code) in ALL_CODES {
+        assert!(codes.insert(code), "{name} reuses code {code}.");
+    }
+}
+
+#[test]
+fn codes_are_in_the_range_of_their_component() {
+    for (name, code) in ALL_CODES {
+        let range = match name.split('_').next().unwrap() {
+            "CLIENT" => 1000..2000,
+            "GATEWAY" => 2000..3000,
+            "MEMPOOL" => 3000..4000,
+            "PROPOSALS" | "BATCHER" => 4000..5000,
+            "L1" => 5000..6000,
+            "GAS" => 6000..7000,
+            "CLASS" => 7000..8000,
+            prefix => panic!("{name} has an unregistered component prefix {prefix}."),
+        };
+        assert!(range.contains(&code.value()), "{name} is out of the range {range:?}.");
+    }
+}
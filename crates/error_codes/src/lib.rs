@@ -0,0 +1,95 @@
+pub mod codes;
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Counts the errors surfaced by the components, labeled by their code and severity.
+pub const ERRORS: &str = "sequencer_errors";
+pub const ERROR_CODE_LABEL: &str = "error_code";
+pub const SEVERITY_LABEL: &str = "severity";
+
+/// A stable numeric identifier of an error, kept across versions and shared by all the components.
+/// The codes are registered in [`codes`], where each component is assigned a range of its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How much attention an error calls for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    /// The request was rejected on its merits, e.g., a duplicate transaction; nothing is wrong
+    /// with the node.
+    Info,
+    /// The node is degraded, e.g., a component is unreachable or at capacity; the request may
+    /// succeed later.
+    Warning,
+    /// The node misbehaves, e.g., an internal invariant is broken.
+    Error,
+}
+
+impl Severity {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error with a registered code, so that it can be labeled, matched and surfaced uniformly
+/// across components. Errors wrapping the errors of other components delegate to them.
+pub trait CodedError: std::error::Error {
+    fn error_code(&self) -> ErrorCode;
+
+    fn severity(&self) -> Severity;
+
+    fn report(&self) -> ErrorReport {
+        ErrorReport {
+            code: self.error_code(),
+            severity: self.severity(),
+            message: self.to_string(),
+        }
+    }
+}
+
+/// The code, severity and message of an error, as surfaced outside of the component raising it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub code: ErrorCode,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// Increments the [`ERRORS`] counter for the given error.
+pub fn count_error<E: CodedError + ?Sized>(error: &E) {
+    metrics::increment_counter!(
+        ERRORS,
+        ERROR_CODE_LABEL => error.error_code().to_string(),
+        SEVERITY_LABEL => error.severity().as_str()
+    );
+}
@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use axum::http::{HeaderMap, HeaderValue};
+use serde::Deserialize;
+use serde_json::Value;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{AllResourceBounds, ResourceBounds};
+use tracing::debug;
+
+use crate::config::{ApiVersionConfig, ApiVersionsConfig};
+use crate::errors::{GatewayResult, GatewaySpecError};
+use crate::metrics::record_api_version_request;
+
+#[cfg(test)]
+#[path = "api_versions_test.rs"]
+mod api_versions_test;
+
+/// The HTTP header naming the version of the request format a request was served in.
+pub const API_VERSION_HEADER: &str = "x-starknet-api-version";
+/// The HTTP header answered on requests in a deprecated version of the request format.
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// The versions of the transaction format accepted by the versioned endpoints of the gateway,
+/// named by the path segment of the endpoints, e.g., `/gateway/v0.7/add_transaction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiVersion {
+    // Transactions bound the L1 gas and the L2 gas only.
+    V0_7,
+    // Transactions bound the L1 data gas too.
+    V0_8,
+}
+
+impl ApiVersion {
+    /// The version of the format accepted by the unversioned endpoints.
+    pub const LATEST: ApiVersion = ApiVersion::V0_8;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V0_7 => "v0.7",
+            ApiVersion::V0_8 => "v0.8",
+        }
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = GatewaySpecError;
+
+    fn from_str(version: &str) -> GatewayResult<Self> {
+        match version {
+            "v0.7" => Ok(ApiVersion::V0_7),
+            "v0.8" => Ok(ApiVersion::V0_8),
+            _ => Err(GatewaySpecError::ValidationFailure {
+                data: format!("Unknown API version: {version}. Expected v0.7 or v0.8."),
+            }),
+        }
+    }
+}
+
+/// Negotiates the version of the versioned requests, and converts the transactions of older
+/// versions into the format of the latest one, which the rest of the gateway handles.
+#[derive(Clone, Debug, Default)]
+pub struct ApiVersions {
+    config: ApiVersionsConfig,
+}
+
+impl ApiVersions {
+    pub fn new(config: &ApiVersionsConfig) -> Self {
+        Self { config: config.clone() }
+    }
+
+    fn version_config(&self, version: ApiVersion) -> &ApiVersionConfig {
+        match version {
+            ApiVersion::V0_7 => &self.config.v0_7,
+            ApiVersion::V0_8 => &self.config.v0_8,
+        }
+    }
+
+    /// Returns the version named by the path segment of a request, if it is enabled.
+    pub fn negotiate(&self, version: &str) -> GatewayResult<ApiVersion> {
+        let version = ApiVersion::from_str(version)?;
+        if !self.version_config(version).enable {
+            return Err(GatewaySpecError::ValidationFailure {
+                data: format!(
+                    "API version {} is no longer supported; use version {}.",
+                    version.as_str(),
+                    ApiVersion::LATEST.as_str()
+                ),
+            });
+        }
+        record_api_version_request(version.as_str());
+        Ok(version)
+    }
+
+    /// The headers answered on a request served in the given version.
+    pub fn response_headers(&self, version: ApiVersion) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(API_VERSION_HEADER, HeaderValue::from_static(version.as_str()));
+        if self.version_config(version).deprecated {
+            headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+        }
+        headers
+    }
+}
+
+// The resource bounds of a transaction in version 0.7.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResourceBoundsMappingV0_7 {
+    l1_gas: ResourceBounds,
+    l2_gas: ResourceBounds,
+}
+
+impl From<ResourceBoundsMappingV0_7> for AllResourceBounds {
+    // The hash of a transaction commits to its L1 gas and L2 gas bounds only, so the conversion
+    // keeps the hash, and the signature, of the transaction.
+    fn from(resource_bounds: ResourceBoundsMappingV0_7) -> Self {
+        Self {
+            l1_gas: resource_bounds.l1_gas,
+            l2_gas: resource_bounds.l2_gas,
+            l1_data_gas: ResourceBounds::default(),
+        }
+    }
+}
+
+/// Parses a transaction in the given version of the transaction format into the latest one.
+pub fn parse_tx(version: ApiVersion, mut tx: Value) -> GatewayResult<RpcTransaction> {
+    if version == ApiVersion::V0_7 {
+        if let Some(resource_bounds) = tx.get_mut("resource_bounds") {
+            let resource_bounds_v0_7: ResourceBoundsMappingV0_7 =
+                serde_json::from_value(resource_bounds.take()).map_err(invalid_tx)?;
+            *resource_bounds = serde_json::to_value(AllResourceBounds::from(resource_bounds_v0_7))
+                .expect("Resource bounds are serializable.");
+        }
+    }
+    serde_json::from_value(tx).map_err(invalid_tx)
+}
+
+fn invalid_tx(error: serde_json::Error) -> GatewaySpecError {
+    debug!("Failed to parse a versioned transaction: {error}");
+    GatewaySpecError::ValidationFailure { data: format!("Invalid transaction: {error}") }
+}
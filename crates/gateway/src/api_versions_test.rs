@@ -0,0 +1,80 @@
+use assert_matches::assert_matches;
+use blockifier::test_utils::CairoVersion;
+use mempool_test_utils::starknet_api_test_utils::invoke_tx;
+use starknet_api::rpc_transaction::{RpcInvokeTransaction, RpcTransaction};
+use starknet_api::transaction::ResourceBounds;
+
+use crate::api_versions::{
+    parse_tx,
+    ApiVersion,
+    ApiVersions,
+    API_VERSION_HEADER,
+    DEPRECATION_HEADER,
+};
+use crate::config::{ApiVersionConfig, ApiVersionsConfig};
+use crate::errors::GatewaySpecError;
+
+// An invoke transaction that doesn't bound the L1 data gas, as transactions of version 0.7.
+fn tx_without_l1_data_gas() -> RpcTransaction {
+    let mut tx = invoke_tx(CairoVersion::Cairo1);
+    let RpcTransaction::Invoke(RpcInvokeTransaction::V3(invoke_tx)) = &mut tx else {
+        panic!("Unexpected transaction type");
+    };
+    invoke_tx.resource_bounds.l1_data_gas = ResourceBounds::default();
+    tx
+}
+
+#[test]
+fn tx_of_version_0_7_is_converted_to_the_latest_version() {
+    let tx = tx_without_l1_data_gas();
+    let mut tx_v0_7 = serde_json::to_value(&tx).unwrap();
+    tx_v0_7["resource_bounds"].as_object_mut().unwrap().remove("l1_data_gas");
+
+    assert_eq!(parse_tx(ApiVersion::V0_7, tx_v0_7.clone()).unwrap(), tx);
+    // The latest version requires the L1 data gas bounds.
+    assert_matches!(
+        parse_tx(ApiVersion::V0_8, tx_v0_7),
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+}
+
+#[test]
+fn tx_of_version_0_7_with_l1_data_gas_is_rejected() {
+    let tx = serde_json::to_value(tx_without_l1_data_gas()).unwrap();
+
+    assert_matches!(
+        parse_tx(ApiVersion::V0_7, tx),
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+}
+
+#[test]
+fn unknown_and_disabled_versions_are_rejected() {
+    let api_versions = ApiVersions::new(&ApiVersionsConfig {
+        v0_7: ApiVersionConfig { enable: false, deprecated: true },
+        ..Default::default()
+    });
+
+    assert_eq!(api_versions.negotiate("v0.8").unwrap(), ApiVersion::V0_8);
+    assert_matches!(
+        api_versions.negotiate("v0.7"),
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+    assert_matches!(
+        api_versions.negotiate("v0.6"),
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+}
+
+#[test]
+fn deprecated_version_is_answered_with_a_deprecation_header() {
+    let api_versions = ApiVersions::new(&ApiVersionsConfig::default());
+
+    let headers = api_versions.response_headers(ApiVersion::V0_7);
+    assert_eq!(headers[API_VERSION_HEADER], "v0.7");
+    assert_eq!(headers[DEPRECATION_HEADER], "true");
+
+    let headers = api_versions.response_headers(ApiVersion::V0_8);
+    assert_eq!(headers[API_VERSION_HEADER], "v0.8");
+    assert!(!headers.contains_key(DEPRECATION_HEADER));
+}
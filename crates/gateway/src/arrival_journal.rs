@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_infra::journal::{read_json_lines, JournalOptions, JsonLinesJournal};
+
+#[cfg(test)]
+#[path = "arrival_journal_test.rs"]
+mod arrival_journal_test;
+
+/// A single line of the arrival journal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum JournalEntry {
+    /// A transaction accepted by the gateway.
+    Arrival { tx_hash: TransactionHash, arrival_timestamp_micros: u128, source: String },
+    /// The order in which the transactions of a committed block were included in it.
+    Inclusion { tx_hashes: Vec<TransactionHash> },
+}
+
+/// A journal of transaction arrivals and block inclusion orders, used to audit the ordering
+/// fairness of the sequencer. The entries are written in the background, off the submission path.
+#[derive(Debug)]
+pub struct ArrivalJournal {
+    journal: JsonLinesJournal<JournalEntry>,
+}
+
+impl ArrivalJournal {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(Self { journal: JsonLinesJournal::open(path, JournalOptions::default())? })
+    }
+
+    pub fn record_arrival(&self, tx_hash: TransactionHash, arrival_time: SystemTime, source: &str) {
+        let arrival_timestamp_micros = arrival_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Arrival time should be after the unix epoch.")
+            .as_micros();
+        self.journal.append(JournalEntry::Arrival {
+            tx_hash,
+            arrival_timestamp_micros,
+            source: source.to_string(),
+        });
+    }
+
+    pub fn record_inclusion(&self, tx_hashes: Vec<TransactionHash>) {
+        self.journal.append(JournalEntry::Inclusion { tx_hashes });
+    }
+
+    /// Closes the journal, once the entries recorded so far are written.
+    pub fn close(self) {
+        self.journal.close();
+    }
+}
+
+pub fn read_journal(path: &Path) -> std::io::Result<Vec<JournalEntry>> {
+    read_json_lines(path)
+}
+
+/// Compares the inclusion order of a block against the arrival order of its transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockOrderingReport {
+    pub n_txs: usize,
+    /// Transactions of the block with no arrival entry, e.g., not submitted through this gateway.
+    pub n_unknown_arrivals: usize,
+    /// Pairs of transactions included in the opposite order of their arrival.
+    pub n_inversions: usize,
+    /// The largest distance between the arrival rank and the inclusion rank of a transaction.
+    pub max_displacement: usize,
+}
+
+/// Generates an ordering report for every block inclusion in the journal, in the order the blocks
+/// were committed.
+pub fn generate_ordering_report(entries: &[JournalEntry]) -> Vec<BlockOrderingReport> {
+    let arrival_times: HashMap<TransactionHash, u128> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            JournalEntry::Arrival { tx_hash, arrival_timestamp_micros, .. } => {
+                Some((*tx_hash, *arrival_timestamp_micros))
+            }
+            JournalEntry::Inclusion { .. } => None,
+        })
+        .collect();
+
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            JournalEntry::Inclusion { tx_hashes } => {
+                Some(block_ordering_report(tx_hashes, &arrival_times))
+            }
+            JournalEntry::Arrival { .. } => None,
+        })
+        .collect()
+}
+
+fn block_ordering_report(
+    tx_hashes: &[TransactionHash],
+    arrival_times: &HashMap<TransactionHash, u128>,
+) -> BlockOrderingReport {
+    // Arrival times of the known transactions, in inclusion order.
+    let included_arrivals: Vec<u128> =
+        tx_hashes.iter().filter_map(|tx_hash| arrival_times.get(tx_hash).copied()).collect();
+
+    let mut n_inversions = 0;
+    for (i, earlier_included) in included_arrivals.iter().enumerate() {
+        n_inversions += included_arrivals[i + 1..]
+            .iter()
+            .filter(|later_included| later_included < &earlier_included)
+            .count();
+    }
+
+    let mut arrival_order: Vec<usize> = (0..included_arrivals.len()).collect();
+    arrival_order.sort_by_key(|&inclusion_rank| included_arrivals[inclusion_rank]);
+    let max_displacement = arrival_order
+        .iter()
+        .enumerate()
+        .map(|(arrival_rank, &inclusion_rank)| arrival_rank.abs_diff(inclusion_rank))
+        .max()
+        .unwrap_or_default();
+
+    BlockOrderingReport {
+        n_txs: tx_hashes.len(),
+        n_unknown_arrivals: tx_hashes.len() - included_arrivals.len(),
+        n_inversions,
+        max_displacement,
+    }
+}
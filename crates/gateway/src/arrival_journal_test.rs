@@ -0,0 +1,62 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use pretty_assertions::assert_eq;
+use starknet_api::transaction::TransactionHash;
+use starknet_types_core::felt::Felt;
+
+use crate::arrival_journal::{
+    generate_ordering_report,
+    read_journal,
+    ArrivalJournal,
+    BlockOrderingReport,
+    JournalEntry,
+};
+
+fn tx_hash(hash: u64) -> TransactionHash {
+    TransactionHash(Felt::from(hash))
+}
+
+fn arrival(hash: u64, arrival_timestamp_micros: u128) -> JournalEntry {
+    JournalEntry::Arrival {
+        tx_hash: tx_hash(hash),
+        arrival_timestamp_micros,
+        source: "http".to_string(),
+    }
+}
+
+#[test]
+fn journal_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+
+    let journal = ArrivalJournal::open(&path).unwrap();
+    journal.record_arrival(tx_hash(1), UNIX_EPOCH + Duration::from_micros(10), "http");
+    journal.record_inclusion(vec![tx_hash(1)]);
+    journal.close();
+
+    assert_eq!(
+        read_journal(&path).unwrap(),
+        vec![arrival(1, 10), JournalEntry::Inclusion { tx_hashes: vec![tx_hash(1)] }]
+    );
+}
+
+#[test]
+fn ordering_report() {
+    let entries = vec![
+        arrival(1, 10),
+        arrival(2, 20),
+        arrival(3, 30),
+        // Transaction 3 jumped ahead of both earlier arrivals; transaction 4 is unknown.
+        JournalEntry::Inclusion { tx_hashes: vec![tx_hash(3), tx_hash(1), tx_hash(4), tx_hash(2)] },
+    ];
+
+    assert_eq!(
+        generate_ordering_report(&entries),
+        vec![BlockOrderingReport {
+            n_txs: 4,
+            n_unknown_arrivals: 1,
+            n_inversions: 2,
+            max_displacement: 2,
+        }]
+    );
+}
@@ -1,9 +1,58 @@
-use starknet_mempool_infra::component_server::{create_empty_server, EmptyServer};
+use async_trait::async_trait;
+use starknet_gateway_types::communication::{
+    GatewayRequest,
+    GatewayRequestAndResponseSender,
+    GatewayResponse,
+};
+use starknet_gateway_types::errors::GatewayError;
+use starknet_gateway_types::gateway_types::AddTransactionInput;
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
+use starknet_mempool_infra::component_server::LocalActiveComponentServer;
+use tokio::sync::mpsc::Receiver;
+use tracing::instrument;
 
 use crate::gateway::Gateway;
 
-pub type GatewayServer = EmptyServer<Gateway>;
+pub type LocalGatewayServer = LocalActiveComponentServer<Gateway, GatewayRequest, GatewayResponse>;
 
-pub fn create_gateway_server(gateway: Gateway) -> GatewayServer {
-    create_empty_server(gateway)
+/// Validations of different transactions are independent, so they are handled concurrently up to
+/// the given bound, e.g., so that a slow declare validation doesn't hold back other transactions.
+pub fn create_gateway_server(
+    gateway: Gateway,
+    rx_gateway: Receiver<GatewayRequestAndResponseSender>,
+    max_concurrent_requests: usize,
+) -> LocalGatewayServer {
+    LocalActiveComponentServer::new(gateway, rx_gateway)
+        .with_max_concurrent_requests(max_concurrent_requests)
+}
+
+#[async_trait]
+impl ComponentRequestHandler<GatewayRequest, GatewayResponse> for Gateway {
+    #[instrument(skip(self))]
+    async fn handle_request(&mut self, request: GatewayRequest) -> GatewayResponse {
+        match request {
+            GatewayRequest::AddTransaction(AddTransactionInput { tx, metadata }) => {
+                GatewayResponse::AddTransaction(
+                    self.add_tx(tx, metadata.source).await.map_err(GatewayError::from),
+                )
+            }
+            GatewayRequest::GetTransactionStatus(tx_hash) => {
+                GatewayResponse::GetTransactionStatus(Ok(self.get_transaction_status(tx_hash)))
+            }
+            GatewayRequest::UpdateTransactionStatuses(updates) => {
+                self.update_transaction_statuses(updates);
+                GatewayResponse::UpdateTransactionStatuses(Ok(()))
+            }
+            GatewayRequest::SetIngestionPaused(paused) => {
+                self.set_ingestion_paused(paused);
+                GatewayResponse::SetIngestionPaused(Ok(()))
+            }
+            GatewayRequest::ReloadConfig => GatewayResponse::ReloadConfig(
+                self.reload_config()
+                    .map_err(|e| GatewayError::ConfigReloadFailed { reason: e.to_string() }),
+            ),
+            GatewayRequest::IsAlive => GatewayResponse::IsAlive(self.is_alive().await),
+            GatewayRequest::IsReady => GatewayResponse::IsReady(self.is_ready().await),
+        }
+    }
 }
@@ -1,12 +1,18 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
+use cached::{Cached, SizedCache};
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_starknet_classes::contract_class::ContractClass as CairoLangContractClass;
+use papyrus_common::class_hash::calculate_class_hash;
 use starknet_api::contract_class::ClassInfo;
-use starknet_api::rpc_transaction::RpcDeclareTransaction;
+use starknet_api::core::ClassHash;
+use starknet_api::rpc_transaction::{ContractClass as RpcContractClass, RpcDeclareTransaction};
+use starknet_api::state::{ContractClass as SierraContractClass, EntryPointType};
 use starknet_sierra_compile::cairo_lang_compiler::CairoLangSierraToCasmCompiler;
 use starknet_sierra_compile::command_line_compiler::CommandLineCompiler;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use starknet_sierra_compile::errors::CompilationUtilError;
 use starknet_sierra_compile::utils::into_contract_class_for_compilation;
 use starknet_sierra_compile::SierraToCasmCompiler;
 use tracing::{debug, error};
@@ -17,20 +23,46 @@ use crate::errors::{GatewayResult, GatewaySpecError};
 #[path = "compilation_test.rs"]
 mod compilation_test;
 
+/// The number of declared classes whose compiled class was found in the compiled class cache.
+pub const COMPILED_CLASS_CACHE_HITS: &str = "gateway_compiled_class_cache_hits";
+/// The number of declared classes that were compiled as they were missing from the compiled class
+/// cache.
+pub const COMPILED_CLASS_CACHE_MISSES: &str = "gateway_compiled_class_cache_misses";
+
+// Compiled classes by the hash of their Sierra class.
+type CompiledClassCache = Arc<Mutex<SizedCache<ClassHash, CasmContractClass>>>;
+
 // TODO(Arni): Pass the compiler with dependancy injection.
 #[derive(Clone)]
 pub struct GatewayCompiler {
     pub sierra_to_casm_compiler: Arc<dyn SierraToCasmCompiler>,
+    // Shared by the clones of the compiler, i.e., across concurrent requests.
+    compiled_class_cache: Option<CompiledClassCache>,
 }
 
 impl GatewayCompiler {
     pub fn new_command_line_compiler(config: SierraToCasmCompilationConfig) -> Self {
-        Self { sierra_to_casm_compiler: Arc::new(CommandLineCompiler::new(config)) }
+        Self {
+            sierra_to_casm_compiler: Arc::new(CommandLineCompiler::new(config)),
+            compiled_class_cache: None,
+        }
     }
 
     // TODO(Arni): Cosider deleting `CairoLangSierraToCasmCompiler`.
     pub fn new_cairo_lang_compiler(config: SierraToCasmCompilationConfig) -> Self {
-        Self { sierra_to_casm_compiler: Arc::new(CairoLangSierraToCasmCompiler { config }) }
+        Self {
+            sierra_to_casm_compiler: Arc::new(CairoLangSierraToCasmCompiler { config }),
+            compiled_class_cache: None,
+        }
+    }
+
+    /// Keeps the compiled classes of the last `cache_size` declared classes, so that classes
+    /// declared repeatedly are compiled once.
+    pub fn with_compiled_class_cache(self, cache_size: usize) -> Self {
+        Self {
+            compiled_class_cache: Some(Arc::new(Mutex::new(SizedCache::with_size(cache_size)))),
+            ..self
+        }
     }
 
     /// Formats the contract class for compilation, compiles it, and returns the compiled contract
@@ -42,9 +74,7 @@ impl GatewayCompiler {
     ) -> GatewayResult<ClassInfo> {
         let RpcDeclareTransaction::V3(tx) = declare_tx;
         let rpc_contract_class = &tx.contract_class;
-        let cairo_lang_contract_class = into_contract_class_for_compilation(rpc_contract_class);
-
-        let casm_contract_class = self.compile(cairo_lang_contract_class)?;
+        let casm_contract_class = self.get_or_compile(rpc_contract_class)?;
 
         Ok(ClassInfo {
             casm_contract_class,
@@ -53,16 +83,66 @@ impl GatewayCompiler {
         })
     }
 
+    /// Same as [`Self::process_declare_tx`], but compiles on a blocking thread, so that the
+    /// compilation does not hold up the runtime serving the gateway requests.
+    pub(crate) async fn process_declare_tx_async(
+        &self,
+        declare_tx: RpcDeclareTransaction,
+    ) -> GatewayResult<ClassInfo> {
+        let gateway_compiler = self.clone();
+        tokio::task::spawn_blocking(move || gateway_compiler.process_declare_tx(&declare_tx))
+            .await
+            .map_err(|join_err| {
+                error!("Compilation task failed: {}", join_err);
+                GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+            })?
+    }
+
+    fn get_or_compile(
+        &self,
+        rpc_contract_class: &RpcContractClass,
+    ) -> GatewayResult<CasmContractClass> {
+        let Some(compiled_class_cache) = &self.compiled_class_cache else {
+            return self.compile(into_contract_class_for_compilation(rpc_contract_class));
+        };
+
+        let class_hash = sierra_class_hash(rpc_contract_class);
+        let cached_casm_contract_class = compiled_class_cache
+            .lock()
+            .expect("Compiled class cache lock is poisoned.")
+            .cache_get(&class_hash)
+            .cloned();
+        if let Some(casm_contract_class) = cached_casm_contract_class {
+            metrics::increment_counter!(COMPILED_CLASS_CACHE_HITS);
+            return Ok(casm_contract_class);
+        }
+        metrics::increment_counter!(COMPILED_CLASS_CACHE_MISSES);
+
+        // The cache is not locked during the compilation, so concurrent declarations of the same
+        // class may both compile it.
+        let casm_contract_class =
+            self.compile(into_contract_class_for_compilation(rpc_contract_class))?;
+        compiled_class_cache
+            .lock()
+            .expect("Compiled class cache lock is poisoned.")
+            .cache_set(class_hash, casm_contract_class.clone());
+        Ok(casm_contract_class)
+    }
+
     fn compile(
         &self,
         cairo_lang_contract_class: CairoLangContractClass,
     ) -> GatewayResult<CasmContractClass> {
         match self.sierra_to_casm_compiler.compile(cairo_lang_contract_class) {
             Ok(casm_contract_class) => Ok(casm_contract_class),
-            Err(starknet_sierra_compile::errors::CompilationUtilError::UnexpectedError(error)) => {
+            Err(CompilationUtilError::UnexpectedError(error)) => {
                 error!("Compilation panicked. Error: {:?}", error);
                 Err(GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() })
             }
+            Err(e @ CompilationUtilError::ClassSizeLimitExceeded { .. }) => {
+                debug!("Compilation failed: {}", e);
+                Err(GatewaySpecError::ContractClassSizeIsTooLarge)
+            }
             Err(e) => {
                 debug!("Compilation failed: {:?}", e);
                 Err(GatewaySpecError::CompilationFailed)
@@ -70,3 +150,21 @@ impl GatewayCompiler {
         }
     }
 }
+
+fn sierra_class_hash(rpc_contract_class: &RpcContractClass) -> ClassHash {
+    calculate_class_hash(&sierra_contract_class(rpc_contract_class))
+}
+
+/// The Sierra class of a declare transaction, as stored by the class manager.
+pub(crate) fn sierra_contract_class(rpc_contract_class: &RpcContractClass) -> SierraContractClass {
+    let entry_points_by_type = &rpc_contract_class.entry_points_by_type;
+    SierraContractClass {
+        sierra_program: rpc_contract_class.sierra_program.clone(),
+        entry_points_by_type: HashMap::from([
+            (EntryPointType::Constructor, entry_points_by_type.constructor.clone()),
+            (EntryPointType::External, entry_points_by_type.external.clone()),
+            (EntryPointType::L1Handler, entry_points_by_type.l1handler.clone()),
+        ]),
+        abi: rpc_contract_class.abi.clone(),
+    }
+}
@@ -1,4 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
 use mempool_test_utils::starknet_api_test_utils::{
     compiled_class_hash as test_contract_compiled_class_hash,
     declare_tx as rpc_declare_tx,
@@ -12,6 +17,7 @@ use starknet_api::rpc_transaction::{
 };
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 use starknet_sierra_compile::errors::CompilationUtilError;
+use starknet_sierra_compile::SierraToCasmCompiler;
 use tracing_test::traced_test;
 
 use crate::compilation::GatewayCompiler;
@@ -37,6 +43,7 @@ fn test_compile_contract_class_bytecode_size_validation(declare_tx_v3: RpcDeclar
     let gateway_compiler =
         GatewayCompiler::new_command_line_compiler(SierraToCasmCompilationConfig {
             max_bytecode_size: 1,
+            ..Default::default()
         });
 
     let result = gateway_compiler.process_declare_tx(&RpcDeclareTransaction::V3(declare_tx_v3));
@@ -47,6 +54,18 @@ fn test_compile_contract_class_bytecode_size_validation(declare_tx_v3: RpcDeclar
     assert!(logs_contain(format!("Compilation failed: {:?}", expected_compilation_error).as_str()));
 }
 
+#[rstest]
+fn test_compile_contract_class_raw_class_size_validation(declare_tx_v3: RpcDeclareTransactionV3) {
+    let gateway_compiler =
+        GatewayCompiler::new_command_line_compiler(SierraToCasmCompilationConfig {
+            max_raw_class_size: 1,
+            ..Default::default()
+        });
+
+    let result = gateway_compiler.process_declare_tx(&RpcDeclareTransaction::V3(declare_tx_v3));
+    assert_eq!(result.unwrap_err(), GatewaySpecError::ContractClassSizeIsTooLarge);
+}
+
 #[traced_test]
 #[rstest]
 fn test_compile_contract_class_bad_sierra(
@@ -83,3 +102,53 @@ fn test_process_declare_tx_success(
     assert_eq!(class_info.sierra_program_length, sierra_program_length);
     assert_eq!(class_info.abi_length, abi_length);
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_process_declare_tx_async(
+    gateway_compiler: GatewayCompiler,
+    declare_tx_v3: RpcDeclareTransactionV3,
+) {
+    let declare_tx = RpcDeclareTransaction::V3(declare_tx_v3);
+
+    let class_info = gateway_compiler.process_declare_tx_async(declare_tx.clone()).await.unwrap();
+    assert_eq!(class_info, gateway_compiler.process_declare_tx(&declare_tx).unwrap());
+}
+
+// Counts the compilations done by the wrapped compiler.
+struct CountingCompiler {
+    compiler: Arc<dyn SierraToCasmCompiler>,
+    n_compilations: Arc<AtomicUsize>,
+}
+
+impl SierraToCasmCompiler for CountingCompiler {
+    fn compile(
+        &self,
+        contract_class: ContractClass,
+    ) -> Result<CasmContractClass, CompilationUtilError> {
+        self.n_compilations.fetch_add(1, Ordering::Relaxed);
+        self.compiler.compile(contract_class)
+    }
+}
+
+#[rstest]
+fn test_compiled_class_cache(
+    gateway_compiler: GatewayCompiler,
+    declare_tx_v3: RpcDeclareTransactionV3,
+) {
+    let n_compilations = Arc::new(AtomicUsize::new(0));
+    let gateway_compiler = GatewayCompiler {
+        sierra_to_casm_compiler: Arc::new(CountingCompiler {
+            compiler: gateway_compiler.sierra_to_casm_compiler,
+            n_compilations: n_compilations.clone(),
+        }),
+        compiled_class_cache: None,
+    }
+    .with_compiled_class_cache(1);
+    let declare_tx = RpcDeclareTransaction::V3(declare_tx_v3);
+
+    let class_info = gateway_compiler.process_declare_tx(&declare_tx).unwrap();
+    // The cache is shared by the clones of the compiler.
+    assert_eq!(gateway_compiler.clone().process_declare_tx(&declare_tx).unwrap(), class_info);
+    assert_eq!(n_compilations.load(Ordering::Relaxed), 1);
+}
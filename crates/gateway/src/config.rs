@@ -1,21 +1,51 @@
 use std::collections::BTreeMap;
 use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use blockifier::context::ChainInfo;
-use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_dynamic_param,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
-use serde::{Deserialize, Serialize};
-use starknet_api::core::Nonce;
+use serde::{Deserialize, Deserializer, Serialize};
+use starknet_api::core::{ClassHash, Nonce};
+use starknet_mempool_types::resource_bounds::FeeConfig;
 use starknet_types_core::felt::Felt;
 use validator::Validate;
 
 use crate::compiler_version::VersionId;
 
+const DEFAULT_STRK_PER_ETH_RATE: u64 = 3000;
+// One STRK.
+const DEFAULT_MIN_DECLARE_BALANCE: u128 = 1_000_000_000_000_000_000;
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
 pub struct GatewayConfig {
     pub network_config: GatewayNetworkConfig,
     pub stateless_tx_validator_config: StatelessTransactionValidatorConfig,
     pub stateful_tx_validator_config: StatefulTransactionValidatorConfig,
+    pub transaction_type_config: TransactionTypeConfig,
+    pub arrival_journal_config: ArrivalJournalConfig,
+    pub request_logging_config: RequestLoggingConfig,
+    pub class_cache_config: ClassCacheConfig,
+    pub compiled_class_cache_config: CompiledClassCacheConfig,
+    pub idempotency_config: IdempotencyConfig,
+    pub duplicate_detection_config: DuplicateDetectionConfig,
+    pub rate_limit_config: RateLimitConfig,
+    pub sender_access_config: SenderAccessConfig,
+    pub transaction_status_config: TransactionStatusConfig,
+    pub transaction_events_config: TransactionEventsConfig,
+    pub tenants_config: TenantsConfig,
+    pub simulation_config: SimulationConfig,
+    pub load_shedding_config: LoadSheddingConfig,
+    pub api_versions_config: ApiVersionsConfig,
+    pub declare_gating_config: DeclareGatingConfig,
 }
 
 impl SerializeConfig for GatewayConfig {
@@ -30,6 +60,34 @@ impl SerializeConfig for GatewayConfig {
                 self.stateful_tx_validator_config.dump(),
                 "stateful_tx_validator_config",
             ),
+            append_sub_config_name(self.transaction_type_config.dump(), "transaction_type_config"),
+            append_sub_config_name(self.arrival_journal_config.dump(), "arrival_journal_config"),
+            append_sub_config_name(self.request_logging_config.dump(), "request_logging_config"),
+            append_sub_config_name(self.class_cache_config.dump(), "class_cache_config"),
+            append_sub_config_name(
+                self.compiled_class_cache_config.dump(),
+                "compiled_class_cache_config",
+            ),
+            append_sub_config_name(self.idempotency_config.dump(), "idempotency_config"),
+            append_sub_config_name(
+                self.duplicate_detection_config.dump(),
+                "duplicate_detection_config",
+            ),
+            append_sub_config_name(self.rate_limit_config.dump(), "rate_limit_config"),
+            append_sub_config_name(self.sender_access_config.dump(), "sender_access_config"),
+            append_sub_config_name(
+                self.transaction_status_config.dump(),
+                "transaction_status_config",
+            ),
+            append_sub_config_name(
+                self.transaction_events_config.dump(),
+                "transaction_events_config",
+            ),
+            append_sub_config_name(self.tenants_config.dump(), "tenants_config"),
+            append_sub_config_name(self.simulation_config.dump(), "simulation_config"),
+            append_sub_config_name(self.load_shedding_config.dump(), "load_shedding_config"),
+            append_sub_config_name(self.api_versions_config.dump(), "api_versions_config"),
+            append_sub_config_name(self.declare_gating_config.dump(), "declare_gating_config"),
         ]
         .into_iter()
         .flatten()
@@ -42,6 +100,10 @@ impl SerializeConfig for GatewayConfig {
 pub struct GatewayNetworkConfig {
     pub ip: IpAddr,
     pub port: u16,
+    #[validate(range(min = 1))]
+    pub max_request_body_size: usize,
+    #[validate(range(min = 1))]
+    pub max_batch_size: usize,
 }
 
 impl SerializeConfig for GatewayNetworkConfig {
@@ -54,21 +116,717 @@ impl SerializeConfig for GatewayNetworkConfig {
                 ParamPrivacyInput::Public,
             ),
             ser_param("port", &self.port, "The gateway server port.", ParamPrivacyInput::Public),
+            ser_param(
+                "max_request_body_size",
+                &self.max_request_body_size,
+                "The maximal size of a request body accepted by the gateway server, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_batch_size",
+                &self.max_batch_size,
+                "The maximal number of transactions in a batch submitted to the gateway.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
 
 impl Default for GatewayNetworkConfig {
     fn default() -> Self {
-        Self { ip: "0.0.0.0".parse().unwrap(), port: 8080 }
+        Self {
+            ip: "0.0.0.0".parse().unwrap(),
+            port: 8080,
+            // Large enough for declare transactions of big contract classes.
+            max_request_body_size: 10 * 1024 * 1024,
+            max_batch_size: 100,
+        }
+    }
+}
+
+/// Configuration of the journal recording the arrival order of accepted transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ArrivalJournalConfig {
+    pub enable: bool,
+    pub path: PathBuf,
+}
+
+impl Default for ArrivalJournalConfig {
+    fn default() -> Self {
+        Self { enable: false, path: PathBuf::from("arrival_journal.jsonl") }
+    }
+}
+
+impl SerializeConfig for ArrivalJournalConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the arrival of every accepted transaction is appended to the journal.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "The path of the arrival journal file.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the structured per-request logs of the gateway. Rejected requests are always
+/// logged.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct RequestLoggingConfig {
+    pub success_sampling_interval: u64,
+}
+
+impl Default for RequestLoggingConfig {
+    fn default() -> Self {
+        Self { success_sampling_interval: 100 }
+    }
+}
+
+impl SerializeConfig for RequestLoggingConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "success_sampling_interval",
+            &self.success_sampling_interval,
+            "One out of every this many accepted transactions is logged. If 0, accepted \
+             transactions are not logged.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// Configuration of the cache of compiled classes used by the stateful validation.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ClassCacheConfig {
+    #[validate(range(min = 1))]
+    pub cache_size: usize,
+    // Classes loaded into the cache when the gateway starts, e.g., the classes of common accounts.
+    #[serde(deserialize_with = "deserialize_class_hashes")]
+    pub warm_up_class_hashes: Vec<ClassHash>,
+}
+
+impl Default for ClassCacheConfig {
+    fn default() -> Self {
+        Self { cache_size: 500, warm_up_class_hashes: Vec::new() }
+    }
+}
+
+impl SerializeConfig for ClassCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "cache_size",
+                &self.cache_size,
+                "The maximal number of compiled classes kept in the cache.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "warm_up_class_hashes",
+                &serialize_class_hashes(&self.warm_up_class_hashes),
+                "Comma-separated hashes of classes that are compiled and cached when the gateway \
+                 starts, to avoid delaying the first transactions using them.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the cache of the classes compiled from the Sierra classes of declare
+/// transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct CompiledClassCacheConfig {
+    #[validate(range(min = 1))]
+    pub cache_size: usize,
+}
+
+impl Default for CompiledClassCacheConfig {
+    fn default() -> Self {
+        Self { cache_size: 100 }
+    }
+}
+
+impl SerializeConfig for CompiledClassCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "cache_size",
+            &self.cache_size,
+            "The maximal number of classes compiled from declare transactions kept in the cache.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// Configuration of the idempotency keys that clients may attach to submissions, so that retried
+/// submissions get the result of the first one.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct IdempotencyConfig {
+    pub enable: bool,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub window: Duration,
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self { enable: false, window: Duration::from_secs(600), capacity: 100000 }
+    }
+}
+
+impl SerializeConfig for IdempotencyConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, resubmissions of a transaction with the idempotency key of its previous \
+                 submission get its result instead of being processed.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "window",
+                &self.window.as_secs(),
+                "The time in seconds for which the result of a submission with an idempotency key \
+                 is remembered.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity",
+                &self.capacity,
+                "The maximal number of remembered results; the oldest ones are forgotten first.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the detection of resubmitted transactions, which are rejected before they are
+/// validated.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct DuplicateDetectionConfig {
+    pub enable: bool,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub window: Duration,
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+    // If false, transactions with the nonce of a remembered transaction of their sender are
+    // rejected as well, even if they bump its fee.
+    pub allow_fee_bump_replacements: bool,
+}
+
+impl Default for DuplicateDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            window: Duration::from_secs(600),
+            capacity: 100000,
+            allow_fee_bump_replacements: true,
+        }
+    }
+}
+
+impl SerializeConfig for DuplicateDetectionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, resubmissions of recently added transactions are rejected as duplicates \
+                 without being validated.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "window",
+                &self.window.as_secs(),
+                "The time in seconds for which added transactions are remembered.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity",
+                &self.capacity,
+                "The maximal number of remembered transactions; the oldest ones are forgotten \
+                 first.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "allow_fee_bump_replacements",
+                &self.allow_fee_bump_replacements,
+                "If false, transactions with the nonce of a remembered transaction of their \
+                 sender are rejected as duplicates as well, even if they bump its fee.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the limits on the rate of submissions, both of each sender and of each client
+/// IP submitting over HTTP. Submissions beyond the limits are rejected with `TooManyRequests`. The
+/// limits can be changed without restarting the node.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct RateLimitConfig {
+    pub sender_limit: TokenBucketConfig,
+    // Applies to the declare transactions of each sender, in addition to its sender limit.
+    pub declare_sender_limit: TokenBucketConfig,
+    pub ip_limit: TokenBucketConfig,
+    // The maximal number of senders or IPs tracked by each limit; submissions of untracked ones
+    // are rejected beyond it.
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            sender_limit: TokenBucketConfig::default(),
+            declare_sender_limit: TokenBucketConfig { enable: true, burst: 5, refill_rate: 0.1 },
+            // Clients behind a shared proxy appear to the gateway with the IP of the proxy.
+            ip_limit: TokenBucketConfig { enable: false, burst: 1000, refill_rate: 100.0 },
+            capacity: 100000,
+        }
+    }
+}
+
+impl SerializeConfig for RateLimitConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        vec![
+            append_sub_config_name(self.sender_limit.dump(), "sender_limit"),
+            append_sub_config_name(self.declare_sender_limit.dump(), "declare_sender_limit"),
+            append_sub_config_name(self.ip_limit.dump(), "ip_limit"),
+            BTreeMap::from_iter([ser_dynamic_param(
+                "capacity",
+                &self.capacity,
+                "The maximal number of senders or IPs tracked by each rate limit; submissions of \
+                 untracked ones are rejected beyond it.",
+                ParamPrivacyInput::Public,
+            )]),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// A token bucket limit: each key may submit up to `burst` transactions at once, and regains
+/// `refill_rate` submissions per second.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TokenBucketConfig {
+    pub enable: bool,
+    #[validate(range(min = 1))]
+    pub burst: u32,
+    #[validate(range(min = 0.0))]
+    pub refill_rate: f64,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self { enable: true, burst: 100, refill_rate: 10.0 }
+    }
+}
+
+impl SerializeConfig for TokenBucketConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_dynamic_param(
+                "enable",
+                &self.enable,
+                "If true, submissions beyond the limit are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "burst",
+                &self.burst,
+                "The maximal number of submissions at once.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "refill_rate",
+                &self.refill_rate,
+                "The number of submissions regained per second.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the lists of the senders allowed and denied to submit transactions, e.g., to
+/// restrict an appchain to the accounts of its operators.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct SenderAccessConfig {
+    pub enable: bool,
+    // A JSON file with the lists, see `SenderAccessLists`.
+    pub path: PathBuf,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub reload_interval: Duration,
+}
+
+impl Default for SenderAccessConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            path: PathBuf::from("sender_access_lists.json"),
+            reload_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SerializeConfig for SenderAccessConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, transactions of senders that are denied, or not allowed, are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "The path of a JSON file with an optional list of the allowed senders, and a list \
+                 of the denied senders.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "reload_interval",
+                &self.reload_interval.as_secs(),
+                "The time in seconds between reads of the sender lists from their file. If 0, the \
+                 lists are read once.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the policy declare transactions are subject to, in addition to the other
+/// transactions, as their compilation and class storage are expensive. The rate of declares of
+/// each sender is limited by [`RateLimitConfig::declare_sender_limit`].
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct DeclareGatingConfig {
+    pub enable_allow_list: bool,
+    // A JSON file with the list of the senders allowed to declare classes, e.g., `["0x1", "0x2"]`.
+    pub allow_list_path: PathBuf,
+    // If set, the fee token balance a sender must hold to declare a class, in fri.
+    pub min_balance: Option<u128>,
+}
+
+impl Default for DeclareGatingConfig {
+    fn default() -> Self {
+        Self {
+            enable_allow_list: false,
+            allow_list_path: PathBuf::from("declare_allow_list.json"),
+            min_balance: None,
+        }
+    }
+}
+
+impl SerializeConfig for DeclareGatingConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([
+            ser_param(
+                "enable_allow_list",
+                &self.enable_allow_list,
+                "If true, declare transactions of senders that are not on the declare allow list \
+                 are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "allow_list_path",
+                &self.allow_list_path,
+                "The path of a JSON file with the list of the senders allowed to declare classes.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(ser_optional_param(
+            &self.min_balance,
+            DEFAULT_MIN_DECLARE_BALANCE,
+            "min_balance",
+            "If set, declare transactions of senders whose STRK balance is lower, in fri, are \
+             rejected.",
+            ParamPrivacyInput::Public,
+        ));
+        config
+    }
+}
+
+/// Configuration of the tracking of the statuses of the transactions received by the gateway.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TransactionStatusConfig {
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+}
+
+impl Default for TransactionStatusConfig {
+    fn default() -> Self {
+        Self { capacity: 100000 }
+    }
+}
+
+impl SerializeConfig for TransactionStatusConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "capacity",
+            &self.capacity,
+            "The maximal number of transactions whose status is tracked; the statuses of the \
+             oldest transactions are forgotten first.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// Configuration of the subscriptions to the lifecycle events of transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TransactionEventsConfig {
+    #[validate(range(min = 1))]
+    pub channel_capacity: usize,
+}
+
+impl Default for TransactionEventsConfig {
+    fn default() -> Self {
+        Self { channel_capacity: 1000 }
+    }
+}
+
+impl SerializeConfig for TransactionEventsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "channel_capacity",
+            &self.channel_capacity,
+            "The number of transaction events buffered for each subscriber; subscribers falling \
+             further behind are disconnected.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// Configuration of the other sequencer backends fronted by the gateway, to which submissions are
+/// routed by their declared chain id.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TenantsConfig {
+    pub enable: bool,
+    // A JSON file listing the tenants, see `TenantConfig`.
+    pub path: PathBuf,
+}
+
+impl Default for TenantsConfig {
+    fn default() -> Self {
+        Self { enable: false, path: PathBuf::from("tenants.json") }
+    }
+}
+
+impl SerializeConfig for TenantsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, submissions declaring the chain id of a tenant are routed to its \
+                 backend.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "The path of a JSON file listing the tenants, with the chain id, mempool URL, rpc \
+                 state reader, limits and enabled transaction types of each.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the simulation of transactions, executed without being added to the mempool.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct SimulationConfig {
+    pub enable: bool,
+    #[validate(range(min = 1))]
+    pub max_n_transactions: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self { enable: true, max_n_transactions: 100 }
+    }
+}
+
+impl SerializeConfig for SimulationConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the gateway serves simulations of transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_n_transactions",
+                &self.max_n_transactions,
+                "The maximal number of transactions simulated in a single request.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Configuration of the limit on the submissions validated concurrently. Submissions beyond it are
+/// rejected at once with `ServiceUnavailable`, for clients to retry, instead of queueing behind the
+/// validations in progress.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct LoadSheddingConfig {
+    pub enable: bool,
+    #[validate(range(min = 1))]
+    pub max_concurrent_validations: usize,
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self { enable: true, max_concurrent_validations: 1000 }
+    }
+}
+
+impl SerializeConfig for LoadSheddingConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, submissions beyond the limit on concurrent validations are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_concurrent_validations",
+                &self.max_concurrent_validations,
+                "The maximal number of submissions validated concurrently.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+fn serialize_class_hashes(class_hashes: &[ClassHash]) -> String {
+    class_hashes.iter().map(|class_hash| class_hash.0.to_hex_string()).collect::<Vec<_>>().join(",")
+}
+
+fn deserialize_class_hashes<'de, D>(de: D) -> Result<Vec<ClassHash>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str: String = Deserialize::deserialize(de)?;
+    if raw_str.is_empty() {
+        return Ok(Vec::new());
+    }
+    raw_str
+        .split(',')
+        .map(|raw_class_hash| {
+            Felt::from_hex(raw_class_hash.trim()).map(ClassHash).map_err(|_| {
+                serde::de::Error::custom(format!("Invalid class hash: {raw_class_hash}."))
+            })
+        })
+        .collect()
+}
+
+/// The set of transaction types accepted by the gateway. Invoke transactions are always accepted.
+/// Applied to the running gateway once the node config is reloaded.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TransactionTypeConfig {
+    pub enable_declare: bool,
+    pub enable_deploy_account: bool,
+}
+
+impl Default for TransactionTypeConfig {
+    fn default() -> Self {
+        Self { enable_declare: true, enable_deploy_account: true }
+    }
+}
+
+impl SerializeConfig for TransactionTypeConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_dynamic_param(
+                "enable_declare",
+                &self.enable_declare,
+                "If false, declare transactions are rejected by the gateway.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "enable_deploy_account",
+                &self.enable_deploy_account,
+                "If false, deploy account transactions are rejected by the gateway.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The versions of the request format accepted by the versioned endpoints of the gateway.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ApiVersionsConfig {
+    pub v0_7: ApiVersionConfig,
+    pub v0_8: ApiVersionConfig,
+}
+
+impl Default for ApiVersionsConfig {
+    fn default() -> Self {
+        Self {
+            v0_7: ApiVersionConfig { enable: true, deprecated: true },
+            v0_8: ApiVersionConfig { enable: true, deprecated: false },
+        }
+    }
+}
+
+impl SerializeConfig for ApiVersionsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        vec![
+            append_sub_config_name(self.v0_7.dump(), "v0_7"),
+            append_sub_config_name(self.v0_8.dump(), "v0_8"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ApiVersionConfig {
+    pub enable: bool,
+    // Requests in a deprecated version are accepted, and answered with a deprecation header for
+    // clients to migrate before the version is disabled.
+    pub deprecated: bool,
+}
+
+impl SerializeConfig for ApiVersionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If false, requests in this version of the request format are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "deprecated",
+                &self.deprecated,
+                "If true, requests in this version of the request format are answered with a \
+                 deprecation header.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct StatelessTransactionValidatorConfig {
-    // If true, validates that the resource bounds are not zero.
-    pub validate_non_zero_l1_gas_fee: bool,
+    // If true, validates that the L2 resource bounds are not zero. The L1 resource bounds are
+    // always validated, as the block builder does.
     pub validate_non_zero_l2_gas_fee: bool,
+    // If true, validates that the maximal fee implied by the resource bounds fits in 128 bits.
+    pub validate_max_fee_overflow: bool,
+    // The fee policy of the chain, which the block builder enforces too.
+    pub fee_config: FeeConfig,
     pub max_calldata_length: usize,
     pub max_signature_length: usize,
 
@@ -81,8 +839,9 @@ pub struct StatelessTransactionValidatorConfig {
 impl Default for StatelessTransactionValidatorConfig {
     fn default() -> Self {
         StatelessTransactionValidatorConfig {
-            validate_non_zero_l1_gas_fee: true,
             validate_non_zero_l2_gas_fee: false,
+            validate_max_fee_overflow: true,
+            fee_config: FeeConfig::default(),
             max_calldata_length: 4000,
             max_signature_length: 4000,
             max_contract_class_object_size: 4089446,
@@ -95,18 +854,19 @@ impl Default for StatelessTransactionValidatorConfig {
 impl SerializeConfig for StatelessTransactionValidatorConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         let members = BTreeMap::from_iter([
-            ser_param(
-                "validate_non_zero_l1_gas_fee",
-                &self.validate_non_zero_l1_gas_fee,
-                "If true, validates that a transaction has non-zero L1 resource bounds.",
-                ParamPrivacyInput::Public,
-            ),
             ser_param(
                 "validate_non_zero_l2_gas_fee",
                 &self.validate_non_zero_l2_gas_fee,
                 "If true, validates that a transaction has non-zero L2 resource bounds.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "validate_max_fee_overflow",
+                &self.validate_max_fee_overflow,
+                "If true, validates that the maximal fee implied by the resource bounds of a \
+                 transaction does not overflow.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "max_signature_length",
                 &self.max_signature_length,
@@ -128,6 +888,7 @@ impl SerializeConfig for StatelessTransactionValidatorConfig {
         ]);
         vec![
             members,
+            append_sub_config_name(self.fee_config.dump(), "fee_config"),
             append_sub_config_name(self.min_sierra_version.dump(), "min_sierra_version"),
             append_sub_config_name(self.max_sierra_version.dump(), "max_sierra_version"),
         ]
@@ -141,18 +902,25 @@ impl SerializeConfig for StatelessTransactionValidatorConfig {
 pub struct RpcStateReaderConfig {
     pub url: String,
     pub json_rpc_version: String,
+    // If set, gas prices given by the rpc server in a single fee token are converted to the other
+    // fee token with this rate, so transactions can be validated whichever token they pay in.
+    pub strk_per_eth_rate: Option<u64>,
 }
 
 #[cfg(any(feature = "testing", test))]
 impl RpcStateReaderConfig {
     pub fn create_for_testing() -> Self {
-        Self { url: "http://localhost:8080".to_string(), json_rpc_version: "2.0".to_string() }
+        Self {
+            url: "http://localhost:8080".to_string(),
+            json_rpc_version: "2.0".to_string(),
+            strk_per_eth_rate: None,
+        }
     }
 }
 
 impl SerializeConfig for RpcStateReaderConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut config = BTreeMap::from_iter([
             ser_param("url", &self.url, "The url of the rpc server.", ParamPrivacyInput::Public),
             ser_param(
                 "json_rpc_version",
@@ -160,7 +928,16 @@ impl SerializeConfig for RpcStateReaderConfig {
                 "The json rpc version.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+        ]);
+        config.extend(ser_optional_param(
+            &self.strk_per_eth_rate,
+            DEFAULT_STRK_PER_ETH_RATE,
+            "strk_per_eth_rate",
+            "The conversion rate used to derive a gas price missing in one fee token from the \
+             other. If not set, gas prices must be given in both fee tokens.",
+            ParamPrivacyInput::Public,
+        ));
+        config
     }
 }
 
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use blockifier::context::ChainInfo;
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
+use starknet_api::core::ContractAddress;
+use starknet_types_core::felt::Felt;
+use tracing::error;
+
+use crate::config::DeclareGatingConfig;
+use crate::errors::{DeclareGatingError, DeclareGatingResult, GatewayResult, GatewaySpecError};
+use crate::sender_access::SenderAccessListsLoadingError;
+use crate::state_reader::StateReaderFactory;
+
+#[cfg(test)]
+#[path = "declare_gating_test.rs"]
+mod declare_gating_test;
+
+/// Rejects the declare transactions of the senders that may not declare classes: those that are
+/// not on the declare allow list, if there is one, and those whose balance is too low to make
+/// spamming declares costly.
+#[derive(Debug, Default)]
+pub struct DeclareGate {
+    allow_list: Option<HashSet<ContractAddress>>,
+    min_balance: Option<u128>,
+}
+
+impl DeclareGate {
+    pub fn new(config: &DeclareGatingConfig) -> Result<Self, SenderAccessListsLoadingError> {
+        let allow_list = config
+            .enable_allow_list
+            .then(|| load_allow_list(&config.allow_list_path))
+            .transpose()?;
+        Ok(Self { allow_list, min_balance: config.min_balance })
+    }
+
+    pub fn validate_sender(&self, sender_address: ContractAddress) -> DeclareGatingResult<()> {
+        if self.allow_list.as_ref().is_some_and(|allow_list| !allow_list.contains(&sender_address))
+        {
+            return Err(DeclareGatingError::SenderNotAllowed { sender_address });
+        }
+        Ok(())
+    }
+
+    /// Checks that the sender holds the minimal STRK balance, in the state of the latest block.
+    pub fn validate_balance(
+        &self,
+        state_reader_factory: &dyn StateReaderFactory,
+        chain_info: &ChainInfo,
+        sender_address: ContractAddress,
+    ) -> GatewayResult<()> {
+        let Some(min_balance) = self.min_balance else {
+            return Ok(());
+        };
+        let state_reader = state_reader_factory.get_state_reader_from_latest_block();
+        let (low, high) = state_reader
+            .get_fee_token_balance(
+                sender_address,
+                chain_info.fee_token_addresses.strk_fee_token_address,
+            )
+            .map_err(|e| {
+                error!("Failed to read the balance of declare sender {}: {}", sender_address, e);
+                GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+            })?;
+        // The balance is a 256-bit integer, split to its low and high 128 bits.
+        if high == Felt::ZERO && low < Felt::from(min_balance) {
+            return Err(DeclareGatingError::InsufficientBalance {
+                sender_address,
+                balance: low,
+                min_balance,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+// Reads the allow list from a JSON file, e.g., `["0x1", "0x2"]`.
+fn load_allow_list(path: &Path) -> Result<HashSet<ContractAddress>, SenderAccessListsLoadingError> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
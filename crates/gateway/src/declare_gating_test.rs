@@ -0,0 +1,71 @@
+use std::fs;
+
+use assert_matches::assert_matches;
+use blockifier::context::BlockContext;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::{CairoVersion, BALANCE};
+use starknet_api::core::ContractAddress;
+
+use crate::config::DeclareGatingConfig;
+use crate::declare_gating::DeclareGate;
+use crate::errors::{DeclareGatingError, GatewaySpecError};
+use crate::rejections::{Rejection, RejectionCode};
+use crate::state_reader_test_utils::local_test_state_reader_factory;
+
+fn address(value: u8) -> ContractAddress {
+    ContractAddress::from(u128::from(value))
+}
+
+#[test]
+fn only_senders_on_the_allow_list_may_declare() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("declare_allow_list.json");
+    fs::write(&path, r#"["0x1"]"#).unwrap();
+    let declare_gate = DeclareGate::new(&DeclareGatingConfig {
+        enable_allow_list: true,
+        allow_list_path: path,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(declare_gate.validate_sender(address(1)), Ok(()));
+    assert_eq!(
+        declare_gate.validate_sender(address(2)),
+        Err(DeclareGatingError::SenderNotAllowed { sender_address: address(2) })
+    );
+    // Without an allow list, any sender may declare.
+    assert_eq!(DeclareGate::default().validate_sender(address(2)), Ok(()));
+}
+
+#[test]
+fn senders_below_the_minimal_balance_may_not_declare() {
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let chain_info = BlockContext::create_for_testing().chain_info().clone();
+    let sender_address =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1).get_instance_address(0);
+    let declare_gate = |min_balance| {
+        DeclareGate::new(&DeclareGatingConfig { min_balance, ..Default::default() }).unwrap()
+    };
+
+    for min_balance in [None, Some(BALANCE)] {
+        assert_eq!(
+            declare_gate(min_balance).validate_balance(
+                &state_reader_factory,
+                &chain_info,
+                sender_address
+            ),
+            Ok(())
+        );
+    }
+    assert_matches!(
+        declare_gate(Some(BALANCE + 1)).validate_balance(
+            &state_reader_factory,
+            &chain_info,
+            sender_address
+        ),
+        Err(GatewaySpecError::InvalidTransaction(Rejection {
+            code: RejectionCode::DeclareBalanceTooLow,
+            ..
+        }))
+    );
+}
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use starknet_api::core::{ChainId, ContractAddress, Nonce};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{Transaction, TransactionHash};
+use tracing::error;
+
+use crate::config::DuplicateDetectionConfig;
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "duplicate_detection_test.rs"]
+mod duplicate_detection_test;
+
+/// Identifies a submitted transaction before it is validated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubmissionKey {
+    pub tx_hash: TransactionHash,
+    pub sender_address: ContractAddress,
+    pub nonce: Nonce,
+}
+
+impl SubmissionKey {
+    /// Calculates the hash and the sender of the given transaction, without compiling its class.
+    pub fn new(tx: &RpcTransaction, chain_id: &ChainId) -> GatewayResult<Self> {
+        let unexpected_error = |error| {
+            error!("Failed to identify a submitted transaction: {}", error);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        };
+        let sender_address = tx.calculate_sender_address().map_err(unexpected_error)?;
+        let tx_hash = Transaction::from(tx.clone())
+            .calculate_transaction_hash(chain_id)
+            .map_err(unexpected_error)?;
+        Ok(Self { tx_hash, sender_address, nonce: *tx.nonce() })
+    }
+}
+
+struct Entry {
+    account_nonce: (ContractAddress, Nonce),
+    expiration: Instant,
+}
+
+#[derive(Default)]
+struct Entries {
+    tx_hashes: HashMap<TransactionHash, Entry>,
+    account_nonces: HashMap<(ContractAddress, Nonce), TransactionHash>,
+    // The transactions in the order they were added, with the time they expire at. Includes
+    // forgotten transactions, until they expire.
+    expirations: VecDeque<(Instant, TransactionHash)>,
+}
+
+impl Entries {
+    fn remove_front(&mut self) {
+        let (expiration, tx_hash) = self.expirations.pop_front().expect("The front entry exists.");
+        // The transaction may have been forgotten, and added again since.
+        if self.tx_hashes.get(&tx_hash).is_some_and(|entry| entry.expiration == expiration) {
+            self.remove(tx_hash);
+        }
+    }
+
+    fn remove(&mut self, tx_hash: TransactionHash) {
+        let Some(Entry { account_nonce, .. }) = self.tx_hashes.remove(&tx_hash) else {
+            return;
+        };
+        // The nonce may have been taken over by a later transaction of the account.
+        if self.account_nonces.get(&account_nonce) == Some(&tx_hash) {
+            self.account_nonces.remove(&account_nonce);
+        }
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while self.expirations.front().is_some_and(|(expiration, _)| *expiration <= now) {
+            self.remove_front();
+        }
+    }
+}
+
+/// Remembers the transactions added to the mempool for a configurable window, so that their
+/// resubmissions are rejected as duplicates without being validated again. Transactions replacing
+/// a remembered transaction of their sender, i.e., bumping its fee, are rejected as well unless
+/// fee-bump replacements are allowed.
+///
+/// Transactions rejected while they are sequenced are forgotten, so that they can be resubmitted,
+/// e.g., once their sender can pay for them.
+pub struct DuplicateDetector {
+    window: Duration,
+    capacity: usize,
+    allow_fee_bump_replacements: bool,
+    entries: Mutex<Entries>,
+}
+
+impl DuplicateDetector {
+    pub fn new(config: &DuplicateDetectionConfig) -> Self {
+        Self {
+            window: config.window,
+            capacity: config.capacity,
+            allow_fee_bump_replacements: config.allow_fee_bump_replacements,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn check(&self, key: &SubmissionKey) -> GatewayResult<()> {
+        let mut entries = self.entries.lock().expect("The duplicate detector lock is poisoned.");
+        entries.remove_expired(Instant::now());
+        let is_duplicate = entries.tx_hashes.contains_key(&key.tx_hash)
+            || (!self.allow_fee_bump_replacements
+                && entries.account_nonces.contains_key(&(key.sender_address, key.nonce)));
+        if is_duplicate {
+            return Err(GatewaySpecError::DuplicateTx);
+        }
+        Ok(())
+    }
+
+    /// Remembers a transaction that was added to the mempool.
+    pub fn record(&self, key: SubmissionKey) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("The duplicate detector lock is poisoned.");
+        entries.remove_expired(now);
+        if entries.tx_hashes.contains_key(&key.tx_hash) {
+            return;
+        }
+        let account_nonce = (key.sender_address, key.nonce);
+        let expiration = now + self.window;
+        entries.tx_hashes.insert(key.tx_hash, Entry { account_nonce, expiration });
+        entries.account_nonces.insert(account_nonce, key.tx_hash);
+        entries.expirations.push_back((expiration, key.tx_hash));
+        while entries.expirations.len() > self.capacity {
+            entries.remove_front();
+        }
+    }
+
+    pub fn forget(&self, tx_hash: TransactionHash) {
+        let mut entries = self.entries.lock().expect("The duplicate detector lock is poisoned.");
+        entries.remove(tx_hash);
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use blockifier::context::ChainInfo;
+use blockifier::test_utils::CairoVersion;
+use mempool_test_utils::starknet_api_test_utils::invoke_tx;
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
+
+use crate::config::DuplicateDetectionConfig;
+use crate::duplicate_detection::{DuplicateDetector, SubmissionKey};
+use crate::errors::GatewaySpecError;
+use crate::utils::rpc_tx_to_account_tx;
+
+fn submission_key(tx_hash: u8, nonce: u8) -> SubmissionKey {
+    SubmissionKey {
+        tx_hash: TransactionHash(tx_hash.into()),
+        sender_address: ContractAddress::default(),
+        nonce: Nonce(nonce.into()),
+    }
+}
+
+fn duplicate_detector(config: DuplicateDetectionConfig) -> DuplicateDetector {
+    DuplicateDetector::new(&config)
+}
+
+#[test]
+fn recorded_transactions_are_duplicates() {
+    let detector = duplicate_detector(DuplicateDetectionConfig::default());
+    assert_eq!(detector.check(&submission_key(1, 0)), Ok(()));
+
+    detector.record(submission_key(1, 0));
+
+    assert_eq!(detector.check(&submission_key(1, 0)), Err(GatewaySpecError::DuplicateTx));
+    // A fee-bump replacement of the transaction.
+    assert_eq!(detector.check(&submission_key(2, 0)), Ok(()));
+}
+
+#[test]
+fn fee_bump_replacements_are_duplicates_unless_allowed() {
+    let detector = duplicate_detector(DuplicateDetectionConfig {
+        allow_fee_bump_replacements: false,
+        ..Default::default()
+    });
+
+    detector.record(submission_key(1, 0));
+
+    assert_eq!(detector.check(&submission_key(2, 0)), Err(GatewaySpecError::DuplicateTx));
+    assert_eq!(detector.check(&submission_key(2, 1)), Ok(()));
+}
+
+#[test]
+fn forgotten_transactions_can_be_resubmitted() {
+    let detector = duplicate_detector(DuplicateDetectionConfig::default());
+    detector.record(submission_key(1, 0));
+
+    detector.forget(TransactionHash(1_u8.into()));
+
+    assert_eq!(detector.check(&submission_key(1, 0)), Ok(()));
+}
+
+#[test]
+fn transactions_are_forgotten_after_the_window() {
+    let detector = duplicate_detector(DuplicateDetectionConfig {
+        window: Duration::ZERO,
+        ..Default::default()
+    });
+
+    detector.record(submission_key(1, 0));
+
+    assert_eq!(detector.check(&submission_key(1, 0)), Ok(()));
+}
+
+#[test]
+fn oldest_transactions_are_forgotten_beyond_the_capacity() {
+    let detector =
+        duplicate_detector(DuplicateDetectionConfig { capacity: 1, ..Default::default() });
+
+    detector.record(submission_key(1, 0));
+    detector.record(submission_key(2, 1));
+
+    assert_eq!(detector.check(&submission_key(1, 0)), Ok(()));
+    assert_eq!(detector.check(&submission_key(2, 1)), Err(GatewaySpecError::DuplicateTx));
+}
+
+#[test]
+fn submission_keys_are_the_hashes_of_the_transactions() {
+    let tx = invoke_tx(CairoVersion::Cairo1);
+    let chain_id = ChainInfo::create_for_testing().chain_id;
+
+    let key = SubmissionKey::new(&tx, &chain_id).unwrap();
+
+    let account_tx = rpc_tx_to_account_tx(&tx, None, &chain_id).unwrap();
+    assert_eq!(key.tx_hash, account_tx.tx_hash());
+    assert_eq!(key.sender_address, tx.calculate_sender_address().unwrap());
+    assert_eq!(key.nonce, *tx.nonce());
+}
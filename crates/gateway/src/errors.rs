@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use axum::Json;
 use blockifier::state::errors::StateError;
 use enum_assoc::Assoc;
 use papyrus_rpc::error::{
@@ -23,29 +24,23 @@ use papyrus_rpc::error::{
 };
 use serde_json::{Error as SerdeError, Value};
 use starknet_api::block::GasPrice;
-use starknet_api::transaction::{Resource, ResourceBounds};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::{AllResourceBounds, Resource, ResourceBounds};
+use starknet_gateway_types::errors::GatewayError;
+use starknet_mempool_types::resource_bounds::ResourceBoundsError;
+use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
 use crate::compiler_version::{VersionId, VersionIdError};
+use crate::rejections::{Rejection, RejectionCode};
 
 pub type GatewayResult<T> = Result<T, GatewaySpecError>;
 
+// Answers with the structured rejection of the error, in the HTTP status of its code.
 impl IntoResponse for GatewaySpecError {
     fn into_response(self) -> Response {
-        let as_rpc = self.into_rpc();
-        // TODO(Arni): Fix the status code. The status code should be a HTTP status code - not a
-        // Json RPC error code. status code.
-        let status =
-            StatusCode::from_u16(u16::try_from(as_rpc.code).expect("Expecting a valid u16"))
-                .expect("Expecting a valid error code");
-
-        let resp = Response::builder()
-            .status(status)
-            .body((as_rpc.message, as_rpc.data))
-            .expect("Expecting valid response");
-        let status = resp.status();
-        let body = serde_json::to_string(resp.body()).expect("Expecting valid body");
-        (status, body).into_response()
+        let rejection = Rejection::from(self);
+        (rejection.code.status_code(), Json(rejection)).into_response()
     }
 }
 
@@ -69,6 +64,16 @@ pub enum StatelessTransactionValidatorError {
     EntryPointsNotUniquelySorted,
     #[error(transparent)]
     InvalidSierraVersion(#[from] VersionIdError),
+    #[error("The maximal fee implied by the resource bounds overflows: {resource_bounds:?}.")]
+    MaxFeeOverflow { resource_bounds: AllResourceBounds },
+    #[error(
+        "Deploy account transactions must have nonce 0, as they are the first transaction of \
+         their account. Got nonce {}.",
+        nonce.0
+    )]
+    NonZeroDeployAccountNonce { nonce: Nonce },
+    #[error(transparent)]
+    ResourceBounds(#[from] ResourceBoundsError),
     #[error(
         "Signature length exceeded maximum: length {signature_length}
         (allowed length: {max_signature_length})."
@@ -85,24 +90,140 @@ pub enum StatelessTransactionValidatorError {
 
 impl From<StatelessTransactionValidatorError> for GatewaySpecError {
     fn from(e: StatelessTransactionValidatorError) -> Self {
-        match e {
-            StatelessTransactionValidatorError::ContractClassObjectSizeTooLarge { .. } => {
-                GatewaySpecError::ContractClassSizeIsTooLarge
+        let rejection = |code| Rejection::new(code, e.to_string());
+        let rejection = match &e {
+            StatelessTransactionValidatorError::CalldataTooLong {
+                calldata_length,
+                max_calldata_length,
+            } => rejection(RejectionCode::CalldataTooLong)
+                .with("calldata_length", calldata_length)
+                .with("max_calldata_length", max_calldata_length),
+            StatelessTransactionValidatorError::ContractClassObjectSizeTooLarge {
+                contract_class_object_size,
+                max_contract_class_object_size,
+            } => rejection(RejectionCode::ContractClassTooLarge)
+                .with("contract_class_object_size", contract_class_object_size)
+                .with("max_contract_class_object_size", max_contract_class_object_size),
+            StatelessTransactionValidatorError::EntryPointsNotUniquelySorted => {
+                rejection(RejectionCode::EntryPointsNotUniquelySorted)
+            }
+            StatelessTransactionValidatorError::InvalidSierraVersion(..) => {
+                rejection(RejectionCode::InvalidSierraVersion)
+            }
+            StatelessTransactionValidatorError::MaxFeeOverflow { resource_bounds } => {
+                rejection(RejectionCode::MaxFeeOverflow).with("resource_bounds", resource_bounds)
             }
-            StatelessTransactionValidatorError::UnsupportedSierraVersion { .. } => {
-                GatewaySpecError::UnsupportedContractClassVersion
+            StatelessTransactionValidatorError::NonZeroDeployAccountNonce { nonce } => {
+                rejection(RejectionCode::NonZeroDeployAccountNonce).with("nonce", nonce)
             }
-            StatelessTransactionValidatorError::CalldataTooLong { .. }
-            | StatelessTransactionValidatorError::EntryPointsNotUniquelySorted
-            | StatelessTransactionValidatorError::InvalidSierraVersion(..)
-            | StatelessTransactionValidatorError::SignatureTooLong { .. }
-            | StatelessTransactionValidatorError::ZeroResourceBounds { .. } => {
-                GatewaySpecError::ValidationFailure { data: e.to_string() }
+            StatelessTransactionValidatorError::ResourceBounds(error) => {
+                resource_bounds_rejection(rejection, error)
             }
+            StatelessTransactionValidatorError::SignatureTooLong {
+                signature_length,
+                max_signature_length,
+            } => rejection(RejectionCode::SignatureTooLong)
+                .with("signature_length", signature_length)
+                .with("max_signature_length", max_signature_length),
+            StatelessTransactionValidatorError::UnsupportedSierraVersion {
+                version,
+                min_version,
+                max_version,
+            } => rejection(RejectionCode::UnsupportedContractClassVersion)
+                .with("version", version.to_string())
+                .with("min_version", min_version.to_string())
+                .with("max_version", max_version.to_string()),
+            StatelessTransactionValidatorError::ZeroResourceBounds {
+                resource,
+                resource_bounds,
+            } => rejection(RejectionCode::ZeroResourceBounds)
+                .with("resource", resource)
+                .with("resource_bounds", resource_bounds),
+        };
+        GatewaySpecError::InvalidTransaction(rejection)
+    }
+}
+
+fn resource_bounds_rejection(
+    rejection: impl Fn(RejectionCode) -> Rejection,
+    error: &ResourceBoundsError,
+) -> Rejection {
+    match *error {
+        ResourceBoundsError::MaxL1GasPriceTooLow { max_price_per_unit, gas_price } => {
+            rejection(RejectionCode::MaxGasPriceTooLow)
+                .with("resource", Resource::L1Gas)
+                .with("max_price_per_unit", format!("{max_price_per_unit:#x}"))
+                .with("gas_price", format!("{gas_price:#x}"))
+        }
+        ResourceBoundsError::MaxL2GasPriceTooLow { max_price_per_unit, gas_price } => {
+            rejection(RejectionCode::MaxGasPriceTooLow)
+                .with("resource", Resource::L2Gas)
+                .with("max_price_per_unit", format!("{max_price_per_unit:#x}"))
+                .with("gas_price", format!("{gas_price:#x}"))
+        }
+        ResourceBoundsError::MaxFeeTooHigh { tx_max_fee, max_fee } => {
+            rejection(RejectionCode::MaxFeeTooHigh)
+                .with("max_fee", format!("{tx_max_fee:#x}"))
+                .with("max_allowed_fee", format!("{max_fee:#x}"))
         }
+        ResourceBoundsError::ZeroResourceBounds { resource, resource_bounds } => {
+            rejection(RejectionCode::ZeroResourceBounds)
+                .with("resource", resource)
+                .with("resource_bounds", resource_bounds)
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum TransactionTypeGateError {
+    #[error("{tx_type} transactions are currently not accepted by the gateway.")]
+    TransactionTypeDisabled { tx_type: String },
+}
+
+impl From<TransactionTypeGateError> for GatewaySpecError {
+    fn from(e: TransactionTypeGateError) -> Self {
+        let TransactionTypeGateError::TransactionTypeDisabled { tx_type } = &e;
+        GatewaySpecError::InvalidTransaction(
+            Rejection::new(RejectionCode::TransactionTypeDisabled, e.to_string())
+                .with("tx_type", tx_type),
+        )
     }
 }
 
+#[derive(Debug, Error)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum DeclareGatingError {
+    #[error(
+        "Declaring requires a STRK balance of at least {min_balance} fri; sender {:#x} has {balance} \
+         fri.",
+        sender_address.0.key()
+    )]
+    InsufficientBalance { sender_address: ContractAddress, balance: Felt, min_balance: u128 },
+    #[error("Sender {:#x} is not on the declare allow list.", sender_address.0.key())]
+    SenderNotAllowed { sender_address: ContractAddress },
+}
+
+impl From<DeclareGatingError> for GatewaySpecError {
+    fn from(e: DeclareGatingError) -> Self {
+        match e {
+            DeclareGatingError::InsufficientBalance { sender_address, balance, min_balance } => {
+                GatewaySpecError::InvalidTransaction(
+                    Rejection::new(RejectionCode::DeclareBalanceTooLow, e.to_string())
+                        .with("sender_address", sender_address)
+                        .with("balance", balance)
+                        .with("min_balance", format!("{min_balance:#x}")),
+                )
+            }
+            DeclareGatingError::SenderNotAllowed { .. } => GatewaySpecError::DeclareNotAllowed,
+        }
+    }
+}
+
+pub type DeclareGatingResult<T> = Result<T, DeclareGatingError>;
+
+pub type TransactionTypeGateResult<T> = Result<T, TransactionTypeGateError>;
+
 pub type StatelessTransactionValidatorResult<T> = Result<T, StatelessTransactionValidatorError>;
 
 pub type StatefulTransactionValidatorResult<T> = Result<T, GatewaySpecError>;
@@ -153,6 +274,19 @@ pub fn serde_err_to_state_err(err: SerdeError) -> StateError {
     StateError::StateReadError(format!("Failed to parse rpc result {:?}", err.to_string()))
 }
 
+// Not Starknet RPC errors; their codes are the HTTP statuses of the responses.
+const DECLARE_NOT_ALLOWED: JsonRpcError<String> =
+    JsonRpcError { code: 403, message: "The sender is not allowed to declare classes", data: None };
+const SENDER_NOT_ALLOWED: JsonRpcError<String> = JsonRpcError {
+    code: 403,
+    message: "The sender is not allowed to submit transactions",
+    data: None,
+};
+const TOO_MANY_REQUESTS: JsonRpcError<String> =
+    JsonRpcError { code: 429, message: "Too many requests, try again later", data: None };
+const SERVICE_UNAVAILABLE: JsonRpcError<String> =
+    JsonRpcError { code: 503, message: "The gateway is overloaded, try again later", data: None };
+
 /// Error returned by the gateway, adhering to the Starknet RPC error format.
 // To get JsonRpcError from GatewaySpecError, use `into_rpc` method.
 // TODO(yair): papyrus_rpc has a test that the add_tx functions return the correct error. Make sure
@@ -170,16 +304,27 @@ pub enum GatewaySpecError {
     CompilationFailed,
     #[assoc(into_rpc = CONTRACT_CLASS_SIZE_IS_TOO_LARGE)]
     ContractClassSizeIsTooLarge,
+    #[assoc(into_rpc = DECLARE_NOT_ALLOWED)]
+    DeclareNotAllowed,
     #[assoc(into_rpc = DUPLICATE_TX)]
     DuplicateTx,
     #[assoc(into_rpc = INSUFFICIENT_ACCOUNT_BALANCE)]
     InsufficientAccountBalance,
     #[assoc(into_rpc = INSUFFICIENT_MAX_FEE)]
     InsufficientMaxFee,
+    /// A transaction rejected by a validation, with the code and the values of its rejection.
+    #[assoc(into_rpc = _0.into_rpc())]
+    InvalidTransaction(Rejection),
     #[assoc(into_rpc = INVALID_TRANSACTION_NONCE)]
     InvalidTransactionNonce,
     #[assoc(into_rpc = NON_ACCOUNT)]
     NonAccount,
+    #[assoc(into_rpc = SENDER_NOT_ALLOWED)]
+    SenderNotAllowed,
+    #[assoc(into_rpc = SERVICE_UNAVAILABLE)]
+    ServiceUnavailable,
+    #[assoc(into_rpc = TOO_MANY_REQUESTS)]
+    TooManyRequests,
     #[assoc(into_rpc = unexpected_error(_data))]
     UnexpectedError { data: String },
     #[assoc(into_rpc = UNSUPPORTED_CONTRACT_CLASS_VERSION)]
@@ -190,6 +335,22 @@ pub enum GatewaySpecError {
     ValidationFailure { data: String },
 }
 
+impl From<GatewaySpecError> for GatewayError {
+    fn from(error: GatewaySpecError) -> Self {
+        match error {
+            GatewaySpecError::TooManyRequests => return GatewayError::TooManyRequests,
+            GatewaySpecError::ServiceUnavailable => return GatewayError::ServiceUnavailable,
+            _ => {}
+        }
+        let as_rpc = error.into_rpc();
+        GatewayError::TransactionRejected {
+            code: as_rpc.code,
+            message: as_rpc.message.to_owned(),
+            data: as_rpc.data,
+        }
+    }
+}
+
 impl Display for GatewaySpecError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let as_rpc = self.clone().into_rpc();
@@ -1,33 +1,109 @@
 use std::clone::Clone;
-use std::net::SocketAddr;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 use async_trait::async_trait;
-use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State};
+use axum::http::HeaderMap;
+use axum::response::Response;
 use axum::routing::{get, post};
 use axum::{Json, Router};
+use blockifier::execution::contract_class::ClassInfo as BlockifierClassInfo;
+use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
+use futures::future::join_all;
+use papyrus_storage::StorageReader;
+use serde::{Deserialize, Serialize};
+use starknet_api::contract_class::ClassInfo;
+use starknet_api::core::{ChainId, ClassHash, ContractAddress};
 use starknet_api::executable_transaction::Transaction;
-use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
 use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::communication::SharedBatcherClient;
+use starknet_class_manager_types::class_manager_types::Class;
+use starknet_class_manager_types::communication::SharedClassManagerClient;
+use starknet_error_codes::count_error;
+use starknet_gateway_types::gateway_types::{
+    AddTransactionResult,
+    TransactionSource,
+    TransactionStatus,
+    TransactionStatusUpdate,
+};
+#[cfg(feature = "allocation_counting")]
+use starknet_mempool_infra::allocation_counter::AllocationScope;
+use starknet_mempool_infra::component_client::CircuitBreakerConfig;
+use starknet_mempool_infra::component_definitions::ComponentHealth;
+use starknet_mempool_infra::component_error::ComponentError;
 use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
-use starknet_mempool_types::communication::SharedMempoolClient;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_infra::request_context::RequestContext;
+use starknet_mempool_infra::resource_monitor::{ResourceStatus, SharedResourceStatus};
+use starknet_mempool_p2p::sender::SharedMempoolP2pSenderClient;
+use starknet_mempool_types::communication::{
+    MempoolClientError,
+    RemoteMempoolClientImpl,
+    SharedMempoolClient,
+};
+use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput};
+use starknet_mempool_types::resource_bounds::{validate_l2_gas_price, SharedL2GasPrice};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
-use tracing::{error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
-use crate::compilation::GatewayCompiler;
-use crate::config::{GatewayConfig, GatewayNetworkConfig, RpcStateReaderConfig};
-use crate::errors::{GatewayResult, GatewayRunError, GatewaySpecError};
+use crate::api_versions::{parse_tx, ApiVersions};
+use crate::arrival_journal::ArrivalJournal;
+use crate::compilation::{sierra_contract_class, GatewayCompiler};
+use crate::config::{GatewayConfig, GatewayNetworkConfig, RateLimitConfig, RpcStateReaderConfig};
+use crate::declare_gating::DeclareGate;
+use crate::duplicate_detection::{DuplicateDetector, SubmissionKey};
+use crate::errors::{
+    GatewayResult,
+    GatewayRunError,
+    GatewaySpecError,
+    StatelessTransactionValidatorError,
+};
+use crate::idempotency::{IdempotencyCache, IDEMPOTENCY_KEY_HEADER};
+use crate::load_shedding::LoadShedder;
+use crate::metrics::{record_rejected_tx, AddTxFunnel, AddTxStage};
+use crate::papyrus_state_reader::PapyrusStateReaderFactory;
+use crate::pending_state_reader::PendingStateReaderFactory;
+use crate::rate_limiting::RateLimiter;
+use crate::rejections::{mempool_rejection, Rejection};
+use crate::request_logging::{RequestLogger, RequestSummary};
 use crate::rpc_state_reader::RpcStateReaderFactory;
+use crate::sender_access::{SenderAccessControl, SenderAccessListsLoadingError};
+use crate::simulation::{SimulateTransactionsRequest, SimulatedTransaction, TransactionSimulator};
 use crate::state_reader::StateReaderFactory;
-use crate::stateful_transaction_validator::StatefulTransactionValidator;
+use crate::stateful_transaction_validator::{
+    validate_class_and_account_are_new,
+    StatefulTransactionValidator,
+};
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
-use crate::utils::compile_contract_and_build_executable_tx;
+use crate::tenants::{load_tenant_configs, TenantConfig, CHAIN_ID_HEADER};
+use crate::transaction_events::{
+    TransactionEvent,
+    TransactionEventBus,
+    TransactionEventFilter,
+    TransactionEventKind,
+    TransactionEventSubscription,
+};
+use crate::transaction_status::TransactionStatusStore;
+use crate::transaction_type_gate::TransactionTypeGate;
+use crate::utils::build_executable_tx;
 
 #[cfg(test)]
 #[path = "gateway_test.rs"]
 pub mod gateway_test;
 
+/// The number of allocations made while processing a submitted transaction.
+#[cfg(feature = "allocation_counting")]
+pub const ADD_TX_ALLOCATIONS: &str = "gateway_add_tx_allocations";
+
+#[derive(Clone)]
 pub struct Gateway {
     pub config: GatewayConfig,
     app_state: AppState,
@@ -35,21 +111,105 @@ pub struct Gateway {
 
 #[derive(Clone)]
 pub struct AppState {
+    pub arrival_journal: Option<Arc<ArrivalJournal>>,
+    pub idempotency_cache: Option<Arc<IdempotencyCache>>,
+    pub duplicate_detector: Option<Arc<DuplicateDetector>>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub load_shedder: Arc<LoadShedder>,
+    // The maximal number of transactions in a batch submitted to the `add_transactions` endpoint.
+    pub max_batch_size: usize,
+    pub sender_access_control: Option<Arc<SenderAccessControl>>,
+    pub request_logger: Arc<RequestLogger>,
+    // Updated by the node resource guard; requests are shed while the node is under pressure.
+    pub resource_status: SharedResourceStatus,
+    // Updated by the fee market of the batcher; transactions that can't pay the L2 gas price of
+    // the next block are rejected.
+    pub l2_gas_price: SharedL2GasPrice,
+    // Set by operators, e.g., during an incident; submissions are rejected while it is set.
+    pub ingestion_paused: Arc<AtomicBool>,
+    pub transaction_type_gate: Arc<TransactionTypeGate>,
+    pub declare_gate: Arc<DeclareGate>,
+    pub api_versions: ApiVersions,
+    // Transactions are received once the mempool accepts them; their later statuses are reported
+    // by the mempool.
+    pub transaction_status_store: Arc<TransactionStatusStore>,
+    // Pushes the lifecycle events of transactions to the subscribers of the websocket endpoint.
+    pub transaction_event_bus: TransactionEventBus,
+    // Executes transactions without adding them to the mempool, unless simulations are disabled.
+    pub transaction_simulator: Option<Arc<TransactionSimulator>>,
     pub stateless_tx_validator: StatelessTransactionValidator,
     pub stateful_tx_validator: Arc<StatefulTransactionValidator>,
     pub state_reader_factory: Arc<dyn StateReaderFactory>,
     pub gateway_compiler: GatewayCompiler,
     pub mempool_client: SharedMempoolClient,
+    // If set, transactions submitted to this node are broadcast to its peers once the mempool
+    // accepts them.
+    pub mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient>,
+    // If set, the classes of declare transactions are stored once the transactions are validated,
+    // for the block builder to execute the transactions that use them.
+    pub class_manager_client: Option<SharedClassManagerClient>,
+    // The other sequencer backends fronted by the gateway, by chain id.
+    pub tenants: Arc<HashMap<ChainId, AppState>>,
 }
 
-impl Gateway {
+impl AppState {
     pub fn new(
-        config: GatewayConfig,
+        config: &GatewayConfig,
         state_reader_factory: Arc<dyn StateReaderFactory>,
         gateway_compiler: GatewayCompiler,
         mempool_client: SharedMempoolClient,
+        mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient>,
+        resource_status: SharedResourceStatus,
     ) -> Self {
-        let app_state = AppState {
+        let arrival_journal = config.arrival_journal_config.enable.then(|| {
+            Arc::new(
+                ArrivalJournal::open(&config.arrival_journal_config.path)
+                    .expect("Failed to open the arrival journal."),
+            )
+        });
+        let idempotency_cache = config
+            .idempotency_config
+            .enable
+            .then(|| Arc::new(IdempotencyCache::new(&config.idempotency_config)));
+        let duplicate_detector = config
+            .duplicate_detection_config
+            .enable
+            .then(|| Arc::new(DuplicateDetector::new(&config.duplicate_detection_config)));
+        let sender_access_control = config.sender_access_config.enable.then(|| {
+            Arc::new(
+                SenderAccessControl::new(&config.sender_access_config)
+                    .expect("Failed to load the sender access lists."),
+            )
+        });
+        let transaction_simulator = config
+            .simulation_config
+            .enable
+            .then(|| Arc::new(TransactionSimulator::new(&config.simulation_config)));
+        AppState {
+            arrival_journal,
+            idempotency_cache,
+            duplicate_detector,
+            rate_limiter: Arc::new(RateLimiter::new(&config.rate_limit_config)),
+            load_shedder: Arc::new(LoadShedder::new(&config.load_shedding_config)),
+            max_batch_size: config.network_config.max_batch_size,
+            sender_access_control,
+            request_logger: Arc::new(RequestLogger::new(&config.request_logging_config)),
+            resource_status,
+            l2_gas_price: SharedL2GasPrice::default(),
+            ingestion_paused: Arc::new(AtomicBool::new(false)),
+            transaction_type_gate: Arc::new(TransactionTypeGate::new(
+                &config.transaction_type_config,
+            )),
+            declare_gate: Arc::new(
+                DeclareGate::new(&config.declare_gating_config)
+                    .expect("Failed to load the declare allow list."),
+            ),
+            api_versions: ApiVersions::new(&config.api_versions_config),
+            transaction_status_store: Arc::new(TransactionStatusStore::new(
+                &config.transaction_status_config,
+            )),
+            transaction_event_bus: TransactionEventBus::new(&config.transaction_events_config),
+            transaction_simulator,
             stateless_tx_validator: StatelessTransactionValidator {
                 config: config.stateless_tx_validator_config.clone(),
             },
@@ -59,24 +219,216 @@ impl Gateway {
             state_reader_factory,
             gateway_compiler,
             mempool_client,
+            mempool_p2p_sender_client,
+            class_manager_client: None,
+            tenants: Default::default(),
+        }
+    }
+
+    // Routes a submission to the backend of the chain it declares, if it is not this node.
+    fn route(self, headers: &HeaderMap) -> GatewayResult<AppState> {
+        let Some(chain_id) = headers.get(CHAIN_ID_HEADER) else {
+            return Ok(self);
         };
+        let chain_id = chain_id.to_str().map_err(|_| GatewaySpecError::ValidationFailure {
+            data: "The chain id must consist of visible ASCII characters.".to_owned(),
+        })?;
+        let chain_id = ChainId::from(chain_id.to_owned());
+        if chain_id == self.stateful_tx_validator.config.chain_info.chain_id {
+            return Ok(self);
+        }
+        // Transactions are signed over their hash, which depends on the chain id; rejecting them
+        // here is clearer than failing their signature validation.
+        self.tenants.get(&chain_id).cloned().ok_or_else(|| GatewaySpecError::ValidationFailure {
+            data: format!(
+                "Unknown chain id: {chain_id}. The gateway serves chain {}.",
+                self.stateful_tx_validator.config.chain_info.chain_id
+            ),
+        })
+    }
+}
+
+impl Gateway {
+    pub fn new(
+        config: GatewayConfig,
+        state_reader_factory: Arc<dyn StateReaderFactory>,
+        gateway_compiler: GatewayCompiler,
+        mempool_client: SharedMempoolClient,
+        mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient>,
+        resource_status: SharedResourceStatus,
+    ) -> Self {
+        let app_state = AppState::new(
+            &config,
+            state_reader_factory,
+            gateway_compiler,
+            mempool_client,
+            mempool_p2p_sender_client,
+            resource_status,
+        );
         Gateway { config, app_state }
     }
 
+    /// Fronts the given backends of other chains as well, routing submissions by their declared
+    /// chain id.
+    pub fn with_tenants(mut self, mut tenants: HashMap<ChainId, AppState>) -> Self {
+        // The submissions of the tenants are validated by this node, within its limit on
+        // concurrent validations.
+        for tenant in tenants.values_mut() {
+            tenant.load_shedder = self.app_state.load_shedder.clone();
+        }
+        self.app_state.tenants = Arc::new(tenants);
+        self
+    }
+
+    /// Stores the classes of the declare transactions this node validates through the given
+    /// class manager.
+    pub fn with_class_manager_client(
+        mut self,
+        class_manager_client: SharedClassManagerClient,
+    ) -> Self {
+        self.app_state.class_manager_client = Some(class_manager_client);
+        self
+    }
+
+    /// Checks the L2 gas bounds of transactions against the given price, set by the fee market of
+    /// the batcher. Otherwise, they are checked against the minimal gas price.
+    pub fn with_l2_gas_price(mut self, l2_gas_price: SharedL2GasPrice) -> Self {
+        self.app_state.l2_gas_price = l2_gas_price;
+        self
+    }
+
+    /// Validates a transaction that reached the node through the given source and adds it to the
+    /// mempool.
+    pub async fn add_tx(
+        &self,
+        tx: RpcTransaction,
+        source: TransactionSource,
+    ) -> GatewayResult<AddTransactionResult> {
+        process_and_add_tx(self.app_state.clone(), tx, source).await
+    }
+
+    pub fn get_transaction_status(&self, tx_hash: TransactionHash) -> TransactionStatus {
+        self.app_state.transaction_status_store.get(tx_hash)
+    }
+
+    /// Records the status changes of transactions reported by the mempool, i.e., their rejection
+    /// or inclusion in a block.
+    pub fn update_transaction_statuses(&self, updates: Vec<TransactionStatusUpdate>) {
+        if let Some(duplicate_detector) = &self.app_state.duplicate_detector {
+            // Rejected transactions may be resubmitted, e.g., once their sender can pay for them.
+            updates
+                .iter()
+                .filter(|update| update.status == TransactionStatus::Rejected)
+                .for_each(|update| duplicate_detector.forget(update.tx_hash));
+        }
+        if let Some(arrival_journal) = &self.app_state.arrival_journal {
+            // The mempool reports the transactions of a committed block together, in their
+            // inclusion order.
+            let included_tx_hashes: Vec<_> = updates
+                .iter()
+                .filter(|update| update.status == TransactionStatus::Included)
+                .map(|update| update.tx_hash)
+                .collect();
+            if !included_tx_hashes.is_empty() {
+                arrival_journal.record_inclusion(included_tx_hashes);
+            }
+        }
+        let applied_updates = self.app_state.transaction_status_store.update(updates);
+        self.app_state.transaction_event_bus.publish_status_updates(applied_updates);
+    }
+
+    /// Returns the gate controlling which transaction types are accepted, allowing them to be
+    /// toggled at runtime.
+    pub fn transaction_type_gate(&self) -> Arc<TransactionTypeGate> {
+        self.app_state.transaction_type_gate.clone()
+    }
+
+    /// Returns the sender access lists enforced by the gateway, if enabled, allowing them to be
+    /// changed at runtime.
+    pub fn sender_access_control(&self) -> Option<Arc<SenderAccessControl>> {
+        self.app_state.sender_access_control.clone()
+    }
+
+    /// Returns the config of the rate limits of this node, allowing them to be changed at runtime.
+    /// The tenants keep the rate limits they are loaded with.
+    pub fn rate_limit_config(&self) -> SharedDynamicConfig<RateLimitConfig> {
+        self.app_state.rate_limiter.config()
+    }
+
+    /// Pauses or resumes the ingestion of transactions, by this node and by its tenants. While
+    /// paused, submissions are rejected; the transactions already in the mempool are not affected.
+    pub fn set_ingestion_paused(&self, paused: bool) {
+        for app_state in std::iter::once(&self.app_state).chain(self.app_state.tenants.values()) {
+            app_state.ingestion_paused.store(paused, Ordering::Relaxed);
+        }
+        info!(target: "audit", paused, "Set the ingestion of transactions paused.");
+    }
+
+    /// Reads the sender access lists of this node and of its tenants from their files again. The
+    /// lists that can't be read remain in force.
+    pub fn reload_config(&self) -> Result<(), SenderAccessListsLoadingError> {
+        let mut result = Ok(());
+        for app_state in std::iter::once(&self.app_state).chain(self.app_state.tenants.values()) {
+            if let Some(sender_access_control) = &app_state.sender_access_control {
+                if let Err(e) = sender_access_control.reload() {
+                    warn!("Failed to reload the sender access lists: {}", e);
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Compiles the classes listed in the configuration into the class cache, so that the first
+    /// transactions using them after a restart are not delayed by fetching and compiling them.
+    pub async fn warm_up_class_cache(&self) {
+        let class_hashes = self.config.class_cache_config.warm_up_class_hashes.clone();
+        if class_hashes.is_empty() {
+            return;
+        }
+        let state_reader_factory = self.app_state.state_reader_factory.clone();
+        let n_class_hashes = class_hashes.len();
+        match tokio::task::spawn_blocking(move || {
+            load_classes(state_reader_factory.as_ref(), &class_hashes)
+        })
+        .await
+        {
+            Ok(n_loaded_classes) => {
+                info!(
+                    "Warmed up the class cache with {}/{} classes.",
+                    n_loaded_classes, n_class_hashes
+                )
+            }
+            Err(join_err) => error!("Failed to warm up the class cache: {}", join_err),
+        }
+    }
+
     pub async fn run(&mut self) -> Result<(), GatewayRunError> {
         // Parses the bind address from GatewayConfig, returning an error for invalid addresses.
-        let GatewayNetworkConfig { ip, port } = self.config.network_config;
+        let GatewayNetworkConfig { ip, port, .. } = self.config.network_config;
         let addr = SocketAddr::new(ip, port);
         let app = self.app();
 
         // Create a server that runs forever.
-        Ok(axum::Server::bind(&addr).serve(app.into_make_service()).await?)
+        // The address of the client is used to rate limit its submissions.
+        Ok(axum::Server::bind(&addr)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+            .await?)
     }
 
     pub fn app(&self) -> Router {
         Router::new()
             .route("/is_alive", get(is_alive))
             .route("/add_tx", post(add_tx))
+            .route("/gateway/add_transaction", post(add_transaction))
+            .route("/gateway/add_transactions", post(add_transactions))
+            .route("/gateway/:version/add_transaction", post(add_versioned_transaction))
+            .route("/gateway/:version/add_transactions", post(add_versioned_transactions))
+            .route("/gateway/simulate_transactions", post(simulate_transactions))
+            .route("/gateway/get_transaction_status", get(get_transaction_status))
+            .route("/gateway/subscribe_transaction_events", get(subscribe_transaction_events))
+            .route("/resource_status", get(resource_status))
+            .layer(DefaultBodyLimit::max(self.config.network_config.max_request_body_size))
             .with_state(self.app_state.clone())
     }
 }
@@ -89,17 +441,466 @@ async fn is_alive() -> GatewayResult<String> {
 }
 
 #[instrument(skip(app_state))]
+async fn resource_status(State(app_state): State<AppState>) -> Json<ResourceStatus> {
+    Json(app_state.resource_status.get())
+}
+
+#[instrument(skip(app_state, headers))]
 async fn add_tx(
     State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(tx): Json<RpcTransaction>,
 ) -> GatewayResult<Json<TransactionHash>> {
-    let mempool_input = tokio::task::spawn_blocking(move || {
+    let client_ip = connect_info.map(|ConnectInfo(address)| address.ip());
+    let result = logged_process_and_add_tx("add_tx", app_state, client_ip, &headers, tx).await?;
+    Ok(Json(result.tx_hash()))
+}
+
+/// The response of the `add_transaction` endpoint, in the format of the Starknet gateway.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddTransactionResponse {
+    pub code: String,
+    pub transaction_hash: TransactionHash,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<ContractAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<ClassHash>,
+}
+
+impl From<AddTransactionResult> for AddTransactionResponse {
+    fn from(result: AddTransactionResult) -> Self {
+        let transaction_hash = result.tx_hash();
+        let (address, class_hash) = match result {
+            AddTransactionResult::Invoke { .. } => (None, None),
+            AddTransactionResult::DeployAccount { address, .. } => (Some(address), None),
+            AddTransactionResult::Declare { class_hash, .. } => (None, Some(class_hash)),
+        };
+        Self { code: "TRANSACTION_RECEIVED".to_owned(), transaction_hash, address, class_hash }
+    }
+}
+
+/// Accepts transactions in the format of the Starknet gateway API, answering with the address of
+/// a deployed account or the hash of a declared class in addition to the transaction hash.
+#[instrument(skip(app_state, headers))]
+async fn add_transaction(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(tx): Json<RpcTransaction>,
+) -> GatewayResult<Json<AddTransactionResponse>> {
+    let client_ip = connect_info.map(|ConnectInfo(address)| address.ip());
+    let result =
+        logged_process_and_add_tx("add_transaction", app_state, client_ip, &headers, tx).await?;
+    Ok(Json(result.into()))
+}
+
+/// Accepts transactions in the given version of the transaction format, e.g.,
+/// `/gateway/v0.7/add_transaction`, as the `add_transaction` endpoint does. Answers with the
+/// version the request was served in, and whether the version is deprecated.
+#[instrument(skip(app_state, headers, tx))]
+async fn add_versioned_transaction(
+    State(app_state): State<AppState>,
+    Path(version): Path<String>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(tx): Json<serde_json::Value>,
+) -> GatewayResult<(HeaderMap, Json<AddTransactionResponse>)> {
+    let api_versions = app_state.api_versions.clone();
+    let version = api_versions.negotiate(&version)?;
+    let tx = parse_tx(version, tx)?;
+    let response = add_transaction(State(app_state), connect_info, headers, Json(tx)).await?;
+    Ok((api_versions.response_headers(version), response))
+}
+
+/// The versioned counterpart of the `add_transactions` endpoint. A batch with a transaction that
+/// doesn't fit the version is rejected as a whole.
+#[instrument(skip(app_state, headers, txs))]
+async fn add_versioned_transactions(
+    State(app_state): State<AppState>,
+    Path(version): Path<String>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(txs): Json<Vec<serde_json::Value>>,
+) -> GatewayResult<(HeaderMap, Json<Vec<AddTransactionsEntry>>)> {
+    let api_versions = app_state.api_versions.clone();
+    let version = api_versions.negotiate(&version)?;
+    let txs = txs.into_iter().map(|tx| parse_tx(version, tx)).collect::<GatewayResult<_>>()?;
+    let response = add_transactions(State(app_state), connect_info, headers, Json(txs)).await?;
+    Ok((api_versions.response_headers(version), response))
+}
+
+/// The result of a transaction of a batch submitted to the `add_transactions` endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AddTransactionsEntry {
+    Received(AddTransactionResponse),
+    // As answered for a single transaction.
+    Rejected(Rejection),
+}
+
+impl From<GatewayResult<AddTransactionResult>> for AddTransactionsEntry {
+    fn from(result: GatewayResult<AddTransactionResult>) -> Self {
+        match result {
+            Ok(result) => AddTransactionsEntry::Received(result.into()),
+            Err(error) => AddTransactionsEntry::Rejected(error.into()),
+        }
+    }
+}
+
+/// Accepts a batch of transactions in the format of the Starknet gateway API, sparing clients
+/// that submit many transactions a round trip per transaction. Answers with the result of each
+/// transaction, in the order of the batch; a rejected transaction doesn't affect the others.
+#[instrument(skip(app_state, headers, txs))]
+async fn add_transactions(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    mut headers: HeaderMap,
+    Json(txs): Json<Vec<RpcTransaction>>,
+) -> GatewayResult<Json<Vec<AddTransactionsEntry>>> {
+    if txs.len() > app_state.max_batch_size {
+        return Err(GatewaySpecError::ValidationFailure {
+            data: format!(
+                "The batch has {} transactions; at most {} transactions are accepted in a batch.",
+                txs.len(),
+                app_state.max_batch_size
+            ),
+        });
+    }
+    let client_ip = connect_info.map(|ConnectInfo(address)| address.ip());
+    // An idempotency key identifies a single submission, so it doesn't apply to the transactions
+    // of a batch.
+    headers.remove(IDEMPOTENCY_KEY_HEADER);
+
+    // The transactions of a sender are added in the order of the batch, as their nonces follow
+    // each other; the transactions of different senders are validated concurrently.
+    let mut txs_by_sender: HashMap<Option<ContractAddress>, Vec<(usize, RpcTransaction)>> =
+        HashMap::new();
+    for (index, tx) in txs.into_iter().enumerate() {
+        let sender_address = tx.calculate_sender_address().ok();
+        txs_by_sender.entry(sender_address).or_default().push((index, tx));
+    }
+    let sender_results = join_all(txs_by_sender.into_values().map(|sender_txs| {
+        let app_state = app_state.clone();
+        let headers = &headers;
+        async move {
+            let mut results = Vec::with_capacity(sender_txs.len());
+            for (index, tx) in sender_txs {
+                let result = logged_process_and_add_tx(
+                    "add_transactions",
+                    app_state.clone(),
+                    client_ip,
+                    headers,
+                    tx,
+                )
+                .await;
+                results.push((index, AddTransactionsEntry::from(result)));
+            }
+            results
+        }
+    }))
+    .await;
+
+    let mut entries: Vec<_> = sender_results.into_iter().flatten().collect();
+    entries.sort_by_key(|(index, _)| *index);
+    Ok(Json(entries.into_iter().map(|(_, entry)| entry).collect()))
+}
+
+/// Executes a batch of transactions on top of the state of a block, without adding them to the
+/// mempool, answering with their execution traces, events and state diffs.
+#[instrument(skip(app_state, headers, request))]
+async fn simulate_transactions(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(request): Json<SimulateTransactionsRequest>,
+) -> GatewayResult<Json<Vec<SimulatedTransaction>>> {
+    // Simulations are at least as expensive as submissions, and are limited alike.
+    if let Some(ConnectInfo(address)) = connect_info {
+        app_state.rate_limiter.check_ip(address.ip())?;
+    }
+    let app_state = app_state.route(&headers)?;
+    if app_state.resource_status.is_under_pressure() {
+        return Err(GatewaySpecError::UnexpectedError {
+            data: "The node is under resource pressure, try again later.".to_owned(),
+        });
+    }
+    let transaction_simulator = app_state.transaction_simulator.clone().ok_or_else(|| {
+        GatewaySpecError::ValidationFailure {
+            data: "Transaction simulation is disabled.".to_owned(),
+        }
+    })?;
+    let SimulateTransactionsRequest { transactions, block_number, simulation_flags } = request;
+    transaction_simulator.validate_n_transactions(transactions.len())?;
+
+    let mut txs = Vec::with_capacity(transactions.len());
+    for tx in transactions {
+        app_state.stateless_tx_validator.validate(&tx)?;
+        let optional_class_info = match &tx {
+            RpcTransaction::Declare(declare_tx) => {
+                let class_info =
+                    app_state.gateway_compiler.process_declare_tx_async(declare_tx.clone()).await?;
+                let executable_tx = build_executable_tx(
+                    tx.clone(),
+                    Some(class_info),
+                    &app_state.stateful_tx_validator.config.chain_info.chain_id,
+                )?;
+                compiled_class_info(executable_tx)?
+            }
+            _ => None,
+        };
+        txs.push((tx, optional_class_info));
+    }
+
+    let simulated_txs = tokio::task::spawn_blocking(move || {
+        transaction_simulator.simulate(
+            app_state.stateful_tx_validator.as_ref(),
+            app_state.state_reader_factory.as_ref(),
+            txs,
+            block_number,
+            &simulation_flags,
+        )
+    })
+    .await
+    .map_err(|join_err| {
+        error!("Failed to simulate transactions: {}", join_err);
+        GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+    })??;
+    Ok(Json(simulated_txs))
+}
+
+/// The query of the `get_transaction_status` endpoint, in the format of the Starknet gateway.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusQuery {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: TransactionHash,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusResponse {
+    pub tx_status: TransactionStatus,
+}
+
+#[instrument(skip(app_state))]
+async fn get_transaction_status(
+    State(app_state): State<AppState>,
+    Query(query): Query<TransactionStatusQuery>,
+) -> Json<TransactionStatusResponse> {
+    Json(TransactionStatusResponse {
+        tx_status: app_state.transaction_status_store.get(query.transaction_hash),
+    })
+}
+
+/// Subscribes to the lifecycle events of a transaction or of the transactions of a sender, which
+/// are pushed over a websocket as JSON messages.
+#[instrument(skip(app_state, ws))]
+async fn subscribe_transaction_events(
+    State(app_state): State<AppState>,
+    Query(filter): Query<TransactionEventFilter>,
+    ws: WebSocketUpgrade,
+) -> GatewayResult<Response> {
+    if filter.is_empty() {
+        return Err(GatewaySpecError::ValidationFailure {
+            data: "Subscriptions must specify a transaction hash or a sender address.".to_owned(),
+        });
+    }
+    // Subscribed before the upgrade, so that no event following the response is missed.
+    let subscription = app_state.transaction_event_bus.subscribe(filter);
+    Ok(ws.on_upgrade(move |socket| push_transaction_events(socket, subscription)))
+}
+
+// Pushes the events of the subscription until the client closes the websocket, or falls behind.
+async fn push_transaction_events(
+    mut socket: WebSocket,
+    mut subscription: TransactionEventSubscription,
+) {
+    loop {
+        tokio::select! {
+            event = subscription.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let message =
+                    serde_json::to_string(&event).expect("Transaction events are serializable.");
+                if socket.send(Message::Text(message)).await.is_err() {
+                    break;
+                }
+            }
+            // Messages of the client other than closing the websocket are ignored.
+            message = socket.recv() => {
+                if matches!(message, None | Some(Err(_)) | Some(Ok(Message::Close(_)))) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn logged_process_and_add_tx(
+    method: &str,
+    app_state: AppState,
+    client_ip: Option<IpAddr>,
+    headers: &HeaderMap,
+    tx: RpcTransaction,
+) -> GatewayResult<AddTransactionResult> {
+    #[cfg(feature = "allocation_counting")]
+    let allocation_scope = AllocationScope::start();
+    let start = Instant::now();
+    let summary = RequestSummary::new(&tx);
+    let request_logger = app_state.request_logger.clone();
+
+    // Submissions start a flow of component requests, followed in the logs by its correlation id.
+    let result = RequestContext::new("gateway")
+        .scope(idempotent_process_and_add_tx(app_state, client_ip, headers, tx))
+        .await;
+    request_logger.log(
+        method,
+        &summary,
+        &result.as_ref().map(AddTransactionResult::tx_hash).map_err(Clone::clone),
+        start.elapsed(),
+    );
+    #[cfg(feature = "allocation_counting")]
+    allocation_scope.record(ADD_TX_ALLOCATIONS);
+    result
+}
+
+// Submissions with an idempotency key are processed once per sender and key; resubmissions of the
+// transaction with the same key get the result of the first submission.
+async fn idempotent_process_and_add_tx(
+    app_state: AppState,
+    client_ip: Option<IpAddr>,
+    headers: &HeaderMap,
+    tx: RpcTransaction,
+) -> GatewayResult<AddTransactionResult> {
+    // Clients are limited by the gateway they submit to, whichever chain they submit to.
+    if let Some(client_ip) = client_ip {
+        app_state.rate_limiter.check_ip(client_ip).inspect_err(|_| {
+            record_rejected_tx(source_name(TransactionSource::Http), "rate_limited");
+        })?;
+    }
+    let app_state = app_state.route(headers)?;
+    let Some(idempotency_key) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return process_and_add_tx(app_state, tx, TransactionSource::Http).await;
+    };
+    let Some(idempotency_cache) = app_state.idempotency_cache.clone() else {
+        return process_and_add_tx(app_state, tx, TransactionSource::Http).await;
+    };
+    let idempotency_key =
+        idempotency_key.to_str().map_err(|_| GatewaySpecError::ValidationFailure {
+            data: "The idempotency key must consist of visible ASCII characters.".to_owned(),
+        })?;
+    let submission_key =
+        SubmissionKey::new(&tx, &app_state.stateful_tx_validator.config.chain_info.chain_id)?;
+    idempotency_cache
+        .get_or_process(
+            &submission_key,
+            idempotency_key,
+            process_and_add_tx(app_state, tx, TransactionSource::Http),
+        )
+        .await
+}
+
+async fn process_and_add_tx(
+    app_state: AppState,
+    tx: RpcTransaction,
+    source: TransactionSource,
+) -> GatewayResult<AddTransactionResult> {
+    let mut funnel = AddTxFunnel::start(source_name(source));
+    let result = validate_and_add_tx(app_state, tx, source, &mut funnel).await;
+    funnel.finish(&result);
+    result
+}
+
+async fn validate_and_add_tx(
+    app_state: AppState,
+    tx: RpcTransaction,
+    source: TransactionSource,
+    funnel: &mut AddTxFunnel,
+) -> GatewayResult<AddTransactionResult> {
+    let arrival_time = SystemTime::now();
+    if app_state.resource_status.is_under_pressure() {
+        return Err(GatewaySpecError::UnexpectedError {
+            data: "The node is under resource pressure, try again later.".to_owned(),
+        });
+    }
+    if app_state.ingestion_paused.load(Ordering::Relaxed) {
+        return Err(GatewaySpecError::UnexpectedError {
+            data: "The gateway is not accepting transactions, try again later.".to_owned(),
+        });
+    }
+    // Held until the transaction is added to the mempool or rejected.
+    let _validation_permit = app_state.load_shedder.try_acquire()?;
+    app_state.transaction_type_gate.validate(&tx)?;
+    // Stateless validations are cheap, and reject malformed classes before they are compiled.
+    funnel.enter(AddTxStage::StatelessValidation);
+    app_state.stateless_tx_validator.validate(&tx)?;
+    validate_l2_gas_price(tx.resource_bounds().l2_gas, app_state.l2_gas_price.get())
+        .map_err(StatelessTransactionValidatorError::from)?;
+    // Senders are limited whether their transactions are submitted to this node or to its peers.
+    funnel.enter(AddTxStage::SenderChecks);
+    let sender_address = tx.calculate_sender_address().map_err(|e| {
+        error!("Failed to calculate the sender address of a transaction: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+    })?;
+    if let Some(sender_access_control) = &app_state.sender_access_control {
+        sender_access_control.validate(sender_address)?;
+    }
+    app_state.rate_limiter.check_sender(sender_address)?;
+    if let RpcTransaction::Declare(_) = &tx {
+        app_state.declare_gate.validate_sender(sender_address)?;
+        app_state.rate_limiter.check_declare_sender(sender_address)?;
+    }
+
+    // Resubmissions are rejected before the class of a declare transaction is compiled.
+    funnel.enter(AddTxStage::DuplicateDetection);
+    let submission_key = match &app_state.duplicate_detector {
+        Some(duplicate_detector) => {
+            let submission_key = SubmissionKey::new(
+                &tx,
+                &app_state.stateful_tx_validator.config.chain_info.chain_id,
+            )?;
+            duplicate_detector.check(&submission_key)?;
+            Some(submission_key)
+        }
+        None => None,
+    };
+
+    funnel.enter(AddTxStage::Compilation);
+    let optional_class_info = match &tx {
+        RpcTransaction::Declare(declare_tx) => {
+            Some(app_state.gateway_compiler.process_declare_tx_async(declare_tx.clone()).await?)
+        }
+        _ => None,
+    };
+    // The class is stored only once the transaction is validated, so that invalid declarations
+    // don't fill the class storage.
+    let declared_class = match (&tx, &optional_class_info, &app_state.class_manager_client) {
+        (
+            RpcTransaction::Declare(RpcDeclareTransaction::V3(declare_tx)),
+            Some(class_info),
+            Some(_),
+        ) => Some(Class {
+            sierra: sierra_contract_class(&declare_tx.contract_class),
+            casm: class_info.casm_contract_class.clone(),
+        }),
+        _ => None,
+    };
+
+    // Transactions received from peers are propagated by the mempool p2p receiver.
+    let mempool_p2p_sender_client = match source {
+        TransactionSource::Http => app_state.mempool_p2p_sender_client.clone(),
+        TransactionSource::P2p => None,
+    };
+    let propagated_tx = mempool_p2p_sender_client.as_ref().map(|_| tx.clone());
+
+    funnel.enter(AddTxStage::StatefulValidation);
+    let (mempool_input, add_tx_result) = tokio::task::spawn_blocking(move || {
         process_tx(
-            app_state.stateless_tx_validator,
             app_state.stateful_tx_validator.as_ref(),
             app_state.state_reader_factory.as_ref(),
-            app_state.gateway_compiler,
+            app_state.declare_gate.as_ref(),
             tx,
+            optional_class_info,
         )
     })
     .await
@@ -108,53 +909,125 @@ async fn add_tx(
         GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
     })??;
 
-    let tx_hash = mempool_input.tx.tx_hash();
+    funnel.enter(AddTxStage::ClassStorage);
+    if let (
+        AddTransactionResult::Declare { class_hash, .. },
+        Some(class),
+        Some(class_manager_client),
+    ) = (&add_tx_result, declared_class, &app_state.class_manager_client)
+    {
+        // A transaction whose class isn't stored can't be executed, so it isn't added to the
+        // mempool.
+        class_manager_client.add_class(*class_hash, class).await.map_err(|e| {
+            count_error(&e);
+            error!("Failed to store class {}: {}", class_hash, e);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        })?;
+    }
+
+    let tx_hash = add_tx_result.tx_hash();
+    let publish_event = |kind| {
+        app_state.transaction_event_bus.publish(TransactionEvent {
+            tx_hash,
+            sender_address: Some(sender_address),
+            kind,
+        })
+    };
+    publish_event(TransactionEventKind::Accepted);
 
+    funnel.enter(AddTxStage::MempoolAddition);
     app_state.mempool_client.add_tx(mempool_input).await.map_err(|e| {
-        error!("Failed to send tx to mempool: {}", e);
-        GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        count_error(&e);
+        match e {
+            MempoolClientError::MempoolError(
+                mempool_error @ (MempoolError::MempoolFull { .. }
+                | MempoolError::NonceTooFarInFuture { .. }
+                | MempoolError::ReplacementTipTooLow { .. }
+                | MempoolError::TooManyPendingTransactions { .. }),
+            ) => {
+                publish_event(TransactionEventKind::Rejected { reason: mempool_error.to_string() });
+                GatewaySpecError::InvalidTransaction(mempool_rejection(&mempool_error))
+            }
+            // E.g., the mempool is briefly unreachable; the transaction may be resubmitted.
+            e if e.is_retriable() => {
+                warn!(
+                    "Failed to send tx to the {}, the tx can be resubmitted: {}",
+                    e.component(),
+                    e
+                );
+                GatewaySpecError::ServiceUnavailable
+            }
+            _ => {
+                error!("Failed to send tx to mempool: {}", e);
+                GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+            }
+        }
     })?;
-    // TODO: Also return `ContractAddress` for deploy and `ClassHash` for Declare.
-    Ok(Json(tx_hash))
+
+    funnel.enter(AddTxStage::Propagation);
+    let applied_updates = app_state.transaction_status_store.update([TransactionStatusUpdate {
+        tx_hash,
+        status: TransactionStatus::Received,
+        sender_address: Some(sender_address),
+        reason: None,
+    }]);
+    app_state.transaction_event_bus.publish_status_updates(applied_updates);
+    if let (Some(duplicate_detector), Some(submission_key)) =
+        (&app_state.duplicate_detector, submission_key)
+    {
+        duplicate_detector.record(submission_key);
+    }
+    if let Some(arrival_journal) = &app_state.arrival_journal {
+        arrival_journal.record_arrival(tx_hash, arrival_time, source_name(source));
+    }
+    if let (Some(mempool_p2p_sender_client), Some(tx)) = (mempool_p2p_sender_client, propagated_tx)
+    {
+        // The transaction is already in the mempool; failing to broadcast it only delays its
+        // arrival at the peers until it is sequenced.
+        if let Err(e) = mempool_p2p_sender_client.add_transaction(tx).await {
+            debug!("Failed to broadcast transaction {} to peers: {}", tx_hash, e);
+        }
+    }
+    Ok(add_tx_result)
+}
+
+fn source_name(source: TransactionSource) -> &'static str {
+    match source {
+        TransactionSource::Http => "http",
+        TransactionSource::P2p => "p2p",
+    }
 }
 
+// Runs the stateful validations of a transaction that passed the stateless ones. The class of a
+// declare transaction is given already compiled.
 fn process_tx(
-    stateless_tx_validator: StatelessTransactionValidator,
     stateful_tx_validator: &StatefulTransactionValidator,
     state_reader_factory: &dyn StateReaderFactory,
-    gateway_compiler: GatewayCompiler,
+    declare_gate: &DeclareGate,
     tx: RpcTransaction,
-) -> GatewayResult<MempoolInput> {
+    optional_class_info: Option<ClassInfo>,
+) -> GatewayResult<(MempoolInput, AddTransactionResult)> {
     // TODO(Arni, 1/5/2024): Perform congestion control.
 
-    // Perform stateless validations.
-    stateless_tx_validator.validate(&tx)?;
-
     // TODO(Arni): remove copy_of_rpc_tx and use executable_tx directly as the mempool input.
     let copy_of_rpc_tx = tx.clone();
-    let executable_tx = compile_contract_and_build_executable_tx(
+    let executable_tx = build_executable_tx(
         tx,
-        &gateway_compiler,
+        optional_class_info,
         &stateful_tx_validator.config.chain_info.chain_id,
     )?;
 
-    // Perfom post compilation validations.
-    if let Transaction::Declare(executable_declare_tx) = &executable_tx {
-        if !executable_declare_tx.validate_compiled_class_hash() {
-            return Err(GatewaySpecError::CompiledClassHashMismatch);
-        }
+    let add_tx_result = AddTransactionResult::from(&executable_tx);
+    let optional_class_info = compiled_class_info(executable_tx)?;
+    validate_class_and_account_are_new(state_reader_factory, &add_tx_result)?;
+    if let RpcTransaction::Declare(RpcDeclareTransaction::V3(declare_tx)) = &copy_of_rpc_tx {
+        declare_gate.validate_balance(
+            state_reader_factory,
+            &stateful_tx_validator.config.chain_info,
+            declare_tx.sender_address,
+        )?;
     }
 
-    let optional_class_info = match executable_tx {
-        starknet_api::executable_transaction::Transaction::Declare(tx) => {
-            Some(tx.class_info.try_into().map_err(|e| {
-                error!("Failed to convert Starknet API ClassInfo to Blockifier ClassInfo: {:?}", e);
-                GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
-            })?)
-        }
-        _ => None,
-    };
-
     let validator = stateful_tx_validator.instantiate_validator(state_reader_factory)?;
     // TODO(Yael 31/7/24): refactor after IntrnalTransaction is ready, delete validate_info and
     // compute all the info outside of run_validate.
@@ -162,7 +1035,7 @@ fn process_tx(
         stateful_tx_validator.run_validate(&copy_of_rpc_tx, optional_class_info, validator)?;
 
     // TODO(Arni): Add the Sierra and the Casm to the mempool input.
-    Ok(MempoolInput {
+    let mempool_input = MempoolInput {
         tx: Transaction::new_from_rpc_tx(
             copy_of_rpc_tx,
             validate_info.tx_hash,
@@ -172,25 +1045,182 @@ fn process_tx(
             sender_address: validate_info.sender_address,
             state: AccountState { nonce: validate_info.account_nonce },
         },
-    })
+        eligible_at: None,
+    };
+    Ok((mempool_input, add_tx_result))
 }
 
+// Returns the class of a declare transaction, as executed by the blockifier, after checking that it
+// was compiled to the class the transaction commits to.
+fn compiled_class_info(executable_tx: Transaction) -> GatewayResult<Option<BlockifierClassInfo>> {
+    // Perfom post compilation validations.
+    if let Transaction::Declare(executable_declare_tx) = &executable_tx {
+        if !executable_declare_tx.validate_compiled_class_hash() {
+            return Err(GatewaySpecError::CompiledClassHashMismatch);
+        }
+    }
+
+    let optional_class_info = match executable_tx {
+        Transaction::Declare(tx) => Some(tx.class_info.try_into().map_err(|e| {
+            error!("Failed to convert Starknet API ClassInfo to Blockifier ClassInfo: {:?}", e);
+            GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+        })?),
+        _ => None,
+    };
+    Ok(optional_class_info)
+}
+
+// Reads the given classes from the latest state, which caches them. Returns the number of classes
+// that were read successfully.
+fn load_classes(
+    state_reader_factory: &dyn StateReaderFactory,
+    class_hashes: &[ClassHash],
+) -> usize {
+    let state_reader = state_reader_factory.get_state_reader_from_latest_block();
+    class_hashes
+        .iter()
+        .filter(|&&class_hash| match state_reader.get_compiled_contract_class(class_hash) {
+            Ok(_) => true,
+            Err(err) => {
+                warn!("Failed to load class {} into the class cache: {}", class_hash, err);
+                false
+            }
+        })
+        .count()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_gateway(
     config: GatewayConfig,
     rpc_state_reader_config: RpcStateReaderConfig,
     compiler_config: SierraToCasmCompilationConfig,
     mempool_client: SharedMempoolClient,
+    mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient>,
+    resource_status: SharedResourceStatus,
+    l2_gas_price: SharedL2GasPrice,
+    class_manager_client: Option<SharedClassManagerClient>,
+    batcher_client: Option<SharedBatcherClient>,
+    storage_reader: Option<StorageReader>,
 ) -> Gateway {
-    let state_reader_factory = Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config });
-    let gateway_compiler = GatewayCompiler::new_command_line_compiler(compiler_config);
+    // The committed state is read from the node's storage if it has one, and from the RPC server
+    // otherwise.
+    let mut state_reader_factory: Arc<dyn StateReaderFactory> = match storage_reader {
+        Some(storage_reader) => Arc::new(PapyrusStateReaderFactory {
+            storage_reader,
+            class_manager_client: class_manager_client.clone(),
+        }),
+        None => {
+            let class_cache = GlobalContractCache::new(config.class_cache_config.cache_size);
+            Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config, class_cache })
+        }
+    };
+    // Transactions are validated on top of the block being built, if the batcher is reachable.
+    if let Some(batcher_client) = batcher_client {
+        state_reader_factory = Arc::new(PendingStateReaderFactory {
+            committed_state_reader_factory: state_reader_factory,
+            batcher_client,
+        });
+    }
+    let gateway_compiler = GatewayCompiler::new_command_line_compiler(compiler_config)
+        .with_compiled_class_cache(config.compiled_class_cache_config.cache_size);
 
-    Gateway::new(config, state_reader_factory, gateway_compiler, mempool_client)
+    let mut tenants = HashMap::new();
+    if config.tenants_config.enable {
+        let tenant_configs = load_tenant_configs(
+            &config.tenants_config.path,
+            &config.stateful_tx_validator_config.chain_info.chain_id,
+        )
+        .expect("Failed to load the tenants.");
+        for tenant in tenant_configs {
+            let chain_id = tenant.chain_id.clone();
+            let app_state =
+                create_tenant_app_state(tenant, &config, &gateway_compiler, &resource_status);
+            tenants.insert(chain_id, app_state);
+        }
+    }
+
+    let gateway = Gateway::new(
+        config,
+        state_reader_factory,
+        gateway_compiler,
+        mempool_client,
+        mempool_p2p_sender_client,
+        resource_status,
+    )
+    .with_tenants(tenants)
+    .with_l2_gas_price(l2_gas_price);
+    match class_manager_client {
+        Some(class_manager_client) => gateway.with_class_manager_client(class_manager_client),
+        None => gateway,
+    }
+}
+
+// Tenants share the compiler and the resource status of this node, and are not broadcast to its
+// peers.
+fn create_tenant_app_state(
+    tenant: TenantConfig,
+    config: &GatewayConfig,
+    gateway_compiler: &GatewayCompiler,
+    resource_status: &SharedResourceStatus,
+) -> AppState {
+    let config = tenant.gateway_config(config);
+    let class_cache = GlobalContractCache::new(config.class_cache_config.cache_size);
+    let state_reader_factory =
+        Arc::new(RpcStateReaderFactory { config: tenant.rpc_state_reader_config, class_cache });
+    // Transactions of a tenant whose mempool is down are rejected without waiting on it.
+    let mempool_client = RemoteMempoolClientImpl::builder(&tenant.mempool_url)
+        .serialization_format(tenant.mempool_serialization_format)
+        .circuit_breaker_config(CircuitBreakerConfig::default())
+        .build()
+        .unwrap_or_else(|e| {
+            panic!("Failed to create the mempool client of {}: {e}", tenant.chain_id)
+        });
+    AppState::new(
+        &config,
+        state_reader_factory,
+        gateway_compiler.clone(),
+        Arc::new(mempool_client),
+        None,
+        resource_status.clone(),
+    )
+}
+
+// The gateway is taken out of rotation while the node sheds load.
+#[async_trait]
+impl ComponentHealth for Gateway {
+    async fn is_ready(&self) -> bool {
+        !self.app_state.resource_status.is_under_pressure()
+    }
+}
+
+// Reads the sender access lists from their file periodically, so that operators can change them
+// without restarting the node.
+async fn reload_sender_access_lists(
+    sender_access_control: Arc<SenderAccessControl>,
+    reload_interval: Duration,
+) {
+    let mut interval = tokio::time::interval(reload_interval);
+    // The lists were read when the gateway was created.
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = sender_access_control.reload() {
+            warn!("Failed to reload the sender access lists, keeping the current ones: {}", e);
+        }
+    }
 }
 
 #[async_trait]
 impl ComponentStarter for Gateway {
     async fn start(&mut self) -> Result<(), ComponentStartError> {
         info!("Gateway::start()");
+        let reload_interval = self.config.sender_access_config.reload_interval;
+        if let Some(sender_access_control) = self.sender_access_control() {
+            if !reload_interval.is_zero() {
+                tokio::spawn(reload_sender_access_lists(sender_access_control, reload_interval));
+            }
+        }
+        self.warm_up_class_cache().await;
         self.run().await.map_err(|_| ComponentStartError::InternalComponentError)
     }
 }
@@ -1,28 +1,87 @@
+use std::collections::HashMap;
+use std::num::NonZeroU128;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
 use axum::body::{Bytes, HttpBody};
-use axum::extract::State;
-use axum::http::StatusCode;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
+use axum::Json;
 use blockifier::context::ChainInfo;
+use blockifier::test_utils::contracts::FeatureContract;
 use blockifier::test_utils::CairoVersion;
 use mempool_test_utils::starknet_api_test_utils::{create_executable_tx, declare_tx, invoke_tx};
 use mockall::predicate::eq;
-use starknet_api::core::{CompiledClassHash, ContractAddress};
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress};
 use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
 use starknet_api::transaction::{TransactionHash, ValidResourceBounds};
+use starknet_api::{class_hash, felt};
+use starknet_gateway_types::gateway_types::{TransactionSource, TransactionStatus};
+use starknet_mempool_infra::resource_monitor::{
+    ResourcePressure,
+    ResourceStatus,
+    SharedResourceStatus,
+};
+use starknet_mempool_p2p::sender::MockMempoolP2pSenderClient;
 use starknet_mempool_types::communication::MockMempoolClient;
 use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput};
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 
+use crate::api_versions::{ApiVersions, API_VERSION_HEADER, DEPRECATION_HEADER};
 use crate::compilation::GatewayCompiler;
-use crate::config::{StatefulTransactionValidatorConfig, StatelessTransactionValidatorConfig};
+use crate::config::{
+    ApiVersionConfig,
+    ApiVersionsConfig,
+    DuplicateDetectionConfig,
+    IdempotencyConfig,
+    LoadSheddingConfig,
+    RateLimitConfig,
+    RequestLoggingConfig,
+    SenderAccessConfig,
+    SimulationConfig,
+    StatefulTransactionValidatorConfig,
+    StatelessTransactionValidatorConfig,
+    TokenBucketConfig,
+    TransactionEventsConfig,
+    TransactionStatusConfig,
+    TransactionTypeConfig,
+};
+use crate::declare_gating::DeclareGate;
+use crate::duplicate_detection::DuplicateDetector;
 use crate::errors::GatewaySpecError;
-use crate::gateway::{add_tx, AppState, SharedMempoolClient};
+use crate::gateway::{
+    add_transaction,
+    add_transactions,
+    add_tx,
+    add_versioned_transaction,
+    get_transaction_status,
+    load_classes,
+    process_and_add_tx,
+    simulate_transactions,
+    AddTransactionResponse,
+    AddTransactionsEntry,
+    AppState,
+    SharedMempoolClient,
+    TransactionStatusQuery,
+    TransactionStatusResponse,
+};
+use crate::idempotency::{IdempotencyCache, IDEMPOTENCY_KEY_HEADER};
+use crate::load_shedding::LoadShedder;
+use crate::rate_limiting::RateLimiter;
+use crate::rejections::{Rejection, RejectionCode};
+use crate::request_logging::RequestLogger;
+use crate::sender_access::SenderAccessControl;
+use crate::simulation::{SimulateTransactionsRequest, TransactionSimulator};
 use crate::state_reader_test_utils::{local_test_state_reader_factory, TestStateReaderFactory};
 use crate::stateful_transaction_validator::StatefulTransactionValidator;
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
+use crate::tenants::CHAIN_ID_HEADER;
+use crate::transaction_events::TransactionEventBus;
+use crate::transaction_status::TransactionStatusStore;
+use crate::transaction_type_gate::TransactionTypeGate;
 use crate::utils::rpc_tx_to_account_tx;
 
 pub fn app_state(
@@ -30,6 +89,31 @@ pub fn app_state(
     state_reader_factory: TestStateReaderFactory,
 ) -> AppState {
     AppState {
+        arrival_journal: None,
+        idempotency_cache: Some(Arc::new(IdempotencyCache::new(&IdempotencyConfig::default()))),
+        duplicate_detector: Some(Arc::new(DuplicateDetector::new(
+            &DuplicateDetectionConfig::default(),
+        ))),
+        rate_limiter: Arc::new(RateLimiter::new(&RateLimitConfig::default())),
+        load_shedder: Arc::new(LoadShedder::new(&LoadSheddingConfig::default())),
+        max_batch_size: 2,
+        sender_access_control: None,
+        request_logger: Arc::new(RequestLogger::new(&RequestLoggingConfig::default())),
+        resource_status: SharedResourceStatus::default(),
+        l2_gas_price: SharedL2GasPrice::default(),
+        ingestion_paused: Arc::new(AtomicBool::new(false)),
+        transaction_type_gate: Arc::new(
+            TransactionTypeGate::new(&TransactionTypeConfig::default()),
+        ),
+        declare_gate: Arc::new(DeclareGate::default()),
+        api_versions: ApiVersions::default(),
+        transaction_status_store: Arc::new(TransactionStatusStore::new(
+            &TransactionStatusConfig::default(),
+        )),
+        transaction_event_bus: TransactionEventBus::new(&TransactionEventsConfig::default()),
+        transaction_simulator: Some(Arc::new(TransactionSimulator::new(
+            &SimulationConfig::default(),
+        ))),
         stateless_tx_validator: StatelessTransactionValidator {
             config: StatelessTransactionValidatorConfig::default(),
         },
@@ -41,6 +125,9 @@ pub fn app_state(
         ),
         state_reader_factory: Arc::new(state_reader_factory),
         mempool_client,
+        mempool_p2p_sender_client: None,
+        class_manager_client: None,
+        tenants: Default::default(),
     }
 }
 
@@ -57,11 +144,9 @@ fn create_tx() -> (RpcTransaction, SenderAddress) {
     (tx, sender_address)
 }
 
-#[tokio::test]
-async fn test_add_tx() {
-    let (tx, sender_address) = create_tx();
-    let tx_hash = calculate_hash(&tx);
-
+// Returns an app state whose mempool expects to receive the given transaction once.
+fn app_state_expecting_tx(tx: &RpcTransaction, sender_address: SenderAddress) -> AppState {
+    let tx_hash = calculate_hash(tx);
     let mut mock_mempool_client = MockMempoolClient::new();
     mock_mempool_client
         .expect_add_tx()
@@ -77,12 +162,21 @@ async fn test_add_tx() {
                 ValidResourceBounds::AllResources(tx.resource_bounds().clone()),
             ),
             account: Account { sender_address, state: AccountState { nonce: *tx.nonce() } },
+            eligible_at: None,
         }))
         .return_once(|_| Ok(()));
     let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
-    let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
+    app_state(Arc::new(mock_mempool_client), state_reader_factory)
+}
 
-    let response = add_tx(State(app_state), tx.into()).await.into_response();
+#[tokio::test]
+async fn test_add_tx() {
+    let (tx, sender_address) = create_tx();
+    let tx_hash = calculate_hash(&tx);
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+
+    let response =
+        add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
 
     let status_code = response.status();
     let response_bytes = &to_bytes(response).await;
@@ -91,6 +185,331 @@ async fn test_add_tx() {
     assert_eq!(tx_hash, serde_json::from_slice(response_bytes).unwrap());
 }
 
+#[tokio::test]
+async fn test_add_transaction() {
+    let (tx, sender_address) = create_tx();
+    let tx_hash = calculate_hash(&tx);
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+
+    let response =
+        add_transaction(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
+
+    let status_code = response.status();
+    let response_bytes = &to_bytes(response).await;
+
+    assert_eq!(status_code, StatusCode::OK, "{response_bytes:?}");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(response_bytes).unwrap(),
+        serde_json::json!({"code": "TRANSACTION_RECEIVED", "transaction_hash": tx_hash})
+    );
+}
+
+#[tokio::test]
+async fn test_add_transactions_answers_each_transaction() {
+    let (tx, sender_address) = create_tx();
+    let tx_hash = calculate_hash(&tx);
+    // The mempool expects to receive the transaction once; its resubmission in the same batch is
+    // rejected as a duplicate.
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+
+    let Json(entries) =
+        add_transactions(State(app_state), None, HeaderMap::new(), Json(vec![tx.clone(), tx]))
+            .await
+            .unwrap();
+
+    assert_eq!(
+        entries,
+        vec![
+            AddTransactionsEntry::Received(AddTransactionResponse {
+                code: "TRANSACTION_RECEIVED".to_owned(),
+                transaction_hash: tx_hash,
+                address: None,
+                class_hash: None,
+            }),
+            AddTransactionsEntry::Rejected(GatewaySpecError::DuplicateTx.into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_add_versioned_transaction_answers_the_served_version() {
+    let (tx, sender_address) = create_tx();
+    let mut app_state = app_state_expecting_tx(&tx, sender_address);
+    app_state.api_versions = ApiVersions::new(&ApiVersionsConfig {
+        v0_8: ApiVersionConfig { enable: true, deprecated: true },
+        ..Default::default()
+    });
+
+    let (headers, _) = add_versioned_transaction(
+        State(app_state),
+        Path("v0.8".to_owned()),
+        None,
+        HeaderMap::new(),
+        Json(serde_json::to_value(tx).unwrap()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(headers[API_VERSION_HEADER], "v0.8");
+    assert_eq!(headers[DEPRECATION_HEADER], "true");
+}
+
+#[tokio::test]
+async fn test_add_versioned_transaction_in_a_disabled_version_is_rejected() {
+    let (tx, _) = create_tx();
+    // The mempool is not reached.
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let mut app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    app_state.api_versions = ApiVersions::new(&ApiVersionsConfig {
+        v0_8: ApiVersionConfig { enable: false, deprecated: false },
+        ..Default::default()
+    });
+
+    let err = add_versioned_transaction(
+        State(app_state),
+        Path("v0.8".to_owned()),
+        None,
+        HeaderMap::new(),
+        Json(serde_json::to_value(tx).unwrap()),
+    )
+    .await
+    .unwrap_err();
+
+    assert_matches!(err, GatewaySpecError::ValidationFailure { .. });
+}
+
+#[tokio::test]
+async fn test_add_transactions_beyond_the_batch_size_is_rejected() {
+    let (tx, _) = create_tx();
+    // The mempool is not reached.
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+
+    let err = add_transactions(State(app_state), None, HeaderMap::new(), Json(vec![tx.clone(); 3]))
+        .await
+        .unwrap_err();
+
+    assert_matches!(err, GatewaySpecError::ValidationFailure { .. });
+}
+
+#[tokio::test]
+async fn test_added_tx_is_received() {
+    let (tx, sender_address) = create_tx();
+    let tx_hash = calculate_hash(&tx);
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+    let query = || Query(TransactionStatusQuery { transaction_hash: tx_hash });
+
+    let response = get_transaction_status(State(app_state.clone()), query()).await;
+    assert_eq!(response.0, TransactionStatusResponse { tx_status: TransactionStatus::NotReceived });
+
+    add_tx(State(app_state.clone()), None, HeaderMap::new(), tx.into()).await.unwrap();
+
+    let response = get_transaction_status(State(app_state), query()).await;
+    assert_eq!(response.0, TransactionStatusResponse { tx_status: TransactionStatus::Received });
+}
+
+#[tokio::test]
+async fn test_add_tx_with_idempotency_key_is_added_once() {
+    let (tx, sender_address) = create_tx();
+    let tx_hash = calculate_hash(&tx);
+    // The mempool expects to receive the transaction once.
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+    let mut headers = HeaderMap::new();
+    headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("key"));
+
+    for _ in 0..2 {
+        let response = add_tx(State(app_state.clone()), None, headers.clone(), tx.clone().into())
+            .await
+            .into_response();
+
+        let status_code = response.status();
+        let response_bytes = &to_bytes(response).await;
+
+        assert_eq!(status_code, StatusCode::OK, "{response_bytes:?}");
+        assert_eq!(tx_hash, serde_json::from_slice(response_bytes).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_resubmitted_tx_is_rejected_as_duplicate() {
+    let (tx, sender_address) = create_tx();
+    // The mempool expects to receive the transaction once.
+    let app_state = app_state_expecting_tx(&tx, sender_address);
+
+    process_and_add_tx(app_state.clone(), tx.clone(), TransactionSource::Http).await.unwrap();
+    let result = process_and_add_tx(app_state, tx, TransactionSource::Http).await;
+
+    assert_eq!(result, Err(GatewaySpecError::DuplicateTx));
+}
+
+#[tokio::test]
+async fn test_add_tx_beyond_the_sender_rate_limit_is_rejected() {
+    let (tx, sender_address) = create_tx();
+    // The mempool expects to receive the transaction once.
+    let mut app_state = app_state_expecting_tx(&tx, sender_address);
+    app_state.rate_limiter = Arc::new(RateLimiter::new(&RateLimitConfig {
+        sender_limit: TokenBucketConfig { enable: true, burst: 1, refill_rate: 0.0 },
+        ..Default::default()
+    }));
+
+    add_tx(State(app_state.clone()), None, HeaderMap::new(), tx.clone().into()).await.unwrap();
+    let response =
+        add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn test_add_tx_of_denied_sender_is_rejected() {
+    let (tx, sender_address) = create_tx();
+    // The mempool is not reached.
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let mut app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sender_access_lists.json");
+    std::fs::write(&path, serde_json::json!({"deny": [sender_address]}).to_string()).unwrap();
+    app_state.sender_access_control = Some(Arc::new(
+        SenderAccessControl::new(&SenderAccessConfig { enable: true, path, ..Default::default() })
+            .unwrap(),
+    ));
+
+    let response =
+        add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_simulate_transactions() {
+    let tx = invoke_tx(CairoVersion::Cairo0);
+    let tx_hash = calculate_hash(&tx);
+    // Simulated transactions are not added to the mempool.
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo0, false);
+    let app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    let request = SimulateTransactionsRequest {
+        transactions: vec![tx],
+        block_number: None,
+        simulation_flags: vec![],
+    };
+
+    let Json(simulated_txs) =
+        simulate_transactions(State(app_state), None, HeaderMap::new(), Json(request))
+            .await
+            .unwrap();
+
+    assert_eq!(simulated_txs.len(), 1);
+    assert_eq!(simulated_txs[0].transaction_hash, tx_hash);
+    assert_eq!(simulated_txs[0].revert_error, None);
+    assert!(simulated_txs[0].execute_invocation.is_some());
+}
+
+#[tokio::test]
+async fn test_simulate_transactions_when_disabled_is_rejected() {
+    let (tx, _) = create_tx();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let mut app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    app_state.transaction_simulator = None;
+    let request = SimulateTransactionsRequest {
+        transactions: vec![tx],
+        block_number: None,
+        simulation_flags: vec![],
+    };
+
+    let err = simulate_transactions(State(app_state), None, HeaderMap::new(), Json(request))
+        .await
+        .unwrap_err();
+
+    assert_matches!(err, GatewaySpecError::ValidationFailure { .. });
+}
+
+// Returns a mempool p2p sender client expecting the given transaction to be broadcast the given
+// number of times.
+fn mempool_p2p_sender_client_expecting_tx(
+    tx: &RpcTransaction,
+    times: usize,
+) -> MockMempoolP2pSenderClient {
+    let mut mock_mempool_p2p_sender_client = MockMempoolP2pSenderClient::new();
+    mock_mempool_p2p_sender_client
+        .expect_add_transaction()
+        .times(times)
+        .with(eq(tx.clone()))
+        .returning(|_| Ok(()));
+    mock_mempool_p2p_sender_client
+}
+
+#[tokio::test]
+async fn test_add_tx_broadcasts_tx_to_peers() {
+    let (tx, sender_address) = create_tx();
+    let mut app_state = app_state_expecting_tx(&tx, sender_address);
+    app_state.mempool_p2p_sender_client =
+        Some(Arc::new(mempool_p2p_sender_client_expecting_tx(&tx, 1)));
+
+    add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_tx_received_from_peers_is_not_broadcast() {
+    let (tx, sender_address) = create_tx();
+    let mut app_state = app_state_expecting_tx(&tx, sender_address);
+    app_state.mempool_p2p_sender_client =
+        Some(Arc::new(mempool_p2p_sender_client_expecting_tx(&tx, 0)));
+
+    process_and_add_tx(app_state, tx, TransactionSource::P2p).await.unwrap();
+}
+
+const TENANT_CHAIN_ID: &str = "SN_TENANT";
+
+// Returns an app state with no expected transactions, fronting a tenant whose mempool expects the
+// given transaction once.
+fn app_state_with_tenant_expecting_tx(
+    tx: &RpcTransaction,
+    sender_address: SenderAddress,
+) -> AppState {
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let mut app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    let tenant_app_state = app_state_expecting_tx(tx, sender_address);
+    app_state.tenants =
+        Arc::new(HashMap::from([(ChainId::from(TENANT_CHAIN_ID.to_owned()), tenant_app_state)]));
+    app_state
+}
+
+#[tokio::test]
+async fn test_add_tx_is_routed_by_chain_id() {
+    let (tx, sender_address) = create_tx();
+    let app_state = app_state_with_tenant_expecting_tx(&tx, sender_address);
+    let mut headers = HeaderMap::new();
+    headers.insert(CHAIN_ID_HEADER, HeaderValue::from_static(TENANT_CHAIN_ID));
+
+    add_tx(State(app_state), None, headers, tx.into()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_add_tx_with_own_chain_id_is_not_routed() {
+    let (tx, sender_address) = create_tx();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let tenant_app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    let mut app_state = app_state_expecting_tx(&tx, sender_address);
+    app_state.tenants =
+        Arc::new(HashMap::from([(ChainId::from(TENANT_CHAIN_ID.to_owned()), tenant_app_state)]));
+    let own_chain_id = ChainInfo::create_for_testing().chain_id.to_string();
+    let mut headers = HeaderMap::new();
+    headers.insert(CHAIN_ID_HEADER, HeaderValue::from_str(&own_chain_id).unwrap());
+
+    add_tx(State(app_state), None, headers, tx.into()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_add_tx_with_unknown_chain_id_is_rejected() {
+    let (tx, _) = create_tx();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    let mut headers = HeaderMap::new();
+    headers.insert(CHAIN_ID_HEADER, HeaderValue::from_static("SN_UNKNOWN"));
+
+    let err = add_tx(State(app_state), None, headers, tx.into()).await.unwrap_err();
+    assert_matches!(err, GatewaySpecError::ValidationFailure { .. });
+}
+
 async fn to_bytes(res: Response) -> Bytes {
     res.into_body().collect().await.unwrap().to_bytes()
 }
@@ -110,10 +529,110 @@ async fn test_compiled_class_hash_mismatch() {
     let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
     let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
 
-    let err = add_tx(State(app_state), tx.into()).await.unwrap_err();
+    let err = add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.unwrap_err();
     assert_matches!(err, GatewaySpecError::CompiledClassHashMismatch);
 }
 
+#[tokio::test]
+async fn test_add_tx_sheds_load_under_resource_pressure() {
+    let (tx, _) = create_tx();
+
+    // The mempool is not reached.
+    let mock_mempool_client = MockMempoolClient::new();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
+    app_state
+        .resource_status
+        .set(ResourceStatus { pressure: ResourcePressure::High, ..Default::default() });
+
+    let err = add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.unwrap_err();
+    assert_matches!(err, GatewaySpecError::UnexpectedError { .. });
+}
+
+#[tokio::test]
+async fn test_add_tx_beyond_the_concurrent_validations_is_shed() {
+    let (tx, _) = create_tx();
+    // The mempool is not reached.
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let mut app_state = app_state(Arc::new(MockMempoolClient::new()), state_reader_factory);
+    app_state.load_shedder = Arc::new(LoadShedder::new(&LoadSheddingConfig {
+        enable: true,
+        max_concurrent_validations: 1,
+    }));
+    let _validation_in_progress = app_state.load_shedder.try_acquire().unwrap();
+
+    let response =
+        add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_add_tx_is_rejected_while_ingestion_is_paused() {
+    let (tx, _) = create_tx();
+
+    // The mempool is not reached.
+    let mock_mempool_client = MockMempoolClient::new();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
+    app_state.ingestion_paused.store(true, Ordering::Relaxed);
+
+    let err = add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.unwrap_err();
+    assert_matches!(err, GatewaySpecError::UnexpectedError { .. });
+}
+
+#[tokio::test]
+async fn test_add_tx_below_the_l2_gas_price_is_rejected() {
+    let (tx, _) = create_tx();
+
+    // The mempool is not reached.
+    let mock_mempool_client = MockMempoolClient::new();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
+    let max_l2_gas_price = tx.resource_bounds().l2_gas.max_price_per_unit;
+    app_state.l2_gas_price.set(NonZeroU128::new(max_l2_gas_price + 1).unwrap());
+
+    let err = add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.unwrap_err();
+    assert_matches!(
+        err,
+        GatewaySpecError::InvalidTransaction(Rejection {
+            code: RejectionCode::MaxGasPriceTooLow,
+            ..
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_tx_is_answered_with_its_rejection() {
+    let (tx, _) = create_tx();
+
+    // The mempool is not reached.
+    let mock_mempool_client = MockMempoolClient::new();
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let app_state = app_state(Arc::new(mock_mempool_client), state_reader_factory);
+    let max_l2_gas_price = tx.resource_bounds().l2_gas.max_price_per_unit;
+    app_state.l2_gas_price.set(NonZeroU128::new(max_l2_gas_price + 1).unwrap());
+
+    let response =
+        add_tx(State(app_state), None, HeaderMap::new(), tx.into()).await.into_response();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response).await).unwrap();
+    assert_eq!(body["code"], "MAX_GAS_PRICE_TOO_LOW");
+    assert_eq!(body["data"]["resource"], "L2_GAS");
+    assert_eq!(body["data"]["gas_price"], format!("{:#x}", max_l2_gas_price + 1));
+}
+
+#[test]
+fn load_classes_skips_undeclared_classes() {
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+    let declared_class_hash =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1).get_class_hash();
+
+    let class_hashes = [declared_class_hash, class_hash!("0xdead")];
+    assert_eq!(load_classes(&state_reader_factory, &class_hashes), 1);
+}
+
 fn calculate_hash(rpc_tx: &RpcTransaction) -> TransactionHash {
     let optional_class_info = match &rpc_tx {
         RpcTransaction::Declare(_declare_tx) => {
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::AddTransactionResult;
+use tokio::sync::OnceCell;
+
+use crate::config::IdempotencyConfig;
+use crate::duplicate_detection::SubmissionKey;
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "idempotency_test.rs"]
+mod idempotency_test;
+
+/// The HTTP header carrying the client-supplied idempotency key of a submission.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+type SharedResult = Arc<OnceCell<GatewayResult<AddTransactionResult>>>;
+
+// Keys are chosen by the clients, so they are scoped to the sender of the transaction; a key reused
+// by another sender never returns the result of someone else's submission.
+type ScopedKey = (ContractAddress, String);
+
+struct Entry {
+    tx_hash: TransactionHash,
+    result: SharedResult,
+}
+
+#[derive(Default)]
+struct Entries {
+    results: HashMap<ScopedKey, Entry>,
+    // The keys in the order they were first seen, with the time they expire at.
+    expirations: VecDeque<(Instant, ScopedKey)>,
+}
+
+impl Entries {
+    fn remove_front(&mut self) {
+        let (_, key) = self.expirations.pop_front().expect("The front entry exists.");
+        self.results.remove(&key);
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while self.expirations.front().is_some_and(|(expiration, _)| *expiration <= now) {
+            self.remove_front();
+        }
+    }
+}
+
+/// Remembers the result of every submission carrying an idempotency key for a configurable window,
+/// so that resubmissions of the same transaction with the same key get the result of the first
+/// submission instead of being processed again. Concurrent submissions with the same key wait for
+/// the first one to finish.
+///
+/// Unlike the [`DuplicateDetector`](crate::duplicate_detection::DuplicateDetector), which rejects
+/// resubmitted transactions, a keyed retry is answered as the first submission was, and is never
+/// processed, so it doesn't reach the duplicate detection.
+///
+/// Transient failures (i.e., unexpected errors) are not remembered, so that they can be retried.
+pub struct IdempotencyCache {
+    window: Duration,
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl IdempotencyCache {
+    pub fn new(config: &IdempotencyConfig) -> Self {
+        Self {
+            window: config.window,
+            capacity: config.capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Returns the remembered result of the submission of the sender with the given key, or
+    /// processes the submission if there is none. Reusing a key for another transaction of the
+    /// sender is rejected.
+    pub async fn get_or_process<F>(
+        &self,
+        submission: &SubmissionKey,
+        key: &str,
+        process: F,
+    ) -> GatewayResult<AddTransactionResult>
+    where
+        F: Future<Output = GatewayResult<AddTransactionResult>>,
+    {
+        if key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(GatewaySpecError::ValidationFailure {
+                data: format!(
+                    "The idempotency key is longer than {MAX_IDEMPOTENCY_KEY_LENGTH} bytes."
+                ),
+            });
+        }
+
+        let shared_result = self.shared_result(submission, key)?;
+        let process_remembered = || async move {
+            match process.await {
+                Err(err @ GatewaySpecError::UnexpectedError { .. }) => Err(err),
+                result => Ok(result),
+            }
+        };
+        match shared_result.get_or_try_init(process_remembered).await {
+            Ok(result) => result.clone(),
+            Err(transient_err) => Err(transient_err),
+        }
+    }
+
+    // Returns the result shared by the submissions of the sender with the given key, which is set
+    // by the first of them to finish.
+    fn shared_result(&self, submission: &SubmissionKey, key: &str) -> GatewayResult<SharedResult> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("The idempotency cache lock is poisoned.");
+        entries.remove_expired(now);
+        let scoped_key = (submission.sender_address, key.to_owned());
+        if let Some(entry) = entries.results.get(&scoped_key) {
+            if entry.tx_hash != submission.tx_hash {
+                return Err(GatewaySpecError::ValidationFailure {
+                    data: "The idempotency key was already used for another transaction."
+                        .to_owned(),
+                });
+            }
+            return Ok(entry.result.clone());
+        }
+        let shared_result = SharedResult::default();
+        entries.results.insert(
+            scoped_key.clone(),
+            Entry { tx_hash: submission.tx_hash, result: shared_result.clone() },
+        );
+        entries.expirations.push_back((now + self.window, scoped_key));
+        while entries.expirations.len() > self.capacity {
+            entries.remove_front();
+        }
+        Ok(shared_result)
+    }
+}
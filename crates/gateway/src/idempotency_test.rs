@@ -0,0 +1,202 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{contract_address, felt};
+use starknet_gateway_types::gateway_types::AddTransactionResult;
+
+use crate::config::IdempotencyConfig;
+use crate::duplicate_detection::SubmissionKey;
+use crate::errors::{GatewayResult, GatewaySpecError};
+use crate::idempotency::{IdempotencyCache, MAX_IDEMPOTENCY_KEY_LENGTH};
+
+const KEY: &str = "key";
+
+fn idempotency_cache(window: Duration) -> IdempotencyCache {
+    IdempotencyCache::new(&IdempotencyConfig { enable: true, window, capacity: 100 })
+}
+
+fn submission(sender_address: ContractAddress, tx_hash: u8) -> SubmissionKey {
+    SubmissionKey {
+        tx_hash: TransactionHash(tx_hash.into()),
+        sender_address,
+        nonce: Nonce(felt!(0_u8)),
+    }
+}
+
+fn tx() -> SubmissionKey {
+    submission(contract_address!("0x1"), 1)
+}
+
+fn accepted(tx_hash: u8) -> GatewayResult<AddTransactionResult> {
+    Ok(AddTransactionResult::Invoke { tx_hash: TransactionHash(tx_hash.into()) })
+}
+
+// Processes a submission with the given result, counting the submissions that were processed.
+async fn process(
+    n_processed: &AtomicUsize,
+    result: GatewayResult<AddTransactionResult>,
+) -> GatewayResult<AddTransactionResult> {
+    n_processed.fetch_add(1, Ordering::Relaxed);
+    tokio::task::yield_now().await;
+    result
+}
+
+#[tokio::test]
+async fn resubmissions_get_the_first_result() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(2))).await,
+        accepted(1)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 1);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), "other_key", process(&n_processed, accepted(2))).await,
+        accepted(2)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn rejections_are_remembered() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+
+    let rejection = Err(GatewaySpecError::InvalidTransactionNonce);
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, rejection.clone())).await,
+        rejection
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        rejection
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn unexpected_errors_are_retried() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+
+    let unexpected_error = Err(GatewaySpecError::UnexpectedError { data: "Failure.".to_owned() });
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, unexpected_error.clone())).await,
+        unexpected_error
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn results_expire_after_the_window() {
+    let cache = idempotency_cache(Duration::ZERO);
+    let n_processed = AtomicUsize::new(0);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(2))).await,
+        accepted(2)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn concurrent_submissions_are_processed_once() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+
+    let (first_result, second_result) = tokio::join!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))),
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(2)))
+    );
+    assert_eq!(first_result, accepted(1));
+    assert_eq!(second_result, accepted(1));
+    assert_eq!(n_processed.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn long_keys_are_rejected() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+
+    let long_key = "k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1);
+    assert_matches!(
+        cache.get_or_process(&tx(), &long_key, process(&n_processed, accepted(1))).await,
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn keys_are_scoped_to_the_sender() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+    let other_sender_tx = submission(contract_address!("0x2"), 2);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(
+        cache.get_or_process(&other_sender_tx, KEY, process(&n_processed, accepted(2))).await,
+        accepted(2)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn reusing_a_key_for_another_transaction_is_rejected() {
+    let cache = idempotency_cache(Duration::from_secs(60));
+    let n_processed = AtomicUsize::new(0);
+    let other_tx = submission(contract_address!("0x1"), 2);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_matches!(
+        cache.get_or_process(&other_tx, KEY, process(&n_processed, accepted(2))).await,
+        Err(GatewaySpecError::ValidationFailure { .. })
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn oldest_results_are_forgotten_beyond_the_capacity() {
+    let cache = IdempotencyCache::new(&IdempotencyConfig {
+        enable: true,
+        window: Duration::from_secs(60),
+        capacity: 1,
+    });
+    let n_processed = AtomicUsize::new(0);
+
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), "other_key", process(&n_processed, accepted(1))).await,
+        accepted(1)
+    );
+    assert_eq!(
+        cache.get_or_process(&tx(), KEY, process(&n_processed, accepted(2))).await,
+        accepted(2)
+    );
+    assert_eq!(n_processed.load(Ordering::Relaxed), 3);
+}
@@ -1,18 +1,37 @@
+pub mod api_versions;
+pub mod arrival_journal;
 pub mod communication;
 pub mod compilation;
 mod compiler_version;
 pub mod config;
+pub mod declare_gating;
+pub mod duplicate_detection;
 pub mod errors;
 pub mod gateway;
+pub mod idempotency;
+pub mod load_shedding;
+pub mod metrics;
+mod papyrus_state_reader;
+mod pending_state_reader;
+pub mod rate_limiting;
+pub mod rejections;
+pub mod request_logging;
 mod rpc_objects;
 mod rpc_state_reader;
 #[cfg(test)]
 mod rpc_state_reader_test;
+pub mod sender_access;
+pub mod simulation;
 mod state_reader;
 #[cfg(test)]
 mod state_reader_test_utils;
 mod stateful_transaction_validator;
 mod stateless_transaction_validator;
+pub mod tenants;
 #[cfg(test)]
 mod test_utils;
+pub mod transaction_events;
+pub mod transaction_status;
+pub mod transaction_type_gate;
 mod utils;
+mod validation_error_messages;
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::config::LoadSheddingConfig;
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "load_shedding_test.rs"]
+mod load_shedding_test;
+
+/// The number of submissions being validated.
+pub const VALIDATIONS_IN_PROGRESS: &str = "gateway_validations_in_progress";
+/// Counts the submissions rejected as the limit on concurrent validations was reached.
+pub const SHED_TXS: &str = "gateway_shed_txs";
+
+/// Limits the submissions validated concurrently, rejecting the submissions beyond the limit
+/// rather than queueing them, so that the latency of the accepted ones stays bounded.
+pub struct LoadShedder {
+    config: LoadSheddingConfig,
+    in_progress: Arc<AtomicUsize>,
+}
+
+impl LoadShedder {
+    pub fn new(config: &LoadSheddingConfig) -> Self {
+        Self { config: config.clone(), in_progress: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    /// Returns a permit to validate a submission, held until the validation ends, or
+    /// `ServiceUnavailable` if the limit on concurrent validations is reached.
+    pub fn try_acquire(&self) -> GatewayResult<ValidationPermit> {
+        let in_progress = self.in_progress.fetch_add(1, Ordering::Relaxed) + 1;
+        // Dropped on rejection as well, undoing the increment.
+        let permit = ValidationPermit { in_progress: self.in_progress.clone() };
+        if self.config.enable && in_progress > self.config.max_concurrent_validations {
+            metrics::increment_counter!(SHED_TXS);
+            return Err(GatewaySpecError::ServiceUnavailable);
+        }
+        metrics::gauge!(VALIDATIONS_IN_PROGRESS, to_gauge_value(in_progress));
+        Ok(permit)
+    }
+}
+
+/// A slot of a submission being validated, freed once dropped.
+pub struct ValidationPermit {
+    in_progress: Arc<AtomicUsize>,
+}
+
+impl Drop for ValidationPermit {
+    fn drop(&mut self) {
+        let in_progress = self.in_progress.fetch_sub(1, Ordering::Relaxed) - 1;
+        metrics::gauge!(VALIDATIONS_IN_PROGRESS, to_gauge_value(in_progress));
+    }
+}
+
+#[allow(clippy::as_conversions)]
+fn to_gauge_value(in_progress: usize) -> f64 {
+    in_progress as f64
+}
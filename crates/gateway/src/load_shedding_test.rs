@@ -0,0 +1,27 @@
+use assert_matches::assert_matches;
+
+use crate::config::LoadSheddingConfig;
+use crate::errors::GatewaySpecError;
+use crate::load_shedding::LoadShedder;
+
+#[test]
+fn validations_beyond_the_limit_are_shed() {
+    let load_shedder =
+        LoadShedder::new(&LoadSheddingConfig { enable: true, max_concurrent_validations: 2 });
+
+    let _first_permit = load_shedder.try_acquire().unwrap();
+    let second_permit = load_shedder.try_acquire().unwrap();
+    assert_matches!(load_shedder.try_acquire(), Err(GatewaySpecError::ServiceUnavailable));
+
+    // The slot of a validation is freed once it ends.
+    drop(second_permit);
+    assert_matches!(load_shedder.try_acquire(), Ok(_));
+}
+
+#[test]
+fn validations_are_not_limited_when_disabled() {
+    let load_shedder =
+        LoadShedder::new(&LoadSheddingConfig { enable: false, max_concurrent_validations: 1 });
+
+    let _permits: Vec<_> = (0..3).map(|_| load_shedder.try_acquire().unwrap()).collect();
+}
@@ -0,0 +1,154 @@
+//! The metrics of the gateway. Submitted transactions are labeled by the channel they arrived
+//! through.
+
+use std::time::Instant;
+
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "metrics_test.rs"]
+mod metrics_test;
+
+/// Counts the transactions accepted and added to the mempool, labeled by their source.
+pub const ACCEPTED_TXS: &str = "gateway_accepted_txs";
+/// Counts the transactions rejected by the gateway or the mempool, labeled by their source and by
+/// the reason of the rejection; see [`AddTxStage::rejection_reason`].
+pub const REJECTED_TXS: &str = "gateway_rejected_txs";
+/// The time from the submission of a transaction until it is added to the mempool or rejected, in
+/// seconds, labeled by its source and by the outcome: `accepted` or `rejected`.
+pub const ADD_TX_LATENCY: &str = "gateway_add_tx_latency_seconds";
+/// The time a transaction spent in each stage of the pipeline, in seconds, labeled by the stage.
+/// A rejected transaction is recorded up to the stage that rejected it.
+pub const ADD_TX_STAGE_LATENCY: &str = "gateway_add_tx_stage_latency_seconds";
+/// Counts the requests to the versioned endpoints, labeled by the version of their format.
+pub const API_VERSION_REQUESTS: &str = "gateway_api_version_requests";
+
+pub const SOURCE_LABEL: &str = "source";
+pub const REASON_LABEL: &str = "reason";
+const OUTCOME_LABEL: &str = "outcome";
+const STAGE_LABEL: &str = "stage";
+const VERSION_LABEL: &str = "version";
+
+/// The stages a submitted transaction goes through, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AddTxStage {
+    // Resource pressure, paused ingestion, load shedding and the transaction type gate.
+    Admission,
+    StatelessValidation,
+    // Sender access control and rate limiting.
+    SenderChecks,
+    DuplicateDetection,
+    Compilation,
+    StatefulValidation,
+    ClassStorage,
+    MempoolAddition,
+    // Recording the arrival of the accepted transaction, and broadcasting it to peers.
+    Propagation,
+}
+
+impl AddTxStage {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            AddTxStage::Admission => "admission",
+            AddTxStage::StatelessValidation => "stateless_validation",
+            AddTxStage::SenderChecks => "sender_checks",
+            AddTxStage::DuplicateDetection => "duplicate_detection",
+            AddTxStage::Compilation => "compilation",
+            AddTxStage::StatefulValidation => "stateful_validation",
+            AddTxStage::ClassStorage => "class_storage",
+            AddTxStage::MempoolAddition => "mempool_addition",
+            AddTxStage::Propagation => "propagation",
+        }
+    }
+
+    /// The reason a transaction rejected at this stage with the given error is counted under.
+    pub(crate) fn rejection_reason(&self, error: &GatewaySpecError) -> &'static str {
+        match error {
+            GatewaySpecError::TooManyRequests => return "rate_limited",
+            GatewaySpecError::ServiceUnavailable => return "unavailable",
+            GatewaySpecError::DuplicateTx => return "duplicate",
+            GatewaySpecError::SenderNotAllowed => return "sender_not_allowed",
+            GatewaySpecError::DeclareNotAllowed => return "declare_not_allowed",
+            _ => {}
+        }
+        match self {
+            AddTxStage::Admission => "not_admitted",
+            AddTxStage::StatelessValidation => "stateless_validation_failed",
+            AddTxStage::SenderChecks | AddTxStage::Propagation => "internal_error",
+            AddTxStage::DuplicateDetection => "duplicate",
+            AddTxStage::Compilation => "compilation_failed",
+            AddTxStage::StatefulValidation => "stateful_validation_failed",
+            AddTxStage::ClassStorage => "class_storage_failed",
+            AddTxStage::MempoolAddition => "mempool_rejected",
+        }
+    }
+}
+
+/// Follows a transaction through the stages of the pipeline: records the time it spent in each
+/// stage, and, once it is accepted or rejected, its end-to-end latency and the reason of its
+/// rejection.
+pub(crate) struct AddTxFunnel {
+    source: &'static str,
+    start: Instant,
+    stage: AddTxStage,
+    stage_start: Instant,
+}
+
+impl AddTxFunnel {
+    pub(crate) fn start(source: &'static str) -> Self {
+        let start = Instant::now();
+        Self { source, start, stage: AddTxStage::Admission, stage_start: start }
+    }
+
+    /// Ends the current stage, and starts the given one.
+    pub(crate) fn enter(&mut self, stage: AddTxStage) {
+        let now = Instant::now();
+        self.record_stage_latency(now);
+        self.stage = stage;
+        self.stage_start = now;
+    }
+
+    pub(crate) fn finish<T>(self, result: &GatewayResult<T>) {
+        let now = Instant::now();
+        self.record_stage_latency(now);
+        let latency = now.duration_since(self.start).as_secs_f64();
+        match result {
+            Ok(_) => {
+                metrics::increment_counter!(ACCEPTED_TXS, SOURCE_LABEL => self.source);
+                metrics::histogram!(
+                    ADD_TX_LATENCY,
+                    latency,
+                    SOURCE_LABEL => self.source,
+                    OUTCOME_LABEL => "accepted"
+                );
+            }
+            Err(error) => {
+                record_rejected_tx(self.source, self.stage.rejection_reason(error));
+                metrics::histogram!(
+                    ADD_TX_LATENCY,
+                    latency,
+                    SOURCE_LABEL => self.source,
+                    OUTCOME_LABEL => "rejected"
+                );
+            }
+        }
+    }
+
+    fn record_stage_latency(&self, now: Instant) {
+        metrics::histogram!(
+            ADD_TX_STAGE_LATENCY,
+            now.duration_since(self.stage_start).as_secs_f64(),
+            STAGE_LABEL => self.stage.name()
+        );
+    }
+}
+
+/// Counts a transaction rejected for the given reason.
+pub(crate) fn record_rejected_tx(source: &'static str, reason: &'static str) {
+    metrics::increment_counter!(REJECTED_TXS, SOURCE_LABEL => source, REASON_LABEL => reason);
+}
+
+/// Counts a request to a versioned endpoint in the given version.
+pub(crate) fn record_api_version_request(version: &'static str) {
+    metrics::increment_counter!(API_VERSION_REQUESTS, VERSION_LABEL => version);
+}
@@ -0,0 +1,44 @@
+use rstest::rstest;
+
+use crate::errors::GatewaySpecError;
+use crate::metrics::AddTxStage;
+
+#[rstest]
+#[case::rate_limited(AddTxStage::SenderChecks, GatewaySpecError::TooManyRequests, "rate_limited")]
+#[case::load_shed(AddTxStage::Admission, GatewaySpecError::ServiceUnavailable, "unavailable")]
+#[case::duplicate(AddTxStage::DuplicateDetection, GatewaySpecError::DuplicateTx, "duplicate")]
+#[case::stateless(
+    AddTxStage::StatelessValidation,
+    GatewaySpecError::ValidationFailure { data: "Calldata too long.".to_owned() },
+    "stateless_validation_failed"
+)]
+#[case::compilation(
+    AddTxStage::Compilation,
+    GatewaySpecError::CompilationFailed,
+    "compilation_failed"
+)]
+#[case::stateful(
+    AddTxStage::StatefulValidation,
+    GatewaySpecError::InvalidTransactionNonce,
+    "stateful_validation_failed"
+)]
+#[case::mempool(
+    AddTxStage::MempoolAddition,
+    GatewaySpecError::ValidationFailure { data: "Mempool is full.".to_owned() },
+    "mempool_rejected"
+)]
+fn rejections_are_counted_by_reason(
+    #[case] stage: AddTxStage,
+    #[case] error: GatewaySpecError,
+    #[case] expected_reason: &str,
+) {
+    assert_eq!(stage.rejection_reason(&error), expected_reason);
+}
+
+#[test]
+fn mempool_unavailability_is_not_counted_as_a_mempool_rejection() {
+    assert_eq!(
+        AddTxStage::MempoolAddition.rejection_reason(&GatewaySpecError::ServiceUnavailable),
+        "unavailable"
+    );
+}
@@ -0,0 +1,246 @@
+use std::num::NonZeroU128;
+
+use blockifier::blockifier::block::{BlockInfo, GasPrices};
+use blockifier::execution::contract_class::{ContractClass, ContractClassV0, ContractClassV1};
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use papyrus_storage::compiled_class::CasmStorageReader;
+use papyrus_storage::db::RO;
+use papyrus_storage::header::HeaderStorageReader;
+use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::{StorageReader, StorageTxn};
+use starknet_api::block::{BlockNumber, GasPrice};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::state::{StateNumber, StorageKey};
+use starknet_class_manager_types::communication::SharedClassManagerClient;
+use starknet_types_core::felt::Felt;
+use tokio::runtime::Handle;
+
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+#[cfg(test)]
+#[path = "papyrus_state_reader_test.rs"]
+mod papyrus_state_reader_test;
+
+/// Reads the state at the end of a block from the node's storage, as an alternative to reading it
+/// from an RPC server.
+pub struct PapyrusStateReader {
+    storage_reader: StorageReader,
+    // The block at the end of which the state is read. If not set, the latest block in the
+    // storage is read.
+    block_number: Option<BlockNumber>,
+    // Provides the compiled classes of the classes declared in the blocks built by this node,
+    // which the storage doesn't hold.
+    class_manager_client: Option<SharedClassManagerClient>,
+}
+
+impl PapyrusStateReader {
+    pub fn from_number(
+        storage_reader: StorageReader,
+        block_number: BlockNumber,
+        class_manager_client: Option<SharedClassManagerClient>,
+    ) -> Self {
+        Self { storage_reader, block_number: Some(block_number), class_manager_client }
+    }
+
+    pub fn from_latest(
+        storage_reader: StorageReader,
+        class_manager_client: Option<SharedClassManagerClient>,
+    ) -> Self {
+        Self { storage_reader, block_number: None, class_manager_client }
+    }
+
+    fn reader(&self) -> StateResult<StorageTxn<'_, RO>> {
+        self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)
+    }
+
+    fn block_number(&self) -> StateResult<BlockNumber> {
+        if let Some(block_number) = self.block_number {
+            return Ok(block_number);
+        }
+        let state_marker = self.reader()?.get_state_marker().map_err(storage_err_to_state_err)?;
+        state_marker
+            .prev()
+            .ok_or_else(|| StateError::StateReadError("The storage has no blocks.".to_owned()))
+    }
+
+    fn state_number(&self) -> StateResult<StateNumber> {
+        Ok(StateNumber::unchecked_right_after_block(self.block_number()?))
+    }
+
+    // Returns the block the given class was declared in, if it was declared up to the state.
+    fn get_class_declaration_block_number(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<Option<BlockNumber>> {
+        let state_number = self.state_number()?;
+        let declaration_block_number = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| state_reader.get_class_definition_block_number(&class_hash))
+            .map_err(storage_err_to_state_err)?;
+        Ok(declaration_block_number.filter(|block_number| state_number.is_after(*block_number)))
+    }
+
+    // The state is read on a blocking thread of the runtime, which waits for the class manager.
+    fn get_casm_from_class_manager(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<Option<CasmContractClass>> {
+        let Some(class_manager_client) = &self.class_manager_client else {
+            return Ok(None);
+        };
+        Handle::current()
+            .block_on(class_manager_client.get_executable(class_hash))
+            .map_err(|err| StateError::StateReadError(err.to_string()))
+    }
+}
+
+impl MempoolStateReader for PapyrusStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        let block_number = self.block_number()?;
+        let header = self
+            .reader()?
+            .get_block_header(block_number)
+            .map_err(storage_err_to_state_err)?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!("Missing the header of block {block_number}."))
+            })?;
+        Ok(BlockInfo {
+            block_number,
+            block_timestamp: header.timestamp,
+            sequencer_address: header.sequencer.0,
+            gas_prices: GasPrices::new(
+                to_non_zero_gas_price(header.l1_gas_price.price_in_wei)?,
+                to_non_zero_gas_price(header.l1_gas_price.price_in_fri)?,
+                to_non_zero_gas_price(header.l1_data_gas_price.price_in_wei)?,
+                to_non_zero_gas_price(header.l1_data_gas_price.price_in_fri)?,
+                // TODO: Take the L2 gas prices from the header once they are stored.
+                NonZeroU128::MIN,
+                NonZeroU128::MIN,
+            ),
+            use_kzg_da: header.l1_da_mode == L1DataAvailabilityMode::Blob,
+        })
+    }
+}
+
+impl BlockifierStateReader for PapyrusStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let state_number = self.state_number()?;
+        self.reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_storage_at(state_number, &contract_address, &key)
+            })
+            .map_err(storage_err_to_state_err)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let state_number = self.state_number()?;
+        let nonce = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| state_reader.get_nonce_at(state_number, &contract_address))
+            .map_err(storage_err_to_state_err)?;
+        Ok(nonce.unwrap_or_default())
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let state_number = self.state_number()?;
+        let class_hash = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_class_hash_at(state_number, &contract_address)
+            })
+            .map_err(storage_err_to_state_err)?;
+        Ok(class_hash.unwrap_or_default())
+    }
+
+    /// Returns the compiled class of a declared Cairo 1 class, or of a deprecated (Cairo 0) class.
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        if self.get_class_declaration_block_number(class_hash)?.is_some() {
+            let mut casm =
+                self.reader()?.get_casm(&class_hash).map_err(storage_err_to_state_err)?;
+            if casm.is_none() {
+                casm = self.get_casm_from_class_manager(class_hash)?;
+            }
+            let casm = casm.ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "Missing the compiled class of declared class {class_hash}."
+                ))
+            })?;
+            return Ok(ContractClass::V1(ContractClassV1::try_from(casm)?));
+        }
+
+        let state_number = self.state_number()?;
+        let deprecated_class = self
+            .reader()?
+            .get_state_reader()
+            .and_then(|state_reader| {
+                state_reader.get_deprecated_class_definition_at(state_number, &class_hash)
+            })
+            .map_err(storage_err_to_state_err)?;
+        match deprecated_class {
+            Some(deprecated_class) => Ok(ContractClassV0::try_from(deprecated_class)?.into()),
+            None => Err(StateError::UndeclaredClassHash(class_hash)),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let Some(declaration_block_number) = self.get_class_declaration_block_number(class_hash)?
+        else {
+            return Ok(CompiledClassHash::default());
+        };
+        let state_diff = self
+            .reader()?
+            .get_state_diff(declaration_block_number)
+            .map_err(storage_err_to_state_err)?;
+        state_diff
+            .and_then(|state_diff| state_diff.declared_classes.get(&class_hash).copied())
+            .ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "Missing the compiled class hash of declared class {class_hash}."
+                ))
+            })
+    }
+}
+
+/// Creates readers of the state in the node's storage.
+pub struct PapyrusStateReaderFactory {
+    pub storage_reader: StorageReader,
+    pub class_manager_client: Option<SharedClassManagerClient>,
+}
+
+impl StateReaderFactory for PapyrusStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        Box::new(PapyrusStateReader::from_latest(
+            self.storage_reader.clone(),
+            self.class_manager_client.clone(),
+        ))
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        Box::new(PapyrusStateReader::from_number(
+            self.storage_reader.clone(),
+            block_number,
+            self.class_manager_client.clone(),
+        ))
+    }
+}
+
+fn to_non_zero_gas_price(gas_price: GasPrice) -> StateResult<NonZeroU128> {
+    NonZeroU128::new(gas_price.0).ok_or_else(|| {
+        StateError::StateReadError(format!("Expected a positive gas price, got {gas_price:?}."))
+    })
+}
+
+fn storage_err_to_state_err(err: papyrus_storage::StorageError) -> StateError {
+    StateError::StateReadError(err.to_string())
+}
@@ -0,0 +1,91 @@
+use assert_matches::assert_matches;
+use blockifier::state::errors::StateError;
+use indexmap::indexmap;
+use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::state::StateStorageWriter;
+use papyrus_storage::test_utils::get_test_storage;
+use papyrus_storage::StorageWriter;
+use starknet_api::block::{BlockHeader, BlockNumber, GasPrice, GasPricePerToken};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::state::{StorageKey, ThinStateDiff};
+use starknet_api::{contract_address, felt, patricia_key};
+
+use crate::papyrus_state_reader::PapyrusStateReaderFactory;
+use crate::state_reader::StateReaderFactory;
+
+const GAS_PRICE: GasPricePerToken =
+    GasPricePerToken { price_in_fri: GasPrice(10), price_in_wei: GasPrice(1) };
+
+fn append_block(
+    storage_writer: &mut StorageWriter,
+    block_number: BlockNumber,
+    state_diff: ThinStateDiff,
+) {
+    let header = BlockHeader {
+        block_number,
+        l1_gas_price: GAS_PRICE,
+        l1_data_gas_price: GAS_PRICE,
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &header)
+        .unwrap()
+        .append_state_diff(block_number, state_diff)
+        .unwrap()
+        .commit()
+        .unwrap();
+}
+
+fn state_diff(address: ContractAddress, nonce: Nonce, storage_value: u8) -> ThinStateDiff {
+    ThinStateDiff {
+        nonces: indexmap!(address => nonce),
+        storage_diffs: indexmap!(address => indexmap!(StorageKey::from(1_u128) => felt!(storage_value))),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reads_the_state_of_the_storage() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let address = contract_address!("0x100");
+    append_block(&mut storage_writer, BlockNumber(0), state_diff(address, Nonce(felt!(1_u8)), 1));
+    append_block(&mut storage_writer, BlockNumber(1), state_diff(address, Nonce(felt!(2_u8)), 2));
+    let factory = PapyrusStateReaderFactory { storage_reader, class_manager_client: None };
+
+    let latest_state_reader = factory.get_state_reader_from_latest_block();
+    assert_eq!(latest_state_reader.get_nonce_at(address).unwrap(), Nonce(felt!(2_u8)));
+    assert_eq!(
+        latest_state_reader.get_storage_at(address, StorageKey::from(1_u128)).unwrap(),
+        felt!(2_u8)
+    );
+    let block_info = latest_state_reader.get_block_info().unwrap();
+    assert_eq!(block_info.block_number, BlockNumber(1));
+    assert_eq!(block_info.gas_prices.strk_l1_gas_price.get(), GAS_PRICE.price_in_fri.0);
+
+    let state_reader = factory.get_state_reader(BlockNumber(0));
+    assert_eq!(state_reader.get_nonce_at(address).unwrap(), Nonce(felt!(1_u8)));
+    assert_eq!(
+        state_reader.get_storage_at(address, StorageKey::from(1_u128)).unwrap(),
+        felt!(1_u8)
+    );
+    assert_eq!(state_reader.get_block_info().unwrap().block_number, BlockNumber(0));
+
+    // Accounts missing from the state have no nonce.
+    assert_eq!(
+        latest_state_reader.get_nonce_at(contract_address!("0x200")).unwrap(),
+        Nonce::default()
+    );
+}
+
+#[test]
+fn empty_storage_has_no_latest_block() {
+    let ((storage_reader, _storage_writer), _temp_dir) = get_test_storage();
+    let factory = PapyrusStateReaderFactory { storage_reader, class_manager_client: None };
+
+    assert_matches!(
+        factory.get_state_reader_from_latest_block().get_block_info(),
+        Err(StateError::StateReadError(_))
+    );
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use blockifier::blockifier::block::BlockInfo;
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::{StorageKey, ThinStateDiff};
+use starknet_batcher_types::communication::SharedBatcherClient;
+use starknet_types_core::felt::Felt;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+#[cfg(test)]
+#[path = "pending_state_reader_test.rs"]
+mod pending_state_reader_test;
+
+/// Reads the state of the block the batcher is building: the state changes of the transactions
+/// added to it so far, on top of the state of the latest committed block.
+pub struct PendingStateReader {
+    committed_state_reader: Box<dyn MempoolStateReader>,
+    state_diff: ThinStateDiff,
+}
+
+impl PendingStateReader {
+    pub fn new(
+        committed_state_reader: Box<dyn MempoolStateReader>,
+        state_diff: ThinStateDiff,
+    ) -> Self {
+        Self { committed_state_reader, state_diff }
+    }
+}
+
+impl MempoolStateReader for PendingStateReader {
+    // The info of the committed block, as transactions are validated in the context of the block
+    // following it either way.
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        self.committed_state_reader.get_block_info()
+    }
+}
+
+impl BlockifierStateReader for PendingStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        match self.state_diff.storage_diffs.get(&contract_address).and_then(|diff| diff.get(&key)) {
+            Some(value) => Ok(*value),
+            None => self.committed_state_reader.get_storage_at(contract_address, key),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        match self.state_diff.nonces.get(&contract_address) {
+            Some(nonce) => Ok(*nonce),
+            None => self.committed_state_reader.get_nonce_at(contract_address),
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let class_hash = self
+            .state_diff
+            .replaced_classes
+            .get(&contract_address)
+            .or_else(|| self.state_diff.deployed_contracts.get(&contract_address));
+        match class_hash {
+            Some(class_hash) => Ok(*class_hash),
+            None => self.committed_state_reader.get_class_hash_at(contract_address),
+        }
+    }
+
+    // The pending state has no classes, as the batcher doesn't publish the declared ones.
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.committed_state_reader.get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        match self.state_diff.declared_classes.get(&class_hash) {
+            Some(compiled_class_hash) => Ok(*compiled_class_hash),
+            None => self.committed_state_reader.get_compiled_class_hash(class_hash),
+        }
+    }
+}
+
+/// Creates readers of the latest state with the state changes of the block being built on top, so
+/// that transactions following the ones in the block pass validation, e.g., the next transactions
+/// of an account. Readers of earlier blocks read the committed state only.
+pub struct PendingStateReaderFactory {
+    pub committed_state_reader_factory: Arc<dyn StateReaderFactory>,
+    pub batcher_client: SharedBatcherClient,
+}
+
+impl PendingStateReaderFactory {
+    // Returns the state changes of the block being built on top of the given block, if any. Called
+    // from the blocking threads the validation runs on.
+    fn pending_state_diff(&self, block_number: BlockNumber) -> Option<ThinStateDiff> {
+        let pending_state =
+            match Handle::current().block_on(self.batcher_client.get_pending_state()) {
+                Ok(pending_state) => pending_state?,
+                Err(err) => {
+                    warn!("Failed to get the pending state, reading the committed state: {}", err);
+                    return None;
+                }
+            };
+        (pending_state.block_number == block_number.unchecked_next())
+            .then_some(pending_state.state_diff)
+    }
+
+    fn with_pending_state(
+        &self,
+        committed_state_reader: Box<dyn MempoolStateReader>,
+        block_number: BlockNumber,
+    ) -> Box<dyn MempoolStateReader> {
+        match self.pending_state_diff(block_number) {
+            Some(state_diff) => {
+                Box::new(PendingStateReader::new(committed_state_reader, state_diff))
+            }
+            None => committed_state_reader,
+        }
+    }
+}
+
+impl StateReaderFactory for PendingStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        let committed_state_reader =
+            self.committed_state_reader_factory.get_state_reader_from_latest_block();
+        match committed_state_reader.get_block_info() {
+            Ok(block_info) => {
+                self.with_pending_state(committed_state_reader, block_info.block_number)
+            }
+            // The committed state is read as is; the caller reports its errors.
+            Err(_) => committed_state_reader,
+        }
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        let committed_state_reader =
+            self.committed_state_reader_factory.get_state_reader(block_number);
+        self.with_pending_state(committed_state_reader, block_number)
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::CairoVersion;
+use rstest::rstest;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_api::felt;
+use starknet_api::state::ThinStateDiff;
+use starknet_batcher_types::batcher_types::PendingState;
+use starknet_batcher_types::communication::{BatcherClientResult, MockBatcherClient};
+use starknet_mempool_infra::component_client::ClientError;
+
+use crate::pending_state_reader::PendingStateReaderFactory;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+use crate::state_reader_test_utils::local_test_state_reader_factory;
+
+fn pending_state_reader_factory(
+    pending_state: BatcherClientResult<Option<PendingState>>,
+) -> PendingStateReaderFactory {
+    let mut batcher_client = MockBatcherClient::new();
+    batcher_client.expect_get_pending_state().return_once(|| pending_state);
+    PendingStateReaderFactory {
+        committed_state_reader_factory: Arc::new(local_test_state_reader_factory(
+            CairoVersion::Cairo0,
+            false,
+        )),
+        batcher_client: Arc::new(batcher_client),
+    }
+}
+
+fn pending_nonce() -> Nonce {
+    Nonce(felt!(7_u8))
+}
+
+fn latest_block_number() -> BlockNumber {
+    let committed_state_reader_factory =
+        local_test_state_reader_factory(CairoVersion::Cairo0, false);
+    committed_state_reader_factory.state_reader.get_block_info().unwrap().block_number
+}
+
+fn account_nonce_pending_at(block_number: BlockNumber) -> Option<PendingState> {
+    let account_address =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0).get_instance_address(0);
+    Some(PendingState {
+        block_number,
+        state_diff: ThinStateDiff {
+            nonces: [(account_address, pending_nonce())].into(),
+            ..Default::default()
+        },
+    })
+}
+
+// The factory is called from the blocking threads the validation runs on.
+async fn read_account_nonce(factory: PendingStateReaderFactory) -> Nonce {
+    let account_address =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0).get_instance_address(0);
+    tokio::task::spawn_blocking(move || {
+        factory.get_state_reader_from_latest_block().get_nonce_at(account_address).unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn reads_the_pending_state_of_the_next_block() {
+    let factory = pending_state_reader_factory(Ok(account_nonce_pending_at(
+        latest_block_number().unchecked_next(),
+    )));
+
+    assert_eq!(read_account_nonce(factory).await, pending_nonce());
+}
+
+#[rstest]
+#[case::no_pending_state(Ok(None))]
+#[case::pending_state_of_other_block(Ok(account_nonce_pending_at(latest_block_number())))]
+#[case::batcher_failure(Err(ClientError::CircuitOpen.into()))]
+#[tokio::test]
+async fn reads_the_committed_state_without_a_pending_state_of_the_next_block(
+    #[case] pending_state: BatcherClientResult<Option<PendingState>>,
+) {
+    let factory = pending_state_reader_factory(pending_state);
+
+    assert_eq!(read_account_nonce(factory).await, Nonce::default());
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use starknet_api::core::ContractAddress;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+
+use crate::config::{RateLimitConfig, TokenBucketConfig};
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "rate_limiting_test.rs"]
+mod rate_limiting_test;
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// The token buckets of a limit, by key. Keys without a bucket have a full one.
+struct TokenBuckets<K> {
+    buckets: Mutex<HashMap<K, TokenBucket>>,
+}
+
+impl<K: Eq + Hash> TokenBuckets<K> {
+    fn new() -> Self {
+        Self { buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns whether the key may submit at the given time under the given limit, taking a token
+    /// of its bucket if so. At most `capacity` keys are tracked.
+    fn try_acquire(
+        &self,
+        key: K,
+        limit: &TokenBucketConfig,
+        capacity: usize,
+        now: Instant,
+    ) -> bool {
+        let burst = f64::from(limit.burst);
+        let mut buckets = self.buckets.lock().expect("The rate limiter lock is poisoned.");
+        if buckets.len() >= capacity && !buckets.contains_key(&key) {
+            // Refilled buckets are equivalent to missing ones.
+            buckets.retain(|_, bucket| refilled_tokens(bucket, limit, now) < burst);
+            if buckets.len() >= capacity {
+                return false;
+            }
+        }
+
+        let bucket = buckets.entry(key).or_insert(TokenBucket { tokens: burst, last_refill: now });
+        bucket.tokens = refilled_tokens(bucket, limit, now);
+        bucket.last_refill = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+fn refilled_tokens(bucket: &TokenBucket, limit: &TokenBucketConfig, now: Instant) -> f64 {
+    let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+    (bucket.tokens + elapsed * limit.refill_rate).min(limit.burst.into())
+}
+
+/// Limits the rate of submissions of each sender, and of each client IP submitting over HTTP, so
+/// that a single spamming account or client can't flood the mempool. Declare transactions, whose
+/// compilation and class storage are expensive, are further limited per sender.
+pub struct RateLimiter {
+    config: SharedDynamicConfig<RateLimitConfig>,
+    sender_buckets: TokenBuckets<ContractAddress>,
+    declare_sender_buckets: TokenBuckets<ContractAddress>,
+    ip_buckets: TokenBuckets<IpAddr>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            config: SharedDynamicConfig::new(config.clone()),
+            sender_buckets: TokenBuckets::new(),
+            declare_sender_buckets: TokenBuckets::new(),
+            ip_buckets: TokenBuckets::new(),
+        }
+    }
+
+    /// The config of the limits, which can be changed at runtime. The buckets are kept across
+    /// changes, and hold at most the new burst.
+    pub fn config(&self) -> SharedDynamicConfig<RateLimitConfig> {
+        self.config.clone()
+    }
+
+    pub fn check_sender(&self, sender_address: ContractAddress) -> GatewayResult<()> {
+        let RateLimitConfig { sender_limit, capacity, .. } = self.config.get();
+        check(&self.sender_buckets, &sender_limit, capacity, sender_address)
+    }
+
+    pub fn check_declare_sender(&self, sender_address: ContractAddress) -> GatewayResult<()> {
+        let RateLimitConfig { declare_sender_limit, capacity, .. } = self.config.get();
+        check(&self.declare_sender_buckets, &declare_sender_limit, capacity, sender_address)
+    }
+
+    pub fn check_ip(&self, ip: IpAddr) -> GatewayResult<()> {
+        let RateLimitConfig { ip_limit, capacity, .. } = self.config.get();
+        check(&self.ip_buckets, &ip_limit, capacity, ip)
+    }
+}
+
+fn check<K: Eq + Hash>(
+    token_buckets: &TokenBuckets<K>,
+    limit: &TokenBucketConfig,
+    capacity: usize,
+    key: K,
+) -> GatewayResult<()> {
+    if limit.enable && !token_buckets.try_acquire(key, limit, capacity, Instant::now()) {
+        return Err(GatewaySpecError::TooManyRequests);
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::{Duration, Instant};
+
+use starknet_api::core::ContractAddress;
+
+use crate::config::{RateLimitConfig, TokenBucketConfig};
+use crate::errors::GatewaySpecError;
+use crate::rate_limiting::{RateLimiter, TokenBuckets};
+
+// Token buckets with a fixed limit.
+struct LimitedTokenBuckets {
+    token_buckets: TokenBuckets<u8>,
+    limit: TokenBucketConfig,
+    capacity: usize,
+}
+
+impl LimitedTokenBuckets {
+    fn try_acquire(&self, key: u8, now: Instant) -> bool {
+        self.token_buckets.try_acquire(key, &self.limit, self.capacity, now)
+    }
+}
+
+fn token_buckets(burst: u32, refill_rate: f64, capacity: usize) -> LimitedTokenBuckets {
+    LimitedTokenBuckets {
+        token_buckets: TokenBuckets::new(),
+        limit: TokenBucketConfig { enable: true, burst, refill_rate },
+        capacity,
+    }
+}
+
+#[test]
+fn keys_are_limited_to_their_burst() {
+    let token_buckets = token_buckets(2, 1.0, 10);
+    let now = Instant::now();
+
+    assert!(token_buckets.try_acquire(1, now));
+    assert!(token_buckets.try_acquire(1, now));
+    assert!(!token_buckets.try_acquire(1, now));
+    // The buckets of other keys are independent.
+    assert!(token_buckets.try_acquire(2, now));
+}
+
+#[test]
+fn buckets_are_refilled_at_the_refill_rate() {
+    let token_buckets = token_buckets(2, 2.0, 10);
+    let now = Instant::now();
+    assert!(token_buckets.try_acquire(1, now));
+    assert!(token_buckets.try_acquire(1, now));
+
+    let later = now + Duration::from_millis(500);
+    assert!(token_buckets.try_acquire(1, later));
+    assert!(!token_buckets.try_acquire(1, later));
+
+    // Buckets are not refilled beyond the burst.
+    let much_later = later + Duration::from_secs(10);
+    assert!(token_buckets.try_acquire(1, much_later));
+    assert!(token_buckets.try_acquire(1, much_later));
+    assert!(!token_buckets.try_acquire(1, much_later));
+}
+
+#[test]
+fn untracked_keys_are_limited_beyond_the_capacity() {
+    let token_buckets = token_buckets(1, 1.0, 1);
+    let now = Instant::now();
+    assert!(token_buckets.try_acquire(1, now));
+
+    assert!(!token_buckets.try_acquire(2, now));
+
+    // Once the bucket of the tracked key is refilled, it is no longer tracked.
+    assert!(token_buckets.try_acquire(2, now + Duration::from_secs(1)));
+}
+
+#[test]
+fn limited_senders_get_too_many_requests() {
+    let rate_limiter = RateLimiter::new(&RateLimitConfig {
+        sender_limit: TokenBucketConfig { enable: true, burst: 1, refill_rate: 0.0 },
+        ..Default::default()
+    });
+    let sender_address = ContractAddress::from(1_u128);
+
+    assert_eq!(rate_limiter.check_sender(sender_address), Ok(()));
+    assert_eq!(rate_limiter.check_sender(sender_address), Err(GatewaySpecError::TooManyRequests));
+}
+
+#[test]
+fn declares_are_limited_apart_from_other_transactions() {
+    let rate_limiter = RateLimiter::new(&RateLimitConfig {
+        declare_sender_limit: TokenBucketConfig { enable: true, burst: 1, refill_rate: 0.0 },
+        ..Default::default()
+    });
+    let sender_address = ContractAddress::from(1_u128);
+
+    assert_eq!(rate_limiter.check_declare_sender(sender_address), Ok(()));
+    assert_eq!(
+        rate_limiter.check_declare_sender(sender_address),
+        Err(GatewaySpecError::TooManyRequests)
+    );
+    assert_eq!(rate_limiter.check_sender(sender_address), Ok(()));
+}
+
+#[test]
+fn disabled_limits_are_not_enforced() {
+    let rate_limiter = RateLimiter::new(&RateLimitConfig {
+        ip_limit: TokenBucketConfig { enable: false, burst: 1, refill_rate: 0.0 },
+        ..Default::default()
+    });
+    let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    for _ in 0..3 {
+        assert_eq!(rate_limiter.check_ip(ip), Ok(()));
+    }
+}
+
+#[test]
+fn limits_change_at_runtime() {
+    let config = RateLimitConfig {
+        sender_limit: TokenBucketConfig { enable: true, burst: 3, refill_rate: 0.0 },
+        ..Default::default()
+    };
+    let rate_limiter = RateLimiter::new(&config);
+    let sender_address = ContractAddress::from(1_u128);
+    assert_eq!(rate_limiter.check_sender(sender_address), Ok(()));
+
+    // The bucket of the sender holds at most the new burst.
+    rate_limiter.config().set(RateLimitConfig {
+        sender_limit: TokenBucketConfig { enable: true, burst: 1, refill_rate: 0.0 },
+        ..config.clone()
+    });
+    assert_eq!(rate_limiter.check_sender(sender_address), Ok(()));
+    assert_eq!(rate_limiter.check_sender(sender_address), Err(GatewaySpecError::TooManyRequests));
+
+    rate_limiter.config().set(RateLimitConfig {
+        sender_limit: TokenBucketConfig { enable: false, burst: 1, refill_rate: 0.0 },
+        ..config
+    });
+    assert_eq!(rate_limiter.check_sender(sender_address), Ok(()));
+}
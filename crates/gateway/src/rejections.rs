@@ -0,0 +1,194 @@
+use axum::http::StatusCode;
+use papyrus_rpc::error::{
+    validation_failure,
+    JsonRpcError,
+    CONTRACT_CLASS_SIZE_IS_TOO_LARGE,
+    UNSUPPORTED_CONTRACT_CLASS_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use starknet_mempool_types::errors::MempoolError;
+
+use crate::errors::GatewaySpecError;
+
+#[cfg(test)]
+#[path = "rejections_test.rs"]
+mod rejections_test;
+
+/// The reason a request was rejected, as answered to the HTTP clients of the gateway, e.g.,
+/// `INVALID_NONCE`. Codes are never renamed or reused, so that clients, such as wallets, may match
+/// on them to present an actionable message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RejectionCode {
+    // Stateless validation.
+    CalldataTooLong,
+    ContractClassTooLarge,
+    EntryPointsNotUniquelySorted,
+    InvalidSierraVersion,
+    MaxFeeOverflow,
+    MaxFeeTooHigh,
+    MaxGasPriceTooLow,
+    NonZeroDeployAccountNonce,
+    SignatureTooLong,
+    TransactionTypeDisabled,
+    UnsupportedContractClassVersion,
+    UnsupportedTransactionVersion,
+    ZeroResourceBounds,
+    // Compilation.
+    CompilationFailed,
+    CompiledClassHashMismatch,
+    // Stateful validation.
+    AccountAlreadyDeployed,
+    AccountValidationFailed,
+    ClassAlreadyDeclared,
+    ClassHashNotFound,
+    DeclareBalanceTooLow,
+    EntryPointNotFound,
+    InsufficientAccountBalance,
+    InsufficientMaxFee,
+    InvalidNonce,
+    NonAccount,
+    ValidationFailed,
+    // Admission, by the gateway and by the mempool.
+    DeclareNotAllowed,
+    DuplicateTransaction,
+    InvalidRequest,
+    MempoolFull,
+    NonceTooFarInFuture,
+    ReplacementTipTooLow,
+    SenderNotAllowed,
+    ServiceUnavailable,
+    TooManyPendingTransactions,
+    TooManyRequests,
+    // The gateway failed to process the request.
+    InternalError,
+}
+
+impl RejectionCode {
+    /// The HTTP status of the responses to requests rejected with this code.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            RejectionCode::DeclareNotAllowed | RejectionCode::SenderNotAllowed => {
+                StatusCode::FORBIDDEN
+            }
+            RejectionCode::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            RejectionCode::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            RejectionCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// The body of the response to a rejected request: the code of the rejection, a message to present
+/// to the user, and the values behind the rejection, e.g., the expected and the actual nonce.
+/// Addresses, hashes, nonces and amounts are given as hex strings, as in transactions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejection {
+    pub code: RejectionCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub data: Map<String, Value>,
+}
+
+impl Rejection {
+    pub fn new(code: RejectionCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), data: Map::new() }
+    }
+
+    /// Adds a value behind the rejection to its data.
+    pub fn with(mut self, key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).expect("Rejection data should be serializable.");
+        self.data.insert(key.to_owned(), value);
+        self
+    }
+
+    /// The Starknet RPC error of the rejection. Rejections without an RPC error of their own are
+    /// validation failures.
+    pub fn into_rpc(self) -> JsonRpcError<String> {
+        match self.code {
+            RejectionCode::ContractClassTooLarge => CONTRACT_CLASS_SIZE_IS_TOO_LARGE,
+            RejectionCode::UnsupportedContractClassVersion => UNSUPPORTED_CONTRACT_CLASS_VERSION,
+            _ => validation_failure(self.message),
+        }
+    }
+}
+
+impl From<GatewaySpecError> for Rejection {
+    fn from(error: GatewaySpecError) -> Self {
+        let code = match &error {
+            GatewaySpecError::InvalidTransaction(rejection) => return rejection.clone(),
+            // Free-form errors are described by their data.
+            GatewaySpecError::UnexpectedError { data } => {
+                return Rejection::new(RejectionCode::InternalError, data.clone());
+            }
+            GatewaySpecError::ValidationFailure { data } => {
+                return Rejection::new(RejectionCode::InvalidRequest, data.clone());
+            }
+            GatewaySpecError::ClassAlreadyDeclared => RejectionCode::ClassAlreadyDeclared,
+            GatewaySpecError::ClassHashNotFound => RejectionCode::ClassHashNotFound,
+            GatewaySpecError::CompiledClassHashMismatch => RejectionCode::CompiledClassHashMismatch,
+            GatewaySpecError::CompilationFailed => RejectionCode::CompilationFailed,
+            GatewaySpecError::ContractClassSizeIsTooLarge => RejectionCode::ContractClassTooLarge,
+            GatewaySpecError::DeclareNotAllowed => RejectionCode::DeclareNotAllowed,
+            GatewaySpecError::DuplicateTx => RejectionCode::DuplicateTransaction,
+            GatewaySpecError::InsufficientAccountBalance => {
+                RejectionCode::InsufficientAccountBalance
+            }
+            GatewaySpecError::InsufficientMaxFee => RejectionCode::InsufficientMaxFee,
+            GatewaySpecError::InvalidTransactionNonce => RejectionCode::InvalidNonce,
+            GatewaySpecError::NonAccount => RejectionCode::NonAccount,
+            GatewaySpecError::SenderNotAllowed => RejectionCode::SenderNotAllowed,
+            GatewaySpecError::ServiceUnavailable => RejectionCode::ServiceUnavailable,
+            GatewaySpecError::TooManyRequests => RejectionCode::TooManyRequests,
+            GatewaySpecError::UnsupportedContractClassVersion => {
+                RejectionCode::UnsupportedContractClassVersion
+            }
+            GatewaySpecError::UnsupportedTxVersion => RejectionCode::UnsupportedTransactionVersion,
+        };
+        Rejection::new(code, error.into_rpc().message)
+    }
+}
+
+/// The rejection of a transaction the mempool refused to add.
+pub fn mempool_rejection(error: &MempoolError) -> Rejection {
+    let message = error.to_string();
+    match *error {
+        MempoolError::DuplicateNonce { address, nonce } => {
+            Rejection::new(RejectionCode::DuplicateTransaction, message)
+                .with("sender_address", address)
+                .with("nonce", nonce)
+        }
+        MempoolError::DuplicateTransaction { tx_hash } => {
+            Rejection::new(RejectionCode::DuplicateTransaction, message).with("tx_hash", tx_hash)
+        }
+        MempoolError::MempoolFull { tx_hash } => {
+            Rejection::new(RejectionCode::MempoolFull, message).with("tx_hash", tx_hash)
+        }
+        MempoolError::NonceTooFarInFuture { address, nonce, max_allowed_nonce } => {
+            Rejection::new(RejectionCode::NonceTooFarInFuture, message)
+                .with("sender_address", address)
+                .with("nonce", nonce)
+                .with("max_allowed_nonce", max_allowed_nonce)
+        }
+        MempoolError::TooManyPendingTransactions { address, max_pending_txs } => {
+            Rejection::new(RejectionCode::TooManyPendingTransactions, message)
+                .with("sender_address", address)
+                .with("max_pending_txs", max_pending_txs)
+        }
+        MempoolError::TooManyScheduledTransactions { max_scheduled_txs } => {
+            Rejection::new(RejectionCode::MempoolFull, message)
+                .with("max_scheduled_txs", max_scheduled_txs)
+        }
+        MempoolError::ReplacementTipTooLow { address, nonce, tip, min_tip } => {
+            Rejection::new(RejectionCode::ReplacementTipTooLow, message)
+                .with("sender_address", address)
+                .with("nonce", nonce)
+                .with("tip", tip)
+                .with("min_tip", min_tip)
+        }
+        MempoolError::TransactionStreamUnavailable
+        | MempoolError::TransactionNotFound { .. }
+        | MempoolError::FeltOutOfRange => Rejection::new(RejectionCode::InternalError, message),
+    }
+}
@@ -0,0 +1,95 @@
+use axum::http::StatusCode;
+use papyrus_rpc::error::{validation_failure, CONTRACT_CLASS_SIZE_IS_TOO_LARGE};
+use rstest::rstest;
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::transaction::Tip;
+use starknet_api::{contract_address, felt, patricia_key};
+use starknet_mempool_types::errors::MempoolError;
+
+use crate::errors::GatewaySpecError;
+use crate::rejections::{mempool_rejection, Rejection, RejectionCode};
+
+#[test]
+fn rejection_is_serialized_with_its_code_message_and_data() {
+    let rejection = Rejection::new(RejectionCode::InvalidNonce, "Invalid nonce.")
+        .with("expected_nonce", Nonce(felt!(5_u8)))
+        .with("actual_nonce", Nonce(felt!(3_u8)));
+
+    assert_eq!(
+        serde_json::to_value(&rejection).unwrap(),
+        serde_json::json!({
+            "code": "INVALID_NONCE",
+            "message": "Invalid nonce.",
+            "data": {"expected_nonce": "0x5", "actual_nonce": "0x3"},
+        })
+    );
+    // Rejections without data are answered without it.
+    assert_eq!(
+        serde_json::to_value(Rejection::new(RejectionCode::DuplicateTransaction, "Duplicate."))
+            .unwrap(),
+        serde_json::json!({"code": "DUPLICATE_TRANSACTION", "message": "Duplicate."})
+    );
+}
+
+#[rstest]
+#[case::duplicate(GatewaySpecError::DuplicateTx, RejectionCode::DuplicateTransaction)]
+#[case::rate_limited(GatewaySpecError::TooManyRequests, RejectionCode::TooManyRequests)]
+#[case::free_form(
+    GatewaySpecError::ValidationFailure { data: "Invalid request.".to_owned() },
+    RejectionCode::InvalidRequest
+)]
+#[case::internal(
+    GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() },
+    RejectionCode::InternalError
+)]
+fn spec_errors_are_answered_with_their_code(
+    #[case] error: GatewaySpecError,
+    #[case] expected_code: RejectionCode,
+) {
+    assert_eq!(Rejection::from(error).code, expected_code);
+}
+
+#[test]
+fn free_form_errors_are_answered_with_their_data_as_message() {
+    let error = GatewaySpecError::ValidationFailure { data: "Invalid request.".to_owned() };
+    assert_eq!(Rejection::from(error).message, "Invalid request.");
+}
+
+#[rstest]
+#[case::rejected(RejectionCode::InvalidNonce, StatusCode::BAD_REQUEST)]
+#[case::forbidden(RejectionCode::SenderNotAllowed, StatusCode::FORBIDDEN)]
+#[case::rate_limited(RejectionCode::TooManyRequests, StatusCode::TOO_MANY_REQUESTS)]
+#[case::unavailable(RejectionCode::ServiceUnavailable, StatusCode::SERVICE_UNAVAILABLE)]
+#[case::internal(RejectionCode::InternalError, StatusCode::INTERNAL_SERVER_ERROR)]
+fn rejections_are_answered_in_the_status_of_their_code(
+    #[case] code: RejectionCode,
+    #[case] expected_status: StatusCode,
+) {
+    assert_eq!(code.status_code(), expected_status);
+}
+
+#[test]
+fn rejections_keep_their_rpc_error() {
+    let as_rpc = Rejection::new(RejectionCode::ContractClassTooLarge, "Too large.").into_rpc();
+    assert_eq!(as_rpc.code, CONTRACT_CLASS_SIZE_IS_TOO_LARGE.code);
+
+    let as_rpc = Rejection::new(RejectionCode::InvalidNonce, "Invalid nonce.").into_rpc();
+    let expected_rpc = validation_failure("Invalid nonce.".to_owned());
+    assert_eq!((as_rpc.code, as_rpc.data), (expected_rpc.code, expected_rpc.data));
+}
+
+#[test]
+fn mempool_rejection_carries_the_values_of_the_error() {
+    let error = MempoolError::ReplacementTipTooLow {
+        address: contract_address!("0x100"),
+        nonce: Nonce(felt!(1_u8)),
+        tip: Tip(10),
+        min_tip: Tip(11),
+    };
+
+    let rejection = mempool_rejection(&error);
+
+    assert_eq!(rejection.code, RejectionCode::ReplacementTipTooLow);
+    assert_eq!(rejection.message, error.to_string());
+    assert_eq!(rejection.data["min_tip"], "0xb");
+}
@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use starknet_api::core::ContractAddress;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::TransactionHash;
+use tracing::info;
+
+use crate::config::RequestLoggingConfig;
+use crate::errors::GatewayResult;
+
+#[cfg(test)]
+#[path = "request_logging_test.rs"]
+mod request_logging_test;
+
+/// The fields of a request that are logged, captured before the transaction is consumed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestSummary {
+    pub tx_type: &'static str,
+    pub sender: Option<ContractAddress>,
+}
+
+impl RequestSummary {
+    pub fn new(tx: &RpcTransaction) -> Self {
+        let tx_type = match tx {
+            RpcTransaction::Declare(_) => "Declare",
+            RpcTransaction::DeployAccount(_) => "DeployAccount",
+            RpcTransaction::Invoke(_) => "Invoke",
+        };
+        Self { tx_type, sender: tx.calculate_sender_address().ok() }
+    }
+}
+
+/// Emits a structured log per gateway request. Rejections are always logged, while accepted
+/// transactions are sampled to keep the log volume bounded at high TPS.
+#[derive(Debug)]
+pub struct RequestLogger {
+    success_sampling_interval: u64,
+    n_successes: AtomicU64,
+}
+
+impl RequestLogger {
+    pub fn new(config: &RequestLoggingConfig) -> Self {
+        Self {
+            success_sampling_interval: config.success_sampling_interval,
+            n_successes: AtomicU64::new(0),
+        }
+    }
+
+    pub fn log(
+        &self,
+        method: &str,
+        summary: &RequestSummary,
+        result: &GatewayResult<TransactionHash>,
+        latency: Duration,
+    ) {
+        let RequestSummary { tx_type, sender } = summary;
+        let latency_micros = latency.as_micros();
+        match result {
+            Ok(tx_hash) => {
+                if self.should_log_success() {
+                    info!(
+                        target: "gateway_requests",
+                        method,
+                        tx_type,
+                        sender = ?sender,
+                        %tx_hash,
+                        latency_micros,
+                        outcome = "accepted",
+                        "Gateway request."
+                    );
+                }
+            }
+            Err(error) => {
+                info!(
+                    target: "gateway_requests",
+                    method,
+                    tx_type,
+                    sender = ?sender,
+                    error = %error,
+                    latency_micros,
+                    outcome = "rejected",
+                    "Gateway request."
+                );
+            }
+        }
+    }
+
+    fn should_log_success(&self) -> bool {
+        if self.success_sampling_interval == 0 {
+            return false;
+        }
+        self.n_successes.fetch_add(1, Ordering::Relaxed) % self.success_sampling_interval == 0
+    }
+}
@@ -0,0 +1,15 @@
+use rstest::rstest;
+
+use crate::config::RequestLoggingConfig;
+use crate::request_logging::RequestLogger;
+
+#[rstest]
+#[case::log_all(1, vec![true, true, true])]
+#[case::sampled(2, vec![true, false, true, false, true])]
+#[case::disabled(0, vec![false, false, false])]
+fn success_sampling(#[case] success_sampling_interval: u64, #[case] expected: Vec<bool>) {
+    let logger = RequestLogger::new(&RequestLoggingConfig { success_sampling_interval });
+
+    let sampled: Vec<bool> = expected.iter().map(|_| logger.should_log_success()).collect();
+    assert_eq!(sampled, expected);
+}
@@ -78,18 +78,25 @@ pub struct BlockHeader {
     pub starknet_version: String,
 }
 
-impl TryInto<BlockInfo> for BlockHeader {
-    type Error = RPCStateReaderError;
-    fn try_into(self) -> Result<BlockInfo, Self::Error> {
+impl BlockHeader {
+    /// Converts the header to the block info used for validation. If `strk_per_eth_rate` is set, a
+    /// gas price given in a single fee token is derived in the other one, as transactions pay fees
+    /// in the fee token of their version (STRK for V3, ETH otherwise).
+    pub fn into_block_info(
+        self,
+        strk_per_eth_rate: Option<u64>,
+    ) -> Result<BlockInfo, RPCStateReaderError> {
+        let l1_gas_price = self.l1_gas_price.complete_with_rate(strk_per_eth_rate);
+        let l1_data_gas_price = self.l1_data_gas_price.complete_with_rate(strk_per_eth_rate);
         Ok(BlockInfo {
             block_number: self.block_number,
             sequencer_address: self.sequencer_address,
             block_timestamp: self.timestamp,
             gas_prices: GasPrices::new(
-                parse_gas_price(self.l1_gas_price.price_in_wei)?,
-                parse_gas_price(self.l1_gas_price.price_in_fri)?,
-                parse_gas_price(self.l1_data_gas_price.price_in_wei)?,
-                parse_gas_price(self.l1_data_gas_price.price_in_fri)?,
+                parse_gas_price(l1_gas_price.price_in_wei)?,
+                parse_gas_price(l1_gas_price.price_in_fri)?,
+                parse_gas_price(l1_data_gas_price.price_in_wei)?,
+                parse_gas_price(l1_data_gas_price.price_in_fri)?,
                 // TODO(Aner): add to BlockHeader and take the value from it.
                 NonZeroU128::MIN,
                 NonZeroU128::MIN,
@@ -99,6 +106,33 @@ impl TryInto<BlockInfo> for BlockHeader {
     }
 }
 
+impl TryInto<BlockInfo> for BlockHeader {
+    type Error = RPCStateReaderError;
+    fn try_into(self) -> Result<BlockInfo, Self::Error> {
+        self.into_block_info(None)
+    }
+}
+
+impl ResourcePrice {
+    // Derives a price missing in one fee token from the price in the other one.
+    fn complete_with_rate(self, strk_per_eth_rate: Option<u64>) -> Self {
+        let Some(rate) = strk_per_eth_rate.filter(|&rate| rate != 0).map(u128::from) else {
+            return self;
+        };
+        match (self.price_in_wei.0, self.price_in_fri.0) {
+            // Raised to the minimal price, as a price in fri below the rate is converted to zero
+            // wei, which isn't a valid gas price.
+            (0, price_in_fri) => {
+                Self { price_in_wei: GasPrice((price_in_fri / rate).max(1)), ..self }
+            }
+            (price_in_wei, 0) => {
+                Self { price_in_fri: GasPrice(price_in_wei.saturating_mul(rate)), ..self }
+            }
+            _ => self,
+        }
+    }
+}
+
 fn parse_gas_price(gas_price: GasPrice) -> Result<NonZeroU128, RPCStateReaderError> {
     NonZeroU128::new(gas_price.0).ok_or(RPCStateReaderError::GasPriceParsingFailure(gas_price))
 }
@@ -1,6 +1,7 @@
 use blockifier::blockifier::block::BlockInfo;
 use blockifier::execution::contract_class::{ContractClass, ContractClassV0, ContractClassV1};
 use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::GlobalContractCache;
 use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
 use papyrus_rpc::CompiledContractClass;
 use reqwest::blocking::Client as BlockingClient;
@@ -31,14 +32,20 @@ use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 pub struct RpcStateReader {
     pub config: RpcStateReaderConfig,
     pub block_id: BlockId,
+    // Compiled classes, shared by all the readers of the gateway.
+    pub class_cache: GlobalContractCache,
 }
 
 impl RpcStateReader {
-    pub fn from_number(config: &RpcStateReaderConfig, block_number: BlockNumber) -> Self {
-        Self { config: config.clone(), block_id: BlockId::Number(block_number) }
+    pub fn from_number(
+        config: &RpcStateReaderConfig,
+        block_number: BlockNumber,
+        class_cache: GlobalContractCache,
+    ) -> Self {
+        Self { config: config.clone(), block_id: BlockId::Number(block_number), class_cache }
     }
-    pub fn from_latest(config: &RpcStateReaderConfig) -> Self {
-        Self { config: config.clone(), block_id: BlockId::Latest }
+    pub fn from_latest(config: &RpcStateReaderConfig, class_cache: GlobalContractCache) -> Self {
+        Self { config: config.clone(), block_id: BlockId::Latest, class_cache }
     }
     // Note: This function is blocking though it is sending a request to the rpc server and waiting
     // for the response.
@@ -81,6 +88,25 @@ impl RpcStateReader {
             },
         }
     }
+
+    // Fetches a class from the rpc server and compiles it to a blockifier class.
+    fn fetch_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        let get_compiled_class_params =
+            GetCompiledContractClassParams { class_hash, block_id: self.block_id };
+
+        let result =
+            self.send_rpc_request("starknet_getCompiledContractClass", get_compiled_class_params)?;
+        let contract_class: CompiledContractClass =
+            serde_json::from_value(result).map_err(serde_err_to_state_err)?;
+        match contract_class {
+            CompiledContractClass::V1(contract_class_v1) => Ok(ContractClass::V1(
+                ContractClassV1::try_from(contract_class_v1).map_err(StateError::ProgramError)?,
+            )),
+            CompiledContractClass::V0(contract_class_v0) => Ok(ContractClass::V0(
+                ContractClassV0::try_from(contract_class_v0).map_err(StateError::ProgramError)?,
+            )),
+        }
+    }
 }
 
 impl MempoolStateReader for RpcStateReader {
@@ -92,7 +118,7 @@ impl MempoolStateReader for RpcStateReader {
             self.send_rpc_request("starknet_getBlockWithTxHashes", get_block_params)?,
         )
         .map_err(serde_err_to_state_err)?;
-        let block_info = block_header.try_into()?;
+        let block_info = block_header.into_block_info(self.config.strk_per_eth_rate)?;
         Ok(block_info)
     }
 }
@@ -126,21 +152,13 @@ impl BlockifierStateReader for RpcStateReader {
     }
 
     fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
-        let get_compiled_class_params =
-            GetCompiledContractClassParams { class_hash, block_id: self.block_id };
-
-        let result =
-            self.send_rpc_request("starknet_getCompiledContractClass", get_compiled_class_params)?;
-        let contract_class: CompiledContractClass =
-            serde_json::from_value(result).map_err(serde_err_to_state_err)?;
-        match contract_class {
-            CompiledContractClass::V1(contract_class_v1) => Ok(ContractClass::V1(
-                ContractClassV1::try_from(contract_class_v1).map_err(StateError::ProgramError)?,
-            )),
-            CompiledContractClass::V0(contract_class_v0) => Ok(ContractClass::V0(
-                ContractClassV0::try_from(contract_class_v0).map_err(StateError::ProgramError)?,
-            )),
+        // The class of a given hash never changes, so a cached class is valid in any block.
+        if let Some(contract_class) = self.class_cache.get(&class_hash) {
+            return Ok(contract_class);
         }
+        let contract_class = self.fetch_compiled_contract_class(class_hash)?;
+        self.class_cache.set(class_hash, contract_class.clone());
+        Ok(contract_class)
     }
 
     fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
@@ -166,14 +184,15 @@ impl BlockifierStateReader for RpcStateReader {
 
 pub struct RpcStateReaderFactory {
     pub config: RpcStateReaderConfig,
+    pub class_cache: GlobalContractCache,
 }
 
 impl StateReaderFactory for RpcStateReaderFactory {
     fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
-        Box::new(RpcStateReader::from_latest(&self.config))
+        Box::new(RpcStateReader::from_latest(&self.config, self.class_cache.clone()))
     }
 
     fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
-        Box::new(RpcStateReader::from_number(&self.config, block_number))
+        Box::new(RpcStateReader::from_number(&self.config, block_number, self.class_cache.clone()))
     }
 }
@@ -1,5 +1,7 @@
 use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::global_cache::{GlobalContractCache, GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST};
 use blockifier::state::state_api::StateReader;
+use blockifier::transaction::objects::FeeType;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use papyrus_rpc::CompiledContractClass;
 use serde::Serialize;
@@ -21,8 +23,12 @@ use crate::rpc_objects::{
     RpcResponse,
     RpcSuccessResponse,
 };
-use crate::rpc_state_reader::RpcStateReader;
-use crate::state_reader::MempoolStateReader;
+use crate::rpc_state_reader::{RpcStateReader, RpcStateReaderFactory};
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+
+fn class_cache() -> GlobalContractCache {
+    GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST)
+}
 
 async fn run_rpc_server() -> mockito::ServerGuard {
     mockito::Server::new_async().await
@@ -81,7 +87,7 @@ async fn test_get_block_info() {
         }),
     );
 
-    let client = RpcStateReader::from_latest(&config);
+    let client = RpcStateReader::from_latest(&config, class_cache());
     let result =
         tokio::task::spawn_blocking(move || client.get_block_info()).await.unwrap().unwrap();
     // TODO(yair): Add partial_eq for BlockInfo and assert_eq the whole BlockInfo.
@@ -89,6 +95,92 @@ async fn test_get_block_info() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_get_block_info_converts_missing_gas_prices() {
+    let mut server = run_rpc_server().await;
+    let config = RpcStateReaderConfig {
+        url: server.url(),
+        strk_per_eth_rate: Some(1000),
+        ..Default::default()
+    };
+
+    let mock = mock_rpc_interaction(
+        &mut server,
+        &config.json_rpc_version,
+        "starknet_getBlockWithTxHashes",
+        GetBlockWithTxHashesParams { block_id: BlockId::Latest },
+        &RpcResponse::Success(RpcSuccessResponse {
+            result: serde_json::to_value(BlockHeader {
+                // Gas prices are given in a single fee token.
+                l1_gas_price: ResourcePrice {
+                    price_in_wei: GasPrice(0),
+                    price_in_fri: GasPrice(5000),
+                },
+                l1_data_gas_price: ResourcePrice {
+                    price_in_wei: GasPrice(2),
+                    price_in_fri: GasPrice(0),
+                },
+                ..Default::default()
+            })
+            .unwrap(),
+            ..Default::default()
+        }),
+    );
+
+    let client = RpcStateReader::from_latest(&config, class_cache());
+    let gas_prices = tokio::task::spawn_blocking(move || client.get_block_info())
+        .await
+        .unwrap()
+        .unwrap()
+        .gas_prices;
+    assert_eq!(gas_prices.get_l1_gas_price_by_fee_type(&FeeType::Eth).get(), 5);
+    assert_eq!(gas_prices.get_l1_data_gas_price_by_fee_type(&FeeType::Strk).get(), 2000);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_get_block_info_raises_gas_prices_converted_to_zero() {
+    let mut server = run_rpc_server().await;
+    let config = RpcStateReaderConfig {
+        url: server.url(),
+        strk_per_eth_rate: Some(1000),
+        ..Default::default()
+    };
+
+    let mock = mock_rpc_interaction(
+        &mut server,
+        &config.json_rpc_version,
+        "starknet_getBlockWithTxHashes",
+        GetBlockWithTxHashesParams { block_id: BlockId::Latest },
+        &RpcResponse::Success(RpcSuccessResponse {
+            result: serde_json::to_value(BlockHeader {
+                // Prices in fri below the rate are converted to zero wei.
+                l1_gas_price: ResourcePrice {
+                    price_in_wei: GasPrice(0),
+                    price_in_fri: GasPrice(999),
+                },
+                l1_data_gas_price: ResourcePrice {
+                    price_in_wei: GasPrice(0),
+                    price_in_fri: GasPrice(1),
+                },
+                ..Default::default()
+            })
+            .unwrap(),
+            ..Default::default()
+        }),
+    );
+
+    let client = RpcStateReader::from_latest(&config, class_cache());
+    let gas_prices = tokio::task::spawn_blocking(move || client.get_block_info())
+        .await
+        .unwrap()
+        .unwrap()
+        .gas_prices;
+    assert_eq!(gas_prices.get_l1_gas_price_by_fee_type(&FeeType::Eth).get(), 1);
+    assert_eq!(gas_prices.get_l1_data_gas_price_by_fee_type(&FeeType::Eth).get(), 1);
+    mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_get_storage_at() {
     let mut server = run_rpc_server().await;
@@ -111,7 +203,7 @@ async fn test_get_storage_at() {
         }),
     );
 
-    let client = RpcStateReader::from_latest(&config);
+    let client = RpcStateReader::from_latest(&config, class_cache());
     let result = tokio::task::spawn_blocking(move || {
         client.get_storage_at(contract_address!("0x1"), starknet_api::state::StorageKey::from(0u32))
     })
@@ -140,7 +232,7 @@ async fn test_get_nonce_at() {
         }),
     );
 
-    let client = RpcStateReader::from_latest(&config);
+    let client = RpcStateReader::from_latest(&config, class_cache());
     let result = tokio::task::spawn_blocking(move || client.get_nonce_at(contract_address!("0x1")))
         .await
         .unwrap()
@@ -170,7 +262,7 @@ async fn test_get_compiled_contract_class() {
         }),
     );
 
-    let client = RpcStateReader::from_latest(&config);
+    let client = RpcStateReader::from_latest(&config, class_cache());
     let result =
         tokio::task::spawn_blocking(move || client.get_compiled_contract_class(class_hash!("0x1")))
             .await
@@ -180,6 +272,43 @@ async fn test_get_compiled_contract_class() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn get_compiled_contract_class_is_cached_across_readers() {
+    let mut server = run_rpc_server().await;
+    let config = RpcStateReaderConfig { url: server.url(), ..Default::default() };
+
+    let mock = mock_rpc_interaction(
+        &mut server,
+        &config.json_rpc_version,
+        "starknet_getCompiledContractClass",
+        GetCompiledContractClassParams {
+            block_id: BlockId::Latest,
+            class_hash: class_hash!("0x1"),
+        },
+        &RpcResponse::Success(RpcSuccessResponse {
+            result: serde_json::to_value(CompiledContractClass::V1(CasmContractClass::default()))
+                .unwrap(),
+            ..Default::default()
+        }),
+    );
+
+    let factory = RpcStateReaderFactory { config, class_cache: class_cache() };
+    let results = tokio::task::spawn_blocking(move || {
+        [factory.get_state_reader_from_latest_block(), factory.get_state_reader_from_latest_block()]
+            .map(|state_reader| state_reader.get_compiled_contract_class(class_hash!("0x1")))
+    })
+    .await
+    .unwrap();
+    for result in results {
+        assert_eq!(
+            result.unwrap(),
+            ContractClass::V1(CasmContractClass::default().try_into().unwrap())
+        );
+    }
+    // The class is fetched from the rpc server only once.
+    mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_get_class_hash_at() {
     let mut server = run_rpc_server().await;
@@ -201,7 +330,7 @@ async fn test_get_class_hash_at() {
         }),
     );
 
-    let client = RpcStateReader::from_latest(&config);
+    let client = RpcStateReader::from_latest(&config, class_cache());
     let result =
         tokio::task::spawn_blocking(move || client.get_class_hash_at(contract_address!("0x1")))
             .await
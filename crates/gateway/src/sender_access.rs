@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ContractAddress;
+use thiserror::Error;
+use tracing::info;
+
+use crate::config::SenderAccessConfig;
+use crate::errors::{GatewayResult, GatewaySpecError};
+
+#[cfg(test)]
+#[path = "sender_access_test.rs"]
+mod sender_access_test;
+
+/// The senders that may submit transactions. Senders on the deny list are rejected; if there is an
+/// allow list, only the senders on it are accepted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SenderAccessLists {
+    #[serde(default)]
+    pub allow: Option<HashSet<ContractAddress>>,
+    #[serde(default)]
+    pub deny: HashSet<ContractAddress>,
+}
+
+impl SenderAccessLists {
+    /// Reads the lists from a JSON file, e.g., `{"allow": ["0x1", "0x2"], "deny": ["0x2"]}`.
+    pub fn load(path: &Path) -> Result<Self, SenderAccessListsLoadingError> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    fn is_allowed(&self, sender_address: ContractAddress) -> bool {
+        !self.deny.contains(&sender_address)
+            && self.allow.as_ref().is_none_or(|allow| allow.contains(&sender_address))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SenderAccessListsLoadingError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parsing(#[from] serde_json::Error),
+}
+
+/// Rejects the transactions of the senders that may not submit transactions. The access lists are
+/// read from a file, and can be reloaded at runtime, e.g., to onboard a sender of an appchain.
+#[derive(Debug)]
+pub struct SenderAccessControl {
+    path: PathBuf,
+    lists: RwLock<SenderAccessLists>,
+}
+
+impl SenderAccessControl {
+    pub fn new(config: &SenderAccessConfig) -> Result<Self, SenderAccessListsLoadingError> {
+        let lists = SenderAccessLists::load(&config.path)?;
+        Ok(Self { path: config.path.clone(), lists: RwLock::new(lists) })
+    }
+
+    pub fn validate(&self, sender_address: ContractAddress) -> GatewayResult<()> {
+        let lists = self.lists.read().expect("The sender access lists lock is poisoned.");
+        if !lists.is_allowed(sender_address) {
+            return Err(GatewaySpecError::SenderNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Reads the access lists from their file again. If it can't be read, the current lists
+    /// remain in force.
+    pub fn reload(&self) -> Result<(), SenderAccessListsLoadingError> {
+        self.set_lists(SenderAccessLists::load(&self.path)?);
+        Ok(())
+    }
+
+    pub fn set_lists(&self, lists: SenderAccessLists) {
+        let mut current_lists =
+            self.lists.write().expect("The sender access lists lock is poisoned.");
+        if *current_lists == lists {
+            return;
+        }
+        info!(
+            target: "audit",
+            n_allowed_senders = lists.allow.as_ref().map(HashSet::len),
+            n_denied_senders = lists.deny.len(),
+            "Updated the sender access lists."
+        );
+        *current_lists = lists;
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use assert_matches::assert_matches;
+use starknet_api::core::ContractAddress;
+
+use crate::config::SenderAccessConfig;
+use crate::errors::GatewaySpecError;
+use crate::sender_access::{SenderAccessControl, SenderAccessLists, SenderAccessListsLoadingError};
+
+fn address(value: u8) -> ContractAddress {
+    ContractAddress::from(u128::from(value))
+}
+
+fn sender_access_control(path: &Path, lists: &str) -> SenderAccessControl {
+    fs::write(path, lists).unwrap();
+    SenderAccessControl::new(&SenderAccessConfig {
+        enable: true,
+        path: path.to_owned(),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn denied_senders_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let access_control =
+        sender_access_control(&dir.path().join("lists.json"), r#"{"deny": ["0x1"]}"#);
+
+    assert_eq!(access_control.validate(address(1)), Err(GatewaySpecError::SenderNotAllowed));
+    assert_eq!(access_control.validate(address(2)), Ok(()));
+}
+
+#[test]
+fn only_allowed_senders_are_accepted() {
+    let dir = tempfile::tempdir().unwrap();
+    let access_control = sender_access_control(
+        &dir.path().join("lists.json"),
+        r#"{"allow": ["0x1", "0x2"], "deny": ["0x2"]}"#,
+    );
+
+    assert_eq!(access_control.validate(address(1)), Ok(()));
+    // The deny list takes precedence.
+    assert_eq!(access_control.validate(address(2)), Err(GatewaySpecError::SenderNotAllowed));
+    assert_eq!(access_control.validate(address(3)), Err(GatewaySpecError::SenderNotAllowed));
+}
+
+#[test]
+fn reloaded_lists_are_enforced() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lists.json");
+    let access_control = sender_access_control(&path, r#"{"deny": ["0x1"]}"#);
+
+    fs::write(&path, r#"{"deny": ["0x2"]}"#).unwrap();
+    access_control.reload().unwrap();
+
+    assert_eq!(access_control.validate(address(1)), Ok(()));
+    assert_eq!(access_control.validate(address(2)), Err(GatewaySpecError::SenderNotAllowed));
+}
+
+#[test]
+fn failed_reloads_keep_the_current_lists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lists.json");
+    let access_control = sender_access_control(&path, r#"{"deny": ["0x1"]}"#);
+
+    fs::write(&path, "not json").unwrap();
+    assert_matches!(access_control.reload(), Err(SenderAccessListsLoadingError::Parsing(_)));
+
+    assert_eq!(access_control.validate(address(1)), Err(GatewaySpecError::SenderNotAllowed));
+}
+
+#[test]
+fn lists_can_be_set_at_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let access_control = sender_access_control(&dir.path().join("lists.json"), "{}");
+    assert_eq!(access_control.validate(address(1)), Ok(()));
+
+    access_control.set_lists(SenderAccessLists {
+        allow: Some(HashSet::from([address(2)])),
+        ..Default::default()
+    });
+
+    assert_eq!(access_control.validate(address(1)), Err(GatewaySpecError::SenderNotAllowed));
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use blockifier::execution::call_info::CallInfo;
+use blockifier::execution::contract_class::ClassInfo;
+use blockifier::state::cached_state::{CachedState, StateMaps, TransactionalState};
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::transaction::transactions::ExecutableTransaction;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::state::StorageKey;
+use starknet_api::transaction::{EventContent, Fee, TransactionHash};
+use starknet_types_core::felt::Felt;
+use tracing::error;
+
+use crate::config::SimulationConfig;
+use crate::errors::{GatewayResult, GatewaySpecError};
+use crate::state_reader::StateReaderFactory;
+use crate::stateful_transaction_validator::StatefulTransactionValidator;
+use crate::utils::rpc_tx_to_account_tx;
+
+#[cfg(test)]
+#[path = "simulation_test.rs"]
+mod simulation_test;
+
+/// Relaxes the execution of simulated transactions, in the format of the Starknet RPC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SimulationFlag {
+    /// The `__validate__` entry points of the accounts are not run.
+    SkipValidate,
+    /// Fees are neither checked against the balances of the accounts, nor charged.
+    SkipFeeCharge,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateTransactionsRequest {
+    pub transactions: Vec<RpcTransaction>,
+    // The block on top of whose state the transactions are executed; the latest block if not set.
+    #[serde(default)]
+    pub block_number: Option<BlockNumber>,
+    #[serde(default)]
+    pub simulation_flags: Vec<SimulationFlag>,
+}
+
+/// An event emitted by a simulated transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SimulatedEvent {
+    pub from_address: ContractAddress,
+    #[serde(flatten)]
+    pub content: EventContent,
+}
+
+/// The changes a simulated transaction made to the state, on top of those of the transactions
+/// simulated before it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SimulatedStateDiff {
+    pub nonces: BTreeMap<ContractAddress, Nonce>,
+    // The classes of deployed contracts, and the replaced classes of existing contracts.
+    pub class_hashes: BTreeMap<ContractAddress, ClassHash>,
+    pub storage_diffs: BTreeMap<ContractAddress, BTreeMap<StorageKey, Felt>>,
+    pub declared_classes: BTreeMap<ClassHash, CompiledClassHash>,
+}
+
+impl From<StateMaps> for SimulatedStateDiff {
+    fn from(state_maps: StateMaps) -> Self {
+        let mut storage_diffs: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+        for ((address, key), value) in state_maps.storage {
+            storage_diffs.entry(address).or_default().insert(key, value);
+        }
+        Self {
+            nonces: state_maps.nonces.into_iter().collect(),
+            class_hashes: state_maps.class_hashes.into_iter().collect(),
+            storage_diffs,
+            declared_classes: state_maps.compiled_class_hashes.into_iter().collect(),
+        }
+    }
+}
+
+/// The execution trace and effects of a simulated transaction.
+#[derive(Debug, Serialize)]
+pub struct SimulatedTransaction {
+    pub transaction_hash: TransactionHash,
+    pub validate_invocation: Option<CallInfo>,
+    pub execute_invocation: Option<CallInfo>,
+    pub fee_transfer_invocation: Option<CallInfo>,
+    pub revert_error: Option<String>,
+    pub fee: Fee,
+    pub events: Vec<SimulatedEvent>,
+    pub state_diff: SimulatedStateDiff,
+}
+
+impl SimulatedTransaction {
+    fn new(
+        transaction_hash: TransactionHash,
+        execution_info: TransactionExecutionInfo,
+        state_diff: StateMaps,
+    ) -> Self {
+        let TransactionExecutionInfo {
+            validate_call_info,
+            execute_call_info,
+            fee_transfer_call_info,
+            revert_error,
+            receipt,
+        } = execution_info;
+        let events = [&validate_call_info, &execute_call_info, &fee_transfer_call_info]
+            .into_iter()
+            .flatten()
+            .flat_map(events_of_call)
+            .collect();
+        Self {
+            transaction_hash,
+            validate_invocation: validate_call_info,
+            execute_invocation: execute_call_info,
+            fee_transfer_invocation: fee_transfer_call_info,
+            revert_error,
+            fee: receipt.fee,
+            events,
+            state_diff: state_diff.into(),
+        }
+    }
+}
+
+// Returns the events emitted by a call and its inner calls, in the order they were emitted.
+fn events_of_call(call_info: &CallInfo) -> Vec<SimulatedEvent> {
+    let mut ordered_events: Vec<_> = call_info
+        .iter()
+        .flat_map(|call_info| {
+            call_info.execution.events.iter().map(|ordered_event| {
+                let event = SimulatedEvent {
+                    from_address: call_info.call.storage_address,
+                    content: ordered_event.event.clone(),
+                };
+                (ordered_event.order, event)
+            })
+        })
+        .collect();
+    ordered_events.sort_by_key(|(order, _)| *order);
+    ordered_events.into_iter().map(|(_, event)| event).collect()
+}
+
+/// Executes batches of transactions on top of the state of a block, without adding them to the
+/// mempool. Each transaction is executed on top of the effects of the transactions preceding it.
+pub struct TransactionSimulator {
+    max_n_transactions: usize,
+}
+
+impl TransactionSimulator {
+    pub fn new(config: &SimulationConfig) -> Self {
+        Self { max_n_transactions: config.max_n_transactions }
+    }
+
+    pub fn validate_n_transactions(&self, n_transactions: usize) -> GatewayResult<()> {
+        if n_transactions > self.max_n_transactions {
+            return Err(GatewaySpecError::ValidationFailure {
+                data: format!(
+                    "Cannot simulate {n_transactions} transactions at once; the maximum is {}.",
+                    self.max_n_transactions
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Simulates the given transactions, with the classes of the declare transactions among them.
+    /// Fails if any of the transactions can't be executed; reverted transactions are simulated.
+    pub fn simulate(
+        &self,
+        stateful_tx_validator: &StatefulTransactionValidator,
+        state_reader_factory: &dyn StateReaderFactory,
+        txs: Vec<(RpcTransaction, Option<ClassInfo>)>,
+        block_number: Option<BlockNumber>,
+        simulation_flags: &[SimulationFlag],
+    ) -> GatewayResult<Vec<SimulatedTransaction>> {
+        self.validate_n_transactions(txs.len())?;
+        let state_reader = match block_number {
+            Some(block_number) => state_reader_factory.get_state_reader(block_number),
+            None => state_reader_factory.get_state_reader_from_latest_block(),
+        };
+        let block_info = state_reader.get_block_info().map_err(|e| {
+            error!("Failed to get the block info of a simulation: {}", e);
+            GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+        })?;
+        let block_context = stateful_tx_validator.block_context(block_info);
+        let charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge);
+        let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
+
+        let mut state = CachedState::new(state_reader);
+        txs.into_iter()
+            .enumerate()
+            .map(|(index, (tx, class_info))| {
+                let account_tx =
+                    rpc_tx_to_account_tx(&tx, class_info, &block_context.chain_info().chain_id)?;
+                let transaction_hash = account_tx.tx_hash();
+                let mut tx_state = TransactionalState::create_transactional(&mut state);
+                let execution_info = account_tx
+                    .execute(&mut tx_state, &block_context, charge_fee, validate)
+                    .map_err(|e| GatewaySpecError::ValidationFailure {
+                        data: format!("Failed to execute transaction {index}: {e}"),
+                    })?;
+                let state_diff = tx_state.to_state_diff().map_err(|e| {
+                    error!("Failed to get the state diff of a simulated transaction: {}", e);
+                    GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+                })?;
+                tx_state.commit();
+                Ok(SimulatedTransaction::new(transaction_hash, execution_info, state_diff))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use assert_matches::assert_matches;
+use blockifier::state::cached_state::StateMaps;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::CairoVersion;
+use mempool_test_utils::starknet_api_test_utils::{invoke_tx, MultiAccountTransactionGenerator};
+use pretty_assertions::assert_eq;
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::state::StorageKey;
+use starknet_api::{felt, patricia_key};
+use starknet_types_core::felt::Felt;
+
+use crate::config::{SimulationConfig, StatefulTransactionValidatorConfig};
+use crate::errors::GatewaySpecError;
+use crate::simulation::{
+    SimulatedStateDiff,
+    SimulatedTransaction,
+    SimulationFlag,
+    TransactionSimulator,
+};
+use crate::state_reader_test_utils::local_test_state_reader_factory;
+use crate::stateful_transaction_validator::StatefulTransactionValidator;
+use crate::utils::rpc_tx_to_account_tx;
+
+// The Cairo 0 test contract is used, as the trivial calls of the generated transactions are to an
+// entry point of it.
+fn simulate(
+    simulator: &TransactionSimulator,
+    txs: Vec<RpcTransaction>,
+    simulation_flags: &[SimulationFlag],
+) -> Result<Vec<SimulatedTransaction>, GatewaySpecError> {
+    let stateful_tx_validator = StatefulTransactionValidator {
+        config: StatefulTransactionValidatorConfig::create_for_testing(),
+    };
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo0, false);
+    simulator.simulate(
+        &stateful_tx_validator,
+        &state_reader_factory,
+        txs.into_iter().map(|tx| (tx, None)).collect(),
+        None,
+        simulation_flags,
+    )
+}
+
+#[test]
+fn simulated_transactions_are_executed() {
+    let simulator = TransactionSimulator::new(&SimulationConfig::default());
+    let tx = invoke_tx(CairoVersion::Cairo0);
+    let chain_id = StatefulTransactionValidatorConfig::create_for_testing().chain_info.chain_id;
+    let tx_hash = rpc_tx_to_account_tx(&tx, None, &chain_id).unwrap().tx_hash();
+    let sender_address = tx.calculate_sender_address().unwrap();
+
+    let simulated_txs = simulate(&simulator, vec![tx], &[]).unwrap();
+
+    let [simulated_tx] = simulated_txs.as_slice() else {
+        panic!("Expected a single simulated transaction, got {}.", simulated_txs.len());
+    };
+    assert_eq!(simulated_tx.transaction_hash, tx_hash);
+    assert_eq!(simulated_tx.revert_error, None);
+    assert!(simulated_tx.validate_invocation.is_some());
+    assert!(simulated_tx.execute_invocation.is_some());
+    assert!(simulated_tx.fee_transfer_invocation.is_some());
+    assert!(simulated_tx.fee.0 > 0);
+    assert_eq!(simulated_tx.state_diff.nonces.get(&sender_address), Some(&Nonce(Felt::ONE)));
+}
+
+#[test]
+fn transactions_are_simulated_on_top_of_the_preceding_ones() {
+    let simulator = TransactionSimulator::new(&SimulationConfig::default());
+    let mut tx_generator = MultiAccountTransactionGenerator::new_for_account_contracts([
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0),
+    ]);
+    let account = tx_generator.account_with_id(0);
+    let txs = vec![account.generate_default_invoke(), account.generate_default_invoke()];
+    let sender_address = txs[0].calculate_sender_address().unwrap();
+
+    let simulated_txs = simulate(&simulator, txs, &[]).unwrap();
+
+    let nonces: Vec<_> = simulated_txs
+        .iter()
+        .map(|simulated_tx| simulated_tx.state_diff.nonces[&sender_address])
+        .collect();
+    assert_eq!(nonces, vec![Nonce(Felt::ONE), Nonce(Felt::TWO)]);
+}
+
+#[test]
+fn fees_are_not_charged_if_skipped() {
+    let simulator = TransactionSimulator::new(&SimulationConfig::default());
+
+    let simulated_txs = simulate(
+        &simulator,
+        vec![invoke_tx(CairoVersion::Cairo0)],
+        &[SimulationFlag::SkipFeeCharge, SimulationFlag::SkipValidate],
+    )
+    .unwrap();
+
+    assert!(simulated_txs[0].validate_invocation.is_none());
+    assert!(simulated_txs[0].fee_transfer_invocation.is_none());
+}
+
+#[test]
+fn too_many_transactions_are_rejected() {
+    let simulator = TransactionSimulator::new(&SimulationConfig {
+        max_n_transactions: 1,
+        ..Default::default()
+    });
+
+    let result = simulate(
+        &simulator,
+        vec![invoke_tx(CairoVersion::Cairo0), invoke_tx(CairoVersion::Cairo0)],
+        &[],
+    );
+
+    assert_matches!(result, Err(GatewaySpecError::ValidationFailure { .. }));
+}
+
+#[test]
+fn state_diffs_group_the_storage_by_contract() {
+    let address = ContractAddress::from(1_u128);
+    let key = |value: u8| StorageKey(patricia_key!(value));
+    let state_maps = StateMaps {
+        storage: HashMap::from([
+            ((address, key(1)), felt!(1_u8)),
+            ((address, key(2)), felt!(2_u8)),
+        ]),
+        ..Default::default()
+    };
+
+    let state_diff = SimulatedStateDiff::from(state_maps);
+
+    assert_eq!(
+        state_diff,
+        SimulatedStateDiff {
+            storage_diffs: [(address, [(key(1), felt!(1_u8)), (key(2), felt!(2_u8))].into())]
+                .into(),
+            ..Default::default()
+        }
+    );
+}
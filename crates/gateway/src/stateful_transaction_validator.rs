@@ -3,24 +3,28 @@ use blockifier::blockifier::stateful_validator::{
     StatefulValidator,
     StatefulValidatorResult as BlockifierStatefulValidatorResult,
 };
-use blockifier::bouncer::BouncerConfig;
-use blockifier::context::BlockContext;
+use blockifier::context::{BlockContext, BlockContextBuilder};
 use blockifier::execution::contract_class::ClassInfo;
 use blockifier::state::cached_state::CachedState;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
 use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::versioned_constants::VersionedConstants;
 #[cfg(test)]
 use mockall::automock;
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::rpc_transaction::{RpcInvokeTransaction, RpcTransaction};
 use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::AddTransactionResult;
 use starknet_types_core::felt::Felt;
 use tracing::error;
 
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::{GatewaySpecError, StatefulTransactionValidatorResult};
+use crate::rejections::{Rejection, RejectionCode};
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 use crate::utils::{get_sender_address, rpc_tx_to_account_tx};
+use crate::validation_error_messages::validation_rejection;
 
 #[cfg(test)]
 #[path = "stateful_transaction_validator_test.rs"]
@@ -84,7 +88,7 @@ impl StatefulTransactionValidator {
         let skip_validate = skip_stateful_validations(rpc_tx, account_nonce);
         validator
             .validate(account_tx, skip_validate)
-            .map_err(|err| GatewaySpecError::ValidationFailure { data: err.to_string() })?;
+            .map_err(|err| GatewaySpecError::InvalidTransaction(validation_rejection(&err)))?;
         Ok(ValidateInfo { tx_hash, sender_address, account_nonce })
     }
 
@@ -97,22 +101,29 @@ impl StatefulTransactionValidator {
         let latest_block_info = get_latest_block_info(state_reader_factory)?;
         let state_reader = state_reader_factory.get_state_reader(latest_block_info.block_number);
         let state = CachedState::new(state_reader);
+        let block_context = self.block_context(latest_block_info);
+
+        Ok(BlockifierStatefulValidator::create(state, block_context))
+    }
+
+    /// Returns the context of the block following the given one, in which transactions are
+    /// validated.
+    pub fn block_context(&self, latest_block_info: BlockInfo) -> BlockContext {
         let versioned_constants = VersionedConstants::latest_constants_with_overrides(
             self.config.validate_max_n_steps,
             self.config.max_recursion_depth,
         );
-        let mut block_info = latest_block_info;
-        block_info.block_number = block_info.block_number.unchecked_next();
         // TODO(yael 21/4/24): create the block context using pre_process_block once we will be
         // able to read the block_hash of 10 blocks ago from papyrus.
-        let block_context = BlockContext::new(
-            block_info,
-            self.config.chain_info.clone(),
-            versioned_constants,
-            BouncerConfig::max(),
-        );
-
-        Ok(BlockifierStatefulValidator::create(state, block_context))
+        BlockContextBuilder::new()
+            .chain_info(self.config.chain_info.clone())
+            .block_number(latest_block_info.block_number.unchecked_next())
+            .block_timestamp(latest_block_info.block_timestamp)
+            .gas_prices(latest_block_info.gas_prices)
+            .sequencer_address(latest_block_info.sequencer_address)
+            .use_kzg_da(latest_block_info.use_kzg_da)
+            .versioned_constants(versioned_constants)
+            .build()
     }
 }
 
@@ -131,6 +142,47 @@ fn skip_stateful_validations(tx: &RpcTransaction, account_nonce: Nonce) -> bool
     }
 }
 
+/// Checks that a declared class is not declared yet, and that a deployed account is not deployed
+/// yet. The blockifier doesn't execute declare transactions when validating them, and reports
+/// redeployed accounts as an execution failure of their constructor.
+pub fn validate_class_and_account_are_new(
+    state_reader_factory: &dyn StateReaderFactory,
+    add_tx_result: &AddTransactionResult,
+) -> StatefulTransactionValidatorResult<()> {
+    let unexpected_error = |e: StateError| {
+        error!("Failed to read the state of a declare or deploy account transaction: {}", e);
+        GatewaySpecError::UnexpectedError { data: "Internal server error.".to_owned() }
+    };
+    match *add_tx_result {
+        AddTransactionResult::Declare { class_hash, .. } => {
+            let state_reader = state_reader_factory.get_state_reader_from_latest_block();
+            match state_reader.get_compiled_contract_class(class_hash) {
+                Ok(_) => Err(GatewaySpecError::ClassAlreadyDeclared),
+                Err(StateError::UndeclaredClassHash(_)) => Ok(()),
+                Err(e) => Err(unexpected_error(e)),
+            }
+        }
+        AddTransactionResult::DeployAccount { address, .. } => {
+            let state_reader = state_reader_factory.get_state_reader_from_latest_block();
+            let class_hash = state_reader.get_class_hash_at(address).map_err(unexpected_error)?;
+            if class_hash != ClassHash::default() {
+                let message = format!(
+                    "Account {:#x} is already deployed, with class {:#x}.",
+                    address.0.key(),
+                    class_hash.0
+                );
+                return Err(GatewaySpecError::InvalidTransaction(
+                    Rejection::new(RejectionCode::AccountAlreadyDeployed, message)
+                        .with("address", address)
+                        .with("class_hash", class_hash),
+                ));
+            }
+            Ok(())
+        }
+        AddTransactionResult::Invoke { .. } => Ok(()),
+    }
+}
+
 pub fn get_latest_block_info(
     state_reader_factory: &dyn StateReaderFactory,
 ) -> StatefulTransactionValidatorResult<BlockInfo> {
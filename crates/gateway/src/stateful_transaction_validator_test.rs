@@ -3,6 +3,7 @@ use blockifier::blockifier::stateful_validator::{
     StatefulValidatorResult as BlockifierStatefulValidatorResult,
 };
 use blockifier::context::BlockContext;
+use blockifier::test_utils::contracts::FeatureContract;
 use blockifier::test_utils::CairoVersion;
 use blockifier::transaction::errors::{TransactionFeeError, TransactionPreValidationError};
 use mempool_test_utils::invoke_tx_args;
@@ -18,21 +19,25 @@ use mockall::predicate::eq;
 use num_bigint::BigUint;
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
-use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce, PatriciaKey};
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
-use starknet_api::{contract_address, felt, patricia_key};
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_gateway_types::gateway_types::AddTransactionResult;
 use starknet_types_core::felt::Felt;
 
 use super::ValidateInfo;
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::GatewaySpecError;
+use crate::rejections::{Rejection, RejectionCode};
 use crate::state_reader::{MockStateReaderFactory, StateReaderFactory};
 use crate::state_reader_test_utils::local_test_state_reader_factory;
 use crate::stateful_transaction_validator::{
+    validate_class_and_account_are_new,
     MockStatefulTransactionValidatorTrait,
     StatefulTransactionValidator,
 };
+use crate::validation_error_messages::validation_rejection;
 
 pub const STATEFUL_VALIDATOR_FEE_ERROR: BlockifierStatefulValidatorError =
     BlockifierStatefulValidatorError::TransactionPreValidationError(
@@ -82,7 +87,7 @@ fn test_stateful_tx_validator(
 ) {
     let expected_result_as_stateful_transaction_result =
         expected_result.as_ref().map(|validate_info| *validate_info).map_err(|blockifier_error| {
-            GatewaySpecError::ValidationFailure { data: blockifier_error.to_string() }
+            GatewaySpecError::InvalidTransaction(validation_rejection(blockifier_error))
         });
 
     let mut mock_validator = MockStatefulTransactionValidatorTrait::new();
@@ -162,3 +167,47 @@ fn test_skip_stateful_validation(
         .returning(|_, _| Ok(()));
     let _ = stateful_validator.run_validate(&rpc_tx, None, mock_validator);
 }
+
+const ACCOUNT_CONTRACT: FeatureContract =
+    FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1);
+
+fn declare_result(class_hash: ClassHash) -> AddTransactionResult {
+    AddTransactionResult::Declare { tx_hash: TransactionHash::default(), class_hash }
+}
+
+fn deploy_account_result(address: ContractAddress) -> AddTransactionResult {
+    AddTransactionResult::DeployAccount { tx_hash: TransactionHash::default(), address }
+}
+
+#[rstest]
+#[case::new_class(declare_result(class_hash!(1_u8)), Ok(()))]
+#[case::declared_class(
+    declare_result(FeatureContract::TestContract(CairoVersion::Cairo1).get_class_hash()),
+    Err(GatewaySpecError::ClassAlreadyDeclared)
+)]
+#[case::new_account(deploy_account_result(contract_address!("0x1")), Ok(()))]
+#[case::deployed_account(
+    deploy_account_result(ACCOUNT_CONTRACT.get_instance_address(0)),
+    Err(GatewaySpecError::InvalidTransaction(
+        Rejection::new(
+            RejectionCode::AccountAlreadyDeployed,
+            format!(
+                "Account {:#x} is already deployed, with class {:#x}.",
+                ACCOUNT_CONTRACT.get_instance_address(0).0.key(),
+                ACCOUNT_CONTRACT.get_class_hash().0
+            ),
+        )
+        .with("address", ACCOUNT_CONTRACT.get_instance_address(0))
+        .with("class_hash", ACCOUNT_CONTRACT.get_class_hash()),
+    ))
+)]
+fn test_validate_class_and_account_are_new(
+    #[case] add_tx_result: AddTransactionResult,
+    #[case] expected_result: Result<(), GatewaySpecError>,
+) {
+    let state_reader_factory = local_test_state_reader_factory(CairoVersion::Cairo1, false);
+
+    let result = validate_class_and_account_are_new(&state_reader_factory, &add_tx_result);
+
+    assert_eq!(result, expected_result);
+}
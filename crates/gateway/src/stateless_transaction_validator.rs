@@ -1,3 +1,4 @@
+use starknet_api::core::Nonce;
 use starknet_api::rpc_transaction::{
     RpcDeclareTransaction,
     RpcDeployAccountTransaction,
@@ -5,7 +6,7 @@ use starknet_api::rpc_transaction::{
     RpcTransaction,
 };
 use starknet_api::state::EntryPoint;
-use starknet_api::transaction::{AllResourceBounds, Resource};
+use starknet_api::transaction::{AllResourceBounds, Resource, ValidResourceBounds};
 use starknet_types_core::felt::Felt;
 use tracing::{instrument, Level};
 
@@ -31,8 +32,12 @@ impl StatelessTransactionValidator {
         self.validate_resource_bounds(tx)?;
         self.validate_tx_size(tx)?;
 
-        if let RpcTransaction::Declare(declare_tx) = tx {
-            self.validate_declare_tx(declare_tx)?;
+        match tx {
+            RpcTransaction::Declare(declare_tx) => self.validate_declare_tx(declare_tx)?,
+            RpcTransaction::DeployAccount(deploy_account_tx) => {
+                validate_deploy_account_tx(deploy_account_tx)?
+            }
+            RpcTransaction::Invoke(_) => {}
         }
         Ok(())
     }
@@ -43,12 +48,16 @@ impl StatelessTransactionValidator {
     ) -> StatelessTransactionValidatorResult<()> {
         let resource_bounds_mapping = tx.resource_bounds();
 
-        if self.config.validate_non_zero_l1_gas_fee {
-            validate_resource_is_non_zero(resource_bounds_mapping, Resource::L1Gas)?;
-        }
+        // Checked with the same fee policy the block builder executes with.
+        self.config.fee_config.validate_resource_bounds(&ValidResourceBounds::AllResources(
+            resource_bounds_mapping.clone(),
+        ))?;
         if self.config.validate_non_zero_l2_gas_fee {
             validate_resource_is_non_zero(resource_bounds_mapping, Resource::L2Gas)?;
         }
+        if self.config.validate_max_fee_overflow {
+            validate_max_fee_does_not_overflow(resource_bounds_mapping)?;
+        }
 
         Ok(())
     }
@@ -188,3 +197,39 @@ fn validate_resource_is_non_zero(
 
     Ok(())
 }
+
+// The maximal fee is the sum, over all resources, of the max amount times the max price per unit.
+fn validate_max_fee_does_not_overflow(
+    all_resource_bounds: &AllResourceBounds,
+) -> StatelessTransactionValidatorResult<()> {
+    let max_fee = [Resource::L1Gas, Resource::L2Gas, Resource::L1DataGas].into_iter().try_fold(
+        0_u128,
+        |max_fee, resource| {
+            let resource_bounds = all_resource_bounds.get_bound(resource);
+            u128::from(resource_bounds.max_amount)
+                .checked_mul(resource_bounds.max_price_per_unit)
+                .and_then(|resource_max_fee| max_fee.checked_add(resource_max_fee))
+        },
+    );
+    if max_fee.is_none() {
+        return Err(StatelessTransactionValidatorError::MaxFeeOverflow {
+            resource_bounds: all_resource_bounds.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+// Deploy account transactions are the first transactions of their accounts.
+fn validate_deploy_account_tx(
+    deploy_account_tx: &RpcDeployAccountTransaction,
+) -> StatelessTransactionValidatorResult<()> {
+    let nonce = match deploy_account_tx {
+        RpcDeployAccountTransaction::V3(tx) => tx.nonce,
+    };
+    if nonce != Nonce::default() {
+        return Err(StatelessTransactionValidatorError::NonZeroDeployAccountNonce { nonce });
+    }
+
+    Ok(())
+}
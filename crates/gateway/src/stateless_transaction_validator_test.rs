@@ -2,17 +2,18 @@ use std::sync::OnceLock;
 use std::vec;
 
 use assert_matches::assert_matches;
-use mempool_test_utils::declare_tx_args;
 use mempool_test_utils::starknet_api_test_utils::{
     create_resource_bounds_mapping,
     rpc_declare_tx,
+    rpc_deploy_account_tx,
     rpc_tx_for_testing,
     zero_resource_bounds_mapping,
     TransactionType,
     NON_EMPTY_RESOURCE_BOUNDS,
 };
+use mempool_test_utils::{declare_tx_args, deploy_account_tx_args};
 use rstest::rstest;
-use starknet_api::core::EntryPointSelector;
+use starknet_api::core::{EntryPointSelector, Nonce};
 use starknet_api::rpc_transaction::{ContractClass, EntryPointByType};
 use starknet_api::state::EntryPoint;
 use starknet_api::transaction::{
@@ -23,6 +24,7 @@ use starknet_api::transaction::{
     TransactionSignature,
 };
 use starknet_api::{calldata, felt};
+use starknet_mempool_types::resource_bounds::{FeeConfig, ResourceBoundsError};
 use starknet_types_core::felt::Felt;
 
 use crate::compiler_version::{VersionId, VersionIdError};
@@ -42,12 +44,21 @@ fn max_sierra_version() -> &'static VersionId {
     static MAX_SIERRA_VERSION: OnceLock<VersionId> = OnceLock::new();
     MAX_SIERRA_VERSION.get_or_init(|| VersionId::new(1, 5, usize::MAX))
 }
+// Resource bounds with only the L1 gas bounds set, which are always validated.
+fn l1_resource_bounds_mapping() -> AllResourceBounds {
+    create_resource_bounds_mapping(
+        NON_EMPTY_RESOURCE_BOUNDS,
+        ResourceBounds::default(),
+        ResourceBounds::default(),
+    )
+}
 fn default_validator_config_for_testing() -> &'static StatelessTransactionValidatorConfig {
     static DEFAULT_VALIDATOR_CONFIG_FOR_TESTING: OnceLock<StatelessTransactionValidatorConfig> =
         OnceLock::new();
     DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.get_or_init(|| StatelessTransactionValidatorConfig {
-        validate_non_zero_l1_gas_fee: false,
         validate_non_zero_l2_gas_fee: false,
+        validate_max_fee_overflow: true,
+        fee_config: FeeConfig::default(),
         max_calldata_length: 1,
         max_signature_length: 1,
         max_contract_class_object_size: 100000,
@@ -57,47 +68,17 @@ fn default_validator_config_for_testing() -> &'static StatelessTransactionValida
 }
 
 #[rstest]
-#[case::ignore_resource_bounds(
+#[case::ignore_l2_resource_bounds(
     StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: false,
         validate_non_zero_l2_gas_fee: false,
         ..default_validator_config_for_testing().clone()
     },
-    zero_resource_bounds_mapping(),
-    calldata![],
-    TransactionSignature::default()
-)]
-#[case::valid_l1_gas(
-    StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: true,
-        validate_non_zero_l2_gas_fee: false,
-        ..default_validator_config_for_testing().clone()
-    },
-    create_resource_bounds_mapping(
-        NON_EMPTY_RESOURCE_BOUNDS,
-        ResourceBounds::default(),
-        ResourceBounds::default(),
-    ),
-    calldata![],
-    TransactionSignature::default()
-)]
-#[case::valid_l2_gas(
-    StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: false,
-        validate_non_zero_l2_gas_fee: true,
-        ..default_validator_config_for_testing().clone()
-    },
-    create_resource_bounds_mapping(
-        ResourceBounds::default(),
-        NON_EMPTY_RESOURCE_BOUNDS,
-        ResourceBounds::default(),
-    ),
+    l1_resource_bounds_mapping(),
     calldata![],
     TransactionSignature::default()
 )]
 #[case::valid_l1_and_l2_gas(
     StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: true,
         validate_non_zero_l2_gas_fee: true,
         ..default_validator_config_for_testing().clone()
     },
@@ -111,19 +92,19 @@ fn default_validator_config_for_testing() -> &'static StatelessTransactionValida
 )]
 #[case::non_empty_valid_calldata(
     default_validator_config_for_testing().clone(),
-    zero_resource_bounds_mapping(),
+    l1_resource_bounds_mapping(),
     calldata![Felt::ONE],
     TransactionSignature::default()
 )]
 #[case::non_empty_valid_signature(
     default_validator_config_for_testing().clone(),
-    zero_resource_bounds_mapping(),
+    l1_resource_bounds_mapping(),
     calldata![],
     TransactionSignature(vec![Felt::ONE])
 )]
 #[case::valid_tx(
     default_validator_config_for_testing().clone(),
-    zero_resource_bounds_mapping(),
+    l1_resource_bounds_mapping(),
     calldata![],
     TransactionSignature::default()
 )]
@@ -143,19 +124,26 @@ fn test_positive_flow(
 
 #[rstest]
 #[case::zero_l1_gas_resource_bounds(
-    StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: true,
-        validate_non_zero_l2_gas_fee: false,
-        ..default_validator_config_for_testing().clone()
-    },
+    default_validator_config_for_testing().clone(),
     zero_resource_bounds_mapping(),
-    StatelessTransactionValidatorError::ZeroResourceBounds{
+    ResourceBoundsError::ZeroResourceBounds{
         resource: Resource::L1Gas, resource_bounds: ResourceBounds::default()
-    }
+    }.into()
+)]
+#[case::zero_l1_gas_price(
+    default_validator_config_for_testing().clone(),
+    create_resource_bounds_mapping(
+        ResourceBounds { max_amount: 1, max_price_per_unit: 0 },
+        ResourceBounds::default(),
+        ResourceBounds::default(),
+    ),
+    ResourceBoundsError::ZeroResourceBounds{
+        resource: Resource::L1Gas,
+        resource_bounds: ResourceBounds { max_amount: 1, max_price_per_unit: 0 },
+    }.into()
 )]
 #[case::zero_l2_gas_resource_bounds(
     StatelessTransactionValidatorConfig{
-        validate_non_zero_l1_gas_fee: false,
         validate_non_zero_l2_gas_fee: true,
         ..default_validator_config_for_testing().clone()
     },
@@ -168,6 +156,68 @@ fn test_positive_flow(
         resource: Resource::L2Gas, resource_bounds: ResourceBounds::default()
     }
 )]
+#[case::max_fee_overflow(
+    default_validator_config_for_testing().clone(),
+    create_resource_bounds_mapping(
+        ResourceBounds { max_amount: u64::MAX, max_price_per_unit: u128::MAX },
+        ResourceBounds::default(),
+        ResourceBounds::default(),
+    ),
+    StatelessTransactionValidatorError::MaxFeeOverflow {
+        resource_bounds: create_resource_bounds_mapping(
+            ResourceBounds { max_amount: u64::MAX, max_price_per_unit: u128::MAX },
+            ResourceBounds::default(),
+            ResourceBounds::default(),
+        ),
+    }
+)]
+#[case::summed_max_fee_overflow(
+    default_validator_config_for_testing().clone(),
+    create_resource_bounds_mapping(
+        ResourceBounds { max_amount: 1, max_price_per_unit: u128::MAX },
+        ResourceBounds { max_amount: 1, max_price_per_unit: 1 },
+        ResourceBounds::default(),
+    ),
+    StatelessTransactionValidatorError::MaxFeeOverflow {
+        resource_bounds: create_resource_bounds_mapping(
+            ResourceBounds { max_amount: 1, max_price_per_unit: u128::MAX },
+            ResourceBounds { max_amount: 1, max_price_per_unit: 1 },
+            ResourceBounds::default(),
+        ),
+    }
+)]
+#[case::l1_gas_price_below_minimum(
+    StatelessTransactionValidatorConfig{
+        fee_config: FeeConfig { min_l1_gas_price: 2.try_into().unwrap(), ..Default::default() },
+        ..default_validator_config_for_testing().clone()
+    },
+    l1_resource_bounds_mapping(),
+    ResourceBoundsError::MaxL1GasPriceTooLow{ max_price_per_unit: 1, gas_price: 2 }.into()
+)]
+#[case::l2_gas_price_below_minimum(
+    StatelessTransactionValidatorConfig{
+        fee_config: FeeConfig { min_l2_gas_price: 2.try_into().unwrap(), ..Default::default() },
+        ..default_validator_config_for_testing().clone()
+    },
+    create_resource_bounds_mapping(
+        NON_EMPTY_RESOURCE_BOUNDS,
+        NON_EMPTY_RESOURCE_BOUNDS,
+        ResourceBounds::default(),
+    ),
+    ResourceBoundsError::MaxL2GasPriceTooLow{ max_price_per_unit: 1, gas_price: 2 }.into()
+)]
+#[case::max_fee_too_high(
+    StatelessTransactionValidatorConfig{
+        fee_config: FeeConfig { max_fee: Some(2), ..Default::default() },
+        ..default_validator_config_for_testing().clone()
+    },
+    create_resource_bounds_mapping(
+        NON_EMPTY_RESOURCE_BOUNDS,
+        NON_EMPTY_RESOURCE_BOUNDS,
+        NON_EMPTY_RESOURCE_BOUNDS,
+    ),
+    ResourceBoundsError::MaxFeeTooHigh{ tx_max_fee: 3, max_fee: 2 }.into()
+)]
 fn test_invalid_resource_bounds(
     #[case] config: StatelessTransactionValidatorConfig,
     #[case] resource_bounds: AllResourceBounds,
@@ -190,7 +240,7 @@ fn test_calldata_too_long(
         StatelessTransactionValidator { config: default_validator_config_for_testing().clone() };
     let tx = rpc_tx_for_testing(
         tx_type,
-        zero_resource_bounds_mapping(),
+        l1_resource_bounds_mapping(),
         calldata![Felt::ONE, Felt::TWO],
         TransactionSignature::default(),
     );
@@ -213,7 +263,7 @@ fn test_signature_too_long(
         StatelessTransactionValidator { config: default_validator_config_for_testing().clone() };
     let tx = rpc_tx_for_testing(
         tx_type,
-        zero_resource_bounds_mapping(),
+        l1_resource_bounds_mapping(),
         calldata![],
         TransactionSignature(vec![Felt::ONE, Felt::TWO]),
     );
@@ -227,6 +277,21 @@ fn test_signature_too_long(
     );
 }
 
+#[test]
+fn test_deploy_account_with_non_zero_nonce() {
+    let tx_validator =
+        StatelessTransactionValidator { config: default_validator_config_for_testing().clone() };
+    let tx = rpc_deploy_account_tx(deploy_account_tx_args!(
+        resource_bounds: l1_resource_bounds_mapping(),
+        nonce: Nonce(Felt::ONE),
+    ));
+
+    assert_eq!(
+        tx_validator.validate(&tx).unwrap_err(),
+        StatelessTransactionValidatorError::NonZeroDeployAccountNonce { nonce: Nonce(Felt::ONE) }
+    );
+}
+
 #[rstest]
 #[case::sierra_program_length_zero(
     vec![],
@@ -305,7 +370,9 @@ fn test_declare_sierra_version_failure(
         StatelessTransactionValidator { config: default_validator_config_for_testing().clone() };
 
     let contract_class = ContractClass { sierra_program, ..Default::default() };
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_eq!(tx_validator.validate(&tx).unwrap_err(), expected_error);
 }
@@ -325,7 +392,9 @@ fn test_declare_sierra_version_sucsses(#[case] sierra_program: Vec<Felt>) {
         StatelessTransactionValidator { config: default_validator_config_for_testing().clone() };
 
     let contract_class = ContractClass { sierra_program, ..Default::default() };
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_matches!(tx_validator.validate(&tx), Ok(()));
 }
@@ -344,7 +413,9 @@ fn test_declare_contract_class_size_too_long() {
         ..Default::default()
     };
     let contract_class_length = serde_json::to_string(&contract_class).unwrap().len();
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_matches!(
         tx_validator.validate(&tx).unwrap_err(),
@@ -412,7 +483,9 @@ fn test_declare_entry_points_not_sorted_by_selector(
         },
         ..Default::default()
     };
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_eq!(tx_validator.validate(&tx), expected);
 
@@ -425,7 +498,9 @@ fn test_declare_entry_points_not_sorted_by_selector(
         },
         ..Default::default()
     };
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_eq!(tx_validator.validate(&tx), expected);
 
@@ -438,7 +513,9 @@ fn test_declare_entry_points_not_sorted_by_selector(
         },
         ..Default::default()
     };
-    let tx = rpc_declare_tx(declare_tx_args!(contract_class));
+    let tx = rpc_declare_tx(
+        declare_tx_args!(resource_bounds: l1_resource_bounds_mapping(), contract_class),
+    );
 
     assert_eq!(tx_validator.validate(&tx), expected);
 }
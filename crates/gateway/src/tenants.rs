@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ChainId;
+use starknet_mempool_infra::serialization::SerializationFormat;
+use thiserror::Error;
+
+use crate::config::{
+    GatewayConfig,
+    RpcStateReaderConfig,
+    StatelessTransactionValidatorConfig,
+    TransactionTypeConfig,
+};
+
+#[cfg(test)]
+#[path = "tenants_test.rs"]
+mod tenants_test;
+
+/// The HTTP header carrying the chain id a submission is intended for. Submissions without it are
+/// intended for the chain of this node.
+pub const CHAIN_ID_HEADER: &str = "x-chain-id";
+
+/// A sequencer backend fronted by the gateway in addition to this node. Its transactions are
+/// validated against its own state and limits, and added to its own mempool.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TenantConfig {
+    pub chain_id: ChainId,
+    // The URL of the mempool of the backend.
+    pub mempool_url: String,
+    // The wire format the mempool of the backend is served in.
+    #[serde(default)]
+    pub mempool_serialization_format: SerializationFormat,
+    pub rpc_state_reader_config: RpcStateReaderConfig,
+    #[serde(default)]
+    pub stateless_tx_validator_config: StatelessTransactionValidatorConfig,
+    #[serde(default)]
+    pub transaction_type_config: TransactionTypeConfig,
+}
+
+impl TenantConfig {
+    /// The configuration of the gateway of this tenant: that of this node, with the limits and
+    /// transaction types of the tenant. Arrivals are journaled, and senders are restricted, for
+    /// the chain of this node only.
+    pub fn gateway_config(&self, config: &GatewayConfig) -> GatewayConfig {
+        let mut config = config.clone();
+        config.stateless_tx_validator_config = self.stateless_tx_validator_config.clone();
+        config.transaction_type_config = self.transaction_type_config.clone();
+        config.stateful_tx_validator_config.chain_info.chain_id = self.chain_id.clone();
+        config.arrival_journal_config.enable = false;
+        config.sender_access_config.enable = false;
+        config
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TenantsLoadingError {
+    #[error("Chain id {0} is served by more than one backend.")]
+    DuplicateChainId(ChainId),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parsing(#[from] serde_json::Error),
+}
+
+/// Reads the tenants from a JSON file holding a list of [`TenantConfig`]s. The chain of this node
+/// can't be served by a tenant.
+pub fn load_tenant_configs(
+    path: &Path,
+    own_chain_id: &ChainId,
+) -> Result<Vec<TenantConfig>, TenantsLoadingError> {
+    let tenants: Vec<TenantConfig> = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let mut chain_ids = HashSet::from([own_chain_id]);
+    for tenant in &tenants {
+        if !chain_ids.insert(&tenant.chain_id) {
+            return Err(TenantsLoadingError::DuplicateChainId(tenant.chain_id.clone()));
+        }
+    }
+    Ok(tenants)
+}
@@ -0,0 +1,69 @@
+use std::fs;
+
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::core::ChainId;
+use starknet_mempool_infra::serialization::SerializationFormat;
+
+use crate::config::{GatewayConfig, TransactionTypeConfig};
+use crate::tenants::{load_tenant_configs, TenantConfig, TenantsLoadingError};
+
+const TENANT: &str = r#"{
+    "chain_id": "SN_TENANT",
+    "mempool_url": "http://mempool.tenant:8080/",
+    "rpc_state_reader_config": {"url": "http://rpc.tenant:8080/", "json_rpc_version": "2.0"},
+    "transaction_type_config": {"enable_declare": false, "enable_deploy_account": true}
+}"#;
+
+fn own_chain_id() -> ChainId {
+    ChainId::from("SN_OWN".to_owned())
+}
+
+fn load(tenants: &[&str]) -> Result<Vec<TenantConfig>, TenantsLoadingError> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tenants.json");
+    fs::write(&path, format!("[{}]", tenants.join(","))).unwrap();
+    load_tenant_configs(&path, &own_chain_id())
+}
+
+#[test]
+fn tenant_config_overrides_node_config() {
+    let tenants = load(&[TENANT]).unwrap();
+    assert_eq!(tenants.len(), 1);
+    let tenant = &tenants[0];
+    assert_eq!(tenant.chain_id, ChainId::from("SN_TENANT".to_owned()));
+    assert_eq!(tenant.rpc_state_reader_config.strk_per_eth_rate, None);
+    assert_eq!(tenant.mempool_serialization_format, SerializationFormat::Bincode);
+
+    let mut node_config = GatewayConfig::default();
+    node_config.arrival_journal_config.enable = true;
+    let tenant_config = tenant.gateway_config(&node_config);
+
+    assert_eq!(tenant_config.stateful_tx_validator_config.chain_info.chain_id, tenant.chain_id);
+    assert_eq!(
+        tenant_config.transaction_type_config,
+        TransactionTypeConfig { enable_declare: false, enable_deploy_account: true }
+    );
+    assert!(!tenant_config.arrival_journal_config.enable);
+    assert_eq!(tenant_config.network_config, node_config.network_config);
+}
+
+#[test]
+fn duplicate_chain_ids_are_rejected() {
+    assert_matches!(
+        load(&[TENANT, TENANT]),
+        Err(TenantsLoadingError::DuplicateChainId(chain_id))
+            if chain_id == ChainId::from("SN_TENANT".to_owned())
+    );
+
+    let own_chain_tenant = TENANT.replace("SN_TENANT", "SN_OWN");
+    assert_matches!(
+        load(&[own_chain_tenant.as_str()]),
+        Err(TenantsLoadingError::DuplicateChainId(chain_id)) if chain_id == own_chain_id()
+    );
+}
+
+#[test]
+fn malformed_tenants_are_rejected() {
+    assert_matches!(load(&["{}"]), Err(TenantsLoadingError::Parsing(_)));
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::{TransactionStatus, TransactionStatusUpdate};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::debug;
+
+use crate::config::TransactionEventsConfig;
+
+#[cfg(test)]
+#[path = "transaction_events_test.rs"]
+mod transaction_events_test;
+
+/// A step in the lifecycle of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionEventKind {
+    /// The transaction passed the validations of the gateway.
+    Accepted,
+    /// The transaction was added to the mempool.
+    InMempool,
+    /// The transaction was included in a committed block.
+    InBlock,
+    /// The transaction was rejected by the mempool, or while it was sequenced.
+    Rejected { reason: String },
+}
+
+/// An event in the lifecycle of a transaction, pushed to the subscribers of the transaction or of
+/// its sender.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionEvent {
+    pub tx_hash: TransactionHash,
+    // Unknown for the transactions included in a block that weren't received by this gateway.
+    pub sender_address: Option<ContractAddress>,
+    #[serde(flatten)]
+    pub kind: TransactionEventKind,
+}
+
+impl TransactionEvent {
+    pub fn from_status_update(update: TransactionStatusUpdate) -> Option<Self> {
+        let TransactionStatusUpdate { tx_hash, status, sender_address, reason } = update;
+        let kind = match status {
+            TransactionStatus::NotReceived => return None,
+            TransactionStatus::Received => TransactionEventKind::InMempool,
+            TransactionStatus::Included => TransactionEventKind::InBlock,
+            TransactionStatus::Rejected => {
+                TransactionEventKind::Rejected { reason: reason.unwrap_or_default() }
+            }
+        };
+        Some(Self { tx_hash, sender_address, kind })
+    }
+}
+
+/// The events a subscriber is interested in: those of a transaction, or of the transactions of a
+/// sender.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionEventFilter {
+    #[serde(default, rename = "transactionHash")]
+    pub tx_hash: Option<TransactionHash>,
+    #[serde(default, rename = "senderAddress")]
+    pub sender_address: Option<ContractAddress>,
+}
+
+impl TransactionEventFilter {
+    pub fn is_empty(&self) -> bool {
+        self.tx_hash.is_none() && self.sender_address.is_none()
+    }
+
+    fn matches(&self, event: &TransactionEvent) -> bool {
+        self.tx_hash.is_some_and(|tx_hash| tx_hash == event.tx_hash)
+            || self.sender_address.is_some_and(|address| Some(address) == event.sender_address)
+    }
+}
+
+/// Pushes the transaction events published by the gateway to its subscribers. Subscribers that
+/// fall behind by more than the configured number of events are dropped, rather than slowing down
+/// the gateway.
+#[derive(Clone)]
+pub struct TransactionEventBus {
+    sender: broadcast::Sender<TransactionEvent>,
+}
+
+impl TransactionEventBus {
+    pub fn new(config: &TransactionEventsConfig) -> Self {
+        let (sender, _) = broadcast::channel(config.channel_capacity);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: TransactionEvent) {
+        // Events published while there are no subscribers are dropped.
+        let _ = self.sender.send(event);
+    }
+
+    /// Publishes the events of the given status changes of transactions.
+    pub fn publish_status_updates(&self, updates: Vec<TransactionStatusUpdate>) {
+        for update in updates {
+            if let Some(event) = TransactionEvent::from_status_update(update) {
+                self.publish(event);
+            }
+        }
+    }
+
+    /// Subscribes to the events published from now on that match the given filter.
+    pub fn subscribe(&self, filter: TransactionEventFilter) -> TransactionEventSubscription {
+        TransactionEventSubscription { filter, receiver: self.sender.subscribe() }
+    }
+}
+
+pub struct TransactionEventSubscription {
+    filter: TransactionEventFilter,
+    receiver: broadcast::Receiver<TransactionEvent>,
+}
+
+impl TransactionEventSubscription {
+    /// Returns the next matching event, or `None` once the subscriber fell behind; it should then
+    /// resubscribe and query the statuses of its transactions.
+    pub async fn next(&mut self) -> Option<TransactionEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(n_skipped_events)) => {
+                    debug!(
+                        "Dropping a transaction event subscriber that fell behind by {} events.",
+                        n_skipped_events
+                    );
+                    return None;
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
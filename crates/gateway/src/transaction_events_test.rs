@@ -0,0 +1,83 @@
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::{TransactionStatus, TransactionStatusUpdate};
+
+use crate::config::TransactionEventsConfig;
+use crate::transaction_events::{
+    TransactionEvent,
+    TransactionEventBus,
+    TransactionEventFilter,
+    TransactionEventKind,
+};
+
+fn address(value: u8) -> ContractAddress {
+    ContractAddress::from(u128::from(value))
+}
+
+fn event(tx_hash: u8, sender_address: u8, kind: TransactionEventKind) -> TransactionEvent {
+    TransactionEvent {
+        tx_hash: TransactionHash(tx_hash.into()),
+        sender_address: Some(address(sender_address)),
+        kind,
+    }
+}
+
+#[tokio::test]
+async fn subscribers_get_the_matching_events() {
+    let bus = TransactionEventBus::new(&TransactionEventsConfig::default());
+    let mut tx_subscription = bus.subscribe(TransactionEventFilter {
+        tx_hash: Some(TransactionHash(1_u8.into())),
+        ..Default::default()
+    });
+    let mut sender_subscription = bus.subscribe(TransactionEventFilter {
+        sender_address: Some(address(2)),
+        ..Default::default()
+    });
+
+    bus.publish(event(1, 1, TransactionEventKind::Accepted));
+    bus.publish(event(2, 2, TransactionEventKind::InMempool));
+    bus.publish(event(1, 1, TransactionEventKind::InBlock));
+    drop(bus);
+
+    assert_eq!(tx_subscription.next().await, Some(event(1, 1, TransactionEventKind::Accepted)));
+    assert_eq!(tx_subscription.next().await, Some(event(1, 1, TransactionEventKind::InBlock)));
+    assert_eq!(tx_subscription.next().await, None);
+    assert_eq!(
+        sender_subscription.next().await,
+        Some(event(2, 2, TransactionEventKind::InMempool))
+    );
+    assert_eq!(sender_subscription.next().await, None);
+}
+
+#[tokio::test]
+async fn subscribers_falling_behind_are_dropped() {
+    let bus = TransactionEventBus::new(&TransactionEventsConfig { channel_capacity: 1 });
+    let mut subscription = bus.subscribe(TransactionEventFilter {
+        tx_hash: Some(TransactionHash(1_u8.into())),
+        ..Default::default()
+    });
+
+    bus.publish(event(1, 1, TransactionEventKind::Accepted));
+    bus.publish(event(1, 1, TransactionEventKind::InMempool));
+
+    assert_eq!(subscription.next().await, None);
+}
+
+#[test]
+fn rejections_carry_their_reason() {
+    let update = TransactionStatusUpdate {
+        tx_hash: TransactionHash(1_u8.into()),
+        status: TransactionStatus::Rejected,
+        sender_address: Some(address(1)),
+        reason: Some("invalid nonce".to_owned()),
+    };
+
+    assert_eq!(
+        TransactionEvent::from_status_update(update),
+        Some(event(1, 1, TransactionEventKind::Rejected { reason: "invalid nonce".to_owned() }))
+    );
+    assert_eq!(
+        serde_json::to_value(event(1, 1, TransactionEventKind::InBlock)).unwrap(),
+        serde_json::json!({"tx_hash": "0x1", "sender_address": "0x1", "event": "IN_BLOCK"})
+    );
+}
@@ -0,0 +1,89 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::{TransactionStatus, TransactionStatusUpdate};
+
+use crate::config::TransactionStatusConfig;
+
+#[cfg(test)]
+#[path = "transaction_status_test.rs"]
+mod transaction_status_test;
+
+struct TrackedTransaction {
+    status: TransactionStatus,
+    sender_address: Option<ContractAddress>,
+}
+
+#[derive(Default)]
+struct Entries {
+    transactions: HashMap<TransactionHash, TrackedTransaction>,
+    // The transactions in the order they were first seen.
+    tx_hashes: VecDeque<TransactionHash>,
+}
+
+/// Tracks the statuses of the transactions received by the gateway: they are received once the
+/// mempool accepts them, and are then rejected or included according to the events of the
+/// mempool. Up to a configurable number of transactions are tracked; the statuses of the oldest
+/// ones are forgotten first.
+pub struct TransactionStatusStore {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl TransactionStatusStore {
+    pub fn new(config: &TransactionStatusConfig) -> Self {
+        Self { capacity: config.capacity, entries: Mutex::new(Entries::default()) }
+    }
+
+    pub fn get(&self, tx_hash: TransactionHash) -> TransactionStatus {
+        let entries = self.entries.lock().expect("The transaction status store lock is poisoned.");
+        entries
+            .transactions
+            .get(&tx_hash)
+            .map_or(TransactionStatus::NotReceived, |transaction| transaction.status)
+    }
+
+    /// Records the given status changes, and returns those that were applied, with the senders of
+    /// the transactions if they are known. Inclusion is final, e.g., a transaction included in a
+    /// block before the gateway recorded its receipt remains included.
+    pub fn update(
+        &self,
+        updates: impl IntoIterator<Item = TransactionStatusUpdate>,
+    ) -> Vec<TransactionStatusUpdate> {
+        let mut guard =
+            self.entries.lock().expect("The transaction status store lock is poisoned.");
+        let entries = &mut *guard;
+        let mut applied_updates = Vec::new();
+        for mut update in updates {
+            match entries.transactions.get_mut(&update.tx_hash) {
+                Some(transaction) => {
+                    if transaction.status == TransactionStatus::Included {
+                        continue;
+                    }
+                    transaction.status = update.status;
+                    transaction.sender_address =
+                        transaction.sender_address.or(update.sender_address);
+                    update.sender_address = transaction.sender_address;
+                }
+                None => {
+                    entries.transactions.insert(
+                        update.tx_hash,
+                        TrackedTransaction {
+                            status: update.status,
+                            sender_address: update.sender_address,
+                        },
+                    );
+                    entries.tx_hashes.push_back(update.tx_hash);
+                }
+            }
+            applied_updates.push(update);
+        }
+        while entries.tx_hashes.len() > self.capacity {
+            let tx_hash = entries.tx_hashes.pop_front().expect("The front entry exists.");
+            entries.transactions.remove(&tx_hash);
+        }
+        applied_updates
+    }
+}
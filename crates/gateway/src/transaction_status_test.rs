@@ -0,0 +1,75 @@
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::{TransactionStatus, TransactionStatusUpdate};
+
+use crate::config::TransactionStatusConfig;
+use crate::transaction_status::TransactionStatusStore;
+
+fn update(tx_hash: u8, status: TransactionStatus) -> TransactionStatusUpdate {
+    TransactionStatusUpdate {
+        tx_hash: TransactionHash(tx_hash.into()),
+        status,
+        sender_address: None,
+        reason: None,
+    }
+}
+
+fn status(store: &TransactionStatusStore, tx_hash: u8) -> TransactionStatus {
+    store.get(TransactionHash(tx_hash.into()))
+}
+
+#[test]
+fn statuses_follow_the_updates() {
+    let store = TransactionStatusStore::new(&TransactionStatusConfig::default());
+    assert_eq!(status(&store, 1), TransactionStatus::NotReceived);
+
+    store.update([update(1, TransactionStatus::Received), update(2, TransactionStatus::Received)]);
+    store.update([update(1, TransactionStatus::Included), update(2, TransactionStatus::Rejected)]);
+
+    assert_eq!(status(&store, 1), TransactionStatus::Included);
+    assert_eq!(status(&store, 2), TransactionStatus::Rejected);
+
+    // A rejected transaction may be resubmitted.
+    store.update([update(2, TransactionStatus::Received)]);
+    assert_eq!(status(&store, 2), TransactionStatus::Received);
+}
+
+#[test]
+fn inclusion_is_final() {
+    let store = TransactionStatusStore::new(&TransactionStatusConfig::default());
+
+    store.update([update(1, TransactionStatus::Included)]);
+    store.update([update(1, TransactionStatus::Received)]);
+    store.update([update(1, TransactionStatus::Rejected)]);
+
+    assert_eq!(status(&store, 1), TransactionStatus::Included);
+}
+
+#[test]
+fn applied_updates_carry_the_known_senders() {
+    let store = TransactionStatusStore::new(&TransactionStatusConfig::default());
+    let sender_address = Some(ContractAddress::from(1_u128));
+    let received =
+        TransactionStatusUpdate { sender_address, ..update(1, TransactionStatus::Received) };
+
+    assert_eq!(store.update([received.clone()]), vec![received]);
+    assert_eq!(
+        store.update([update(1, TransactionStatus::Included)]),
+        vec![TransactionStatusUpdate { sender_address, ..update(1, TransactionStatus::Included) }]
+    );
+    // Updates of included transactions are not applied.
+    assert_eq!(store.update([update(1, TransactionStatus::Rejected)]), vec![]);
+}
+
+#[test]
+fn oldest_statuses_are_forgotten_beyond_the_capacity() {
+    let store = TransactionStatusStore::new(&TransactionStatusConfig { capacity: 2 });
+
+    store.update([update(1, TransactionStatus::Received), update(2, TransactionStatus::Received)]);
+    // Status changes of tracked transactions don't make them newer.
+    store.update([update(1, TransactionStatus::Included), update(3, TransactionStatus::Received)]);
+
+    assert_eq!(status(&store, 1), TransactionStatus::NotReceived);
+    assert_eq!(status(&store, 2), TransactionStatus::Received);
+    assert_eq!(status(&store, 3), TransactionStatus::Received);
+}
@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use starknet_api::rpc_transaction::RpcTransaction;
+use tracing::info;
+
+use crate::config::TransactionTypeConfig;
+use crate::errors::{TransactionTypeGateError, TransactionTypeGateResult};
+
+#[cfg(test)]
+#[path = "transaction_type_gate_test.rs"]
+mod transaction_type_gate_test;
+
+/// Decides which transaction types the gateway accepts. Initialized from [`TransactionTypeConfig`]
+/// and can be toggled at runtime, e.g., to pause declares during an incident.
+#[derive(Debug)]
+pub struct TransactionTypeGate {
+    declare_enabled: AtomicBool,
+    deploy_account_enabled: AtomicBool,
+}
+
+impl TransactionTypeGate {
+    pub fn new(config: &TransactionTypeConfig) -> Self {
+        Self {
+            declare_enabled: AtomicBool::new(config.enable_declare),
+            deploy_account_enabled: AtomicBool::new(config.enable_deploy_account),
+        }
+    }
+
+    pub fn validate(&self, tx: &RpcTransaction) -> TransactionTypeGateResult<()> {
+        let (enabled, tx_type) = match tx {
+            RpcTransaction::Declare(_) => (&self.declare_enabled, "Declare"),
+            RpcTransaction::DeployAccount(_) => (&self.deploy_account_enabled, "DeployAccount"),
+            RpcTransaction::Invoke(_) => return Ok(()),
+        };
+
+        if !enabled.load(Ordering::Relaxed) {
+            return Err(TransactionTypeGateError::TransactionTypeDisabled {
+                tx_type: tx_type.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn set_declare_enabled(&self, enabled: bool) {
+        Self::toggle(&self.declare_enabled, enabled, "Declare");
+    }
+
+    pub fn set_deploy_account_enabled(&self, enabled: bool) {
+        Self::toggle(&self.deploy_account_enabled, enabled, "DeployAccount");
+    }
+
+    fn toggle(flag: &AtomicBool, enabled: bool, tx_type: &str) {
+        let previous = flag.swap(enabled, Ordering::Relaxed);
+        if previous != enabled {
+            info!(target: "audit", tx_type, enabled, "Toggled acceptance of transaction type.");
+        }
+    }
+}
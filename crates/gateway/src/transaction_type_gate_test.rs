@@ -0,0 +1,58 @@
+use assert_matches::assert_matches;
+use mempool_test_utils::starknet_api_test_utils::{rpc_tx_for_testing, TransactionType};
+use rstest::rstest;
+use starknet_api::calldata;
+use starknet_api::transaction::{AllResourceBounds, TransactionSignature};
+
+use crate::config::TransactionTypeConfig;
+use crate::errors::TransactionTypeGateError;
+use crate::transaction_type_gate::TransactionTypeGate;
+
+#[rstest]
+#[case::declare(TransactionType::Declare, "Declare")]
+#[case::deploy_account(TransactionType::DeployAccount, "DeployAccount")]
+fn disabled_transaction_type_is_rejected(
+    #[case] tx_type: TransactionType,
+    #[case] expected_tx_type: &str,
+) {
+    let gate = TransactionTypeGate::new(&TransactionTypeConfig {
+        enable_declare: false,
+        enable_deploy_account: false,
+    });
+    let tx = rpc_tx_for_testing(
+        tx_type,
+        AllResourceBounds::default(),
+        calldata![],
+        TransactionSignature::default(),
+    );
+
+    assert_matches!(
+        gate.validate(&tx),
+        Err(TransactionTypeGateError::TransactionTypeDisabled { tx_type })
+        if tx_type == expected_tx_type
+    );
+}
+
+#[rstest]
+fn toggling_transaction_type_at_runtime(
+    #[values(TransactionType::Declare, TransactionType::DeployAccount, TransactionType::Invoke)]
+    tx_type: TransactionType,
+) {
+    let is_invoke = matches!(tx_type, TransactionType::Invoke);
+    let gate = TransactionTypeGate::new(&TransactionTypeConfig::default());
+    let tx = rpc_tx_for_testing(
+        tx_type,
+        AllResourceBounds::default(),
+        calldata![],
+        TransactionSignature::default(),
+    );
+    assert_matches!(gate.validate(&tx), Ok(()));
+
+    gate.set_declare_enabled(false);
+    gate.set_deploy_account_enabled(false);
+    assert_eq!(gate.validate(&tx).is_ok(), is_invoke);
+
+    gate.set_declare_enabled(true);
+    gate.set_deploy_account_enabled(true);
+    assert_matches!(gate.validate(&tx), Ok(()));
+}
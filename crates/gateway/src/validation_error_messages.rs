@@ -0,0 +1,219 @@
+use blockifier::blockifier::stateful_validator::StatefulValidatorError;
+use blockifier::execution::errors::{EntryPointExecutionError, PreExecutionError};
+use blockifier::transaction::errors::{
+    TransactionExecutionError,
+    TransactionFeeError,
+    TransactionPreValidationError,
+};
+use num_bigint::BigUint;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
+
+use crate::rejections::{Rejection, RejectionCode};
+
+#[cfg(test)]
+#[path = "validation_error_messages_test.rs"]
+mod validation_error_messages_test;
+
+/// Returns the message reported to the user for a transaction that failed stateful validation.
+/// Common failures are explained with the values needed to fix the transaction, and the rest are
+/// reported as returned by the blockifier.
+pub fn validation_error_message(err: &StatefulValidatorError) -> String {
+    let message = match err {
+        StatefulValidatorError::TransactionPreValidationError(err) => {
+            pre_validation_error_message(err)
+        }
+        StatefulValidatorError::TransactionExecutionError(err) => execution_error_message(err),
+        StatefulValidatorError::StateError(_)
+        | StatefulValidatorError::TransactionExecutorError(_) => None,
+    };
+    message.unwrap_or_else(|| err.to_string())
+}
+
+/// Returns the rejection of a transaction that failed stateful validation: the message of
+/// [`validation_error_message`], with the code and the values of the failure.
+pub fn validation_rejection(err: &StatefulValidatorError) -> Rejection {
+    let rejection = Rejection::new(RejectionCode::ValidationFailed, validation_error_message(err));
+    match err {
+        StatefulValidatorError::TransactionPreValidationError(err) => {
+            pre_validation_rejection(rejection, err)
+        }
+        StatefulValidatorError::TransactionExecutionError(err) => {
+            execution_rejection(rejection, err)
+        }
+        StatefulValidatorError::StateError(_)
+        | StatefulValidatorError::TransactionExecutorError(_) => rejection,
+    }
+}
+
+fn pre_validation_rejection(
+    rejection: Rejection,
+    err: &TransactionPreValidationError,
+) -> Rejection {
+    match err {
+        TransactionPreValidationError::InvalidNonce {
+            address,
+            account_nonce,
+            incoming_tx_nonce,
+        } => Rejection { code: RejectionCode::InvalidNonce, ..rejection }
+            .with("sender_address", address)
+            .with("expected_nonce", account_nonce)
+            .with("actual_nonce", incoming_tx_nonce),
+        TransactionPreValidationError::TransactionFeeError(err) => fee_rejection(rejection, err),
+        TransactionPreValidationError::StateError(_) => rejection,
+    }
+}
+
+fn execution_rejection(rejection: Rejection, err: &TransactionExecutionError) -> Rejection {
+    match err {
+        TransactionExecutionError::ValidateTransactionError {
+            error:
+                EntryPointExecutionError::PreExecutionError(PreExecutionError::EntryPointNotFound(
+                    selector,
+                )),
+            class_hash,
+            storage_address,
+            ..
+        }
+        | TransactionExecutionError::ExecutionError {
+            error:
+                EntryPointExecutionError::PreExecutionError(PreExecutionError::EntryPointNotFound(
+                    selector,
+                )),
+            class_hash,
+            storage_address,
+            ..
+        } => Rejection { code: RejectionCode::EntryPointNotFound, ..rejection }
+            .with("entry_point_selector", selector)
+            .with("class_hash", class_hash)
+            .with("contract_address", storage_address),
+        // The account refused the transaction, e.g., for an invalid signature.
+        TransactionExecutionError::ValidateTransactionError { .. } => {
+            Rejection { code: RejectionCode::AccountValidationFailed, ..rejection }
+        }
+        TransactionExecutionError::TransactionFeeError(err) => fee_rejection(rejection, err),
+        TransactionExecutionError::TransactionPreValidationError(err) => {
+            pre_validation_rejection(rejection, err)
+        }
+        _ => rejection,
+    }
+}
+
+// Amounts are given as hex strings, as they may exceed the precision of JSON numbers.
+fn fee_rejection(rejection: Rejection, err: &TransactionFeeError) -> Rejection {
+    match err {
+        TransactionFeeError::MaxFeeExceedsBalance { max_fee, balance } => {
+            Rejection { code: RejectionCode::InsufficientAccountBalance, ..rejection }
+                .with("max_fee", max_fee)
+                .with("balance", format!("{balance:#x}"))
+        }
+        TransactionFeeError::L1GasBoundsExceedBalance { max_amount, max_price, balance } => {
+            Rejection { code: RejectionCode::InsufficientAccountBalance, ..rejection }
+                .with("max_amount", format!("{max_amount:#x}"))
+                .with("max_price_per_unit", format!("{max_price:#x}"))
+                .with("balance", format!("{balance:#x}"))
+        }
+        TransactionFeeError::MaxFeeTooLow { min_fee, max_fee } => {
+            Rejection { code: RejectionCode::InsufficientMaxFee, ..rejection }
+                .with("max_fee", max_fee)
+                .with("min_fee", min_fee)
+        }
+        TransactionFeeError::MaxL1GasPriceTooLow { max_l1_gas_price, actual_l1_gas_price } => {
+            Rejection { code: RejectionCode::MaxGasPriceTooLow, ..rejection }
+                .with("max_price_per_unit", format!("{max_l1_gas_price:#x}"))
+                .with("gas_price", format!("{actual_l1_gas_price:#x}"))
+        }
+        TransactionFeeError::MaxL1GasAmountTooLow { max_l1_gas_amount, minimal_l1_gas_amount } => {
+            Rejection { code: RejectionCode::InsufficientMaxFee, ..rejection }
+                .with("max_amount", format!("{max_l1_gas_amount:#x}"))
+                .with("min_amount", format!("{minimal_l1_gas_amount:#x}"))
+        }
+        _ => rejection,
+    }
+}
+
+fn pre_validation_error_message(err: &TransactionPreValidationError) -> Option<String> {
+    match err {
+        TransactionPreValidationError::InvalidNonce {
+            address,
+            account_nonce,
+            incoming_tx_nonce,
+        } => Some(invalid_nonce_message(*address, *account_nonce, *incoming_tx_nonce)),
+        TransactionPreValidationError::TransactionFeeError(err) => fee_error_message(err),
+        TransactionPreValidationError::StateError(_) => None,
+    }
+}
+
+fn execution_error_message(err: &TransactionExecutionError) -> Option<String> {
+    match err {
+        TransactionExecutionError::ValidateTransactionError {
+            error,
+            class_hash,
+            storage_address,
+            ..
+        }
+        | TransactionExecutionError::ExecutionError {
+            error, class_hash, storage_address, ..
+        } => match error {
+            EntryPointExecutionError::PreExecutionError(PreExecutionError::EntryPointNotFound(
+                selector,
+            )) => Some(entry_point_not_found_message(*selector, *class_hash, *storage_address)),
+            _ => None,
+        },
+        TransactionExecutionError::TransactionFeeError(err) => fee_error_message(err),
+        TransactionExecutionError::TransactionPreValidationError(err) => {
+            pre_validation_error_message(err)
+        }
+        _ => None,
+    }
+}
+
+fn fee_error_message(err: &TransactionFeeError) -> Option<String> {
+    match err {
+        TransactionFeeError::MaxFeeExceedsBalance { max_fee, balance } => Some(format!(
+            "Insufficient account balance: the max fee requires a balance of {}, but the account \
+             balance is {balance}. Fund the account or lower the max fee.",
+            max_fee.0
+        )),
+        TransactionFeeError::L1GasBoundsExceedBalance { max_amount, max_price, balance } => {
+            let required_balance = BigUint::from(*max_amount) * *max_price;
+            Some(format!(
+                "Insufficient account balance: the L1 gas bounds (max amount {max_amount}, max \
+                 price per unit {max_price}) require a balance of {required_balance}, but the \
+                 account balance is {balance}. Fund the account or lower the resource bounds."
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn invalid_nonce_message(
+    address: ContractAddress,
+    account_nonce: Nonce,
+    incoming_tx_nonce: Nonce,
+) -> String {
+    let hint = if incoming_tx_nonce < account_nonce {
+        "A transaction with this nonce was already executed"
+    } else {
+        "Transactions with the preceding nonces must be executed first"
+    };
+    format!(
+        "Invalid transaction nonce for account {:#x}: expected nonce {}, got {}. {hint}.",
+        address.0.key(),
+        account_nonce.0,
+        incoming_tx_nonce.0
+    )
+}
+
+fn entry_point_not_found_message(
+    selector: EntryPointSelector,
+    class_hash: ClassHash,
+    address: ContractAddress,
+) -> String {
+    format!(
+        "Entry point {:#x} was not found in class {:#x} of contract {:#x}. Make sure the contract \
+         is deployed with a class that implements this entry point.",
+        selector.0,
+        class_hash.0,
+        address.0.key()
+    )
+}
@@ -0,0 +1,106 @@
+use blockifier::blockifier::stateful_validator::StatefulValidatorError;
+use blockifier::execution::errors::{EntryPointExecutionError, PreExecutionError};
+use blockifier::state::errors::StateError;
+use blockifier::transaction::errors::{
+    TransactionExecutionError,
+    TransactionFeeError,
+    TransactionPreValidationError,
+};
+use num_bigint::BigUint;
+use rstest::rstest;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
+use starknet_api::transaction::Fee;
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+
+use crate::rejections::RejectionCode;
+use crate::validation_error_messages::{validation_error_message, validation_rejection};
+
+fn invalid_nonce_error(account_nonce: u8, incoming_tx_nonce: u8) -> StatefulValidatorError {
+    StatefulValidatorError::TransactionPreValidationError(
+        TransactionPreValidationError::InvalidNonce {
+            address: contract_address!("0x100"),
+            account_nonce: Nonce(felt!(account_nonce)),
+            incoming_tx_nonce: Nonce(felt!(incoming_tx_nonce)),
+        },
+    )
+}
+
+#[rstest]
+#[case::nonce_too_low(
+    invalid_nonce_error(5, 3),
+    "Invalid transaction nonce for account 0x100: expected nonce 5, got 3. A transaction with \
+     this nonce was already executed."
+)]
+#[case::nonce_too_high(
+    invalid_nonce_error(5, 7),
+    "Invalid transaction nonce for account 0x100: expected nonce 5, got 7. Transactions with the \
+     preceding nonces must be executed first."
+)]
+#[case::max_fee_exceeds_balance(
+    StatefulValidatorError::TransactionPreValidationError(
+        TransactionPreValidationError::TransactionFeeError(
+            TransactionFeeError::MaxFeeExceedsBalance { max_fee: Fee(1000), balance: BigUint::from(10_u8) },
+        ),
+    ),
+    "Insufficient account balance: the max fee requires a balance of 1000, but the account balance \
+     is 10. Fund the account or lower the max fee."
+)]
+#[case::l1_gas_bounds_exceed_balance(
+    StatefulValidatorError::TransactionPreValidationError(
+        TransactionPreValidationError::TransactionFeeError(
+            TransactionFeeError::L1GasBoundsExceedBalance {
+                max_amount: 100,
+                max_price: 30,
+                balance: BigUint::from(10_u8),
+            },
+        ),
+    ),
+    "Insufficient account balance: the L1 gas bounds (max amount 100, max price per unit 30) require \
+     a balance of 3000, but the account balance is 10. Fund the account or lower the resource \
+     bounds."
+)]
+#[case::entry_point_not_found(
+    StatefulValidatorError::TransactionExecutionError(
+        TransactionExecutionError::ValidateTransactionError {
+            error: EntryPointExecutionError::PreExecutionError(
+                PreExecutionError::EntryPointNotFound(EntryPointSelector(felt!("0x2"))),
+            ),
+            class_hash: class_hash!("0x3"),
+            storage_address: contract_address!("0x100"),
+            selector: EntryPointSelector(felt!("0x2")),
+        },
+    ),
+    "Entry point 0x2 was not found in class 0x3 of contract 0x100. Make sure the contract is \
+     deployed with a class that implements this entry point."
+)]
+fn chain_state_errors_are_explained(
+    #[case] err: StatefulValidatorError,
+    #[case] expected_message: &str,
+) {
+    assert_eq!(validation_error_message(&err), expected_message);
+}
+
+#[test]
+fn other_errors_are_reported_as_is() {
+    let err = StatefulValidatorError::StateError(StateError::StateReadError("Failure.".to_owned()));
+    assert_eq!(validation_error_message(&err), err.to_string());
+}
+
+#[test]
+fn invalid_nonce_is_rejected_with_the_expected_nonce() {
+    let err = invalid_nonce_error(5, 3);
+
+    let rejection = validation_rejection(&err);
+
+    assert_eq!(rejection.code, RejectionCode::InvalidNonce);
+    assert_eq!(rejection.message, validation_error_message(&err));
+    assert_eq!(rejection.data["sender_address"], "0x100");
+    assert_eq!(rejection.data["expected_nonce"], "0x5");
+    assert_eq!(rejection.data["actual_nonce"], "0x3");
+}
+
+#[test]
+fn other_errors_are_rejected_as_failed_validations() {
+    let err = StatefulValidatorError::StateError(StateError::StateReadError("Failure.".to_owned()));
+    assert_eq!(validation_rejection(&err).code, RejectionCode::ValidationFailed);
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::predicate::*;
+use mockall::*;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionHash;
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use starknet_mempool_infra::component_error::{Component, ComponentError};
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use crate::errors::GatewayError;
+use crate::gateway_types::{
+    AddTransactionInput,
+    AddTransactionResult,
+    GatewayResult,
+    TransactionStatus,
+    TransactionStatusUpdate,
+};
+
+pub type LocalGatewayClientImpl = LocalComponentClient<GatewayRequest, GatewayResponse>;
+pub type RemoteGatewayClientImpl = RemoteComponentClient<GatewayRequest, GatewayResponse>;
+pub type GatewayClientResult<T> = Result<T, GatewayClientError>;
+pub type GatewayRequestAndResponseSender =
+    ComponentRequestAndResponseSender<GatewayRequest, GatewayResponse>;
+pub type SharedGatewayClient = Arc<dyn GatewayClient>;
+
+/// Serves as the gateway's shared interface. Requires `Send + Sync` to allow transferring and
+/// sharing resources (inputs, futures) across threads.
+#[automock]
+#[async_trait]
+pub trait GatewayClient: Send + Sync {
+    /// Validates a transaction and adds it to the mempool.
+    async fn add_tx(&self, input: AddTransactionInput)
+        -> GatewayClientResult<AddTransactionResult>;
+    /// Returns the status of the transaction with the given hash.
+    async fn get_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> GatewayClientResult<TransactionStatus>;
+    /// Records the given changes of transaction statuses, e.g., the transactions of a committed
+    /// block.
+    async fn update_transaction_statuses(
+        &self,
+        updates: Vec<TransactionStatusUpdate>,
+    ) -> GatewayClientResult<()>;
+    /// Pauses or resumes the ingestion of transactions. While paused, submissions are rejected.
+    async fn set_ingestion_paused(&self, paused: bool) -> GatewayClientResult<()>;
+    /// Reloads the parts of the configuration that can change at runtime, e.g., the sender access
+    /// lists.
+    async fn reload_config(&self) -> GatewayClientResult<()>;
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
+pub enum GatewayRequest {
+    AddTransaction(AddTransactionInput),
+    GetTransactionStatus(TransactionHash),
+    UpdateTransactionStatuses(Vec<TransactionStatusUpdate>),
+    SetIngestionPaused(bool),
+    ReloadConfig,
+    IsAlive,
+    IsReady,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum GatewayResponse {
+    AddTransaction(GatewayResult<AddTransactionResult>),
+    GetTransactionStatus(GatewayResult<TransactionStatus>),
+    UpdateTransactionStatuses(GatewayResult<()>),
+    SetIngestionPaused(GatewayResult<()>),
+    ReloadConfig(GatewayResult<()>),
+    IsAlive(bool),
+    IsReady(bool),
+}
+
+impl HealthCheckRequest for GatewayRequest {
+    fn is_alive() -> Self {
+        GatewayRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        GatewayRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for GatewayResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            GatewayResponse::IsAlive(health) | GatewayResponse::IsReady(health) => Some(*health),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum GatewayClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    GatewayError(#[from] GatewayError),
+}
+
+impl ComponentError for GatewayClientError {
+    fn component(&self) -> Component {
+        Component::Gateway
+    }
+
+    fn is_retriable(&self) -> bool {
+        match self {
+            GatewayClientError::ClientError(err) => err.is_retriable(),
+            GatewayClientError::GatewayError(err) => err.is_retriable(),
+        }
+    }
+}
+
+impl CodedError for GatewayClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            GatewayClientError::ClientError(err) => err.error_code(),
+            GatewayClientError::GatewayError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            GatewayClientError::ClientError(err) => err.severity(),
+            GatewayClientError::GatewayError(err) => err.severity(),
+        }
+    }
+}
+
+#[async_trait]
+impl GatewayClient for LocalGatewayClientImpl {
+    async fn add_tx(
+        &self,
+        input: AddTransactionInput,
+    ) -> GatewayClientResult<AddTransactionResult> {
+        let request = GatewayRequest::AddTransaction(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(GatewayResponse, AddTransaction, GatewayClientError, GatewayError)
+    }
+
+    async fn get_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> GatewayClientResult<TransactionStatus> {
+        let request = GatewayRequest::GetTransactionStatus(tx_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            GetTransactionStatus,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn update_transaction_statuses(
+        &self,
+        updates: Vec<TransactionStatusUpdate>,
+    ) -> GatewayClientResult<()> {
+        let request = GatewayRequest::UpdateTransactionStatuses(updates);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            UpdateTransactionStatuses,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn set_ingestion_paused(&self, paused: bool) -> GatewayClientResult<()> {
+        let request = GatewayRequest::SetIngestionPaused(paused);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            SetIngestionPaused,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn reload_config(&self) -> GatewayClientResult<()> {
+        let request = GatewayRequest::ReloadConfig;
+        let response = self.send(request).await?;
+        handle_response_variants!(GatewayResponse, ReloadConfig, GatewayClientError, GatewayError)
+    }
+}
+
+#[async_trait]
+impl GatewayClient for RemoteGatewayClientImpl {
+    async fn add_tx(
+        &self,
+        input: AddTransactionInput,
+    ) -> GatewayClientResult<AddTransactionResult> {
+        let request = GatewayRequest::AddTransaction(input);
+        let response = self.send(request).await?;
+        handle_response_variants!(GatewayResponse, AddTransaction, GatewayClientError, GatewayError)
+    }
+
+    async fn get_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> GatewayClientResult<TransactionStatus> {
+        let request = GatewayRequest::GetTransactionStatus(tx_hash);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            GetTransactionStatus,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn update_transaction_statuses(
+        &self,
+        updates: Vec<TransactionStatusUpdate>,
+    ) -> GatewayClientResult<()> {
+        let request = GatewayRequest::UpdateTransactionStatuses(updates);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            UpdateTransactionStatuses,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn set_ingestion_paused(&self, paused: bool) -> GatewayClientResult<()> {
+        let request = GatewayRequest::SetIngestionPaused(paused);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            GatewayResponse,
+            SetIngestionPaused,
+            GatewayClientError,
+            GatewayError
+        )
+    }
+
+    async fn reload_config(&self) -> GatewayClientResult<()> {
+        let request = GatewayRequest::ReloadConfig;
+        let response = self.send(request).await?;
+        handle_response_variants!(GatewayResponse, ReloadConfig, GatewayClientError, GatewayError)
+    }
+}
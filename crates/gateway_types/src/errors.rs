@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GatewayError {
+    /// The transaction was rejected, with the JSON-RPC error returned to the user.
+    #[error("Transaction rejected with code {code}: {message}")]
+    TransactionRejected { code: i32, message: String, data: Option<String> },
+    #[error("Internal gateway error.")]
+    InternalError,
+    /// The sender of the transaction, or the client submitting it, exceeded its rate limit.
+    #[error("Too many requests, try again later.")]
+    TooManyRequests,
+    /// The gateway reached its limit on concurrent validations; the transaction can be resubmitted.
+    #[error("The gateway is overloaded, try again later.")]
+    ServiceUnavailable,
+    #[error("Failed to reload the gateway configuration: {reason}")]
+    ConfigReloadFailed { reason: String },
+}
+
+impl GatewayError {
+    /// Whether the transaction may be accepted if submitted again as is, once the gateway is less
+    /// loaded.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            GatewayError::TooManyRequests | GatewayError::ServiceUnavailable => true,
+            GatewayError::TransactionRejected { .. }
+            | GatewayError::InternalError
+            | GatewayError::ConfigReloadFailed { .. } => false,
+        }
+    }
+}
+
+impl CodedError for GatewayError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            GatewayError::TransactionRejected { .. } => codes::GATEWAY_TRANSACTION_REJECTED,
+            GatewayError::InternalError => codes::GATEWAY_INTERNAL_ERROR,
+            GatewayError::TooManyRequests => codes::GATEWAY_TOO_MANY_REQUESTS,
+            GatewayError::ServiceUnavailable => codes::GATEWAY_SERVICE_UNAVAILABLE,
+            GatewayError::ConfigReloadFailed { .. } => codes::GATEWAY_CONFIG_RELOAD_FAILED,
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            GatewayError::TransactionRejected { .. }
+            | GatewayError::TooManyRequests
+            | GatewayError::ServiceUnavailable => Severity::Info,
+            GatewayError::InternalError | GatewayError::ConfigReloadFailed { .. } => {
+                Severity::Error
+            }
+        }
+    }
+}
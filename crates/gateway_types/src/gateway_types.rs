@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::TransactionHash;
+
+use crate::errors::GatewayError;
+
+/// The channel through which a transaction reached the node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionSource {
+    #[default]
+    Http,
+    P2p,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionMetadata {
+    pub source: TransactionSource,
+}
+
+/// Input of the request to validate a transaction and add it to the mempool.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddTransactionInput {
+    pub tx: RpcTransaction,
+    pub metadata: TransactionMetadata,
+}
+
+/// The result of adding a transaction, including the address of a deployed account or the hash of
+/// a declared class.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AddTransactionResult {
+    Invoke { tx_hash: TransactionHash },
+    DeployAccount { tx_hash: TransactionHash, address: ContractAddress },
+    Declare { tx_hash: TransactionHash, class_hash: ClassHash },
+}
+
+impl AddTransactionResult {
+    pub fn tx_hash(&self) -> TransactionHash {
+        match self {
+            AddTransactionResult::Invoke { tx_hash }
+            | AddTransactionResult::DeployAccount { tx_hash, .. }
+            | AddTransactionResult::Declare { tx_hash, .. } => *tx_hash,
+        }
+    }
+}
+
+impl From<&Transaction> for AddTransactionResult {
+    fn from(tx: &Transaction) -> Self {
+        match tx {
+            Transaction::Declare(declare_tx) => AddTransactionResult::Declare {
+                tx_hash: declare_tx.tx_hash,
+                class_hash: declare_tx.tx.class_hash(),
+            },
+            Transaction::DeployAccount(deploy_account_tx) => AddTransactionResult::DeployAccount {
+                tx_hash: deploy_account_tx.tx_hash,
+                address: deploy_account_tx.contract_address,
+            },
+            Transaction::Invoke(invoke_tx) => {
+                AddTransactionResult::Invoke { tx_hash: invoke_tx.tx_hash }
+            }
+            Transaction::L1Handler(_) => {
+                unreachable!("L1 handler transactions are not added through the gateway.")
+            }
+        }
+    }
+}
+
+/// The status of a transaction in its lifecycle through the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionStatus {
+    /// The transaction is unknown to the node, or its status was forgotten.
+    NotReceived,
+    /// The transaction was accepted into the mempool.
+    Received,
+    /// The transaction was rejected while it was sequenced, and dropped from the mempool.
+    Rejected,
+    /// The transaction was included in a committed block.
+    Included,
+}
+
+/// A change of the status of a transaction, following an event of the mempool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatusUpdate {
+    pub tx_hash: TransactionHash,
+    pub status: TransactionStatus,
+    // Unknown for committed transactions, whose senders are known to the gateway.
+    pub sender_address: Option<ContractAddress>,
+    // Why a rejected transaction was rejected.
+    pub reason: Option<String>,
+}
+
+pub type GatewayResult<T> = Result<T, GatewayError>;
@@ -0,0 +1,71 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderRequest,
+    L1GasPriceProviderRequestAndResponseSender,
+    L1GasPriceProviderResponse,
+};
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
+use tokio::sync::mpsc::Receiver;
+
+use crate::l1_gas_price_provider::L1GasPriceProvider;
+
+pub type LocalL1GasPriceProviderServer =
+    LocalComponentServer<L1GasPriceProvider, L1GasPriceProviderRequest, L1GasPriceProviderResponse>;
+pub type RemoteL1GasPriceProviderServer = RemoteComponentServer<
+    L1GasPriceProvider,
+    L1GasPriceProviderRequest,
+    L1GasPriceProviderResponse,
+>;
+
+pub fn create_local_l1_gas_price_provider_server(
+    l1_gas_price_provider: L1GasPriceProvider,
+    rx_l1_gas_price_provider: Receiver<L1GasPriceProviderRequestAndResponseSender>,
+) -> LocalL1GasPriceProviderServer {
+    LocalComponentServer::new(l1_gas_price_provider, rx_l1_gas_price_provider)
+}
+
+pub fn create_remote_l1_gas_price_provider_server(
+    l1_gas_price_provider: L1GasPriceProvider,
+    ip_address: IpAddr,
+    port: u16,
+    serialization_format: SerializationFormat,
+) -> RemoteL1GasPriceProviderServer {
+    RemoteComponentServer::new(l1_gas_price_provider, ip_address, port)
+        .with_serialization_format(serialization_format)
+}
+
+#[async_trait]
+impl ComponentRequestHandler<L1GasPriceProviderRequest, L1GasPriceProviderResponse>
+    for L1GasPriceProvider
+{
+    async fn handle_request(
+        &mut self,
+        request: L1GasPriceProviderRequest,
+    ) -> L1GasPriceProviderResponse {
+        match request {
+            L1GasPriceProviderRequest::AddPriceSample(height, sample) => {
+                L1GasPriceProviderResponse::AddPriceSample(self.add_price_sample(height, sample))
+            }
+            L1GasPriceProviderRequest::GetPriceInfo(timestamp) => {
+                L1GasPriceProviderResponse::GetPriceInfo(self.get_price_info(timestamp))
+            }
+            L1GasPriceProviderRequest::IsAlive => {
+                L1GasPriceProviderResponse::IsAlive(self.is_alive().await)
+            }
+            L1GasPriceProviderRequest::IsReady => {
+                L1GasPriceProviderResponse::IsReady(self.is_ready().await)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for L1GasPriceProvider {}
+
+#[async_trait]
+impl ComponentHealth for L1GasPriceProvider {}
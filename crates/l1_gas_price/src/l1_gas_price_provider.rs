@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use papyrus_base_layer::PriceSample;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::{L1GasPriceProviderResult, PriceInfo};
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "l1_gas_price_provider_test.rs"]
+pub mod l1_gas_price_provider_test;
+
+// About an hour of Ethereum blocks.
+const DEFAULT_NUMBER_OF_BLOCKS_FOR_MEAN: usize = 300;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct L1GasPriceProviderConfig {
+    // The number of base layer blocks the gas prices are averaged over, smoothing out their
+    // spikes.
+    #[validate(range(min = 1))]
+    pub number_of_blocks_for_mean: usize,
+    // The maximal number of kept samples; the oldest ones are dropped.
+    #[validate(range(min = 1))]
+    pub storage_limit: usize,
+}
+
+impl Default for L1GasPriceProviderConfig {
+    fn default() -> Self {
+        Self {
+            number_of_blocks_for_mean: DEFAULT_NUMBER_OF_BLOCKS_FOR_MEAN,
+            storage_limit: 10 * DEFAULT_NUMBER_OF_BLOCKS_FOR_MEAN,
+        }
+    }
+}
+
+impl SerializeConfig for L1GasPriceProviderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "number_of_blocks_for_mean",
+                &self.number_of_blocks_for_mean,
+                "The number of base layer blocks the L1 gas prices are averaged over.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "storage_limit",
+                &self.storage_limit,
+                "The maximal number of kept base layer price samples.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Provides the L1 gas prices of built blocks: the moving average of the gas prices of the
+/// base layer blocks preceding them, sampled by the L1 gas price scraper.
+pub struct L1GasPriceProvider {
+    config: L1GasPriceProviderConfig,
+    // The samples of consecutive base layer blocks, by height.
+    price_samples: VecDeque<(u64, PriceSample)>,
+}
+
+impl L1GasPriceProvider {
+    pub fn new(config: L1GasPriceProviderConfig) -> Self {
+        Self { config, price_samples: VecDeque::new() }
+    }
+
+    pub fn add_price_sample(
+        &mut self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderResult<()> {
+        if let Some((last_height, _)) = self.price_samples.back() {
+            let expected = last_height + 1;
+            if height != expected {
+                return Err(L1GasPriceProviderError::UnexpectedHeight { expected, found: height });
+            }
+        }
+        self.price_samples.push_back((height, sample));
+        if self.price_samples.len() > self.config.storage_limit {
+            self.price_samples.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Averages the gas prices of the latest blocks up to the given timestamp, so that all the
+    /// nodes that sampled them agree on the prices of a block with this timestamp.
+    pub fn get_price_info(&self, timestamp: BlockTimestamp) -> L1GasPriceProviderResult<PriceInfo> {
+        // The timestamps of base layer blocks don't decrease.
+        let n_samples =
+            self.price_samples.partition_point(|(_, sample)| sample.timestamp <= timestamp.0);
+        let n_required = self.config.number_of_blocks_for_mean;
+        if n_samples < n_required {
+            return Err(L1GasPriceProviderError::NotEnoughSamples {
+                timestamp,
+                n_samples,
+                n_required,
+            });
+        }
+
+        let (base_fee_sum, blob_fee_sum) = self
+            .price_samples
+            .range(n_samples - n_required..n_samples)
+            .fold((0_u128, 0_u128), |(base_fee_sum, blob_fee_sum), (_, sample)| {
+                (
+                    base_fee_sum.saturating_add(sample.base_fee_per_gas),
+                    blob_fee_sum.saturating_add(sample.blob_fee),
+                )
+            });
+        let n_required = u128::try_from(n_required).expect("The window size should fit u128.");
+        Ok(PriceInfo {
+            base_fee_per_gas: base_fee_sum / n_required,
+            blob_fee: blob_fee_sum / n_required,
+        })
+    }
+}
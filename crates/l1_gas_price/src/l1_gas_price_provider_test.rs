@@ -0,0 +1,84 @@
+use assert_matches::assert_matches;
+use papyrus_base_layer::PriceSample;
+use starknet_api::block::BlockTimestamp;
+use starknet_l1_gas_price_types::errors::L1GasPriceProviderError;
+use starknet_l1_gas_price_types::l1_gas_price_types::PriceInfo;
+
+use crate::l1_gas_price_provider::{L1GasPriceProvider, L1GasPriceProviderConfig};
+
+const N_BLOCKS_FOR_MEAN: usize = 3;
+
+// The sample of the block of the given height, mined at 10 times its height.
+fn price_sample(height: u64) -> PriceSample {
+    PriceSample {
+        timestamp: 10 * height,
+        base_fee_per_gas: u128::from(height) * 100,
+        blob_fee: u128::from(height),
+    }
+}
+
+fn provider_with_samples(heights: std::ops::Range<u64>) -> L1GasPriceProvider {
+    let mut provider = L1GasPriceProvider::new(L1GasPriceProviderConfig {
+        number_of_blocks_for_mean: N_BLOCKS_FOR_MEAN,
+        storage_limit: 5,
+    });
+    for height in heights {
+        provider.add_price_sample(height, price_sample(height)).unwrap();
+    }
+    provider
+}
+
+#[test]
+fn price_info_is_the_mean_of_the_latest_blocks_up_to_the_timestamp() {
+    let provider = provider_with_samples(0..5);
+
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(45)),
+        Ok(PriceInfo { base_fee_per_gas: 300, blob_fee: 3 })
+    );
+    // Blocks later than the timestamp are ignored.
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(39)),
+        Ok(PriceInfo { base_fee_per_gas: 200, blob_fee: 2 })
+    );
+}
+
+#[test]
+fn price_info_requires_a_full_window() {
+    let provider = provider_with_samples(0..5);
+
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(15)),
+        Err(L1GasPriceProviderError::NotEnoughSamples {
+            timestamp: BlockTimestamp(15),
+            n_samples: 2,
+            n_required: N_BLOCKS_FOR_MEAN,
+        })
+    );
+}
+
+#[test]
+fn samples_beyond_the_storage_limit_are_dropped() {
+    let provider = provider_with_samples(0..7);
+
+    // Blocks 0 and 1 were dropped, leaving 2 samples up to block 3.
+    assert_matches!(
+        provider.get_price_info(BlockTimestamp(30)),
+        Err(L1GasPriceProviderError::NotEnoughSamples { n_samples: 2, .. })
+    );
+    assert_eq!(
+        provider.get_price_info(BlockTimestamp(60)),
+        Ok(PriceInfo { base_fee_per_gas: 500, blob_fee: 5 })
+    );
+}
+
+#[test]
+fn samples_are_added_in_order() {
+    let mut provider = provider_with_samples(0..2);
+
+    assert_eq!(
+        provider.add_price_sample(3, price_sample(3)),
+        Err(L1GasPriceProviderError::UnexpectedHeight { expected: 2, found: 3 })
+    );
+    assert_eq!(provider.add_price_sample(2, price_sample(2)), Ok(()));
+}
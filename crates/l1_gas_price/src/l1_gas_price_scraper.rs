@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
+use papyrus_base_layer::BaseLayerContract;
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderClientError,
+    SharedL1GasPriceProviderClient,
+};
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use thiserror::Error;
+use tracing::{debug, warn};
+use validator::Validate;
+
+#[cfg(test)]
+#[path = "l1_gas_price_scraper_test.rs"]
+pub mod l1_gas_price_scraper_test;
+
+#[derive(Debug, Error)]
+pub enum L1GasPriceScraperError<E: std::error::Error> {
+    #[error("Failed to read from the base layer: {0}")]
+    BaseLayerError(E),
+    #[error(transparent)]
+    ProviderError(#[from] L1GasPriceProviderClientError),
+}
+
+pub type L1GasPriceScraperResult<T, E> = Result<T, L1GasPriceScraperError<E>>;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct L1GasPriceScraperConfig {
+    pub base_layer_config: EthereumBaseLayerConfig,
+    // The number of base layer blocks sampled on startup, which should cover the window the
+    // prices are averaged over.
+    pub startup_num_blocks: u64,
+    // Blocks are sampled once they have this many confirmations, so that reorgs of the base layer
+    // don't change the sampled prices.
+    pub finality: u64,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub polling_interval: Duration,
+}
+
+impl Default for L1GasPriceScraperConfig {
+    fn default() -> Self {
+        Self {
+            base_layer_config: EthereumBaseLayerConfig::default(),
+            startup_num_blocks: 300,
+            finality: 0,
+            polling_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl SerializeConfig for L1GasPriceScraperConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let members = BTreeMap::from_iter([
+            ser_param(
+                "startup_num_blocks",
+                &self.startup_num_blocks,
+                "The number of base layer blocks sampled on startup.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "finality",
+                &self.finality,
+                "The number of confirmations a base layer block needs before it is sampled.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "polling_interval",
+                &self.polling_interval.as_secs(),
+                "The interval, in seconds, between polls of the base layer for new blocks.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![members, append_sub_config_name(self.base_layer_config.dump(), "base_layer_config")]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Samples the gas prices of the base layer blocks once they are final, and adds them to the L1
+/// gas price provider.
+pub struct L1GasPriceScraper<B: BaseLayerContract> {
+    config: L1GasPriceScraperConfig,
+    l1_gas_price_provider: SharedL1GasPriceProviderClient,
+    base_layer: B,
+    // Set on the first poll, by the latest block and the number of blocks sampled on startup.
+    next_block_number: Option<u64>,
+}
+
+impl<B> L1GasPriceScraper<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    B::Error: std::error::Error,
+{
+    pub fn new(
+        config: L1GasPriceScraperConfig,
+        l1_gas_price_provider: SharedL1GasPriceProviderClient,
+        base_layer: B,
+    ) -> Self {
+        Self { config, l1_gas_price_provider, base_layer, next_block_number: None }
+    }
+
+    /// Samples the final blocks that weren't sampled yet, and returns the number of sampled
+    /// blocks.
+    pub async fn update_prices(&mut self) -> L1GasPriceScraperResult<usize, B::Error> {
+        let Some(latest_block_number) = self
+            .base_layer
+            .latest_l1_block_number(self.config.finality)
+            .await
+            .map_err(L1GasPriceScraperError::BaseLayerError)?
+        else {
+            return Ok(0);
+        };
+        let startup_num_blocks = self.config.startup_num_blocks;
+        let next_block_number = self
+            .next_block_number
+            .get_or_insert((latest_block_number + 1).saturating_sub(startup_num_blocks));
+
+        let mut n_sampled_blocks = 0;
+        while *next_block_number <= latest_block_number {
+            let Some(sample) = self
+                .base_layer
+                .get_price_sample(*next_block_number)
+                .await
+                .map_err(L1GasPriceScraperError::BaseLayerError)?
+            else {
+                break;
+            };
+            self.l1_gas_price_provider.add_price_sample(*next_block_number, sample).await?;
+            *next_block_number += 1;
+            n_sampled_blocks += 1;
+        }
+        Ok(n_sampled_blocks)
+    }
+}
+
+#[async_trait]
+impl<B> ComponentServerStarter for L1GasPriceScraper<B>
+where
+    B: BaseLayerContract + Send + Sync,
+    B::Error: std::error::Error + Send,
+{
+    async fn start(&mut self) {
+        let mut interval = tokio::time::interval(self.config.polling_interval);
+        loop {
+            interval.tick().await;
+            match self.update_prices().await {
+                Ok(n_sampled_blocks) => debug!("Sampled {} base layer blocks.", n_sampled_blocks),
+                Err(err) => warn!("Failed to sample the gas prices of the base layer: {}", err),
+            }
+        }
+    }
+}
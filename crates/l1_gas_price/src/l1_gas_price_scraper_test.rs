@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::predicate::eq;
+use mockall::Sequence;
+use papyrus_base_layer::{BaseLayerContract, PriceSample};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_l1_gas_price_types::communication::MockL1GasPriceProviderClient;
+use thiserror::Error;
+
+use crate::l1_gas_price_scraper::{L1GasPriceScraper, L1GasPriceScraperConfig};
+
+const STARTUP_NUM_BLOCKS: u64 = 2;
+const FINALITY: u64 = 1;
+
+#[derive(Debug, Error)]
+#[error("Base layer error.")]
+struct FakeBaseLayerError;
+
+// A base layer whose blocks are mined at 10 times their height.
+struct FakeBaseLayer {
+    latest_block_number: u64,
+}
+
+#[async_trait]
+impl BaseLayerContract for FakeBaseLayer {
+    type Error = FakeBaseLayerError;
+
+    async fn latest_proved_block(
+        &self,
+        _min_confirmations: Option<u64>,
+    ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error> {
+        unimplemented!()
+    }
+
+    async fn latest_l1_block_number(
+        &self,
+        min_confirmations: u64,
+    ) -> Result<Option<u64>, Self::Error> {
+        Ok(self.latest_block_number.checked_sub(min_confirmations))
+    }
+
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<PriceSample>, Self::Error> {
+        Ok((block_number <= self.latest_block_number).then(|| price_sample(block_number)))
+    }
+}
+
+fn price_sample(block_number: u64) -> PriceSample {
+    PriceSample {
+        timestamp: 10 * block_number,
+        base_fee_per_gas: u128::from(block_number),
+        blob_fee: 1,
+    }
+}
+
+fn expect_samples(
+    provider: &mut MockL1GasPriceProviderClient,
+    seq: &mut Sequence,
+    block_numbers: std::ops::RangeInclusive<u64>,
+) {
+    for block_number in block_numbers {
+        provider
+            .expect_add_price_sample()
+            .with(eq(block_number), eq(price_sample(block_number)))
+            .times(1)
+            .in_sequence(seq)
+            .returning(|_, _| Ok(()));
+    }
+}
+
+fn scraper(
+    provider: MockL1GasPriceProviderClient,
+    latest_block_number: u64,
+) -> L1GasPriceScraper<FakeBaseLayer> {
+    let config = L1GasPriceScraperConfig {
+        startup_num_blocks: STARTUP_NUM_BLOCKS,
+        finality: FINALITY,
+        ..Default::default()
+    };
+    L1GasPriceScraper::new(config, Arc::new(provider), FakeBaseLayer { latest_block_number })
+}
+
+#[tokio::test]
+async fn samples_the_final_blocks_from_the_startup_window_on() {
+    let mut provider = MockL1GasPriceProviderClient::new();
+    let mut seq = Sequence::new();
+    // Block 10 is not final yet.
+    expect_samples(&mut provider, &mut seq, 8..=9);
+    let mut scraper = scraper(provider, 10);
+
+    assert_eq!(scraper.update_prices().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn samples_new_blocks_on_later_polls() {
+    let mut provider = MockL1GasPriceProviderClient::new();
+    let mut seq = Sequence::new();
+    expect_samples(&mut provider, &mut seq, 8..=12);
+    let mut scraper = scraper(provider, 10);
+    scraper.update_prices().await.unwrap();
+
+    scraper.base_layer.latest_block_number = 13;
+
+    assert_eq!(scraper.update_prices().await.unwrap(), 3);
+    assert_eq!(scraper.update_prices().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn samples_from_genesis_on_a_short_chain() {
+    let mut provider = MockL1GasPriceProviderClient::new();
+    let mut seq = Sequence::new();
+    expect_samples(&mut provider, &mut seq, 0..=0);
+    let mut scraper = scraper(provider, 1);
+
+    assert_eq!(scraper.update_prices().await.unwrap(), 1);
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::predicate::*;
+use mockall::*;
+use papyrus_base_layer::PriceSample;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use crate::errors::L1GasPriceProviderError;
+use crate::l1_gas_price_types::{L1GasPriceProviderResult, PriceInfo};
+
+pub type LocalL1GasPriceProviderClientImpl =
+    LocalComponentClient<L1GasPriceProviderRequest, L1GasPriceProviderResponse>;
+pub type RemoteL1GasPriceProviderClientImpl =
+    RemoteComponentClient<L1GasPriceProviderRequest, L1GasPriceProviderResponse>;
+pub type L1GasPriceProviderClientResult<T> = Result<T, L1GasPriceProviderClientError>;
+pub type L1GasPriceProviderRequestAndResponseSender =
+    ComponentRequestAndResponseSender<L1GasPriceProviderRequest, L1GasPriceProviderResponse>;
+pub type SharedL1GasPriceProviderClient = Arc<dyn L1GasPriceProviderClient>;
+
+/// Serves as the L1 gas price provider's shared interface. Requires `Send + Sync` to allow
+/// transferring and sharing resources (inputs, futures) across threads.
+#[automock]
+#[async_trait]
+pub trait L1GasPriceProviderClient: Send + Sync {
+    /// Adds the gas prices of a base layer block. Blocks are added in order, without gaps.
+    async fn add_price_sample(
+        &self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderClientResult<()>;
+    /// Returns the mean gas prices of the latest base layer blocks up to the given timestamp.
+    /// Called by the batcher when it seals a block with the given timestamp.
+    async fn get_price_info(
+        &self,
+        timestamp: BlockTimestamp,
+    ) -> L1GasPriceProviderClientResult<PriceInfo>;
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
+pub enum L1GasPriceProviderRequest {
+    AddPriceSample(u64, PriceSample),
+    GetPriceInfo(BlockTimestamp),
+    IsAlive,
+    IsReady,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum L1GasPriceProviderResponse {
+    AddPriceSample(L1GasPriceProviderResult<()>),
+    GetPriceInfo(L1GasPriceProviderResult<PriceInfo>),
+    IsAlive(bool),
+    IsReady(bool),
+}
+
+impl HealthCheckRequest for L1GasPriceProviderRequest {
+    fn is_alive() -> Self {
+        L1GasPriceProviderRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        L1GasPriceProviderRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for L1GasPriceProviderResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            L1GasPriceProviderResponse::IsAlive(health)
+            | L1GasPriceProviderResponse::IsReady(health) => Some(*health),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum L1GasPriceProviderClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    L1GasPriceProviderError(#[from] L1GasPriceProviderError),
+}
+
+impl CodedError for L1GasPriceProviderClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            L1GasPriceProviderClientError::ClientError(err) => err.error_code(),
+            L1GasPriceProviderClientError::L1GasPriceProviderError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            L1GasPriceProviderClientError::ClientError(err) => err.severity(),
+            L1GasPriceProviderClientError::L1GasPriceProviderError(err) => err.severity(),
+        }
+    }
+}
+
+#[async_trait]
+impl L1GasPriceProviderClient for LocalL1GasPriceProviderClientImpl {
+    async fn add_price_sample(
+        &self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderClientResult<()> {
+        let request = L1GasPriceProviderRequest::AddPriceSample(height, sample);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1GasPriceProviderResponse,
+            AddPriceSample,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+
+    async fn get_price_info(
+        &self,
+        timestamp: BlockTimestamp,
+    ) -> L1GasPriceProviderClientResult<PriceInfo> {
+        let request = L1GasPriceProviderRequest::GetPriceInfo(timestamp);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1GasPriceProviderResponse,
+            GetPriceInfo,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+}
+
+#[async_trait]
+impl L1GasPriceProviderClient for RemoteL1GasPriceProviderClientImpl {
+    async fn add_price_sample(
+        &self,
+        height: u64,
+        sample: PriceSample,
+    ) -> L1GasPriceProviderClientResult<()> {
+        let request = L1GasPriceProviderRequest::AddPriceSample(height, sample);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1GasPriceProviderResponse,
+            AddPriceSample,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+
+    async fn get_price_info(
+        &self,
+        timestamp: BlockTimestamp,
+    ) -> L1GasPriceProviderClientResult<PriceInfo> {
+        let request = L1GasPriceProviderRequest::GetPriceInfo(timestamp);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1GasPriceProviderResponse,
+            GetPriceInfo,
+            L1GasPriceProviderClientError,
+            L1GasPriceProviderError
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1GasPriceProviderError {
+    #[error("Expected the price sample of L1 block {expected}, got the one of L1 block {found}.")]
+    UnexpectedHeight { expected: u64, found: u64 },
+    #[error(
+        "Only {n_samples} price samples precede timestamp {}, {n_required} are required.",
+        timestamp.0
+    )]
+    NotEnoughSamples { timestamp: BlockTimestamp, n_samples: usize, n_required: usize },
+}
+
+impl CodedError for L1GasPriceProviderError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            L1GasPriceProviderError::UnexpectedHeight { .. } => {
+                codes::GAS_PRICE_PROVIDER_UNEXPECTED_HEIGHT
+            }
+            L1GasPriceProviderError::NotEnoughSamples { .. } => {
+                codes::GAS_PRICE_PROVIDER_NOT_ENOUGH_SAMPLES
+            }
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            L1GasPriceProviderError::UnexpectedHeight { .. }
+            | L1GasPriceProviderError::NotEnoughSamples { .. } => Severity::Warning,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::L1GasPriceProviderError;
+
+pub type L1GasPriceProviderResult<T> = Result<T, L1GasPriceProviderError>;
+
+/// The L1 gas prices blocks are built with, averaged over recent base layer blocks, in wei.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceInfo {
+    pub base_fee_per_gas: u128,
+    pub blob_fee: u128,
+}
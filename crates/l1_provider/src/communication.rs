@@ -0,0 +1,62 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use starknet_l1_provider_types::communication::{
+    L1ProviderRequest,
+    L1ProviderRequestAndResponseSender,
+    L1ProviderResponse,
+};
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
+use tokio::sync::mpsc::Receiver;
+
+use crate::l1_provider::L1Provider;
+
+pub type LocalL1ProviderServer =
+    LocalComponentServer<L1Provider, L1ProviderRequest, L1ProviderResponse>;
+pub type RemoteL1ProviderServer =
+    RemoteComponentServer<L1Provider, L1ProviderRequest, L1ProviderResponse>;
+
+pub fn create_local_l1_provider_server(
+    l1_provider: L1Provider,
+    rx_l1_provider: Receiver<L1ProviderRequestAndResponseSender>,
+) -> LocalL1ProviderServer {
+    LocalComponentServer::new(l1_provider, rx_l1_provider)
+}
+
+pub fn create_remote_l1_provider_server(
+    l1_provider: L1Provider,
+    ip_address: IpAddr,
+    port: u16,
+    serialization_format: SerializationFormat,
+) -> RemoteL1ProviderServer {
+    RemoteComponentServer::new(l1_provider, ip_address, port)
+        .with_serialization_format(serialization_format)
+}
+
+#[async_trait]
+impl ComponentRequestHandler<L1ProviderRequest, L1ProviderResponse> for L1Provider {
+    async fn handle_request(&mut self, request: L1ProviderRequest) -> L1ProviderResponse {
+        match request {
+            L1ProviderRequest::GetTransactions(n_txs) => {
+                L1ProviderResponse::GetTransactions(self.get_txs(n_txs))
+            }
+            L1ProviderRequest::AddTransaction(tx) => {
+                L1ProviderResponse::AddTransaction(self.add_tx(tx))
+            }
+            L1ProviderRequest::CommitBlock(committed_txs) => {
+                L1ProviderResponse::CommitBlock(self.commit_block(&committed_txs))
+            }
+            L1ProviderRequest::IsAlive => L1ProviderResponse::IsAlive(self.is_alive().await),
+            L1ProviderRequest::IsReady => L1ProviderResponse::IsReady(self.is_ready().await),
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for L1Provider {}
+
+#[async_trait]
+impl ComponentHealth for L1Provider {}
@@ -0,0 +1,56 @@
+use indexmap::IndexMap;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_l1_provider_types::errors::L1ProviderError;
+use starknet_l1_provider_types::l1_provider_types::L1ProviderResult;
+use tracing::debug;
+
+#[cfg(test)]
+#[path = "l1_provider_test.rs"]
+mod l1_provider_test;
+
+/// Holds the L1 handler transactions of the messages sent from L1, until they are included in a
+/// committed block.
+// TODO: Scrape the messages from the L1 contract instead of having them added.
+#[derive(Debug, Default)]
+pub struct L1Provider {
+    // Pending transactions, in the order they were added.
+    pending_txs: IndexMap<TransactionHash, L1HandlerTransaction>,
+}
+
+impl L1Provider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns up to `n_txs` pending transactions, oldest first, without removing them.
+    pub fn get_txs(&self, n_txs: usize) -> L1ProviderResult<Vec<L1HandlerTransaction>> {
+        Ok(self.pending_txs.values().take(n_txs).cloned().collect())
+    }
+
+    pub fn add_tx(&mut self, tx: L1HandlerTransaction) -> L1ProviderResult<()> {
+        if self.pending_txs.contains_key(&tx.tx_hash) {
+            return Err(L1ProviderError::DuplicateTransaction { tx_hash: tx.tx_hash });
+        }
+        self.pending_txs.insert(tx.tx_hash, tx);
+        Ok(())
+    }
+
+    pub fn commit_block(&mut self, committed_txs: &[TransactionHash]) -> L1ProviderResult<()> {
+        let n_pending_txs = self.pending_txs.len();
+        for tx_hash in committed_txs {
+            // Keeps the order of the remaining transactions.
+            self.pending_txs.shift_remove(tx_hash);
+        }
+        debug!(
+            "Committed {} L1 handler transactions, {} are pending.",
+            n_pending_txs - self.pending_txs.len(),
+            self.pending_txs.len()
+        );
+        Ok(())
+    }
+
+    pub fn n_txs(&self) -> usize {
+        self.pending_txs.len()
+    }
+}
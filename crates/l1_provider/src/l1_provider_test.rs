@@ -0,0 +1,55 @@
+use assert_matches::assert_matches;
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::felt;
+use starknet_api::transaction::{Fee, TransactionHash};
+use starknet_l1_provider_types::errors::L1ProviderError;
+
+use crate::l1_provider::L1Provider;
+
+fn l1_handler_tx(tx_hash: u8) -> L1HandlerTransaction {
+    L1HandlerTransaction {
+        tx: Default::default(),
+        tx_hash: TransactionHash(felt!(tx_hash)),
+        paid_fee_on_l1: Fee(1),
+    }
+}
+
+fn l1_provider_with_txs(tx_hashes: &[u8]) -> L1Provider {
+    let mut l1_provider = L1Provider::new();
+    for &tx_hash in tx_hashes {
+        l1_provider.add_tx(l1_handler_tx(tx_hash)).unwrap();
+    }
+    l1_provider
+}
+
+#[test]
+fn get_txs_returns_the_oldest_txs_and_keeps_them_pending() {
+    let l1_provider = l1_provider_with_txs(&[3, 1, 2]);
+
+    assert_eq!(l1_provider.get_txs(2).unwrap(), vec![l1_handler_tx(3), l1_handler_tx(1)]);
+    // Every proposal of a height is offered the same transactions.
+    assert_eq!(l1_provider.get_txs(2).unwrap(), vec![l1_handler_tx(3), l1_handler_tx(1)]);
+    assert_eq!(l1_provider.n_txs(), 3);
+}
+
+#[test]
+fn add_tx_rejects_a_pending_tx() {
+    let mut l1_provider = l1_provider_with_txs(&[1]);
+
+    assert_matches!(
+        l1_provider.add_tx(l1_handler_tx(1)),
+        Err(L1ProviderError::DuplicateTransaction { tx_hash }) if tx_hash == TransactionHash(felt!(1_u8))
+    );
+    assert_eq!(l1_provider.n_txs(), 1);
+}
+
+#[test]
+fn commit_block_drops_the_committed_txs() {
+    let mut l1_provider = l1_provider_with_txs(&[1, 2, 3]);
+
+    // Hashes of account transactions in the block are ignored.
+    let committed_txs = [TransactionHash(felt!(2_u8)), TransactionHash(felt!(4_u8))];
+    l1_provider.commit_block(&committed_txs).unwrap();
+
+    assert_eq!(l1_provider.get_txs(3).unwrap(), vec![l1_handler_tx(1), l1_handler_tx(3)]);
+}
@@ -0,0 +1,2 @@
+pub mod communication;
+pub mod l1_provider;
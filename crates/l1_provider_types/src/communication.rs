@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mockall::predicate::*;
+use mockall::*;
+use papyrus_proc_macros::handle_response_variants;
+use serde::{Deserialize, Serialize};
+use starknet_api::executable_transaction::L1HandlerTransaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use crate::errors::L1ProviderError;
+use crate::l1_provider_types::L1ProviderResult;
+
+pub type LocalL1ProviderClientImpl = LocalComponentClient<L1ProviderRequest, L1ProviderResponse>;
+pub type RemoteL1ProviderClientImpl = RemoteComponentClient<L1ProviderRequest, L1ProviderResponse>;
+pub type L1ProviderClientResult<T> = Result<T, L1ProviderClientError>;
+pub type L1ProviderRequestAndResponseSender =
+    ComponentRequestAndResponseSender<L1ProviderRequest, L1ProviderResponse>;
+pub type SharedL1ProviderClient = Arc<dyn L1ProviderClient>;
+
+/// Serves as the L1 provider's shared interface. Requires `Send + Sync` to allow transferring and
+/// sharing resources (inputs, futures) across threads.
+#[automock]
+#[async_trait]
+pub trait L1ProviderClient: Send + Sync {
+    /// Returns up to `n_txs` pending L1 handler transactions, in the order their messages were
+    /// sent on L1. The transactions stay pending until a block including them is committed, so
+    /// that every proposal of a height is offered the same transactions.
+    async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>>;
+    /// Adds the L1 handler transaction of a message sent on L1.
+    async fn add_tx(&self, tx: L1HandlerTransaction) -> L1ProviderClientResult<()>;
+    /// Drops the pending transactions included in a committed block. Hashes of other
+    /// transactions are ignored.
+    async fn commit_block(&self, committed_txs: Vec<TransactionHash>)
+        -> L1ProviderClientResult<()>;
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
+pub enum L1ProviderRequest {
+    GetTransactions(usize),
+    AddTransaction(L1HandlerTransaction),
+    CommitBlock(Vec<TransactionHash>),
+    IsAlive,
+    IsReady,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum L1ProviderResponse {
+    GetTransactions(L1ProviderResult<Vec<L1HandlerTransaction>>),
+    AddTransaction(L1ProviderResult<()>),
+    CommitBlock(L1ProviderResult<()>),
+    IsAlive(bool),
+    IsReady(bool),
+}
+
+impl HealthCheckRequest for L1ProviderRequest {
+    fn is_alive() -> Self {
+        L1ProviderRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        L1ProviderRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for L1ProviderResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            L1ProviderResponse::IsAlive(health) | L1ProviderResponse::IsReady(health) => {
+                Some(*health)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum L1ProviderClientError {
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    #[error(transparent)]
+    L1ProviderError(#[from] L1ProviderError),
+}
+
+impl CodedError for L1ProviderClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            L1ProviderClientError::ClientError(err) => err.error_code(),
+            L1ProviderClientError::L1ProviderError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            L1ProviderClientError::ClientError(err) => err.severity(),
+            L1ProviderClientError::L1ProviderError(err) => err.severity(),
+        }
+    }
+}
+
+#[async_trait]
+impl L1ProviderClient for LocalL1ProviderClientImpl {
+    async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>> {
+        let request = L1ProviderRequest::GetTransactions(n_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            GetTransactions,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn add_tx(&self, tx: L1HandlerTransaction) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::AddTransaction(tx);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            AddTransaction,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn commit_block(
+        &self,
+        committed_txs: Vec<TransactionHash>,
+    ) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::CommitBlock(committed_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            CommitBlock,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+}
+
+#[async_trait]
+impl L1ProviderClient for RemoteL1ProviderClientImpl {
+    async fn get_txs(&self, n_txs: usize) -> L1ProviderClientResult<Vec<L1HandlerTransaction>> {
+        let request = L1ProviderRequest::GetTransactions(n_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            GetTransactions,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn add_tx(&self, tx: L1HandlerTransaction) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::AddTransaction(tx);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            AddTransaction,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+
+    async fn commit_block(
+        &self,
+        committed_txs: Vec<TransactionHash>,
+    ) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::CommitBlock(committed_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            L1ProviderResponse,
+            CommitBlock,
+            L1ProviderClientError,
+            L1ProviderError
+        )
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionHash;
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1ProviderError {
+    #[error("Transaction with hash {tx_hash} is already pending.")]
+    DuplicateTransaction { tx_hash: TransactionHash },
+}
+
+impl CodedError for L1ProviderError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            L1ProviderError::DuplicateTransaction { .. } => {
+                codes::L1_PROVIDER_DUPLICATE_TRANSACTION
+            }
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            L1ProviderError::DuplicateTransaction { .. } => Severity::Info,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+use crate::errors::L1ProviderError;
+
+pub type L1ProviderResult<T> = Result<T, L1ProviderError>;
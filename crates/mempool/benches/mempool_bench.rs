@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used)]
+
+// Benchmarks the throughput of concurrent `add_tx` calls by the number of mempool shards. With
+// senders spread evenly across shards, the throughput is expected to scale near-linearly with the
+// number of shards, up to the number of adding threads.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use mempool_test_utils::starknet_api_test_utils::{
+    create_executable_tx,
+    test_resource_bounds_mapping,
+};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::transaction::{Tip, TransactionHash, ValidResourceBounds};
+use starknet_api::{contract_address, felt, patricia_key};
+use starknet_mempool::config::MempoolConfig;
+use starknet_mempool::sharded_mempool::ShardedMempool;
+use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput};
+
+const N_THREADS: u64 = 8;
+const N_ACCOUNTS_PER_THREAD: u64 = 64;
+const N_TXS_PER_ACCOUNT: u64 = 32;
+
+// The inputs added by each thread; threads add transactions of distinct accounts.
+fn thread_inputs() -> Vec<Vec<MempoolInput>> {
+    (0..N_THREADS)
+        .map(|thread| {
+            (0..N_ACCOUNTS_PER_THREAD)
+                .flat_map(|account| {
+                    let account = thread * N_ACCOUNTS_PER_THREAD + account;
+                    let sender_address = contract_address!(account);
+                    (0..N_TXS_PER_ACCOUNT).map(move |nonce| MempoolInput {
+                        tx: create_executable_tx(
+                            sender_address,
+                            TransactionHash(felt!(account * N_TXS_PER_ACCOUNT + nonce)),
+                            Tip(nonce),
+                            Nonce(felt!(nonce)),
+                            ValidResourceBounds::AllResources(test_resource_bounds_mapping()),
+                        ),
+                        account: Account { sender_address, state: AccountState::default() },
+                        eligible_at: None,
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn concurrent_add_tx_benchmark(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("concurrent_add_tx");
+    group.throughput(Throughput::Elements(N_THREADS * N_ACCOUNTS_PER_THREAD * N_TXS_PER_ACCOUNT));
+    for n_shards in [1, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(n_shards),
+            &n_shards,
+            |bencher, &n_shards| {
+                bencher.iter_batched(
+                    || {
+                        let config = MempoolConfig { n_shards, ..Default::default() };
+                        (ShardedMempool::new(config, None, None), thread_inputs())
+                    },
+                    |(mempool, thread_inputs)| {
+                        std::thread::scope(|scope| {
+                            for inputs in thread_inputs {
+                                let mempool = &mempool;
+                                scope.spawn(move || {
+                                    for input in inputs {
+                                        mempool.add_tx(input).unwrap();
+                                    }
+                                });
+                            }
+                        });
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, concurrent_add_tx_benchmark);
+criterion_main!(benches);
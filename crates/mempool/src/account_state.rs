@@ -0,0 +1,83 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use starknet_api::core::{ContractAddress, Nonce};
+
+pub const DEFAULT_MAX_TRACKED_ACCOUNTS: usize = 100_000;
+
+/// Reads the nonces of accounts from the committed state, e.g., from storage. Consulted for the
+/// accounts the mempool has not seen in a committed block.
+// TODO(Mohammad): implement over the state sync client once it exists.
+pub trait AccountNonceReader: Debug + Send + Sync {
+    /// The nonce of the next transaction of the account, as of the latest committed block.
+    fn get_nonce(&self, address: ContractAddress) -> Option<Nonce>;
+}
+
+/// The committed state of accounts, as notified by the committed blocks. Transactions with nonces
+/// below the committed nonce of their account can never be sequenced.
+///
+/// Holds the accounts updated by the most recent blocks, up to a capacity; the accounts not
+/// updated for the longest are forgotten first, and are then looked up by the reader, if any.
+#[derive(Debug)]
+pub(crate) struct AccountStateTracker {
+    max_tracked_accounts: usize,
+    account_nonce_reader: Option<Arc<dyn AccountNonceReader>>,
+    // The nonce of the next transaction of each tracked account, and the commit that set it.
+    next_nonces: HashMap<ContractAddress, (Nonce, u64)>,
+    // The tracked accounts, ordered by the commit that last updated them.
+    accounts_by_commit: BTreeSet<(u64, ContractAddress)>,
+    n_commits: u64,
+}
+
+impl AccountStateTracker {
+    pub(crate) fn new(max_tracked_accounts: usize) -> Self {
+        Self {
+            max_tracked_accounts,
+            account_nonce_reader: None,
+            next_nonces: HashMap::new(),
+            accounts_by_commit: BTreeSet::new(),
+            n_commits: 0,
+        }
+    }
+
+    pub(crate) fn set_account_nonce_reader(&mut self, reader: Arc<dyn AccountNonceReader>) {
+        self.account_nonce_reader = Some(reader);
+    }
+
+    /// The nonce of the next transaction of the account, as of the latest committed block, if
+    /// known.
+    pub(crate) fn next_nonce(&self, address: ContractAddress) -> Option<Nonce> {
+        match self.next_nonces.get(&address) {
+            Some(&(next_nonce, _)) => Some(next_nonce),
+            None => self.account_nonce_reader.as_ref()?.get_nonce(address),
+        }
+    }
+
+    /// Records the next nonces of the accounts updated by a committed block.
+    pub(crate) fn commit(
+        &mut self,
+        next_nonces: impl IntoIterator<Item = (ContractAddress, Nonce)>,
+    ) {
+        let commit = self.n_commits;
+        self.n_commits += 1;
+        for (address, next_nonce) in next_nonces {
+            if let Some((_, last_commit)) = self.next_nonces.insert(address, (next_nonce, commit)) {
+                self.accounts_by_commit.remove(&(last_commit, address));
+            }
+            self.accounts_by_commit.insert((commit, address));
+        }
+
+        while self.next_nonces.len() > self.max_tracked_accounts {
+            let (_, address) =
+                self.accounts_by_commit.pop_first().expect("Tracked accounts should be ordered.");
+            self.next_nonces.remove(&address);
+        }
+    }
+}
+
+impl Default for AccountStateTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRACKED_ACCOUNTS)
+    }
+}
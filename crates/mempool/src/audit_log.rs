@@ -0,0 +1,133 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::{Tip, TransactionHash};
+use tracing::error;
+
+use crate::config::AuditLogConfig;
+
+#[cfg(test)]
+#[path = "audit_log_test.rs"]
+mod audit_log_test;
+
+/// An event of a transaction in the mempool, as recorded in the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditEvent {
+    /// The transaction was added to the mempool.
+    Added { tx_hash: TransactionHash, sender_address: ContractAddress, nonce: Nonce, tip: Tip },
+    /// The mempool refused to add the transaction.
+    Rejected {
+        tx_hash: TransactionHash,
+        sender_address: ContractAddress,
+        nonce: Nonce,
+        reason: String,
+    },
+    /// The transaction was dropped from the mempool before it was sequenced.
+    Evicted { tx_hash: TransactionHash, reason: String },
+    /// The transaction was taken from the mempool for the block in creation.
+    Selected { tx_hash: TransactionHash },
+}
+
+/// A line of the audit log: an event, and the time it occurred at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp_micros: u128,
+    pub event: AuditEvent,
+}
+
+#[derive(Debug)]
+struct AuditLogFile {
+    writer: BufWriter<File>,
+    n_bytes: u64,
+}
+
+/// An append-only log of the additions, evictions and selections of the mempool transactions,
+/// stored as JSON lines, for the analysis of censorship or ordering complaints. Once the log file
+/// exceeds its maximal size, it is rotated: renamed with the suffix `.1`, the former `.1` file to
+/// `.2`, and so on, keeping the configured number of rotated files.
+#[derive(Debug)]
+pub struct MempoolAuditLog {
+    config: AuditLogConfig,
+    file: Mutex<AuditLogFile>,
+}
+
+impl MempoolAuditLog {
+    pub fn open(config: &AuditLogConfig) -> std::io::Result<Self> {
+        let file = open_log_file(&config.path)?;
+        Ok(Self { config: config.clone(), file: Mutex::new(file) })
+    }
+
+    /// Appends the events, with the current time. The mempool is not failed by the audit log:
+    /// events that can't be written are logged and skipped.
+    pub fn record(&self, events: impl IntoIterator<Item = AuditEvent>) {
+        let timestamp_micros = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The system time should be after the UNIX epoch.")
+            .as_micros();
+        let mut file = self.file.lock().expect("Audit log lock should not be poisoned.");
+        for event in events {
+            let record = AuditRecord { timestamp_micros, event };
+            if let Err(err) = self.append(&mut file, &record) {
+                error!("Failed to write {:?} to the mempool audit log: {}", record, err);
+            }
+        }
+        if let Err(err) = file.writer.flush() {
+            error!("Failed to flush the mempool audit log: {}", err);
+        }
+    }
+
+    fn append(&self, file: &mut AuditLogFile, record: &AuditRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let line_size = u64::try_from(line.len()).expect("The line size should fit u64.");
+        if file.n_bytes > 0 && file.n_bytes + line_size > self.config.max_file_size {
+            file.writer.flush()?;
+            self.rotate()?;
+            *file = open_log_file(&self.config.path)?;
+        }
+        file.writer.write_all(&line)?;
+        file.n_bytes += line_size;
+        Ok(())
+    }
+
+    // Shifts the rotated files by one, dropping the oldest, and renames the log file to the first
+    // rotated file.
+    fn rotate(&self) -> std::io::Result<()> {
+        let path = &self.config.path;
+        if self.config.max_rotated_files == 0 {
+            return fs::remove_file(path);
+        }
+        for index in (1..self.config.max_rotated_files).rev() {
+            let rotated_path = rotated_file_path(path, index);
+            if rotated_path.exists() {
+                fs::rename(rotated_path, rotated_file_path(path, index + 1))?;
+            }
+        }
+        fs::rename(path, rotated_file_path(path, 1))
+    }
+}
+
+fn open_log_file(path: &Path) -> std::io::Result<AuditLogFile> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let n_bytes = file.metadata()?.len();
+    Ok(AuditLogFile { writer: BufWriter::new(file), n_bytes })
+}
+
+/// The path of the rotated audit log file of the given index; 1 is the most recent.
+pub fn rotated_file_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated_path = path.as_os_str().to_owned();
+    rotated_path.push(format!(".{index}"));
+    PathBuf::from(rotated_path)
+}
+
+pub fn read_audit_log(path: &Path) -> std::io::Result<Vec<AuditRecord>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .map(|line| -> std::io::Result<AuditRecord> { Ok(serde_json::from_str(&line?)?) })
+        .collect()
+}
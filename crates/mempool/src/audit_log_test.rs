@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{contract_address, felt, patricia_key};
+
+use crate::audit_log::{read_audit_log, rotated_file_path, AuditEvent, MempoolAuditLog};
+use crate::config::AuditLogConfig;
+
+fn selected(tx_hash: u8) -> AuditEvent {
+    AuditEvent::Selected { tx_hash: TransactionHash(felt!(tx_hash)) }
+}
+
+#[test]
+fn recorded_events_are_read_back_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = AuditLogConfig { path: dir.path().join("audit_log.jsonl"), ..Default::default() };
+    let events = vec![
+        AuditEvent::Added {
+            tx_hash: TransactionHash(felt!(1_u8)),
+            sender_address: contract_address!("0x1"),
+            nonce: Nonce(felt!(0_u8)),
+            tip: Default::default(),
+        },
+        AuditEvent::Evicted { tx_hash: TransactionHash(felt!(1_u8)), reason: "expired".into() },
+    ];
+
+    MempoolAuditLog::open(&config).unwrap().record(events.clone());
+    // Reopening the log appends to it.
+    MempoolAuditLog::open(&config).unwrap().record([selected(2)]);
+
+    let records = read_audit_log(&config.path).unwrap();
+    let recorded_events: Vec<_> = records.iter().map(|record| record.event.clone()).collect();
+    assert_eq!(recorded_events, [events, vec![selected(2)]].concat());
+    assert!(records.windows(2).all(|pair| pair[0].timestamp_micros <= pair[1].timestamp_micros));
+}
+
+#[test]
+fn log_is_rotated_when_full() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit_log.jsonl");
+    // Room for a single record per file.
+    let config = AuditLogConfig { path: path.clone(), max_file_size: 1, max_rotated_files: 2 };
+    let audit_log = MempoolAuditLog::open(&config).unwrap();
+
+    audit_log.record((1..=4).map(selected));
+
+    let events = |path: PathBuf| {
+        read_audit_log(&path).unwrap().into_iter().map(|record| record.event).collect::<Vec<_>>()
+    };
+    assert_eq!(events(path.clone()), [selected(4)]);
+    assert_eq!(events(rotated_file_path(&path, 1)), [selected(3)]);
+    assert_eq!(events(rotated_file_path(&path, 2)), [selected(2)]);
+    // The oldest rotated file is dropped.
+    assert!(!rotated_file_path(&path, 3).exists());
+}
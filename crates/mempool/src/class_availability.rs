@@ -0,0 +1,198 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use starknet_api::core::{ClassHash, ContractAddress};
+use starknet_api::executable_transaction::Transaction;
+use starknet_class_manager_types::communication::SharedClassManagerClient;
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(test)]
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+#[cfg(test)]
+#[path = "class_availability_test.rs"]
+mod class_availability_test;
+
+/// The maximal number of class lookups waiting for the class manager; classes missed while the
+/// lookups are full are looked up on their next check.
+pub const MAX_PENDING_CLASS_LOOKUPS: usize = 1000;
+
+/// Answers whether the class a transaction depends on is available for execution, e.g., whether
+/// the class of the account an invoke is sent from was already declared.
+pub trait ClassAvailabilityChecker: Debug + Send + Sync {
+    fn is_target_class_available(&self, tx: &Transaction) -> bool;
+
+    /// Starts the background work of the checker, if any. Called once, when the mempool starts.
+    fn start(&self) {}
+}
+
+/// Checks the availability of classes against the class manager.
+///
+/// The mempool checks transactions synchronously, so the checker answers from the classes it
+/// already found in the class manager, and looks the missing ones up in the background; the
+/// mempool checks the transactions it held back meanwhile again once it is asked for transactions.
+/// Deploy account and declare transactions target the class they deploy or declare. The mempool
+/// doesn't know the classes of deployed contracts, so the classes an invoke calls aren't checked;
+/// an invoke is only held back while the class of its sender isn't available, for a sender
+/// deployed by a deploy account transaction the checker saw. The classes of the other senders are
+/// part of the committed state.
+#[derive(Debug)]
+pub struct ClassManagerAvailabilityChecker {
+    classes: Arc<Mutex<ClassLookupState>>,
+    lookup_sender: Sender<ClassHash>,
+    // Taken once the checker is started.
+    lookup_task: Mutex<Option<ClassLookupTask>>,
+    // The number of lookups the class manager answered so far.
+    #[cfg(test)]
+    n_completed_lookups: watch::Receiver<usize>,
+}
+
+#[derive(Debug, Default)]
+struct ClassLookupState {
+    // Classes are never removed from the class manager, so a class found once stays available.
+    available_classes: HashSet<ClassHash>,
+    // Classes sent to the class manager and not answered yet, which are not looked up again
+    // meanwhile.
+    pending_lookups: HashSet<ClassHash>,
+    // The classes of the accounts of deploy account transactions whose class wasn't found yet.
+    deployed_account_classes: HashMap<ContractAddress, ClassHash>,
+}
+
+impl ClassManagerAvailabilityChecker {
+    /// Creates the checker, which looks the missing classes up once it is started.
+    pub fn new(class_manager_client: SharedClassManagerClient) -> Self {
+        let classes = Arc::new(Mutex::new(ClassLookupState::default()));
+        let (lookup_sender, lookup_receiver) = channel(MAX_PENDING_CLASS_LOOKUPS);
+        #[cfg(test)]
+        let (n_completed_lookups_sender, n_completed_lookups) = watch::channel(0);
+        let lookup_task = ClassLookupTask {
+            class_manager_client,
+            lookup_receiver,
+            classes: classes.clone(),
+            #[cfg(test)]
+            n_completed_lookups: n_completed_lookups_sender,
+        };
+        Self {
+            classes,
+            lookup_sender,
+            lookup_task: Mutex::new(Some(lookup_task)),
+            #[cfg(test)]
+            n_completed_lookups,
+        }
+    }
+
+    // Waits until the class manager answered the given number of lookups.
+    #[cfg(test)]
+    async fn wait_for_lookups(&mut self, n_lookups: usize) {
+        self.n_completed_lookups
+            .wait_for(|&n_completed_lookups| n_completed_lookups >= n_lookups)
+            .await
+            .expect("The lookup task should be running.");
+    }
+
+    fn is_class_available(&self, classes: &mut ClassLookupState, class_hash: ClassHash) -> bool {
+        if classes.available_classes.contains(&class_hash) {
+            return true;
+        }
+        if classes.pending_lookups.contains(&class_hash) {
+            return false;
+        }
+        match self.lookup_sender.try_send(class_hash) {
+            Ok(()) => {
+                classes.pending_lookups.insert(class_hash);
+            }
+            Err(_) => {
+                debug!(
+                    "Class lookups are full; class {} is looked up on its next check.",
+                    class_hash
+                );
+            }
+        }
+        false
+    }
+}
+
+impl ClassAvailabilityChecker for ClassManagerAvailabilityChecker {
+    /// Spawns the task looking the missing classes up.
+    fn start(&self) {
+        let lookup_task = self.lookup_task.lock().expect("Failed to lock the lookup task.").take();
+        match lookup_task {
+            Some(lookup_task) => {
+                tokio::spawn(lookup_task.run());
+            }
+            None => warn!("The class availability checker is already started."),
+        }
+    }
+
+    fn is_target_class_available(&self, tx: &Transaction) -> bool {
+        let mut classes = self.classes.lock().expect("Failed to lock the class lookup state.");
+        match tx {
+            Transaction::Declare(tx) => self.is_class_available(&mut classes, tx.tx.class_hash()),
+            Transaction::DeployAccount(tx) => {
+                let class_hash = tx.class_hash();
+                let is_available = self.is_class_available(&mut classes, class_hash);
+                if is_available {
+                    classes.deployed_account_classes.remove(&tx.contract_address());
+                } else {
+                    classes.deployed_account_classes.insert(tx.contract_address(), class_hash);
+                }
+                is_available
+            }
+            // Only the class of the sender is checked, see the checker docs.
+            Transaction::Invoke(tx) => {
+                let sender_address = tx.sender_address();
+                let Some(&class_hash) = classes.deployed_account_classes.get(&sender_address)
+                else {
+                    return true;
+                };
+                let is_available = self.is_class_available(&mut classes, class_hash);
+                if is_available {
+                    classes.deployed_account_classes.remove(&sender_address);
+                }
+                is_available
+            }
+            Transaction::L1Handler(_) => true,
+        }
+    }
+}
+
+// Looks the classes sent by the checker up in the class manager.
+struct ClassLookupTask {
+    class_manager_client: SharedClassManagerClient,
+    lookup_receiver: Receiver<ClassHash>,
+    classes: Arc<Mutex<ClassLookupState>>,
+    #[cfg(test)]
+    n_completed_lookups: watch::Sender<usize>,
+}
+
+// The class manager client isn't `Debug`.
+impl Debug for ClassLookupTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClassLookupTask").finish_non_exhaustive()
+    }
+}
+
+impl ClassLookupTask {
+    async fn run(mut self) {
+        while let Some(class_hash) = self.lookup_receiver.recv().await {
+            let is_available = match self.class_manager_client.get_sierra(class_hash).await {
+                Ok(class) => class.is_some(),
+                Err(err) => {
+                    warn!("Failed to look class {} up in the class manager: {}", class_hash, err);
+                    false
+                }
+            };
+            {
+                let mut classes =
+                    self.classes.lock().expect("Failed to lock the class lookup state.");
+                classes.pending_lookups.remove(&class_hash);
+                if is_available {
+                    classes.available_classes.insert(class_hash);
+                }
+            }
+            #[cfg(test)]
+            self.n_completed_lookups.send_modify(|n_completed_lookups| *n_completed_lookups += 1);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use starknet_api::core::{ClassHash, ContractAddress, Nonce, PatriciaKey};
+use starknet_api::executable_transaction::{InvokeTransaction, Transaction};
+use starknet_api::state::ContractClass as SierraContractClass;
+use starknet_api::test_utils::deploy_account::{executable_deploy_account_tx, DeployAccountTxArgs};
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{class_hash, contract_address, felt, patricia_key};
+use starknet_class_manager_types::communication::MockClassManagerClient;
+
+use crate::class_availability::{ClassAvailabilityChecker, ClassManagerAvailabilityChecker};
+
+fn declared_class_hash() -> ClassHash {
+    class_hash!(1_u8)
+}
+
+fn deploy_account(class_hash: ClassHash) -> Transaction {
+    let mut tx = executable_deploy_account_tx(
+        DeployAccountTxArgs { class_hash, ..Default::default() },
+        Nonce::default(),
+    );
+    tx.contract_address = deployed_account_address();
+    Transaction::DeployAccount(tx)
+}
+
+fn deployed_account_address() -> ContractAddress {
+    contract_address!("0x100")
+}
+
+fn invoke(sender_address: ContractAddress) -> Transaction {
+    Transaction::Invoke(InvokeTransaction {
+        tx: invoke_tx(InvokeTxArgs { sender_address, ..Default::default() }),
+        tx_hash: TransactionHash::default(),
+    })
+}
+
+// The class manager holds the declared class only; each class is expected to be looked up the
+// given number of times.
+fn unstarted_checker(n_lookups: usize) -> ClassManagerAvailabilityChecker {
+    let mut class_manager_client = MockClassManagerClient::new();
+    class_manager_client.expect_get_sierra().times(n_lookups).returning(|class_hash| {
+        Ok((class_hash == declared_class_hash()).then(SierraContractClass::default))
+    });
+    ClassManagerAvailabilityChecker::new(Arc::new(class_manager_client))
+}
+
+fn checker(n_lookups: usize) -> ClassManagerAvailabilityChecker {
+    let checker = unstarted_checker(n_lookups);
+    checker.start();
+    checker
+}
+
+#[tokio::test]
+async fn classes_are_looked_up_once_started() {
+    let mut checker = unstarted_checker(1);
+    let tx = deploy_account(declared_class_hash());
+
+    // The lookup waits for the checker to start.
+    assert!(!checker.is_target_class_available(&tx));
+    tokio::task::yield_now().await;
+    assert!(!checker.is_target_class_available(&tx));
+
+    checker.start();
+    checker.wait_for_lookups(1).await;
+    assert!(checker.is_target_class_available(&tx));
+}
+
+#[tokio::test]
+async fn declared_class_becomes_available_once_looked_up() {
+    let mut checker = checker(1);
+    let tx = deploy_account(declared_class_hash());
+
+    // The first check only triggers the lookup.
+    assert!(!checker.is_target_class_available(&tx));
+    checker.wait_for_lookups(1).await;
+
+    assert!(checker.is_target_class_available(&tx));
+}
+
+#[tokio::test]
+async fn undeclared_class_is_not_available() {
+    let mut checker = checker(2);
+    let tx = deploy_account(class_hash!(2_u8));
+
+    assert!(!checker.is_target_class_available(&tx));
+    checker.wait_for_lookups(1).await;
+
+    // The class is looked up again, as it may be declared meanwhile.
+    assert!(!checker.is_target_class_available(&tx));
+    checker.wait_for_lookups(2).await;
+}
+
+#[tokio::test]
+async fn pending_lookup_is_not_repeated() {
+    let mut checker = checker(1);
+    let tx = deploy_account(declared_class_hash());
+
+    assert!(!checker.is_target_class_available(&tx));
+    assert!(!checker.is_target_class_available(&tx));
+    checker.wait_for_lookups(1).await;
+
+    assert!(checker.is_target_class_available(&tx));
+}
+
+#[tokio::test]
+async fn invoke_of_existing_account_needs_no_class_lookup() {
+    let checker = checker(0);
+
+    assert!(checker.is_target_class_available(&invoke(contract_address!("0x200"))));
+}
+
+#[tokio::test]
+async fn invoke_of_deployed_account_targets_its_class() {
+    let mut checker = checker(1);
+
+    // The sender is deployed with a class the checker looks up.
+    assert!(!checker.is_target_class_available(&deploy_account(declared_class_hash())));
+    assert!(!checker.is_target_class_available(&invoke(deployed_account_address())));
+    checker.wait_for_lookups(1).await;
+
+    assert!(checker.is_target_class_available(&invoke(deployed_account_address())));
+}
+
+#[tokio::test]
+async fn invoke_of_account_deployed_with_undeclared_class_is_held_back() {
+    let mut checker = checker(2);
+
+    assert!(!checker.is_target_class_available(&deploy_account(class_hash!(2_u8))));
+    checker.wait_for_lookups(1).await;
+
+    assert!(!checker.is_target_class_available(&invoke(deployed_account_address())));
+    checker.wait_for_lookups(2).await;
+}
@@ -1,19 +1,43 @@
+use std::collections::HashMap;
 use std::net::IpAddr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use starknet_api::executable_transaction::Transaction;
-use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
-use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::communication::SharedGatewayClient;
+use starknet_gateway_types::gateway_types::{TransactionStatus, TransactionStatusUpdate};
+use starknet_mempool_infra::component_definitions::{ComponentHealth, ComponentRequestHandler};
+use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
 use starknet_mempool_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
 use starknet_mempool_types::communication::{
     MempoolRequest,
     MempoolRequestAndResponseSender,
     MempoolResponse,
 };
-use starknet_mempool_types::mempool_types::{MempoolInput, MempoolResult};
-use tokio::sync::mpsc::Receiver;
+use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::{
+    AccountState,
+    CommitBlockArgs,
+    DropAccountTxsArgs,
+    MempoolInput,
+    MempoolResult,
+    RejectedTransaction,
+    TxBudget,
+};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::mempool::MEMPOOL_SIZE;
+use crate::sharded_mempool::ShardedMempool;
 
-use crate::mempool::Mempool;
+#[cfg(test)]
+#[path = "communication_test.rs"]
+pub mod communication_test;
 
 pub type MempoolServer =
     LocalComponentServer<MempoolCommunicationWrapper, MempoolRequest, MempoolResponse>;
@@ -21,55 +45,320 @@ pub type MempoolServer =
 pub type RemoteMempoolServer =
     RemoteComponentServer<MempoolCommunicationWrapper, MempoolRequest, MempoolResponse>;
 
+/// Requests are handled concurrently up to the given bound, so that additions to different shards
+/// are written in parallel.
 pub fn create_mempool_server(
-    mempool: Mempool,
+    mempool: ShardedMempool,
     rx_mempool: Receiver<MempoolRequestAndResponseSender>,
+    tx_stream_sender: Option<Sender<Transaction>>,
+    gateway_client: Option<SharedGatewayClient>,
+    max_concurrent_requests: usize,
 ) -> MempoolServer {
-    let communication_wrapper = MempoolCommunicationWrapper::new(mempool);
+    let communication_wrapper =
+        MempoolCommunicationWrapper::new(mempool, tx_stream_sender, gateway_client);
     LocalComponentServer::new(communication_wrapper, rx_mempool)
+        .with_max_concurrent_requests(max_concurrent_requests)
 }
 
 pub fn create_remote_mempool_server(
-    mempool: Mempool,
+    mempool: ShardedMempool,
     ip_address: IpAddr,
     port: u16,
+    serialization_format: SerializationFormat,
+    gateway_client: Option<SharedGatewayClient>,
 ) -> RemoteMempoolServer {
-    let communication_wrapper = MempoolCommunicationWrapper::new(mempool);
+    // Transactions can only be streamed to components running in the same process.
+    let communication_wrapper = MempoolCommunicationWrapper::new(mempool, None, gateway_client);
     RemoteComponentServer::new(communication_wrapper, ip_address, port)
+        .with_serialization_format(serialization_format)
 }
 
-/// Wraps the mempool to enable inbound async communication from other components.
+/// Wraps the mempool to enable inbound async communication from other components. Clones share the
+/// mempool, so that requests are handled concurrently; the shards serialize the operations on
+/// their accounts.
+#[derive(Clone)]
 pub struct MempoolCommunicationWrapper {
-    mempool: Mempool,
+    // Shared with the sweep of the expired transactions.
+    mempool: Arc<ShardedMempool>,
+    // Eligible transactions are pushed to this sender while the transaction stream is open.
+    tx_stream_sender: Option<Sender<Transaction>>,
+    // The task pushing the eligible transactions, while the transaction stream is open.
+    tx_stream_pusher: Arc<Mutex<Option<JoinHandle<()>>>>,
+    // Wakes the pusher up once transactions may have become eligible.
+    tx_stream_wakeup: Arc<Notify>,
+    // If set, the rejected and committed transactions are reported to the gateway, which tracks
+    // the statuses of the transactions.
+    gateway_client: Option<SharedGatewayClient>,
 }
 
 impl MempoolCommunicationWrapper {
-    pub fn new(mempool: Mempool) -> Self {
-        MempoolCommunicationWrapper { mempool }
+    pub fn new(
+        mempool: ShardedMempool,
+        tx_stream_sender: Option<Sender<Transaction>>,
+        gateway_client: Option<SharedGatewayClient>,
+    ) -> Self {
+        MempoolCommunicationWrapper {
+            mempool: Arc::new(mempool),
+            tx_stream_sender,
+            tx_stream_pusher: Arc::new(Mutex::new(None)),
+            tx_stream_wakeup: Arc::new(Notify::new()),
+            gateway_client,
+        }
     }
 
     fn add_tx(&mut self, mempool_input: MempoolInput) -> MempoolResult<()> {
-        self.mempool.add_tx(mempool_input)
+        self.mempool.add_tx(mempool_input)?;
+        self.tx_stream_wakeup.notify_one();
+        Ok(())
     }
 
     fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<Transaction>> {
         self.mempool.get_txs(n_txs)
     }
+
+    fn get_txs_by_budget(&mut self, budget: TxBudget) -> MempoolResult<Vec<Transaction>> {
+        self.mempool.get_txs_by_budget(budget)
+    }
+
+    fn get_txs_within(
+        &mut self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolResult<Vec<Transaction>> {
+        self.mempool.get_txs_within(n_txs, max_wait)
+    }
+
+    fn open_tx_stream(&mut self) -> MempoolResult<()> {
+        let Some(sender) = self.tx_stream_sender.as_ref().filter(|sender| !sender.is_closed())
+        else {
+            return Err(MempoolError::TransactionStreamUnavailable);
+        };
+        let mut tx_stream_pusher =
+            self.tx_stream_pusher.lock().expect("Transaction stream lock should not be poisoned.");
+        if tx_stream_pusher.as_ref().is_some_and(|pusher| !pusher.is_finished()) {
+            return Ok(());
+        }
+        *tx_stream_pusher = Some(tokio::spawn(push_eligible_txs(
+            Arc::downgrade(&self.mempool),
+            sender.clone(),
+            self.tx_stream_wakeup.clone(),
+        )));
+        Ok(())
+    }
+
+    // Transactions already pushed to the stream are not returned to the mempool. Once closed, no
+    // more transactions are pushed.
+    async fn close_tx_stream(&mut self) -> MempoolResult<()> {
+        let pusher = self
+            .tx_stream_pusher
+            .lock()
+            .expect("Transaction stream lock should not be poisoned.")
+            .take();
+        if let Some(pusher) = pusher {
+            pusher.abort();
+            let _ = pusher.await;
+        }
+        Ok(())
+    }
+
+    fn evict_txs(&mut self, n_txs: usize) -> MempoolResult<usize> {
+        self.mempool.evict_txs(n_txs)
+    }
+
+    fn return_txs(&mut self, txs: Vec<Transaction>) -> MempoolResult<()> {
+        self.mempool.return_txs(txs)?;
+        self.tx_stream_wakeup.notify_one();
+        Ok(())
+    }
+
+    fn reject_txs(&mut self, rejected_txs: Vec<RejectedTransaction>) -> MempoolResult<()> {
+        let updates = rejected_txs
+            .iter()
+            .map(|tx| TransactionStatusUpdate {
+                tx_hash: tx.tx_hash,
+                status: TransactionStatus::Rejected,
+                sender_address: Some(tx.address),
+                reason: Some(tx.reason.to_string()),
+            })
+            .collect();
+        self.mempool.reject_txs(rejected_txs);
+        self.report_tx_statuses(updates);
+        Ok(())
+    }
+
+    // The committed transactions were either taken from the mempool, or are dropped with the
+    // transactions preceding the updated account nonces.
+    fn commit_block(&mut self, args: CommitBlockArgs) -> MempoolResult<()> {
+        let CommitBlockArgs { committed_txs, nonces } = args;
+        debug!("Committing a block with {} transactions.", committed_txs.len());
+        // The mempool is given the nonce of the last committed transaction of each account.
+        let state_changes = nonces
+            .into_iter()
+            .map(|(address, nonce)| {
+                let last_committed_nonce =
+                    nonce.try_decrement().map_err(|_| MempoolError::FeltOutOfRange)?;
+                Ok((address, AccountState { nonce: last_committed_nonce }))
+            })
+            .collect::<MempoolResult<HashMap<_, _>>>()?;
+        self.mempool.commit_block(state_changes)?;
+        let updates = committed_txs
+            .into_iter()
+            .map(|tx_hash| TransactionStatusUpdate {
+                tx_hash,
+                status: TransactionStatus::Included,
+                sender_address: None,
+                reason: None,
+            })
+            .collect();
+        self.report_tx_statuses(updates);
+        self.tx_stream_wakeup.notify_one();
+        Ok(())
+    }
+
+    fn drop_account_txs(
+        &mut self,
+        args: DropAccountTxsArgs,
+    ) -> MempoolResult<Vec<TransactionHash>> {
+        Ok(self.mempool.drop_account_txs(args))
+    }
+
+    fn flush(&mut self) -> MempoolResult<Vec<TransactionHash>> {
+        Ok(self.mempool.flush())
+    }
+
+    // The statuses are reported without waiting for the gateway, which may itself be waiting for
+    // the mempool to add a transaction.
+    fn report_tx_statuses(&self, updates: Vec<TransactionStatusUpdate>) {
+        let Some(gateway_client) = self.gateway_client.clone() else {
+            return;
+        };
+        if updates.is_empty() {
+            return;
+        }
+        tokio::spawn(async move {
+            if let Err(err) = gateway_client.update_transaction_statuses(updates).await {
+                warn!("Failed to report transaction statuses to the gateway: {}", err);
+            }
+        });
+    }
 }
 
 #[async_trait]
 impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommunicationWrapper {
     async fn handle_request(&mut self, request: MempoolRequest) -> MempoolResponse {
-        match request {
+        let response = match request {
             MempoolRequest::AddTransaction(mempool_input) => {
                 MempoolResponse::AddTransaction(self.add_tx(mempool_input))
             }
             MempoolRequest::GetTransactions(n_txs) => {
                 MempoolResponse::GetTransactions(self.get_txs(n_txs))
             }
-        }
+            MempoolRequest::GetTransactionsByBudget(budget) => {
+                MempoolResponse::GetTransactionsByBudget(self.get_txs_by_budget(budget))
+            }
+            MempoolRequest::GetTransactionsWithin(n_txs, max_wait) => {
+                MempoolResponse::GetTransactionsWithin(self.get_txs_within(n_txs, max_wait))
+            }
+            MempoolRequest::OpenTransactionStream => {
+                MempoolResponse::OpenTransactionStream(self.open_tx_stream())
+            }
+            MempoolRequest::CloseTransactionStream => {
+                MempoolResponse::CloseTransactionStream(self.close_tx_stream().await)
+            }
+            MempoolRequest::EvictTransactions(n_txs) => {
+                MempoolResponse::EvictTransactions(self.evict_txs(n_txs))
+            }
+            MempoolRequest::ReturnTransactions(txs) => {
+                MempoolResponse::ReturnTransactions(self.return_txs(txs))
+            }
+            MempoolRequest::RejectTransactions(txs) => {
+                MempoolResponse::RejectTransactions(self.reject_txs(txs))
+            }
+            MempoolRequest::CommitBlock(args) => {
+                MempoolResponse::CommitBlock(self.commit_block(args))
+            }
+            MempoolRequest::DropAccountTransactions(args) => {
+                MempoolResponse::DropAccountTransactions(self.drop_account_txs(args))
+            }
+            MempoolRequest::Flush => MempoolResponse::Flush(self.flush()),
+            MempoolRequest::IsAlive => MempoolResponse::IsAlive(self.is_alive().await),
+            MempoolRequest::IsReady => MempoolResponse::IsReady(self.is_ready().await),
+        };
+        record_mempool_size(&self.mempool);
+        response
     }
 }
 
 #[async_trait]
-impl ComponentStarter for MempoolCommunicationWrapper {}
+impl ComponentHealth for MempoolCommunicationWrapper {}
+
+#[async_trait]
+impl ComponentStarter for MempoolCommunicationWrapper {
+    async fn start(&mut self) -> Result<(), ComponentStartError> {
+        if let Some(class_availability_checker) = self.mempool.class_availability_checker() {
+            class_availability_checker.start();
+        }
+        // Expired transactions are swept in the background, as long as the mempool is alive.
+        if let Some(sweep_interval) = self.mempool.sweep_interval() {
+            let mempool = Arc::downgrade(&self.mempool);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(sweep_interval);
+                loop {
+                    interval.tick().await;
+                    let Some(mempool) = mempool.upgrade() else {
+                        break;
+                    };
+                    mempool.evict_expired_txs();
+                    record_mempool_size(&mempool);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+// Pushes the eligible transactions to the transaction stream as it frees up, and waits for
+// transactions to be added once none is eligible. Transactions are taken from the mempool only
+// once they fit in the stream, so that no transaction is taken without being pushed.
+async fn push_eligible_txs(
+    mempool: Weak<ShardedMempool>,
+    sender: Sender<Transaction>,
+    wakeup: Arc<Notify>,
+) {
+    // Ends once the receiving end of the stream is dropped.
+    while let Ok(permit) = sender.reserve().await {
+        let Some(mempool) = mempool.upgrade() else {
+            return;
+        };
+        // The reserved slot is filled along with the rest of the free capacity.
+        let txs = match mempool.get_txs(sender.capacity() + 1) {
+            Ok(txs) => txs,
+            Err(err) => {
+                warn!("Failed to take transactions for the transaction stream: {}", err);
+                return;
+            }
+        };
+        drop(mempool);
+
+        let mut txs = txs.into_iter();
+        let Some(first_tx) = txs.next() else {
+            drop(permit);
+            wakeup.notified().await;
+            continue;
+        };
+        permit.send(first_tx);
+        for tx in txs {
+            sender.try_send(tx).expect("Transaction stream should have free capacity.");
+        }
+    }
+}
+
+fn record_mempool_size(mempool: &ShardedMempool) {
+    metrics::gauge!(MEMPOOL_SIZE, to_gauge_value(mempool.n_txs()));
+}
+
+// Mempool sizes are far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_gauge_value(n_txs: usize) -> f64 {
+    n_txs as f64
+}
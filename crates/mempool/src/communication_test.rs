@@ -0,0 +1,81 @@
+use assert_matches::assert_matches;
+use mempool_test_utils::starknet_api_test_utils::{
+    create_executable_tx,
+    test_resource_bounds_mapping,
+};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::{Tip, TransactionHash, ValidResourceBounds};
+use starknet_api::{contract_address, felt, patricia_key};
+use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
+use starknet_mempool_types::communication::{MempoolRequest, MempoolResponse};
+use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput};
+use tokio::sync::mpsc::{channel, Receiver};
+use tokio::time::{timeout, Duration};
+
+use crate::communication::MempoolCommunicationWrapper;
+use crate::config::MempoolConfig;
+use crate::sharded_mempool::ShardedMempool;
+
+const PUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn tx_input(tx_nonce: u64) -> MempoolInput {
+    let sender_address = contract_address!("0x1");
+    let tx = create_executable_tx(
+        sender_address,
+        TransactionHash(felt!(tx_nonce)),
+        Tip(0),
+        Nonce(felt!(tx_nonce)),
+        ValidResourceBounds::AllResources(test_resource_bounds_mapping()),
+    );
+    MempoolInput {
+        tx,
+        account: Account { sender_address, state: AccountState::default() },
+        eligible_at: None,
+    }
+}
+
+fn mempool_with_tx_stream(capacity: usize) -> (MempoolCommunicationWrapper, Receiver<Transaction>) {
+    let (tx_stream_sender, tx_stream_receiver) = channel(capacity);
+    let mempool = ShardedMempool::new(MempoolConfig::default(), None, None);
+    (MempoolCommunicationWrapper::new(mempool, Some(tx_stream_sender), None), tx_stream_receiver)
+}
+
+async fn add_tx(mempool: &mut MempoolCommunicationWrapper, tx_nonce: u64) {
+    let response = mempool.handle_request(MempoolRequest::AddTransaction(tx_input(tx_nonce))).await;
+    assert_matches!(response, MempoolResponse::AddTransaction(Ok(())));
+}
+
+async fn pushed_tx_hash(tx_stream: &mut Receiver<Transaction>) -> TransactionHash {
+    timeout(PUSH_TIMEOUT, tx_stream.recv()).await.unwrap().unwrap().tx_hash()
+}
+
+#[tokio::test]
+async fn open_tx_stream_is_refilled_as_it_frees_up() {
+    let (mut mempool, mut tx_stream) = mempool_with_tx_stream(2);
+    for tx_nonce in 0_u64..3 {
+        add_tx(&mut mempool, tx_nonce).await;
+    }
+
+    let response = mempool.handle_request(MempoolRequest::OpenTransactionStream).await;
+
+    assert_matches!(response, MempoolResponse::OpenTransactionStream(Ok(())));
+    // All the transactions are pushed without further requests, though only two fit in the stream.
+    for tx_nonce in 0_u64..3 {
+        assert_eq!(pushed_tx_hash(&mut tx_stream).await, TransactionHash(felt!(tx_nonce)));
+    }
+}
+
+#[tokio::test]
+async fn txs_added_to_the_mempool_are_pushed_while_the_tx_stream_is_open() {
+    let (mut mempool, mut tx_stream) = mempool_with_tx_stream(2);
+    mempool.handle_request(MempoolRequest::OpenTransactionStream).await;
+
+    add_tx(&mut mempool, 0).await;
+    assert_eq!(pushed_tx_hash(&mut tx_stream).await, TransactionHash(felt!(0_u8)));
+
+    let response = mempool.handle_request(MempoolRequest::CloseTransactionStream).await;
+    assert_matches!(response, MempoolResponse::CloseTransactionStream(Ok(())));
+    add_tx(&mut mempool, 1).await;
+    assert!(timeout(PUSH_TIMEOUT, tx_stream.recv()).await.is_err());
+}
@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use papyrus_config::converters::{
+    deserialize_milliseconds_to_duration,
+    deserialize_seconds_to_duration,
+};
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_dynamic_param,
+    ser_optional_param,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::account_state::DEFAULT_MAX_TRACKED_ACCOUNTS;
+
+const DEFAULT_MAX_NONCE_LOOKAHEAD: u64 = 50;
+const DEFAULT_MAX_PENDING_TXS_PER_ACCOUNT: usize = 64;
+const DEFAULT_MAX_RETURNS: usize = 5;
+const DEFAULT_MAX_SCHEDULED_TXS: usize = 1000;
+
+/// The mempool related configuration.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct MempoolConfig {
+    // If set, the additions, evictions and selections of transactions are recorded in an audit
+    // log.
+    #[validate]
+    pub audit_log: Option<AuditLogConfig>,
+    // If true, a transaction becomes eligible for sequencing only once the class it depends on is
+    // available. Adds a class lookup per transaction.
+    pub enable_class_availability_check: bool,
+    // If set, transactions are evicted once they expire, or to make room for transactions of
+    // higher priority once the mempool is full.
+    #[validate]
+    pub eviction: Option<EvictionConfig>,
+    // If set, transactions may carry nonces of at most the account nonce plus this value. Bounds
+    // the number of future-nonce transactions an account can park in the mempool.
+    pub max_nonce_lookahead: Option<u64>,
+    // If set, an account may hold at most this many pending transactions, i.e., transactions with
+    // nonces ahead of the account nonce, which wait for the preceding nonces before becoming
+    // eligible for sequencing.
+    pub max_pending_txs_per_account: Option<usize>,
+    // The maximal number of transactions held until they become eligible at their timestamp.
+    // Split evenly between the shards.
+    pub max_scheduled_txs: usize,
+    // The number of accounts whose committed nonces are tracked, to drop the transactions whose
+    // nonces were already committed. Split evenly between the shards.
+    pub max_tracked_accounts: usize,
+    // The number of shards the transactions are partitioned into by sender address. Transactions
+    // of different shards are added concurrently.
+    #[validate(range(min = 1))]
+    pub n_shards: usize,
+    // How transactions of equal priority are ordered.
+    pub priority_tie_breaking: PriorityTieBreaking,
+    // How transactions returned by the batcher become eligible again.
+    pub reinsertion_policy: ReinsertionPolicy,
+    // When a transaction may replace a transaction of the same account and nonce.
+    #[validate]
+    pub replacement_policy: ReplacementPolicy,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            audit_log: None,
+            enable_class_availability_check: false,
+            eviction: None,
+            max_nonce_lookahead: None,
+            max_pending_txs_per_account: None,
+            max_scheduled_txs: DEFAULT_MAX_SCHEDULED_TXS,
+            max_tracked_accounts: DEFAULT_MAX_TRACKED_ACCOUNTS,
+            n_shards: 1,
+            priority_tie_breaking: PriorityTieBreaking::default(),
+            reinsertion_policy: ReinsertionPolicy::default(),
+            replacement_policy: ReplacementPolicy::default(),
+        }
+    }
+}
+
+impl SerializeConfig for MempoolConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([
+            ser_param(
+                "enable_class_availability_check",
+                &self.enable_class_availability_check,
+                "If true, transactions whose target class is not yet available are held back from \
+                 sequencing.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_scheduled_txs",
+                &self.max_scheduled_txs,
+                "The maximal number of transactions the mempool holds until the timestamp they \
+                 become eligible at. Transactions scheduled beyond it are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_tracked_accounts",
+                &self.max_tracked_accounts,
+                "The maximal number of accounts whose committed nonces the mempool tracks, to drop \
+                 transactions whose nonces were already committed. The accounts committed least \
+                 recently are forgotten first.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "n_shards",
+                &self.n_shards,
+                "The number of shards the mempool transactions are partitioned into by sender \
+                 address.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "priority_tie_breaking",
+                &self.priority_tie_breaking,
+                "How transactions of equal priority are ordered: by descending transaction hash \
+                 (TxHash), or by ascending arrival time (ArrivalTime).",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(ser_optional_param(
+            &self.max_nonce_lookahead,
+            DEFAULT_MAX_NONCE_LOOKAHEAD,
+            "max_nonce_lookahead",
+            "The maximal distance of a transaction nonce ahead of its account nonce. If not set, \
+             any future nonce is accepted.",
+            ParamPrivacyInput::Public,
+        ));
+        config.extend(ser_optional_param(
+            &self.max_pending_txs_per_account,
+            DEFAULT_MAX_PENDING_TXS_PER_ACCOUNT,
+            "max_pending_txs_per_account",
+            "The maximal number of transactions with nonces ahead of the account nonce an account \
+             may hold. If not set, the number is unbounded.",
+            ParamPrivacyInput::Public,
+        ));
+        config.extend(append_sub_config_name(self.reinsertion_policy.dump(), "reinsertion_policy"));
+        config.extend(append_sub_config_name(self.replacement_policy.dump(), "replacement_policy"));
+        config.extend(ser_optional_sub_config(&self.eviction, "eviction"));
+        config.extend(ser_optional_sub_config(&self.audit_log, "audit_log"));
+        config
+    }
+}
+
+/// The limits of the transactions held by the mempool. The capacity limits are split evenly
+/// between the shards of the mempool.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct EvictionConfig {
+    // Transactions are evicted once they are held by the mempool for longer than this.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub tx_ttl: Duration,
+    // The interval between sweeps of the expired transactions.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub sweep_interval: Duration,
+    #[validate(range(min = 1))]
+    pub max_n_txs: usize,
+    // The maximal total size of the held transactions, in their binary encoding.
+    #[validate(range(min = 1))]
+    pub max_n_bytes: usize,
+}
+
+impl Default for EvictionConfig {
+    fn default() -> Self {
+        Self {
+            tx_ttl: Duration::from_secs(3600),
+            sweep_interval: Duration::from_secs(10),
+            max_n_txs: 100_000,
+            max_n_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl SerializeConfig for EvictionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_dynamic_param(
+                "tx_ttl",
+                &self.tx_ttl.as_secs(),
+                "The time, in seconds, after which a transaction held by the mempool is evicted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "sweep_interval",
+                &self.sweep_interval.as_secs(),
+                "The interval, in seconds, between sweeps of the expired transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "max_n_txs",
+                &self.max_n_txs,
+                "The maximal number of transactions held by the mempool. Once reached, the lowest \
+                 priority transactions are evicted to make room for higher priority ones.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_dynamic_param(
+                "max_n_bytes",
+                &self.max_n_bytes,
+                "The maximal total size, in bytes, of the transactions held by the mempool. Once \
+                 reached, the lowest priority transactions are evicted to make room for higher \
+                 priority ones.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The append-only log of the events of the mempool transactions; see
+/// [`MempoolAuditLog`](crate::audit_log::MempoolAuditLog).
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct AuditLogConfig {
+    pub path: PathBuf,
+    // The log file is rotated once it exceeds this size, in bytes.
+    #[validate(range(min = 1))]
+    pub max_file_size: u64,
+    // The number of rotated files kept; older ones are deleted.
+    pub max_rotated_files: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("mempool_audit_log.jsonl"),
+            max_file_size: 100 * 1024 * 1024,
+            max_rotated_files: 10,
+        }
+    }
+}
+
+impl SerializeConfig for AuditLogConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "path",
+                &self.path,
+                "The path of the mempool audit log file.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_file_size",
+                &self.max_file_size,
+                "The size, in bytes, beyond which the mempool audit log file is rotated.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_rotated_files",
+                &self.max_rotated_files,
+                "The number of rotated mempool audit log files kept; older files are deleted.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The replacement of a transaction in the mempool by a transaction of the same account and nonce,
+/// i.e., fee escalation. Transactions already taken for the block in creation can't be replaced.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ReplacementPolicy {
+    // If false, a transaction with the nonce of a transaction in the mempool is rejected.
+    pub enable_replacement: bool,
+    // The minimal increase of the tip of the replacing transaction, in percents of the tip of the
+    // replaced one. The tip must increase in any case.
+    pub min_tip_bump_percentage: u64,
+}
+
+impl Default for ReplacementPolicy {
+    fn default() -> Self {
+        Self { enable_replacement: false, min_tip_bump_percentage: 10 }
+    }
+}
+
+impl SerializeConfig for ReplacementPolicy {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable_replacement",
+                &self.enable_replacement,
+                "If true, a transaction may replace a transaction of the same account and nonce \
+                 in the mempool by raising its tip.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "min_tip_bump_percentage",
+                &self.min_tip_bump_percentage,
+                "The minimal increase of the tip of a replacing transaction, in percents of the \
+                 tip of the replaced transaction.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The re-insertion of transactions returned by the batcher, e.g., since their proposal was aborted
+/// or their execution failed temporarily. A returned transaction cools down before it becomes
+/// eligible again, so that it isn't taken again at once, and is evicted once returned too many
+/// times.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReinsertionPolicy {
+    // Returned transactions become eligible for sequencing again once this passes.
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub return_cooldown: Duration,
+    // If set, a transaction returned more times than this is evicted, with the transactions of its
+    // account with higher nonces.
+    pub max_returns: Option<usize>,
+}
+
+impl SerializeConfig for ReinsertionPolicy {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([ser_param(
+            "return_cooldown",
+            &u64::try_from(self.return_cooldown.as_millis()).expect("Cooldown should fit u64."),
+            "The time, in milliseconds, after which a transaction returned by the batcher is \
+             eligible for sequencing again.",
+            ParamPrivacyInput::Public,
+        )]);
+        config.extend(ser_optional_param(
+            &self.max_returns,
+            DEFAULT_MAX_RETURNS,
+            "max_returns",
+            "The maximal number of times a transaction may be returned by the batcher; once \
+             exceeded, it is evicted. If not set, transactions may be returned any number of \
+             times.",
+            ParamPrivacyInput::Public,
+        ));
+        config
+    }
+}
+
+/// The order of transactions of equal priority.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum PriorityTieBreaking {
+    /// Transactions are ordered by descending hash, which is the same on every node.
+    #[default]
+    TxHash,
+    /// Transactions that arrived earlier come first.
+    ArrivalTime,
+}
@@ -1,5 +1,11 @@
+pub mod account_state;
+pub mod audit_log;
+pub mod class_availability;
 pub mod communication;
+pub mod config;
 pub mod mempool;
+pub mod priority;
+pub mod sharded_mempool;
 pub(crate) mod suspended_transaction_pool;
 pub(crate) mod transaction_pool;
 pub(crate) mod transaction_queue;
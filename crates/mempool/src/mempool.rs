@@ -1,22 +1,60 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+use starknet_api::block::BlockTimestamp;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::hash::StarkHash;
 use starknet_api::transaction::{Tip, TransactionHash, ValidResourceBounds};
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput, MempoolResult};
+use starknet_mempool_types::mempool_types::{
+    Account,
+    AccountState,
+    EvictionReason,
+    MempoolInput,
+    MempoolResult,
+    RejectedTransaction,
+    TxBudget,
+};
+use tracing::{debug, info, warn};
 
-use crate::transaction_pool::TransactionPool;
+use crate::account_state::{AccountNonceReader, AccountStateTracker};
+use crate::audit_log::{AuditEvent, MempoolAuditLog};
+use crate::class_availability::ClassAvailabilityChecker;
+use crate::config::{EvictionConfig, MempoolConfig, PriorityTieBreaking, ReinsertionPolicy};
+use crate::priority::{MempoolPriorityPolicy, PriorityKey, TipPriority};
+use crate::transaction_pool::{tx_size, TransactionPool};
 use crate::transaction_queue::TransactionQueue;
 
 #[cfg(test)]
 #[path = "mempool_test.rs"]
 pub mod mempool_test;
 
+/// The number of transactions evicted from the mempool, labeled by the reason of the eviction:
+/// expiry, or making room for a transaction of higher priority.
+pub const EVICTED_TXS: &str = "mempool_evicted_txs";
+const EVICTION_REASON_LABEL: &str = "reason";
+/// The number of transactions held in the mempool, sampled after each request it handles.
+pub const MEMPOOL_SIZE: &str = "mempool_size";
+
 type AccountToNonce = HashMap<ContractAddress, Nonce>;
 
 #[derive(Debug, Default)]
 pub struct Mempool {
+    config: MempoolConfig,
+    // Consulted before a transaction becomes eligible, if the class availability check is enabled.
+    class_availability_checker: Option<Arc<dyn ClassAvailabilityChecker>>,
+    // Scores the eligible transactions; if not set, transactions are prioritized by tip.
+    priority_policy: Option<Arc<dyn MempoolPriorityPolicy>>,
+    // The eviction limits, if transactions are evicted. Shared by the shards of a sharded mempool,
+    // each holding an even share of the capacity, and changed once the node config is reloaded.
+    eviction_config: Option<SharedDynamicConfig<EvictionConfig>>,
+    // If set, the events of the transactions are recorded in it. Shared by the shards of a sharded
+    // mempool.
+    audit_log: Option<Arc<MempoolAuditLog>>,
     // TODO: add docstring explaining visibility and coupling of the fields.
     // All transactions currently held in the mempool.
     tx_pool: TransactionPool,
@@ -26,25 +64,103 @@ pub struct Mempool {
     mempool_state: HashMap<ContractAddress, AccountState>,
     // The most recent account nonces received, for all account in the pool.
     account_nonces: AccountToNonce,
+    // The committed nonces of the accounts, by which transactions that can no longer be sequenced
+    // are dropped.
+    account_states: AccountStateTracker,
+    // The nonce of the first transaction taken from each account during block creation.
+    block_start_nonces: AccountToNonce,
+    // Transactions that are not eligible before a timestamp. They are added to the pool once it
+    // passes.
+    scheduled_txs: ScheduledTransactions,
+    // The arrival times of the transactions taken during block creation, kept for the transactions
+    // that are returned.
+    taken_tx_arrival_times: HashMap<TransactionHash, Instant>,
+    // The number of times each transaction in the pool was returned.
+    return_counts: HashMap<TransactionHash, usize>,
+    // Returned transactions that are not eligible before their cooldown ends, with the end of
+    // their cooldown and their sender.
+    cooling_down_txs: HashMap<TransactionHash, (Instant, ContractAddress)>,
+    // Transactions held back by the class availability check, with their sender. They are checked
+    // again once transactions are requested, as the lookups of their classes finish meanwhile.
+    class_pending_txs: HashMap<TransactionHash, ContractAddress>,
 }
 
 impl Mempool {
+    pub fn new(
+        config: MempoolConfig,
+        class_availability_checker: Option<Arc<dyn ClassAvailabilityChecker>>,
+        priority_policy: Option<Arc<dyn MempoolPriorityPolicy>>,
+    ) -> Self {
+        let eviction_config = config.eviction.map(SharedDynamicConfig::new);
+        let audit_log = open_audit_log(&config);
+        Self::new_shard(
+            config,
+            eviction_config,
+            audit_log,
+            class_availability_checker,
+            priority_policy,
+        )
+    }
+
+    /// Creates a mempool holding one of `config.n_shards` even shares of the capacity limits of
+    /// the given eviction config.
+    pub(crate) fn new_shard(
+        config: MempoolConfig,
+        eviction_config: Option<SharedDynamicConfig<EvictionConfig>>,
+        audit_log: Option<Arc<MempoolAuditLog>>,
+        class_availability_checker: Option<Arc<dyn ClassAvailabilityChecker>>,
+        priority_policy: Option<Arc<dyn MempoolPriorityPolicy>>,
+    ) -> Self {
+        let account_states =
+            AccountStateTracker::new(config.max_tracked_accounts.div_ceil(config.n_shards));
+        Mempool {
+            config,
+            account_states,
+            class_availability_checker,
+            priority_policy,
+            eviction_config,
+            audit_log,
+            ..Default::default()
+        }
+    }
+
+    /// Looks up the committed nonces of the accounts not seen in the blocks committed since the
+    /// mempool started, or forgotten since, with the given reader.
+    pub fn with_account_nonce_reader(mut self, reader: Arc<dyn AccountNonceReader>) -> Self {
+        self.account_states.set_account_nonce_reader(reader);
+        self
+    }
+
     pub fn empty() -> Self {
         Mempool::default()
     }
 
+    /// The number of transactions held in the mempool, eligible for sequencing or not.
+    pub fn n_txs(&self) -> usize {
+        self.tx_pool.n_txs()
+    }
+
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
         self.tx_queue.iter_over_ready_txs()
     }
 
+    /// Returns the priority keys of the current eligible transactions, in the order of `iter`.
+    pub(crate) fn iter_priority_keys(&self) -> impl Iterator<Item = PriorityKey> + '_ {
+        self.tx_queue.iter_over_ready_priority_keys()
+    }
+
     /// Retrieves up to `n_txs` transactions with the highest priority from the mempool.
     /// Transactions are guaranteed to be unique across calls until `commit_block` is invoked.
     // TODO: the last part about commit_block is incorrect if we delete txs in get_txs and then push
     // back. TODO: Consider renaming to `pop_txs` to be more consistent with the standard
     // library.
     pub fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<Transaction>> {
+        self.release_scheduled_txs(current_timestamp());
+        self.release_returned_txs(Instant::now());
+        self.release_class_pending_txs();
+
         let mut eligible_tx_references: Vec<TransactionReference> = Vec::with_capacity(n_txs);
         let mut n_remaining_txs = n_txs;
 
@@ -55,8 +171,75 @@ impl Mempool {
             eligible_tx_references.extend(chunk);
         }
 
-        let mut eligible_txs: Vec<Transaction> = Vec::with_capacity(n_txs);
-        for tx_ref in &eligible_tx_references {
+        self.take_txs(&eligible_tx_references)
+    }
+
+    /// Retrieves the transactions with the highest priority whose gas estimates and sizes fit in
+    /// the budget together; see [`TxBudget`]. Transactions exceeding the remaining budget are
+    /// skipped and left in the mempool, so that a large transaction doesn't hold back the smaller
+    /// ones behind it.
+    pub fn get_txs_by_budget(&mut self, mut budget: TxBudget) -> MempoolResult<Vec<Transaction>> {
+        self.release_scheduled_txs(current_timestamp());
+        self.release_returned_txs(Instant::now());
+        self.release_class_pending_txs();
+
+        let mut skipped_txs = Vec::new();
+        let mut txs = Vec::new();
+        loop {
+            match self.take_next_tx_within(&mut budget, &mut skipped_txs)? {
+                BudgetedTake::Taken(tx) => txs.push(tx),
+                BudgetedTake::Skipped => {}
+                BudgetedTake::Done => break,
+            }
+        }
+        self.requeue_skipped_txs(skipped_txs);
+
+        Ok(txs)
+    }
+
+    /// Takes the eligible transaction of the highest priority if it fits in the budget, and spends
+    /// its resources. A transaction that doesn't fit is added to `skipped_txs`, which must be
+    /// requeued with `requeue_skipped_txs` once done.
+    pub(crate) fn take_next_tx_within(
+        &mut self,
+        budget: &mut TxBudget,
+        skipped_txs: &mut Vec<TransactionReference>,
+    ) -> MempoolResult<BudgetedTake> {
+        if budget.is_exhausted() {
+            return Ok(BudgetedTake::Done);
+        }
+        let Some(tx_reference) = self.tx_queue.pop_ready_chunk(1).pop() else {
+            return Ok(BudgetedTake::Done);
+        };
+        let tx_size = self.tx_pool.get_size(tx_reference.tx_hash)?;
+        if !budget.try_spend(tx_reference.gas_estimate(), tx_size) {
+            skipped_txs.push(tx_reference);
+            return Ok(BudgetedTake::Skipped);
+        }
+
+        let tx_references = [tx_reference];
+        self.enqueue_next_eligible_txs(&tx_references)?;
+        let tx = self.take_txs(&tx_references)?.pop().expect("A transaction was taken.");
+        Ok(BudgetedTake::Taken(tx))
+    }
+
+    /// Returns the transactions skipped by `take_next_tx_within` to the queue.
+    pub(crate) fn requeue_skipped_txs(&mut self, skipped_txs: Vec<TransactionReference>) {
+        for tx_reference in skipped_txs {
+            self.enqueue(tx_reference);
+        }
+    }
+
+    // Removes the given eligible transactions, which were popped from the queue, from the pool,
+    // and advances the mempool state of their accounts past them.
+    fn take_txs(
+        &mut self,
+        tx_references: &[TransactionReference],
+    ) -> MempoolResult<Vec<Transaction>> {
+        let mut eligible_txs: Vec<Transaction> = Vec::with_capacity(tx_references.len());
+        for tx_ref in tx_references {
+            let arrival_time = self.tx_pool.get_arrival_time(tx_ref.tx_hash)?;
+            self.taken_tx_arrival_times.insert(tx_ref.tx_hash, arrival_time);
             let tx = self.tx_pool.remove(tx_ref.tx_hash)?;
             let address = tx.contract_address();
             if !self.tx_pool.contains_account(address) {
@@ -64,29 +247,440 @@ impl Mempool {
             }
             eligible_txs.push(tx);
         }
+        self.record_audit_events(
+            eligible_txs.iter().map(|tx| AuditEvent::Selected { tx_hash: tx.tx_hash() }),
+        );
 
         // Update the mempool state with the given transactions' nonces.
         for tx in &eligible_txs {
             self.mempool_state.entry(tx.contract_address()).or_default().nonce = tx.nonce();
+            self.block_start_nonces.entry(tx.contract_address()).or_insert(tx.nonce());
         }
 
         Ok(eligible_txs)
     }
 
-    /// Adds a new transaction to the mempool.
-    /// TODO: support fee escalation and transactions with future nonces.
+    /// Adds a new transaction to the mempool. If enabled by the replacement policy, a transaction
+    /// replaces the transaction of the same account and nonce in the mempool, given a high enough
+    /// tip.
     /// TODO: check Account nonce and balance.
     pub fn add_tx(&mut self, input: MempoolInput) -> MempoolResult<()> {
+        let (tx_hash, sender_address, nonce) =
+            (input.tx.tx_hash(), input.tx.contract_address(), input.tx.nonce());
+        let tip = input.tx.tip().unwrap_or_default();
+        let result = self.insert_tx(input);
+        self.record_audit_events([match &result {
+            Ok(()) => AuditEvent::Added { tx_hash, sender_address, nonce, tip },
+            Err(err) => {
+                AuditEvent::Rejected { tx_hash, sender_address, nonce, reason: err.to_string() }
+            }
+        }]);
+        result
+    }
+
+    fn insert_tx(&mut self, mut input: MempoolInput) -> MempoolResult<()> {
+        // The given account state may predate the latest committed blocks.
+        if let Some(next_nonce) = self.account_states.next_nonce(input.account.sender_address) {
+            let account_nonce = &mut input.account.state.nonce;
+            *account_nonce = (*account_nonce).max(next_nonce);
+        }
         self.validate_input(&input)?;
-        let MempoolInput { tx, account: Account { sender_address, state: AccountState { nonce } } } =
-            input;
+        if let Some(eligible_at) =
+            input.eligible_at.filter(|&eligible_at| eligible_at > current_timestamp())
+        {
+            return self.schedule_tx(eligible_at, input);
+        }
+
+        let MempoolInput {
+            tx,
+            account: Account { sender_address, state: AccountState { nonce } },
+            ..
+        } = input;
+        let tx_reference = TransactionReference::new(&tx);
+
+        // Replace the transaction of the same nonce, if any; validated to be allowed. Otherwise,
+        // make room for the transaction.
+        let is_replaced_tx_queued =
+            self.tx_queue.get_nonce(sender_address) == Some(tx_reference.nonce);
+        match self.tx_pool.get_by_address_and_nonce(sender_address, tx_reference.nonce).cloned() {
+            Some(replaced_tx) => {
+                if is_replaced_tx_queued {
+                    self.tx_queue.remove(sender_address);
+                }
+                self.tx_pool.remove(replaced_tx.tx_hash)?;
+                self.record_audit_events([AuditEvent::Evicted {
+                    tx_hash: replaced_tx.tx_hash,
+                    reason: "replaced".to_owned(),
+                }]);
+            }
+            None => self.make_room_for(&tx)?,
+        }
+
         self.tx_pool.insert(tx)?;
         self.align_to_account_state(sender_address, nonce);
+
+        // The replacing transaction takes the place of the replaced one in the queue.
+        if is_replaced_tx_queued
+            && self.tx_queue.get_nonce(sender_address).is_none()
+            && self.is_target_class_available(&tx_reference)
+        {
+            self.enqueue(tx_reference);
+        }
         Ok(())
     }
 
+    // Holds a transaction until it becomes eligible at the given timestamp. A transaction already
+    // scheduled, or of an account and nonce already scheduled, is rejected as a duplicate.
+    fn schedule_tx(
+        &mut self,
+        eligible_at: BlockTimestamp,
+        input: MempoolInput,
+    ) -> MempoolResult<()> {
+        let tx_reference = TransactionReference::new(&input.tx);
+        let (tx_hash, address, nonce) =
+            (tx_reference.tx_hash, tx_reference.sender_address, tx_reference.nonce);
+        if self.scheduled_txs.txs.contains_key(&tx_hash) {
+            return Err(MempoolError::DuplicateTransaction { tx_hash });
+        }
+        if self.scheduled_txs.account_nonces.contains(&(address, nonce)) {
+            return Err(MempoolError::DuplicateNonce { address, nonce });
+        }
+        let max_scheduled_txs = self.config.max_scheduled_txs.div_ceil(self.config.n_shards);
+        if self.scheduled_txs.txs.len() >= max_scheduled_txs {
+            return Err(MempoolError::TooManyScheduledTransactions {
+                max_scheduled_txs: self.config.max_scheduled_txs,
+            });
+        }
+        self.scheduled_txs.insert(eligible_at, tx_reference, input);
+        Ok(())
+    }
+
+    /// Adds the scheduled transactions that are eligible at `timestamp` to the pool. Transactions
+    /// that can no longer be added, e.g., since another transaction took their nonce meanwhile, are
+    /// dropped.
+    pub fn release_scheduled_txs(&mut self, timestamp: BlockTimestamp) {
+        for input in self.scheduled_txs.pop_eligible(timestamp) {
+            let tx_hash = input.tx.tx_hash();
+            if let Err(err) = self.insert_tx(MempoolInput { eligible_at: None, ..input }) {
+                warn!("Dropped scheduled transaction {}: {}", tx_hash, err);
+                self.record_audit_events([AuditEvent::Evicted {
+                    tx_hash,
+                    reason: format!("scheduled: {err}"),
+                }]);
+            }
+        }
+    }
+
+    /// Returns transactions taken by `get_txs` that were not added to the block, e.g., since the
+    /// proposal was aborted, so that they can be sequenced again. The transactions returned for an
+    /// account are expected to be the last ones taken from it. Transactions whose nonces were
+    /// committed meanwhile, e.g., by a block of another proposer, are dropped.
+    ///
+    /// Per the reinsertion policy, returned transactions become eligible again once they cool
+    /// down, and a transaction returned too many times is evicted, with the transactions of its
+    /// account with higher nonces.
+    pub fn return_txs(&mut self, txs: Vec<Transaction>) -> MempoolResult<()> {
+        let ReinsertionPolicy { return_cooldown, max_returns } = self.config.reinsertion_policy;
+        let mut lowest_returned_nonces = AccountToNonce::new();
+        let mut lowest_evicted_nonces = AccountToNonce::new();
+        let mut returned_txs = Vec::with_capacity(txs.len());
+        let mut n_evicted_txs = 0;
+        for tx in txs {
+            let (address, nonce) = (tx.contract_address(), tx.nonce());
+            if self.account_states.next_nonce(address).is_some_and(|next_nonce| nonce < next_nonce)
+            {
+                debug!("Dropped returned transaction {}: its nonce was committed.", tx.tx_hash());
+                self.taken_tx_arrival_times.remove(&tx.tx_hash());
+                self.return_counts.remove(&tx.tx_hash());
+                continue;
+            }
+            lowest_returned_nonces
+                .entry(address)
+                .and_modify(|lowest_nonce| *lowest_nonce = (*lowest_nonce).min(nonce))
+                .or_insert(nonce);
+
+            let n_returns = self.return_counts.entry(tx.tx_hash()).or_default();
+            *n_returns += 1;
+            if max_returns.is_some_and(|max_returns| *n_returns > max_returns) {
+                lowest_evicted_nonces
+                    .entry(address)
+                    .and_modify(|lowest_nonce| *lowest_nonce = (*lowest_nonce).min(nonce))
+                    .or_insert(nonce);
+            }
+            returned_txs.push(tx);
+        }
+
+        let now = Instant::now();
+        for tx in returned_txs {
+            let arrival_time =
+                self.taken_tx_arrival_times.remove(&tx.tx_hash()).unwrap_or_else(Instant::now);
+            // Transactions following an evicted transaction of their account can't be sequenced.
+            if lowest_evicted_nonces
+                .get(&tx.contract_address())
+                .is_some_and(|&evicted_nonce| tx.nonce() >= evicted_nonce)
+            {
+                self.return_counts.remove(&tx.tx_hash());
+                self.record_audit_events([AuditEvent::Evicted {
+                    tx_hash: tx.tx_hash(),
+                    reason: "returned".to_owned(),
+                }]);
+                n_evicted_txs += 1;
+                continue;
+            }
+            self.tx_pool.insert_with_arrival_time(tx, arrival_time)?;
+        }
+
+        for (address, nonce) in lowest_returned_nonces {
+            // Rewind the mempool state to the last transaction taken and not returned, if any.
+            let block_start_nonce = match self.block_start_nonces.get(&address) {
+                Some(&block_start_nonce) if block_start_nonce < nonce => {
+                    let last_taken_nonce = Nonce(nonce.0 - StarkHash::ONE);
+                    self.mempool_state.entry(address).or_default().nonce = last_taken_nonce;
+                    block_start_nonce
+                }
+                _ => {
+                    self.mempool_state.remove(&address);
+                    self.block_start_nonces.remove(&address);
+                    nonce
+                }
+            };
+            self.account_nonces.entry(address).or_insert(block_start_nonce);
+
+            // The returned transaction replaces the queued transaction of its account, which
+            // follows it, once it cools down.
+            self.tx_queue.remove(address);
+            let Some(tx_reference) = self.tx_pool.get_by_address_and_nonce(address, nonce).cloned()
+            else {
+                // Evicted.
+                continue;
+            };
+            if return_cooldown.is_zero() {
+                if self.is_target_class_available(&tx_reference) {
+                    self.enqueue(tx_reference);
+                }
+            } else {
+                self.cooling_down_txs
+                    .insert(tx_reference.tx_hash, (now + return_cooldown, address));
+            }
+        }
+
+        for (address, nonce) in lowest_evicted_nonces {
+            n_evicted_txs += self.evict_from_nonce(address, nonce, "returned");
+            info!(
+                %address,
+                "Evicted the transactions of account {} from nonce {:?}: returned too many times.",
+                address,
+                nonce
+            );
+        }
+        if n_evicted_txs > 0 {
+            record_evicted_txs(n_evicted_txs, "returned");
+        }
+
+        Ok(())
+    }
+
+    /// Makes the returned transactions that cooled down by `now` eligible for sequencing again,
+    /// unless another transaction of their account became eligible meanwhile.
+    pub fn release_returned_txs(&mut self, now: Instant) {
+        let cooled_down_txs: Vec<(TransactionHash, ContractAddress)> = self
+            .cooling_down_txs
+            .iter()
+            .filter(|(_, &(cooldown_end, _))| cooldown_end <= now)
+            .map(|(&tx_hash, &(_, address))| (tx_hash, address))
+            .collect();
+        for (tx_hash, address) in cooled_down_txs {
+            self.cooling_down_txs.remove(&tx_hash);
+            let Ok(tx) = self.tx_pool.get_by_tx_hash(tx_hash) else {
+                continue;
+            };
+            let tx_reference = TransactionReference::new(tx);
+            let is_next_tx = self.tx_pool.get_previous_tx(address, tx_reference.nonce).is_none();
+            if is_next_tx
+                && self.tx_queue.get_nonce(address).is_none()
+                && self.is_target_class_available(&tx_reference)
+            {
+                self.enqueue(tx_reference);
+            }
+        }
+    }
+
+    /// Makes the transactions held back by the class availability check whose class became
+    /// available eligible for sequencing, unless another transaction of their account became
+    /// eligible meanwhile.
+    pub fn release_class_pending_txs(&mut self) {
+        let class_pending_txs: Vec<(TransactionHash, ContractAddress)> =
+            self.class_pending_txs.drain().collect();
+        for (tx_hash, address) in class_pending_txs {
+            let Ok(tx) = self.tx_pool.get_by_tx_hash(tx_hash) else {
+                continue;
+            };
+            let tx_reference = TransactionReference::new(tx);
+            let is_next_tx = self.tx_pool.get_previous_tx(address, tx_reference.nonce).is_none();
+            if is_next_tx
+                && self.tx_queue.get_nonce(address).is_none()
+                && self.is_target_class_available(&tx_reference)
+            {
+                self.enqueue(tx_reference);
+            }
+        }
+    }
+
+    /// Evicts up to `n_txs` transactions that are not eligible for sequencing, to relieve resource
+    /// pressure. Transactions are evicted from the highest nonce of their account down, lowest tip
+    /// first, so that no nonce gaps are created. Returns the number of evicted transactions.
+    pub fn evict_txs(&mut self, n_txs: usize) -> MempoolResult<usize> {
+        let mut n_evicted_txs = 0;
+        while n_evicted_txs < n_txs {
+            let mut candidates = self.eviction_candidates();
+            if candidates.is_empty() {
+                break;
+            }
+
+            candidates.sort_by_key(|tx| tx.tip);
+            candidates.truncate(n_txs - n_evicted_txs);
+            n_evicted_txs += candidates.len();
+            self.evict(candidates)?;
+        }
+
+        Ok(n_evicted_txs)
+    }
+
+    /// The transactions `evict_txs` may evict next: the highest nonce transaction of each account,
+    /// unless it is eligible for sequencing.
+    pub(crate) fn eviction_candidates(&self) -> Vec<TransactionReference> {
+        self.tx_pool
+            .iter_highest_nonce_txs()
+            .filter(|tx| self.tx_queue.get_nonce(tx.sender_address) != Some(tx.nonce))
+            .cloned()
+            .collect()
+    }
+
+    /// Evicts the given eviction candidates. See `evict_txs`.
+    pub(crate) fn evict(&mut self, txs: Vec<TransactionReference>) -> MempoolResult<()> {
+        self.record_audit_events(txs.iter().map(|tx| AuditEvent::Evicted {
+            tx_hash: tx.tx_hash,
+            reason: "resource_pressure".to_owned(),
+        }));
+        for tx in txs {
+            self.tx_pool.remove(tx.tx_hash)?;
+            if !self.tx_pool.contains_account(tx.sender_address) {
+                self.account_nonces.remove(&tx.sender_address);
+            }
+        }
+        Ok(())
+    }
+
+    /// Evicts the transactions held for longer than the configured TTL, each with the transactions
+    /// of its account with higher nonces, which can't be sequenced without it. Returns the number
+    /// of evicted transactions.
+    pub fn evict_expired_txs(&mut self, now: Instant) -> usize {
+        let Some(EvictionConfig { tx_ttl, .. }) = self.eviction_config() else {
+            return 0;
+        };
+        let Some(expiry_time) = now.checked_sub(tx_ttl) else {
+            return 0;
+        };
+
+        let mut lowest_expired_nonces: HashMap<ContractAddress, Nonce> = HashMap::new();
+        for tx in self.tx_pool.txs_arrived_before(expiry_time) {
+            lowest_expired_nonces
+                .entry(tx.sender_address)
+                .and_modify(|nonce| *nonce = (*nonce).min(tx.nonce))
+                .or_insert(tx.nonce);
+        }
+        let n_evicted_txs = lowest_expired_nonces
+            .into_iter()
+            .map(|(address, nonce)| self.evict_from_nonce(address, nonce, "expired"))
+            .sum();
+
+        if n_evicted_txs > 0 {
+            debug!("Evicted {} expired transactions.", n_evicted_txs);
+            record_evicted_txs(n_evicted_txs, "expired");
+        }
+        n_evicted_txs
+    }
+
+    /// Evicts the transactions the block builder rejected, each with the transactions of its
+    /// account with higher nonces, which can't be sequenced without it. A rejected transaction
+    /// that was taken from the mempool is no longer in it. Returns the number of evicted
+    /// transactions.
+    pub fn reject_txs(&mut self, rejected_txs: &[RejectedTransaction]) -> usize {
+        let mut n_evicted_txs = 0;
+        for rejected_tx in rejected_txs {
+            debug!("Transaction {} was rejected: {}.", rejected_tx.tx_hash, rejected_tx.reason);
+            n_evicted_txs += self.evict_from_nonce(
+                rejected_tx.address,
+                rejected_tx.nonce,
+                &format!("rejected: {}", rejected_tx.reason),
+            );
+        }
+
+        if n_evicted_txs > 0 {
+            debug!("Evicted {} transactions following rejected ones.", n_evicted_txs);
+            record_evicted_txs(n_evicted_txs, "rejected");
+        }
+        n_evicted_txs
+    }
+
+    /// Drops all the transactions of the given account: its eligible and pending transactions, and
+    /// its scheduled ones. Transactions already taken for the block in creation are not affected.
+    /// Returns the hashes of the dropped transactions.
+    pub fn drop_account_txs(
+        &mut self,
+        address: ContractAddress,
+        reason: EvictionReason,
+    ) -> Vec<TransactionHash> {
+        self.tx_queue.remove(address);
+        let mut dropped_tx_hashes = self.tx_pool.remove_account_txs(address);
+        self.account_nonces.remove(&address);
+        dropped_tx_hashes.extend(
+            self.scheduled_txs.remove_where(|tx_reference| tx_reference.sender_address == address),
+        );
+        self.record_audit_events(
+            dropped_tx_hashes
+                .iter()
+                .map(|&tx_hash| AuditEvent::Evicted { tx_hash, reason: reason.to_string() }),
+        );
+
+        info!(
+            %address,
+            %reason,
+            n_dropped_txs = dropped_tx_hashes.len(),
+            "Dropped the transactions of account {}: {}.",
+            address,
+            reason
+        );
+        dropped_tx_hashes
+    }
+
+    /// Drops all the transactions held in the mempool: the eligible and pending ones, and the
+    /// scheduled ones. Transactions already taken for the block in creation are not affected.
+    /// Returns the hashes of the dropped transactions.
+    pub fn flush(&mut self) -> Vec<TransactionHash> {
+        let addresses: Vec<ContractAddress> =
+            self.tx_pool.iter_highest_nonce_txs().map(|tx| tx.sender_address).collect();
+        let mut dropped_tx_hashes = Vec::new();
+        for address in addresses {
+            self.tx_queue.remove(address);
+            dropped_tx_hashes.extend(self.tx_pool.remove_account_txs(address));
+            self.account_nonces.remove(&address);
+        }
+        dropped_tx_hashes.extend(self.scheduled_txs.remove_where(|_| true));
+        self.record_audit_events(
+            dropped_tx_hashes
+                .iter()
+                .map(|&tx_hash| AuditEvent::Evicted { tx_hash, reason: "flushed".to_owned() }),
+        );
+
+        info!(n_dropped_txs = dropped_tx_hashes.len(), "Flushed the mempool.");
+        dropped_tx_hashes
+    }
+
     /// Update the mempool's internal state according to the committed block (resolves nonce gaps,
-    /// updates account balances).
+    /// updates account balances). The transactions whose nonces are below the committed nonces of
+    /// their accounts, pending or scheduled, are dropped, and the next nonces of the accounts
+    /// become eligible.
     // TODO: the part about resolving nonce gaps is incorrect if we delete txs in get_txs and then
     // push back.
     // state_changes: a map that associates each account address with the state of the committed
@@ -95,10 +689,22 @@ impl Mempool {
         &mut self,
         state_changes: HashMap<ContractAddress, AccountState>,
     ) -> MempoolResult<()> {
+        let mut next_nonces = AccountToNonce::with_capacity(state_changes.len());
         for (&address, AccountState { nonce }) in &state_changes {
             let next_nonce = nonce.try_increment().map_err(|_| MempoolError::FeltOutOfRange)?;
+            next_nonces.insert(address, next_nonce);
+        }
+
+        let n_txs_before_commit = self.tx_pool.n_txs();
+        for (&address, &next_nonce) in &next_nonces {
             self.align_to_account_state(address, next_nonce);
         }
+        let n_dropped_txs = n_txs_before_commit - self.tx_pool.n_txs()
+            + self.drop_committed_scheduled_txs(&next_nonces);
+        if n_dropped_txs > 0 {
+            debug!("Dropped {} transactions whose nonces were committed.", n_dropped_txs);
+        }
+        self.account_states.commit(next_nonces);
 
         // Rewind nonces of addresses that were not included in block.
         let addresses_not_included_in_block =
@@ -108,10 +714,27 @@ impl Mempool {
         }
 
         self.mempool_state.clear();
+        self.block_start_nonces.clear();
+        self.taken_tx_arrival_times.clear();
+        // Forget the returns of the transactions that left the pool, e.g., once committed.
+        let tx_pool = &self.tx_pool;
+        self.return_counts.retain(|&tx_hash, _| tx_pool.get_by_tx_hash(tx_hash).is_ok());
 
         Ok(())
     }
 
+    // Drops the scheduled transactions whose nonces are below the given next nonces of their
+    // accounts. Returns the number of dropped transactions.
+    fn drop_committed_scheduled_txs(&mut self, next_nonces: &AccountToNonce) -> usize {
+        self.scheduled_txs
+            .remove_where(|tx_reference| {
+                next_nonces
+                    .get(&tx_reference.sender_address)
+                    .is_some_and(|&next_nonce| tx_reference.nonce < next_nonce)
+            })
+            .len()
+    }
+
     // TODO(Mohammad): Rename this method once consensus API is added.
     fn _update_gas_price_threshold(&mut self, threshold: u128) {
         self.tx_queue._update_gas_price_threshold(threshold);
@@ -131,8 +754,56 @@ impl Mempool {
             return Err(duplicate_nonce_error);
         }
 
+        // Check the transaction nonce is within the allowed lookahead window.
+        if let Some(max_nonce_lookahead) = self.config.max_nonce_lookahead {
+            let max_allowed_nonce = Nonce(account_nonce.0 + StarkHash::from(max_nonce_lookahead));
+            if tx_nonce > max_allowed_nonce {
+                return Err(MempoolError::NonceTooFarInFuture {
+                    address: sender_address,
+                    nonce: tx_nonce,
+                    max_allowed_nonce,
+                });
+            }
+        }
+
         // Stateful checks.
 
+        // Check the transaction may replace the transaction of the same nonce, if any.
+        let replaced_tx = self.tx_pool.get_by_address_and_nonce(sender_address, tx_nonce);
+        if let Some(replaced_tx) = replaced_tx {
+            let tx_reference = TransactionReference::new(&input.tx);
+            if replaced_tx.tx_hash == tx_reference.tx_hash {
+                return Err(MempoolError::DuplicateTransaction { tx_hash: tx_reference.tx_hash });
+            }
+            if !self.config.replacement_policy.enable_replacement {
+                return Err(duplicate_nonce_error);
+            }
+            let min_tip = self.min_replacement_tip(replaced_tx.tip);
+            if tx_reference.tip < min_tip {
+                return Err(MempoolError::ReplacementTipTooLow {
+                    address: sender_address,
+                    nonce: tx_nonce,
+                    tip: tx_reference.tip,
+                    min_tip,
+                });
+            }
+        }
+
+        // Check the account has room for another pending transaction, i.e., a transaction that
+        // waits for the preceding nonces before becoming eligible.
+        if let Some(max_pending_txs) = self.config.max_pending_txs_per_account {
+            if tx_nonce > account_nonce
+                && replaced_tx.is_none()
+                && self.tx_pool.n_txs_with_higher_nonce(sender_address, account_nonce)
+                    >= max_pending_txs
+            {
+                return Err(MempoolError::TooManyPendingTransactions {
+                    address: sender_address,
+                    max_pending_txs,
+                });
+            }
+        }
+
         // Check nonce against mempool state.
         if let Some(AccountState { nonce: mempool_state_nonce }) =
             self.mempool_state.get(&sender_address)
@@ -161,10 +832,13 @@ impl Mempool {
                 state: AccountState { nonce: tx.nonce },
             };
 
-            if let Some(next_tx_reference) =
-                self.tx_pool.get_next_eligible_tx(current_account_state)?
-            {
-                self.tx_queue.insert(next_tx_reference.clone());
+            let Some(next_tx_reference) =
+                self.tx_pool.get_next_eligible_tx(current_account_state)?.cloned()
+            else {
+                continue;
+            };
+            if self.is_target_class_available(&next_tx_reference) {
+                self.enqueue(next_tx_reference);
             }
         }
 
@@ -198,10 +872,172 @@ impl Mempool {
 
         // Maybe close nonce gap.
         if self.tx_queue.get_nonce(address).is_none() {
-            if let Some(tx_reference) = self.tx_pool.get_by_address_and_nonce(address, nonce) {
-                self.tx_queue.insert(tx_reference.clone());
+            if let Some(tx_reference) =
+                self.tx_pool.get_by_address_and_nonce(address, nonce).cloned()
+            {
+                if self.is_target_class_available(&tx_reference) {
+                    self.enqueue(tx_reference);
+                }
+            }
+        }
+    }
+
+    // The current eviction limits of this mempool, if transactions are evicted.
+    fn eviction_config(&self) -> Option<EvictionConfig> {
+        let n_shards = self.config.n_shards;
+        self.eviction_config.as_ref().map(|eviction_config| {
+            let eviction_config = eviction_config.get();
+            EvictionConfig {
+                max_n_txs: eviction_config.max_n_txs.div_ceil(n_shards),
+                max_n_bytes: eviction_config.max_n_bytes.div_ceil(n_shards),
+                ..eviction_config
+            }
+        })
+    }
+
+    // Evicts the lowest priority transactions until the given transaction fits the configured
+    // capacity. Only transactions of lower priority than the added one are evicted, each with the
+    // transactions of its account with higher nonces; the transactions of its sender are kept.
+    // Fails, without evicting, if there is no room for the transaction.
+    fn make_room_for(&mut self, tx: &Transaction) -> MempoolResult<()> {
+        let Some(EvictionConfig { max_n_txs, max_n_bytes, .. }) = self.eviction_config() else {
+            return Ok(());
+        };
+        let tx_reference = TransactionReference::new(tx);
+        let added_tx_size = tx_size(tx);
+        let is_full = |n_txs: usize, n_bytes: usize| {
+            n_txs >= max_n_txs || n_bytes.saturating_add(added_tx_size) > max_n_bytes
+        };
+        let (mut n_txs, mut n_bytes) = (self.tx_pool.n_txs(), self.tx_pool.n_bytes());
+        if !is_full(n_txs, n_bytes) {
+            return Ok(());
+        }
+
+        // Only the transaction with the highest nonce of an account can be evicted alone, so it is
+        // the candidate of its account; once evicted, the transaction preceding it is.
+        let added_tx_priority = self.priority(&tx_reference);
+        let mut candidates: BinaryHeap<Reverse<(PriorityKey, ContractAddress, Nonce)>> = self
+            .tx_pool
+            .iter_highest_nonce_txs()
+            .filter(|tx| tx.sender_address != tx_reference.sender_address)
+            .map(|tx| Reverse((self.priority_key(tx), tx.sender_address, tx.nonce)))
+            .collect();
+        let mut evicted_txs = Vec::new();
+        while is_full(n_txs, n_bytes) {
+            let Some(Reverse((_, address, nonce))) = candidates
+                .pop()
+                .filter(|Reverse((priority_key, ..))| priority_key.priority < added_tx_priority)
+            else {
+                return Err(MempoolError::MempoolFull { tx_hash: tx_reference.tx_hash });
+            };
+            let evicted_tx = self
+                .tx_pool
+                .get_by_address_and_nonce(address, nonce)
+                .expect("Eviction candidates should appear in the pool.");
+            n_txs -= 1;
+            n_bytes -= tx_size(self.tx_pool.get_by_tx_hash(evicted_tx.tx_hash)?);
+            evicted_txs.push((address, nonce));
+            if let Some(previous_tx) = self.tx_pool.get_previous_tx(address, nonce) {
+                candidates.push(Reverse((
+                    self.priority_key(previous_tx),
+                    address,
+                    previous_tx.nonce,
+                )));
             }
         }
+
+        for &(address, nonce) in &evicted_txs {
+            self.evict_from_nonce(address, nonce, "capacity");
+        }
+        debug!(
+            "Evicted {} transactions to make room for {}.",
+            evicted_txs.len(),
+            tx_reference.tx_hash
+        );
+        record_evicted_txs(evicted_txs.len(), "capacity");
+        Ok(())
+    }
+
+    // Evicts the transactions of the given account from the given nonce on, for the given reason.
+    // Returns the number of evicted transactions.
+    fn evict_from_nonce(&mut self, address: ContractAddress, nonce: Nonce, reason: &str) -> usize {
+        if self.tx_queue.get_nonce(address).is_some_and(|queued_nonce| queued_nonce >= nonce) {
+            self.tx_queue.remove(address);
+        }
+        let evicted_tx_hashes = self.tx_pool.remove_from_nonce(address, nonce);
+        if !self.tx_pool.contains_account(address) {
+            self.account_nonces.remove(&address);
+        }
+        self.record_audit_events(
+            evicted_tx_hashes
+                .iter()
+                .map(|&tx_hash| AuditEvent::Evicted { tx_hash, reason: reason.to_owned() }),
+        );
+        evicted_tx_hashes.len()
+    }
+
+    fn record_audit_events(&self, events: impl IntoIterator<Item = AuditEvent>) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(events);
+        }
+    }
+
+    /// The minimal tip of a transaction replacing a transaction with the given tip.
+    fn min_replacement_tip(&self, replaced_tip: Tip) -> Tip {
+        let min_tip_bump_percentage = self.config.replacement_policy.min_tip_bump_percentage;
+        let min_tip = u128::from(replaced_tip.0) * (100 + u128::from(min_tip_bump_percentage));
+        let min_tip = u64::try_from(min_tip.div_ceil(100)).unwrap_or(u64::MAX);
+        Tip(min_tip.max(replaced_tip.0.saturating_add(1)))
+    }
+
+    fn enqueue(&mut self, tx_reference: TransactionReference) {
+        // Released once it cools down.
+        if self.cooling_down_txs.contains_key(&tx_reference.tx_hash) {
+            return;
+        }
+        let priority_key = self.priority_key(&tx_reference);
+        self.tx_queue.insert(tx_reference, priority_key);
+    }
+
+    fn priority(&self, tx_reference: &TransactionReference) -> u128 {
+        match &self.priority_policy {
+            Some(priority_policy) => priority_policy.priority(tx_reference),
+            None => TipPriority.priority(tx_reference),
+        }
+    }
+
+    fn priority_key(&self, tx_reference: &TransactionReference) -> PriorityKey {
+        let priority = self.priority(tx_reference);
+        let earlier_arrival = match self.config.priority_tie_breaking {
+            PriorityTieBreaking::TxHash => None,
+            PriorityTieBreaking::ArrivalTime => Some(Reverse(
+                self.tx_pool
+                    .get_arrival_time(tx_reference.tx_hash)
+                    .expect("Transactions considered for the queue must appear in the pool."),
+            )),
+        };
+        PriorityKey { priority, earlier_arrival, tx_hash: tx_reference.tx_hash }
+    }
+
+    // Transactions held back by this check remain in the pool, and are reconsidered once
+    // transactions are requested, or on the next alignment of their account state.
+    fn is_target_class_available(&mut self, tx_reference: &TransactionReference) -> bool {
+        if !self.config.enable_class_availability_check {
+            return true;
+        }
+        let Some(class_availability_checker) = &self.class_availability_checker else {
+            return true;
+        };
+
+        let tx = self
+            .tx_pool
+            .get_by_tx_hash(tx_reference.tx_hash)
+            .expect("Transactions considered for the queue must appear in the pool.");
+        let is_available = class_availability_checker.is_target_class_available(tx);
+        if !is_available {
+            self.class_pending_txs.insert(tx_reference.tx_hash, tx_reference.sender_address);
+        }
+        is_available
     }
 
     #[cfg(test)]
@@ -210,6 +1046,104 @@ impl Mempool {
     }
 }
 
+pub(crate) fn current_timestamp() -> BlockTimestamp {
+    BlockTimestamp(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The system time should be after the UNIX epoch.")
+            .as_secs(),
+    )
+}
+
+/// Opens the audit log of the given config, if enabled.
+pub(crate) fn open_audit_log(config: &MempoolConfig) -> Option<Arc<MempoolAuditLog>> {
+    config.audit_log.as_ref().map(|audit_log_config| {
+        Arc::new(
+            MempoolAuditLog::open(audit_log_config).expect("Failed to open the mempool audit log."),
+        )
+    })
+}
+
+fn record_evicted_txs(n_evicted_txs: usize, reason: &'static str) {
+    let n_evicted_txs =
+        u64::try_from(n_evicted_txs).expect("Number of transactions should fit u64.");
+    metrics::counter!(EVICTED_TXS, n_evicted_txs, EVICTION_REASON_LABEL => reason);
+}
+
+/// The outcome of `Mempool::take_next_tx_within`.
+#[derive(Debug)]
+pub(crate) enum BudgetedTake {
+    Taken(Transaction),
+    // The transaction of the highest priority doesn't fit in the remaining budget.
+    Skipped,
+    // The budget is exhausted, or no transaction is eligible.
+    Done,
+}
+
+// Transactions held until they become eligible at a timestamp.
+#[derive(Debug, Default)]
+struct ScheduledTransactions {
+    txs: HashMap<TransactionHash, MempoolInput>,
+    // The scheduled transactions, by the timestamp they become eligible at.
+    release_times: BTreeMap<BlockTimestamp, Vec<TransactionReference>>,
+    // The account and nonce of each scheduled transaction.
+    account_nonces: HashSet<(ContractAddress, Nonce)>,
+}
+
+impl ScheduledTransactions {
+    fn insert(
+        &mut self,
+        eligible_at: BlockTimestamp,
+        tx_reference: TransactionReference,
+        input: MempoolInput,
+    ) {
+        self.txs.insert(tx_reference.tx_hash, input);
+        self.account_nonces.insert((tx_reference.sender_address, tx_reference.nonce));
+        self.release_times.entry(eligible_at).or_default().push(tx_reference);
+    }
+
+    // Removes the transactions that are eligible at `timestamp`, in the order they became
+    // eligible.
+    fn pop_eligible(&mut self, timestamp: BlockTimestamp) -> Vec<MempoolInput> {
+        let not_yet_eligible_txs =
+            self.release_times.split_off(&BlockTimestamp(timestamp.0.saturating_add(1)));
+        let eligible_txs = std::mem::replace(&mut self.release_times, not_yet_eligible_txs);
+        eligible_txs
+            .into_values()
+            .flatten()
+            .filter_map(|tx_reference| self.remove(&tx_reference))
+            .collect()
+    }
+
+    // Removes the transactions matching the predicate. Returns their hashes.
+    fn remove_where(
+        &mut self,
+        predicate: impl Fn(&TransactionReference) -> bool,
+    ) -> Vec<TransactionHash> {
+        let mut removed_txs = Vec::new();
+        for tx_references in self.release_times.values_mut() {
+            tx_references.retain(|tx_reference| {
+                let is_removed = predicate(tx_reference);
+                if is_removed {
+                    removed_txs.push(tx_reference.clone());
+                }
+                !is_removed
+            });
+        }
+        self.release_times.retain(|_, tx_references| !tx_references.is_empty());
+        for tx_reference in &removed_txs {
+            self.remove(tx_reference);
+        }
+        removed_txs.into_iter().map(|tx_reference| tx_reference.tx_hash).collect()
+    }
+
+    // Removes a transaction whose reference was already taken out of the release times.
+    fn remove(&mut self, tx_reference: &TransactionReference) -> Option<MempoolInput> {
+        self.account_nonces.remove(&(tx_reference.sender_address, tx_reference.nonce));
+        self.txs.remove(&tx_reference.tx_hash)
+    }
+}
+
 /// Provides a lightweight representation of a transaction for mempool usage (e.g., excluding
 /// execution fields).
 /// TODO(Mohammad): rename this struct to `ThinTransaction` once that name
@@ -241,4 +1175,13 @@ impl TransactionReference {
     pub fn get_l2_gas_price(&self) -> u128 {
         self.resource_bounds.get_l2_bounds().max_price_per_unit
     }
+
+    /// An upper bound of the gas the transaction consumes: the max amount of L2 gas it is bounded
+    /// by, or of L1 gas for transactions bounding L1 gas only.
+    pub fn gas_estimate(&self) -> u64 {
+        match &self.resource_bounds {
+            ValidResourceBounds::L1Gas(l1_bounds) => l1_bounds.max_amount,
+            ValidResourceBounds::AllResources(all_bounds) => all_bounds.l2_gas.max_amount,
+        }
+    }
 }
@@ -1,5 +1,8 @@
 use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use assert_matches::assert_matches;
 use mempool_test_utils::starknet_api_test_utils::{
@@ -8,17 +11,49 @@ use mempool_test_utils::starknet_api_test_utils::{
 };
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
+use starknet_api::block::BlockTimestamp;
 use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::hash::StarkHash;
-use starknet_api::transaction::{Tip, TransactionHash, ValidResourceBounds};
+use starknet_api::transaction::{
+    AllResourceBounds,
+    ResourceBounds,
+    Tip,
+    TransactionHash,
+    ValidResourceBounds,
+};
 use starknet_api::{contract_address, felt, patricia_key};
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::{Account, AccountState};
+use starknet_mempool_types::mempool_types::{
+    Account,
+    AccountState,
+    EvictionReason,
+    RejectedTransaction,
+    RejectionReason,
+    TxBudget,
+};
 use starknet_types_core::felt::Felt;
 
-use crate::mempool::{AccountToNonce, Mempool, MempoolInput, TransactionReference};
-use crate::transaction_pool::TransactionPool;
+use crate::account_state::AccountNonceReader;
+use crate::audit_log::{read_audit_log, AuditEvent};
+use crate::class_availability::ClassAvailabilityChecker;
+use crate::config::{
+    AuditLogConfig,
+    EvictionConfig,
+    MempoolConfig,
+    PriorityTieBreaking,
+    ReinsertionPolicy,
+    ReplacementPolicy,
+};
+use crate::mempool::{
+    current_timestamp,
+    AccountToNonce,
+    Mempool,
+    MempoolInput,
+    TransactionReference,
+};
+use crate::priority::{MempoolPriorityPolicy, PriorityKey, TipPriority};
+use crate::transaction_pool::{tx_size, TransactionPool};
 use crate::transaction_queue::TransactionQueue;
 
 // Utils.
@@ -101,11 +136,23 @@ impl From<MempoolContent> for Mempool {
     fn from(mempool_content: MempoolContent) -> Mempool {
         let MempoolContent { tx_pool, tx_queue, account_nonces } = mempool_content;
         Mempool {
+            config: Default::default(),
+            class_availability_checker: None,
+            priority_policy: None,
+            eviction_config: None,
+            audit_log: None,
             tx_pool: tx_pool.unwrap_or_default(),
             tx_queue: tx_queue.unwrap_or_default(),
             // TODO: Add implementation when needed.
             mempool_state: Default::default(),
             account_nonces: account_nonces.unwrap_or_default(),
+            account_states: Default::default(),
+            block_start_nonces: Default::default(),
+            scheduled_txs: Default::default(),
+            taken_tx_arrival_times: Default::default(),
+            return_counts: Default::default(),
+            cooling_down_txs: Default::default(),
+            class_pending_txs: Default::default(),
         }
     }
 }
@@ -124,7 +171,12 @@ impl FromIterator<TransactionReference> for TransactionQueue {
     fn from_iter<T: IntoIterator<Item = TransactionReference>>(txs: T) -> Self {
         let mut queue = Self::default();
         for tx in txs {
-            queue.insert(tx);
+            let priority_key = PriorityKey {
+                priority: TipPriority.priority(&tx),
+                earlier_arrival: None,
+                tx_hash: tx.tx_hash,
+            };
+            queue.insert(tx, priority_key);
         }
         queue
     }
@@ -168,7 +220,7 @@ macro_rules! add_tx_input {
             Nonce(felt!($tx_nonce)),
             $resource_bounds,
         );
-        MempoolInput { tx, account }
+        MempoolInput { tx, account, eligible_at: None }
     }};
     (tip: $tip:expr, tx_hash: $tx_hash:expr, sender_address: $sender_address:expr,
         tx_nonce: $tx_nonce:expr, account_nonce: $account_nonce:expr) => {{
@@ -197,6 +249,14 @@ macro_rules! add_tx_input {
     };
 }
 
+fn bounded_l2_gas(max_amount: u64) -> ValidResourceBounds {
+    let all_bounds = test_resource_bounds_mapping();
+    ValidResourceBounds::AllResources(AllResourceBounds {
+        l2_gas: ResourceBounds { max_amount, ..all_bounds.l2_gas },
+        ..all_bounds
+    })
+}
+
 // Fixtures.
 
 #[fixture]
@@ -426,6 +486,63 @@ fn test_get_txs_while_increasing_gas_price_threshold() {
     assert!(txs.is_empty());
 }
 
+// get_txs_by_budget tests.
+
+#[rstest]
+fn test_get_txs_by_budget_skips_txs_exceeding_gas_budget(mut mempool: Mempool) {
+    // Setup.
+    let input_gas_100 = add_tx_input!(tip: 30, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(100));
+    let input_gas_1000 = add_tx_input!(tip: 20, tx_hash: 2, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(1000));
+    let input_gas_50 = add_tx_input!(tip: 10, tx_hash: 3, sender_address: "0x2", tx_nonce: 0_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(50));
+    for input in [&input_gas_100, &input_gas_1000, &input_gas_50] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let budget = TxBudget { max_gas: 200, max_bytes: usize::MAX };
+    let txs = mempool.get_txs_by_budget(budget).unwrap();
+
+    // Assert: the transaction exceeding the remaining budget is skipped, and left in the mempool.
+    assert_eq!(txs, [input_gas_100.tx, input_gas_50.tx]);
+    assert_eq!(mempool.get_txs(3).unwrap(), [input_gas_1000.tx]);
+}
+
+#[rstest]
+fn test_get_txs_by_budget_takes_next_nonces_within_budget(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tip: 10, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(100));
+    let input_nonce_1 = add_tx_input!(tip: 10, tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(100));
+    let input_nonce_2 = add_tx_input!(tip: 10, tx_hash: 3, sender_address: "0x0", tx_nonce: 2_u8, account_nonce: 0_u8, resource_bounds: bounded_l2_gas(100));
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let budget = TxBudget { max_gas: 250, max_bytes: usize::MAX };
+    let txs = mempool.get_txs_by_budget(budget).unwrap();
+
+    // Assert.
+    assert_eq!(txs, [input_nonce_0.tx, input_nonce_1.tx]);
+    assert_eq!(mempool.get_txs(3).unwrap(), [input_nonce_2.tx]);
+}
+
+#[rstest]
+fn test_get_txs_by_budget_bounds_size(mut mempool: Mempool) {
+    // Setup.
+    let input_tip_20 = add_tx_input!(tip: 20, tx_hash: 1, sender_address: "0x0");
+    let input_tip_10 = add_tx_input!(tip: 10, tx_hash: 2, sender_address: "0x1");
+    for input in [&input_tip_20, &input_tip_10] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let max_bytes = tx_size(&input_tip_20.tx) + tx_size(&input_tip_10.tx) - 1;
+    let txs = mempool.get_txs_by_budget(TxBudget { max_gas: u64::MAX, max_bytes }).unwrap();
+
+    // Assert.
+    assert_eq!(txs, [input_tip_20.tx]);
+}
+
 // add_tx tests.
 
 #[rstest]
@@ -671,6 +788,744 @@ fn test_add_tx_filling_hole(mut mempool: Mempool) {
     expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
 }
 
+#[derive(Debug)]
+struct NoClassAvailable;
+
+impl ClassAvailabilityChecker for NoClassAvailable {
+    fn is_target_class_available(&self, _tx: &Transaction) -> bool {
+        false
+    }
+}
+
+#[rstest]
+#[case::check_enabled(true, false)]
+#[case::check_disabled(false, true)]
+fn test_add_tx_class_availability_check(
+    #[case] enable_class_availability_check: bool,
+    #[case] expect_queued: bool,
+) {
+    // Setup.
+    let config = MempoolConfig { enable_class_availability_check, ..Default::default() };
+    let mut mempool = Mempool::new(config, Some(Arc::new(NoClassAvailable)), None);
+    let input = add_tx_input!(tx_nonce: 0_u8, account_nonce: 0_u8);
+
+    // Test.
+    add_tx(&mut mempool, &input);
+
+    // Assert: the transaction is always kept in the pool, but queued only if its class is
+    // available or the check is disabled.
+    let expected_queue_txs: Vec<TransactionReference> =
+        if expect_queued { vec![TransactionReference::new(&input.tx)] } else { vec![] };
+    let expected_mempool_content =
+        MempoolContent::with_pool_and_queue([input.tx], expected_queue_txs);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+// Reports the classes as available once they are declared, as the class manager does once its
+// lookups finish.
+#[derive(Debug, Default)]
+struct DeclaredClasses {
+    is_declared: AtomicBool,
+}
+
+impl ClassAvailabilityChecker for DeclaredClasses {
+    fn is_target_class_available(&self, _tx: &Transaction) -> bool {
+        self.is_declared.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn test_get_txs_releases_txs_whose_class_became_available() {
+    // Setup: the transaction is held back, as its class is not available.
+    let class_availability_checker = Arc::new(DeclaredClasses::default());
+    let config = MempoolConfig { enable_class_availability_check: true, ..Default::default() };
+    let mut mempool = Mempool::new(config, Some(class_availability_checker.clone()), None);
+    let input = add_tx_input!(tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &input);
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![]);
+
+    // Test: the class becomes available.
+    class_availability_checker.is_declared.store(true, Ordering::SeqCst);
+
+    // Assert: the transaction is released, though the state of its account didn't change.
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![input.tx]);
+}
+
+#[rstest]
+#[case::within_window(5_u8, true)]
+#[case::beyond_window(6_u8, false)]
+fn test_add_tx_nonce_lookahead(#[case] tx_nonce: u8, #[case] expect_accepted: bool) {
+    // Setup.
+    let config = MempoolConfig { max_nonce_lookahead: Some(3), ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    let input =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: tx_nonce, account_nonce: 2_u8);
+
+    // Test and assert.
+    if expect_accepted {
+        add_tx(&mut mempool, &input);
+    } else {
+        add_tx_expect_error(
+            &mut mempool,
+            &input,
+            MempoolError::NonceTooFarInFuture {
+                address: contract_address!("0x0"),
+                nonce: Nonce(felt!(tx_nonce)),
+                max_allowed_nonce: Nonce(felt!(5_u8)),
+            },
+        );
+    }
+}
+
+#[rstest]
+#[case::eligible_tx(0_u8, true)]
+#[case::pending_tx(3_u8, false)]
+fn test_add_tx_pending_txs_limit(#[case] tx_nonce: u8, #[case] expect_accepted: bool) {
+    // Setup: the account already holds the maximal number of pending transactions.
+    let config = MempoolConfig { max_pending_txs_per_account: Some(2), ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    for (tx_hash, pending_nonce) in [(1_u8, 1_u8), (2, 2)] {
+        let pending_input = add_tx_input!(
+            tx_hash: tx_hash, sender_address: "0x0", tx_nonce: pending_nonce, account_nonce: 0_u8
+        );
+        add_tx(&mut mempool, &pending_input);
+    }
+    let input =
+        add_tx_input!(tx_hash: 3, sender_address: "0x0", tx_nonce: tx_nonce, account_nonce: 0_u8);
+
+    // Test and assert: only a transaction that fills the nonce gap is accepted.
+    if expect_accepted {
+        add_tx(&mut mempool, &input);
+        assert_eq!(mempool.get_txs(3).unwrap().len(), 3);
+    } else {
+        add_tx_expect_error(
+            &mut mempool,
+            &input,
+            MempoolError::TooManyPendingTransactions {
+                address: contract_address!("0x0"),
+                max_pending_txs: 2,
+            },
+        );
+    }
+}
+
+#[rstest]
+fn test_pending_txs_limit_is_per_account() {
+    // Setup.
+    let config = MempoolConfig { max_pending_txs_per_account: Some(1), ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    let pending_input =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &pending_input);
+
+    // Test and assert.
+    let other_account_pending_input =
+        add_tx_input!(tx_hash: 2, sender_address: "0x1", tx_nonce: 1_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &other_account_pending_input);
+}
+
+// Replacement tests.
+
+fn mempool_with_replacement() -> Mempool {
+    let config = MempoolConfig {
+        replacement_policy: ReplacementPolicy {
+            enable_replacement: true,
+            min_tip_bump_percentage: 10,
+        },
+        ..Default::default()
+    };
+    Mempool::new(config, None, None)
+}
+
+#[rstest]
+#[case::queued_tx(0_u8)]
+#[case::pending_tx(1_u8)]
+fn test_add_tx_replaces_tx_with_bumped_tip(#[case] tx_nonce: u8) {
+    // Setup.
+    let mut mempool = mempool_with_replacement();
+    let replaced_input = add_tx_input!(tip: 100, tx_hash: 1, sender_address: "0x0",
+        tx_nonce: tx_nonce, account_nonce: 0_u8);
+    add_tx(&mut mempool, &replaced_input);
+
+    // Test.
+    let input = add_tx_input!(tip: 110, tx_hash: 2, sender_address: "0x0", tx_nonce: tx_nonce,
+        account_nonce: 0_u8);
+    add_tx(&mut mempool, &input);
+
+    // Assert: the replacing transaction takes the place of the replaced one.
+    let expected_queue_txs: Vec<TransactionReference> = match tx_nonce {
+        0 => vec![TransactionReference::new(&input.tx)],
+        _ => vec![],
+    };
+    let expected_mempool_content =
+        MempoolContent::with_pool_and_queue([input.tx.clone()], expected_queue_txs);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+#[case::insufficient_bump(109, 110)]
+#[case::equal_tip(100, 110)]
+fn test_add_tx_replacement_fails_on_insufficient_tip_bump(#[case] tip: u64, #[case] min_tip: u64) {
+    // Setup.
+    let mut mempool = mempool_with_replacement();
+    let replaced_input = add_tx_input!(tip: 100, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &replaced_input);
+
+    // Test and assert: the replaced transaction is kept.
+    let input = add_tx_input!(tip: tip, tx_hash: 2, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::ReplacementTipTooLow {
+            address: contract_address!("0x0"),
+            nonce: Nonce(felt!(0_u8)),
+            tip: Tip(tip),
+            min_tip: Tip(min_tip),
+        },
+    );
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [replaced_input.tx.clone()],
+        [TransactionReference::new(&replaced_input.tx)],
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_with_same_nonce_fails_if_replacement_disabled(mut mempool: Mempool) {
+    // Setup.
+    let replaced_input = add_tx_input!(tip: 100, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &replaced_input);
+
+    // Test and assert.
+    let input = add_tx_input!(tip: 200, tx_hash: 2, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x0"),
+            nonce: Nonce(felt!(0_u8)),
+        },
+    );
+}
+
+// Priority tests.
+
+#[derive(Debug)]
+struct LowestTipFirst;
+
+impl MempoolPriorityPolicy for LowestTipFirst {
+    fn priority(&self, tx: &TransactionReference) -> u128 {
+        u128::MAX - u128::from(tx.tip.0)
+    }
+}
+
+#[rstest]
+fn test_get_txs_by_custom_priority_policy() {
+    // Setup.
+    let mut mempool = Mempool::new(MempoolConfig::default(), None, Some(Arc::new(LowestTipFirst)));
+    let input_tip_20 = add_tx_input!(tip: 20, tx_hash: 1, sender_address: "0x0");
+    let input_tip_10 = add_tx_input!(tip: 10, tx_hash: 2, sender_address: "0x1");
+    let input_tip_30 = add_tx_input!(tip: 30, tx_hash: 3, sender_address: "0x2");
+    for input in [&input_tip_20, &input_tip_10, &input_tip_30] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test and assert.
+    assert_eq!(mempool.get_txs(3).unwrap(), [input_tip_10.tx, input_tip_20.tx, input_tip_30.tx]);
+}
+
+#[rstest]
+#[case::by_tx_hash(PriorityTieBreaking::TxHash, false)]
+#[case::by_arrival_time(PriorityTieBreaking::ArrivalTime, true)]
+fn test_get_txs_breaks_priority_ties(
+    #[case] priority_tie_breaking: PriorityTieBreaking,
+    #[case] expect_earlier_arrival_first: bool,
+) {
+    // Setup: transactions of equal tips, where the earlier arrival has the lower hash.
+    let config = MempoolConfig { priority_tie_breaking, ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    let earlier_input = add_tx_input!(tip: 5, tx_hash: 1, sender_address: "0x0");
+    let later_input = add_tx_input!(tip: 5, tx_hash: 2, sender_address: "0x1");
+    add_tx(&mut mempool, &earlier_input);
+    std::thread::sleep(Duration::from_millis(1));
+    add_tx(&mut mempool, &later_input);
+
+    // Test and assert.
+    let expected_txs = if expect_earlier_arrival_first {
+        [earlier_input.tx, later_input.tx]
+    } else {
+        [later_input.tx, earlier_input.tx]
+    };
+    assert_eq!(mempool.get_txs(2).unwrap(), expected_txs);
+}
+
+#[rstest]
+fn test_returned_tx_keeps_its_arrival_time() {
+    // Setup.
+    let config = MempoolConfig {
+        priority_tie_breaking: PriorityTieBreaking::ArrivalTime,
+        ..Default::default()
+    };
+    let mut mempool = Mempool::new(config, None, None);
+    let earlier_input = add_tx_input!(tip: 5, tx_hash: 1, sender_address: "0x0");
+    let later_input = add_tx_input!(tip: 5, tx_hash: 2, sender_address: "0x1");
+    add_tx(&mut mempool, &earlier_input);
+    std::thread::sleep(Duration::from_millis(1));
+    add_tx(&mut mempool, &later_input);
+    let taken_txs = mempool.get_txs(1).unwrap();
+
+    // Test.
+    mempool.return_txs(taken_txs).unwrap();
+
+    // Assert: the returned transaction still precedes the later arrival.
+    assert_eq!(mempool.get_txs(2).unwrap(), [earlier_input.tx, later_input.tx]);
+}
+
+// Scheduled transactions tests.
+
+#[rstest]
+fn test_scheduled_tx_is_eligible_only_after_its_timestamp(mut mempool: Mempool) {
+    // Setup.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8)
+    };
+    add_tx(&mut mempool, &input);
+
+    // Test and assert: the transaction is held back until its timestamp.
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![]);
+    mempool.release_scheduled_txs(BlockTimestamp(eligible_at.0 - 1));
+    assert_eq!(mempool.tx_pool().n_txs(), 0);
+
+    // Test and assert: the transaction is eligible once its timestamp passes.
+    mempool.release_scheduled_txs(eligible_at);
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [input.tx.clone()],
+        [TransactionReference::new(&input.tx)],
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_scheduled_tx_with_past_timestamp_is_added_immediately(mut mempool: Mempool) {
+    let input = MempoolInput { eligible_at: Some(BlockTimestamp(0)), ..add_tx_input!() };
+
+    add_tx(&mut mempool, &input);
+
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![input.tx]);
+}
+
+#[rstest]
+fn test_invalid_scheduled_tx_is_dropped_on_release(mut mempool: Mempool) {
+    // Setup: a scheduled transaction, whose hash is taken by another transaction meanwhile.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let scheduled_input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    let input =
+        add_tx_input!(tx_hash: 1, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &scheduled_input);
+    add_tx(&mut mempool, &input);
+
+    // Test.
+    mempool.release_scheduled_txs(eligible_at);
+
+    // Assert.
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [input.tx.clone()],
+        [TransactionReference::new(&input.tx)],
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_duplicate_scheduled_tx_is_rejected(mut mempool: Mempool) {
+    // Setup.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    add_tx(&mut mempool, &input);
+
+    // Test and assert: the same transaction, or another of the same nonce, is a duplicate.
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::DuplicateTransaction { tx_hash: input.tx.tx_hash() },
+    );
+    let same_nonce_input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    add_tx_expect_error(
+        &mut mempool,
+        &same_nonce_input,
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x0"),
+            nonce: Nonce(felt!(1_u8)),
+        },
+    );
+}
+
+#[test]
+fn test_scheduled_txs_limit() {
+    // Setup: the mempool already holds the maximal number of scheduled transactions.
+    let config = MempoolConfig { max_scheduled_txs: 1, ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let scheduled_input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    add_tx(&mut mempool, &scheduled_input);
+
+    // Test and assert.
+    let input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 2, sender_address: "0x1", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::TooManyScheduledTransactions { max_scheduled_txs: 1 },
+    );
+}
+
+// evict_txs tests.
+
+#[rstest]
+#[case::lowest_tip_first(1, 1)]
+#[case::all_non_eligible(10, 3)]
+fn test_evict_txs(#[case] n_txs: usize, #[case] expected_n_evicted_txs: usize) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tip: 10, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8,
+        account_nonce: 0_u8);
+    let input_nonce_1 = add_tx_input!(tip: 10, tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8,
+        account_nonce: 0_u8);
+    let input_nonce_2 = add_tx_input!(tip: 5, tx_hash: 3, sender_address: "0x0", tx_nonce: 2_u8,
+        account_nonce: 0_u8);
+    // A transaction held back by a nonce gap.
+    let input_with_gap = add_tx_input!(tip: 20, tx_hash: 4, sender_address: "0x1", tx_nonce: 1_u8,
+        account_nonce: 0_u8);
+    let mut mempool = Mempool::empty();
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_2, &input_with_gap] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    assert_eq!(mempool.evict_txs(n_txs), Ok(expected_n_evicted_txs));
+
+    // Assert: eligible transactions are never evicted.
+    let expected_pool_txs = match expected_n_evicted_txs {
+        1 => vec![input_nonce_0.tx.clone(), input_nonce_1.tx, input_with_gap.tx],
+        _ => vec![input_nonce_0.tx.clone()],
+    };
+    let expected_queue_txs = [TransactionReference::new(&input_nonce_0.tx)];
+    let expected_mempool_content =
+        MempoolContent::with_pool_and_queue(expected_pool_txs, expected_queue_txs);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+// TTL and capacity eviction tests.
+
+const TX_TTL: Duration = Duration::from_secs(60);
+
+fn mempool_with_eviction(max_n_txs: usize, max_n_bytes: usize) -> Mempool {
+    let eviction_config =
+        EvictionConfig { tx_ttl: TX_TTL, max_n_txs, max_n_bytes, ..Default::default() };
+    Mempool::new(
+        MempoolConfig { eviction: Some(eviction_config), ..Default::default() },
+        None,
+        None,
+    )
+}
+
+#[rstest]
+fn test_evict_expired_txs() {
+    // Setup.
+    let mut mempool = mempool_with_eviction(10, usize::MAX);
+    let input_nonce_0 =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    let input_nonce_1 =
+        add_tx_input!(tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test and assert: transactions are kept until they expire.
+    assert_eq!(mempool.evict_expired_txs(Instant::now()), 0);
+    assert_eq!(mempool.evict_expired_txs(Instant::now() + TX_TTL + Duration::from_secs(1)), 2);
+    MempoolContent::with_pool_and_queue([], []).assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_full_mempool_evicts_lowest_priority_tx() {
+    // Setup.
+    let mut mempool = mempool_with_eviction(2, usize::MAX);
+    let low_tip_input = add_tx_input!(tip: 10, tx_hash: 1, sender_address: "0x0");
+    let high_tip_input = add_tx_input!(tip: 20, tx_hash: 2, sender_address: "0x1");
+    for input in [&low_tip_input, &high_tip_input] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test and assert: a transaction of lower priority than all held ones is rejected.
+    let lowest_tip_input = add_tx_input!(tip: 5, tx_hash: 3, sender_address: "0x2");
+    add_tx_expect_error(
+        &mut mempool,
+        &lowest_tip_input,
+        MempoolError::MempoolFull { tx_hash: lowest_tip_input.tx.tx_hash() },
+    );
+
+    // Test and assert: a transaction of higher priority evicts the lowest priority one.
+    let highest_tip_input = add_tx_input!(tip: 30, tx_hash: 4, sender_address: "0x3");
+    add_tx(&mut mempool, &highest_tip_input);
+    let expected_txs = [&high_tip_input, &highest_tip_input].map(|input| input.tx.clone());
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        expected_txs.clone(),
+        expected_txs.each_ref().map(TransactionReference::new),
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_full_mempool_evicts_highest_nonce_of_account_first() {
+    // Setup: the transaction of the lowest priority precedes another transaction of its account.
+    let mut mempool = mempool_with_eviction(2, usize::MAX);
+    let input_nonce_0 = add_tx_input!(tip: 1, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8,
+        account_nonce: 0_u8);
+    let input_nonce_1 = add_tx_input!(tip: 5, tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8,
+        account_nonce: 0_u8);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let input = add_tx_input!(tip: 10, tx_hash: 3, sender_address: "0x1");
+    add_tx(&mut mempool, &input);
+
+    // Assert: evicting the highest nonce transaction of the account leaves no nonce gap.
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [input_nonce_0.tx.clone(), input.tx.clone()],
+        [&input_nonce_0.tx, &input.tx].map(TransactionReference::new),
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_tx_larger_than_mempool_is_rejected() {
+    let mut mempool = mempool_with_eviction(10, 1);
+    let input = add_tx_input!();
+
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::MempoolFull { tx_hash: input.tx.tx_hash() },
+    );
+}
+
+// drop_account_txs tests.
+
+#[rstest]
+fn test_drop_account_txs(mut mempool: Mempool) {
+    // Setup: an account with an eligible, a pending and a scheduled transaction, and another
+    // account.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let queued_input =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    let pending_input =
+        add_tx_input!(tx_hash: 2, sender_address: "0x0", tx_nonce: 2_u8, account_nonce: 0_u8);
+    let scheduled_input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 3, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8)
+    };
+    let other_input =
+        add_tx_input!(tx_hash: 4, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8);
+    for input in [&queued_input, &pending_input, &scheduled_input, &other_input] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let mut dropped_tx_hashes =
+        mempool.drop_account_txs(contract_address!("0x0"), EvictionReason::Denylisted);
+
+    // Assert: all the transactions of the account are dropped, including the scheduled one.
+    dropped_tx_hashes.sort();
+    assert_eq!(
+        dropped_tx_hashes,
+        [&queued_input, &pending_input, &scheduled_input].map(|input| input.tx.tx_hash())
+    );
+    mempool.release_scheduled_txs(eligible_at);
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [other_input.tx.clone()],
+        [TransactionReference::new(&other_input.tx)],
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+// flush tests.
+
+#[rstest]
+fn test_flush(mut mempool: Mempool) {
+    // Setup: an eligible, a pending and a scheduled transaction, of two accounts.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let queued_input =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    let pending_input =
+        add_tx_input!(tx_hash: 2, sender_address: "0x0", tx_nonce: 2_u8, account_nonce: 0_u8);
+    let scheduled_input = MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: 3, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8)
+    };
+    for input in [&queued_input, &pending_input, &scheduled_input] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let mut dropped_tx_hashes = mempool.flush();
+
+    // Assert: all the transactions are dropped, including the scheduled one.
+    dropped_tx_hashes.sort();
+    assert_eq!(
+        dropped_tx_hashes,
+        [&queued_input, &pending_input, &scheduled_input].map(|input| input.tx.tx_hash())
+    );
+    mempool.release_scheduled_txs(eligible_at);
+    let expected_mempool_content = MempoolContent::with_pool_and_queue([], []);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+// return_txs tests.
+
+#[rstest]
+fn test_return_txs() {
+    // Setup.
+    let tx_address0_nonce0 = add_tx_input!(tip: 10, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8).tx;
+    let tx_address0_nonce1 = add_tx_input!(tip: 10, tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8).tx;
+    let tx_address0_nonce2 = add_tx_input!(tip: 10, tx_hash: 3, sender_address: "0x0", tx_nonce: 2_u8, account_nonce: 0_u8).tx;
+    let tx_address1_nonce0 = add_tx_input!(tip: 5, tx_hash: 4, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8).tx;
+
+    let queue_txs = [&tx_address0_nonce0, &tx_address1_nonce0].map(TransactionReference::new);
+    let pool_txs =
+        [&tx_address0_nonce0, &tx_address0_nonce1, &tx_address0_nonce2, &tx_address1_nonce0]
+            .map(|tx| tx.clone());
+    let mut mempool: Mempool = MempoolContent::with_pool_and_queue(pool_txs, queue_txs).into();
+    assert_eq!(mempool.get_txs(4).unwrap().len(), 4);
+
+    // Test: only the first transaction of account 0x0 was added to the block.
+    let returned_txs =
+        vec![tx_address0_nonce1.clone(), tx_address0_nonce2.clone(), tx_address1_nonce0.clone()];
+    mempool.return_txs(returned_txs).unwrap();
+
+    // Assert: the returned transactions are eligible again.
+    let expected_queue_txs =
+        [&tx_address0_nonce1, &tx_address1_nonce0].map(TransactionReference::new);
+    let expected_pool_txs =
+        [tx_address0_nonce1.clone(), tx_address0_nonce2, tx_address1_nonce0.clone()];
+    let expected_mempool_content =
+        MempoolContent::with_pool_and_queue(expected_pool_txs, expected_queue_txs);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+
+    let state_changes =
+        HashMap::from([(contract_address!("0x0"), AccountState { nonce: Nonce(felt!(0_u16)) })]);
+    assert!(mempool.commit_block(state_changes).is_ok());
+    assert_eq!(mempool.get_txs(2).unwrap(), &[tx_address0_nonce1, tx_address1_nonce0]);
+}
+
+#[rstest]
+fn test_returned_tx_is_eligible_once_cooled_down() {
+    // Setup.
+    let return_cooldown = Duration::from_secs(60);
+    let config = MempoolConfig {
+        reinsertion_policy: ReinsertionPolicy { return_cooldown, max_returns: None },
+        ..Default::default()
+    };
+    let mut mempool = Mempool::new(config, None, None);
+    let input = add_tx_input!(tx_nonce: 0_u8, account_nonce: 0_u8);
+    add_tx(&mut mempool, &input);
+    let taken_txs = mempool.get_txs(1).unwrap();
+
+    // Test.
+    mempool.return_txs(taken_txs).unwrap();
+
+    // Assert: the returned transaction is held back until it cools down.
+    assert!(mempool.get_txs(1).unwrap().is_empty());
+    mempool.release_returned_txs(Instant::now() + return_cooldown);
+    let expected_mempool_content =
+        MempoolContent::with_queue([TransactionReference::new(&input.tx)]);
+    expected_mempool_content.assert_eq_transaction_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_tx_returned_too_many_times_is_evicted() {
+    // Setup.
+    let config = MempoolConfig {
+        reinsertion_policy: ReinsertionPolicy {
+            return_cooldown: Duration::ZERO,
+            max_returns: Some(1),
+        },
+        ..Default::default()
+    };
+    let mut mempool = Mempool::new(config, None, None);
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, tx_nonce: 0_u8, account_nonce: 0_u8);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, tx_nonce: 1_u8, account_nonce: 0_u8);
+    let input_nonce_2 = add_tx_input!(tx_hash: 3, tx_nonce: 2_u8, account_nonce: 0_u8);
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test: the transaction with nonce 1 is returned twice, while the one with nonce 0 is kept for
+    // the block.
+    assert_eq!(mempool.get_txs(2).unwrap(), [input_nonce_0.tx, input_nonce_1.tx.clone()]);
+    mempool.return_txs(vec![input_nonce_1.tx.clone()]).unwrap();
+    assert_eq!(mempool.get_txs(1).unwrap(), [input_nonce_1.tx.clone()]);
+    mempool.return_txs(vec![input_nonce_1.tx]).unwrap();
+
+    // Assert: the transaction is evicted, with the following one.
+    let expected_mempool_content = MempoolContent::with_pool_and_queue([], []);
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+// reject_txs tests.
+
+#[rstest]
+fn test_reject_txs_evicts_later_nonces() {
+    // Setup.
+    let tx_address0_nonce0 =
+        add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8).tx;
+    let tx_address0_nonce1 =
+        add_tx_input!(tx_hash: 2, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8).tx;
+    let tx_address0_nonce2 =
+        add_tx_input!(tx_hash: 3, sender_address: "0x0", tx_nonce: 2_u8, account_nonce: 0_u8).tx;
+    let tx_address1_nonce0 =
+        add_tx_input!(tx_hash: 4, sender_address: "0x1", tx_nonce: 0_u8, account_nonce: 0_u8).tx;
+
+    let queue_txs = [&tx_address0_nonce0, &tx_address1_nonce0].map(TransactionReference::new);
+    let pool_txs =
+        [&tx_address0_nonce0, &tx_address0_nonce1, &tx_address0_nonce2, &tx_address1_nonce0]
+            .map(|tx| tx.clone());
+    let mut mempool: Mempool = MempoolContent::with_pool_and_queue(pool_txs, queue_txs).into();
+
+    // Test.
+    let rejected_tx = RejectedTransaction {
+        tx_hash: tx_address0_nonce1.tx_hash(),
+        address: tx_address0_nonce1.contract_address(),
+        nonce: tx_address0_nonce1.nonce(),
+        reason: RejectionReason::InsufficientBalance,
+    };
+    let n_evicted_txs = mempool.reject_txs(&[rejected_tx]);
+
+    // Assert: the rejected transaction and the later one of its account are evicted.
+    assert_eq!(n_evicted_txs, 2);
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [tx_address0_nonce0.clone(), tx_address1_nonce0.clone()],
+        [&tx_address0_nonce0, &tx_address1_nonce0].map(TransactionReference::new),
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
 // commit_block tests.
 
 #[rstest]
@@ -769,6 +1624,129 @@ fn test_commit_block_from_different_leader() {
     expected_mempool_content.assert_eq_transaction_queue_content(&mempool);
 }
 
+#[rstest]
+fn test_add_tx_with_committed_nonce_fails_after_commit_block(mut mempool: Mempool) {
+    // Setup: a block committing nonce 3, while the gateway still reads account nonce 2.
+    let state_changes =
+        HashMap::from([(contract_address!("0x0"), AccountState { nonce: Nonce(felt!(3_u8)) })]);
+    assert!(mempool.commit_block(state_changes).is_ok());
+    let input_committed_nonce = add_tx_input!(tx_hash: 1, tx_nonce: 3_u8, account_nonce: 2_u8);
+    let input_next_nonce = add_tx_input!(tx_hash: 2, tx_nonce: 4_u8, account_nonce: 2_u8);
+
+    // Test and assert: the committed nonce is rejected, and the next nonce is eligible at once.
+    add_tx_expect_error(
+        &mut mempool,
+        &input_committed_nonce,
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x0"),
+            nonce: Nonce(felt!(3_u8)),
+        },
+    );
+    add_tx(&mut mempool, &input_next_nonce);
+    let expected_mempool_content =
+        MempoolContent::with_queue([TransactionReference::new(&input_next_nonce.tx)]);
+    expected_mempool_content.assert_eq_transaction_queue_content(&mempool);
+}
+
+#[rstest]
+fn test_commit_block_drops_committed_scheduled_txs(mut mempool: Mempool) {
+    // Setup.
+    let eligible_at = BlockTimestamp(current_timestamp().0 + 3600);
+    let scheduled_input = |tx_hash: u8, tx_nonce: u8| MempoolInput {
+        eligible_at: Some(eligible_at),
+        ..add_tx_input!(tx_hash: tx_hash, tx_nonce: tx_nonce, account_nonce: 0_u8)
+    };
+    let input_committed_nonce = scheduled_input(1, 1);
+    let input_next_nonce = scheduled_input(2, 2);
+    for input in [&input_committed_nonce, &input_next_nonce] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let state_changes =
+        HashMap::from([(contract_address!("0x0"), AccountState { nonce: Nonce(felt!(1_u8)) })]);
+    assert!(mempool.commit_block(state_changes).is_ok());
+
+    // Assert: only the transaction with the next nonce is left to be released.
+    assert_eq!(mempool.scheduled_txs.pop_eligible(eligible_at), vec![input_next_nonce]);
+}
+
+#[rstest]
+fn test_return_txs_drops_txs_committed_meanwhile(mut mempool: Mempool) {
+    // Setup: transactions taken for a proposal, while another proposer commits the first one.
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, tx_nonce: 0_u8, account_nonce: 0_u8);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, tx_nonce: 1_u8, account_nonce: 0_u8);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+    let txs = mempool.get_txs(2).unwrap();
+    let state_changes =
+        HashMap::from([(contract_address!("0x0"), AccountState { nonce: Nonce(felt!(0_u8)) })]);
+    assert!(mempool.commit_block(state_changes).is_ok());
+
+    // Test.
+    assert!(mempool.return_txs(txs).is_ok());
+
+    // Assert: only the transaction that wasn't committed is eligible again.
+    let expected_mempool_content = MempoolContent::with_pool_and_queue(
+        [input_nonce_1.tx.clone()],
+        [TransactionReference::new(&input_nonce_1.tx)],
+    );
+    expected_mempool_content.assert_eq_pool_and_queue_content(&mempool);
+}
+
+#[derive(Debug)]
+struct CommittedNonce(Nonce);
+
+impl AccountNonceReader for CommittedNonce {
+    fn get_nonce(&self, _address: ContractAddress) -> Option<Nonce> {
+        Some(self.0)
+    }
+}
+
+#[rstest]
+fn test_add_tx_reads_committed_nonce_of_cold_account() {
+    // Setup: an account whose nonce 2 was committed before the mempool started.
+    let mut mempool = Mempool::new(MempoolConfig::default(), None, None)
+        .with_account_nonce_reader(Arc::new(CommittedNonce(Nonce(felt!(3_u8)))));
+    let input = add_tx_input!(tx_nonce: 2_u8, account_nonce: 0_u8);
+
+    // Test and assert.
+    add_tx_expect_error(
+        &mut mempool,
+        &input,
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x0"),
+            nonce: Nonce(felt!(2_u8)),
+        },
+    );
+}
+
+#[rstest]
+fn test_least_recently_committed_accounts_are_forgotten() {
+    // Setup.
+    let config = MempoolConfig { max_tracked_accounts: 1, ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    for address in [contract_address!("0x0"), contract_address!("0x1")] {
+        let state_changes = HashMap::from([(address, AccountState { nonce: Nonce(felt!(1_u8)) })]);
+        assert!(mempool.commit_block(state_changes).is_ok());
+    }
+
+    // Test and assert: the stale account state of the forgotten account is trusted.
+    add_tx(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 1, sender_address: "0x0", tx_nonce: 1_u8, account_nonce: 0_u8),
+    );
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 2, sender_address: "0x1", tx_nonce: 1_u8, account_nonce: 0_u8),
+        MempoolError::DuplicateNonce {
+            address: contract_address!("0x1"),
+            nonce: Nonce(felt!(1_u8)),
+        },
+    );
+}
+
 // account_nonces tests.
 
 #[rstest]
@@ -1037,3 +2015,42 @@ fn test_tx_pool_capacity(mut mempool: Mempool) {
     mempool.get_txs(2).unwrap();
     assert_eq!(mempool.tx_pool().n_txs(), 0);
 }
+
+#[test]
+fn test_audit_log_records_tx_events() {
+    // Setup.
+    let dir = tempfile::tempdir().unwrap();
+    let audit_log_config =
+        AuditLogConfig { path: dir.path().join("audit_log.jsonl"), ..Default::default() };
+    let config = MempoolConfig { audit_log: Some(audit_log_config.clone()), ..Default::default() };
+    let mut mempool = Mempool::new(config, None, None);
+    let input = add_tx_input!(tip: 5, tx_hash: 1, sender_address: "0x0", tx_nonce: 0_u8, account_nonce: 0_u8);
+    let tx_hash = input.tx.tx_hash();
+
+    // Test.
+    add_tx(&mut mempool, &input);
+    let duplicate_error = MempoolError::DuplicateTransaction { tx_hash };
+    add_tx_expect_error(&mut mempool, &input, duplicate_error.clone());
+    mempool.get_txs(1).unwrap();
+
+    // Assert.
+    let events: Vec<AuditEvent> = read_audit_log(&audit_log_config.path)
+        .unwrap()
+        .into_iter()
+        .map(|record| record.event)
+        .collect();
+    let (sender_address, nonce) = (contract_address!("0x0"), Nonce(felt!(0_u8)));
+    assert_eq!(
+        events,
+        [
+            AuditEvent::Added { tx_hash, sender_address, nonce, tip: Tip(5) },
+            AuditEvent::Rejected {
+                tx_hash,
+                sender_address,
+                nonce,
+                reason: duplicate_error.to_string()
+            },
+            AuditEvent::Selected { tx_hash },
+        ]
+    );
+}
@@ -0,0 +1,32 @@
+use std::cmp::Reverse;
+use std::fmt::Debug;
+use std::time::Instant;
+
+use starknet_api::transaction::TransactionHash;
+
+use crate::mempool::TransactionReference;
+
+/// Scores the transactions eligible for sequencing: transactions with higher scores are returned
+/// first by `get_txs`. Transactions with equal scores are ordered by the configured tie-breaking.
+pub trait MempoolPriorityPolicy: Debug + Send + Sync {
+    fn priority(&self, tx: &TransactionReference) -> u128;
+}
+
+/// The default policy: the higher the tip, the higher the priority.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TipPriority;
+
+impl MempoolPriorityPolicy for TipPriority {
+    fn priority(&self, tx: &TransactionReference) -> u128 {
+        tx.tip.0.into()
+    }
+}
+
+/// The order of a transaction in the queue: the higher the key, the earlier it is sequenced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PriorityKey {
+    pub priority: u128,
+    // Set only if ties are broken by arrival time, so that earlier arrivals come first.
+    pub earlier_arrival: Option<Reverse<Instant>>,
+    pub tx_hash: TransactionHash,
+}
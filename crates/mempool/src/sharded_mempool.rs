@@ -0,0 +1,365 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::time::{Duration, Instant};
+
+use starknet_api::core::ContractAddress;
+use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_types::mempool_types::{
+    AccountState,
+    DropAccountTxsArgs,
+    MempoolInput,
+    MempoolResult,
+    RejectedTransaction,
+    TxBudget,
+};
+
+use crate::account_state::AccountNonceReader;
+use crate::class_availability::ClassAvailabilityChecker;
+use crate::config::{EvictionConfig, MempoolConfig};
+use crate::mempool::{current_timestamp, open_audit_log, BudgetedTake, Mempool};
+use crate::priority::MempoolPriorityPolicy;
+
+#[cfg(test)]
+#[path = "sharded_mempool_test.rs"]
+pub mod sharded_mempool_test;
+
+/// A mempool partitioned by sender address into independently locked shards, so that transactions
+/// of different shards are added concurrently. Since all the state of an account lives in a single
+/// shard, each shard is a complete mempool of its accounts; reads that span accounts, e.g.,
+/// `get_txs`, merge the shards.
+pub struct ShardedMempool {
+    shards: Vec<Mutex<Mempool>>,
+    sweep_interval: Option<Duration>,
+    eviction_config: Option<SharedDynamicConfig<EvictionConfig>>,
+    class_availability_checker: Option<Arc<dyn ClassAvailabilityChecker>>,
+}
+
+impl ShardedMempool {
+    /// Expects a validated config. The capacity limits are split evenly between the shards.
+    pub fn new(
+        config: MempoolConfig,
+        class_availability_checker: Option<Arc<dyn ClassAvailabilityChecker>>,
+        priority_policy: Option<Arc<dyn MempoolPriorityPolicy>>,
+    ) -> Self {
+        let eviction_config = config.eviction.map(SharedDynamicConfig::new);
+        let audit_log = open_audit_log(&config);
+        let shards = (0..config.n_shards)
+            .map(|_| {
+                Mutex::new(Mempool::new_shard(
+                    config.clone(),
+                    eviction_config.clone(),
+                    audit_log.clone(),
+                    class_availability_checker.clone(),
+                    priority_policy.clone(),
+                ))
+            })
+            .collect();
+        let sweep_interval = config.eviction.map(|eviction_config| eviction_config.sweep_interval);
+        ShardedMempool { shards, sweep_interval, eviction_config, class_availability_checker }
+    }
+
+    /// Looks up the committed nonces of accounts unknown to their shards with the given reader. See
+    /// `Mempool::with_account_nonce_reader`.
+    pub fn with_account_nonce_reader(mut self, reader: Arc<dyn AccountNonceReader>) -> Self {
+        self.shards = self
+            .shards
+            .into_iter()
+            .map(|shard| {
+                let shard = shard.into_inner().expect("Mempool shard lock should not be poisoned.");
+                Mutex::new(shard.with_account_nonce_reader(reader.clone()))
+            })
+            .collect();
+        self
+    }
+
+    /// The interval between sweeps of the expired transactions, if they are evicted.
+    pub fn sweep_interval(&self) -> Option<Duration> {
+        self.sweep_interval
+    }
+
+    /// The class availability checker shared by all shards, if the classes of transactions are
+    /// checked.
+    pub fn class_availability_checker(&self) -> Option<&Arc<dyn ClassAvailabilityChecker>> {
+        self.class_availability_checker.as_ref()
+    }
+
+    /// The eviction config of all shards, if transactions are evicted. Its TTL and capacity limits
+    /// can be changed at runtime; the sweep interval is fixed once the mempool is started.
+    pub fn eviction_config(&self) -> Option<SharedDynamicConfig<EvictionConfig>> {
+        self.eviction_config.clone()
+    }
+
+    pub fn n_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The number of transactions held in all shards.
+    pub fn n_txs(&self) -> usize {
+        (0..self.n_shards()).map(|shard_index| self.lock_shard(shard_index).n_txs()).sum()
+    }
+
+    /// Adds a new transaction to the shard of its sender. Only blocks on additions to the same
+    /// shard.
+    pub fn add_tx(&self, input: MempoolInput) -> MempoolResult<()> {
+        self.lock_shard_of(input.tx.contract_address()).add_tx(input)
+    }
+
+    /// Retrieves up to `n_txs` transactions with the highest priority across all shards, in the
+    /// order a single mempool holding all transactions would.
+    pub fn get_txs(&self, n_txs: usize) -> MempoolResult<Vec<Transaction>> {
+        take_txs(&mut self.lock_all_shards(), n_txs, None)
+    }
+
+    /// Like `get_txs`, but returns within about `max_wait`, so that a busy mempool doesn't hold up
+    /// the block in creation: shards still held by other operations once it passes are skipped,
+    /// and the transactions taken by then are returned.
+    pub fn get_txs_within(
+        &self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolResult<Vec<Transaction>> {
+        let deadline = Instant::now() + max_wait;
+        take_txs(&mut self.lock_shards_until(deadline), n_txs, Some(deadline))
+    }
+
+    /// Retrieves the transactions with the highest priority across all shards that fit in the
+    /// budget, in the order a single mempool holding all transactions would. See
+    /// `Mempool::get_txs_by_budget`.
+    pub fn get_txs_by_budget(&self, mut budget: TxBudget) -> MempoolResult<Vec<Transaction>> {
+        let mut shards = self.lock_all_shards();
+        let (timestamp, now) = (current_timestamp(), Instant::now());
+        for shard in shards.iter_mut() {
+            shard.release_scheduled_txs(timestamp);
+            shard.release_returned_txs(now);
+            shard.release_class_pending_txs();
+        }
+
+        // Takes from the shard whose next eligible transaction has the highest priority, until no
+        // shard has a transaction within the budget.
+        let mut skipped_txs_per_shard = vec![Vec::new(); shards.len()];
+        let mut txs = Vec::new();
+        while let Some((_, shard_index)) = shards
+            .iter()
+            .enumerate()
+            .filter_map(|(shard_index, shard)| {
+                shard.iter_priority_keys().next().map(|priority_key| (priority_key, shard_index))
+            })
+            .max()
+        {
+            match shards[shard_index]
+                .take_next_tx_within(&mut budget, &mut skipped_txs_per_shard[shard_index])?
+            {
+                BudgetedTake::Taken(tx) => txs.push(tx),
+                BudgetedTake::Skipped => {}
+                BudgetedTake::Done => break,
+            }
+        }
+        for (shard, skipped_txs) in shards.iter_mut().zip(skipped_txs_per_shard) {
+            shard.requeue_skipped_txs(skipped_txs);
+        }
+
+        Ok(txs)
+    }
+
+    /// Returns transactions taken by `get_txs` that were not added to the block to the shards of
+    /// their senders. See `Mempool::return_txs`.
+    pub fn return_txs(&self, txs: Vec<Transaction>) -> MempoolResult<()> {
+        let mut txs_per_shard: HashMap<usize, Vec<Transaction>> = HashMap::new();
+        for tx in txs {
+            txs_per_shard.entry(self.shard_index(tx.contract_address())).or_default().push(tx);
+        }
+        for (shard_index, shard_txs) in txs_per_shard {
+            self.lock_shard(shard_index).return_txs(shard_txs)?;
+        }
+        Ok(())
+    }
+
+    /// Evicts the rejected transactions from the shards of their senders. See
+    /// `Mempool::reject_txs`.
+    pub fn reject_txs(&self, rejected_txs: Vec<RejectedTransaction>) -> usize {
+        let mut rejected_txs_per_shard: HashMap<usize, Vec<RejectedTransaction>> = HashMap::new();
+        for rejected_tx in rejected_txs {
+            rejected_txs_per_shard
+                .entry(self.shard_index(rejected_tx.address))
+                .or_default()
+                .push(rejected_tx);
+        }
+        rejected_txs_per_shard
+            .into_iter()
+            .map(|(shard_index, shard_rejected_txs)| {
+                self.lock_shard(shard_index).reject_txs(&shard_rejected_txs)
+            })
+            .sum()
+    }
+
+    /// Drops all the transactions of an account from its shard. See `Mempool::drop_account_txs`.
+    pub fn drop_account_txs(&self, args: DropAccountTxsArgs) -> Vec<TransactionHash> {
+        let DropAccountTxsArgs { address, reason } = args;
+        self.lock_shard_of(address).drop_account_txs(address, reason)
+    }
+
+    /// Drops all the transactions held in the mempool, shard by shard. See `Mempool::flush`.
+    pub fn flush(&self) -> Vec<TransactionHash> {
+        (0..self.n_shards()).flat_map(|shard_index| self.lock_shard(shard_index).flush()).collect()
+    }
+
+    /// Evicts up to `n_txs` transactions that are not eligible for sequencing across all shards, in
+    /// the order a single mempool holding all transactions would. See `Mempool::evict_txs`.
+    pub fn evict_txs(&self, n_txs: usize) -> MempoolResult<usize> {
+        let mut shards = self.lock_all_shards();
+        let mut n_evicted_txs = 0;
+        while n_evicted_txs < n_txs {
+            let mut candidates: Vec<_> = shards
+                .iter()
+                .enumerate()
+                .flat_map(|(shard_index, shard)| {
+                    shard.eviction_candidates().into_iter().map(move |tx| (tx, shard_index))
+                })
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+
+            candidates.sort_by_key(|(tx, _)| tx.tip);
+            candidates.truncate(n_txs - n_evicted_txs);
+            n_evicted_txs += candidates.len();
+            let mut candidates_per_shard = vec![Vec::new(); shards.len()];
+            for (tx, shard_index) in candidates {
+                candidates_per_shard[shard_index].push(tx);
+            }
+            for (shard, shard_candidates) in shards.iter_mut().zip(candidates_per_shard) {
+                shard.evict(shard_candidates)?;
+            }
+        }
+        Ok(n_evicted_txs)
+    }
+
+    /// Evicts the expired transactions, shard by shard. See `Mempool::evict_expired_txs`.
+    pub fn evict_expired_txs(&self) -> usize {
+        let now = Instant::now();
+        (0..self.n_shards())
+            .map(|shard_index| self.lock_shard(shard_index).evict_expired_txs(now))
+            .sum()
+    }
+
+    /// Updates all shards according to the committed block, each with the state changes of its
+    /// accounts. See `Mempool::commit_block`.
+    pub fn commit_block(
+        &self,
+        state_changes: HashMap<ContractAddress, AccountState>,
+    ) -> MempoolResult<()> {
+        let mut state_changes_per_shard = vec![HashMap::new(); self.n_shards()];
+        for (address, account_state) in state_changes {
+            state_changes_per_shard[self.shard_index(address)].insert(address, account_state);
+        }
+        // Shards without state changes still rewind the accounts not included in the block.
+        for (mut shard, shard_state_changes) in
+            self.lock_all_shards().into_iter().zip(state_changes_per_shard)
+        {
+            shard.commit_block(shard_state_changes)?;
+        }
+        Ok(())
+    }
+
+    fn shard_index(&self, address: ContractAddress) -> usize {
+        let mut hasher = DefaultHasher::new();
+        address.hash(&mut hasher);
+        let n_shards = u64::try_from(self.n_shards()).expect("Number of shards should fit in u64.");
+        usize::try_from(hasher.finish() % n_shards).expect("Shard index should fit in usize.")
+    }
+
+    fn lock_shard(&self, shard_index: usize) -> MutexGuard<'_, Mempool> {
+        self.shards[shard_index].lock().expect("Mempool shard lock should not be poisoned.")
+    }
+
+    fn lock_shard_of(&self, address: ContractAddress) -> MutexGuard<'_, Mempool> {
+        self.lock_shard(self.shard_index(address))
+    }
+
+    // Shards are always locked in index order, to avoid deadlocks between cross-shard operations.
+    fn lock_all_shards(&self) -> Vec<MutexGuard<'_, Mempool>> {
+        (0..self.n_shards()).map(|shard_index| self.lock_shard(shard_index)).collect()
+    }
+
+    // Locks the shards in index order like `lock_all_shards`, skipping the shards still held by
+    // other operations once the deadline passes.
+    fn lock_shards_until(&self, deadline: Instant) -> Vec<MutexGuard<'_, Mempool>> {
+        self.shards
+            .iter()
+            .filter_map(|shard| loop {
+                match shard.try_lock() {
+                    Ok(shard) => return Some(shard),
+                    Err(TryLockError::WouldBlock) if Instant::now() >= deadline => return None,
+                    Err(TryLockError::WouldBlock) => std::thread::yield_now(),
+                    Err(TryLockError::Poisoned(_)) => {
+                        panic!("Mempool shard lock should not be poisoned.")
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+// Takes up to `n_txs` transactions with the highest priority across the given shards. Once the
+// deadline, if any, passes, no further rounds are taken and the transactions taken so far are
+// returned.
+fn take_txs(
+    shards: &mut [MutexGuard<'_, Mempool>],
+    n_txs: usize,
+    deadline: Option<Instant>,
+) -> MempoolResult<Vec<Transaction>> {
+    let mut txs = Vec::with_capacity(n_txs);
+    let (timestamp, now) = (current_timestamp(), Instant::now());
+    for shard in shards.iter_mut() {
+        shard.release_scheduled_txs(timestamp);
+        shard.release_returned_txs(now);
+        shard.release_class_pending_txs();
+    }
+
+    // Each round takes the highest priority eligible transactions of all shards; transactions
+    // that become eligible by it are considered in the next round.
+    while txs.len() < n_txs {
+        if !txs.is_empty() && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+        }
+        let n_remaining_txs = n_txs - txs.len();
+        let mut candidates: Vec<_> = shards
+            .iter()
+            .enumerate()
+            .flat_map(|(shard_index, shard)| {
+                shard
+                    .iter_priority_keys()
+                    .take(n_remaining_txs)
+                    .map(move |priority_key| (Reverse(priority_key), shard_index))
+            })
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_unstable();
+        candidates.truncate(n_remaining_txs);
+
+        let mut n_txs_per_shard = vec![0; shards.len()];
+        for &(_, shard_index) in &candidates {
+            n_txs_per_shard[shard_index] += 1;
+        }
+        let mut txs_per_shard = Vec::with_capacity(shards.len());
+        for (shard, n_shard_txs) in shards.iter_mut().zip(n_txs_per_shard) {
+            txs_per_shard.push(shard.get_txs(n_shard_txs)?.into_iter());
+        }
+        // Each shard returns its transactions in priority order, so the transactions of the
+        // round are taken in the order of the candidates.
+        txs.extend(candidates.into_iter().map(|(_, shard_index)| {
+            txs_per_shard[shard_index]
+                .next()
+                .expect("A shard returns as many transactions as it has candidates.")
+        }));
+    }
+
+    Ok(txs)
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mempool_test_utils::starknet_api_test_utils::{
+    create_executable_tx,
+    test_resource_bounds_mapping,
+    VALID_L2_GAS_MAX_AMOUNT,
+};
+use pretty_assertions::assert_eq;
+use rstest::{fixture, rstest};
+use starknet_api::core::{ContractAddress, Nonce, PatriciaKey};
+use starknet_api::transaction::{Tip, TransactionHash, ValidResourceBounds};
+use starknet_api::{contract_address, felt, patricia_key};
+use starknet_mempool_types::mempool_types::{Account, AccountState, MempoolInput, TxBudget};
+
+use crate::config::{EvictionConfig, MempoolConfig};
+use crate::mempool::Mempool;
+use crate::sharded_mempool::ShardedMempool;
+
+const N_SHARDS: usize = 4;
+const N_ACCOUNTS: u64 = 16;
+const N_TXS_PER_ACCOUNT: u64 = 3;
+
+// Utils.
+
+fn tx_input(account: u64, tx_nonce: u64, tip: u64) -> MempoolInput {
+    let sender_address = contract_address!(account);
+    let tx = create_executable_tx(
+        sender_address,
+        TransactionHash(felt!(account * 100 + tx_nonce)),
+        Tip(tip),
+        Nonce(felt!(tx_nonce)),
+        ValidResourceBounds::AllResources(test_resource_bounds_mapping()),
+    );
+    MempoolInput {
+        tx,
+        account: Account { sender_address, state: AccountState::default() },
+        eligible_at: None,
+    }
+}
+
+// Transactions of several accounts, with tips that interleave the accounts by priority.
+fn tx_inputs() -> Vec<MempoolInput> {
+    (0..N_ACCOUNTS)
+        .flat_map(|account| {
+            (0..N_TXS_PER_ACCOUNT)
+                .map(move |nonce| tx_input(account, nonce, (account * 7 + nonce * 5) % 11))
+        })
+        .collect()
+}
+
+// Fixtures.
+
+/// A sharded mempool and a single mempool holding the same transactions.
+#[fixture]
+fn mempools() -> (ShardedMempool, Mempool) {
+    let sharded_mempool =
+        ShardedMempool::new(MempoolConfig { n_shards: N_SHARDS, ..Default::default() }, None, None);
+    let mut mempool = Mempool::empty();
+    for input in tx_inputs() {
+        sharded_mempool.add_tx(input.clone()).unwrap();
+        mempool.add_tx(input).unwrap();
+    }
+    (sharded_mempool, mempool)
+}
+
+// Tests.
+
+#[rstest]
+#[case::fewer_than_eligible_txs(5)]
+#[case::all_eligible_txs(16)]
+#[case::more_than_eligible_txs(35)]
+#[case::more_than_all_txs(100)]
+fn get_txs_orders_like_single_mempool(mempools: (ShardedMempool, Mempool), #[case] n_txs: usize) {
+    let (sharded_mempool, mut mempool) = mempools;
+
+    assert_eq!(sharded_mempool.get_txs(n_txs).unwrap(), mempool.get_txs(n_txs).unwrap());
+    // The remaining transactions are also taken in the same order.
+    assert_eq!(sharded_mempool.get_txs(100).unwrap(), mempool.get_txs(100).unwrap());
+}
+
+#[rstest]
+#[case::fewer_than_eligible_txs(5)]
+#[case::more_than_eligible_txs(35)]
+#[case::more_than_all_txs(100)]
+fn get_txs_by_budget_orders_like_single_mempool(
+    mempools: (ShardedMempool, Mempool),
+    #[case] n_txs: u64,
+) {
+    let (sharded_mempool, mut mempool) = mempools;
+    // All transactions have the same gas estimate.
+    let budget = TxBudget { max_gas: n_txs * VALID_L2_GAS_MAX_AMOUNT, max_bytes: usize::MAX };
+
+    let txs = sharded_mempool.get_txs_by_budget(budget).unwrap();
+
+    assert_eq!(txs, mempool.get_txs_by_budget(budget).unwrap());
+    let n_all_txs = N_ACCOUNTS * N_TXS_PER_ACCOUNT;
+    assert_eq!(txs.len(), usize::try_from(n_txs.min(n_all_txs)).unwrap());
+}
+
+#[rstest]
+fn get_txs_within_orders_like_single_mempool(mempools: (ShardedMempool, Mempool)) {
+    let (sharded_mempool, mut mempool) = mempools;
+
+    assert_eq!(
+        sharded_mempool.get_txs_within(100, Duration::from_secs(1)).unwrap(),
+        mempool.get_txs(100).unwrap()
+    );
+}
+
+#[rstest]
+fn get_txs_within_skips_held_shards(mempools: (ShardedMempool, Mempool)) {
+    let (sharded_mempool, _) = mempools;
+    let held_shard_index = 0;
+    let held_shard = sharded_mempool.shards[held_shard_index].lock().unwrap();
+
+    let txs = sharded_mempool.get_txs_within(100, Duration::from_millis(10)).unwrap();
+
+    // The transactions of the other shards are returned regardless.
+    assert!(!txs.is_empty());
+    assert!(txs
+        .iter()
+        .all(|tx| sharded_mempool.shard_index(tx.contract_address()) != held_shard_index));
+    drop(held_shard);
+    // Waiting for the held shard takes the whole time box, so the transactions that became
+    // eligible meanwhile in the other shards are left as well.
+    let remaining_txs = sharded_mempool.get_txs(100).unwrap();
+    assert!(remaining_txs
+        .iter()
+        .any(|tx| sharded_mempool.shard_index(tx.contract_address()) == held_shard_index));
+    assert_eq!(
+        txs.len() + remaining_txs.len(),
+        usize::try_from(N_ACCOUNTS * N_TXS_PER_ACCOUNT).unwrap()
+    );
+}
+
+#[rstest]
+fn commit_block_updates_all_shards(mempools: (ShardedMempool, Mempool)) {
+    let (sharded_mempool, mut mempool) = mempools;
+    let n_txs = 32;
+    let taken_txs = sharded_mempool.get_txs(n_txs).unwrap();
+    assert_eq!(taken_txs, mempool.get_txs(n_txs).unwrap());
+
+    // Only some of the taken transactions are included in the block.
+    let state_changes: HashMap<ContractAddress, AccountState> = taken_txs
+        .iter()
+        .step_by(2)
+        .map(|tx| (tx.contract_address(), AccountState { nonce: tx.nonce() }))
+        .collect();
+    sharded_mempool.commit_block(state_changes.clone()).unwrap();
+    mempool.commit_block(state_changes).unwrap();
+
+    assert_eq!(sharded_mempool.get_txs(100).unwrap(), mempool.get_txs(100).unwrap());
+}
+
+#[rstest]
+fn return_txs_routes_to_sender_shards(mempools: (ShardedMempool, Mempool)) {
+    let (sharded_mempool, _) = mempools;
+    let taken_txs = sharded_mempool.get_txs(16).unwrap();
+
+    sharded_mempool.return_txs(taken_txs.clone()).unwrap();
+
+    assert_eq!(sharded_mempool.get_txs(16).unwrap(), taken_txs);
+}
+
+#[test]
+fn add_tx_concurrently() {
+    let sharded_mempool = Arc::new(ShardedMempool::new(
+        MempoolConfig { n_shards: N_SHARDS, ..Default::default() },
+        None,
+        None,
+    ));
+    let handles: Vec<_> = (0..N_ACCOUNTS)
+        .map(|account| {
+            let sharded_mempool = sharded_mempool.clone();
+            std::thread::spawn(move || {
+                for nonce in 0..N_TXS_PER_ACCOUNT {
+                    sharded_mempool.add_tx(tx_input(account, nonce, 0)).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let n_txs = usize::try_from(N_ACCOUNTS * N_TXS_PER_ACCOUNT).unwrap();
+    assert_eq!(sharded_mempool.get_txs(100).unwrap().len(), n_txs);
+}
+
+#[test]
+fn evict_txs_evicts_like_single_mempool() {
+    // Each account has a distinct tip, so that the evicted transactions don't depend on the order
+    // the accounts are visited in.
+    let sharded_mempool =
+        ShardedMempool::new(MempoolConfig { n_shards: N_SHARDS, ..Default::default() }, None, None);
+    let mut mempool = Mempool::empty();
+    for account in 0..N_ACCOUNTS {
+        for nonce in 0..N_TXS_PER_ACCOUNT {
+            sharded_mempool.add_tx(tx_input(account, nonce, account)).unwrap();
+            mempool.add_tx(tx_input(account, nonce, account)).unwrap();
+        }
+    }
+
+    assert_eq!(sharded_mempool.evict_txs(20).unwrap(), mempool.evict_txs(20).unwrap());
+
+    assert_eq!(sharded_mempool.get_txs(100).unwrap(), mempool.get_txs(100).unwrap());
+}
+
+#[rstest]
+fn n_txs_counts_all_shards(mempools: (ShardedMempool, Mempool)) {
+    let (sharded_mempool, mut mempool) = mempools;
+    assert_eq!(sharded_mempool.n_txs(), mempool.n_txs());
+    assert_eq!(mempool.n_txs(), usize::try_from(N_ACCOUNTS * N_TXS_PER_ACCOUNT).unwrap());
+
+    sharded_mempool.get_txs(5).unwrap();
+    mempool.get_txs(5).unwrap();
+
+    assert_eq!(sharded_mempool.n_txs(), mempool.n_txs());
+}
+
+#[test]
+fn eviction_limits_change_at_runtime() {
+    let eviction_config = EvictionConfig { max_n_txs: 2, ..Default::default() };
+    let sharded_mempool = ShardedMempool::new(
+        MempoolConfig { eviction: Some(eviction_config), ..Default::default() },
+        None,
+        None,
+    );
+    let mut tx_inputs = tx_inputs().into_iter();
+    // Transactions that don't fit the mempool are rejected, or evict held ones.
+    for input in tx_inputs.by_ref().take(10) {
+        let _ = sharded_mempool.add_tx(input);
+    }
+    assert_eq!(sharded_mempool.n_txs(), 2);
+
+    sharded_mempool
+        .eviction_config()
+        .unwrap()
+        .set(EvictionConfig { max_n_txs: 4, ..eviction_config });
+    for input in tx_inputs {
+        let _ = sharded_mempool.add_tx(input);
+    }
+
+    assert_eq!(sharded_mempool.n_txs(), 4);
+}
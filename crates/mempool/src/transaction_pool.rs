@@ -1,4 +1,6 @@
 use std::collections::{hash_map, BTreeMap, HashMap};
+use std::ops::Bound;
+use std::time::Instant;
 
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
@@ -22,14 +24,29 @@ pub struct TransactionPool {
     txs_by_account: AccountTransactionIndex,
     // Tracks the capacity of the pool.
     capacity: PoolCapacity,
+    // The time each transaction arrived at the pool.
+    arrival_times: ArrivalTimes,
+    // The size of each transaction, computed once it is inserted.
+    tx_sizes: HashMap<TransactionHash, usize>,
 }
 
 impl TransactionPool {
     pub fn insert(&mut self, tx: Transaction) -> MempoolResult<()> {
+        self.insert_with_arrival_time(tx, Instant::now())
+    }
+
+    /// Inserts a transaction that arrived at the given time, e.g., a transaction that is returned
+    /// to the pool.
+    pub fn insert_with_arrival_time(
+        &mut self,
+        tx: Transaction,
+        arrival_time: Instant,
+    ) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
 
         // Insert to pool.
+        let tx_size = tx_size(&tx);
         if let hash_map::Entry::Vacant(entry) = self.tx_pool.entry(tx_hash) {
             entry.insert(tx);
         } else {
@@ -45,7 +62,9 @@ impl TransactionPool {
             )
         };
 
-        self.capacity.add();
+        self.capacity.add(tx_size);
+        self.arrival_times.0.insert(tx_hash, arrival_time);
+        self.tx_sizes.insert(tx_hash, tx_size);
 
         Ok(())
     }
@@ -63,7 +82,8 @@ impl TransactionPool {
             )
         });
 
-        self.capacity.remove();
+        self.remove_size(tx_hash);
+        self.arrival_times.0.remove(&tx_hash);
 
         Ok(tx)
     }
@@ -72,21 +92,94 @@ impl TransactionPool {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
         for TransactionReference { tx_hash, .. } in removed_txs {
-            self.tx_pool.remove(&tx_hash).unwrap_or_else(|| {
-                panic!(
-                    "Transaction pool consistency error: transaction with hash {tx_hash} appears \
-                     in account mapping, but does not appear in the main mapping"
-                );
-            });
-
-            self.capacity.remove();
+            self.remove_from_main_mapping(tx_hash);
+        }
+    }
+
+    /// Removes the transactions of the given account from the given nonce on, returning their
+    /// hashes.
+    pub fn remove_from_nonce(
+        &mut self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Vec<TransactionHash> {
+        let removed_txs = self.txs_by_account.remove_from_nonce(address, nonce);
+
+        removed_txs
+            .into_iter()
+            .map(|TransactionReference { tx_hash, .. }| {
+                self.remove_from_main_mapping(tx_hash);
+                tx_hash
+            })
+            .collect()
+    }
+
+    /// Removes all the transactions of the given account, returning their hashes.
+    pub fn remove_account_txs(&mut self, address: ContractAddress) -> Vec<TransactionHash> {
+        let removed_txs = self.txs_by_account.remove_account(address);
+
+        removed_txs
+            .into_iter()
+            .map(|TransactionReference { tx_hash, .. }| {
+                self.remove_from_main_mapping(tx_hash);
+                tx_hash
+            })
+            .collect()
+    }
+
+    // Removes a transaction that was already removed from the account mapping.
+    fn remove_from_main_mapping(&mut self, tx_hash: TransactionHash) {
+        if self.tx_pool.remove(&tx_hash).is_none() {
+            panic!(
+                "Transaction pool consistency error: transaction with hash {tx_hash} appears in \
+                 account mapping, but does not appear in the main mapping"
+            );
         }
+        self.remove_size(tx_hash);
+        self.arrival_times.0.remove(&tx_hash);
+    }
+
+    fn remove_size(&mut self, tx_hash: TransactionHash) {
+        let tx_size = self
+            .tx_sizes
+            .remove(&tx_hash)
+            .expect("Transactions in the pool should have a recorded size.");
+        self.capacity.remove(tx_size);
     }
 
-    pub fn _get_by_tx_hash(&self, tx_hash: TransactionHash) -> MempoolResult<&Transaction> {
+    pub fn get_by_tx_hash(&self, tx_hash: TransactionHash) -> MempoolResult<&Transaction> {
         self.tx_pool.get(&tx_hash).ok_or(MempoolError::TransactionNotFound { tx_hash })
     }
 
+    /// The size of the transaction, in its binary encoding.
+    pub fn get_size(&self, tx_hash: TransactionHash) -> MempoolResult<usize> {
+        self.tx_sizes.get(&tx_hash).copied().ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
+    pub fn get_arrival_time(&self, tx_hash: TransactionHash) -> MempoolResult<Instant> {
+        self.arrival_times
+            .0
+            .get(&tx_hash)
+            .copied()
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
+    /// Returns the transactions that arrived before the given time.
+    pub fn txs_arrived_before(&self, time: Instant) -> Vec<&TransactionReference> {
+        self.arrival_times
+            .0
+            .iter()
+            .filter(|(_, &arrival_time)| arrival_time < time)
+            .map(|(tx_hash, _)| {
+                let tx =
+                    self.tx_pool.get(tx_hash).expect("Timed transaction should be in the pool.");
+                self.txs_by_account
+                    .get(tx.contract_address(), tx.nonce())
+                    .expect("Transaction should appear in the account mapping.")
+            })
+            .collect()
+    }
+
     pub fn get_by_address_and_nonce(
         &self,
         address: ContractAddress,
@@ -105,11 +198,36 @@ impl TransactionPool {
         Ok(self.get_by_address_and_nonce(sender_address, next_nonce))
     }
 
-    #[cfg(test)]
+    /// Returns the number of transactions of the given account with nonces higher than the given
+    /// one.
+    pub fn n_txs_with_higher_nonce(&self, address: ContractAddress, nonce: Nonce) -> usize {
+        self.txs_by_account.n_txs_with_higher_nonce(address, nonce)
+    }
+
+    /// Returns the transaction of the given account with the highest nonce lower than the given
+    /// one.
+    pub fn get_previous_tx(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Option<&TransactionReference> {
+        self.txs_by_account.get_previous(address, nonce)
+    }
+
+    /// Returns the transaction with the highest nonce of each account in the pool.
+    pub fn iter_highest_nonce_txs(&self) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_account.iter_highest_nonce_txs()
+    }
+
     pub fn n_txs(&self) -> usize {
         self.capacity.n_txs
     }
 
+    /// The total size of the transactions in the pool, in their binary encoding.
+    pub fn n_bytes(&self) -> usize {
+        self.capacity.n_bytes
+    }
+
     pub fn contains_account(&self, address: ContractAddress) -> bool {
         self.txs_by_account.contains(address)
     }
@@ -162,24 +280,90 @@ impl AccountTransactionIndex {
         txs_with_lower_nonce.into_values().collect()
     }
 
+    fn remove_from_nonce(
+        &mut self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Vec<TransactionReference> {
+        let Some(account_txs) = self.0.get_mut(&address) else {
+            return Vec::default();
+        };
+
+        let txs_with_higher_or_equal_nonce = account_txs.split_off(&nonce);
+        if account_txs.is_empty() {
+            self.0.remove(&address);
+        }
+
+        txs_with_higher_or_equal_nonce.into_values().collect()
+    }
+
+    fn remove_account(&mut self, address: ContractAddress) -> Vec<TransactionReference> {
+        self.0
+            .remove(&address)
+            .map(|account_txs| account_txs.into_values().collect())
+            .unwrap_or_default()
+    }
+
+    fn get_previous(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Option<&TransactionReference> {
+        self.0.get(&address)?.range(..nonce).next_back().map(|(_, tx)| tx)
+    }
+
     fn contains(&self, address: ContractAddress) -> bool {
         self.0.contains_key(&address)
     }
+
+    fn n_txs_with_higher_nonce(&self, address: ContractAddress, nonce: Nonce) -> usize {
+        self.0.get(&address).map_or(0, |account_txs| {
+            account_txs.range((Bound::Excluded(nonce), Bound::Unbounded)).count()
+        })
+    }
+
+    fn iter_highest_nonce_txs(&self) -> impl Iterator<Item = &TransactionReference> {
+        self.0.values().filter_map(|account_txs| account_txs.values().next_back())
+    }
+}
+
+// Arrival times are not part of the content of the pool: pools holding the same transactions are
+// equal regardless of when the transactions arrived.
+#[derive(Debug, Default)]
+struct ArrivalTimes(HashMap<TransactionHash, Instant>);
+
+impl PartialEq for ArrivalTimes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
+impl Eq for ArrivalTimes {}
+
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct PoolCapacity {
     n_txs: usize,
-    // TODO(Ayelet): Add size tracking.
+    n_bytes: usize,
 }
 
 impl PoolCapacity {
-    fn add(&mut self) {
+    fn add(&mut self, tx_size: usize) {
         self.n_txs += 1;
+        self.n_bytes += tx_size;
     }
 
-    fn remove(&mut self) {
+    fn remove(&mut self, tx_size: usize) {
         self.n_txs =
             self.n_txs.checked_sub(1).expect("Underflow: Cannot subtract from an empty pool.");
+        self.n_bytes = self
+            .n_bytes
+            .checked_sub(tx_size)
+            .expect("Underflow: Cannot subtract more than the pool size.");
     }
 }
+
+/// The size of a transaction, in its binary encoding.
+pub fn tx_size(tx: &Transaction) -> usize {
+    let tx_size = bincode::serialized_size(tx).expect("Transactions should be serializable.");
+    usize::try_from(tx_size).expect("Transaction size should fit in usize.")
+}
@@ -11,6 +11,7 @@ use starknet_api::transaction::{
 };
 
 use crate::mempool::TransactionReference;
+use crate::priority::PriorityKey;
 
 // A queue holding the transaction that with nonces that match account nonces.
 // Note: the derived comparison functionality considers the order guaranteed by the data structures
@@ -18,30 +19,31 @@ use crate::mempool::TransactionReference;
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct TransactionQueue {
     gas_price_threshold: u128,
-    // Transactions with gas price above gas price threshold (sorted by tip).
+    // Transactions with gas price above gas price threshold (sorted by priority key).
     priority_queue: BTreeSet<PriorityTransaction>,
     // Transactions with gas price below gas price threshold (sorted by price).
     pending_queue: BTreeSet<PendingTransaction>,
     // Set of account addresses for efficient existence checks.
-    address_to_tx: HashMap<ContractAddress, TransactionReference>,
+    address_to_tx: HashMap<ContractAddress, QueuedTransaction>,
 }
 
 impl TransactionQueue {
     /// Adds a transaction to the mempool, ensuring unique keys.
     /// Panics: if given a duplicate tx.
-    pub fn insert(&mut self, tx_reference: TransactionReference) {
+    pub fn insert(&mut self, tx_reference: TransactionReference, priority_key: PriorityKey) {
+        let queued_tx = QueuedTransaction { tx: tx_reference, priority_key };
         assert_eq!(
-            self.address_to_tx.insert(tx_reference.sender_address, tx_reference.clone()),
+            self.address_to_tx.insert(queued_tx.sender_address, queued_tx.clone()),
             None,
             "Only a single transaction from the same contract class can be in the mempool at a \
              time."
         );
 
         let new_tx_successfully_inserted =
-            if tx_reference.get_l2_gas_price() < self.gas_price_threshold {
-                self.pending_queue.insert(tx_reference.into())
+            if queued_tx.get_l2_gas_price() < self.gas_price_threshold {
+                self.pending_queue.insert(queued_tx.into())
             } else {
-                self.priority_queue.insert(tx_reference.into())
+                self.priority_queue.insert(queued_tx.into())
             };
         assert!(
             new_tx_successfully_inserted,
@@ -52,7 +54,7 @@ impl TransactionQueue {
     // TODO(gilad): remove collect
     pub fn pop_ready_chunk(&mut self, n_txs: usize) -> Vec<TransactionReference> {
         let txs: Vec<TransactionReference> =
-            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.0)).collect();
+            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.0.tx)).collect();
         for tx in &txs {
             self.address_to_tx.remove(&tx.sender_address);
         }
@@ -63,7 +65,13 @@ impl TransactionQueue {
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter_over_ready_txs(&self) -> impl Iterator<Item = &TransactionReference> {
-        self.priority_queue.iter().rev().map(|tx| &tx.0)
+        self.priority_queue.iter().rev().map(|tx| &tx.tx)
+    }
+
+    /// Returns an iterator of the priority keys of the current eligible transactions, in
+    /// descending order.
+    pub fn iter_over_ready_priority_keys(&self) -> impl Iterator<Item = PriorityKey> + '_ {
+        self.priority_queue.iter().rev().map(|tx| tx.priority_key)
     }
 
     pub fn get_nonce(&self, address: ContractAddress) -> Option<Nonce> {
@@ -73,12 +81,12 @@ impl TransactionQueue {
     /// Removes the transaction of the given account address from the queue.
     /// This is well-defined, since there is at most one transaction per address in the queue.
     pub fn remove(&mut self, address: ContractAddress) -> bool {
-        let Some(tx_reference) = self.address_to_tx.remove(&address) else {
+        let Some(queued_tx) = self.address_to_tx.remove(&address) else {
             return false;
         };
 
-        self.priority_queue.remove(&tx_reference.clone().into())
-            || self.pending_queue.remove(&tx_reference.into())
+        self.priority_queue.remove(&queued_tx.clone().into())
+            || self.pending_queue.remove(&queued_tx.into())
     }
 
     pub fn has_ready_txs(&self) -> bool {
@@ -96,15 +104,22 @@ impl TransactionQueue {
     }
 
     fn _promote_txs_to_priority(&mut self, threshold: u128) {
-        let tmp_split_tx = PendingTransaction(TransactionReference {
-            resource_bounds: ValidResourceBounds::AllResources(AllResourceBounds {
-                l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: threshold },
-                ..Default::default()
-            }),
-            sender_address: ContractAddress::default(),
-            nonce: Nonce::default(),
-            tx_hash: TransactionHash::default(),
-            tip: Tip::default(),
+        let tmp_split_tx = PendingTransaction(QueuedTransaction {
+            tx: TransactionReference {
+                resource_bounds: ValidResourceBounds::AllResources(AllResourceBounds {
+                    l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: threshold },
+                    ..Default::default()
+                }),
+                sender_address: ContractAddress::default(),
+                nonce: Nonce::default(),
+                tx_hash: TransactionHash::default(),
+                tip: Tip::default(),
+            },
+            priority_key: PriorityKey {
+                priority: 0,
+                earlier_arrival: None,
+                tx_hash: TransactionHash::default(),
+            },
         });
 
         // Split off the pending queue at the given transaction higher than the threshold.
@@ -112,8 +127,8 @@ impl TransactionQueue {
 
         // Insert all transactions from the split point into the priority queue, skip
         // `tmp_split_tx`.
-        // Note: extend will reorder transactions by `Tip` during insertion, despite them being
-        // initially ordered by fee.
+        // Note: extend will reorder transactions by priority key during insertion, despite them
+        // being initially ordered by fee.
         self.priority_queue.extend(txs_over_threshold.map(|tx| tx.0.into()));
     }
 
@@ -134,9 +149,17 @@ impl TransactionQueue {
     }
 }
 
-/// Encapsulates a transaction reference to assess its order (i.e., gas price).
+/// A transaction in the queue, with the key it is prioritized by.
+#[derive(Clone, Debug, Eq, PartialEq, derive_more::Deref)]
+struct QueuedTransaction {
+    #[deref]
+    tx: TransactionReference,
+    priority_key: PriorityKey,
+}
+
+/// Encapsulates a queued transaction to assess its order (i.e., gas price).
 #[derive(Clone, Debug, derive_more::Deref, derive_more::From)]
-struct PendingTransaction(pub TransactionReference);
+struct PendingTransaction(pub QueuedTransaction);
 
 /// Compare transactions based only on their gas price, using the Eq trait. It ensures that
 /// two gas price are either exactly equal or not.
@@ -165,14 +188,15 @@ impl PartialOrd for PendingTransaction {
     }
 }
 
-/// This struct behaves similarly to `PendingTransaction`, encapsulating a transaction reference
-/// to assess its order (i.e., tip); see its documentation for more details.
+/// This struct behaves similarly to `PendingTransaction`, encapsulating a queued transaction to
+/// assess its order (i.e., priority key, which is unique per transaction); see its documentation
+/// for more details.
 #[derive(Clone, Debug, derive_more::Deref, derive_more::From)]
-struct PriorityTransaction(pub TransactionReference);
+struct PriorityTransaction(pub QueuedTransaction);
 
 impl PartialEq for PriorityTransaction {
     fn eq(&self, other: &PriorityTransaction) -> bool {
-        self.tip == other.tip && self.tx_hash == other.tx_hash
+        self.priority_key == other.priority_key
     }
 }
 
@@ -180,7 +204,7 @@ impl Eq for PriorityTransaction {}
 
 impl Ord for PriorityTransaction {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.tip.cmp(&other.tip).then_with(|| self.tx_hash.cmp(&other.tx_hash))
+        self.priority_key.cmp(&other.priority_key)
     }
 }
 
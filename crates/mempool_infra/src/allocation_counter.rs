@@ -0,0 +1,64 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static N_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations made through it. Registered as the global
+/// allocator when the `allocation_counting` feature is enabled, which is therefore incompatible
+/// with other global allocators (e.g., the `jemalloc` feature of the blockifier).
+pub struct CountingAllocator;
+
+// Safety: all calls are forwarded to the system allocator as is.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        N_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// The number of allocations, including reallocations, made by the process so far.
+pub fn n_allocations() -> usize {
+    N_ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+/// Counts the allocations made from its creation on. The count is process-wide, so it includes
+/// the allocations of any work running concurrently, e.g., other requests.
+#[derive(Clone, Copy, Debug)]
+pub struct AllocationScope {
+    start: usize,
+}
+
+impl AllocationScope {
+    pub fn start() -> Self {
+        Self { start: n_allocations() }
+    }
+
+    pub fn n_allocations(&self) -> usize {
+        n_allocations().wrapping_sub(self.start)
+    }
+
+    /// Records the number of allocations made in the scope to the given histogram.
+    pub fn record(&self, histogram: &'static str) {
+        metrics::histogram!(histogram, to_histogram_value(self.n_allocations()));
+    }
+}
+
+// Allocation counts are far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_histogram_value(n_allocations: usize) -> f64 {
+    n_allocations as f64
+}
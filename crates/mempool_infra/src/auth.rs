@@ -0,0 +1,31 @@
+use hyper::header::{HeaderValue, AUTHORIZATION};
+use hyper::HeaderMap;
+
+/// The value of the `authorization` header of requests carrying the given bearer token, or `None`
+/// for an empty token, which disables authentication.
+///
+/// # Panics
+/// If the token is not a valid header value, e.g., contains a newline.
+pub fn bearer_auth_header(auth_token: &str) -> Option<HeaderValue> {
+    if auth_token.is_empty() {
+        return None;
+    }
+    let mut header_value = HeaderValue::from_str(&format!("Bearer {auth_token}"))
+        .expect("Auth tokens should be valid header values.");
+    // Keeps the token out of logs and of the compression tables of HTTP/2.
+    header_value.set_sensitive(true);
+    Some(header_value)
+}
+
+/// Whether the headers of a request carry the expected `authorization` header. Compared in constant
+/// time, so that the response time does not leak how much of the token a guess got right.
+pub fn is_authorized(headers: &HeaderMap, expected_auth_header: &HeaderValue) -> bool {
+    headers.get(AUTHORIZATION).is_some_and(|auth_header| {
+        constant_time_eq(auth_header.as_bytes(), expected_auth_header.as_bytes())
+    })
+}
+
+/// Whether the two byte strings are equal, compared in constant time for strings of equal length.
+pub fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |diff, (l, r)| diff | (l ^ r)) == 0
+}
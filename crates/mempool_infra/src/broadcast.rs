@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{self, Receiver, Sender};
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::component_client::RemoteComponentClient;
+use crate::component_definitions::{ComponentRequestHandler, RemoteComponentCommunicationConfig};
+use crate::component_runner::ComponentStarter;
+use crate::component_server::RemoteComponentServer;
+use crate::payload_metrics::{component_name, RequestVariant};
+
+/// The number of events buffered for the subscribers of a broadcast; a subscriber falling further
+/// behind skips the oldest ones.
+pub const DEFAULT_BROADCAST_CAPACITY: usize = 64;
+
+/// Broadcasts events of a type to the subscribers of the process. Unlike a request, which reaches a
+/// single component, an event, e.g., a committed block, reaches all the components subscribed to
+/// it.
+#[derive(Clone)]
+pub struct LocalBroadcast<Event: Clone> {
+    tx: Sender<Event>,
+}
+
+impl<Event: Clone> LocalBroadcast<Event> {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Publishes the event to the current subscribers, and returns their number. Events published
+    /// without subscribers are dropped.
+    pub fn publish(&self, event: Event) -> usize {
+        self.tx.send(event).unwrap_or_default()
+    }
+
+    /// Subscribes to the events published from now on.
+    pub fn subscribe(&self) -> EventSubscriber<Event> {
+        EventSubscriber { rx: self.tx.subscribe() }
+    }
+
+    pub fn n_subscribers(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+impl<Event: Clone> Default for LocalBroadcast<Event> {
+    fn default() -> Self {
+        Self::new(DEFAULT_BROADCAST_CAPACITY)
+    }
+}
+
+/// Receives the events of a [`LocalBroadcast`].
+pub struct EventSubscriber<Event: Clone> {
+    rx: Receiver<Event>,
+}
+
+impl<Event: Clone> EventSubscriber<Event> {
+    /// Returns the next event, or `None` once all the broadcasts are dropped. A subscriber that
+    /// fell behind skips the events dropped from the buffer, so it only learns of the recent ones.
+    pub async fn recv(&mut self) -> Option<Event> {
+        loop {
+            match self.rx.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(n_skipped_events)) => warn!(
+                    "A subscriber of {} events fell behind, skipping {} events.",
+                    component_name::<Event>(),
+                    n_skipped_events
+                ),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Publishes events to the subscribers of the process, and to the relays of other processes over
+/// the internal network; see [`BroadcastRelay`]. Delivery to remote relays is best effort: failures
+/// are logged, and not retried beyond the retries of the relay clients. The relayed events are
+/// labeled in the metrics of the relay clients as requests; see [`RequestVariant`].
+pub struct EventPublisher<Event>
+where
+    Event: Clone + Serialize + RequestVariant + Send + Sync + 'static,
+{
+    local_broadcast: LocalBroadcast<Event>,
+    remote_relays: Vec<Arc<RemoteComponentClient<Event, ()>>>,
+}
+
+impl<Event> EventPublisher<Event>
+where
+    Event: Clone + Serialize + RequestVariant + Send + Sync + 'static,
+{
+    pub fn new(local_broadcast: LocalBroadcast<Event>) -> Self {
+        Self { local_broadcast, remote_relays: Vec::new() }
+    }
+
+    /// Also publishes the events to the relay served at the address of the given configuration.
+    pub fn with_remote_relay(mut self, config: &RemoteComponentCommunicationConfig) -> Self {
+        self.remote_relays.push(Arc::new(RemoteComponentClient::from_config(config)));
+        self
+    }
+
+    /// Publishes the event locally, then to the remote relays concurrently, and returns once all
+    /// the relays answered or failed.
+    pub async fn publish(&self, event: Event) {
+        self.local_broadcast.publish(event.clone());
+        let mut deliveries = JoinSet::new();
+        for relay in &self.remote_relays {
+            let relay = Arc::clone(relay);
+            let event = event.clone();
+            deliveries.spawn(async move { relay.send(event).await });
+        }
+        while let Some(delivery) = deliveries.join_next().await {
+            match delivery {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => warn!(
+                    "Failed to relay a {} event to a remote process: {}",
+                    component_name::<Event>(),
+                    err
+                ),
+                Err(err) => {
+                    warn!("The relay of a {} event panicked: {}", component_name::<Event>(), err)
+                }
+            }
+        }
+    }
+
+    pub fn local_broadcast(&self) -> &LocalBroadcast<Event> {
+        &self.local_broadcast
+    }
+}
+
+/// Republishes the events published by other processes to the subscribers of this one. Served by
+/// a remote server; see [`create_broadcast_relay_server`].
+pub struct BroadcastRelay<Event: Clone> {
+    local_broadcast: LocalBroadcast<Event>,
+}
+
+impl<Event: Clone> BroadcastRelay<Event> {
+    pub fn new(local_broadcast: LocalBroadcast<Event>) -> Self {
+        Self { local_broadcast }
+    }
+}
+
+#[async_trait]
+impl<Event: Clone + Send + Sync> ComponentStarter for BroadcastRelay<Event> {}
+
+#[async_trait]
+impl<Event: Clone + Send + Sync> ComponentRequestHandler<Event, ()> for BroadcastRelay<Event> {
+    async fn handle_request(&mut self, event: Event) {
+        self.local_broadcast.publish(event);
+    }
+}
+
+pub type BroadcastRelayServer<Event> = RemoteComponentServer<BroadcastRelay<Event>, Event, ()>;
+
+/// Creates the server relaying the events published to this process, at the address, in the wire
+/// format and with the auth token of the given configuration.
+pub fn create_broadcast_relay_server<Event>(
+    local_broadcast: LocalBroadcast<Event>,
+    config: &RemoteComponentCommunicationConfig,
+) -> BroadcastRelayServer<Event>
+where
+    Event: Clone + DeserializeOwned + Send + Sync + 'static,
+{
+    RemoteComponentServer::new(BroadcastRelay::new(local_broadcast), config.ip, config.port)
+        .with_serialization_format(config.serialization_format)
+        .with_auth_token(&config.auth_token)
+}
@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::sync::mpsc::{channel, Receiver, Sender, WeakSender};
+use tracing::warn;
+
+/// The number of messages queued in a channel.
+pub const CHANNEL_DEPTH: &str = "channel_depth";
+/// The highest number of messages queued in a channel since it was created.
+pub const CHANNEL_DEPTH_HIGH_WATERMARK: &str = "channel_depth_high_watermark";
+const CHANNEL_LABEL: &str = "channel";
+
+/// The interval between samples of the depth of a monitored channel.
+pub const DEPTH_SAMPLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Exports the depth of a bounded channel, and its high watermark, as gauges labeled with the
+/// channel name.
+#[derive(Debug)]
+pub struct ChannelDepthGauge {
+    channel_name: &'static str,
+    high_watermark: AtomicUsize,
+}
+
+impl ChannelDepthGauge {
+    pub fn new(channel_name: &'static str) -> Self {
+        Self { channel_name, high_watermark: AtomicUsize::new(0) }
+    }
+
+    /// Records the current depth of the channel of `sender`, and returns it.
+    pub fn record<T>(&self, sender: &Sender<T>) -> usize {
+        let depth = sender.max_capacity() - sender.capacity();
+        let high_watermark = self.high_watermark.fetch_max(depth, Ordering::Relaxed).max(depth);
+        metrics::gauge!(CHANNEL_DEPTH, to_gauge_value(depth), CHANNEL_LABEL => self.channel_name);
+        metrics::gauge!(
+            CHANNEL_DEPTH_HIGH_WATERMARK,
+            to_gauge_value(high_watermark),
+            CHANNEL_LABEL => self.channel_name
+        );
+        depth
+    }
+
+    pub fn high_watermark(&self) -> usize {
+        self.high_watermark.load(Ordering::Relaxed)
+    }
+}
+
+// Channel depths are bounded by the channel capacity, far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_gauge_value(depth: usize) -> f64 {
+    depth as f64
+}
+
+/// Creates a bounded channel whose depth is sampled every [`DEPTH_SAMPLING_INTERVAL`] and exported
+/// under the given name, until all of its senders are dropped. The monitored channels are sampled
+/// together by a single task. Must be called within a tokio runtime for the channel to be
+/// monitored.
+pub fn monitored_channel<T: Send + 'static>(
+    channel_name: &'static str,
+    buffer: usize,
+) -> (Sender<T>, Receiver<T>) {
+    let (sender, receiver) = channel(buffer);
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        warn!("Channel {} is created outside a runtime and is not monitored.", channel_name);
+        return (sender, receiver);
+    };
+    let mut sampler = DEPTH_SAMPLER.lock().expect("Failed to lock the channel depth sampler.");
+    sampler.channels.push(Box::new(MonitoredChannel {
+        gauge: ChannelDepthGauge::new(channel_name),
+        weak_sender: sender.downgrade(),
+    }));
+    if !sampler.is_running {
+        sampler.is_running = true;
+        handle.spawn(sample_channel_depths());
+    }
+    (sender, receiver)
+}
+
+// The channels monitored by the process, sampled by a single task. The task runs on the runtime of
+// the first monitored channel, and is spawned again if that runtime shuts down.
+static DEPTH_SAMPLER: Mutex<DepthSampler> =
+    Mutex::new(DepthSampler { channels: Vec::new(), is_running: false });
+
+struct DepthSampler {
+    channels: Vec<Box<dyn SampledChannel>>,
+    is_running: bool,
+}
+
+trait SampledChannel: Send {
+    // Records the depth of the channel. Returns false once all of its senders are dropped.
+    fn sample(&self) -> bool;
+}
+
+struct MonitoredChannel<T> {
+    gauge: ChannelDepthGauge,
+    weak_sender: WeakSender<T>,
+}
+
+impl<T: Send> SampledChannel for MonitoredChannel<T> {
+    fn sample(&self) -> bool {
+        let Some(sender) = self.weak_sender.upgrade() else {
+            return false;
+        };
+        self.gauge.record(&sender);
+        true
+    }
+}
+
+// Marks the sampler as stopped when its task is dropped, i.e., when its runtime shuts down.
+struct RunningSampler;
+
+impl Drop for RunningSampler {
+    fn drop(&mut self) {
+        DEPTH_SAMPLER.lock().expect("Failed to lock the channel depth sampler.").is_running = false;
+    }
+}
+
+// Keeps running while idle, so that channels monitored later are sampled without respawning it.
+async fn sample_channel_depths() {
+    let _running = RunningSampler;
+    let mut interval = tokio::time::interval(DEPTH_SAMPLING_INTERVAL);
+    loop {
+        interval.tick().await;
+        DEPTH_SAMPLER
+            .lock()
+            .expect("Failed to lock the channel depth sampler.")
+            .channels
+            .retain(|channel| channel.sample());
+    }
+}
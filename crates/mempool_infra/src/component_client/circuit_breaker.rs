@@ -0,0 +1,212 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use super::definitions::{ClientError, ClientResult};
+
+/// Counts the state transitions of the circuit breakers of remote component clients, labeled with
+/// the component and the new state.
+pub const CIRCUIT_BREAKER_TRANSITIONS: &str = "remote_client_circuit_breaker_transitions";
+/// The state of the circuit breaker of a remote component client, labeled with the component: 0 if
+/// closed, 1 if half-open and 2 if open.
+pub const CIRCUIT_BREAKER_STATE: &str = "remote_client_circuit_breaker_state";
+const COMPONENT_LABEL: &str = "component";
+const STATE_LABEL: &str = "state";
+
+const DEFAULT_FAILURE_THRESHOLD: usize = 5;
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// The policy of the circuit breaker of a remote component client. After `failure_threshold`
+/// consecutive requests fail with a retriable error (see [`ClientError::is_retriable`]), the
+/// circuit opens and requests fail fast with [`ClientError::CircuitOpen`] for `cooldown`. The
+/// circuit then half-opens and lets a single request through to probe the server: the circuit
+/// closes if it succeeds, and opens again otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: usize,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self { failure_threshold: DEFAULT_FAILURE_THRESHOLD, cooldown: DEFAULT_COOLDOWN }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    const fn gauge_value(self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: usize,
+    opened_at: Option<Instant>,
+    // Whether the request probing the server of a half-open circuit is yet to be answered.
+    probe_in_flight: bool,
+}
+
+/// Tracks the outcome of the requests of a remote component client, and stops sending requests to
+/// its server while the server is failing; see [`CircuitBreakerConfig`]. Shared by the clones of
+/// the client.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    component: &'static str,
+    state: Mutex<CircuitBreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig, component: &'static str) -> Self {
+        metrics::gauge!(
+            CIRCUIT_BREAKER_STATE,
+            CircuitState::Closed.gauge_value(),
+            COMPONENT_LABEL => component
+        );
+        Self {
+            config,
+            component,
+            state: Mutex::new(CircuitBreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+            }),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Asks to send a request: fails with [`ClientError::CircuitOpen`] while the circuit is open,
+    /// or while the probe of a half-open circuit is in flight. The outcome of the request is to be
+    /// recorded through the returned permit.
+    pub fn acquire(&self) -> ClientResult<CircuitBreakerPermit<'_>> {
+        let mut state = self.lock();
+        match state.state {
+            CircuitState::Closed => {}
+            CircuitState::Open => {
+                let opened_at = state.opened_at.expect("An open circuit should have been opened.");
+                if opened_at.elapsed() < self.config.cooldown {
+                    return Err(ClientError::CircuitOpen);
+                }
+                self.transition(&mut state, CircuitState::HalfOpen);
+                state.probe_in_flight = true;
+            }
+            CircuitState::HalfOpen => {
+                if state.probe_in_flight {
+                    return Err(ClientError::CircuitOpen);
+                }
+                state.probe_in_flight = true;
+            }
+        }
+        Ok(CircuitBreakerPermit { breaker: self, is_recorded: false })
+    }
+
+    fn record_success(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures = 0;
+        state.probe_in_flight = false;
+        if state.state != CircuitState::Closed {
+            self.transition(&mut state, CircuitState::Closed);
+        }
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures += 1;
+        state.probe_in_flight = false;
+        let should_open = match state.state {
+            CircuitState::Closed => state.consecutive_failures >= self.config.failure_threshold,
+            CircuitState::HalfOpen => true,
+            // Requests sent before the circuit opened may fail after it did.
+            CircuitState::Open => false,
+        };
+        if should_open {
+            state.opened_at = Some(Instant::now());
+            self.transition(&mut state, CircuitState::Open);
+        }
+    }
+
+    // Lets another request probe the server, without telling whether it is failing.
+    fn release_probe(&self) {
+        self.lock().probe_in_flight = false;
+    }
+
+    fn transition(&self, state: &mut CircuitBreakerState, to: CircuitState) {
+        match to {
+            CircuitState::Open => warn!(
+                "The circuit of the {} client opened after {} consecutive failures.",
+                self.component, state.consecutive_failures
+            ),
+            _ => info!("The circuit of the {} client is {}.", self.component, to.as_str()),
+        }
+        state.state = to;
+        metrics::increment_counter!(
+            CIRCUIT_BREAKER_TRANSITIONS,
+            COMPONENT_LABEL => self.component,
+            STATE_LABEL => to.as_str()
+        );
+        metrics::gauge!(CIRCUIT_BREAKER_STATE, to.gauge_value(), COMPONENT_LABEL => self.component);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CircuitBreakerState> {
+        self.state.lock().expect("The circuit breaker lock should not be poisoned.")
+    }
+}
+
+/// The permission to send a request through a [`CircuitBreaker`]. Dropping it without recording
+/// the outcome, e.g., if the request is cancelled, leaves the circuit as is.
+#[derive(Debug)]
+pub struct CircuitBreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    is_recorded: bool,
+}
+
+impl CircuitBreakerPermit<'_> {
+    /// Records the outcome of the request. Only retriable errors count as failures; the others
+    /// are answers of a reachable server, except for timeouts, which tell of a wedged one. Missed
+    /// deadlines tell nothing about the server.
+    pub fn record<T>(mut self, result: &ClientResult<T>) {
+        self.is_recorded = true;
+        match result {
+            Err(ClientError::DeadlineExceeded) => self.breaker.release_probe(),
+            Err(err) if err.is_retriable() || matches!(err, ClientError::Timeout(_)) => {
+                self.breaker.record_failure()
+            }
+            _ => self.breaker.record_success(),
+        }
+    }
+}
+
+impl Drop for CircuitBreakerPermit<'_> {
+    fn drop(&mut self) {
+        if !self.is_recorded {
+            self.breaker.release_probe();
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper::body::HttpBody;
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use hyper::service::Service;
+use hyper::{Client, Uri};
+use hyper_rustls::{HttpsConnector, MaybeHttpsStream};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tonic::codegen::BoxFuture;
+
+/// The number of connections currently open by the remote clients of a component.
+pub const OPEN_CONNECTIONS: &str = "remote_client_open_connections";
+/// The number of connections opened by the remote clients of a component. Growing along with the
+/// requests means connections are not reused.
+pub const OPENED_CONNECTIONS: &str = "remote_client_opened_connections";
+/// The number of requests sent by the remote clients of a component and not answered yet. Divided
+/// by the open connections, the number of concurrent requests on a connection.
+pub const IN_FLIGHT_REQUESTS: &str = "remote_client_in_flight_requests";
+const COMPONENT_LABEL: &str = "component";
+
+pub const DEFAULT_POOL_SIZE: usize = 4;
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+// Pings open connections, so that a connection dropped by the network is detected before a request
+// is sent on it.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(20);
+
+/// The connections of a remote component client to its server. Requests are multiplexed over
+/// HTTP/2 connections, which are opened on the first request and kept alive while in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectionPoolConfig {
+    // The number of connections requests are spread over, so that a slow response or a lost
+    // packet only delays the requests sharing its connection.
+    pub pool_size: usize,
+    // Connections idle for longer are closed.
+    pub idle_timeout: Duration,
+}
+
+impl Default for ConnectionPoolConfig {
+    fn default() -> Self {
+        Self { pool_size: DEFAULT_POOL_SIZE, idle_timeout: DEFAULT_IDLE_TIMEOUT }
+    }
+}
+
+/// Spreads the requests of a client, and of its clones, over a fixed number of connections, round
+/// robin.
+pub(crate) struct ConnectionPool<B> {
+    clients: Arc<Vec<Client<MeteredConnector, B>>>,
+    next_client: Arc<AtomicUsize>,
+    open_connections: Arc<AtomicUsize>,
+}
+
+impl<B> ConnectionPool<B>
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    pub(crate) fn new(
+        connector: HttpsConnector<HttpConnector>,
+        config: &ConnectionPoolConfig,
+        component: &'static str,
+    ) -> Self {
+        let open_connections = Arc::new(AtomicUsize::new(0));
+        let connector = MeteredConnector {
+            inner: connector,
+            component,
+            open_connections: Arc::clone(&open_connections),
+        };
+        // Each client multiplexes its requests over a single HTTP/2 connection per server.
+        let clients = (0..config.pool_size.max(1))
+            .map(|_| {
+                Client::builder()
+                    .http2_only(true)
+                    .pool_idle_timeout(config.idle_timeout)
+                    .http2_keep_alive_interval(KEEP_ALIVE_INTERVAL)
+                    .build(connector.clone())
+            })
+            .collect();
+        Self {
+            clients: Arc::new(clients),
+            next_client: Arc::new(AtomicUsize::new(0)),
+            open_connections,
+        }
+    }
+
+    pub(crate) fn client(&self) -> &Client<MeteredConnector, B> {
+        let index = self.next_client.fetch_add(1, Ordering::Relaxed) % self.clients.len();
+        &self.clients[index]
+    }
+
+    pub(crate) fn n_open_connections(&self) -> usize {
+        self.open_connections.load(Ordering::Relaxed)
+    }
+}
+
+// Can't derive because derive forces the body type to also be `Clone`.
+impl<B> Clone for ConnectionPool<B> {
+    fn clone(&self) -> Self {
+        Self {
+            clients: Arc::clone(&self.clients),
+            next_client: Arc::clone(&self.next_client),
+            open_connections: Arc::clone(&self.open_connections),
+        }
+    }
+}
+
+/// Counts a request as in flight until dropped.
+pub(crate) struct InFlightRequest {
+    component: &'static str,
+}
+
+impl InFlightRequest {
+    pub(crate) fn new(component: &'static str) -> Self {
+        metrics::increment_gauge!(IN_FLIGHT_REQUESTS, 1.0, COMPONENT_LABEL => component);
+        Self { component }
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        metrics::decrement_gauge!(IN_FLIGHT_REQUESTS, 1.0, COMPONENT_LABEL => self.component);
+    }
+}
+
+/// Opens connections with the inner connector, and counts them while open.
+#[derive(Clone)]
+pub(crate) struct MeteredConnector {
+    inner: HttpsConnector<HttpConnector>,
+    component: &'static str,
+    open_connections: Arc<AtomicUsize>,
+}
+
+impl Service<Uri> for MeteredConnector {
+    type Response = MeteredConnection;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = BoxFuture<MeteredConnection, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connecting = self.inner.call(uri);
+        let component = self.component;
+        let open_connections = Arc::clone(&self.open_connections);
+        Box::pin(async move {
+            let stream = connecting.await?;
+            open_connections.fetch_add(1, Ordering::Relaxed);
+            metrics::increment_counter!(OPENED_CONNECTIONS, COMPONENT_LABEL => component);
+            metrics::increment_gauge!(OPEN_CONNECTIONS, 1.0, COMPONENT_LABEL => component);
+            Ok(MeteredConnection { stream, component, open_connections })
+        })
+    }
+}
+
+/// A connection counted as open until dropped.
+pub(crate) struct MeteredConnection {
+    stream: MaybeHttpsStream<TcpStream>,
+    component: &'static str,
+    open_connections: Arc<AtomicUsize>,
+}
+
+impl Drop for MeteredConnection {
+    fn drop(&mut self) {
+        self.open_connections.fetch_sub(1, Ordering::Relaxed);
+        metrics::decrement_gauge!(OPEN_CONNECTIONS, 1.0, COMPONENT_LABEL => self.component);
+    }
+}
+
+impl Connection for MeteredConnection {
+    fn connected(&self) -> Connected {
+        self.stream.connected()
+    }
+}
+
+impl AsyncRead for MeteredConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for MeteredConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
@@ -1,22 +1,119 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
+use hyper::http::uri::InvalidUri;
 use hyper::StatusCode;
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
 use thiserror::Error;
 
-use crate::component_definitions::ServerError;
+use crate::component_definitions::{HealthCheckResponse, ServerError};
+use crate::serialization::SerializationError;
+use crate::tls::TlsError;
 
 #[derive(Clone, Debug, Error)]
 pub enum ClientError {
     #[error("Communication error: {0}")]
     CommunicationFailure(Arc<hyper::Error>),
     #[error("Could not deserialize server response: {0}")]
-    ResponseDeserializationFailure(Arc<bincode::Error>),
+    ResponseDeserializationFailure(Arc<SerializationError>),
     #[error("Could not parse the response: {0}")]
     ResponseParsingFailure(Arc<hyper::Error>),
     #[error("Got status code: {0}, with server error: {1}")]
     ResponseError(StatusCode, ServerError),
     #[error("Got an unexpected response type: {0}")]
     UnexpectedResponse(String),
+    #[error("The deadline of the request passed before it was answered.")]
+    DeadlineExceeded,
+    #[error("The server is failing; the request was not sent.")]
+    CircuitOpen,
+    #[error("The server did not answer the request within {0:?}.")]
+    Timeout(Duration),
+    #[error("gRPC call failed: {0}")]
+    GrpcFailure(Arc<tonic::Status>),
+}
+
+impl ClientError {
+    /// Whether the request may succeed if sent again: transport failures and server-side
+    /// unavailability are transient, whereas malformed requests and responses are not. Requests
+    /// past their deadline are of no use anymore, and requests failed fast by an open circuit
+    /// breaker are not to be retried until the circuit half-opens. Requests that timed out may still be
+    /// handled by the wedged server, so sending them again is left to the caller.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            ClientError::CommunicationFailure(_) | ClientError::ResponseParsingFailure(_) => true,
+            ClientError::ResponseError(status_code, _) => {
+                status_code.is_server_error() || *status_code == StatusCode::TOO_MANY_REQUESTS
+            }
+            ClientError::GrpcFailure(status) => {
+                matches!(status.code(), tonic::Code::Unavailable | tonic::Code::ResourceExhausted)
+            }
+            ClientError::ResponseDeserializationFailure(_)
+            | ClientError::UnexpectedResponse(_)
+            | ClientError::DeadlineExceeded
+            | ClientError::CircuitOpen
+            | ClientError::Timeout(_) => false,
+        }
+    }
+}
+
+impl CodedError for ClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ClientError::CommunicationFailure(_) => codes::CLIENT_COMMUNICATION_FAILURE,
+            ClientError::ResponseDeserializationFailure(_) => {
+                codes::CLIENT_RESPONSE_DESERIALIZATION_FAILURE
+            }
+            ClientError::ResponseParsingFailure(_) => codes::CLIENT_RESPONSE_PARSING_FAILURE,
+            ClientError::ResponseError(..) => codes::CLIENT_SERVER_ERROR_RESPONSE,
+            ClientError::UnexpectedResponse(_) => codes::CLIENT_UNEXPECTED_RESPONSE,
+            ClientError::DeadlineExceeded => codes::CLIENT_DEADLINE_EXCEEDED,
+            ClientError::CircuitOpen => codes::CLIENT_CIRCUIT_OPEN,
+            ClientError::Timeout(_) => codes::CLIENT_TIMEOUT,
+            ClientError::GrpcFailure(_) => codes::CLIENT_GRPC_FAILURE,
+        }
+    }
+
+    // Transient failures, missed deadlines and failing servers degrade the node; the others are
+    // mismatches between components.
+    fn severity(&self) -> Severity {
+        if self.is_retriable()
+            || matches!(
+                self,
+                ClientError::DeadlineExceeded | ClientError::CircuitOpen | ClientError::Timeout(_)
+            )
+        {
+            Severity::Warning
+        } else {
+            Severity::Error
+        }
+    }
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
+
+/// Probes the health of a component through its server; see
+/// [`ComponentHealth`](crate::component_definitions::ComponentHealth).
+#[async_trait]
+pub trait ComponentHealthClient: Send + Sync {
+    async fn is_alive(&self) -> ClientResult<bool>;
+    async fn is_ready(&self) -> ClientResult<bool>;
+}
+
+pub type SharedComponentHealthClient = Arc<dyn ComponentHealthClient>;
+
+pub(crate) fn health_of<Response: HealthCheckResponse>(response: &Response) -> ClientResult<bool> {
+    response.health().ok_or_else(|| {
+        ClientError::UnexpectedResponse("Expected the answer to a health probe.".to_string())
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum RemoteClientCreationError {
+    #[error("Invalid URL {url}: {source}")]
+    InvalidUrl { url: String, source: InvalidUri },
+    #[error("Unsupported URL scheme in {0}; expected http or https.")]
+    UnsupportedScheme(String),
+    #[error(transparent)]
+    Tls(#[from] TlsError),
+}
@@ -1,6 +1,18 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
 use tokio::sync::mpsc::{channel, Sender};
+use tokio::time::{timeout, Instant};
+use tracing::Instrument;
 
-use crate::component_definitions::ComponentRequestAndResponseSender;
+use super::definitions::{health_of, ClientError, ClientResult, ComponentHealthClient};
+use crate::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use crate::request_context::RequestContext;
+use crate::request_metrics::LocalRequestMetrics;
 
 /// The `LocalComponentClient` struct is a generic client for sending component requests and
 /// receiving responses asynchronously.
@@ -12,6 +24,8 @@ use crate::component_definitions::ComponentRequestAndResponseSender;
 /// # Fields
 /// - `tx`: An asynchronous sender channel for transmitting
 ///   `ComponentRequestAndResponseSender<Request, Response>` messages.
+/// - `timeout`: The time to wait for the answer of a request, if any; see
+///   [`LocalComponentClient::with_timeout`].
 ///
 /// # Example
 /// ```rust
@@ -57,6 +71,7 @@ where
     Response: Send + Sync,
 {
     tx: Sender<ComponentRequestAndResponseSender<Request, Response>>,
+    timeout: Option<Duration>,
 }
 
 impl<Request, Response> LocalComponentClient<Request, Response>
@@ -65,17 +80,64 @@ where
     Response: Send + Sync,
 {
     pub fn new(tx: Sender<ComponentRequestAndResponseSender<Request, Response>>) -> Self {
-        Self { tx }
+        Self { tx, timeout: None }
     }
 
-    // TODO(Tsabary, 1/5/2024): Consider implementation for messages without expected responses.
+    /// Fails the requests the server doesn't answer within the given time with
+    /// [`ClientError::Timeout`], so that callers aren't stalled by a wedged server. A zero timeout
+    /// is no timeout.
+    pub fn with_timeout(mut self, request_timeout: Duration) -> Self {
+        self.timeout = (!request_timeout.is_zero()).then_some(request_timeout);
+        self
+    }
 
-    pub async fn send(&self, request: Request) -> Response {
-        let (res_tx, mut res_rx) = channel::<Response>(1);
-        let request_and_res_tx = ComponentRequestAndResponseSender { request, tx: res_tx };
-        self.tx.send(request_and_res_tx).await.expect("Outbound connection should be open.");
+    // TODO(Tsabary, 1/5/2024): Consider implementation for messages without expected responses.
 
-        res_rx.recv().await.expect("Inbound connection should be open.")
+    /// Sends the request with the context of the request being handled, if any; see
+    /// [`RequestContext`]. Fails only if the deadline of the context passes before the server
+    /// handles the request, or if the server doesn't answer within the timeout of the client. The latency of the request and the depth of the request queue of the
+    /// server are exported; see [`LocalRequestMetrics`].
+    pub async fn send(&self, request: Request) -> ClientResult<Response> {
+        let request_metrics = LocalRequestMetrics::new::<Request>();
+        let sent_at = Instant::now();
+        let context = RequestContext::current_or_new();
+        let span = context.span();
+        let context = context.sent_from(&span);
+        let deadline = context.deadline;
+        async move {
+            let (res_tx, mut res_rx) = channel::<Response>(1);
+            let request_and_res_tx =
+                ComponentRequestAndResponseSender { request, tx: res_tx, context };
+            // Both enqueuing the request and awaiting its answer hang if the server is wedged.
+            let exchange = async {
+                self.tx
+                    .send(request_and_res_tx)
+                    .await
+                    .expect("Outbound connection should be open.");
+                request_metrics.record_queue_depth(&self.tx);
+                res_rx.recv().await
+            };
+            let response = match self.timeout {
+                Some(request_timeout) => timeout(request_timeout, exchange)
+                    .await
+                    .map_err(|_| ClientError::Timeout(request_timeout))?,
+                None => exchange.await,
+            };
+            request_metrics.record_queue_depth(&self.tx);
+            match response {
+                Some(response) => {
+                    request_metrics.record_latency(sent_at.elapsed());
+                    Ok(response)
+                }
+                // Servers drop the requests they can't answer by their deadline.
+                None if deadline.is_some_and(|deadline| deadline <= Instant::now()) => {
+                    Err(ClientError::DeadlineExceeded)
+                }
+                None => panic!("Inbound connection should be open."),
+            }
+        }
+        .instrument(span)
+        .await
     }
 }
 
@@ -87,6 +149,21 @@ where
     Response: Send + Sync,
 {
     fn clone(&self) -> Self {
-        Self { tx: self.tx.clone() }
+        Self { tx: self.tx.clone(), timeout: self.timeout }
+    }
+}
+
+#[async_trait]
+impl<Request, Response> ComponentHealthClient for LocalComponentClient<Request, Response>
+where
+    Request: HealthCheckRequest + Send + Sync,
+    Response: HealthCheckResponse + Send + Sync,
+{
+    async fn is_alive(&self) -> ClientResult<bool> {
+        health_of(&self.send(Request::is_alive()).await?)
+    }
+
+    async fn is_ready(&self) -> ClientResult<bool> {
+        health_of(&self.send(Request::is_ready()).await?)
     }
 }
@@ -1,7 +1,11 @@
+mod circuit_breaker;
+mod connection_pool;
 mod definitions;
 mod local_component_client;
 mod remote_component_client;
 
+pub use circuit_breaker::*;
+pub use connection_pool::*;
 pub use definitions::*;
 pub use local_component_client::*;
 pub use remote_component_client::*;
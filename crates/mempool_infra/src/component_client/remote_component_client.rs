@@ -1,16 +1,100 @@
 use std::marker::PhantomData;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use bincode::{deserialize, serialize};
+use async_trait::async_trait;
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
-use hyper::{Body, Client, Request as HyperRequest, Response as HyperResponse, StatusCode, Uri};
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use hyper::{Body, HeaderMap, Request as HyperRequest, Response as HyperResponse, StatusCode, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rand::Rng;
+use rustls::{ClientConfig, RootCertStore};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tokio::time::Instant;
+use tonic::body::BoxBody;
+use tonic::metadata::MetadataMap;
+use tracing::{debug, Instrument};
 
-use super::definitions::{ClientError, ClientResult};
-use crate::component_definitions::APPLICATION_OCTET_STREAM;
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use super::connection_pool::{ConnectionPool, ConnectionPoolConfig, InFlightRequest};
+use super::definitions::{
+    health_of,
+    ClientError,
+    ClientResult,
+    ComponentHealthClient,
+    RemoteClientCreationError,
+};
+use crate::auth::bearer_auth_header;
+use crate::component_definitions::{
+    HealthCheckRequest,
+    HealthCheckResponse,
+    RemoteComponentCommunicationConfig,
+    RemoteTransport,
+    ServerError,
+};
+use crate::grpc::{client_error_of, grpc_method, SerializedCodec};
+use crate::payload_metrics::{component_name, PayloadSizeHistograms, RequestVariant};
+use crate::request_context::RequestContext;
+use crate::serialization::SerializationFormat;
+use crate::tls::{root_cert_store, TlsError, TlsIdentity};
+
+const DEFAULT_MAX_RETRIES: usize = 3;
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// The TLS settings of a remote component client. Used for https URLs only.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    // A PEM file of the root certificates to trust. If unset, the native root certificates of the
+    // platform are trusted.
+    pub ca_certs_path: Option<PathBuf>,
+    // The certificate the client authenticates with, for servers requiring mutual TLS.
+    pub client_identity: Option<TlsIdentity>,
+}
+
+/// The retry policy of a remote component client. Requests failing with a retriable error (see
+/// [`ClientError::is_retriable`]) are retried with an exponential backoff: the delay before the
+/// n-th retry is `base_delay * 2^(n-1)`, capped at `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryConfig {
+    // Number of extra attempts to send a request to the server in case of a failure.
+    pub max_retries: usize,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    // If set, each delay is drawn uniformly between half of it and all of it, so that clients
+    // failing together don't retry together.
+    pub jitter: bool,
+}
+
+impl RetryConfig {
+    /// The delay before the given retry, counted from 1.
+    pub fn delay(&self, retry: usize) -> Duration {
+        let exponent = u32::try_from(retry.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = 2_u32
+            .checked_pow(exponent)
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+        rand::thread_rng().gen_range(delay / 2..=delay)
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: true,
+        }
+    }
+}
 
 /// The `RemoteComponentClient` struct is a generic client for sending component requests and
 /// receiving responses asynchronously through HTTP connection.
@@ -22,22 +106,35 @@ use crate::component_definitions::APPLICATION_OCTET_STREAM;
 ///
 /// # Fields
 /// - `uri`: URI address of the server.
-/// - `client`: The inner HTTP client that initiates the connection to the server and manages it.
-/// - `max_retries`: Configurable number of extra attempts to send a request to server in case of a
-///   failure.
+/// - `connector`: Opens the connections to the server, over TLS for https URLs.
+/// - `connection_pool`: The connections requests are sent over; see [`ConnectionPoolConfig`].
+///   Shared by the clones of the client.
+/// - `grpc_connection_pool`: The connections gRPC calls are sent over. Connections are opened on
+///   the first request, so the pool of the unused transport costs nothing.
+/// - `retry_config`: The policy of retrying requests that failed due to a transient error, e.g., a
+///   dropped connection.
+/// - `serialization_format`: The wire format of the requests; responses are read in the format they
+///   are tagged with. Bincode by default.
+/// - `transport`: Whether requests are sent as plain HTTP requests, or as gRPC calls; see
+///   [`RemoteTransport`]. HTTP by default.
+/// - `circuit_breaker`: If set, stops sending requests to a failing server for a while; see
+///   [`CircuitBreakerConfig`]. Shared by the clones of the client.
+/// - `auth_header`: If set, the `authorization` header sent with the requests, for servers
+///   requiring a bearer token; see [`RemoteComponentClient::with_auth_token`].
 ///
 /// # Example
 /// ```rust
 /// // Example usage of the RemoteComponentClient
 ///
 /// use serde::{Deserialize, Serialize};
+/// use strum_macros::IntoStaticStr;
 ///
 /// use crate::starknet_mempool_infra::component_client::RemoteComponentClient;
 ///
-/// // Define your request and response types
-/// #[derive(Serialize)]
-/// struct MyRequest {
-///     pub content: String,
+/// // Define your request and response types; requests are labeled in the metrics by their variant.
+/// #[derive(Serialize, IntoStaticStr)]
+/// enum MyRequest {
+///     Echo(String),
 /// }
 ///
 /// #[derive(Deserialize)]
@@ -54,13 +151,17 @@ use crate::component_definitions::APPLICATION_OCTET_STREAM;
 ///     let client = RemoteComponentClient::<MyRequest, MyResponse>::new(ip_address, port, 2);
 ///
 ///     // Instantiate a request.
-///     let request = MyRequest { content: "Hello, world!".to_string() };
+///     let request = MyRequest::Echo("Hello, world!".to_string());
 ///
 ///     // Send the request; typically, the client should await for a response.
 ///     client.send(request);
 /// }
 /// ```
 ///
+/// To talk to a deployed component, the client can also be created from a URL, e.g.,
+/// `RemoteComponentClient::<MyRequest, MyResponse>::builder("https://mempool.example.com:8080/")
+/// .build()`; see [`RemoteComponentClientBuilder`].
+///
 /// # Notes
 /// - The `RemoteComponentClient` struct is designed to work in an asynchronous environment,
 ///   utilizing Tokio's async runtime and hyper framwork to send HTTP requests and receive HTTP
@@ -71,15 +172,21 @@ where
     Response: DeserializeOwned,
 {
     uri: Uri,
-    client: Client<hyper::client::HttpConnector>,
-    max_retries: usize,
+    connector: HttpsConnector<HttpConnector>,
+    connection_pool: ConnectionPool<Body>,
+    grpc_connection_pool: ConnectionPool<BoxBody>,
+    retry_config: RetryConfig,
+    serialization_format: SerializationFormat,
+    transport: RemoteTransport,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    auth_header: Option<HeaderValue>,
     _req: PhantomData<Request>,
     _res: PhantomData<Response>,
 }
 
 impl<Request, Response> RemoteComponentClient<Request, Response>
 where
-    Request: Serialize,
+    Request: Serialize + RequestVariant,
     Response: DeserializeOwned,
 {
     pub fn new(ip_address: IpAddr, port: u16, max_retries: usize) -> Self {
@@ -87,63 +194,272 @@ where
             IpAddr::V4(ip_address) => format!("http://{}:{}/", ip_address, port).parse().unwrap(),
             IpAddr::V6(ip_address) => format!("http://[{}]:{}/", ip_address, port).parse().unwrap(),
         };
-        // TODO(Tsabary): Add a configuration for the maximum number of idle connections.
-        // TODO(Tsabary): Add a configuration for "keep-alive" time of idle connections.
-        let client =
-            Client::builder().http2_only(true).pool_max_idle_per_host(usize::MAX).build_http();
-        Self { uri, client, max_retries, _req: PhantomData, _res: PhantomData }
+        let retry_config = RetryConfig {
+            max_retries,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            jitter: false,
+        };
+        Self::with_connector(uri, http_connector(), retry_config)
+    }
+
+    /// Creates a client of the component served at the address of the given configuration, in
+    /// its wire format, over its transport, with its auth token and its connection pool.
+    pub fn from_config(config: &RemoteComponentCommunicationConfig) -> Self {
+        Self::new(config.ip, config.port, config.retries)
+            .with_serialization_format(config.serialization_format)
+            .with_transport(config.transport)
+            .with_auth_token(&config.auth_token)
+            .with_connection_pool_config(ConnectionPoolConfig {
+                pool_size: config.connection_pool_size,
+                idle_timeout: config.idle_connection_timeout,
+            })
+    }
+
+    /// Creates a client of the component served at the given http or https URL.
+    pub fn from_url(
+        url: &str,
+        tls_config: Option<TlsConfig>,
+        retry_config: RetryConfig,
+    ) -> Result<Self, RemoteClientCreationError> {
+        let uri: Uri = url.parse().map_err(|source| RemoteClientCreationError::InvalidUrl {
+            url: url.to_string(),
+            source,
+        })?;
+        let connector = match uri.scheme_str() {
+            Some("http") => http_connector(),
+            Some("https") => https_connector(&tls_config.unwrap_or_default())?,
+            _ => return Err(RemoteClientCreationError::UnsupportedScheme(url.to_string())),
+        };
+        Ok(Self::with_connector(uri, connector, retry_config))
     }
 
+    pub fn builder(url: &str) -> RemoteComponentClientBuilder<Request, Response> {
+        RemoteComponentClientBuilder::new(url)
+    }
+
+    fn with_connector(
+        uri: Uri,
+        connector: HttpsConnector<HttpConnector>,
+        retry_config: RetryConfig,
+    ) -> Self {
+        let pool_config = ConnectionPoolConfig::default();
+        let component = component_name::<Request>();
+        Self {
+            uri,
+            connection_pool: ConnectionPool::new(connector.clone(), &pool_config, component),
+            grpc_connection_pool: ConnectionPool::new(connector.clone(), &pool_config, component),
+            connector,
+            retry_config,
+            serialization_format: SerializationFormat::default(),
+            transport: RemoteTransport::default(),
+            circuit_breaker: None,
+            auth_header: None,
+            _req: PhantomData,
+            _res: PhantomData,
+        }
+    }
+
+    pub fn with_serialization_format(mut self, serialization_format: SerializationFormat) -> Self {
+        self.serialization_format = serialization_format;
+        self
+    }
+
+    /// Sends the requests over the given transport, which the server must serve.
+    pub fn with_transport(mut self, transport: RemoteTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Sends the requests with the given bearer token, which the server must expect. An empty token
+    /// sends the requests unauthenticated.
+    ///
+    /// # Panics
+    /// If the token is not a valid header value.
+    pub fn with_auth_token(mut self, auth_token: &str) -> Self {
+        self.auth_header = bearer_auth_header(auth_token);
+        self
+    }
+
+    /// Sends the requests over a pool of connections of the given size and idle timeout, instead of
+    /// the default one.
+    pub fn with_connection_pool_config(mut self, config: ConnectionPoolConfig) -> Self {
+        let component = component_name::<Request>();
+        self.connection_pool = ConnectionPool::new(self.connector.clone(), &config, component);
+        self.grpc_connection_pool = ConnectionPool::new(self.connector.clone(), &config, component);
+        self
+    }
+
+    /// The number of connections currently open to the server, by the client and its clones.
+    pub fn n_open_connections(&self) -> usize {
+        self.connection_pool.n_open_connections() + self.grpc_connection_pool.n_open_connections()
+    }
+
+    /// Guards the requests of the client with a circuit breaker; see [`CircuitBreakerConfig`].
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker =
+            Some(Arc::new(CircuitBreaker::new(config, component_name::<Request>())));
+        self
+    }
+
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit_breaker.as_deref()
+    }
+
+    /// Sends the request with the context of the request being handled, if any; see
+    /// [`RequestContext`].
     pub async fn send(&self, component_request: Request) -> ClientResult<Response> {
-        // Construct and request, and send it up to 'max_retries' times. Return if received a
-        // successful response.
-        for _ in 0..self.max_retries {
-            let http_request = self.construct_http_request(&component_request);
-            let res = self.try_send(http_request).await;
-            if res.is_ok() {
-                return res;
+        let context = RequestContext::current_or_new();
+        let span = context.span();
+        let context = context.sent_from(&span);
+        let Some(circuit_breaker) = &self.circuit_breaker else {
+            return self.send_with_context(component_request, &context).instrument(span).await;
+        };
+        // The circuit breaker tracks the outcome of requests, regardless of their retries.
+        let permit = circuit_breaker.acquire()?;
+        let res = self.send_with_context(component_request, &context).instrument(span).await;
+        permit.record(&res);
+        res
+    }
+
+    async fn send_with_context(
+        &self,
+        component_request: Request,
+        context: &RequestContext,
+    ) -> ClientResult<Response> {
+        // The request is serialized once, and its size is recorded once regardless of retries.
+        let histograms = PayloadSizeHistograms::new(&component_request);
+        let serialized_request = self
+            .serialization_format
+            .serialize(&component_request)
+            .expect("Request serialization should succeed");
+        histograms.record_request_size(serialized_request.len());
+        let _in_flight_request = InFlightRequest::new(histograms.component);
+
+        // Construct the request and send it, retrying up to 'max_retries' times as long as the
+        // failure is retriable.
+        let mut retry = 0;
+        loop {
+            if context.is_expired() {
+                return Err(ClientError::DeadlineExceeded);
+            }
+            let res = match self.transport {
+                RemoteTransport::Http => {
+                    let http_request =
+                        self.construct_http_request(serialized_request.clone(), context);
+                    self.try_send(http_request, &histograms).await
+                }
+                RemoteTransport::Grpc => {
+                    self.try_send_grpc(serialized_request.clone(), context, &histograms).await
+                }
+            };
+            match res {
+                Err(err) if err.is_retriable() && retry < self.retry_config.max_retries => {
+                    retry += 1;
+                    let delay = self.retry_config.delay(retry);
+                    debug!(
+                        "Request to {} failed: {}. Retry {} in {:?}.",
+                        self.uri, err, retry, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                res => return res,
             }
         }
-        // Construct and send the request, return the received respone regardless whether it
-        // successful or not.
-        let http_request = self.construct_http_request(&component_request);
-        self.try_send(http_request).await
     }
 
-    fn construct_http_request(&self, component_request: &Request) -> HyperRequest<Body> {
-        HyperRequest::post(self.uri.clone())
-            .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-            .body(Body::from(
-                serialize(component_request).expect("Request serialization should succeed"),
-            ))
-            .expect("Request building should succeed")
+    fn construct_http_request(
+        &self,
+        serialized_request: Vec<u8>,
+        context: &RequestContext,
+    ) -> HyperRequest<Body> {
+        let mut http_request = HyperRequest::post(self.uri.clone())
+            .header(CONTENT_TYPE, self.serialization_format.content_type())
+            .body(Body::from(serialized_request))
+            .expect("Request building should succeed");
+        context.insert_headers(http_request.headers_mut());
+        if let Some(auth_header) = &self.auth_header {
+            http_request.headers_mut().insert(AUTHORIZATION, auth_header.clone());
+        }
+        http_request
     }
 
-    async fn try_send(&self, http_request: HyperRequest<Body>) -> ClientResult<Response> {
+    async fn try_send(
+        &self,
+        http_request: HyperRequest<Body>,
+        histograms: &PayloadSizeHistograms,
+    ) -> ClientResult<Response> {
         let http_response = self
-            .client
+            .connection_pool
+            .client()
             .request(http_request)
             .await
             .map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
 
         match http_response.status() {
-            StatusCode::OK => get_response_body(http_response).await,
-            status_code => Err(ClientError::ResponseError(
-                status_code,
-                get_response_body(http_response).await?,
-            )),
+            StatusCode::OK => self.get_response_body(http_response, histograms).await,
+            status_code => match self.get_response_body(http_response, histograms).await? {
+                ServerError::DeadlineExceeded => Err(ClientError::DeadlineExceeded),
+                server_error => Err(ClientError::ResponseError(status_code, server_error)),
+            },
         }
     }
-}
 
-async fn get_response_body<Response>(response: HyperResponse<Body>) -> Result<Response, ClientError>
-where
-    Response: DeserializeOwned,
-{
-    let body_bytes = to_bytes(response.into_body())
-        .await
-        .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
-    deserialize(&body_bytes).map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
+    // Calls the method of the component, with the context carried as metadata. The deadline of the
+    // context is also sent as the standard gRPC timeout, for proxies to honor.
+    async fn try_send_grpc(
+        &self,
+        serialized_request: Vec<u8>,
+        context: &RequestContext,
+        histograms: &PayloadSizeHistograms,
+    ) -> ClientResult<Response> {
+        let mut grpc_request = tonic::Request::new(serialized_request);
+        let mut headers = HeaderMap::new();
+        context.insert_headers(&mut headers);
+        if let Some(auth_header) = &self.auth_header {
+            headers.insert(AUTHORIZATION, auth_header.clone());
+        }
+        *grpc_request.metadata_mut() = MetadataMap::from_headers(headers);
+        if let Some(deadline) = context.deadline {
+            grpc_request.set_timeout(deadline.saturating_duration_since(Instant::now()));
+        }
+
+        let mut grpc_client = tonic::client::Grpc::with_origin(
+            self.grpc_connection_pool.client().clone(),
+            self.uri.clone(),
+        );
+        grpc_client.ready().await.map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
+        let serialized_response = grpc_client
+            .unary(grpc_request, grpc_method::<Request>(), SerializedCodec)
+            .await
+            .map_err(client_error_of)?
+            .into_inner();
+        histograms.record_response_size(serialized_response.len());
+        self.serialization_format
+            .deserialize(&serialized_response)
+            .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
+    }
+
+    // Reads the body in the format it is tagged with, or in the format of the client if untagged.
+    async fn get_response_body<T: DeserializeOwned>(
+        &self,
+        response: HyperResponse<Body>,
+        histograms: &PayloadSizeHistograms,
+    ) -> Result<T, ClientError> {
+        let serialization_format = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(SerializationFormat::from_content_type)
+            .unwrap_or(self.serialization_format);
+        let body_bytes = to_bytes(response.into_body())
+            .await
+            .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
+        histograms.record_response_size(body_bytes.len());
+        serialization_format
+            .deserialize(&body_bytes)
+            .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
+    }
 }
 
 // Can't derive because derive forces the generics to also be `Clone`, which we prefer not to do
@@ -156,10 +472,182 @@ where
     fn clone(&self) -> Self {
         Self {
             uri: self.uri.clone(),
-            client: self.client.clone(),
-            max_retries: self.max_retries,
+            connector: self.connector.clone(),
+            connection_pool: self.connection_pool.clone(),
+            grpc_connection_pool: self.grpc_connection_pool.clone(),
+            retry_config: self.retry_config,
+            serialization_format: self.serialization_format,
+            transport: self.transport,
+            circuit_breaker: self.circuit_breaker.clone(),
+            auth_header: self.auth_header.clone(),
             _req: PhantomData,
             _res: PhantomData,
         }
     }
 }
+
+#[async_trait]
+impl<Request, Response> ComponentHealthClient for RemoteComponentClient<Request, Response>
+where
+    Request: Serialize + RequestVariant + HealthCheckRequest + Send + Sync,
+    Response: DeserializeOwned + HealthCheckResponse + Send + Sync,
+{
+    async fn is_alive(&self) -> ClientResult<bool> {
+        health_of(&self.send(Request::is_alive()).await?)
+    }
+
+    async fn is_ready(&self) -> ClientResult<bool> {
+        health_of(&self.send(Request::is_ready()).await?)
+    }
+}
+
+// For http URLs, where TLS is not used and hence no root certificates are trusted.
+fn http_connector() -> HttpsConnector<HttpConnector> {
+    let tls_config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(RootCertStore::empty())
+        .with_no_client_auth();
+    HttpsConnectorBuilder::new().with_tls_config(tls_config).https_or_http().enable_http2().build()
+}
+
+fn https_connector(
+    tls_config: &TlsConfig,
+) -> Result<HttpsConnector<HttpConnector>, RemoteClientCreationError> {
+    let builder = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_cert_store(tls_config.ca_certs_path.as_deref())?);
+    let tls_config = match &tls_config.client_identity {
+        Some(client_identity) => {
+            let (certs, key) = client_identity.load()?;
+            builder.with_client_auth_cert(certs, key).map_err(|e| {
+                RemoteClientCreationError::Tls(TlsError::IdentityLoadingFailure(e.to_string()))
+            })?
+        }
+        None => builder.with_no_client_auth(),
+    };
+    Ok(HttpsConnectorBuilder::new().with_tls_config(tls_config).https_only().enable_http2().build())
+}
+
+/// Builds a [`RemoteComponentClient`] of a deployed component from its URL, with the default retry
+/// policy, bincode serialization, no circuit breaker, no auth token, the default connection pool
+/// and the native root certificates of the platform unless set otherwise.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use serde::Serialize;
+/// use starknet_mempool_infra::component_client::{
+///     CircuitBreakerConfig,
+///     RemoteComponentClient,
+///     RetryConfig,
+///     TlsConfig,
+/// };
+/// use strum_macros::IntoStaticStr;
+///
+/// #[derive(Serialize, IntoStaticStr)]
+/// enum MyRequest {
+///     Echo(String),
+/// }
+///
+/// let client = RemoteComponentClient::<MyRequest, String>::builder("https://localhost:8080/")
+///     .tls_config(TlsConfig { ca_certs_path: None, client_identity: None })
+///     .retry_config(RetryConfig {
+///         max_retries: 5,
+///         base_delay: Duration::from_millis(50),
+///         max_delay: Duration::from_secs(1),
+///         jitter: true,
+///     })
+///     .circuit_breaker_config(CircuitBreakerConfig {
+///         failure_threshold: 5,
+///         cooldown: Duration::from_secs(10),
+///     })
+///     .build();
+/// ```
+pub struct RemoteComponentClientBuilder<Request, Response>
+where
+    Request: Serialize,
+    Response: DeserializeOwned,
+{
+    url: String,
+    tls_config: Option<TlsConfig>,
+    retry_config: RetryConfig,
+    serialization_format: SerializationFormat,
+    transport: RemoteTransport,
+    circuit_breaker_config: Option<CircuitBreakerConfig>,
+    auth_token: String,
+    connection_pool_config: ConnectionPoolConfig,
+    _req: PhantomData<Request>,
+    _res: PhantomData<Response>,
+}
+
+impl<Request, Response> RemoteComponentClientBuilder<Request, Response>
+where
+    Request: Serialize + RequestVariant,
+    Response: DeserializeOwned,
+{
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            tls_config: None,
+            retry_config: RetryConfig::default(),
+            serialization_format: SerializationFormat::default(),
+            transport: RemoteTransport::default(),
+            circuit_breaker_config: None,
+            auth_token: String::new(),
+            connection_pool_config: ConnectionPoolConfig::default(),
+            _req: PhantomData,
+            _res: PhantomData,
+        }
+    }
+
+    pub fn tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    pub fn serialization_format(mut self, serialization_format: SerializationFormat) -> Self {
+        self.serialization_format = serialization_format;
+        self
+    }
+
+    pub fn transport(mut self, transport: RemoteTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn circuit_breaker_config(mut self, circuit_breaker_config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker_config = Some(circuit_breaker_config);
+        self
+    }
+
+    pub fn auth_token(mut self, auth_token: &str) -> Self {
+        self.auth_token = auth_token.to_string();
+        self
+    }
+
+    pub fn connection_pool_config(mut self, connection_pool_config: ConnectionPoolConfig) -> Self {
+        self.connection_pool_config = connection_pool_config;
+        self
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<RemoteComponentClient<Request, Response>, RemoteClientCreationError> {
+        let client =
+            RemoteComponentClient::from_url(&self.url, self.tls_config, self.retry_config)?
+                .with_serialization_format(self.serialization_format)
+                .with_transport(self.transport)
+                .with_auth_token(&self.auth_token)
+                .with_connection_pool_config(self.connection_pool_config);
+        Ok(match self.circuit_breaker_config {
+            Some(config) => client.with_circuit_breaker(config),
+            None => client,
+        })
+    }
+}
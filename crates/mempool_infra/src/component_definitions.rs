@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
 use std::net::IpAddr;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use papyrus_config::converters::deserialize_seconds_to_duration;
 use papyrus_config::dumping::{ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
@@ -9,7 +11,19 @@ use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, Sender};
 use validator::Validate;
 
+use crate::component_client::{DEFAULT_IDLE_TIMEOUT, DEFAULT_POOL_SIZE};
+use crate::request_context::RequestContext;
+use crate::request_priority::{
+    deserialize_request_priorities,
+    serialize_request_priorities,
+    RequestPriorities,
+};
+use crate::serialization::SerializationFormat;
+
 const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 32;
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 1;
+// Well beyond the deadlines of the requests of consensus, so that only wedged servers time out.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 const DEFAULT_RETRIES: usize = 3;
 
 #[async_trait]
@@ -17,6 +31,37 @@ pub trait ComponentRequestHandler<Request, Response> {
     async fn handle_request(&mut self, request: Request) -> Response;
 }
 
+/// The liveness and readiness of a component, answered to the probes of the node, e.g., of k8s.
+/// Servers answer the probes with the standard `IsAlive` and `IsReady` request variants; see
+/// [`HealthCheckRequest`].
+#[async_trait]
+pub trait ComponentHealth {
+    /// Whether the component works, or should be restarted. Answering the probe is the proof of
+    /// life of most components.
+    async fn is_alive(&self) -> bool {
+        true
+    }
+
+    /// Whether the component can take requests, or should be taken out of rotation.
+    async fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+/// Implemented by the requests of components answering health probes, by the `IsAlive` and
+/// `IsReady` variants.
+pub trait HealthCheckRequest {
+    fn is_alive() -> Self;
+    fn is_ready() -> Self;
+}
+
+/// Implemented by the responses of components answering health probes, by the `IsAlive(bool)` and
+/// `IsReady(bool)` variants.
+pub trait HealthCheckResponse {
+    /// The answer to a probe, or `None` if the response is not to a probe.
+    fn health(&self) -> Option<bool>;
+}
+
 pub struct ComponentCommunication<T: Send + Sync> {
     tx: Option<Sender<T>>,
     rx: Option<Receiver<T>>,
@@ -43,45 +88,115 @@ where
 {
     pub request: Request,
     pub tx: Sender<Response>,
+    pub context: RequestContext,
 }
 
 pub const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+pub const APPLICATION_JSON: &str = "application/json";
 
 #[derive(Debug, Error, Deserialize, Serialize, Clone)]
 pub enum ServerError {
     #[error("Could not deserialize client request: {0}")]
     RequestDeserializationFailure(String),
+    #[error("The deadline of the request passed before it was handled.")]
+    DeadlineExceeded,
+    #[error("The request does not carry the auth token of the component.")]
+    Unauthorized,
 }
 
 // The communication configuration of the local component.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct LocalComponentCommunicationConfig {
+    #[validate(range(min = 1))]
     pub channel_buffer_size: usize,
+    // Honored by the servers of components that can be cloned, e.g., the gateway and the mempool;
+    // see `LocalComponentServer::with_max_concurrent_requests`.
+    #[validate(range(min = 1))]
+    pub max_concurrent_requests: usize,
+    // Applied by the local clients of the component; see `LocalComponentClient::with_timeout`.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub request_timeout: Duration,
+    // Honored by the servers handling requests one at a time; see
+    // `LocalComponentServer::with_request_priorities`.
+    #[serde(deserialize_with = "deserialize_request_priorities")]
+    pub request_priorities: RequestPriorities,
 }
 
 impl SerializeConfig for LocalComponentCommunicationConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
-            "channel_buffer_size",
-            &self.channel_buffer_size,
-            "The communication channel buffer size.",
-            ParamPrivacyInput::Public,
-        )])
+        BTreeMap::from_iter([
+            ser_param(
+                "channel_buffer_size",
+                &self.channel_buffer_size,
+                "The communication channel buffer size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_concurrent_requests",
+                &self.max_concurrent_requests,
+                "The maximum number of requests handled concurrently, each in a task of its own. \
+                 If 1, requests are handled one at a time.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "request_timeout",
+                &self.request_timeout.as_secs(),
+                "The time in seconds a local client waits for the answer of a request before \
+                 failing it. If 0, requests never time out.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "request_priorities",
+                &serialize_request_priorities(&self.request_priorities),
+                "The priorities of the requests queued for the component, as \
+                 'Variant1:priority1,Variant2:priority2'; queued requests of a higher priority \
+                 are handled first. Unlisted requests have priority 0.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
 
 impl Default for LocalComponentCommunicationConfig {
     fn default() -> Self {
-        Self { channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE }
+        Self {
+            channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            request_priorities: RequestPriorities::new(),
+        }
     }
 }
 
+/// The protocol remote components are served over: plain HTTP requests, or gRPC calls, which load
+/// balancers and proxies route and time out like any other gRPC service. Both run over HTTP/2, and
+/// carry the requests and responses in the serialization format of the component.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteTransport {
+    #[default]
+    Http,
+    Grpc,
+}
+
 // The communication configuration of the remote component.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct RemoteComponentCommunicationConfig {
     pub ip: IpAddr,
     pub port: u16,
     pub retries: usize,
+    // Shared by the server and its clients.
+    pub serialization_format: SerializationFormat,
+    // Shared by the server and its clients.
+    pub transport: RemoteTransport,
+    // Shared by the server and its clients. If set, the server only answers requests carrying it as
+    // a bearer token.
+    pub auth_token: String,
+    // Applied by the clients of the component; see `ConnectionPoolConfig`.
+    #[validate(range(min = 1))]
+    pub connection_pool_size: usize,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub idle_connection_timeout: Duration,
 }
 
 impl SerializeConfig for RemoteComponentCommunicationConfig {
@@ -105,12 +220,54 @@ impl SerializeConfig for RemoteComponentCommunicationConfig {
                 "The max number of retries for sending a message.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "serialization_format",
+                &self.serialization_format,
+                "The wire format of the requests and responses: 'bincode' for performance, or \
+                 'json' for debugging.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "transport",
+                &self.transport,
+                "The protocol of the requests and responses: 'http', or 'grpc' for compatibility \
+                 with gRPC load balancers.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "auth_token",
+                &self.auth_token,
+                "The bearer token the clients authenticate with to the server. If empty, requests \
+                 are not authenticated.",
+                ParamPrivacyInput::Private,
+            ),
+            ser_param(
+                "connection_pool_size",
+                &self.connection_pool_size,
+                "The number of connections each client spreads its requests over.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "idle_connection_timeout",
+                &self.idle_connection_timeout.as_secs(),
+                "The time in seconds an idle connection of a client stays open.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
 
 impl Default for RemoteComponentCommunicationConfig {
     fn default() -> Self {
-        Self { ip: "0.0.0.0".parse().unwrap(), port: 8080, retries: DEFAULT_RETRIES }
+        Self {
+            ip: "0.0.0.0".parse().unwrap(),
+            port: 8080,
+            retries: DEFAULT_RETRIES,
+            serialization_format: SerializationFormat::default(),
+            transport: RemoteTransport::default(),
+            auth_token: String::new(),
+            connection_pool_size: DEFAULT_POOL_SIZE,
+            idle_connection_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
     }
 }
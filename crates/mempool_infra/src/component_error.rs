@@ -0,0 +1,44 @@
+//! The classification of the errors returned to the clients of the components, shared by all of
+//! them, so that callers can react to a failure without matching on the errors of each component.
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use starknet_error_codes::CodedError;
+
+/// The components of the sequencer, as the origin of an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Component {
+    Batcher,
+    ClassManager,
+    Gateway,
+    Mempool,
+}
+
+impl Component {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Component::Batcher => "batcher",
+            Component::ClassManager => "class_manager",
+            Component::Gateway => "gateway",
+            Component::Mempool => "mempool",
+        }
+    }
+}
+
+impl Display for Component {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error of a request to a component, either in reaching the component or raised by it.
+pub trait ComponentError: CodedError {
+    /// The component the failed request was sent to.
+    fn component(&self) -> Component;
+
+    /// Whether the request may succeed if sent again as is, e.g., once an unreachable or
+    /// overloaded component recovers, as opposed to a request rejected on its merits, e.g., an
+    /// invalid transaction.
+    fn is_retriable(&self) -> bool;
+}
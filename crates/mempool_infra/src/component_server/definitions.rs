@@ -1,18 +1,29 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use tokio::sync::mpsc::Receiver;
-use tracing::{error, info};
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
 
 use crate::component_definitions::{ComponentRequestAndResponseSender, ComponentRequestHandler};
 use crate::component_runner::ComponentStarter;
+use crate::request_metrics::LocalRequestMetrics;
+use crate::request_priority::PrioritizedRequests;
+
+// The requests taken off the channel of a server handling them by priority. Bounded, so that a
+// server falling behind still fills its channel and holds back its clients.
+const MAX_PRIORITIZED_REQUESTS: usize = 128;
 
+// Servers are owned by the task running them, so they need not be shared between threads.
 #[async_trait]
-pub trait ComponentServerStarter: Send + Sync {
+pub trait ComponentServerStarter: Send {
     async fn start(&mut self);
 }
 
 pub async fn start_component<Component>(component: &mut Component) -> bool
 where
-    Component: ComponentStarter + Sync + Send,
+    Component: ComponentStarter + Send,
 {
     if let Err(err) = component.start().await {
         error!("ComponentServer::start() failed: {:?}", err);
@@ -32,11 +43,91 @@ pub async fn request_response_loop<Request, Response, Component>(
     Response: Send + Sync,
 {
     while let Some(request_and_res_tx) = rx.recv().await {
-        let request = request_and_res_tx.request;
-        let tx = request_and_res_tx.tx;
+        handle_and_respond(component, request_and_res_tx).await;
+    }
+}
 
-        let res = component.handle_request(request).await;
+/// Like [`request_response_loop`], but takes the queued requests off the channel and handles them
+/// by the priority given by `priority_of`, so that requests of a higher priority overtake the
+/// queued requests of a lower one under load. Requests of the same priority are handled in order.
+pub async fn prioritized_request_response_loop<Request, Response, Component>(
+    rx: &mut Receiver<ComponentRequestAndResponseSender<Request, Response>>,
+    component: &mut Component,
+    priority_of: &(dyn Fn(&Request) -> u8 + Send + Sync),
+) where
+    Component: ComponentRequestHandler<Request, Response> + Send + Sync,
+    Request: Send + Sync,
+    Response: Send + Sync,
+{
+    let mut queued_requests = PrioritizedRequests::new();
+    loop {
+        if queued_requests.is_empty() {
+            let Some(request_and_res_tx) = rx.recv().await else {
+                break;
+            };
+            let priority = priority_of(&request_and_res_tx.request);
+            queued_requests.push(request_and_res_tx, priority);
+        }
+        while queued_requests.len() < MAX_PRIORITIZED_REQUESTS {
+            let Ok(request_and_res_tx) = rx.try_recv() else {
+                break;
+            };
+            let priority = priority_of(&request_and_res_tx.request);
+            queued_requests.push(request_and_res_tx, priority);
+        }
+        let request_and_res_tx =
+            queued_requests.pop().expect("A request was queued before handling the next one.");
+        handle_and_respond(component, request_and_res_tx).await;
+    }
+}
 
-        tx.send(res).await.expect("Response connection should be open.");
+/// Like [`request_response_loop`], but handles up to `max_concurrent_requests` requests at a time,
+/// each in a task of its own with a clone of the component, so that a slow request doesn't hold
+/// back the following ones. Requests are taken off the channel only once a task is available.
+pub async fn concurrent_request_response_loop<Request, Response, Component>(
+    rx: &mut Receiver<ComponentRequestAndResponseSender<Request, Response>>,
+    component: &Component,
+    max_concurrent_requests: usize,
+) where
+    Component: ComponentRequestHandler<Request, Response> + Clone + Send + Sync + 'static,
+    Request: Send + Sync + 'static,
+    Response: Send + Sync + 'static,
+{
+    let available_tasks = Arc::new(Semaphore::new(max_concurrent_requests));
+    loop {
+        let task_permit = Arc::clone(&available_tasks)
+            .acquire_owned()
+            .await
+            .expect("The semaphore of the request tasks is never closed.");
+        let Some(request_and_res_tx) = rx.recv().await else {
+            break;
+        };
+        let mut component = component.clone();
+        tokio::spawn(async move {
+            handle_and_respond(&mut component, request_and_res_tx).await;
+            drop(task_permit);
+        });
     }
 }
+
+async fn handle_and_respond<Request, Response, Component>(
+    component: &mut Component,
+    request_and_res_tx: ComponentRequestAndResponseSender<Request, Response>,
+) where
+    Component: ComponentRequestHandler<Request, Response> + Send + Sync,
+    Request: Send + Sync,
+    Response: Send + Sync,
+{
+    let ComponentRequestAndResponseSender { request, tx, context } = request_and_res_tx;
+
+    // Requests the component sends while handling this one carry its context. Requests that can't
+    // be answered by their deadline are dropped, which the client reports as a timeout.
+    let handling_start = Instant::now();
+    let Some(res) = context.scope_until_deadline(component.handle_request(request)).await else {
+        warn!("Dropped a request whose deadline passed before it was handled.");
+        return;
+    };
+    LocalRequestMetrics::new::<Request>().record_handling_time(handling_start.elapsed());
+
+    tx.send(res).await.expect("Response connection should be open.");
+}
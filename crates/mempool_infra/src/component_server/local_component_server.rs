@@ -1,10 +1,34 @@
+use std::future::Future;
+use std::pin::Pin;
+
 use async_trait::async_trait;
 use tokio::sync::mpsc::Receiver;
-use tracing::error;
+use tracing::{error, info};
 
-use super::definitions::{request_response_loop, start_component, ComponentServerStarter};
+use super::definitions::{
+    concurrent_request_response_loop,
+    prioritized_request_response_loop,
+    request_response_loop,
+    start_component,
+    ComponentServerStarter,
+};
 use crate::component_definitions::{ComponentRequestAndResponseSender, ComponentRequestHandler};
 use crate::component_runner::ComponentStarter;
+use crate::payload_metrics::RequestVariant;
+use crate::request_priority::{request_priority, RequestPriorities};
+
+// Maps a request to its priority.
+type PriorityOf<Request> = Box<dyn Fn(&Request) -> u8 + Send + Sync>;
+
+// Handles the requests of the channel with clones of the component, each in a task of its own.
+type ConcurrentLoop<Component, Request, Response> = Box<
+    dyn for<'a> Fn(
+            &'a mut Receiver<ComponentRequestAndResponseSender<Request, Response>>,
+            &'a Component,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>
+        + Send
+        + Sync,
+>;
 
 /// The `LocalComponentServer` struct is a generic server that handles requests and responses for a
 /// specified component. It receives requests, processes them using the provided component, and
@@ -35,6 +59,7 @@ use crate::component_runner::ComponentStarter;
 ///
 /// use async_trait::async_trait;
 /// use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
+/// use starknet_mempool_infra::request_context::RequestContext;
 /// use tokio::task;
 ///
 /// use crate::starknet_mempool_infra::component_definitions::{
@@ -97,7 +122,8 @@ use crate::component_runner::ComponentStarter;
 ///     // Create the request and the response channel.
 ///     let (res_tx, mut res_rx) = tokio::sync::mpsc::channel::<MyResponse>(1);
 ///     let request = MyRequest { content: "request example".to_string() };
-///     let request_and_res_tx = ComponentRequestAndResponseSender { request, tx: res_tx };
+///     let context = RequestContext::new("example");
+///     let request_and_res_tx = ComponentRequestAndResponseSender { request, tx: res_tx, context };
 ///
 ///     // Send the request.
 ///     tx.send(request_and_res_tx).await.unwrap();
@@ -115,6 +141,8 @@ where
 {
     component: Component,
     rx: Receiver<ComponentRequestAndResponseSender<Request, Response>>,
+    priority_of: Option<PriorityOf<Request>>,
+    concurrent_loop: Option<ConcurrentLoop<Component, Request, Response>>,
 }
 
 impl<Component, Request, Response> LocalComponentServer<Component, Request, Response>
@@ -123,11 +151,64 @@ where
     Request: Send + Sync,
     Response: Send + Sync,
 {
+    /// Handles the requests in order, unless set otherwise by
+    /// [`LocalComponentServer::with_request_priorities`] or
+    /// [`LocalComponentServer::with_max_concurrent_requests`].
     pub fn new(
         component: Component,
         rx: Receiver<ComponentRequestAndResponseSender<Request, Response>>,
     ) -> Self {
-        Self { component, rx }
+        Self { component, rx, priority_of: None, concurrent_loop: None }
+    }
+}
+
+impl<Component, Request, Response> LocalComponentServer<Component, Request, Response>
+where
+    Component: ComponentRequestHandler<Request, Response>
+        + ComponentStarter
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Request: Send + Sync + 'static,
+    Response: Send + Sync + 'static,
+{
+    /// Handles up to the given number of requests concurrently, each in a task of its own with a
+    /// clone of the component, once the component is started; see
+    /// [`concurrent_request_response_loop`]. Concurrent requests are handled as they come, so
+    /// request priorities apply only if 1, i.e., if requests are handled one at a time.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        if max_concurrent_requests > 1 {
+            let concurrent_loop: ConcurrentLoop<Component, Request, Response> =
+                Box::new(move |rx, component| {
+                    Box::pin(concurrent_request_response_loop(
+                        rx,
+                        component,
+                        max_concurrent_requests,
+                    ))
+                });
+            self.concurrent_loop = Some(concurrent_loop);
+        }
+        self
+    }
+}
+
+impl<Component, Request, Response> LocalComponentServer<Component, Request, Response>
+where
+    Component: ComponentRequestHandler<Request, Response> + ComponentStarter,
+    Request: RequestVariant + Send + Sync,
+    Response: Send + Sync,
+{
+    /// Handles the queued requests by the priorities of their variants, e.g., so that the
+    /// requests of consensus overtake the bulk of the requests under load; see
+    /// [`prioritized_request_response_loop`]. Without priorities, requests are handled in order.
+    pub fn with_request_priorities(mut self, request_priorities: RequestPriorities) -> Self {
+        if !request_priorities.is_empty() {
+            let priority_of: PriorityOf<Request> =
+                Box::new(move |request| request_priority(&request_priorities, request));
+            self.priority_of = Some(priority_of);
+        }
+        self
     }
 }
 
@@ -140,8 +221,18 @@ where
     Response: Send + Sync,
 {
     async fn start(&mut self) {
-        if start_component(&mut self.component).await {
-            request_response_loop(&mut self.rx, &mut self.component).await;
+        if !start_component(&mut self.component).await {
+            return;
+        }
+        if let Some(concurrent_loop) = &self.concurrent_loop {
+            return concurrent_loop(&mut self.rx, &self.component).await;
+        }
+        match &self.priority_of {
+            Some(priority_of) => {
+                prioritized_request_response_loop(&mut self.rx, &mut self.component, priority_of)
+                    .await
+            }
+            None => request_response_loop(&mut self.rx, &mut self.component).await,
         }
     }
 }
@@ -154,6 +245,7 @@ where
 {
     component: Component,
     rx: Receiver<ComponentRequestAndResponseSender<Request, Response>>,
+    max_concurrent_requests: usize,
 }
 
 impl<Component, Request, Response> LocalActiveComponentServer<Component, Request, Response>
@@ -162,11 +254,20 @@ where
     Request: Send + Sync,
     Response: Send + Sync,
 {
+    /// Handles the requests one at a time, unless set otherwise by
+    /// [`LocalActiveComponentServer::with_max_concurrent_requests`].
     pub fn new(
         component: Component,
         rx: Receiver<ComponentRequestAndResponseSender<Request, Response>>,
     ) -> Self {
-        Self { component, rx }
+        Self { component, rx, max_concurrent_requests: 1 }
+    }
+
+    /// Handles up to the given number of requests concurrently, each in a task of its own with a
+    /// clone of the component; see [`concurrent_request_response_loop`]. Expects a positive number.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
     }
 }
 
@@ -174,21 +275,44 @@ where
 impl<Component, Request, Response> ComponentServerStarter
     for LocalActiveComponentServer<Component, Request, Response>
 where
-    Component: ComponentRequestHandler<Request, Response> + ComponentStarter + Clone + Send + Sync,
-    Request: Send + Sync,
-    Response: Send + Sync,
+    Component: ComponentRequestHandler<Request, Response>
+        + ComponentStarter
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Request: Send + Sync + 'static,
+    Response: Send + Sync + 'static,
 {
     async fn start(&mut self) {
         let mut component = self.component.clone();
         let component_future = async move { component.start().await };
-        let request_response_future = request_response_loop(&mut self.rx, &mut self.component);
+        tokio::pin!(component_future);
+        let max_concurrent_requests = self.max_concurrent_requests;
+        let request_response_future = async {
+            match max_concurrent_requests {
+                1 => request_response_loop(&mut self.rx, &mut self.component).await,
+                _ => {
+                    concurrent_request_response_loop(
+                        &mut self.rx,
+                        &self.component,
+                        max_concurrent_requests,
+                    )
+                    .await
+                }
+            }
+        };
 
         tokio::select! {
-            _res = component_future => {
+            _res = &mut component_future => {
                 error!("Component stopped.");
             }
             _res = request_response_future => {
-                error!("Server stopped.");
+                // The request channel closes once all the clients are dropped, e.g., if no other
+                // component uses this one; the component keeps running regardless.
+                info!("Server stopped: no clients remain.");
+                let _res = component_future.await;
+                error!("Component stopped.");
             }
         };
         error!("Server ended with unexpected Ok.");
@@ -1,23 +1,31 @@
+use std::convert::Infallible;
 use std::marker::PhantomData;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use async_trait::async_trait;
-use bincode::{deserialize, serialize};
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::server::conn::AddrIncoming;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request as HyperRequest, Response as HyperResponse, Server, StatusCode};
+use hyper_rustls::TlsAcceptor;
+use rustls::ServerConfig;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use tokio::sync::Mutex;
+use tonic::body::BoxBody;
+use tonic::codegen::{BoxFuture, Service};
+use tonic::Status;
 
 use super::definitions::ComponentServerStarter;
-use crate::component_definitions::{
-    ComponentRequestHandler,
-    ServerError,
-    APPLICATION_OCTET_STREAM,
-};
+use crate::auth::{bearer_auth_header, is_authorized};
+use crate::component_definitions::{ComponentRequestHandler, RemoteTransport, ServerError};
+use crate::grpc::{grpc_method, SerializedCodec};
+use crate::request_context::{RequestContext, UNKNOWN_ORIGIN};
+use crate::serialization::SerializationFormat;
+use crate::tls::{ServerTlsConfig, TlsResult};
 
 /// The `RemoteComponentServer` struct is a generic server that handles requests and responses for a
 /// specified component. It receives requests, processes them using the provided component, and
@@ -38,6 +46,15 @@ use crate::component_definitions::{
 ///
 /// - `component`: The component responsible for handling the requests and generating responses.
 /// - `socket`: A socket address for the server to listen on.
+/// - `tls_config`: If set, connections are encrypted, and clients may be required to authenticate
+///   with a certificate; see [`RemoteComponentServer::with_tls_config`].
+/// - `serialization_format`: The wire format of requests sent without a content type. Requests
+///   tagged with a content type are read, and answered, in its format. gRPC calls are always read,
+///   and answered, in this format.
+/// - `transport`: Whether the component is served over plain HTTP, or over gRPC; see
+///   [`RemoteTransport`].
+/// - `auth_header`: If set, requests not carrying this `authorization` header are rejected; see
+///   [`RemoteComponentServer::with_auth_token`].
 ///
 /// # Example
 /// ```rust
@@ -110,6 +127,10 @@ where
 {
     socket: SocketAddr,
     component: Arc<Mutex<Component>>,
+    tls_config: Option<Arc<ServerConfig>>,
+    serialization_format: SerializationFormat,
+    transport: RemoteTransport,
+    auth_header: Option<HeaderValue>,
     _req: PhantomData<Request>,
     _res: PhantomData<Response>,
 }
@@ -124,35 +145,112 @@ where
         Self {
             component: Arc::new(Mutex::new(component)),
             socket: SocketAddr::new(ip_address, port),
+            tls_config: None,
+            serialization_format: SerializationFormat::default(),
+            transport: RemoteTransport::default(),
+            auth_header: None,
             _req: PhantomData,
             _res: PhantomData,
         }
     }
 
+    /// Serves over TLS, with mutual authentication if the configuration sets the certificate
+    /// authorities of the clients.
+    pub fn with_tls_config(mut self, tls_config: &ServerTlsConfig) -> TlsResult<Self> {
+        self.tls_config = Some(tls_config.server_config()?);
+        Ok(self)
+    }
+
+    pub fn with_serialization_format(mut self, serialization_format: SerializationFormat) -> Self {
+        self.serialization_format = serialization_format;
+        self
+    }
+
+    pub fn with_transport(mut self, transport: RemoteTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Only answers the requests carrying the given bearer token, and rejects the others as
+    /// unauthorized. An empty token answers all the requests.
+    ///
+    /// # Panics
+    /// If the token is not a valid header value.
+    pub fn with_auth_token(mut self, auth_token: &str) -> Self {
+        self.auth_header = bearer_auth_header(auth_token);
+        self
+    }
+
     async fn handler(
         http_request: HyperRequest<Body>,
         component: Arc<Mutex<Component>>,
+        default_serialization_format: SerializationFormat,
+        auth_header: Option<HeaderValue>,
     ) -> Result<HyperResponse<Body>, hyper::Error> {
+        if let Some(auth_header) = &auth_header {
+            if !is_authorized(http_request.headers(), auth_header) {
+                let http_response = error_response(
+                    StatusCode::UNAUTHORIZED,
+                    ServerError::Unauthorized,
+                    default_serialization_format,
+                )
+                .expect("Response building should succeed");
+                return Ok(http_response);
+            }
+        }
+        // Requests sent without a context, e.g., by other HTTP clients, start a new flow.
+        let context = RequestContext::from_headers(http_request.headers())
+            .unwrap_or_else(|| RequestContext::new(UNKNOWN_ORIGIN));
+        let serialization_format = match http_request.headers().get(CONTENT_TYPE) {
+            None => default_serialization_format,
+            Some(content_type) => {
+                match content_type.to_str().ok().and_then(SerializationFormat::from_content_type) {
+                    Some(serialization_format) => serialization_format,
+                    None => {
+                        let http_response = error_response(
+                            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                            ServerError::RequestDeserializationFailure(format!(
+                                "Unsupported content type: {content_type:?}."
+                            )),
+                            default_serialization_format,
+                        )
+                        .expect("Response building should succeed");
+                        return Ok(http_response);
+                    }
+                }
+            }
+        };
         let body_bytes = to_bytes(http_request.into_body()).await?;
-        let http_response = match deserialize(&body_bytes) {
+        let http_response = match serialization_format.deserialize(&body_bytes) {
             Ok(component_request) => {
-                // Acquire the lock for component computation, release afterwards.
-                let component_response =
-                    { component.lock().await.handle_request(component_request).await };
-                HyperResponse::builder()
-                    .status(StatusCode::OK)
-                    .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-                    .body(Body::from(
-                        serialize(&component_response)
-                            .expect("Response serialization should succeed"),
-                    ))
-            }
-            Err(error) => {
-                let server_error = ServerError::RequestDeserializationFailure(error.to_string());
-                HyperResponse::builder().status(StatusCode::BAD_REQUEST).body(Body::from(
-                    serialize(&server_error).expect("Server error serialization should succeed"),
-                ))
+                // Acquire the lock for component computation, release afterwards. Requests the
+                // component sends while handling this one carry its context.
+                let component_response = context
+                    .scope_until_deadline(async {
+                        component.lock().await.handle_request(component_request).await
+                    })
+                    .await;
+                match component_response {
+                    Some(component_response) => HyperResponse::builder()
+                        .status(StatusCode::OK)
+                        .header(CONTENT_TYPE, serialization_format.content_type())
+                        .body(Body::from(
+                            serialization_format
+                                .serialize(&component_response)
+                                .expect("Response serialization should succeed"),
+                        )),
+                    None => error_response(
+                        StatusCode::GATEWAY_TIMEOUT,
+                        ServerError::DeadlineExceeded,
+                        serialization_format,
+                    ),
+                }
             }
+            Err(error) => error_response(
+                StatusCode::BAD_REQUEST,
+                ServerError::RequestDeserializationFailure(error.to_string()),
+                serialization_format,
+            ),
         }
         .expect("Response building should succeed");
 
@@ -169,15 +267,185 @@ where
     Response: Serialize + Send + Sync + 'static,
 {
     async fn start(&mut self) {
-        let make_svc = make_service_fn(|_conn| {
-            let component = Arc::clone(&self.component);
-            async {
-                Ok::<_, hyper::Error>(service_fn(move |req| {
-                    Self::handler(req, Arc::clone(&component))
-                }))
-            }
+        // The connection type differs with TLS, and the response body with the transport, hence
+        // the service is made in each branch.
+        let component = &self.component;
+        let serialization_format = self.serialization_format;
+        let auth_header = &self.auth_header;
+        let tls_acceptor = self.tls_config.as_ref().map(|tls_config| {
+            TlsAcceptor::new(Arc::clone(tls_config), AddrIncoming::bind(&self.socket).unwrap())
         });
+        match (tls_acceptor, self.transport) {
+            (Some(tls_acceptor), RemoteTransport::Http) => {
+                let make_svc = make_service_fn(|_conn| {
+                    let component = Arc::clone(component);
+                    let auth_header = auth_header.clone();
+                    async move {
+                        Ok::<_, hyper::Error>(service_fn(move |req| {
+                            Self::handler(
+                                req,
+                                Arc::clone(&component),
+                                serialization_format,
+                                auth_header.clone(),
+                            )
+                        }))
+                    }
+                });
+                Server::builder(tls_acceptor).serve(make_svc).await.unwrap();
+            }
+            (None, RemoteTransport::Http) => {
+                let make_svc = make_service_fn(|_conn| {
+                    let component = Arc::clone(component);
+                    let auth_header = auth_header.clone();
+                    async move {
+                        Ok::<_, hyper::Error>(service_fn(move |req| {
+                            Self::handler(
+                                req,
+                                Arc::clone(&component),
+                                serialization_format,
+                                auth_header.clone(),
+                            )
+                        }))
+                    }
+                });
+                Server::bind(&self.socket).serve(make_svc).await.unwrap();
+            }
+            (Some(tls_acceptor), RemoteTransport::Grpc) => {
+                let make_svc = make_service_fn(|_conn| {
+                    let component = Arc::clone(component);
+                    let auth_header = auth_header.clone();
+                    async move {
+                        Ok::<_, hyper::Error>(service_fn(move |req| {
+                            grpc_handler(
+                                req,
+                                Arc::clone(&component),
+                                serialization_format,
+                                auth_header.clone(),
+                            )
+                        }))
+                    }
+                });
+                Server::builder(tls_acceptor).http2_only(true).serve(make_svc).await.unwrap();
+            }
+            (None, RemoteTransport::Grpc) => {
+                let make_svc = make_service_fn(|_conn| {
+                    let component = Arc::clone(component);
+                    let auth_header = auth_header.clone();
+                    async move {
+                        Ok::<_, hyper::Error>(service_fn(move |req| {
+                            grpc_handler(
+                                req,
+                                Arc::clone(&component),
+                                serialization_format,
+                                auth_header.clone(),
+                            )
+                        }))
+                    }
+                });
+                Server::bind(&self.socket).http2_only(true).serve(make_svc).await.unwrap();
+            }
+        }
+    }
+}
+
+// Answers a gRPC call of the method of the component, with the context of the call carried as
+// metadata. Calls of other methods are answered as unimplemented, and calls without the expected
+// auth token as unauthenticated.
+async fn grpc_handler<Component, Request, Response>(
+    http_request: HyperRequest<Body>,
+    component: Arc<Mutex<Component>>,
+    serialization_format: SerializationFormat,
+    auth_header: Option<HeaderValue>,
+) -> Result<HyperResponse<BoxBody>, Infallible>
+where
+    Component: ComponentRequestHandler<Request, Response> + Send + 'static,
+    Request: DeserializeOwned + Send + 'static,
+    Response: Serialize + Send + 'static,
+{
+    let path = http_request.uri().path();
+    if path != grpc_method::<Request>().path() {
+        return Ok(Status::unimplemented(format!("Unknown method: {path}.")).to_http());
+    }
+    if let Some(auth_header) = &auth_header {
+        if !is_authorized(http_request.headers(), auth_header) {
+            return Ok(Status::unauthenticated(ServerError::Unauthorized.to_string()).to_http());
+        }
+    }
+    let context = RequestContext::from_headers(http_request.headers())
+        .unwrap_or_else(|| RequestContext::new(UNKNOWN_ORIGIN));
+    let method = GrpcMethod {
+        component,
+        context,
+        serialization_format,
+        _req: PhantomData::<Request>,
+        _res: PhantomData::<Response>,
+    };
+    Ok(tonic::server::Grpc::new(SerializedCodec).unary(method, http_request).await)
+}
+
+// The gRPC method of a component, handling a call as the HTTP handler handles a request.
+struct GrpcMethod<Component, Request, Response> {
+    component: Arc<Mutex<Component>>,
+    context: RequestContext,
+    serialization_format: SerializationFormat,
+    _req: PhantomData<Request>,
+    _res: PhantomData<Response>,
+}
 
-        Server::bind(&self.socket.clone()).serve(make_svc).await.unwrap();
+impl<Component, Request, Response> Service<tonic::Request<Vec<u8>>>
+    for GrpcMethod<Component, Request, Response>
+where
+    Component: ComponentRequestHandler<Request, Response> + Send + 'static,
+    Request: DeserializeOwned + Send + 'static,
+    Response: Serialize + Send + 'static,
+{
+    type Response = tonic::Response<Vec<u8>>;
+    type Error = Status;
+    type Future = BoxFuture<Self::Response, Status>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Status>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, grpc_request: tonic::Request<Vec<u8>>) -> Self::Future {
+        let component = Arc::clone(&self.component);
+        let context = self.context.clone();
+        let serialization_format = self.serialization_format;
+        Box::pin(async move {
+            let component_request: Request =
+                serialization_format.deserialize(grpc_request.get_ref()).map_err(|error| {
+                    Status::invalid_argument(
+                        ServerError::RequestDeserializationFailure(error.to_string()).to_string(),
+                    )
+                })?;
+            let component_response = context
+                .scope_until_deadline(async {
+                    component.lock().await.handle_request(component_request).await
+                })
+                .await
+                .ok_or_else(|| {
+                    Status::deadline_exceeded(ServerError::DeadlineExceeded.to_string())
+                })?;
+            Ok(tonic::Response::new(
+                serialization_format
+                    .serialize(&component_response)
+                    .expect("Response serialization should succeed"),
+            ))
+        })
     }
 }
+
+fn error_response(
+    status_code: StatusCode,
+    server_error: ServerError,
+    serialization_format: SerializationFormat,
+) -> hyper::http::Result<HyperResponse<Body>> {
+    HyperResponse::builder()
+        .status(status_code)
+        .header(CONTENT_TYPE, serialization_format.content_type())
+        .body(Body::from(
+            serialization_format
+                .serialize(&server_error)
+                .expect("Server error serialization should succeed"),
+        ))
+}
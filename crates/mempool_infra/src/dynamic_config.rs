@@ -0,0 +1,21 @@
+use std::sync::{Arc, RwLock};
+
+/// A configuration whose dynamic params may change at runtime, once the node config is reloaded.
+/// Components take a copy of it for each operation, so that an operation is never run with a
+/// mix of the old and the new values.
+#[derive(Clone, Debug, Default)]
+pub struct SharedDynamicConfig<T>(Arc<RwLock<T>>);
+
+impl<T: Clone> SharedDynamicConfig<T> {
+    pub fn new(config: T) -> Self {
+        Self(Arc::new(RwLock::new(config)))
+    }
+
+    pub fn get(&self) -> T {
+        self.0.read().expect("Dynamic config lock should not be poisoned.").clone()
+    }
+
+    pub fn set(&self, config: T) {
+        *self.0.write().expect("Dynamic config lock should not be poisoned.") = config;
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut};
+use hyper::http::uri::PathAndQuery;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::{Code, Status};
+
+use crate::component_client::ClientError;
+use crate::payload_metrics::component_name;
+
+/// The gRPC method of the requests to a component, e.g., `/starknet.Mempool/Handle` for
+/// `MempoolRequest`. Each component serves a single method, as its requests are an enum.
+pub fn grpc_method<Request>() -> PathAndQuery {
+    format!("/starknet.{}/Handle", component_name::<Request>())
+        .parse()
+        .expect("Component names should be valid URI path segments.")
+}
+
+/// Maps the status of a failed gRPC call to the error of the client. Statuses the server answers
+/// with as the HTTP transport does are mapped to the same errors.
+pub(crate) fn client_error_of(status: Status) -> ClientError {
+    match status.code() {
+        Code::DeadlineExceeded => ClientError::DeadlineExceeded,
+        _ => ClientError::GrpcFailure(Arc::new(status)),
+    }
+}
+
+/// Frames messages that are already serialized, so that the gRPC transport carries requests and
+/// responses in the serialization format of the component, as the HTTP transport does.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SerializedCodec;
+
+impl Codec for SerializedCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = SerializedCodec;
+    type Decoder = SerializedCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        SerializedCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        SerializedCodec
+    }
+}
+
+impl Encoder for SerializedCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for SerializedCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Vec<u8>>, Status> {
+        let mut item = vec![0; src.remaining()];
+        src.copy_to_slice(&mut item);
+        Ok(Some(item))
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{error, warn};
+
+pub const DEFAULT_JOURNAL_QUEUE_CAPACITY: usize = 10_000;
+
+/// How a journal writes its entries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JournalOptions {
+    /// The maximal number of entries waiting to be written; entries recorded beyond it are
+    /// dropped.
+    pub queue_capacity: usize,
+    /// If true, the written entries are synced to the disk before the next ones are written, so
+    /// that they survive a crash of the machine, and not only of the process.
+    pub sync: bool,
+}
+
+impl Default for JournalOptions {
+    fn default() -> Self {
+        Self { queue_capacity: DEFAULT_JOURNAL_QUEUE_CAPACITY, sync: false }
+    }
+}
+
+enum JournalCommand<T> {
+    Append(T),
+    Truncate,
+}
+
+/// An append-only journal of entries stored as JSON lines. The entries are written by a dedicated
+/// thread, so recording an entry never blocks on the disk; the component recording them is not
+/// failed by the journal, and entries that can't be written are logged and skipped.
+#[derive(Debug)]
+pub struct JsonLinesJournal<T> {
+    path: PathBuf,
+    sender: SyncSender<JournalCommand<T>>,
+    writer: JoinHandle<()>,
+    _entry: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + 'static> JsonLinesJournal<T> {
+    /// Opens the journal for appending, creating its file if missing.
+    pub fn open(path: &Path, options: JournalOptions) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (sender, receiver) = sync_channel(options.queue_capacity);
+        let writer =
+            JournalWriter { path: path.to_path_buf(), file: BufWriter::new(file), options };
+        let writer = thread::Builder::new()
+            .name("journal-writer".to_owned())
+            .spawn(move || writer.run(receiver))?;
+        Ok(Self { path: path.to_path_buf(), sender, writer, _entry: PhantomData })
+    }
+
+    /// Records an entry, to be written after the entries recorded before it.
+    pub fn append(&self, entry: T) {
+        self.send(JournalCommand::Append(entry));
+    }
+
+    /// Discards the entries recorded so far.
+    pub fn truncate(&self) {
+        self.send(JournalCommand::Truncate);
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Closes the journal, once the entries recorded so far are written.
+    pub fn close(self) {
+        let Self { path, sender, writer, .. } = self;
+        drop(sender);
+        if writer.join().is_err() {
+            error!("The writer of the journal {:?} panicked.", path);
+        }
+    }
+
+    fn send(&self, command: JournalCommand<T>) {
+        match self.sender.try_send(command) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                error!("The journal {:?} is falling behind; dropping an entry.", self.path)
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                error!("The writer of the journal {:?} stopped; dropping an entry.", self.path)
+            }
+        }
+    }
+}
+
+struct JournalWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+    options: JournalOptions,
+}
+
+impl JournalWriter {
+    // Writes the entries as they are recorded, flushing once no more are waiting. Returns once the
+    // journal is dropped.
+    fn run<T: Serialize>(mut self, receiver: Receiver<JournalCommand<T>>) {
+        while let Ok(command) = receiver.recv() {
+            self.handle(command);
+            while let Ok(command) = receiver.try_recv() {
+                self.handle(command);
+            }
+            if let Err(err) = self.flush() {
+                error!("Failed to flush the journal {:?}: {}", self.path, err);
+            }
+        }
+    }
+
+    fn handle<T: Serialize>(&mut self, command: JournalCommand<T>) {
+        let result = match command {
+            JournalCommand::Append(entry) => self.append(&entry),
+            JournalCommand::Truncate => self.truncate(),
+        };
+        if let Err(err) = result {
+            error!("Failed to write to the journal {:?}: {}", self.path, err);
+        }
+    }
+
+    fn append<T: Serialize>(&mut self, entry: &T) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.file, entry)?;
+        self.file.write_all(b"\n")
+    }
+
+    fn truncate(&mut self) -> std::io::Result<()> {
+        // The buffered entries are written before the file is truncated, and not after.
+        self.file.flush()?;
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.file = BufWriter::new(file);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        if self.options.sync {
+            self.file.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the entries of a journal, or none if its file is missing. The last line is dropped if
+/// torn, e.g., by a crash while it was written.
+pub fn read_json_lines<T: DeserializeOwned>(path: &Path) -> std::io::Result<Vec<T>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+    let mut entries = vec![];
+    for line in BufReader::new(file).lines() {
+        match serde_json::from_str(&line?) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => {
+                warn!("Dropping a malformed line of the journal {:?}: {}", path, err);
+                break;
+            }
+        }
+    }
+    Ok(entries)
+}
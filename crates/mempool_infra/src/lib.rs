@@ -1,5 +1,28 @@
+#[cfg(feature = "allocation_counting")]
+pub mod allocation_counter;
+pub mod auth;
+pub mod broadcast;
+pub mod channel_metrics;
 pub mod component_client;
 pub mod component_definitions;
+pub mod component_error;
 pub mod component_runner;
 pub mod component_server;
+pub mod dynamic_config;
+pub mod grpc;
+pub mod journal;
+pub mod log_format;
+pub mod payload_metrics;
+pub mod request_context;
+pub mod request_metrics;
+pub mod request_priority;
+pub mod resource_monitor;
+pub mod serialization;
+pub mod tls;
+pub mod trace_export;
 pub mod trace_util;
+
+#[cfg(feature = "allocation_counting")]
+// Count the allocations of the hot paths.
+#[global_allocator]
+static ALLOC: allocation_counter::CountingAllocator = allocation_counter::CountingAllocator;
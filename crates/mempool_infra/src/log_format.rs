@@ -0,0 +1,128 @@
+//! The formats of the logs of the node.
+//!
+//! In the JSON format, each log is a single line object for log pipelines to ingest. The fields of
+//! the spans a log is in are flattened into it, so that identifiers recorded once on a span, e.g.,
+//! the `proposal_id` of the batcher, can be filtered on in all the logs of its handling. The
+//! components log identifiers under the same names: `proposal_id`, `tx_hash` and `component`, the
+//! component that logged it.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+use validator::Validate;
+
+// The prefix of the crates of the components, omitted from the component a log is attributed to.
+const COMPONENT_CRATE_PREFIX: &str = "starknet_";
+
+/// The format of the logs of the node.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Compact human readable lines.
+    #[default]
+    Pretty,
+    /// A JSON object per line.
+    Json,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+}
+
+impl SerializeConfig for LoggingConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "format",
+            &self.format,
+            "The format of the logs of the node, either 'pretty' or 'json', a JSON object per \
+             line for log pipelines.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+/// Formats a log as a single line JSON object, of its time, level, component and message, along
+/// with its fields and the fields of the spans it is in. Expects the fields of the spans to be
+/// recorded as JSON objects, by [`JsonFields`](tracing_subscriber::fmt::format::JsonFields).
+pub struct JsonLogFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonLogFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let mut log = Map::new();
+        log.insert("timestamp".to_string(), timestamp.into());
+        log.insert("level".to_string(), metadata.level().as_str().into());
+        if let Some(component) = metadata.module_path().map(component_of) {
+            log.insert("component".to_string(), component.into());
+        }
+        log.insert("target".to_string(), metadata.target().into());
+        // Fields of inner spans override the ones of outer spans, and the fields of the log
+        // override both.
+        for span in ctx.event_scope().into_iter().flat_map(|scope| scope.from_root()) {
+            let extensions = span.extensions();
+            let Some(span_fields) = extensions.get::<FormattedFields<N>>() else {
+                continue;
+            };
+            if let Ok(Value::Object(span_fields)) = serde_json::from_str(span_fields) {
+                log.extend(span_fields);
+            }
+        }
+        event.record(&mut JsonVisitor(&mut log));
+
+        let line = serde_json::to_string(&log).map_err(|_| std::fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+// The component of the given module, named after its crate.
+fn component_of(module_path: &str) -> &str {
+    let crate_name = module_path.split("::").next().unwrap_or(module_path);
+    crate_name.strip_prefix(COMPONENT_CRATE_PREFIX).unwrap_or(crate_name)
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
@@ -0,0 +1,71 @@
+/// The size in bytes of the serialized requests sent by remote component clients.
+pub const REQUEST_PAYLOAD_SIZE: &str = "remote_client_request_payload_size";
+/// The size in bytes of the serialized responses received by remote component clients.
+pub const RESPONSE_PAYLOAD_SIZE: &str = "remote_client_response_payload_size";
+const COMPONENT_LABEL: &str = "component";
+const REQUEST_VARIANT_LABEL: &str = "request_variant";
+
+/// Exports the sizes of the payloads of a remote component request, and of its response, as
+/// histograms labeled with the component and the request variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayloadSizeHistograms {
+    pub component: &'static str,
+    pub request_variant: &'static str,
+}
+
+impl PayloadSizeHistograms {
+    pub fn new<Request: RequestVariant>(request: &Request) -> Self {
+        Self { component: component_name::<Request>(), request_variant: request.variant_name() }
+    }
+
+    pub fn record_request_size(&self, n_bytes: usize) {
+        metrics::histogram!(
+            REQUEST_PAYLOAD_SIZE,
+            to_histogram_value(n_bytes),
+            COMPONENT_LABEL => self.component,
+            REQUEST_VARIANT_LABEL => self.request_variant
+        );
+    }
+
+    pub fn record_response_size(&self, n_bytes: usize) {
+        metrics::histogram!(
+            RESPONSE_PAYLOAD_SIZE,
+            to_histogram_value(n_bytes),
+            COMPONENT_LABEL => self.component,
+            REQUEST_VARIANT_LABEL => self.request_variant
+        );
+    }
+}
+
+// Payload sizes are far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_histogram_value(n_bytes: usize) -> f64 {
+    n_bytes as f64
+}
+
+/// Returns the name of the component served for requests of the given type, which is the name of
+/// the type without its path, a `Versioned` prefix and a `Request` suffix, e.g., `Mempool` for
+/// `MempoolRequest`.
+pub fn component_name<Request>() -> &'static str {
+    let type_name = std::any::type_name::<Request>();
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
+    let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+    let type_name = type_name.strip_prefix("Versioned").unwrap_or(type_name);
+    type_name.strip_suffix("Request").unwrap_or(type_name)
+}
+
+/// A request named by its variant, e.g., `AddTx`, in the metrics and the priorities of requests.
+/// Implemented for the requests deriving `strum_macros::IntoStaticStr`; wrapped requests (e.g., a
+/// request with its version) are named by the variant of the wrapped request.
+pub trait RequestVariant {
+    fn variant_name(&self) -> &'static str;
+}
+
+impl<Request> RequestVariant for Request
+where
+    for<'a> &'a Request: Into<&'static str>,
+{
+    fn variant_name(&self) -> &'static str {
+        self.into()
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::time::Duration;
+
+use hyper::header::HeaderValue;
+use hyper::HeaderMap;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tracing::{info_span, Instrument, Span};
+
+use crate::trace_export::{TraceParent, TRACE_PARENT_HEADER};
+
+/// The HTTP header carrying the correlation id of a remote component request.
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+/// The HTTP header carrying the component a remote component request originated from.
+pub const ORIGIN_COMPONENT_HEADER: &str = "x-origin-component";
+/// The HTTP header carrying the time left until the deadline of a remote component request, in
+/// milliseconds. A duration rather than a point in time, so that it holds across hosts whose clocks
+/// differ.
+pub const DEADLINE_HEADER: &str = "x-deadline-ms";
+
+/// The origin of requests sent outside the handling of any other request.
+pub const UNKNOWN_ORIGIN: &str = "unknown";
+
+tokio::task_local! {
+    // The context of the request the current task handles.
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// Identifies all the component requests made on behalf of a single request, e.g., the handling of
+/// a transaction submitted to the gateway.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CorrelationId(pub u64);
+
+impl CorrelationId {
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for CorrelationId {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(Self)
+    }
+}
+
+/// The context a component request is sent with, attached to the tracing spans of its sending and
+/// handling. Requests sent while handling a request carry the context of the handled request, so
+/// that a single transaction can be followed across components in the logs, and so that they
+/// respect its deadline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestContext {
+    pub correlation_id: CorrelationId,
+    // The component the first request of the correlated requests was made by.
+    pub origin: String,
+    // The time by which the response is of use to the first request; servers drop the requests
+    // they can't answer by then. Sent over HTTP as the time left until it.
+    #[serde(skip)]
+    pub deadline: Option<Instant>,
+    // The span the request is sent from, whose trace the spans of its handling join, if the spans
+    // are exported. Sent over HTTP in the `traceparent` header.
+    #[serde(skip)]
+    pub trace_parent: Option<TraceParent>,
+}
+
+impl RequestContext {
+    /// A context for a new flow of requests, originating at the given component.
+    pub fn new(origin: &str) -> Self {
+        Self {
+            correlation_id: CorrelationId::random(),
+            origin: origin.to_string(),
+            deadline: None,
+            trace_parent: None,
+        }
+    }
+
+    /// Sets the deadline of the requests sent within this context. A context already bounded by an
+    /// earlier deadline keeps it, as the requests are of no use past it.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(self.deadline.map_or(deadline, |current| current.min(deadline)));
+        self
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// The context of the request handled by the current task, if any.
+    pub fn current() -> Option<Self> {
+        REQUEST_CONTEXT.try_with(Clone::clone).ok()
+    }
+
+    /// The context to send a request with: that of the request being handled, or a new one of
+    /// unknown origin otherwise.
+    pub fn current_or_new() -> Self {
+        Self::current().unwrap_or_else(|| Self::new(UNKNOWN_ORIGIN))
+    }
+
+    pub fn span(&self) -> Span {
+        info_span!("request", correlation_id = %self.correlation_id, origin = %self.origin)
+    }
+
+    /// The context to send a request from the given span with, so that the spans of its handling
+    /// join the trace of the span.
+    pub fn sent_from(mut self, span: &Span) -> Self {
+        self.trace_parent = TraceParent::of(span);
+        self
+    }
+
+    /// Runs the given future within this context: requests sent by it carry this context, and its
+    /// logs are in the span of this context, which joins the trace of the span the context was sent
+    /// from.
+    pub async fn scope<F: Future>(mut self, future: F) -> F::Output {
+        let span = self.span();
+        // Spans of nested scopes join the trace of this one.
+        if let Some(trace_parent) = self.trace_parent.take() {
+            trace_parent.adopt(&span);
+        }
+        REQUEST_CONTEXT.scope(self, future.instrument(span)).await
+    }
+
+    /// Like [`RequestContext::scope`], but returns None instead of the output of the future if the
+    /// deadline passes before it completes, in which case the future is dropped; futures whose
+    /// deadline already passed are not polled at all.
+    pub async fn scope_until_deadline<F: Future>(self, future: F) -> Option<F::Output> {
+        if self.is_expired() {
+            return None;
+        }
+        match self.deadline {
+            Some(deadline) => self.scope(tokio::time::timeout_at(deadline, future)).await.ok(),
+            None => Some(self.scope(future).await),
+        }
+    }
+
+    pub(crate) fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(
+            CORRELATION_ID_HEADER,
+            HeaderValue::from_str(&self.correlation_id.to_string())
+                .expect("Correlation ids should be valid header values."),
+        );
+        // Origins are component names, yet an invalid one shouldn't fail the request.
+        if let Ok(origin) = HeaderValue::from_str(&self.origin) {
+            headers.insert(ORIGIN_COMPONENT_HEADER, origin);
+        }
+        if let Some(deadline) = self.deadline {
+            let time_left = deadline.saturating_duration_since(Instant::now()).as_millis();
+            headers.insert(
+                DEADLINE_HEADER,
+                HeaderValue::from(u64::try_from(time_left).unwrap_or(u64::MAX)),
+            );
+        }
+        if let Some(trace_parent) = self.trace_parent {
+            headers.insert(
+                TRACE_PARENT_HEADER,
+                HeaderValue::from_str(&trace_parent.to_string())
+                    .expect("Trace parents should be valid header values."),
+            );
+        }
+    }
+
+    /// The context the headers carry, if any.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let correlation_id = headers.get(CORRELATION_ID_HEADER)?.to_str().ok()?.parse().ok()?;
+        let origin = headers
+            .get(ORIGIN_COMPONENT_HEADER)
+            .and_then(|origin| origin.to_str().ok())
+            .unwrap_or(UNKNOWN_ORIGIN);
+        // The deadline is counted from the arrival of the request; its transfer time is not
+        // deducted.
+        let deadline = headers
+            .get(DEADLINE_HEADER)
+            .and_then(|time_left| time_left.to_str().ok()?.parse().ok())
+            .map(|time_left| Instant::now() + Duration::from_millis(time_left));
+        let trace_parent = headers
+            .get(TRACE_PARENT_HEADER)
+            .and_then(|trace_parent| TraceParent::parse(trace_parent.to_str().ok()?));
+        Some(Self { correlation_id, origin: origin.to_string(), deadline, trace_parent })
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc::Sender;
+
+use crate::payload_metrics::component_name;
+
+/// The time from sending a request to a local component to receiving its response, in seconds,
+/// including the time the request was queued.
+pub const LOCAL_REQUEST_LATENCY: &str = "local_component_request_latency_seconds";
+/// The time a local component spent handling a request, in seconds.
+pub const LOCAL_REQUEST_HANDLING_TIME: &str = "local_component_request_handling_seconds";
+/// The number of requests queued for a local component, sampled as requests are sent and
+/// answered.
+pub const LOCAL_REQUEST_QUEUE_DEPTH: &str = "local_component_request_queue_depth";
+const COMPONENT_LABEL: &str = "component";
+
+/// Exports the latencies of the requests to a local component, and the depth of its request queue,
+/// labeled with the component, so that a component that falls behind under load stands out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocalRequestMetrics {
+    pub component: &'static str,
+}
+
+impl LocalRequestMetrics {
+    /// The metrics of the component serving requests of the given type; see [`component_name`].
+    pub fn new<Request>() -> Self {
+        Self { component: component_name::<Request>() }
+    }
+
+    /// Records the number of requests queued in the channel of `sender`, and returns it.
+    pub fn record_queue_depth<T>(&self, sender: &Sender<T>) -> usize {
+        let depth = sender.max_capacity() - sender.capacity();
+        metrics::gauge!(
+            LOCAL_REQUEST_QUEUE_DEPTH,
+            to_gauge_value(depth),
+            COMPONENT_LABEL => self.component
+        );
+        depth
+    }
+
+    pub fn record_latency(&self, latency: Duration) {
+        metrics::histogram!(
+            LOCAL_REQUEST_LATENCY,
+            latency.as_secs_f64(),
+            COMPONENT_LABEL => self.component
+        );
+    }
+
+    pub fn record_handling_time(&self, handling_time: Duration) {
+        metrics::histogram!(
+            LOCAL_REQUEST_HANDLING_TIME,
+            handling_time.as_secs_f64(),
+            COMPONENT_LABEL => self.component
+        );
+    }
+}
+
+// Queue depths are bounded by the channel capacity, far below the precision limit of f64.
+#[allow(clippy::as_conversions)]
+fn to_gauge_value(depth: usize) -> f64 {
+    depth as f64
+}
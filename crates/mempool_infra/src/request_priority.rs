@@ -0,0 +1,121 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::payload_metrics::RequestVariant;
+
+/// The priority of the requests whose variant has no configured priority.
+pub const DEFAULT_REQUEST_PRIORITY: u8 = 0;
+
+/// The priorities of the requests to a component, by request variant, e.g., `GetTransactions`.
+/// Queued requests of a higher priority are handled first; see [`PrioritizedRequests`].
+pub type RequestPriorities = BTreeMap<String, u8>;
+
+/// Returns the configured priority of the variant of the request.
+pub fn request_priority<Request: RequestVariant>(
+    request_priorities: &RequestPriorities,
+    request: &Request,
+) -> u8 {
+    request_priorities.get(request.variant_name()).copied().unwrap_or(DEFAULT_REQUEST_PRIORITY)
+}
+
+/// A queue of requests, popped by descending priority, and in arrival order within a priority.
+pub struct PrioritizedRequests<T> {
+    queue: BinaryHeap<PrioritizedRequest<T>>,
+    n_pushed: u64,
+}
+
+impl<T> PrioritizedRequests<T> {
+    pub fn new() -> Self {
+        Self { queue: BinaryHeap::new(), n_pushed: 0 }
+    }
+
+    pub fn push(&mut self, request: T, priority: u8) {
+        self.queue.push(PrioritizedRequest { priority, arrival: Reverse(self.n_pushed), request });
+        self.n_pushed += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.queue.pop().map(|prioritized_request| prioritized_request.request)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl<T> Default for PrioritizedRequests<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Ordered by priority, then by earlier arrival; the request itself is not compared.
+struct PrioritizedRequest<T> {
+    priority: u8,
+    arrival: Reverse<u64>,
+    request: T,
+}
+
+impl<T> PrioritizedRequest<T> {
+    fn key(&self) -> (u8, Reverse<u64>) {
+        (self.priority, self.arrival)
+    }
+}
+
+impl<T> PartialEq for PrioritizedRequest<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T> Eq for PrioritizedRequest<T> {}
+
+impl<T> PartialOrd for PrioritizedRequest<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for PrioritizedRequest<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+pub fn serialize_request_priorities(request_priorities: &RequestPriorities) -> String {
+    request_priorities
+        .iter()
+        .map(|(request_variant, priority)| format!("{request_variant}:{priority}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Deserializes request priorities from a "Variant1:priority1,Variant2:priority2" string. An empty
+/// string gives all the requests the default priority.
+pub fn deserialize_request_priorities<'de, D>(de: D) -> Result<RequestPriorities, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw_str: String = Deserialize::deserialize(de)?;
+    let mut request_priorities = RequestPriorities::new();
+    for raw_entry in raw_str.split(',').map(str::trim).filter(|raw_entry| !raw_entry.is_empty()) {
+        let invalid_entry = || -> D::Error {
+            serde::de::Error::custom(format!("Invalid request priority entry: {raw_entry}."))
+        };
+        let (request_variant, raw_priority) =
+            raw_entry.split_once(':').ok_or_else(invalid_entry)?;
+        let priority = raw_priority.trim().parse().map_err(|_| invalid_entry())?;
+        if request_priorities.insert(request_variant.trim().to_string(), priority).is_some() {
+            return Err(serde::de::Error::custom(format!(
+                "Multiple priorities for request {request_variant}."
+            )));
+        }
+    }
+    Ok(request_priorities)
+}
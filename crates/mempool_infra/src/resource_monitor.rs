@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use std::{fs, io};
+
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DEFAULT_MAX_RSS_BYTES: u64 = 16 * 1024 * 1024 * 1024;
+const DEFAULT_MAX_OPEN_FDS: u64 = 65536;
+
+// The configuration of the node resource guard.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ResourceGuardConfig {
+    pub enable: bool,
+    pub max_rss_bytes: u64,
+    pub max_open_fds: u64,
+    #[validate(range(min = 1, max = 100))]
+    pub pressure_threshold_percent: u8,
+    // Zero would make the polling interval panic.
+    #[validate(range(min = 1))]
+    pub polling_interval_ms: u64,
+    pub eviction_batch_size: usize,
+}
+
+impl SerializeConfig for ResourceGuardConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the memory and file descriptor usage of the node is monitored, and load \
+                 is shed when approaching the ceilings.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_rss_bytes",
+                &self.max_rss_bytes,
+                "The resident memory ceiling of the node, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_open_fds",
+                &self.max_open_fds,
+                "The open file descriptor ceiling of the node.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "pressure_threshold_percent",
+                &self.pressure_threshold_percent,
+                "The percentage of a ceiling above which the node is considered under resource \
+                 pressure.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "polling_interval_ms",
+                &self.polling_interval_ms,
+                "The interval between resource usage samples, in milliseconds.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "eviction_batch_size",
+                &self.eviction_batch_size,
+                "The maximal number of transactions evicted from the mempool per sample taken \
+                 under resource pressure.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for ResourceGuardConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            max_rss_bytes: DEFAULT_MAX_RSS_BYTES,
+            max_open_fds: DEFAULT_MAX_OPEN_FDS,
+            pressure_threshold_percent: 90,
+            polling_interval_ms: 1000,
+            eviction_batch_size: 1000,
+        }
+    }
+}
+
+impl ResourceGuardConfig {
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_millis(self.polling_interval_ms)
+    }
+
+    pub fn pressure(&self, usage: &ResourceUsage) -> ResourcePressure {
+        let threshold = u128::from(self.pressure_threshold_percent);
+        let is_above_threshold =
+            |used: u64, ceiling: u64| u128::from(used) * 100 >= u128::from(ceiling) * threshold;
+
+        if is_above_threshold(usage.rss_bytes, self.max_rss_bytes)
+            || is_above_threshold(usage.n_open_fds, self.max_open_fds)
+        {
+            ResourcePressure::High
+        } else {
+            ResourcePressure::Normal
+        }
+    }
+}
+
+/// A sample of the resources held by the process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub rss_bytes: u64,
+    pub n_open_fds: u64,
+}
+
+impl ResourceUsage {
+    /// Samples the resource usage of the current process. Supported on Linux only.
+    pub fn sample() -> io::Result<Self> {
+        Ok(Self { rss_bytes: sample_rss_bytes()?, n_open_fds: sample_n_open_fds()? })
+    }
+}
+
+fn sample_rss_bytes() -> io::Result<u64> {
+    let status = fs::read_to_string("/proc/self/status")?;
+    let rss_kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.trim().strip_suffix("kB"))
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing VmRSS entry."))?;
+    Ok(rss_kilobytes * 1024)
+}
+
+fn sample_n_open_fds() -> io::Result<u64> {
+    let n_open_fds = fs::read_dir("/proc/self/fd")?.count();
+    Ok(u64::try_from(n_open_fds).expect("Number of file descriptors should fit in u64."))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourcePressure {
+    #[default]
+    Normal,
+    // Usage is approaching a ceiling; components should shed load.
+    High,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceStatus {
+    pub usage: ResourceUsage,
+    pub pressure: ResourcePressure,
+}
+
+/// The latest resource status of the node. Updated by the resource guard, and read by components
+/// that shed load or report health.
+#[derive(Clone, Debug, Default)]
+pub struct SharedResourceStatus(Arc<RwLock<ResourceStatus>>);
+
+impl SharedResourceStatus {
+    pub fn get(&self) -> ResourceStatus {
+        *self.0.read().expect("Resource status lock should not be poisoned.")
+    }
+
+    pub fn set(&self, status: ResourceStatus) {
+        *self.0.write().expect("Resource status lock should not be poisoned.") = status;
+    }
+
+    pub fn is_under_pressure(&self) -> bool {
+        self.get().pressure == ResourcePressure::High
+    }
+}
@@ -0,0 +1,59 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::component_definitions::{APPLICATION_JSON, APPLICATION_OCTET_STREAM};
+
+#[derive(Debug, Error)]
+pub enum SerializationError {
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// The wire format of the requests and responses of remote components: bincode is compact and
+/// fast, JSON can be read and written by hand when debugging. Each message is tagged with the
+/// content type of its format, so servers answer clients in the format they were sent requests
+/// in, regardless of their own format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerializationFormat {
+    #[default]
+    Bincode,
+    Json,
+}
+
+impl SerializationFormat {
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            SerializationFormat::Bincode => APPLICATION_OCTET_STREAM,
+            SerializationFormat::Json => APPLICATION_JSON,
+        }
+    }
+
+    /// The format of the given content type, if supported. Parameters, e.g., the charset, are
+    /// ignored.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        match media_type {
+            APPLICATION_OCTET_STREAM => Some(SerializationFormat::Bincode),
+            APPLICATION_JSON => Some(SerializationFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<Vec<u8>, SerializationError> {
+        Ok(match self {
+            SerializationFormat::Bincode => bincode::serialize(value)?,
+            SerializationFormat::Json => serde_json::to_vec(value)?,
+        })
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, SerializationError> {
+        Ok(match self {
+            SerializationFormat::Bincode => bincode::deserialize(bytes)?,
+            SerializationFormat::Json => serde_json::from_slice(bytes)?,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use rustls_pemfile::Item;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum TlsError {
+    #[error("Failed to load the TLS certificate and key: {0}")]
+    IdentityLoadingFailure(String),
+    #[error("Failed to load the TLS root certificates: {0}")]
+    RootCertificatesLoadingFailure(String),
+}
+
+pub type TlsResult<T> = Result<T, TlsError>;
+
+/// The PEM files of a certificate chain and its private key, identifying one end of a connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsIdentity {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsIdentity {
+    pub(crate) fn load(&self) -> TlsResult<(Vec<Certificate>, PrivateKey)> {
+        let identity_error =
+            |path: &Path, e| TlsError::IdentityLoadingFailure(format!("{}: {e}", path.display()));
+
+        let certs = File::open(&self.cert_path)
+            .and_then(|file| rustls_pemfile::certs(&mut BufReader::new(file)))
+            .map_err(|e| identity_error(&self.cert_path, e.to_string()))?;
+        if certs.is_empty() {
+            return Err(identity_error(&self.cert_path, "No certificates were found.".to_string()));
+        }
+
+        let items = File::open(&self.key_path)
+            .and_then(|file| rustls_pemfile::read_all(&mut BufReader::new(file)))
+            .map_err(|e| identity_error(&self.key_path, e.to_string()))?;
+        let key = items
+            .into_iter()
+            .find_map(|item| match item {
+                Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => Some(key),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                identity_error(&self.key_path, "No private key was found.".to_string())
+            })?;
+
+        Ok((certs.into_iter().map(Certificate).collect(), PrivateKey(key)))
+    }
+}
+
+/// The TLS settings of a remote component server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerTlsConfig {
+    pub identity: TlsIdentity,
+    // A PEM file of the root certificates client certificates must be signed by. If set, clients
+    // must authenticate with a certificate (mutual TLS); otherwise, any client may connect.
+    pub client_ca_certs_path: Option<PathBuf>,
+}
+
+impl ServerTlsConfig {
+    pub(crate) fn server_config(&self) -> TlsResult<Arc<ServerConfig>> {
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match &self.client_ca_certs_path {
+            Some(path) => builder.with_client_cert_verifier(
+                AllowAnyAuthenticatedClient::new(root_cert_store(Some(path))?).boxed(),
+            ),
+            None => builder.with_no_client_auth(),
+        };
+        let (certs, key) = self.identity.load()?;
+        let mut server_config = builder
+            .with_single_cert(certs, key)
+            .map_err(|e| TlsError::IdentityLoadingFailure(e.to_string()))?;
+        // Remote component clients talk HTTP/2 only.
+        server_config.alpn_protocols = vec![b"h2".to_vec()];
+        Ok(Arc::new(server_config))
+    }
+}
+
+/// Loads the root certificates of the given PEM file, or the native root certificates of the
+/// platform if no file is given.
+pub(crate) fn root_cert_store(ca_certs_path: Option<&Path>) -> TlsResult<RootCertStore> {
+    let certs = match ca_certs_path {
+        Some(path) => File::open(path)
+            .and_then(|file| rustls_pemfile::certs(&mut BufReader::new(file)))
+            .map_err(|e| {
+                TlsError::RootCertificatesLoadingFailure(format!("{}: {e}", path.display()))
+            })?,
+        None => rustls_native_certs::load_native_certs()
+            .map_err(|e| TlsError::RootCertificatesLoadingFailure(e.to_string()))?
+            .into_iter()
+            .map(|cert| cert.0)
+            .collect(),
+    };
+
+    let mut root_cert_store = RootCertStore::empty();
+    let (n_added_certs, _n_ignored_certs) = root_cert_store.add_parsable_certificates(&certs);
+    if n_added_certs == 0 {
+        return Err(TlsError::RootCertificatesLoadingFailure(
+            "No valid root certificates were found.".to_string(),
+        ));
+    }
+    Ok(root_cert_store)
+}
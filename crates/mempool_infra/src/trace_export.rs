@@ -0,0 +1,419 @@
+//! Export of the tracing spans of the node to an OpenTelemetry collector, over OTLP/HTTP with JSON
+//! encoding.
+//!
+//! Spans join the trace of their parent span. The spans of the handling of a component request join
+//! the trace of the span the request was sent from, which the request carries in its
+//! [`RequestContext`](crate::request_context::RequestContext): through the channel of a local
+//! component, or in the `traceparent` header of a remote one. Whether a trace is exported is
+//! decided once, at its root span, by the configured sampling ratio.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::header::CONTENT_TYPE;
+use hyper::http::uri::InvalidUri;
+use hyper::{Body, Client, Request as HyperRequest, Uri};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{warn, Span, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, Layer, Registry};
+use validator::Validate;
+
+use crate::component_definitions::APPLICATION_JSON;
+
+/// The HTTP header carrying the span a remote component request is sent from, in the W3C trace
+/// context format.
+pub const TRACE_PARENT_HEADER: &str = "traceparent";
+
+const SERVICE_NAME: &str = "starknet_sequencer_node";
+// The number of ended spans waiting to be exported, beyond which spans are dropped.
+const EXPORT_QUEUE_SIZE: usize = 4096;
+const MAX_EXPORT_BATCH_SIZE: usize = 512;
+const EXPORT_INTERVAL: Duration = Duration::from_secs(1);
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// The configuration of the export of the tracing spans of the node to an OpenTelemetry collector.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TraceExportConfig {
+    pub enable: bool,
+    // The OTLP/HTTP traces endpoint of the collector.
+    pub endpoint: String,
+    // The fraction of the traces that are exported.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub sampling_ratio: f64,
+}
+
+impl SerializeConfig for TraceExportConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the tracing spans of the node are exported to an OpenTelemetry \
+                 collector.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "endpoint",
+                &self.endpoint,
+                "The http URL of the OTLP traces endpoint of the collector.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "sampling_ratio",
+                &self.sampling_ratio,
+                "The fraction of the traces that are exported, between 0 and 1.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for TraceExportConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            sampling_ratio: 1.0,
+        }
+    }
+}
+
+/// A span within a trace, as propagated to the spans of the handling of the requests sent from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceParent {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub sampled: bool,
+}
+
+impl TraceParent {
+    /// Parses the value of a `traceparent` header, of version 00.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split('-');
+        let (version, trace_id, span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if version != "00" || trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+        Some(Self {
+            trace_id: u128::from_str_radix(trace_id, 16).ok()?,
+            span_id: u64::from_str_radix(span_id, 16).ok()?,
+            sampled: u8::from_str_radix(flags, 16).ok()? & 1 == 1,
+        })
+    }
+
+    /// The trace parent of the given span, if its trace is recorded.
+    pub fn of(span: &Span) -> Option<Self> {
+        span.with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            let span = registry.span(id)?;
+            let extensions = span.extensions();
+            extensions.get::<SpanRecord>().map(|record| record.trace_parent)
+        })
+        .flatten()
+    }
+
+    /// Makes the given span a child of this one, along with the spans it will contain, even if it
+    /// has a parent within the process.
+    pub fn adopt(&self, span: &Span) {
+        span.with_subscriber(|(id, dispatch)| {
+            let Some(registry) = dispatch.downcast_ref::<Registry>() else {
+                return;
+            };
+            let Some(span) = registry.span(id) else {
+                return;
+            };
+            let mut extensions = span.extensions_mut();
+            if let Some(record) = extensions.get_mut::<SpanRecord>() {
+                record.trace_parent.trace_id = self.trace_id;
+                record.trace_parent.sampled = self.sampled;
+                record.parent_span_id = Some(self.span_id);
+            }
+        });
+    }
+}
+
+impl Display for TraceParent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "00-{:032x}-{:016x}-{:02x}", self.trace_id, self.span_id, u8::from(self.sampled))
+    }
+}
+
+// The trace of a span, kept in its extensions until it ends.
+struct SpanRecord {
+    // The ids of the span itself.
+    trace_parent: TraceParent,
+    parent_span_id: Option<u64>,
+    start_time: SystemTime,
+    attributes: SpanAttributes,
+}
+
+#[derive(Default)]
+struct SpanAttributes(Vec<(&'static str, String)>);
+
+impl Visit for SpanAttributes {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+/// Records the traces of the spans, and queues the ended spans of sampled traces for export.
+pub struct TraceExportLayer {
+    sampling_ratio: f64,
+    ended_spans: Sender<OtlpSpan>,
+}
+
+impl TraceExportLayer {
+    /// Returns the layer, and the task exporting the spans it queues to the configured collector.
+    pub fn new(
+        config: &TraceExportConfig,
+    ) -> Result<(Self, impl Future<Output = ()> + Send), InvalidUri> {
+        let endpoint: Uri = config.endpoint.parse()?;
+        let (ended_spans, ended_spans_rx) = channel(EXPORT_QUEUE_SIZE);
+        let layer = Self { sampling_ratio: config.sampling_ratio, ended_spans };
+        Ok((layer, export_spans(endpoint, ended_spans_rx)))
+    }
+}
+
+impl<S> Layer<S> for TraceExportLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent = span.parent().and_then(|parent| {
+            parent.extensions().get::<SpanRecord>().map(|record| record.trace_parent)
+        });
+        let span_id = rand::thread_rng().gen_range(1..=u64::MAX);
+        let (trace_parent, parent_span_id) = match parent {
+            Some(parent) => (TraceParent { span_id, ..parent }, Some(parent.span_id)),
+            None => {
+                let trace_id = rand::thread_rng().gen_range(1..=u128::MAX);
+                let sampled = is_sampled(trace_id, self.sampling_ratio);
+                (TraceParent { trace_id, span_id, sampled }, None)
+            }
+        };
+        let mut attributes = SpanAttributes::default();
+        attrs.record(&mut attributes);
+        span.extensions_mut().insert(SpanRecord {
+            trace_parent,
+            parent_span_id,
+            start_time: SystemTime::now(),
+            attributes,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(record) = extensions.get_mut::<SpanRecord>() {
+            values.record(&mut record.attributes);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(record) = span.extensions_mut().remove::<SpanRecord>() else {
+            return;
+        };
+        if !record.trace_parent.sampled {
+            return;
+        }
+        let mut attributes = record.attributes.0;
+        attributes.push(("code.namespace", span.metadata().target().to_string()));
+        let otlp_span = OtlpSpan {
+            trace_id: format!("{:032x}", record.trace_parent.trace_id),
+            span_id: format!("{:016x}", record.trace_parent.span_id),
+            parent_span_id: record
+                .parent_span_id
+                .map(|parent_span_id| format!("{parent_span_id:016x}"))
+                .unwrap_or_default(),
+            name: span.name().to_string(),
+            kind: SPAN_KIND_INTERNAL,
+            start_time_unix_nano: unix_nanos(record.start_time),
+            end_time_unix_nano: unix_nanos(SystemTime::now()),
+            attributes: attributes
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key.to_string(), value))
+                .collect(),
+        };
+        // Spans are dropped rather than slowing down the node while the collector lags behind.
+        let _ = self.ended_spans.try_send(otlp_span);
+    }
+}
+
+/// Enables the export of the spans of the node, whose tracing is configured before its
+/// configuration is loaded.
+pub struct TraceExportHandle(pub(crate) reload::Handle<Option<TraceExportLayer>, Registry>);
+
+impl TraceExportHandle {
+    /// Starts exporting the spans of the node, if enabled. Must be called within a tokio runtime.
+    pub fn start(&self, config: &TraceExportConfig) {
+        if !config.enable {
+            return;
+        }
+        let (layer, exporter) = match TraceExportLayer::new(config) {
+            Ok(layer_and_exporter) => layer_and_exporter,
+            Err(e) => {
+                warn!(
+                    "Invalid trace export endpoint {}, not exporting spans: {}",
+                    config.endpoint, e
+                );
+                return;
+            }
+        };
+        if let Err(e) = self.0.reload(Some(layer)) {
+            warn!("Failed to enable the trace export: {}", e);
+            return;
+        }
+        tokio::spawn(exporter);
+    }
+}
+
+// Like the trace id ratio sampler of OpenTelemetry, decides by the lower half of the trace id,
+// which is random.
+fn is_sampled(trace_id: u128, sampling_ratio: f64) -> bool {
+    if sampling_ratio >= 1.0 {
+        return true;
+    }
+    let lower_half = u64::try_from(trace_id & u128::from(u64::MAX)).expect("Masked to 64 bits.");
+    to_ratio(lower_half) < sampling_ratio
+}
+
+// The precision lost in the conversion is negligible for sampling.
+#[allow(clippy::as_conversions)]
+fn to_ratio(value: u64) -> f64 {
+    value as f64 / u64::MAX as f64
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos().to_string()
+}
+
+// Posts the queued spans to the collector in batches, each of the spans ended within an export
+// interval.
+async fn export_spans(endpoint: Uri, mut ended_spans: Receiver<OtlpSpan>) {
+    let client = Client::new();
+    while let Some(first_span) = ended_spans.recv().await {
+        let mut batch = vec![first_span];
+        let export_time = tokio::time::sleep(EXPORT_INTERVAL);
+        tokio::pin!(export_time);
+        while batch.len() < MAX_EXPORT_BATCH_SIZE {
+            tokio::select! {
+                span = ended_spans.recv() => match span {
+                    Some(span) => batch.push(span),
+                    None => break,
+                },
+                _ = &mut export_time => break,
+            }
+        }
+
+        let n_spans = batch.len();
+        let body = serde_json::to_vec(&ExportTraceServiceRequest::new(batch))
+            .expect("Spans should be serializable.");
+        let request = HyperRequest::post(endpoint.clone())
+            .header(CONTENT_TYPE, APPLICATION_JSON)
+            .body(Body::from(body))
+            .expect("Export request building should succeed.");
+        match client.request(request).await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                warn!("The collector rejected {} spans with status {}.", n_spans, response.status())
+            }
+            Err(e) => warn!("Failed to export {} spans: {}", n_spans, e),
+        }
+    }
+}
+
+// The OTLP/JSON encoding of exported spans, with hex encoded ids.
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportTraceServiceRequest {
+    resource_spans: Vec<ResourceSpans>,
+}
+
+impl ExportTraceServiceRequest {
+    fn new(spans: Vec<OtlpSpan>) -> Self {
+        Self {
+            resource_spans: vec![ResourceSpans {
+                resource: Resource {
+                    attributes: vec![KeyValue::new(
+                        "service.name".to_string(),
+                        SERVICE_NAME.to_string(),
+                    )],
+                },
+                scope_spans: vec![ScopeSpans { spans }],
+            }],
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans {
+    resource: Resource,
+    scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Serialize)]
+struct Resource {
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+struct ScopeSpans {
+    spans: Vec<OtlpSpan>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpSpan {
+    trace_id: String,
+    span_id: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    parent_span_id: String,
+    name: String,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+struct KeyValue {
+    key: String,
+    value: AnyValue,
+}
+
+impl KeyValue {
+    fn new(key: String, value: String) -> Self {
+        Self { key, value: AnyValue { string_value: value } }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AnyValue {
+    string_value: String,
+}
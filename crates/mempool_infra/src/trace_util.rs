@@ -1,15 +1,65 @@
 use tracing::metadata::LevelFilter;
+use tracing::warn;
+use tracing_subscriber::fmt::format::JsonFields;
+use tracing_subscriber::layer::Layered;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer, Registry};
+
+use crate::log_format::{JsonLogFormat, LogFormat};
+use crate::trace_export::{TraceExportConfig, TraceExportHandle, TraceExportLayer};
 
 const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
 
-pub fn configure_tracing() {
-    let fmt_layer = fmt::layer().compact().with_target(false);
+// The subscriber the logs are formatted in, below the export of the spans.
+type ExportingRegistry = Layered<reload::Layer<Option<TraceExportLayer>, Registry>, Registry>;
+type FmtLayer = Box<dyn Layer<ExportingRegistry> + Send + Sync>;
+
+/// Configures the logging of the process. The logs are in the pretty format, and the export of the
+/// spans is disabled, until set otherwise through the returned handle, once the configuration of
+/// the process is loaded.
+pub fn configure_tracing() -> TracingHandle {
+    let (trace_export_layer, trace_export_handle) = reload::Layer::new(None::<TraceExportLayer>);
+    let (fmt_layer, fmt_handle) = reload::Layer::new(fmt_layer(LogFormat::Pretty));
     let level_filter_layer =
         EnvFilter::builder().with_default_directive(DEFAULT_LEVEL.into()).from_env_lossy();
 
     // This sets a single subscriber to all of the threads. We may want to implement different
     // subscriber for some threads and use set_global_default instead of init.
-    tracing_subscriber::registry().with(fmt_layer).with(level_filter_layer).init();
+    tracing_subscriber::registry()
+        .with(trace_export_layer)
+        .with(fmt_layer)
+        .with(level_filter_layer)
+        .init();
+    TracingHandle { fmt_handle, trace_export_handle: TraceExportHandle(trace_export_handle) }
+}
+
+fn fmt_layer(format: LogFormat) -> FmtLayer {
+    match format {
+        LogFormat::Pretty => Box::new(fmt::layer().compact().with_target(false)),
+        LogFormat::Json => {
+            Box::new(fmt::layer().event_format(JsonLogFormat).fmt_fields(JsonFields::new()))
+        }
+    }
+}
+
+/// Applies the configuration of the logging of the process, once loaded.
+pub struct TracingHandle {
+    fmt_handle: reload::Handle<FmtLayer, ExportingRegistry>,
+    trace_export_handle: TraceExportHandle,
+}
+
+impl TracingHandle {
+    /// Formats the following logs in the given format. The fields of spans entered before are
+    /// missing from the logs in them.
+    pub fn set_log_format(&self, format: LogFormat) {
+        if let Err(e) = self.fmt_handle.reload(fmt_layer(format)) {
+            warn!("Failed to set the log format to {:?}: {}", format, e);
+        }
+    }
+
+    /// Starts exporting the spans of the process, if enabled. Must be called within a tokio
+    /// runtime.
+    pub fn start_trace_export(&self, config: &TraceExportConfig) {
+        self.trace_export_handle.start(config);
+    }
 }
@@ -116,7 +116,7 @@ impl ComponentStarter for ComponentD {
 #[async_trait]
 impl ComponentCClientTrait for LocalComponentClient<ComponentCRequest, ComponentCResponse> {
     async fn c_inc_counter(&self) -> ClientResult<()> {
-        let res = self.send(ComponentCRequest::CIncCounter).await;
+        let res = self.send(ComponentCRequest::CIncCounter).await?;
         match res {
             ComponentCResponse::CIncCounter => Ok(()),
             _ => Err(ClientError::UnexpectedResponse("Unexpected Responce".to_string())),
@@ -124,7 +124,7 @@ impl ComponentCClientTrait for LocalComponentClient<ComponentCRequest, Component
     }
 
     async fn c_get_counter(&self) -> ClientResult<usize> {
-        let res = self.send(ComponentCRequest::CGetCounter).await;
+        let res = self.send(ComponentCRequest::CGetCounter).await?;
         match res {
             ComponentCResponse::CGetCounter(counter) => Ok(counter),
             _ => Err(ClientError::UnexpectedResponse("Unexpected Responce".to_string())),
@@ -0,0 +1,109 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use hyper::StatusCode;
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::auth::bearer_auth_header;
+use starknet_mempool_infra::component_client::{ClientError, RemoteComponentClient};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestHandler,
+    RemoteComponentCommunicationConfig,
+    RemoteTransport,
+    ServerError,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{ComponentServerStarter, RemoteComponentServer};
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const HTTP_PORT: u16 = 10027;
+const GRPC_PORT: u16 = 10028;
+const AUTH_TOKEN: &str = "mempool-token";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct EchoRequest(String);
+
+impl From<&EchoRequest> for &'static str {
+    fn from(_: &EchoRequest) -> Self {
+        "Echo"
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct EchoResponse(String);
+
+struct Echo;
+
+#[async_trait]
+impl ComponentStarter for Echo {}
+
+#[async_trait]
+impl ComponentRequestHandler<EchoRequest, EchoResponse> for Echo {
+    async fn handle_request(&mut self, request: EchoRequest) -> EchoResponse {
+        EchoResponse(request.0)
+    }
+}
+
+// Starts a server expecting the auth token, and returns the config of its clients.
+async fn start_authenticated_echo(
+    port: u16,
+    transport: RemoteTransport,
+) -> RemoteComponentCommunicationConfig {
+    let config = RemoteComponentCommunicationConfig {
+        ip: LOCAL_IP,
+        port,
+        retries: 0,
+        transport,
+        auth_token: AUTH_TOKEN.to_string(),
+        ..Default::default()
+    };
+    let mut server = RemoteComponentServer::new(Echo, config.ip, config.port)
+        .with_transport(transport)
+        .with_auth_token(&config.auth_token);
+    task::spawn(async move {
+        server.start().await;
+    });
+    task::yield_now().await;
+    config
+}
+
+#[test]
+fn empty_auth_token_disables_authentication() {
+    assert!(bearer_auth_header("").is_none());
+    assert_eq!(bearer_auth_header(AUTH_TOKEN).unwrap(), "Bearer mempool-token");
+}
+
+#[rstest]
+#[case::http(HTTP_PORT, RemoteTransport::Http)]
+#[case::grpc(GRPC_PORT, RemoteTransport::Grpc)]
+#[tokio::test]
+async fn only_requests_with_the_auth_token_are_answered(
+    #[case] port: u16,
+    #[case] transport: RemoteTransport,
+) {
+    let config = start_authenticated_echo(port, transport).await;
+    let request = || EchoRequest("Hello".to_string());
+
+    let authorized_client =
+        RemoteComponentClient::<EchoRequest, EchoResponse>::from_config(&config);
+    assert_eq!(authorized_client.send(request()).await.unwrap(), EchoResponse("Hello".to_string()));
+
+    for auth_token in ["", "wrong-token"] {
+        let client = RemoteComponentClient::<EchoRequest, EchoResponse>::from_config(&config)
+            .with_auth_token(auth_token);
+        let error = client.send(request()).await.unwrap_err();
+        assert!(!error.is_retriable());
+        match (transport, error) {
+            (RemoteTransport::Http, ClientError::ResponseError(status_code, server_error)) => {
+                assert_eq!(status_code, StatusCode::UNAUTHORIZED);
+                assert_matches!(server_error, ServerError::Unauthorized);
+            }
+            (RemoteTransport::Grpc, ClientError::GrpcFailure(status)) => {
+                assert_eq!(status.code(), tonic::Code::Unauthenticated);
+            }
+            (_, error) => panic!("Unexpected error: {error:?}"),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::broadcast::{
+    create_broadcast_relay_server,
+    EventPublisher,
+    LocalBroadcast,
+};
+use starknet_mempool_infra::component_definitions::RemoteComponentCommunicationConfig;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const RELAY_PORT: u16 = 10023;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+struct BlockCommitted(u64);
+
+impl From<&BlockCommitted> for &'static str {
+    fn from(_: &BlockCommitted) -> Self {
+        "BlockCommitted"
+    }
+}
+
+#[tokio::test]
+async fn events_reach_all_the_subscribers() {
+    let broadcast = LocalBroadcast::default();
+    let mut first_subscriber = broadcast.subscribe();
+    let mut second_subscriber = broadcast.subscribe();
+
+    assert_eq!(broadcast.publish(BlockCommitted(1)), 2);
+
+    assert_eq!(first_subscriber.recv().await, Some(BlockCommitted(1)));
+    assert_eq!(second_subscriber.recv().await, Some(BlockCommitted(1)));
+}
+
+#[tokio::test]
+async fn lagging_subscriber_skips_the_oldest_events() {
+    let broadcast = LocalBroadcast::new(2);
+    let mut subscriber = broadcast.subscribe();
+
+    for block_number in 0..4 {
+        broadcast.publish(BlockCommitted(block_number));
+    }
+
+    assert_eq!(subscriber.recv().await, Some(BlockCommitted(2)));
+    assert_eq!(subscriber.recv().await, Some(BlockCommitted(3)));
+}
+
+#[tokio::test]
+async fn subscription_ends_once_the_broadcast_is_dropped() {
+    let broadcast = LocalBroadcast::<BlockCommitted>::default();
+    let mut subscriber = broadcast.subscribe();
+
+    drop(broadcast);
+
+    assert_eq!(subscriber.recv().await, None);
+}
+
+#[tokio::test]
+async fn events_are_relayed_to_remote_subscribers() {
+    let relay_config =
+        RemoteComponentCommunicationConfig { ip: LOCAL_IP, port: RELAY_PORT, ..Default::default() };
+    let remote_broadcast = LocalBroadcast::default();
+    let mut remote_subscriber = remote_broadcast.subscribe();
+    let mut relay_server = create_broadcast_relay_server(remote_broadcast, &relay_config);
+    task::spawn(async move {
+        relay_server.start().await;
+    });
+    task::yield_now().await;
+
+    let publisher = EventPublisher::new(LocalBroadcast::default()).with_remote_relay(&relay_config);
+    let mut local_subscriber = publisher.local_broadcast().subscribe();
+    publisher.publish(BlockCommitted(1)).await;
+
+    assert_eq!(local_subscriber.recv().await, Some(BlockCommitted(1)));
+    assert_eq!(remote_subscriber.recv().await, Some(BlockCommitted(1)));
+}
@@ -0,0 +1,19 @@
+use starknet_mempool_infra::channel_metrics::ChannelDepthGauge;
+use tokio::sync::mpsc::channel;
+
+#[tokio::test]
+async fn records_depth_and_high_watermark() {
+    let gauge = ChannelDepthGauge::new("test_channel");
+    let (sender, mut receiver) = channel::<u8>(10);
+    assert_eq!(gauge.record(&sender), 0);
+
+    for i in 0..3 {
+        sender.send(i).await.unwrap();
+    }
+    assert_eq!(gauge.record(&sender), 3);
+
+    receiver.recv().await.unwrap();
+    receiver.recv().await.unwrap();
+    assert_eq!(gauge.record(&sender), 1);
+    assert_eq!(gauge.high_watermark(), 3);
+}
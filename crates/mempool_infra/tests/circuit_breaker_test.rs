@@ -0,0 +1,158 @@
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use hyper::StatusCode;
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{
+    CircuitBreaker,
+    CircuitBreakerConfig,
+    CircuitState,
+    ClientError,
+    ClientResult,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{ComponentRequestHandler, ServerError};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{ComponentServerStarter, RemoteComponentServer};
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const RECOVERING_SERVER_PORT: u16 = 10022;
+
+const FAILURE_THRESHOLD: usize = 2;
+const COOLDOWN: Duration = Duration::from_millis(100);
+const CONFIG: CircuitBreakerConfig =
+    CircuitBreakerConfig { failure_threshold: FAILURE_THRESHOLD, cooldown: COOLDOWN };
+
+fn unavailable() -> ClientResult<()> {
+    Err(ClientError::ResponseError(
+        StatusCode::SERVICE_UNAVAILABLE,
+        ServerError::RequestDeserializationFailure("Unavailable.".to_string()),
+    ))
+}
+
+fn send(circuit_breaker: &CircuitBreaker, result: ClientResult<()>) -> ClientResult<()> {
+    let permit = circuit_breaker.acquire()?;
+    permit.record(&result);
+    result
+}
+
+fn open_circuit_breaker() -> CircuitBreaker {
+    let circuit_breaker = CircuitBreaker::new(CONFIG, "Test");
+    for _ in 0..FAILURE_THRESHOLD {
+        assert_matches!(send(&circuit_breaker, unavailable()), Err(ClientError::ResponseError(..)));
+    }
+    assert_eq!(circuit_breaker.state(), CircuitState::Open);
+    circuit_breaker
+}
+
+#[test]
+fn opens_after_consecutive_failures() {
+    let circuit_breaker = CircuitBreaker::new(CONFIG, "Test");
+
+    // A success resets the count of consecutive failures.
+    send(&circuit_breaker, unavailable()).unwrap_err();
+    send(&circuit_breaker, Ok(())).unwrap();
+    send(&circuit_breaker, unavailable()).unwrap_err();
+    assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+
+    send(&circuit_breaker, unavailable()).unwrap_err();
+    assert_eq!(circuit_breaker.state(), CircuitState::Open);
+    assert_matches!(circuit_breaker.acquire(), Err(ClientError::CircuitOpen));
+}
+
+#[rstest]
+#[case::unexpected_response(Err(ClientError::UnexpectedResponse("Other.".to_string())))]
+#[case::deadline_exceeded(Err(ClientError::DeadlineExceeded))]
+#[test]
+fn non_retriable_errors_do_not_open(#[case] result: ClientResult<()>) {
+    let circuit_breaker = CircuitBreaker::new(CONFIG, "Test");
+
+    for _ in 0..FAILURE_THRESHOLD {
+        send(&circuit_breaker, result.clone()).unwrap_err();
+    }
+
+    assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+}
+
+#[rstest]
+#[case::probe_succeeds(Ok(()), CircuitState::Closed)]
+#[case::probe_fails(unavailable(), CircuitState::Open)]
+#[tokio::test]
+async fn half_opens_after_cooldown(
+    #[case] probe_result: ClientResult<()>,
+    #[case] expected_state: CircuitState,
+) {
+    let circuit_breaker = open_circuit_breaker();
+    tokio::time::sleep(COOLDOWN).await;
+
+    let probe = circuit_breaker.acquire().unwrap();
+    assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+    // Only one request probes the server.
+    assert_matches!(circuit_breaker.acquire(), Err(ClientError::CircuitOpen));
+    probe.record(&probe_result);
+
+    assert_eq!(circuit_breaker.state(), expected_state);
+}
+
+#[tokio::test]
+async fn dropped_probe_lets_another_probe() {
+    let circuit_breaker = open_circuit_breaker();
+    tokio::time::sleep(COOLDOWN).await;
+
+    drop(circuit_breaker.acquire().unwrap());
+
+    assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+    send(&circuit_breaker, Ok(())).unwrap();
+    assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct EchoRequest(u32);
+
+impl From<&EchoRequest> for &'static str {
+    fn from(_: &EchoRequest) -> Self {
+        "Echo"
+    }
+}
+
+struct Echo;
+
+#[async_trait]
+impl ComponentStarter for Echo {}
+
+#[async_trait]
+impl ComponentRequestHandler<EchoRequest, u32> for Echo {
+    async fn handle_request(&mut self, request: EchoRequest) -> u32 {
+        request.0
+    }
+}
+
+#[tokio::test]
+async fn remote_client_fails_fast_until_server_recovers() {
+    let client =
+        RemoteComponentClient::<EchoRequest, u32>::new(LOCAL_IP, RECOVERING_SERVER_PORT, 0)
+            .with_circuit_breaker(CONFIG);
+
+    // No server is listening yet.
+    for _ in 0..FAILURE_THRESHOLD {
+        assert_matches!(
+            client.send(EchoRequest(1)).await,
+            Err(ClientError::CommunicationFailure(_))
+        );
+    }
+    assert_matches!(client.clone().send(EchoRequest(1)).await, Err(ClientError::CircuitOpen));
+
+    let mut server = RemoteComponentServer::new(Echo, LOCAL_IP, RECOVERING_SERVER_PORT);
+    task::spawn(async move {
+        server.start().await;
+    });
+    task::yield_now().await;
+    tokio::time::sleep(COOLDOWN).await;
+
+    assert_eq!(client.send(EchoRequest(1)).await.unwrap(), 1);
+    assert_eq!(client.circuit_breaker().unwrap().state(), CircuitState::Closed);
+}
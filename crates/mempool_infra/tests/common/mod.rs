@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use starknet_mempool_infra::component_client::ClientResult;
 use starknet_mempool_infra::component_runner::ComponentStarter;
+use strum_macros::IntoStaticStr;
 
 pub(crate) type ValueA = u32;
 pub(crate) type ValueB = u8;
@@ -9,7 +10,7 @@ pub(crate) type ValueB = u8;
 pub(crate) type ResultA = ClientResult<ValueA>;
 pub(crate) type ResultB = ClientResult<ValueB>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, IntoStaticStr)]
 pub enum ComponentARequest {
     AGetValue,
 }
@@ -19,7 +20,7 @@ pub enum ComponentAResponse {
     AGetValue(ValueA),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, IntoStaticStr)]
 pub enum ComponentBRequest {
     BGetValue,
     BSetValue(ValueB),
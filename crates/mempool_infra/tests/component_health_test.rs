@@ -0,0 +1,144 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use async_trait::async_trait;
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    ComponentHealthClient,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentHealth,
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{
+    ComponentServerStarter,
+    LocalComponentServer,
+    RemoteComponentServer,
+};
+use strum_macros::IntoStaticStr;
+use tokio::sync::mpsc::channel;
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const HEALTH_PORT: u16 = 10018;
+const UNEXPECTED_RESPONSE_PORT: u16 = 10019;
+
+#[derive(Serialize, Deserialize, Debug, IntoStaticStr)]
+enum ProbedRequest {
+    IsAlive,
+    IsReady,
+}
+
+impl HealthCheckRequest for ProbedRequest {
+    fn is_alive() -> Self {
+        ProbedRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        ProbedRequest::IsReady
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+enum ProbedResponse {
+    IsAlive(bool),
+    IsReady(bool),
+    Other,
+}
+
+impl HealthCheckResponse for ProbedResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            ProbedResponse::IsAlive(health) | ProbedResponse::IsReady(health) => Some(*health),
+            ProbedResponse::Other => None,
+        }
+    }
+}
+
+// Alive, yet not ready to take requests.
+struct WarmingUpComponent;
+
+#[async_trait]
+impl ComponentStarter for WarmingUpComponent {}
+
+#[async_trait]
+impl ComponentHealth for WarmingUpComponent {
+    async fn is_ready(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl ComponentRequestHandler<ProbedRequest, ProbedResponse> for WarmingUpComponent {
+    async fn handle_request(&mut self, request: ProbedRequest) -> ProbedResponse {
+        match request {
+            ProbedRequest::IsAlive => ProbedResponse::IsAlive(self.is_alive().await),
+            ProbedRequest::IsReady => ProbedResponse::IsReady(self.is_ready().await),
+        }
+    }
+}
+
+// Answers probes with responses of other requests.
+struct MisbehavingComponent;
+
+#[async_trait]
+impl ComponentRequestHandler<ProbedRequest, ProbedResponse> for MisbehavingComponent {
+    async fn handle_request(&mut self, _request: ProbedRequest) -> ProbedResponse {
+        ProbedResponse::Other
+    }
+}
+
+fn local_client() -> LocalComponentClient<ProbedRequest, ProbedResponse> {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<ProbedRequest, ProbedResponse>>(32);
+    let mut server = LocalComponentServer::new(WarmingUpComponent, rx);
+    task::spawn(async move {
+        server.start().await;
+    });
+    LocalComponentClient::new(tx)
+}
+
+async fn remote_client<Component>(
+    component: Component,
+    port: u16,
+) -> RemoteComponentClient<ProbedRequest, ProbedResponse>
+where
+    Component: ComponentRequestHandler<ProbedRequest, ProbedResponse> + Send + 'static,
+{
+    let mut server = RemoteComponentServer::new(component, LOCAL_IP, port);
+    task::spawn(async move {
+        server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+    RemoteComponentClient::new(LOCAL_IP, port, 0)
+}
+
+#[rstest]
+#[case::local(false)]
+#[case::remote(true)]
+#[tokio::test]
+async fn test_health_probes(#[case] is_remote: bool) {
+    let client: Box<dyn ComponentHealthClient> = match is_remote {
+        true => Box::new(remote_client(WarmingUpComponent, HEALTH_PORT).await),
+        false => Box::new(local_client()),
+    };
+
+    assert!(client.is_alive().await.unwrap());
+    assert!(!client.is_ready().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_unexpected_probe_response() {
+    let client = remote_client(MisbehavingComponent, UNEXPECTED_RESPONSE_PORT).await;
+
+    let result = client.is_alive().await;
+
+    assert!(matches!(result, Err(ClientError::UnexpectedResponse(_))));
+}
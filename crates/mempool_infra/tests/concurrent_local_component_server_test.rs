@@ -0,0 +1,133 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rstest::rstest;
+use starknet_mempool_infra::component_client::LocalComponentClient;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
+use starknet_mempool_infra::component_server::{
+    ComponentServerStarter,
+    LocalActiveComponentServer,
+    LocalComponentServer,
+};
+use tokio::sync::mpsc::channel;
+use tokio::task;
+
+const N_REQUESTS: usize = 6;
+const HANDLING_TIME: Duration = Duration::from_millis(50);
+
+struct Sleep(Duration);
+
+// Records the maximal number of requests it handled at once.
+#[derive(Clone, Default)]
+struct Sleeper {
+    n_in_flight_requests: Arc<AtomicUsize>,
+    max_in_flight_requests: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ComponentStarter for Sleeper {
+    async fn start(&mut self) -> Result<(), ComponentStartError> {
+        std::future::pending().await
+    }
+}
+
+#[async_trait]
+impl ComponentRequestHandler<Sleep, ()> for Sleeper {
+    async fn handle_request(&mut self, request: Sleep) {
+        let n_in_flight_requests = self.n_in_flight_requests.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight_requests.fetch_max(n_in_flight_requests, Ordering::SeqCst);
+        tokio::time::sleep(request.0).await;
+        self.n_in_flight_requests.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// A sleeper whose start returns once done, as the start of the components of passive servers.
+#[derive(Clone, Default)]
+struct PassiveSleeper(Sleeper);
+
+#[async_trait]
+impl ComponentStarter for PassiveSleeper {
+    async fn start(&mut self) -> Result<(), ComponentStartError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ComponentRequestHandler<Sleep, ()> for PassiveSleeper {
+    async fn handle_request(&mut self, request: Sleep) {
+        self.0.handle_request(request).await
+    }
+}
+
+fn start_sleeper(
+    sleeper: Sleeper,
+    max_concurrent_requests: usize,
+) -> LocalComponentClient<Sleep, ()> {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<Sleep, ()>>(32);
+    let mut server = LocalActiveComponentServer::new(sleeper, rx)
+        .with_max_concurrent_requests(max_concurrent_requests);
+    task::spawn(async move {
+        server.start().await;
+    });
+    LocalComponentClient::new(tx)
+}
+
+#[rstest]
+#[case::sequential(1)]
+#[case::concurrent(3)]
+#[case::unbounded(N_REQUESTS)]
+#[tokio::test]
+async fn test_concurrent_requests_are_bounded(#[case] max_concurrent_requests: usize) {
+    let sleeper = Sleeper::default();
+    let client = start_sleeper(sleeper.clone(), max_concurrent_requests);
+
+    let requests: Vec<_> = (0..N_REQUESTS)
+        .map(|_| {
+            let client = client.clone();
+            task::spawn(async move { client.send(Sleep(HANDLING_TIME)).await })
+        })
+        .collect();
+    for request in requests {
+        request.await.unwrap().unwrap();
+    }
+
+    assert_eq!(sleeper.max_in_flight_requests.load(Ordering::SeqCst), max_concurrent_requests);
+}
+
+#[tokio::test]
+async fn test_slow_request_does_not_block_others() {
+    let client = start_sleeper(Sleeper::default(), 2);
+
+    let slow_request = client.send(Sleep(Duration::from_secs(10)));
+    let fast_request = client.send(Sleep(Duration::ZERO));
+
+    tokio::select! {
+        _ = slow_request => panic!("The slow request should not complete first."),
+        result = fast_request => result.unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_passive_server_handles_requests_concurrently() {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<Sleep, ()>>(32);
+    let mut server =
+        LocalComponentServer::new(PassiveSleeper::default(), rx).with_max_concurrent_requests(2);
+    task::spawn(async move {
+        server.start().await;
+    });
+    let client = LocalComponentClient::new(tx);
+
+    let slow_request = client.send(Sleep(Duration::from_secs(10)));
+    let fast_request = client.send(Sleep(Duration::ZERO));
+
+    tokio::select! {
+        _ = slow_request => panic!("The slow request should not complete first."),
+        result = fast_request => result.unwrap(),
+    }
+}
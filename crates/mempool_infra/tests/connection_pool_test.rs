@@ -0,0 +1,63 @@
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{ConnectionPoolConfig, RemoteComponentClient};
+use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{ComponentServerStarter, RemoteComponentServer};
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const POOL_PORT: u16 = 10029;
+const POOL_SIZE: usize = 2;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct EchoRequest(u8);
+
+impl From<&EchoRequest> for &'static str {
+    fn from(_: &EchoRequest) -> Self {
+        "Echo"
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct EchoResponse(u8);
+
+struct Echo;
+
+#[async_trait]
+impl ComponentStarter for Echo {}
+
+#[async_trait]
+impl ComponentRequestHandler<EchoRequest, EchoResponse> for Echo {
+    async fn handle_request(&mut self, request: EchoRequest) -> EchoResponse {
+        EchoResponse(request.0)
+    }
+}
+
+#[tokio::test]
+async fn requests_reuse_the_connections_of_the_pool() {
+    let mut server = RemoteComponentServer::new(Echo, LOCAL_IP, POOL_PORT);
+    task::spawn(async move {
+        server.start().await;
+    });
+    task::yield_now().await;
+
+    let client = RemoteComponentClient::<EchoRequest, EchoResponse>::new(LOCAL_IP, POOL_PORT, 0)
+        .with_connection_pool_config(ConnectionPoolConfig {
+            pool_size: POOL_SIZE,
+            idle_timeout: Duration::from_secs(60),
+        });
+    assert_eq!(client.n_open_connections(), 0);
+
+    // The clones of the client share its pool.
+    let clone = client.clone();
+    for i in 0..10 {
+        let client = if i % 3 == 0 { &clone } else { &client };
+        assert_eq!(client.send(EchoRequest(i)).await.unwrap(), EchoResponse(i));
+    }
+
+    assert_eq!(client.n_open_connections(), POOL_SIZE);
+}
@@ -0,0 +1,174 @@
+use std::net::{IpAddr, Ipv6Addr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    ClientResult,
+    LocalComponentClient,
+    RemoteComponentClient,
+};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{
+    ComponentServerStarter,
+    LocalComponentServer,
+    RemoteComponentServer,
+};
+use starknet_mempool_infra::request_context::RequestContext;
+use tokio::sync::mpsc::channel;
+use tokio::task;
+use tokio::time::Instant;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const SLOW_REMOTE_PORT: u16 = 10020;
+const EXPIRED_REMOTE_PORT: u16 = 10021;
+
+const HANDLING_TIME: Duration = Duration::from_millis(200);
+const DEADLINE: Duration = Duration::from_millis(50);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Sleep(Duration);
+
+impl From<&Sleep> for &'static str {
+    fn from(_: &Sleep) -> Self {
+        "Sleep"
+    }
+}
+
+// Answers with the number of requests it started handling, after sleeping for the requested time.
+#[derive(Clone, Default)]
+struct Sleeper {
+    n_handled_requests: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ComponentStarter for Sleeper {}
+
+#[async_trait]
+impl ComponentRequestHandler<Sleep, usize> for Sleeper {
+    async fn handle_request(&mut self, request: Sleep) -> usize {
+        let n_handled_requests = self.n_handled_requests.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::time::sleep(request.0).await;
+        n_handled_requests
+    }
+}
+
+fn start_local_sleeper(sleeper: Sleeper) -> LocalComponentClient<Sleep, usize> {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<Sleep, usize>>(32);
+    let mut server = LocalComponentServer::new(sleeper, rx);
+    task::spawn(async move {
+        server.start().await;
+    });
+    LocalComponentClient::new(tx)
+}
+
+async fn start_remote_sleeper(sleeper: Sleeper, port: u16) -> RemoteComponentClient<Sleep, usize> {
+    let mut server = RemoteComponentServer::new(sleeper, LOCAL_IP, port);
+    task::spawn(async move {
+        server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+    RemoteComponentClient::new(LOCAL_IP, port, 0)
+}
+
+fn context_with_deadline(time_left: Duration) -> RequestContext {
+    RequestContext::new("test").with_deadline(Instant::now() + time_left)
+}
+
+#[tokio::test]
+async fn test_local_request_past_deadline_is_dropped() {
+    let client = start_local_sleeper(Sleeper::default());
+
+    let result: ClientResult<usize> =
+        context_with_deadline(DEADLINE).scope(client.send(Sleep(HANDLING_TIME))).await;
+
+    assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+    // The server keeps serving requests.
+    assert_eq!(client.send(Sleep(Duration::ZERO)).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_local_request_within_deadline_is_answered() {
+    let client = start_local_sleeper(Sleeper::default());
+
+    let result =
+        context_with_deadline(HANDLING_TIME).scope(client.send(Sleep(Duration::ZERO))).await;
+
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_expired_request_is_not_handled() {
+    let sleeper = Sleeper::default();
+    let client = start_local_sleeper(sleeper.clone());
+
+    let result =
+        context_with_deadline(Duration::ZERO).scope(client.send(Sleep(Duration::ZERO))).await;
+
+    assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+    assert_eq!(sleeper.n_handled_requests.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_local_request_unanswered_within_timeout_fails() {
+    let client = start_local_sleeper(Sleeper::default()).with_timeout(DEADLINE);
+
+    let result = client.send(Sleep(HANDLING_TIME)).await;
+
+    assert!(matches!(result, Err(ClientError::Timeout(DEADLINE))));
+}
+
+#[tokio::test]
+async fn test_local_request_to_wedged_server_times_out() {
+    // The server never takes requests off its channel, so the second request can't be enqueued.
+    let (tx, _rx) = channel::<ComponentRequestAndResponseSender<Sleep, usize>>(1);
+    let client = LocalComponentClient::new(tx).with_timeout(DEADLINE);
+    let pending_client = client.clone();
+    task::spawn(async move { pending_client.send(Sleep(Duration::ZERO)).await });
+    task::yield_now().await;
+
+    let result = client.send(Sleep(Duration::ZERO)).await;
+
+    assert!(matches!(result, Err(ClientError::Timeout(DEADLINE))));
+}
+
+#[tokio::test]
+async fn test_remote_request_past_deadline_times_out() {
+    let client = start_remote_sleeper(Sleeper::default(), SLOW_REMOTE_PORT).await;
+
+    let result = context_with_deadline(DEADLINE).scope(client.send(Sleep(HANDLING_TIME))).await;
+
+    assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+}
+
+#[tokio::test]
+async fn test_remote_expired_request_is_not_sent() {
+    let sleeper = Sleeper::default();
+    let client = start_remote_sleeper(sleeper.clone(), EXPIRED_REMOTE_PORT).await;
+
+    let result =
+        context_with_deadline(Duration::ZERO).scope(client.send(Sleep(Duration::ZERO))).await;
+
+    assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+    assert_eq!(sleeper.n_handled_requests.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_earlier_deadline_is_kept() {
+    let now = Instant::now();
+    let earlier_deadline = now + DEADLINE;
+
+    let context = RequestContext::new("test")
+        .with_deadline(earlier_deadline)
+        .with_deadline(now + HANDLING_TIME);
+
+    assert_eq!(context.deadline, Some(earlier_deadline));
+}
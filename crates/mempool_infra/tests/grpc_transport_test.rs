@@ -0,0 +1,109 @@
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{ClientError, RemoteComponentClient};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestHandler,
+    RemoteComponentCommunicationConfig,
+    RemoteTransport,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{ComponentServerStarter, RemoteComponentServer};
+use starknet_mempool_infra::grpc::grpc_method;
+use starknet_mempool_infra::request_context::RequestContext;
+use starknet_mempool_infra::serialization::SerializationFormat;
+use strum_macros::IntoStaticStr;
+use tokio::task;
+use tokio::time::Instant;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const BINCODE_PORT: u16 = 10024;
+const JSON_PORT: u16 = 10025;
+const DEADLINE_PORT: u16 = 10026;
+
+#[derive(Serialize, Deserialize, Debug, IntoStaticStr)]
+enum EchoRequest {
+    Echo(String),
+    Sleep(Duration),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum EchoResponse {
+    Echo(String),
+    Sleep,
+}
+
+struct Echo;
+
+#[async_trait]
+impl ComponentStarter for Echo {}
+
+#[async_trait]
+impl ComponentRequestHandler<EchoRequest, EchoResponse> for Echo {
+    async fn handle_request(&mut self, request: EchoRequest) -> EchoResponse {
+        match request {
+            EchoRequest::Echo(content) => EchoResponse::Echo(content),
+            EchoRequest::Sleep(duration) => {
+                tokio::time::sleep(duration).await;
+                EchoResponse::Sleep
+            }
+        }
+    }
+}
+
+async fn start_grpc_echo(
+    port: u16,
+    serialization_format: SerializationFormat,
+) -> RemoteComponentClient<EchoRequest, EchoResponse> {
+    let config = RemoteComponentCommunicationConfig {
+        ip: LOCAL_IP,
+        port,
+        retries: 0,
+        serialization_format,
+        transport: RemoteTransport::Grpc,
+        ..Default::default()
+    };
+    let mut server = RemoteComponentServer::new(Echo, config.ip, config.port)
+        .with_serialization_format(serialization_format)
+        .with_transport(RemoteTransport::Grpc);
+    task::spawn(async move {
+        server.start().await;
+    });
+    task::yield_now().await;
+    RemoteComponentClient::from_config(&config)
+}
+
+#[test]
+fn grpc_method_is_named_after_the_component() {
+    assert_eq!(grpc_method::<EchoRequest>().path(), "/starknet.Echo/Handle");
+}
+
+#[rstest]
+#[case::bincode(BINCODE_PORT, SerializationFormat::Bincode)]
+#[case::json(JSON_PORT, SerializationFormat::Json)]
+#[tokio::test]
+async fn requests_are_answered_over_grpc(
+    #[case] port: u16,
+    #[case] serialization_format: SerializationFormat,
+) {
+    let client = start_grpc_echo(port, serialization_format).await;
+
+    let response = client.send(EchoRequest::Echo("Hello".to_string())).await.unwrap();
+
+    assert_eq!(response, EchoResponse::Echo("Hello".to_string()));
+}
+
+#[tokio::test]
+async fn grpc_call_past_deadline_times_out() {
+    let client = start_grpc_echo(DEADLINE_PORT, SerializationFormat::Bincode).await;
+
+    let result = RequestContext::new("test")
+        .with_deadline(Instant::now() + Duration::from_millis(50))
+        .scope(client.send(EchoRequest::Sleep(Duration::from_millis(200))))
+        .await;
+
+    assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+}
@@ -0,0 +1,63 @@
+use std::fs;
+
+use starknet_mempool_infra::journal::{read_json_lines, JournalOptions, JsonLinesJournal};
+use tempfile::tempdir;
+
+#[test]
+fn entries_are_appended_in_order() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let journal = JsonLinesJournal::open(&path, JournalOptions::default()).unwrap();
+
+    for entry in 0..3_u8 {
+        journal.append(entry);
+    }
+    journal.close();
+
+    assert_eq!(read_json_lines::<u8>(&path).unwrap(), [0, 1, 2]);
+}
+
+#[test]
+fn reopened_journal_keeps_its_entries() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let journal = JsonLinesJournal::open(&path, JournalOptions::default()).unwrap();
+    journal.append(0_u8);
+    journal.close();
+
+    let journal =
+        JsonLinesJournal::open(&path, JournalOptions { sync: true, ..Default::default() }).unwrap();
+    journal.append(1_u8);
+    journal.close();
+
+    assert_eq!(read_json_lines::<u8>(&path).unwrap(), [0, 1]);
+}
+
+#[test]
+fn truncated_entries_are_discarded() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let journal = JsonLinesJournal::open(&path, JournalOptions::default()).unwrap();
+
+    journal.append(0_u8);
+    journal.truncate();
+    journal.append(1_u8);
+    journal.close();
+
+    assert_eq!(read_json_lines::<u8>(&path).unwrap(), [1]);
+}
+
+#[test]
+fn torn_last_line_is_dropped() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    fs::write(&path, "0\n1\n[2").unwrap();
+
+    assert_eq!(read_json_lines::<u8>(&path).unwrap(), [0, 1]);
+}
+
+#[test]
+fn missing_journal_has_no_entries() {
+    let dir = tempdir().unwrap();
+    assert!(read_json_lines::<u8>(&dir.path().join("journal.jsonl")).unwrap().is_empty());
+}
@@ -28,7 +28,7 @@ type ComponentBClient = LocalComponentClient<ComponentBRequest, ComponentBRespon
 #[async_trait]
 impl ComponentAClientTrait for LocalComponentClient<ComponentARequest, ComponentAResponse> {
     async fn a_get_value(&self) -> ResultA {
-        let res = self.send(ComponentARequest::AGetValue).await;
+        let res = self.send(ComponentARequest::AGetValue).await?;
         match res {
             ComponentAResponse::AGetValue(value) => Ok(value),
         }
@@ -47,7 +47,7 @@ impl ComponentRequestHandler<ComponentARequest, ComponentAResponse> for Componen
 #[async_trait]
 impl ComponentBClientTrait for LocalComponentClient<ComponentBRequest, ComponentBResponse> {
     async fn b_get_value(&self) -> ResultB {
-        let res = self.send(ComponentBRequest::BGetValue).await;
+        let res = self.send(ComponentBRequest::BGetValue).await?;
         match res {
             ComponentBResponse::BGetValue(value) => Ok(value),
             unexpected_response => {
@@ -57,7 +57,7 @@ impl ComponentBClientTrait for LocalComponentClient<ComponentBRequest, Component
     }
 
     async fn b_set_value(&self, value: ValueB) -> ClientResult<()> {
-        match self.send(ComponentBRequest::BSetValue(value)).await {
+        match self.send(ComponentBRequest::BSetValue(value)).await? {
             ComponentBResponse::BSetValue => Ok(()),
             unexpected_response => {
                 Err(ClientError::UnexpectedResponse(format!("{unexpected_response:?}")))
@@ -0,0 +1,65 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use starknet_mempool_infra::log_format::JsonLogFormat;
+use tracing::{info, info_span};
+use tracing_subscriber::fmt::format::JsonFields;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{fmt, Registry};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Returns the logs of the given function, each parsed from a JSON line.
+fn json_logs(f: impl FnOnce()) -> Vec<Value> {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let layer = fmt::layer()
+        .event_format(JsonLogFormat)
+        .fmt_fields(JsonFields::new())
+        .with_writer(move || writer.clone());
+    tracing::subscriber::with_default(Registry::default().with(layer), f);
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    output.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn test_json_log_has_stable_fields() {
+    let logs = json_logs(|| info!(tx_hash = "0x1", n_txs = 2, "Added a transaction."));
+
+    let [log] = logs.as_slice() else { panic!("Expected a single log, got {logs:?}.") };
+    assert_eq!(log["level"], "INFO");
+    assert_eq!(log["component"], "log_format_test");
+    assert_eq!(log["message"], "Added a transaction.");
+    assert_eq!(log["tx_hash"], "0x1");
+    assert_eq!(log["n_txs"], 2);
+    assert!(log["timestamp"].is_string());
+}
+
+#[test]
+fn test_json_log_includes_the_fields_of_its_spans() {
+    let logs = json_logs(|| {
+        let _proposal = info_span!("propose", proposal_id = 7, height = 1).entered();
+        let _tx = info_span!("execute", height = 2, tx_hash = "0x1").entered();
+        info!("Executed a transaction.");
+    });
+
+    let [log] = logs.as_slice() else { panic!("Expected a single log, got {logs:?}.") };
+    // The fields of inner spans override the ones of outer spans.
+    assert_eq!(
+        [&log["proposal_id"], &log["height"], &log["tx_hash"]],
+        [&json!(7), &json!(2), &json!("0x1")]
+    );
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+use starknet_mempool_infra::payload_metrics::{
+    component_name,
+    PayloadSizeHistograms,
+    RequestVariant,
+};
+use strum_macros::IntoStaticStr;
+
+#[derive(Serialize)]
+struct Input {
+    content: String,
+}
+
+#[derive(Serialize, IntoStaticStr)]
+enum MempoolRequest {
+    GetTxs(usize),
+    AddTx(Input),
+    Unit,
+}
+
+// A request wrapped with its version, as encoded by versioned clients.
+#[derive(Serialize)]
+struct VersionedMempoolRequest {
+    version: u32,
+    request: MempoolRequest,
+}
+
+impl From<&VersionedMempoolRequest> for &'static str {
+    fn from(versioned_request: &VersionedMempoolRequest) -> Self {
+        (&versioned_request.request).into()
+    }
+}
+
+#[test]
+fn component_names() {
+    assert_eq!(component_name::<MempoolRequest>(), "Mempool");
+    assert_eq!(component_name::<VersionedMempoolRequest>(), "Mempool");
+    assert_eq!(component_name::<Input>(), "Input");
+}
+
+#[test]
+fn request_variant_names() {
+    assert_eq!(MempoolRequest::GetTxs(1).variant_name(), "GetTxs");
+    assert_eq!(MempoolRequest::AddTx(Input { content: "tx".to_string() }).variant_name(), "AddTx");
+    assert_eq!(MempoolRequest::Unit.variant_name(), "Unit");
+    assert_eq!(
+        VersionedMempoolRequest { version: 1, request: MempoolRequest::GetTxs(1) }.variant_name(),
+        "GetTxs"
+    );
+}
+
+#[test]
+fn histograms_labels() {
+    assert_eq!(
+        PayloadSizeHistograms::new(&MempoolRequest::Unit),
+        PayloadSizeHistograms { component: "Mempool", request_variant: "Unit" }
+    );
+}
@@ -1,8 +1,11 @@
 mod common;
 
 use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use assert_matches::assert_matches;
 use async_trait::async_trait;
 use bincode::{deserialize, serialize};
 use common::{
@@ -22,13 +25,23 @@ use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Client, Request, Response, Server, StatusCode, Uri};
 use rstest::rstest;
 use serde::Serialize;
-use starknet_mempool_infra::component_client::{ClientError, ClientResult, RemoteComponentClient};
+use starknet_mempool_infra::component_client::{
+    ClientError,
+    ClientResult,
+    RemoteClientCreationError,
+    RemoteComponentClient,
+    RetryConfig,
+    TlsConfig,
+};
 use starknet_mempool_infra::component_definitions::{
     ComponentRequestHandler,
     ServerError,
+    APPLICATION_JSON,
     APPLICATION_OCTET_STREAM,
 };
 use starknet_mempool_infra::component_server::{ComponentServerStarter, RemoteComponentServer};
+use starknet_mempool_infra::serialization::SerializationFormat;
+use starknet_mempool_infra::tls::{ServerTlsConfig, TlsError, TlsIdentity};
 use tokio::sync::Mutex;
 use tokio::task;
 
@@ -47,6 +60,18 @@ const UNCONNECTED_SERVER_PORT: u16 = 10002;
 const FAULTY_SERVER_REQ_DESER_PORT: u16 = 10003;
 const FAULTY_SERVER_RES_DESER_PORT: u16 = 10004;
 const RETRY_REQ_PORT: u16 = 10005;
+const NON_RETRIABLE_REQ_PORT: u16 = 10006;
+const TLS_PORT: u16 = 10014;
+const MUTUAL_TLS_PORT: u16 = 10015;
+const MUTUAL_TLS_UNAUTHENTICATED_PORT: u16 = 10016;
+const A_PORT_FROM_URL: u16 = 10012;
+const B_PORT_FROM_URL: u16 = 10013;
+const A_PORT_JSON: u16 = 10023;
+const B_PORT_JSON: u16 = 10024;
+const A_PORT_NEGOTIATED_FORMAT: u16 = 10025;
+const B_PORT_NEGOTIATED_FORMAT: u16 = 10026;
+const A_PORT_HAND_WRITTEN_JSON: u16 = 10027;
+const B_PORT_HAND_WRITTEN_JSON: u16 = 10028;
 const MOCK_SERVER_ERROR: &str = "mock server error";
 const ARBITRARY_DATA: &str = "arbitrary data";
 // ServerError::RequestDeserializationFailure error message.
@@ -54,6 +79,7 @@ const DESERIALIZE_REQ_ERROR_MESSAGE: &str = "Could not deserialize client reques
 // ClientError::ResponseDeserializationFailure error message.
 const DESERIALIZE_RES_ERROR_MESSAGE: &str = "Could not deserialize server response";
 const VALID_VALUE_A: ValueA = 1;
+const TLS_VALUE_B: ValueB = 7;
 
 #[async_trait]
 impl ComponentAClientTrait for RemoteComponentClient<ComponentARequest, ComponentAResponse> {
@@ -156,8 +182,19 @@ where
 }
 
 async fn setup_for_tests(setup_value: ValueB, a_port: u16, b_port: u16) {
-    let a_client = ComponentAClient::new(LOCAL_IP, a_port, MAX_RETRIES);
-    let b_client = ComponentBClient::new(LOCAL_IP, b_port, MAX_RETRIES);
+    setup_for_tests_with_format(setup_value, a_port, b_port, SerializationFormat::Bincode).await;
+}
+
+async fn setup_for_tests_with_format(
+    setup_value: ValueB,
+    a_port: u16,
+    b_port: u16,
+    serialization_format: SerializationFormat,
+) {
+    let a_client = ComponentAClient::new(LOCAL_IP, a_port, MAX_RETRIES)
+        .with_serialization_format(serialization_format);
+    let b_client = ComponentBClient::new(LOCAL_IP, b_port, MAX_RETRIES)
+        .with_serialization_format(serialization_format);
 
     let component_a = ComponentA::new(Box::new(b_client));
     let component_b = ComponentB::new(setup_value, Box::new(a_client.clone()));
@@ -166,12 +203,14 @@ async fn setup_for_tests(setup_value: ValueB, a_port: u16, b_port: u16) {
         ComponentA,
         ComponentARequest,
         ComponentAResponse,
-    >::new(component_a, LOCAL_IP, a_port);
+    >::new(component_a, LOCAL_IP, a_port)
+    .with_serialization_format(serialization_format);
     let mut component_b_server = RemoteComponentServer::<
         ComponentB,
         ComponentBRequest,
         ComponentBResponse,
-    >::new(component_b, LOCAL_IP, b_port);
+    >::new(component_b, LOCAL_IP, b_port)
+    .with_serialization_format(serialization_format);
 
     task::spawn(async move {
         component_a_server.start().await;
@@ -194,6 +233,94 @@ async fn test_proper_setup() {
     test_a_b_functionality(a_client, b_client, setup_value.into()).await;
 }
 
+#[tokio::test]
+async fn test_client_from_url() {
+    let setup_value: ValueB = 30;
+    setup_for_tests(setup_value, A_PORT_FROM_URL, B_PORT_FROM_URL).await;
+    let retry_config = RetryConfig {
+        max_retries: 1,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(1),
+        jitter: false,
+    };
+    let a_client = ComponentAClient::builder(&format!("http://[{LOCAL_IP}]:{A_PORT_FROM_URL}/"))
+        .retry_config(retry_config)
+        .build()
+        .unwrap();
+    let b_client = ComponentBClient::from_url(
+        &format!("http://[{LOCAL_IP}]:{B_PORT_FROM_URL}"),
+        None,
+        retry_config,
+    )
+    .unwrap();
+    test_a_b_functionality(a_client, b_client, setup_value.into()).await;
+}
+
+// Servers answer in the format of the request, whatever their own format.
+#[rstest]
+#[case::json(SerializationFormat::Json, A_PORT_JSON, B_PORT_JSON)]
+#[case::negotiated(
+    SerializationFormat::Bincode,
+    A_PORT_NEGOTIATED_FORMAT,
+    B_PORT_NEGOTIATED_FORMAT
+)]
+#[tokio::test]
+async fn test_json_servers(
+    #[case] client_format: SerializationFormat,
+    #[case] a_port: u16,
+    #[case] b_port: u16,
+) {
+    let setup_value: ValueB = 45;
+    setup_for_tests_with_format(setup_value, a_port, b_port, SerializationFormat::Json).await;
+    let a_client = ComponentAClient::new(LOCAL_IP, a_port, MAX_RETRIES)
+        .with_serialization_format(client_format);
+    let b_client = ComponentBClient::new(LOCAL_IP, b_port, MAX_RETRIES)
+        .with_serialization_format(client_format);
+    test_a_b_functionality(a_client, b_client, setup_value.into()).await;
+}
+
+#[tokio::test]
+async fn test_hand_written_json_request() {
+    let setup_value: ValueB = 60;
+    setup_for_tests(setup_value, A_PORT_HAND_WRITTEN_JSON, B_PORT_HAND_WRITTEN_JSON).await;
+    let uri: Uri = format!("http://[{LOCAL_IP}]:{A_PORT_HAND_WRITTEN_JSON}/").parse().unwrap();
+    let send = |content_type: &'static str| {
+        let http_request = Request::post(uri.clone())
+            .header(CONTENT_TYPE, content_type)
+            .body(Body::from(r#""AGetValue""#))
+            .unwrap();
+        Client::new().request(http_request)
+    };
+
+    let http_response = send(APPLICATION_JSON).await.unwrap();
+    assert_eq!(http_response.status(), StatusCode::OK);
+    assert_eq!(http_response.headers()[CONTENT_TYPE], APPLICATION_JSON);
+    let body_bytes = to_bytes(http_response.into_body()).await.unwrap();
+    assert_eq!(body_bytes, r#"{"AGetValue":60}"#);
+
+    let http_response = send("text/plain").await.unwrap();
+    assert_eq!(http_response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[test]
+fn test_client_from_invalid_url() {
+    let retry_config = RetryConfig::default();
+    assert_matches!(
+        ComponentAClient::from_url("http://[::1", None, retry_config).err(),
+        Some(RemoteClientCreationError::InvalidUrl { .. })
+    );
+    assert_matches!(
+        ComponentAClient::from_url("ftp://localhost:8080/", None, retry_config).err(),
+        Some(RemoteClientCreationError::UnsupportedScheme(_))
+    );
+    let tls_config =
+        TlsConfig { ca_certs_path: Some("nonexistent_ca_certs.pem".into()), client_identity: None };
+    assert_matches!(
+        ComponentAClient::from_url("https://localhost:8080/", Some(tls_config), retry_config).err(),
+        Some(RemoteClientCreationError::Tls(TlsError::RootCertificatesLoadingFailure(_)))
+    );
+}
+
 #[tokio::test]
 async fn test_faulty_client_setup() {
     // Todo(uriel): Find a better way to pass expected value to the setup
@@ -256,7 +383,7 @@ async fn test_faulty_server(
 
 #[tokio::test]
 async fn test_retry_request() {
-    // Spawn a server that responses with OK every other request.
+    // Spawn a server that responses with OK every other request, and is unavailable otherwise.
     task::spawn(async move {
         let should_send_ok = Arc::new(Mutex::new(false));
         async fn handler(
@@ -264,15 +391,17 @@ async fn test_retry_request() {
             should_send_ok: Arc<Mutex<bool>>,
         ) -> Result<Response<Body>, hyper::Error> {
             let mut should_send_ok = should_send_ok.lock().await;
-            let body = ComponentAResponse::AGetValue(VALID_VALUE_A);
             let ret = if *should_send_ok {
+                let body = ComponentAResponse::AGetValue(VALID_VALUE_A);
                 Response::builder()
                     .status(StatusCode::OK)
                     .body(Body::from(serialize(&body).unwrap()))
                     .unwrap()
             } else {
+                let body =
+                    ServerError::RequestDeserializationFailure(MOCK_SERVER_ERROR.to_string());
                 Response::builder()
-                    .status(StatusCode::IM_A_TEAPOT)
+                    .status(StatusCode::SERVICE_UNAVAILABLE)
                     .body(Body::from(serialize(&body).unwrap()))
                     .unwrap()
             };
@@ -303,6 +432,147 @@ async fn test_retry_request() {
 
     // The current server state is 'false', hence the first and only attempt returns an error.
     let a_client_no_retry = ComponentAClient::new(LOCAL_IP, RETRY_REQ_PORT, 0);
-    let expected_error_contained_keywords = [DESERIALIZE_RES_ERROR_MESSAGE];
+    let expected_error_contained_keywords = [StatusCode::SERVICE_UNAVAILABLE.as_str()];
     verify_error(a_client_no_retry.clone(), &expected_error_contained_keywords).await;
 }
+
+#[tokio::test]
+async fn test_non_retriable_request_is_not_retried() {
+    // Spawn a server that counts the requests, and rejects them as malformed.
+    let n_requests = Arc::new(Mutex::new(0));
+    let server_n_requests = n_requests.clone();
+    task::spawn(async move {
+        async fn handler(
+            _http_request: Request<Body>,
+            n_requests: Arc<Mutex<usize>>,
+        ) -> Result<Response<Body>, hyper::Error> {
+            *n_requests.lock().await += 1;
+            let body = ServerError::RequestDeserializationFailure(MOCK_SERVER_ERROR.to_string());
+            Ok(Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(serialize(&body).unwrap()))
+                .unwrap())
+        }
+
+        let socket = SocketAddr::new(LOCAL_IP, NON_RETRIABLE_REQ_PORT);
+        let make_svc = make_service_fn(|_conn| {
+            let n_requests = server_n_requests.clone();
+            async move { Ok::<_, hyper::Error>(service_fn(move |req| handler(req, n_requests.clone()))) }
+        });
+
+        Server::bind(&socket).serve(make_svc).await.unwrap();
+    });
+    // Todo(uriel): Get rid of this
+    // Ensure the server starts running.
+    task::yield_now().await;
+
+    let a_client = ComponentAClient::new(LOCAL_IP, NON_RETRIABLE_REQ_PORT, 3);
+    let expected_error_contained_keywords =
+        [StatusCode::BAD_REQUEST.as_str(), DESERIALIZE_REQ_ERROR_MESSAGE];
+    verify_error(a_client, &expected_error_contained_keywords).await;
+    assert_eq!(*n_requests.lock().await, 1);
+}
+
+#[rstest]
+#[case::first_retry(1, Duration::from_millis(100))]
+#[case::doubled(3, Duration::from_millis(400))]
+#[case::capped(5, Duration::from_millis(1000))]
+#[case::huge_exponent(100, Duration::from_millis(1000))]
+fn test_retry_delay_backoff(#[case] retry: usize, #[case] expected_delay: Duration) {
+    let retry_config = RetryConfig {
+        max_retries: 100,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(1000),
+        jitter: false,
+    };
+    assert_eq!(retry_config.delay(retry), expected_delay);
+
+    let jittered_delay = RetryConfig { jitter: true, ..retry_config }.delay(retry);
+    assert!(expected_delay / 2 <= jittered_delay && jittered_delay <= expected_delay);
+}
+
+fn tls_resource(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/tls").join(file_name)
+}
+
+fn tls_identity(name: &str) -> TlsIdentity {
+    TlsIdentity {
+        cert_path: tls_resource(&format!("{name}.pem")),
+        key_path: tls_resource(&format!("{name}.key")),
+    }
+}
+
+// Spawns a component B server over TLS, requiring client certificates if `mutual` is set, and
+// returns a client of it.
+async fn create_tls_client_and_server(
+    port: u16,
+    mutual: bool,
+    client_identity: Option<TlsIdentity>,
+) -> ComponentBClient {
+    let server_tls_config = ServerTlsConfig {
+        identity: tls_identity("server"),
+        client_ca_certs_path: mutual.then(|| tls_resource("ca.pem")),
+    };
+    // The component never calls component A.
+    let a_client = ComponentAClient::new(LOCAL_IP, UNCONNECTED_SERVER_PORT, MAX_RETRIES);
+    let component_b = ComponentB::new(TLS_VALUE_B, Box::new(a_client));
+    let mut server =
+        RemoteComponentServer::<ComponentB, ComponentBRequest, ComponentBResponse>::new(
+            component_b,
+            LOCAL_IP,
+            port,
+        )
+        .with_tls_config(&server_tls_config)
+        .unwrap();
+    task::spawn(async move {
+        server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+
+    let client_tls_config =
+        TlsConfig { ca_certs_path: Some(tls_resource("ca.pem")), client_identity };
+    ComponentBClient::builder(&format!("https://[{LOCAL_IP}]:{port}/"))
+        .tls_config(client_tls_config)
+        .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+        .build()
+        .unwrap()
+}
+
+#[rstest]
+#[case::tls(TLS_PORT, false, None)]
+#[case::mutual_tls(MUTUAL_TLS_PORT, true, Some(tls_identity("client")))]
+#[tokio::test]
+async fn test_tls(
+    #[case] port: u16,
+    #[case] mutual: bool,
+    #[case] client_identity: Option<TlsIdentity>,
+) {
+    let b_client = create_tls_client_and_server(port, mutual, client_identity).await;
+    assert_eq!(b_client.b_get_value().await.unwrap(), TLS_VALUE_B);
+}
+
+#[tokio::test]
+async fn test_mutual_tls_rejects_unauthenticated_client() {
+    let b_client = create_tls_client_and_server(MUTUAL_TLS_UNAUTHENTICATED_PORT, true, None).await;
+    assert_matches!(b_client.b_get_value().await, Err(ClientError::CommunicationFailure(_)));
+}
+
+#[test]
+fn test_tls_server_with_missing_identity() {
+    let a_client = ComponentAClient::new(LOCAL_IP, UNCONNECTED_SERVER_PORT, MAX_RETRIES);
+    let server_tls_config = ServerTlsConfig {
+        identity: TlsIdentity {
+            cert_path: "nonexistent_cert.pem".into(),
+            key_path: "nonexistent_key.pem".into(),
+        },
+        client_ca_certs_path: None,
+    };
+    let server = RemoteComponentServer::<ComponentB, ComponentBRequest, ComponentBResponse>::new(
+        ComponentB::new(0, Box::new(a_client)),
+        LOCAL_IP,
+        TLS_PORT,
+    )
+    .with_tls_config(&server_tls_config);
+    assert_matches!(server.err(), Some(TlsError::IdentityLoadingFailure(_)));
+}
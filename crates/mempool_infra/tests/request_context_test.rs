@@ -0,0 +1,107 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::{LocalComponentClient, RemoteComponentClient};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{
+    ComponentServerStarter,
+    LocalComponentServer,
+    RemoteComponentServer,
+};
+use starknet_mempool_infra::request_context::{RequestContext, UNKNOWN_ORIGIN};
+use tokio::sync::mpsc::channel;
+use tokio::task;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const FORWARDER_PORT: u16 = 10017;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct GetContext;
+
+impl From<&GetContext> for &'static str {
+    fn from(_: &GetContext) -> Self {
+        "GetContext"
+    }
+}
+
+type ContextResponse = Option<RequestContext>;
+type LocalRecorderClient = LocalComponentClient<GetContext, ContextResponse>;
+
+// Answers with the context of the request it handles.
+struct Recorder;
+
+#[async_trait]
+impl ComponentStarter for Recorder {}
+
+#[async_trait]
+impl ComponentRequestHandler<GetContext, ContextResponse> for Recorder {
+    async fn handle_request(&mut self, _request: GetContext) -> ContextResponse {
+        RequestContext::current()
+    }
+}
+
+// Answers with the context the recorder sees for the request it sends while handling a request.
+struct Forwarder {
+    recorder: LocalRecorderClient,
+}
+
+#[async_trait]
+impl ComponentRequestHandler<GetContext, ContextResponse> for Forwarder {
+    async fn handle_request(&mut self, request: GetContext) -> ContextResponse {
+        self.recorder.send(request).await.unwrap()
+    }
+}
+
+fn start_local_recorder() -> LocalRecorderClient {
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<GetContext, ContextResponse>>(32);
+    let mut server = LocalComponentServer::new(Recorder, rx);
+    task::spawn(async move {
+        server.start().await;
+    });
+    LocalComponentClient::new(tx)
+}
+
+#[tokio::test]
+async fn test_local_request_carries_context() {
+    let client = start_local_recorder();
+    let context = RequestContext::new("test");
+
+    let handled_context = context.clone().scope(client.send(GetContext)).await.unwrap();
+
+    assert_eq!(handled_context, Some(context));
+}
+
+#[tokio::test]
+async fn test_request_outside_of_context_starts_new_flow() {
+    let client = start_local_recorder();
+
+    let first_context = client.send(GetContext).await.unwrap().unwrap();
+    let second_context = client.send(GetContext).await.unwrap().unwrap();
+
+    assert_eq!(first_context.origin, UNKNOWN_ORIGIN);
+    assert_eq!(second_context.origin, UNKNOWN_ORIGIN);
+    assert_ne!(first_context.correlation_id, second_context.correlation_id);
+}
+
+#[tokio::test]
+async fn test_context_propagates_across_components() {
+    let forwarder = Forwarder { recorder: start_local_recorder() };
+    let mut forwarder_server = RemoteComponentServer::new(forwarder, LOCAL_IP, FORWARDER_PORT);
+    task::spawn(async move {
+        forwarder_server.start().await;
+    });
+    // Todo(uriel): Get rid of this
+    task::yield_now().await;
+    let client =
+        RemoteComponentClient::<GetContext, ContextResponse>::new(LOCAL_IP, FORWARDER_PORT, 0);
+    let context = RequestContext::new("test");
+
+    let handled_context = context.clone().scope(client.send(GetContext)).await.unwrap();
+
+    assert_eq!(handled_context, Some(context));
+}
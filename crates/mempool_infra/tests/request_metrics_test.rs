@@ -0,0 +1,24 @@
+use starknet_mempool_infra::request_metrics::LocalRequestMetrics;
+use tokio::sync::mpsc::channel;
+
+enum MempoolRequest {
+    GetTxs,
+}
+
+#[test]
+fn metrics_are_labeled_with_the_component() {
+    assert_eq!(LocalRequestMetrics::new::<MempoolRequest>().component, "Mempool");
+}
+
+#[tokio::test]
+async fn records_the_queued_requests() {
+    let request_metrics = LocalRequestMetrics::new::<MempoolRequest>();
+    let (sender, mut receiver) = channel::<MempoolRequest>(10);
+
+    sender.send(MempoolRequest::GetTxs).await.unwrap();
+    sender.send(MempoolRequest::GetTxs).await.unwrap();
+    assert_eq!(request_metrics.record_queue_depth(&sender), 2);
+
+    receiver.recv().await.unwrap();
+    assert_eq!(request_metrics.record_queue_depth(&sender), 1);
+}
@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::de::value::{Error as DeserializationError, StrDeserializer};
+use serde::de::IntoDeserializer;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{ComponentServerStarter, LocalComponentServer};
+use starknet_mempool_infra::request_context::RequestContext;
+use starknet_mempool_infra::request_priority::{
+    deserialize_request_priorities,
+    request_priority,
+    serialize_request_priorities,
+    PrioritizedRequests,
+    RequestPriorities,
+};
+use strum_macros::IntoStaticStr;
+use tokio::sync::mpsc::channel;
+use tokio::task;
+
+#[derive(Clone, Copy, Debug, PartialEq, IntoStaticStr)]
+enum MempoolRequest {
+    AddTransaction(usize),
+    GetTransactions(usize),
+}
+
+// Records the order it handled the requests in.
+#[derive(Clone, Default)]
+struct Mempool {
+    handled_requests: Arc<Mutex<Vec<MempoolRequest>>>,
+}
+
+#[async_trait]
+impl ComponentStarter for Mempool {}
+
+#[async_trait]
+impl ComponentRequestHandler<MempoolRequest, ()> for Mempool {
+    async fn handle_request(&mut self, request: MempoolRequest) {
+        self.handled_requests.lock().unwrap().push(request);
+    }
+}
+
+fn get_txs_first() -> RequestPriorities {
+    RequestPriorities::from([("GetTransactions".to_string(), 1)])
+}
+
+fn deserialize(raw_str: &str) -> Result<RequestPriorities, DeserializationError> {
+    let deserializer: StrDeserializer<'_, DeserializationError> = raw_str.into_deserializer();
+    deserialize_request_priorities(deserializer)
+}
+
+#[test]
+fn requests_are_popped_by_priority_then_in_order() {
+    let mut requests = PrioritizedRequests::new();
+    requests.push("low_1", 0);
+    requests.push("high_1", 2);
+    requests.push("low_2", 0);
+    requests.push("high_2", 2);
+
+    let popped: Vec<_> = std::iter::from_fn(|| requests.pop()).collect();
+
+    assert_eq!(popped, vec!["high_1", "high_2", "low_1", "low_2"]);
+}
+
+#[test]
+fn unlisted_requests_have_the_default_priority() {
+    let priorities = get_txs_first();
+
+    assert_eq!(request_priority(&priorities, &MempoolRequest::GetTransactions(1)), 1);
+    assert_eq!(request_priority(&priorities, &MempoolRequest::AddTransaction(1)), 0);
+}
+
+#[test]
+fn request_priorities_serialization_round_trip() {
+    let priorities = RequestPriorities::from([
+        ("CommitBlock".to_string(), 2),
+        ("GetTransactions".to_string(), 1),
+    ]);
+
+    let serialized = serialize_request_priorities(&priorities);
+
+    assert_eq!(serialized, "CommitBlock:2,GetTransactions:1");
+    assert_eq!(deserialize(&serialized).unwrap(), priorities);
+    assert_eq!(deserialize("").unwrap(), RequestPriorities::new());
+    assert!(deserialize("GetTransactions").is_err());
+    assert!(deserialize("GetTransactions:1,GetTransactions:2").is_err());
+}
+
+#[tokio::test]
+async fn queued_requests_of_higher_priority_are_handled_first() {
+    let mempool = Mempool::default();
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<MempoolRequest, ()>>(32);
+    let requests = [
+        MempoolRequest::AddTransaction(1),
+        MempoolRequest::AddTransaction(2),
+        MempoolRequest::GetTransactions(1),
+        MempoolRequest::AddTransaction(3),
+        MempoolRequest::GetTransactions(2),
+    ];
+    // The requests are queued before the server starts, as under load.
+    let mut response_receivers = vec![];
+    for request in requests {
+        let (res_tx, res_rx) = channel(1);
+        let context = RequestContext::new("test");
+        tx.send(ComponentRequestAndResponseSender { request, tx: res_tx, context }).await.unwrap();
+        response_receivers.push(res_rx);
+    }
+
+    let mut server =
+        LocalComponentServer::new(mempool.clone(), rx).with_request_priorities(get_txs_first());
+    task::spawn(async move {
+        server.start().await;
+    });
+    for mut res_rx in response_receivers {
+        res_rx.recv().await.unwrap();
+    }
+
+    assert_eq!(
+        *mempool.handled_requests.lock().unwrap(),
+        vec![
+            MempoolRequest::GetTransactions(1),
+            MempoolRequest::GetTransactions(2),
+            MempoolRequest::AddTransaction(1),
+            MempoolRequest::AddTransaction(2),
+            MempoolRequest::AddTransaction(3),
+        ]
+    );
+}
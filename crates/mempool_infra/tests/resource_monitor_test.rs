@@ -0,0 +1,57 @@
+use rstest::rstest;
+use starknet_mempool_infra::resource_monitor::{
+    ResourceGuardConfig,
+    ResourcePressure,
+    ResourceStatus,
+    ResourceUsage,
+    SharedResourceStatus,
+};
+use validator::Validate;
+
+fn config() -> ResourceGuardConfig {
+    ResourceGuardConfig {
+        enable: true,
+        max_rss_bytes: 1000,
+        max_open_fds: 100,
+        pressure_threshold_percent: 90,
+        ..Default::default()
+    }
+}
+
+#[rstest]
+#[case::below_thresholds(ResourceUsage { rss_bytes: 899, n_open_fds: 89 }, ResourcePressure::Normal)]
+#[case::rss_at_threshold(ResourceUsage { rss_bytes: 900, n_open_fds: 0 }, ResourcePressure::High)]
+#[case::fds_at_threshold(ResourceUsage { rss_bytes: 0, n_open_fds: 90 }, ResourcePressure::High)]
+fn pressure(#[case] usage: ResourceUsage, #[case] expected_pressure: ResourcePressure) {
+    assert_eq!(config().pressure(&usage), expected_pressure);
+}
+
+#[rstest]
+#[case::default(config(), true)]
+#[case::zero_polling_interval(ResourceGuardConfig { polling_interval_ms: 0, ..config() }, false)]
+fn config_validation(#[case] config: ResourceGuardConfig, #[case] is_valid: bool) {
+    assert_eq!(config.validate().is_ok(), is_valid);
+}
+
+#[test]
+fn shared_status() {
+    let status = SharedResourceStatus::default();
+    let reader = status.clone();
+    assert!(!reader.is_under_pressure());
+
+    let high = ResourceStatus {
+        usage: ResourceUsage { rss_bytes: 900, n_open_fds: 0 },
+        pressure: ResourcePressure::High,
+    };
+    status.set(high);
+    assert_eq!(reader.get(), high);
+    assert!(reader.is_under_pressure());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn sample_usage() {
+    let usage = ResourceUsage::sample().unwrap();
+    assert!(usage.rss_bytes > 0);
+    assert!(usage.n_open_fds > 0);
+}
@@ -0,0 +1,222 @@
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{body, Body, Request, Response, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_mempool_infra::component_client::{LocalComponentClient, RemoteComponentClient};
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use starknet_mempool_infra::component_server::{
+    ComponentServerStarter,
+    LocalComponentServer,
+    RemoteComponentServer,
+};
+use starknet_mempool_infra::trace_export::{TraceExportConfig, TraceExportLayer, TraceParent};
+use tokio::sync::mpsc::{channel, unbounded_channel, UnboundedReceiver};
+use tokio::task;
+use tokio::time::timeout;
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::DefaultGuard;
+use tracing::{info_span, Instrument};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+const LOCAL_IP: IpAddr = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
+const COLLECTOR_PORT_NESTED_SPANS: u16 = 10029;
+const COLLECTOR_PORT_LOCAL: u16 = 10030;
+const COLLECTOR_PORT_REMOTE: u16 = 10031;
+const SERVER_PORT_REMOTE: u16 = 10032;
+const COLLECTOR_PORT_UNSAMPLED: u16 = 10033;
+// Longer than the export interval.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Ping;
+
+impl From<&Ping> for &'static str {
+    fn from(_: &Ping) -> Self {
+        "Ping"
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Pong;
+
+struct Responder;
+
+#[async_trait]
+impl ComponentStarter for Responder {}
+
+#[async_trait]
+impl ComponentRequestHandler<Ping, Pong> for Responder {
+    async fn handle_request(&mut self, _request: Ping) -> Pong {
+        info_span!("handling").in_scope(|| Pong)
+    }
+}
+
+// Starts a collector that passes on the spans of each export request it receives.
+fn start_collector(port: u16) -> UnboundedReceiver<Vec<Value>> {
+    let (tx, rx) = unbounded_channel();
+    let make_svc = make_service_fn(move |_| {
+        let tx = tx.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let tx = tx.clone();
+                async move {
+                    let body = body::to_bytes(request.into_body()).await.unwrap();
+                    let export_request: Value = serde_json::from_slice(&body).unwrap();
+                    let spans = export_request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+                        .as_array()
+                        .unwrap()
+                        .clone();
+                    tx.send(spans).unwrap();
+                    Ok::<_, Infallible>(Response::new(Body::empty()))
+                }
+            }))
+        }
+    });
+    let server = Server::bind(&SocketAddr::new(LOCAL_IP, port)).serve(make_svc);
+    task::spawn(server);
+    rx
+}
+
+// Exports the spans of the current thread to the collector at the given port.
+fn export_spans(collector_port: u16, sampling_ratio: f64) -> DefaultGuard {
+    let config = TraceExportConfig {
+        enable: true,
+        endpoint: format!("http://[{LOCAL_IP}]:{collector_port}/v1/traces"),
+        sampling_ratio,
+    };
+    let (layer, exporter) = TraceExportLayer::new(&config).unwrap();
+    task::spawn(exporter);
+    // Like the node by default, filters out the debug spans of the connections of the HTTP clients,
+    // which keep the spans of the requests they were opened in from ending.
+    tracing::subscriber::set_default(Registry::default().with(layer).with(LevelFilter::INFO))
+}
+
+// Returns the exported spans, in the order they ended, up to the span of the given name.
+async fn received_spans_until(
+    collector: &mut UnboundedReceiver<Vec<Value>>,
+    last_span_name: &str,
+) -> Vec<Value> {
+    let mut spans: Vec<Value> = Vec::new();
+    while !spans.iter().any(|span| span["name"] == last_span_name) {
+        spans.extend(timeout(EXPORT_TIMEOUT, collector.recv()).await.unwrap().unwrap());
+    }
+    spans
+}
+
+fn attribute<'a>(span: &'a Value, key: &str) -> Option<&'a str> {
+    span["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|attribute| attribute["key"] == key)
+        .map(|attribute| attribute["value"]["stringValue"].as_str().unwrap())
+}
+
+fn assert_is_child(child: &Value, parent: &Value) {
+    assert_eq!(child["traceId"], parent["traceId"]);
+    assert_eq!(child["parentSpanId"], parent["spanId"]);
+}
+
+#[tokio::test]
+async fn test_nested_spans_are_exported_in_one_trace() {
+    let mut collector = start_collector(COLLECTOR_PORT_NESTED_SPANS);
+    let _guard = export_spans(COLLECTOR_PORT_NESTED_SPANS, 1.0);
+
+    info_span!("parent").in_scope(|| info_span!("child", tx_hash = "0x1").in_scope(|| {}));
+
+    let spans = received_spans_until(&mut collector, "parent").await;
+    let [child, parent] = spans.as_slice() else { panic!("Expected 2 spans, got {spans:?}.") };
+    assert_eq!(parent["name"], "parent");
+    assert_eq!(parent.get("parentSpanId"), None);
+    assert_eq!(child["name"], "child");
+    assert_is_child(child, parent);
+    assert_eq!(attribute(child, "tx_hash"), Some("0x1"));
+}
+
+#[tokio::test]
+async fn test_local_request_handling_joins_the_sender_trace() {
+    let mut collector = start_collector(COLLECTOR_PORT_LOCAL);
+    let _guard = export_spans(COLLECTOR_PORT_LOCAL, 1.0);
+    let (tx, rx) = channel::<ComponentRequestAndResponseSender<Ping, Pong>>(32);
+    let mut server = LocalComponentServer::new(Responder, rx);
+    task::spawn(async move {
+        server.start().await;
+    });
+    let client = LocalComponentClient::new(tx);
+
+    async { client.send(Ping).await.unwrap() }.instrument(info_span!("sender")).await;
+
+    let spans = received_spans_until(&mut collector, "sender").await;
+    let [handling, server_request, client_request, sender] = spans.as_slice() else {
+        panic!("Expected 4 spans, got {spans:?}.")
+    };
+    assert_eq!(handling["name"], "handling");
+    assert_is_child(handling, server_request);
+    assert_is_child(server_request, client_request);
+    assert_is_child(client_request, sender);
+}
+
+#[tokio::test]
+async fn test_remote_request_handling_joins_the_sender_trace() {
+    let mut collector = start_collector(COLLECTOR_PORT_REMOTE);
+    let _guard = export_spans(COLLECTOR_PORT_REMOTE, 1.0);
+    let mut server = RemoteComponentServer::new(Responder, LOCAL_IP, SERVER_PORT_REMOTE);
+    task::spawn(async move {
+        server.start().await;
+    });
+    task::yield_now().await;
+    let client = RemoteComponentClient::<Ping, Pong>::new(LOCAL_IP, SERVER_PORT_REMOTE, 0);
+
+    async { client.send(Ping).await.unwrap() }.instrument(info_span!("sender")).await;
+
+    let spans = received_spans_until(&mut collector, "sender").await;
+    let find_span = |name: &str| {
+        let mut matching_spans = spans.iter().filter(|span| span["name"] == name);
+        matching_spans.next().unwrap_or_else(|| panic!("Span {name} wasn't exported."))
+    };
+    let handling = find_span("handling");
+    let sender = find_span("sender");
+    let server_request = spans
+        .iter()
+        .find(|span| span["spanId"] == handling["parentSpanId"])
+        .expect("The parent of the handling span should be exported.");
+    let client_request = spans
+        .iter()
+        .find(|span| span["spanId"] == server_request["parentSpanId"])
+        .expect("The sender of the request should be exported.");
+    assert_eq!(server_request["name"], "request");
+    assert_is_child(server_request, client_request);
+    assert_is_child(client_request, sender);
+}
+
+#[tokio::test]
+async fn test_unsampled_traces_are_not_exported() {
+    let mut collector = start_collector(COLLECTOR_PORT_UNSAMPLED);
+    let _guard = export_spans(COLLECTOR_PORT_UNSAMPLED, 0.0);
+
+    info_span!("parent").in_scope(|| info_span!("child").in_scope(|| {}));
+
+    assert!(timeout(EXPORT_TIMEOUT, collector.recv()).await.is_err());
+}
+
+#[test]
+fn test_trace_parent_header_round_trip() {
+    let trace_parent =
+        TraceParent { trace_id: 0x4bf92f3577b34da6, span_id: 0xb7ad6b71, sampled: true };
+
+    let header_value = trace_parent.to_string();
+
+    assert_eq!(header_value, "00-00000000000000004bf92f3577b34da6-00000000b7ad6b71-01");
+    assert_eq!(TraceParent::parse(&header_value), Some(trace_parent));
+    assert_eq!(TraceParent::parse("01-00000000000000004bf92f3577b34da6-00000000b7ad6b71-01"), None);
+}
@@ -1,6 +0,0 @@
-pub mod communication;
-pub mod components;
-pub mod config;
-pub mod servers;
-pub mod utils;
-pub mod version;
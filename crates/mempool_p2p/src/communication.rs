@@ -0,0 +1,31 @@
+use starknet_mempool_infra::component_server::{
+    create_empty_server,
+    EmptyServer,
+    LocalComponentServer,
+};
+use tokio::sync::mpsc::Receiver;
+
+use crate::receiver::MempoolP2pReceiver;
+use crate::sender::{
+    MempoolP2pSender,
+    MempoolP2pSenderRequest,
+    MempoolP2pSenderRequestAndResponseSender,
+    MempoolP2pSenderResponse,
+};
+
+pub type LocalMempoolP2pSenderServer =
+    LocalComponentServer<MempoolP2pSender, MempoolP2pSenderRequest, MempoolP2pSenderResponse>;
+pub type MempoolP2pReceiverServer = EmptyServer<MempoolP2pReceiver>;
+
+pub fn create_local_mempool_p2p_sender_server(
+    mempool_p2p_sender: MempoolP2pSender,
+    rx_mempool_p2p_sender: Receiver<MempoolP2pSenderRequestAndResponseSender>,
+) -> LocalMempoolP2pSenderServer {
+    LocalComponentServer::new(mempool_p2p_sender, rx_mempool_p2p_sender)
+}
+
+pub fn create_mempool_p2p_receiver_server(
+    mempool_p2p_receiver: MempoolP2pReceiver,
+) -> MempoolP2pReceiverServer {
+    create_empty_server(mempool_p2p_receiver)
+}
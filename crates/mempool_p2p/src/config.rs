@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use papyrus_network::NetworkConfig;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// The configuration of the propagation of mempool transactions between peers.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct MempoolP2pConfig {
+    #[validate]
+    pub network_config: NetworkConfig,
+    // The topic the transactions are broadcast on. Peers propagate transactions to each other
+    // only if they share the topic.
+    pub transactions_topic: String,
+    // The size of the buffers of the channels between the mempool p2p components and the network.
+    #[validate(range(min = 1))]
+    pub network_buffer_size: usize,
+    // Transactions submitted to this node above this rate are not broadcast to peers.
+    #[validate(range(min = 1))]
+    pub max_broadcast_txs_per_second: usize,
+    // Transactions received from peers above this rate are dropped without being validated.
+    #[validate(range(min = 1))]
+    pub max_received_txs_per_second: usize,
+}
+
+impl Default for MempoolP2pConfig {
+    fn default() -> Self {
+        Self {
+            network_config: NetworkConfig::default(),
+            transactions_topic: "mempool_transactions".to_owned(),
+            network_buffer_size: 1000,
+            max_broadcast_txs_per_second: 1000,
+            max_received_txs_per_second: 1000,
+        }
+    }
+}
+
+impl SerializeConfig for MempoolP2pConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([
+            ser_param(
+                "transactions_topic",
+                &self.transactions_topic,
+                "The topic the mempool transactions are broadcast on.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "network_buffer_size",
+                &self.network_buffer_size,
+                "The size of the buffers of the channels between the mempool p2p components and \
+                 the network.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_broadcast_txs_per_second",
+                &self.max_broadcast_txs_per_second,
+                "The maximal number of transactions submitted to this node that are broadcast to \
+                 peers per second.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_received_txs_per_second",
+                &self.max_received_txs_per_second,
+                "The maximal number of transactions received from peers that are validated per \
+                 second; the rest are dropped.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(append_sub_config_name(self.network_config.dump(), "network_config"));
+        config
+    }
+}
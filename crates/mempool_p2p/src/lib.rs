@@ -1,13 +1,70 @@
+pub mod communication;
+pub mod config;
+mod rate_limiter;
 pub mod receiver;
 pub mod sender;
 
-use papyrus_network::NetworkConfig;
+use papyrus_network::gossipsub_impl::Topic;
+use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_gateway_types::communication::SharedGatewayClient;
 
+use crate::config::MempoolP2pConfig;
 use crate::receiver::MempoolP2pReceiver;
 use crate::sender::MempoolP2pSender;
 
+/// A transaction as broadcast to peers, encoded as JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RpcTransactionWrapper(pub RpcTransaction);
+
+impl TryFrom<Vec<u8>> for RpcTransactionWrapper {
+    type Error = serde_json::Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Ok(Self(serde_json::from_slice(&bytes)?))
+    }
+}
+
+impl From<RpcTransactionWrapper> for Vec<u8> {
+    fn from(tx: RpcTransactionWrapper) -> Self {
+        serde_json::to_vec(&tx.0).expect("Transactions should be serializable.")
+    }
+}
+
+/// Creates the components propagating transactions between peers, over a network registered to
+/// the transactions topic: the sender broadcasts the transactions submitted to this node, and the
+/// receiver adds the transactions of peers through the gateway.
 pub fn create_p2p_sender_and_receiver(
-    _network_config: NetworkConfig,
+    config: MempoolP2pConfig,
+    gateway_client: SharedGatewayClient,
 ) -> (MempoolP2pSender, MempoolP2pReceiver) {
-    unimplemented!()
+    let MempoolP2pConfig {
+        network_config,
+        transactions_topic,
+        network_buffer_size,
+        max_broadcast_txs_per_second,
+        max_received_txs_per_second,
+    } = config;
+
+    let mut network_manager = NetworkManager::new(network_config, None);
+    let BroadcastTopicChannels { messages_to_broadcast_sender, broadcast_client_channels } =
+        network_manager
+            .register_broadcast_topic(Topic::new(transactions_topic), network_buffer_size)
+            .expect("Failed to register the mempool transactions topic.");
+    let (continue_propagation_sender, continue_propagation_receiver) =
+        futures::channel::mpsc::channel(network_buffer_size);
+
+    let sender = MempoolP2pSender::new(
+        messages_to_broadcast_sender,
+        continue_propagation_sender,
+        max_broadcast_txs_per_second,
+    );
+    let receiver = MempoolP2pReceiver::new(
+        Some(network_manager),
+        broadcast_client_channels,
+        continue_propagation_receiver,
+        gateway_client,
+        max_received_txs_per_second,
+    );
+    (sender, receiver)
 }
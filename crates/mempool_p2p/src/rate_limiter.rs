@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "rate_limiter_test.rs"]
+mod rate_limiter_test;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Limits the number of events per second. Events are counted in consecutive windows of one
+/// second, starting at the first event after the previous window ended.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    max_per_second: usize,
+    window_start: Option<Instant>,
+    n_events_in_window: usize,
+}
+
+impl RateLimiter {
+    pub fn new(max_per_second: usize) -> Self {
+        Self { max_per_second, window_start: None, n_events_in_window: 0 }
+    }
+
+    /// Returns whether an event at the given time is within the limit, counting it if so.
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        let window_ended = match self.window_start {
+            Some(window_start) => now.duration_since(window_start) >= WINDOW,
+            None => true,
+        };
+        if window_ended {
+            self.window_start = Some(now);
+            self.n_events_in_window = 0;
+        }
+
+        if self.n_events_in_window >= self.max_per_second {
+            return false;
+        }
+        self.n_events_in_window += 1;
+        true
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::{Duration, Instant};
+
+use crate::rate_limiter::RateLimiter;
+
+#[test]
+fn events_above_the_limit_are_rejected_until_the_window_ends() {
+    let mut rate_limiter = RateLimiter::new(2);
+    let start = Instant::now();
+
+    assert!(rate_limiter.try_acquire(start));
+    assert!(rate_limiter.try_acquire(start + Duration::from_millis(500)));
+    assert!(!rate_limiter.try_acquire(start + Duration::from_millis(999)));
+
+    // A new window starts.
+    assert!(rate_limiter.try_acquire(start + Duration::from_secs(1)));
+    assert!(rate_limiter.try_acquire(start + Duration::from_millis(1500)));
+    assert!(!rate_limiter.try_acquire(start + Duration::from_millis(1500)));
+}
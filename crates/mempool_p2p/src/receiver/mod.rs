@@ -1,11 +1,136 @@
+use std::future::pending;
+use std::time::Instant;
+
 use async_trait::async_trait;
+use futures::{FutureExt, StreamExt};
+use papyrus_network::network_manager::{
+    BroadcastClientChannels,
+    BroadcastClientTrait,
+    BroadcastedMessageManager,
+    NetworkManager,
+};
+use starknet_gateway_types::communication::{GatewayClientError, SharedGatewayClient};
+use starknet_gateway_types::errors::GatewayError;
+use starknet_gateway_types::gateway_types::{
+    AddTransactionInput,
+    TransactionMetadata,
+    TransactionSource,
+};
 use starknet_mempool_infra::component_runner::{ComponentStartError, ComponentStarter};
+use tracing::{debug, error, warn};
+
+use crate::rate_limiter::RateLimiter;
+use crate::RpcTransactionWrapper;
 
-pub struct MempoolP2pReceiver;
+#[cfg(test)]
+#[path = "receiver_test.rs"]
+mod receiver_test;
+
+/// Runs the network, and adds the transactions broadcast by peers through the gateway, so that
+/// they pass the same validations as the transactions submitted to this node. Transactions the
+/// gateway accepts continue to propagate to other peers.
+pub struct MempoolP2pReceiver {
+    network_manager: Option<NetworkManager>,
+    broadcast_client_channels: BroadcastClientChannels<RpcTransactionWrapper>,
+    // Propagation requests of the sender, whose messages are handled by the broadcast channels.
+    continue_propagation_receiver: futures::channel::mpsc::Receiver<BroadcastedMessageManager>,
+    gateway_client: SharedGatewayClient,
+    rate_limiter: RateLimiter,
+}
+
+impl MempoolP2pReceiver {
+    pub fn new(
+        network_manager: Option<NetworkManager>,
+        broadcast_client_channels: BroadcastClientChannels<RpcTransactionWrapper>,
+        continue_propagation_receiver: futures::channel::mpsc::Receiver<BroadcastedMessageManager>,
+        gateway_client: SharedGatewayClient,
+        max_received_txs_per_second: usize,
+    ) -> Self {
+        Self {
+            network_manager,
+            broadcast_client_channels,
+            continue_propagation_receiver,
+            gateway_client,
+            rate_limiter: RateLimiter::new(max_received_txs_per_second),
+        }
+    }
+
+    async fn receive_txs(&mut self) {
+        loop {
+            tokio::select! {
+                Some((tx, message_manager)) = self.broadcast_client_channels.next() => {
+                    self.handle_received_tx(tx, message_manager).await;
+                }
+                Some(message_manager) = self.continue_propagation_receiver.next() => {
+                    self.broadcast_client_channels.continue_propagation(&message_manager).await;
+                }
+                else => return,
+            }
+        }
+    }
+
+    async fn handle_received_tx(
+        &mut self,
+        tx: Result<RpcTransactionWrapper, serde_json::Error>,
+        message_manager: BroadcastedMessageManager,
+    ) {
+        let tx = match tx {
+            Ok(RpcTransactionWrapper(tx)) => tx,
+            Err(err) => {
+                debug!("Received a malformed transaction from a peer: {}", err);
+                self.broadcast_client_channels.report_message(message_manager).await;
+                return;
+            }
+        };
+        if !self.rate_limiter.try_acquire(Instant::now()) {
+            debug!("Dropped a transaction received from a peer: the rate limit is exceeded.");
+            return;
+        }
+
+        let input = AddTransactionInput {
+            tx,
+            metadata: TransactionMetadata { source: TransactionSource::P2p },
+        };
+        match self.gateway_client.add_tx(input).await {
+            Ok(result) => {
+                debug!("Added transaction {} received from a peer.", result.tx_hash());
+                self.broadcast_client_channels.continue_propagation(&message_manager).await;
+            }
+            // Valid transactions may be rejected as well, e.g., if they are already known, so the
+            // peer is not reported.
+            Err(GatewayClientError::GatewayError(GatewayError::TransactionRejected {
+                message,
+                ..
+            })) => {
+                debug!("Rejected a transaction received from a peer: {}", message);
+            }
+            Err(GatewayClientError::GatewayError(GatewayError::TooManyRequests)) => {
+                debug!("Rejected a transaction received from a peer: its sender is rate limited.");
+            }
+            Err(GatewayClientError::GatewayError(GatewayError::ServiceUnavailable)) => {
+                debug!("Rejected a transaction received from a peer: the gateway is overloaded.");
+            }
+            Err(err) => warn!("Failed to add a transaction received from a peer: {}", err),
+        }
+    }
+}
 
 #[async_trait]
 impl ComponentStarter for MempoolP2pReceiver {
     async fn start(&mut self) -> Result<(), ComponentStartError> {
-        unimplemented!()
+        let network_future = match self.network_manager.take() {
+            Some(network_manager) => network_manager.run().boxed(),
+            None => pending().boxed(),
+        };
+
+        tokio::select! {
+            result = network_future => {
+                error!("The mempool p2p network stopped: {:?}", result);
+            }
+            () = self.receive_txs() => {
+                error!("The mempool p2p broadcast channels closed.");
+            }
+        }
+        Err(ComponentStartError::InternalComponentError)
     }
 }
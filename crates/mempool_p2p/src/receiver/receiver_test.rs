@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use mempool_test_utils::starknet_api_test_utils::{
+    rpc_tx_for_testing,
+    test_resource_bounds_mapping,
+    TransactionType,
+};
+use papyrus_network::network_manager::test_utils::{
+    create_test_broadcasted_message_manager,
+    mock_register_broadcast_topic,
+};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{Calldata, TransactionHash, TransactionSignature};
+use starknet_gateway_types::communication::MockGatewayClient;
+use starknet_gateway_types::gateway_types::{
+    AddTransactionInput,
+    AddTransactionResult,
+    TransactionMetadata,
+    TransactionSource,
+};
+
+use crate::receiver::MempoolP2pReceiver;
+use crate::RpcTransactionWrapper;
+
+fn tx() -> RpcTransaction {
+    rpc_tx_for_testing(
+        TransactionType::Invoke,
+        test_resource_bounds_mapping(),
+        Calldata::default(),
+        TransactionSignature::default(),
+    )
+}
+
+fn receiver(
+    gateway_client: MockGatewayClient,
+    max_received_txs_per_second: usize,
+) -> MempoolP2pReceiver {
+    let broadcast_client_channels =
+        mock_register_broadcast_topic().unwrap().subscriber_channels.broadcast_client_channels;
+    let (_continue_propagation_sender, continue_propagation_receiver) =
+        futures::channel::mpsc::channel(1);
+    MempoolP2pReceiver::new(
+        None,
+        broadcast_client_channels,
+        continue_propagation_receiver,
+        Arc::new(gateway_client),
+        max_received_txs_per_second,
+    )
+}
+
+// Returns a gateway client expecting the given transaction, received from a peer, the given
+// number of times.
+fn gateway_client_expecting_tx(tx: RpcTransaction, times: usize) -> MockGatewayClient {
+    let expected_input = AddTransactionInput {
+        tx,
+        metadata: TransactionMetadata { source: TransactionSource::P2p },
+    };
+    let mut gateway_client = MockGatewayClient::new();
+    gateway_client
+        .expect_add_tx()
+        .times(times)
+        .withf(move |input| input == &expected_input)
+        .returning(|_| Ok(AddTransactionResult::Invoke { tx_hash: TransactionHash::default() }));
+    gateway_client
+}
+
+#[tokio::test]
+async fn received_tx_is_added_through_the_gateway() {
+    let mut receiver = receiver(gateway_client_expecting_tx(tx(), 1), 10);
+
+    receiver
+        .handle_received_tx(
+            Ok(RpcTransactionWrapper(tx())),
+            create_test_broadcasted_message_manager(),
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn malformed_tx_is_not_added() {
+    let mut receiver = receiver(gateway_client_expecting_tx(tx(), 0), 10);
+
+    receiver
+        .handle_received_tx(
+            RpcTransactionWrapper::try_from(b"not a transaction".to_vec()),
+            create_test_broadcasted_message_manager(),
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn received_txs_above_the_rate_limit_are_dropped() {
+    let mut receiver = receiver(gateway_client_expecting_tx(tx(), 1), 1);
+
+    for _ in 0..2 {
+        receiver
+            .handle_received_tx(
+                Ok(RpcTransactionWrapper(tx())),
+                create_test_broadcasted_message_manager(),
+            )
+            .await;
+    }
+}
@@ -1,19 +1,87 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
+use futures::SinkExt;
+use mockall::automock;
+use papyrus_network::network_manager::BroadcastTopicSender;
 pub use papyrus_network::network_manager::BroadcastedMessageManager;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_mempool_infra::component_client::{ClientError, LocalComponentClient};
-use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    ComponentRequestHandler,
+};
+use starknet_mempool_infra::component_runner::ComponentStarter;
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
-pub struct MempoolP2pSender;
+use crate::rate_limiter::RateLimiter;
+use crate::RpcTransactionWrapper;
+
+#[cfg(test)]
+#[path = "sender_test.rs"]
+mod sender_test;
+
+pub type LocalMempoolP2pSenderClientImpl =
+    LocalComponentClient<MempoolP2pSenderRequest, MempoolP2pSenderResponse>;
+pub type MempoolP2pSenderRequestAndResponseSender =
+    ComponentRequestAndResponseSender<MempoolP2pSenderRequest, MempoolP2pSenderResponse>;
+
+/// Broadcasts transactions to peers. Continuing the propagation of transactions received from
+/// peers is delegated to the receiver, which holds the channels of their broadcasts.
+pub struct MempoolP2pSender {
+    broadcast_topic_sender: BroadcastTopicSender<RpcTransactionWrapper>,
+    continue_propagation_sender: futures::channel::mpsc::Sender<BroadcastedMessageManager>,
+    rate_limiter: RateLimiter,
+}
+
+impl MempoolP2pSender {
+    pub fn new(
+        broadcast_topic_sender: BroadcastTopicSender<RpcTransactionWrapper>,
+        continue_propagation_sender: futures::channel::mpsc::Sender<BroadcastedMessageManager>,
+        max_broadcast_txs_per_second: usize,
+    ) -> Self {
+        Self {
+            broadcast_topic_sender,
+            continue_propagation_sender,
+            rate_limiter: RateLimiter::new(max_broadcast_txs_per_second),
+        }
+    }
+
+    async fn add_transaction(&mut self, transaction: RpcTransaction) -> MempoolP2pSenderResult<()> {
+        if !self.rate_limiter.try_acquire(Instant::now()) {
+            return Err(MempoolP2pSenderError::RateLimitExceeded);
+        }
+        self.broadcast_topic_sender
+            .send(RpcTransactionWrapper(transaction))
+            .await
+            .map_err(|err| MempoolP2pSenderError::NetworkSendError(err.to_string()))
+    }
+
+    async fn continue_propagation(
+        &mut self,
+        propagation_manager: BroadcastedMessageManager,
+    ) -> MempoolP2pSenderResult<()> {
+        self.continue_propagation_sender
+            .send(propagation_manager)
+            .await
+            .map_err(|err| MempoolP2pSenderError::NetworkSendError(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for MempoolP2pSender {}
 
-// This error is defined even though it's empty to be compatible with the other components.
 #[derive(Debug, Error, Serialize, Deserialize, Clone)]
-pub enum MempoolP2pSenderError {}
+pub enum MempoolP2pSenderError {
+    #[error("Failed to send a message to the network: {0}")]
+    NetworkSendError(String),
+    #[error("The broadcast rate limit is exceeded.")]
+    RateLimitExceeded,
+}
 
 pub type MempoolP2pSenderResult<T> = Result<T, MempoolP2pSenderError>;
 
@@ -27,6 +95,7 @@ pub enum MempoolP2pSenderClientError {
 
 pub type MempoolP2pSenderClientResult<T> = Result<T, MempoolP2pSenderClientError>;
 
+#[automock]
 #[async_trait]
 pub trait MempoolP2pSenderClient: Send + Sync {
     /// Adds a transaction to be propagated to other peers. This should only be called on a new
@@ -46,7 +115,7 @@ pub trait MempoolP2pSenderClient: Send + Sync {
 
 pub type SharedMempoolP2pSenderClient = Arc<dyn MempoolP2pSenderClient>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
 pub enum MempoolP2pSenderRequest {
     AddTransaction(RpcTransaction),
     ContinuePropagation(BroadcastedMessageManager),
@@ -64,22 +133,29 @@ impl ComponentRequestHandler<MempoolP2pSenderRequest, MempoolP2pSenderResponse>
 {
     async fn handle_request(
         &mut self,
-        _request: MempoolP2pSenderRequest,
+        request: MempoolP2pSenderRequest,
     ) -> MempoolP2pSenderResponse {
-        unimplemented!()
+        match request {
+            MempoolP2pSenderRequest::AddTransaction(transaction) => {
+                MempoolP2pSenderResponse::AddTransaction(self.add_transaction(transaction).await)
+            }
+            MempoolP2pSenderRequest::ContinuePropagation(propagation_manager) => {
+                MempoolP2pSenderResponse::ContinuePropagation(
+                    self.continue_propagation(propagation_manager).await,
+                )
+            }
+        }
     }
 }
 
 #[async_trait]
-impl MempoolP2pSenderClient
-    for LocalComponentClient<MempoolP2pSenderRequest, MempoolP2pSenderResponse>
-{
+impl MempoolP2pSenderClient for LocalMempoolP2pSenderClientImpl {
     async fn add_transaction(
         &self,
         transaction: RpcTransaction,
     ) -> MempoolP2pSenderClientResult<()> {
         let request = MempoolP2pSenderRequest::AddTransaction(transaction);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(
             MempoolP2pSenderResponse,
             AddTransaction,
@@ -93,7 +169,7 @@ impl MempoolP2pSenderClient
         propagation_manager: BroadcastedMessageManager,
     ) -> MempoolP2pSenderClientResult<()> {
         let request = MempoolP2pSenderRequest::ContinuePropagation(propagation_manager);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(
             MempoolP2pSenderResponse,
             ContinuePropagation,
@@ -0,0 +1,72 @@
+use assert_matches::assert_matches;
+use futures::StreamExt;
+use mempool_test_utils::starknet_api_test_utils::{
+    rpc_tx_for_testing,
+    test_resource_bounds_mapping,
+    TransactionType,
+};
+use papyrus_network::network_manager::test_utils::{
+    mock_register_broadcast_topic,
+    BroadcastNetworkMock,
+};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{Calldata, TransactionSignature};
+use starknet_mempool_infra::component_definitions::ComponentRequestHandler;
+
+use crate::sender::{
+    MempoolP2pSender,
+    MempoolP2pSenderError,
+    MempoolP2pSenderRequest,
+    MempoolP2pSenderResponse,
+};
+use crate::RpcTransactionWrapper;
+
+fn tx() -> RpcTransaction {
+    rpc_tx_for_testing(
+        TransactionType::Invoke,
+        test_resource_bounds_mapping(),
+        Calldata::default(),
+        TransactionSignature::default(),
+    )
+}
+
+fn sender(
+    max_broadcast_txs_per_second: usize,
+) -> (MempoolP2pSender, BroadcastNetworkMock<RpcTransactionWrapper>) {
+    let channels = mock_register_broadcast_topic().unwrap();
+    let (continue_propagation_sender, _continue_propagation_receiver) =
+        futures::channel::mpsc::channel(1);
+    let sender = MempoolP2pSender::new(
+        channels.subscriber_channels.messages_to_broadcast_sender,
+        continue_propagation_sender,
+        max_broadcast_txs_per_second,
+    );
+    (sender, channels.mock_network)
+}
+
+#[tokio::test]
+async fn added_tx_is_broadcast() {
+    let (mut sender, mut mock_network) = sender(10);
+
+    let response = sender.handle_request(MempoolP2pSenderRequest::AddTransaction(tx())).await;
+
+    assert_matches!(response, MempoolP2pSenderResponse::AddTransaction(Ok(())));
+    assert_eq!(
+        mock_network.messages_to_broadcast_receiver.next().await,
+        Some(RpcTransactionWrapper(tx()))
+    );
+}
+
+#[tokio::test]
+async fn added_txs_above_the_rate_limit_are_not_broadcast() {
+    let (mut sender, _mock_network) = sender(1);
+
+    let response = sender.handle_request(MempoolP2pSenderRequest::AddTransaction(tx())).await;
+    assert_matches!(response, MempoolP2pSenderResponse::AddTransaction(Ok(())));
+
+    let response = sender.handle_request(MempoolP2pSenderRequest::AddTransaction(tx())).await;
+    assert_matches!(
+        response,
+        MempoolP2pSenderResponse::AddTransaction(Err(MempoolP2pSenderError::RateLimitExceeded))
+    );
+}
@@ -0,0 +1,309 @@
+//! Canonical transactions of every type and version, along with the values this node is expected
+//! to derive from them. Intended for SDKs and integration suites verifying their compatibility with
+//! the gateway and the batcher.
+
+use starknet_api::core::{
+    ChainId,
+    ClassHash,
+    CompiledClassHash,
+    ContractAddress,
+    EntryPointSelector,
+    Nonce,
+    PatriciaKey,
+};
+use starknet_api::data_availability::DataAvailabilityMode;
+use starknet_api::rpc_transaction::{
+    RpcDeployAccountTransaction,
+    RpcDeployAccountTransactionV3,
+    RpcInvokeTransaction,
+    RpcInvokeTransactionV3,
+    RpcTransaction,
+};
+use starknet_api::transaction::{
+    AccountDeploymentData,
+    AllResourceBounds,
+    Calldata,
+    ContractAddressSalt,
+    DeclareTransaction,
+    DeclareTransactionV0V1,
+    DeclareTransactionV2,
+    DeclareTransactionV3,
+    DeployAccountTransaction,
+    DeployAccountTransactionV1,
+    DeployAccountTransactionV3,
+    DeployTransaction,
+    Fee,
+    InvokeTransaction,
+    InvokeTransactionV0,
+    InvokeTransactionV1,
+    InvokeTransactionV3,
+    L1HandlerTransaction,
+    PaymasterData,
+    ResourceBounds,
+    Tip,
+    Transaction,
+    TransactionHash,
+    TransactionSignature,
+    TransactionVersion,
+    ValidResourceBounds,
+};
+use starknet_api::{calldata, class_hash, contract_address, felt, patricia_key};
+
+/// The chain all test vectors are hashed for.
+pub const TEST_VECTORS_CHAIN_ID: ChainId = ChainId::Sepolia;
+
+/// A canonical transaction and the values derived from it.
+#[derive(Clone, Debug)]
+pub struct TransactionTestVector {
+    pub name: &'static str,
+    pub tx: Transaction,
+    /// The RPC form of the transaction, for transactions accepted by the gateway. Converts into
+    /// `tx`.
+    pub rpc_tx: Option<RpcTransaction>,
+    /// The hash of `tx` on `TEST_VECTORS_CHAIN_ID`.
+    pub expected_tx_hash: TransactionHash,
+    /// The maximal fee the transaction may be charged: `max_fee` for deprecated transactions, and
+    /// the sum of max amount times max price per unit over the resource bounds of V3 transactions,
+    /// excluding the tip. Zero for transactions that are not charged by the sender.
+    pub expected_max_fee: Fee,
+}
+
+fn signature() -> TransactionSignature {
+    TransactionSignature(vec![felt!("0x5"), felt!("0x6")])
+}
+
+fn resource_bounds() -> AllResourceBounds {
+    AllResourceBounds {
+        l1_gas: ResourceBounds { max_amount: 0x100, max_price_per_unit: 0x1000 },
+        l2_gas: ResourceBounds { max_amount: 0x200, max_price_per_unit: 0x20 },
+        l1_data_gas: ResourceBounds { max_amount: 0x80, max_price_per_unit: 0x400 },
+    }
+}
+
+// l1_gas: 0x100 * 0x1000, l2_gas: 0x200 * 0x20, l1_data_gas: 0x80 * 0x400.
+const RESOURCE_BOUNDS_MAX_FEE: Fee = Fee(0x100000 + 0x4000 + 0x20000);
+const DEPRECATED_MAX_FEE: Fee = Fee(0x2386f26fc10000);
+
+fn rpc_invoke_tx_v3() -> RpcInvokeTransactionV3 {
+    RpcInvokeTransactionV3 {
+        sender_address: contract_address!("0x1000"),
+        calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+        signature: signature(),
+        nonce: Nonce(felt!("0x7")),
+        resource_bounds: resource_bounds(),
+        tip: Tip(0x10),
+        paymaster_data: PaymasterData(vec![]),
+        account_deployment_data: AccountDeploymentData(vec![]),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+    }
+}
+
+fn rpc_deploy_account_tx_v3() -> RpcDeployAccountTransactionV3 {
+    RpcDeployAccountTransactionV3 {
+        signature: signature(),
+        nonce: Nonce(felt!("0x0")),
+        class_hash: class_hash!("0x2000"),
+        contract_address_salt: ContractAddressSalt(felt!("0x3")),
+        constructor_calldata: calldata![felt!("0x4")],
+        resource_bounds: resource_bounds(),
+        tip: Tip(0x10),
+        paymaster_data: PaymasterData(vec![]),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+    }
+}
+
+/// Returns the test vectors, one per transaction type and version.
+// TODO: Add the RPC form of declare V3 once the class hash is computed in its conversion.
+pub fn transaction_test_vectors() -> Vec<TransactionTestVector> {
+    let deprecated_declare = DeclareTransactionV0V1 {
+        max_fee: DEPRECATED_MAX_FEE,
+        signature: signature(),
+        nonce: Nonce(felt!("0x1")),
+        class_hash: class_hash!("0x2000"),
+        sender_address: contract_address!("0x1000"),
+    };
+
+    vec![
+        TransactionTestVector {
+            name: "declare_v0",
+            tx: Transaction::Declare(DeclareTransaction::V0(DeclareTransactionV0V1 {
+                nonce: Nonce::default(),
+                ..deprecated_declare.clone()
+            })),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x77915a389da921f743d90adf958813756e83c68a038f2ddce6e4318cb0b5aed"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "declare_v1",
+            tx: Transaction::Declare(DeclareTransaction::V1(deprecated_declare)),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x74a2a5363ad9ce4cae382047c65d2b6ef73d37089dfe92d7d10e17add444547"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "declare_v2",
+            tx: Transaction::Declare(DeclareTransaction::V2(DeclareTransactionV2 {
+                max_fee: DEPRECATED_MAX_FEE,
+                signature: signature(),
+                nonce: Nonce(felt!("0x1")),
+                class_hash: class_hash!("0x2000"),
+                compiled_class_hash: CompiledClassHash(felt!("0x2001")),
+                sender_address: contract_address!("0x1000"),
+            })),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x11258010517c3c79010e969ef5d702ec93155e0ee2981129ebb2bf3332ba773"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "declare_v3",
+            tx: Transaction::Declare(DeclareTransaction::V3(DeclareTransactionV3 {
+                resource_bounds: ValidResourceBounds::AllResources(resource_bounds()),
+                tip: Tip(0x10),
+                signature: signature(),
+                nonce: Nonce(felt!("0x1")),
+                class_hash: class_hash!("0x2000"),
+                compiled_class_hash: CompiledClassHash(felt!("0x2001")),
+                sender_address: contract_address!("0x1000"),
+                nonce_data_availability_mode: DataAvailabilityMode::L1,
+                fee_data_availability_mode: DataAvailabilityMode::L1,
+                paymaster_data: PaymasterData(vec![]),
+                account_deployment_data: AccountDeploymentData(vec![]),
+            })),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x305f8883bc81fcf3df74f0c3cc5307cd37a7a1f1e8ce703cb7acb00812d61ac"
+            )),
+            expected_max_fee: RESOURCE_BOUNDS_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "deploy",
+            tx: Transaction::Deploy(DeployTransaction {
+                version: TransactionVersion::ZERO,
+                class_hash: class_hash!("0x2000"),
+                contract_address_salt: ContractAddressSalt(felt!("0x3")),
+                constructor_calldata: calldata![felt!("0x4")],
+            }),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x5317006807bf2a8bad9493bdd4d9e9a234b415510e11e4bffdd988ecd3d8422"
+            )),
+            expected_max_fee: Fee(0),
+        },
+        TransactionTestVector {
+            name: "deploy_account_v1",
+            tx: Transaction::DeployAccount(DeployAccountTransaction::V1(
+                DeployAccountTransactionV1 {
+                    max_fee: DEPRECATED_MAX_FEE,
+                    signature: signature(),
+                    nonce: Nonce(felt!("0x0")),
+                    class_hash: class_hash!("0x2000"),
+                    contract_address_salt: ContractAddressSalt(felt!("0x3")),
+                    constructor_calldata: calldata![felt!("0x4")],
+                },
+            )),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x549e03b3f7a7cfcf8e035be3aee72917fe0577feeab55d6687b22be5f54ee7b"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "deploy_account_v3",
+            tx: Transaction::DeployAccount(DeployAccountTransaction::V3(
+                DeployAccountTransactionV3 {
+                    resource_bounds: ValidResourceBounds::AllResources(resource_bounds()),
+                    tip: Tip(0x10),
+                    signature: signature(),
+                    nonce: Nonce(felt!("0x0")),
+                    class_hash: class_hash!("0x2000"),
+                    contract_address_salt: ContractAddressSalt(felt!("0x3")),
+                    constructor_calldata: calldata![felt!("0x4")],
+                    nonce_data_availability_mode: DataAvailabilityMode::L1,
+                    fee_data_availability_mode: DataAvailabilityMode::L1,
+                    paymaster_data: PaymasterData(vec![]),
+                },
+            )),
+            rpc_tx: Some(RpcTransaction::DeployAccount(RpcDeployAccountTransaction::V3(
+                rpc_deploy_account_tx_v3(),
+            ))),
+            expected_tx_hash: TransactionHash(felt!(
+                "0x5fa7cf9147a2f7ff0a9e7f3328ae19a8c6de010aa280271a1879a09b52c2726"
+            )),
+            expected_max_fee: RESOURCE_BOUNDS_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "invoke_v0",
+            tx: Transaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+                max_fee: DEPRECATED_MAX_FEE,
+                signature: signature(),
+                contract_address: contract_address!("0x1000"),
+                entry_point_selector: EntryPointSelector(felt!("0x8")),
+                calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+            })),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x512ecea4828f26ffb1614e3aa45f469a2f2c0043629c832113c818f7edf5f27"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "invoke_v1",
+            tx: Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+                max_fee: DEPRECATED_MAX_FEE,
+                signature: signature(),
+                nonce: Nonce(felt!("0x7")),
+                sender_address: contract_address!("0x1000"),
+                calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+            })),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x2f69ea79e76bbf2e25a14182b16721938df23b283e6dd625d02e3cf2970b334"
+            )),
+            expected_max_fee: DEPRECATED_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "invoke_v3",
+            tx: Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+                resource_bounds: ValidResourceBounds::AllResources(resource_bounds()),
+                tip: Tip(0x10),
+                signature: signature(),
+                nonce: Nonce(felt!("0x7")),
+                sender_address: contract_address!("0x1000"),
+                calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+                nonce_data_availability_mode: DataAvailabilityMode::L1,
+                fee_data_availability_mode: DataAvailabilityMode::L1,
+                paymaster_data: PaymasterData(vec![]),
+                account_deployment_data: AccountDeploymentData(vec![]),
+            })),
+            rpc_tx: Some(RpcTransaction::Invoke(RpcInvokeTransaction::V3(rpc_invoke_tx_v3()))),
+            expected_tx_hash: TransactionHash(felt!(
+                "0x2a5e01e0a46b83c611019b11270658c7d6b6d1b22cc36cd790a5dbaccff9cd2"
+            )),
+            expected_max_fee: RESOURCE_BOUNDS_MAX_FEE,
+        },
+        TransactionTestVector {
+            name: "l1_handler",
+            tx: Transaction::L1Handler(L1HandlerTransaction {
+                version: TransactionVersion::ZERO,
+                nonce: Nonce(felt!("0x9")),
+                contract_address: contract_address!("0x1000"),
+                entry_point_selector: EntryPointSelector(felt!("0x8")),
+                calldata: calldata![felt!("0x1234"), felt!("0x1")],
+            }),
+            rpc_tx: None,
+            expected_tx_hash: TransactionHash(felt!(
+                "0x5239f13976bf38a7b9981a7515090883eae4cd0e275b84a6774c42723bf1ca4"
+            )),
+            expected_max_fee: Fee(0),
+        },
+    ]
+}
@@ -0,0 +1,177 @@
+use mempool_test_utils::test_vectors::{
+    transaction_test_vectors,
+    TransactionTestVector,
+    TEST_VECTORS_CHAIN_ID,
+};
+use starknet_api::executable_transaction;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::{
+    DeclareTransaction,
+    DeployAccountTransaction,
+    Fee,
+    InvokeTransaction,
+    Transaction,
+    TransactionHash,
+    ValidResourceBounds,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+fn max_fee(tx: &Transaction) -> Fee {
+    let resource_bounds = match tx {
+        Transaction::Declare(DeclareTransaction::V0(tx) | DeclareTransaction::V1(tx)) => {
+            return tx.max_fee;
+        }
+        Transaction::Declare(DeclareTransaction::V2(tx)) => return tx.max_fee,
+        Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => return tx.max_fee,
+        Transaction::Invoke(InvokeTransaction::V0(tx)) => return tx.max_fee,
+        Transaction::Invoke(InvokeTransaction::V1(tx)) => return tx.max_fee,
+        Transaction::Deploy(_) | Transaction::L1Handler(_) => return Fee(0),
+        Transaction::Declare(DeclareTransaction::V3(tx)) => &tx.resource_bounds,
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => &tx.resource_bounds,
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => &tx.resource_bounds,
+    };
+    let ValidResourceBounds::AllResources(resource_bounds) = resource_bounds else {
+        panic!("Test vectors should bound all resources.");
+    };
+    Fee([resource_bounds.l1_gas, resource_bounds.l2_gas, resource_bounds.l1_data_gas]
+        .iter()
+        .map(|bounds| u128::from(bounds.max_amount) * bounds.max_price_per_unit)
+        .sum())
+}
+
+fn test_vector(name: &str) -> TransactionTestVector {
+    transaction_test_vectors()
+        .into_iter()
+        .find(|vector| vector.name == name)
+        .unwrap_or_else(|| panic!("No test vector named {name}."))
+}
+
+fn short_string(string: &str) -> Felt {
+    Felt::from_bytes_be_slice(string.as_bytes())
+}
+
+// A resource bound, as hashed by V3 transactions: the resource name, followed by the max amount
+// (64 bits) and the max price per unit (128 bits).
+fn resource_bound(name: &str, max_amount: u64, max_price_per_unit: u128) -> Felt {
+    short_string(name) * Felt::TWO.pow(192_u32)
+        + Felt::from(max_amount) * Felt::TWO.pow(128_u32)
+        + Felt::from(max_price_per_unit)
+}
+
+// The fields shared by the hashes of the V3 test vectors, up to and including the data
+// availability modes: tip 0x10, the resource bounds of the vectors, no paymaster data and L1 data
+// availability.
+fn v3_common_fields(tx_prefix: &str, sender_address: Felt, nonce: Felt) -> Vec<Felt> {
+    let tip_and_resource_bounds = Poseidon::hash_array(&[
+        Felt::from(0x10_u8),
+        resource_bound("L1_GAS", 0x100, 0x1000),
+        resource_bound("L2_GAS", 0x200, 0x20),
+    ]);
+    vec![
+        short_string(tx_prefix),
+        Felt::THREE,
+        sender_address,
+        tip_and_resource_bounds,
+        Poseidon::hash_array(&[]),
+        short_string("SN_SEPOLIA"),
+        nonce,
+        Felt::ZERO,
+    ]
+}
+
+// The expected hashes are checked against hashes computed here from the transaction hash
+// specification, independently of the hashing of starknet_api.
+#[test]
+fn tx_hashes_follow_the_specification() {
+    let calldata = [Felt::ONE, Felt::TWO, Felt::THREE];
+    let sender_address = Felt::from(0x1000_u16);
+
+    let invoke_v1_hash = Pedersen::hash_array(&[
+        short_string("invoke"),
+        Felt::ONE,
+        sender_address,
+        Felt::ZERO,
+        Pedersen::hash_array(&calldata),
+        Felt::from(0x2386f26fc10000_u64),
+        short_string("SN_SEPOLIA"),
+        Felt::from(0x7_u8),
+    ]);
+    assert_eq!(test_vector("invoke_v1").expected_tx_hash, TransactionHash(invoke_v1_hash));
+
+    let mut invoke_v3_fields = v3_common_fields("invoke", sender_address, Felt::from(0x7_u8));
+    invoke_v3_fields.extend([Poseidon::hash_array(&[]), Poseidon::hash_array(&calldata)]);
+    assert_eq!(
+        test_vector("invoke_v3").expected_tx_hash,
+        TransactionHash(Poseidon::hash_array(&invoke_v3_fields))
+    );
+
+    let mut declare_v3_fields = v3_common_fields("declare", sender_address, Felt::ONE);
+    declare_v3_fields.extend([
+        Poseidon::hash_array(&[]),
+        Felt::from(0x2000_u16),
+        Felt::from(0x2001_u16),
+    ]);
+    assert_eq!(
+        test_vector("declare_v3").expected_tx_hash,
+        TransactionHash(Poseidon::hash_array(&declare_v3_fields))
+    );
+}
+
+#[test]
+fn tx_hashes() {
+    for vector in transaction_test_vectors() {
+        assert_eq!(
+            vector.tx.calculate_transaction_hash(&TEST_VECTORS_CHAIN_ID).unwrap(),
+            vector.expected_tx_hash,
+            "Unexpected hash of {}.",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn max_fees() {
+    for vector in transaction_test_vectors() {
+        assert_eq!(
+            max_fee(&vector.tx),
+            vector.expected_max_fee,
+            "Unexpected fee of {}.",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn rpc_conversions() {
+    for vector in transaction_test_vectors() {
+        let Some(rpc_tx) = vector.rpc_tx else {
+            continue;
+        };
+        assert_eq!(
+            Transaction::from(rpc_tx.clone()),
+            vector.tx,
+            "Unexpected conversion of {}.",
+            vector.name
+        );
+
+        // The hash the gateway assigns the transaction when converting it to an executable one.
+        let tx_hash = match rpc_tx {
+            RpcTransaction::DeployAccount(tx) => {
+                executable_transaction::DeployAccountTransaction::from_rpc_tx(
+                    tx,
+                    &TEST_VECTORS_CHAIN_ID,
+                )
+                .unwrap()
+                .tx_hash
+            }
+            RpcTransaction::Invoke(tx) => {
+                executable_transaction::InvokeTransaction::from_rpc_tx(tx, &TEST_VECTORS_CHAIN_ID)
+                    .unwrap()
+                    .tx_hash
+            }
+            RpcTransaction::Declare(_) => unreachable!("Declare vectors have no RPC form."),
+        };
+        assert_eq!(tx_hash, vector.expected_tx_hash, "Unexpected hash of {}.", vector.name);
+    }
+}
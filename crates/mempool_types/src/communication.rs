@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use mockall::predicate::*;
@@ -6,16 +7,30 @@ use mockall::*;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_error_codes::{CodedError, ErrorCode, Severity};
 use starknet_mempool_infra::component_client::{
     ClientError,
     LocalComponentClient,
     RemoteComponentClient,
 };
-use starknet_mempool_infra::component_definitions::ComponentRequestAndResponseSender;
+use starknet_mempool_infra::component_definitions::{
+    ComponentRequestAndResponseSender,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+use starknet_mempool_infra::component_error::{Component, ComponentError};
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use crate::errors::MempoolError;
-use crate::mempool_types::MempoolInput;
+use crate::mempool_types::{
+    CommitBlockArgs,
+    DropAccountTxsArgs,
+    MempoolInput,
+    RejectedTransaction,
+    TxBudget,
+};
 
 pub type LocalMempoolClientImpl = LocalComponentClient<MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolClientImpl = RemoteComponentClient<MempoolRequest, MempoolResponse>;
@@ -34,18 +49,98 @@ pub trait MempoolClient: Send + Sync {
     // TODO: Rename tx to transaction
     async fn add_tx(&self, mempool_input: MempoolInput) -> MempoolClientResult<()>;
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<Transaction>>;
+    /// Like `get_txs`, but bounded by the gas estimates and the sizes of the transactions instead
+    /// of their number, so that the block builder takes one block's worth of transactions.
+    async fn get_txs_by_budget(&self, budget: TxBudget) -> MempoolClientResult<Vec<Transaction>>;
+    /// Like `get_txs`, but answers within about `max_wait` with the transactions taken by then,
+    /// which may be fewer than are ready, instead of waiting on shards held by other operations.
+    async fn get_txs_within(
+        &self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolClientResult<Vec<Transaction>>;
+    /// Makes the mempool push eligible transactions over the transaction stream, instead of
+    /// waiting for `get_txs` requests.
+    async fn open_tx_stream(&self) -> MempoolClientResult<()>;
+    async fn close_tx_stream(&self) -> MempoolClientResult<()>;
+    /// Evicts up to `n_txs` transactions that are not eligible for sequencing. Returns the number
+    /// of evicted transactions.
+    async fn evict_txs(&self, n_txs: usize) -> MempoolClientResult<usize>;
+    /// Returns transactions taken by `get_txs` that were not added to a block, so that they are
+    /// sequenced again.
+    async fn return_txs(&self, txs: Vec<Transaction>) -> MempoolClientResult<()>;
+    /// Reports transactions taken by `get_txs` that the block builder rejected, so that they and
+    /// the transactions depending on them are not sequenced again.
+    async fn reject_txs(&self, rejected_txs: Vec<RejectedTransaction>) -> MempoolClientResult<()>;
+    /// Drops the transactions of a committed block from the mempool, and updates the nonces of
+    /// the accounts it changed.
+    async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()>;
+    /// Drops all the transactions of an account in one operation, e.g., once it is denylisted,
+    /// including the ones that are not eligible yet. Returns the hashes of the dropped
+    /// transactions.
+    async fn drop_account_txs(
+        &self,
+        args: DropAccountTxsArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>>;
+    /// Drops all the transactions held in the mempool, e.g., by an operator. Transactions already
+    /// taken for the block in creation are not affected. Returns the hashes of the dropped
+    /// transactions.
+    async fn flush(&self) -> MempoolClientResult<Vec<TransactionHash>>;
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr)]
 pub enum MempoolRequest {
     AddTransaction(MempoolInput),
     GetTransactions(usize),
+    GetTransactionsByBudget(TxBudget),
+    GetTransactionsWithin(usize, Duration),
+    OpenTransactionStream,
+    CloseTransactionStream,
+    EvictTransactions(usize),
+    ReturnTransactions(Vec<Transaction>),
+    RejectTransactions(Vec<RejectedTransaction>),
+    CommitBlock(CommitBlockArgs),
+    DropAccountTransactions(DropAccountTxsArgs),
+    Flush,
+    IsAlive,
+    IsReady,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum MempoolResponse {
     AddTransaction(MempoolResult<()>),
     GetTransactions(MempoolResult<Vec<Transaction>>),
+    GetTransactionsByBudget(MempoolResult<Vec<Transaction>>),
+    GetTransactionsWithin(MempoolResult<Vec<Transaction>>),
+    OpenTransactionStream(MempoolResult<()>),
+    CloseTransactionStream(MempoolResult<()>),
+    EvictTransactions(MempoolResult<usize>),
+    ReturnTransactions(MempoolResult<()>),
+    RejectTransactions(MempoolResult<()>),
+    CommitBlock(MempoolResult<()>),
+    DropAccountTransactions(MempoolResult<Vec<TransactionHash>>),
+    Flush(MempoolResult<Vec<TransactionHash>>),
+    IsAlive(bool),
+    IsReady(bool),
+}
+
+impl HealthCheckRequest for MempoolRequest {
+    fn is_alive() -> Self {
+        MempoolRequest::IsAlive
+    }
+
+    fn is_ready() -> Self {
+        MempoolRequest::IsReady
+    }
+}
+
+impl HealthCheckResponse for MempoolResponse {
+    fn health(&self) -> Option<bool> {
+        match self {
+            MempoolResponse::IsAlive(health) | MempoolResponse::IsReady(health) => Some(*health),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -56,17 +151,46 @@ pub enum MempoolClientError {
     MempoolError(#[from] MempoolError),
 }
 
+impl ComponentError for MempoolClientError {
+    fn component(&self) -> Component {
+        Component::Mempool
+    }
+
+    fn is_retriable(&self) -> bool {
+        match self {
+            MempoolClientError::ClientError(err) => err.is_retriable(),
+            MempoolClientError::MempoolError(err) => err.is_retriable(),
+        }
+    }
+}
+
+impl CodedError for MempoolClientError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            MempoolClientError::ClientError(err) => err.error_code(),
+            MempoolClientError::MempoolError(err) => err.error_code(),
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            MempoolClientError::ClientError(err) => err.severity(),
+            MempoolClientError::MempoolError(err) => err.severity(),
+        }
+    }
+}
+
 #[async_trait]
 impl MempoolClient for LocalMempoolClientImpl {
     async fn add_tx(&self, mempool_input: MempoolInput) -> MempoolClientResult<()> {
         let request = MempoolRequest::AddTransaction(mempool_input);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(MempoolResponse, AddTransaction, MempoolClientError, MempoolError)
     }
 
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<Transaction>> {
         let request = MempoolRequest::GetTransactions(n_txs);
-        let response = self.send(request).await;
+        let response = self.send(request).await?;
         handle_response_variants!(
             MempoolResponse,
             GetTransactions,
@@ -74,6 +198,113 @@ impl MempoolClient for LocalMempoolClientImpl {
             MempoolError
         )
     }
+
+    async fn get_txs_by_budget(&self, budget: TxBudget) -> MempoolClientResult<Vec<Transaction>> {
+        let request = MempoolRequest::GetTransactionsByBudget(budget);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionsByBudget,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_txs_within(
+        &self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolClientResult<Vec<Transaction>> {
+        let request = MempoolRequest::GetTransactionsWithin(n_txs, max_wait);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionsWithin,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn open_tx_stream(&self) -> MempoolClientResult<()> {
+        let request = MempoolRequest::OpenTransactionStream;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            OpenTransactionStream,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn close_tx_stream(&self) -> MempoolClientResult<()> {
+        let request = MempoolRequest::CloseTransactionStream;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            CloseTransactionStream,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn evict_txs(&self, n_txs: usize) -> MempoolClientResult<usize> {
+        let request = MempoolRequest::EvictTransactions(n_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            EvictTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn return_txs(&self, txs: Vec<Transaction>) -> MempoolClientResult<()> {
+        let request = MempoolRequest::ReturnTransactions(txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            ReturnTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn reject_txs(&self, rejected_txs: Vec<RejectedTransaction>) -> MempoolClientResult<()> {
+        let request = MempoolRequest::RejectTransactions(rejected_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            RejectTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()> {
+        let request = MempoolRequest::CommitBlock(args);
+        let response = self.send(request).await?;
+        handle_response_variants!(MempoolResponse, CommitBlock, MempoolClientError, MempoolError)
+    }
+
+    async fn drop_account_txs(
+        &self,
+        args: DropAccountTxsArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::DropAccountTransactions(args);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            DropAccountTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn flush(&self) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::Flush;
+        let response = self.send(request).await?;
+        handle_response_variants!(MempoolResponse, Flush, MempoolClientError, MempoolError)
+    }
 }
 
 #[async_trait]
@@ -94,4 +325,111 @@ impl MempoolClient for RemoteMempoolClientImpl {
             MempoolError
         )
     }
+
+    async fn get_txs_by_budget(&self, budget: TxBudget) -> MempoolClientResult<Vec<Transaction>> {
+        let request = MempoolRequest::GetTransactionsByBudget(budget);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionsByBudget,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_txs_within(
+        &self,
+        n_txs: usize,
+        max_wait: Duration,
+    ) -> MempoolClientResult<Vec<Transaction>> {
+        let request = MempoolRequest::GetTransactionsWithin(n_txs, max_wait);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            GetTransactionsWithin,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn open_tx_stream(&self) -> MempoolClientResult<()> {
+        let request = MempoolRequest::OpenTransactionStream;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            OpenTransactionStream,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn close_tx_stream(&self) -> MempoolClientResult<()> {
+        let request = MempoolRequest::CloseTransactionStream;
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            CloseTransactionStream,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn evict_txs(&self, n_txs: usize) -> MempoolClientResult<usize> {
+        let request = MempoolRequest::EvictTransactions(n_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            EvictTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn return_txs(&self, txs: Vec<Transaction>) -> MempoolClientResult<()> {
+        let request = MempoolRequest::ReturnTransactions(txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            ReturnTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn reject_txs(&self, rejected_txs: Vec<RejectedTransaction>) -> MempoolClientResult<()> {
+        let request = MempoolRequest::RejectTransactions(rejected_txs);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            RejectTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()> {
+        let request = MempoolRequest::CommitBlock(args);
+        let response = self.send(request).await?;
+        handle_response_variants!(MempoolResponse, CommitBlock, MempoolClientError, MempoolError)
+    }
+
+    async fn drop_account_txs(
+        &self,
+        args: DropAccountTxsArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::DropAccountTransactions(args);
+        let response = self.send(request).await?;
+        handle_response_variants!(
+            MempoolResponse,
+            DropAccountTransactions,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn flush(&self) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::Flush;
+        let response = self.send(request).await?;
+        handle_response_variants!(MempoolResponse, Flush, MempoolClientError, MempoolError)
+    }
 }
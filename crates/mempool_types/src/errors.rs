@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use starknet_api::core::{ContractAddress, Nonce};
-use starknet_api::transaction::TransactionHash;
+use starknet_api::transaction::{Tip, TransactionHash};
+use starknet_error_codes::{codes, CodedError, ErrorCode, Severity};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
@@ -9,9 +10,94 @@ pub enum MempoolError {
     DuplicateNonce { address: ContractAddress, nonce: Nonce },
     #[error("Duplicate transaction, with hash: {tx_hash}")]
     DuplicateTransaction { tx_hash: TransactionHash },
+    #[error("Mempool is full, and the transaction with hash {tx_hash} has too low a priority.")]
+    MempoolFull { tx_hash: TransactionHash },
+    #[error(
+        "Transaction nonce too far in the future, sender address: {address}, nonce: {:?}, maximal \
+         allowed nonce: {:?}",
+        nonce,
+        max_allowed_nonce
+    )]
+    NonceTooFarInFuture { address: ContractAddress, nonce: Nonce, max_allowed_nonce: Nonce },
+    #[error(
+        "Too many pending transactions, sender address: {address}, maximal number of pending \
+         transactions per account: {max_pending_txs}"
+    )]
+    TooManyPendingTransactions { address: ContractAddress, max_pending_txs: usize },
+    #[error(
+        "Too many scheduled transactions, maximal number of scheduled transactions: \
+         {max_scheduled_txs}"
+    )]
+    TooManyScheduledTransactions { max_scheduled_txs: usize },
+    #[error(
+        "Replacing transaction tip is too low, sender address: {address}, nonce: {:?}, tip: {}, \
+         minimal replacement tip: {}",
+        nonce,
+        tip.0,
+        min_tip.0
+    )]
+    ReplacementTipTooLow { address: ContractAddress, nonce: Nonce, tip: Tip, min_tip: Tip },
+    #[error("Transaction stream is not available.")]
+    TransactionStreamUnavailable,
     #[error("Transaction with hash: {tx_hash} not found")]
     TransactionNotFound { tx_hash: TransactionHash },
     // TODO(Mohammad): Consider using `StarknetApiError` once it implements `PartialEq`.
     #[error("Out of range.")]
     FeltOutOfRange,
 }
+
+impl MempoolError {
+    /// Whether the request may succeed if sent again as is, once transactions leave the mempool.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            MempoolError::MempoolFull { .. }
+            | MempoolError::TooManyScheduledTransactions { .. }
+            | MempoolError::TransactionStreamUnavailable => true,
+            MempoolError::DuplicateNonce { .. }
+            | MempoolError::DuplicateTransaction { .. }
+            | MempoolError::NonceTooFarInFuture { .. }
+            | MempoolError::TooManyPendingTransactions { .. }
+            | MempoolError::ReplacementTipTooLow { .. }
+            | MempoolError::TransactionNotFound { .. }
+            | MempoolError::FeltOutOfRange => false,
+        }
+    }
+}
+
+impl CodedError for MempoolError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            MempoolError::DuplicateNonce { .. } => codes::MEMPOOL_DUPLICATE_NONCE,
+            MempoolError::DuplicateTransaction { .. } => codes::MEMPOOL_DUPLICATE_TRANSACTION,
+            MempoolError::MempoolFull { .. } => codes::MEMPOOL_FULL,
+            MempoolError::NonceTooFarInFuture { .. } => codes::MEMPOOL_NONCE_TOO_FAR_IN_FUTURE,
+            MempoolError::TooManyPendingTransactions { .. } => {
+                codes::MEMPOOL_TOO_MANY_PENDING_TRANSACTIONS
+            }
+            MempoolError::TooManyScheduledTransactions { .. } => {
+                codes::MEMPOOL_TOO_MANY_SCHEDULED_TRANSACTIONS
+            }
+            MempoolError::ReplacementTipTooLow { .. } => codes::MEMPOOL_REPLACEMENT_TIP_TOO_LOW,
+            MempoolError::TransactionStreamUnavailable => {
+                codes::MEMPOOL_TRANSACTION_STREAM_UNAVAILABLE
+            }
+            MempoolError::TransactionNotFound { .. } => codes::MEMPOOL_TRANSACTION_NOT_FOUND,
+            MempoolError::FeltOutOfRange => codes::MEMPOOL_FELT_OUT_OF_RANGE,
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            MempoolError::DuplicateNonce { .. }
+            | MempoolError::DuplicateTransaction { .. }
+            | MempoolError::NonceTooFarInFuture { .. }
+            | MempoolError::TooManyPendingTransactions { .. }
+            | MempoolError::ReplacementTipTooLow { .. }
+            | MempoolError::TransactionNotFound { .. } => Severity::Info,
+            MempoolError::MempoolFull { .. }
+            | MempoolError::TooManyScheduledTransactions { .. }
+            | MempoolError::TransactionStreamUnavailable => Severity::Warning,
+            MempoolError::FeltOutOfRange => Severity::Error,
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod communication;
 pub mod errors;
 pub mod mempool_types;
+pub mod resource_bounds;
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockTimestamp;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::Transaction;
+use starknet_api::transaction::TransactionHash;
 
 use crate::errors::MempoolError;
 
@@ -21,6 +26,103 @@ pub struct Account {
 pub struct MempoolInput {
     pub tx: Transaction,
     pub account: Account,
+    // If set, the transaction is held by the mempool and becomes eligible for sequencing only once
+    // this timestamp passes. Intended for scheduled operational transactions, not for user ones.
+    #[serde(default)]
+    pub eligible_at: Option<BlockTimestamp>,
+}
+
+/// Bounds the resources of the transactions taken by `get_txs_by_budget`, e.g., to the limits of a
+/// block. The gas of a transaction is estimated by the max amount of its resource bounds, so that
+/// the transactions taken never exceed the budget once executed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxBudget {
+    pub max_gas: u64,
+    // Of the transactions in their binary encoding.
+    pub max_bytes: usize,
+}
+
+impl TxBudget {
+    /// Spends the given resources if they fit in the remaining budget, and returns whether they
+    /// did.
+    pub fn try_spend(&mut self, gas: u64, n_bytes: usize) -> bool {
+        if gas > self.max_gas || n_bytes > self.max_bytes {
+            return false;
+        }
+        self.max_gas -= gas;
+        self.max_bytes -= n_bytes;
+        true
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.max_gas == 0 || self.max_bytes == 0
+    }
+}
+
+/// The transactions of a committed block, and the nonces of the accounts it changed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommitBlockArgs {
+    pub committed_txs: Vec<TransactionHash>,
+    // The nonce of every account with committed transactions, following its last committed one.
+    pub nonces: HashMap<ContractAddress, Nonce>,
+}
+
+/// Why the transactions of an account are dropped from the mempool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvictionReason {
+    /// The account was added to the denylist.
+    Denylisted,
+    /// The deployment of the account failed, so its transactions can't be executed.
+    FailedDeployment,
+}
+
+impl fmt::Display for EvictionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvictionReason::Denylisted => write!(f, "denylisted"),
+            EvictionReason::FailedDeployment => write!(f, "failed deployment"),
+        }
+    }
+}
+
+/// An account whose transactions are all dropped from the mempool, and the reason.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropAccountTxsArgs {
+    pub address: ContractAddress,
+    pub reason: EvictionReason,
+}
+
+/// Why the block builder rejected a transaction taken from the mempool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejectionReason {
+    /// The nonce of the transaction doesn't follow the nonce of its account.
+    InvalidNonce,
+    /// The balance of the account doesn't cover the maximal fee of the transaction.
+    InsufficientBalance,
+    /// The transaction took longer to execute than allowed.
+    ExecutionTimeout,
+    /// The transaction failed to execute for any other reason, e.g., a failed validation.
+    ExecutionFailed,
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::InvalidNonce => write!(f, "invalid nonce"),
+            RejectionReason::InsufficientBalance => write!(f, "insufficient balance"),
+            RejectionReason::ExecutionTimeout => write!(f, "execution timeout"),
+            RejectionReason::ExecutionFailed => write!(f, "execution failed"),
+        }
+    }
+}
+
+/// A transaction taken from the mempool that the block builder rejected, and the reason.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedTransaction {
+    pub tx_hash: TransactionHash,
+    pub address: ContractAddress,
+    pub nonce: Nonce,
+    pub reason: RejectionReason,
 }
 
 pub type MempoolResult<T> = Result<T, MempoolError>;
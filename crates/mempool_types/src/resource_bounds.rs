@@ -0,0 +1,197 @@
+//! Resource bounds checks shared by the gateway, which admits transactions to the mempool, and the
+//! block builder, which executes them. Both stages run the same checks, so a transaction the
+//! gateway admits is never dropped by the block builder for its resource bounds alone.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroU128;
+use std::sync::{Arc, RwLock};
+
+use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::{AllResourceBounds, Resource, ResourceBounds, ValidResourceBounds};
+use thiserror::Error;
+
+/// The gas price of the blocks built by the sequencer.
+// TODO: Replace with the gas prices of the proposal, once the block builder takes them.
+pub const MIN_GAS_PRICE: NonZeroU128 = NonZeroU128::MIN;
+// A thousand fee tokens.
+const DEFAULT_MAX_FEE: u128 = 1_000_000_000_000_000_000_000;
+
+/// The fee policy of the chain. The gateway rejects the transactions that don't follow it before
+/// they reach the mempool, and the block builder executes the blocks with gas prices at least its
+/// minimal ones and leaves out the transactions that don't follow it, so both must have the same
+/// policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeConfig {
+    pub min_l1_gas_price: NonZeroU128,
+    pub min_l2_gas_price: NonZeroU128,
+    // If set, caps the maximal fee a transaction commits to by its resource bounds.
+    pub max_fee: Option<u128>,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self { min_l1_gas_price: MIN_GAS_PRICE, min_l2_gas_price: MIN_GAS_PRICE, max_fee: None }
+    }
+}
+
+impl SerializeConfig for FeeConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([
+            ser_param(
+                "min_l1_gas_price",
+                &self.min_l1_gas_price,
+                "The minimal L1 gas price of the built blocks. Transactions whose max L1 gas \
+                 price is lower are rejected.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "min_l2_gas_price",
+                &self.min_l2_gas_price,
+                "The minimal L2 gas price of the built blocks. Transactions whose max L2 gas \
+                 price is lower are rejected, unless they bound their L2 gas amount to zero.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(ser_optional_param(
+            &self.max_fee,
+            DEFAULT_MAX_FEE,
+            "max_fee",
+            "If set, transactions whose resource bounds imply a higher maximal fee are rejected.",
+            ParamPrivacyInput::Public,
+        ));
+        config
+    }
+}
+
+impl FeeConfig {
+    /// The L1 gas price of a block, given the L1 gas price of the base layer.
+    pub fn l1_gas_price(&self, base_layer_gas_price: u128) -> NonZeroU128 {
+        self.min_l1_gas_price.max(NonZeroU128::new(base_layer_gas_price).unwrap_or(MIN_GAS_PRICE))
+    }
+
+    /// The L2 gas price of a block, given the L2 gas price set by the fee market.
+    pub fn l2_gas_price(&self, fee_market_gas_price: NonZeroU128) -> NonZeroU128 {
+        self.min_l2_gas_price.max(fee_market_gas_price)
+    }
+
+    /// Validates the resource bounds of a transaction against the minimal gas prices and the
+    /// maximal fee of the policy.
+    pub fn validate_resource_bounds(
+        &self,
+        resource_bounds: &ValidResourceBounds,
+    ) -> Result<(), ResourceBoundsError> {
+        validate_l1_gas_bounds(resource_bounds.get_l1_bounds(), self.min_l1_gas_price)?;
+        validate_l2_gas_price(resource_bounds.get_l2_bounds(), self.min_l2_gas_price)?;
+        if let Some(max_fee) = self.max_fee {
+            validate_max_fee(resource_bounds, max_fee)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The L2 gas price of the next block. Updated by the fee market of the batcher once a block is
+/// committed, and read by the block builder and the gateway.
+#[derive(Clone, Debug)]
+pub struct SharedL2GasPrice(Arc<RwLock<NonZeroU128>>);
+
+impl SharedL2GasPrice {
+    pub fn new(l2_gas_price: NonZeroU128) -> Self {
+        Self(Arc::new(RwLock::new(l2_gas_price)))
+    }
+
+    pub fn get(&self) -> NonZeroU128 {
+        *self.0.read().expect("L2 gas price lock should not be poisoned.")
+    }
+
+    pub fn set(&self, l2_gas_price: NonZeroU128) {
+        *self.0.write().expect("L2 gas price lock should not be poisoned.") = l2_gas_price;
+    }
+}
+
+impl Default for SharedL2GasPrice {
+    fn default() -> Self {
+        Self::new(MIN_GAS_PRICE)
+    }
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourceBoundsError {
+    #[error("Max L1 gas price {max_price_per_unit} is lower than the L1 gas price {gas_price}.")]
+    MaxL1GasPriceTooLow { max_price_per_unit: u128, gas_price: u128 },
+    #[error("Max L2 gas price {max_price_per_unit} is lower than the L2 gas price {gas_price}.")]
+    MaxL2GasPriceTooLow { max_price_per_unit: u128, gas_price: u128 },
+    #[error("The maximal fee {tx_max_fee} implied by the resource bounds exceeds {max_fee}.")]
+    MaxFeeTooHigh { tx_max_fee: u128, max_fee: u128 },
+    #[error("Expected a positive amount of {resource:?}. Got {resource_bounds:?}.")]
+    ZeroResourceBounds { resource: Resource, resource_bounds: ResourceBounds },
+}
+
+/// Validates the L1 gas bounds of a transaction against the L1 gas price of the block it is
+/// executed in: the max amount must be positive, and the max price per unit must cover the gas
+/// price. These are necessary for passing the fee bounds check of the execution, which also
+/// requires the max amount to cover the minimal gas consumption of the transaction.
+pub fn validate_l1_gas_bounds(
+    l1_bounds: ResourceBounds,
+    l1_gas_price: NonZeroU128,
+) -> Result<(), ResourceBoundsError> {
+    if l1_bounds.max_amount == 0 || l1_bounds.max_price_per_unit == 0 {
+        return Err(ResourceBoundsError::ZeroResourceBounds {
+            resource: Resource::L1Gas,
+            resource_bounds: l1_bounds,
+        });
+    }
+    if l1_bounds.max_price_per_unit < l1_gas_price.get() {
+        return Err(ResourceBoundsError::MaxL1GasPriceTooLow {
+            max_price_per_unit: l1_bounds.max_price_per_unit,
+            gas_price: l1_gas_price.get(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates the L2 gas bounds of a transaction against the L2 gas price of the block it is
+/// executed in, which the fee market moves with the congestion. Transactions that bound their L2
+/// gas amount to zero are not checked.
+pub fn validate_l2_gas_price(
+    l2_bounds: ResourceBounds,
+    l2_gas_price: NonZeroU128,
+) -> Result<(), ResourceBoundsError> {
+    if l2_bounds.max_amount > 0 && l2_bounds.max_price_per_unit < l2_gas_price.get() {
+        return Err(ResourceBoundsError::MaxL2GasPriceTooLow {
+            max_price_per_unit: l2_bounds.max_price_per_unit,
+            gas_price: l2_gas_price.get(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates that the maximal fee a transaction commits to, the sum over the resources of the max
+/// amount times the max price per unit, is at most the given maximal fee.
+pub fn validate_max_fee(
+    resource_bounds: &ValidResourceBounds,
+    max_fee: u128,
+) -> Result<(), ResourceBoundsError> {
+    let all_resource_bounds = match resource_bounds {
+        ValidResourceBounds::L1Gas(l1_bounds) => {
+            [*l1_bounds, ResourceBounds::default(), ResourceBounds::default()]
+        }
+        ValidResourceBounds::AllResources(AllResourceBounds { l1_gas, l2_gas, l1_data_gas }) => {
+            [*l1_gas, *l2_gas, *l1_data_gas]
+        }
+    };
+    // Saturates, as a fee that overflows exceeds any maximal fee.
+    let tx_max_fee = all_resource_bounds.iter().fold(0_u128, |tx_max_fee, bounds| {
+        tx_max_fee
+            .saturating_add(u128::from(bounds.max_amount).saturating_mul(bounds.max_price_per_unit))
+    });
+    if tx_max_fee > max_fee {
+        return Err(ResourceBoundsError::MaxFeeTooHigh { tx_max_fee, max_fee });
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::process::Command;
 
+use ethers::types::U256;
 use ethers::utils::{Ganache, GanacheInstance};
 use pretty_assertions::assert_eq;
 use starknet_api::block::{BlockHash, BlockNumber};
@@ -8,7 +9,11 @@ use starknet_api::felt;
 use tar::Archive;
 use tempfile::{tempdir, TempDir};
 
-use crate::ethereum_base_layer_contract::{EthereumBaseLayerConfig, EthereumBaseLayerContract};
+use crate::ethereum_base_layer_contract::{
+    blob_fee,
+    EthereumBaseLayerConfig,
+    EthereumBaseLayerContract,
+};
 use crate::BaseLayerContract;
 
 type EthereumContractAddress = String;
@@ -91,3 +96,13 @@ async fn latest_proved_block_ethereum() {
         assert_eq!(latest_block, expected);
     }
 }
+
+#[test]
+fn blob_fee_follows_eip_4844() {
+    const UPDATE_FRACTION: u64 = 3338477;
+    // The blob fee is the minimal fee, times e to the power of the excess blob gas over the update
+    // fraction, rounded down.
+    assert_eq!(blob_fee(U256::zero()), U256::from(1));
+    assert_eq!(blob_fee(U256::from(UPDATE_FRACTION)), U256::from(2));
+    assert_eq!(blob_fee(U256::from(10 * UPDATE_FRACTION)), U256::from(22026));
+}
@@ -15,7 +15,11 @@ use starknet_api::hash::StarkHash;
 use starknet_api::StarknetApiError;
 use url::ParseError;
 
-use crate::BaseLayerContract;
+use crate::{BaseLayerContract, PriceSample};
+
+// The parameters of the blob gas price, as set by EIP-4844.
+const MIN_BASE_FEE_PER_BLOB_GAS: u64 = 1;
+const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3338477;
 
 #[derive(thiserror::Error, Debug)]
 pub enum EthereumBaseLayerError {
@@ -115,4 +119,57 @@ impl BaseLayerContract for EthereumBaseLayerContract {
             BlockHash(StarkHash::from_hex_unchecked(state_block_hash.encode_hex().as_str())),
         )))
     }
+
+    async fn latest_l1_block_number(
+        &self,
+        min_confirmations: u64,
+    ) -> Result<Option<u64>, Self::Error> {
+        let block_number = self.contract.client().get_block_number().await?;
+        Ok(block_number.as_u64().checked_sub(min_confirmations))
+    }
+
+    async fn get_price_sample(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<PriceSample>, Self::Error> {
+        let Some(block) = self.contract.client().get_block(block_number).await? else {
+            return Ok(None);
+        };
+        // Blocks before EIP-1559 and EIP-4844 have no base fee and no blob fee, respectively.
+        Ok(Some(PriceSample {
+            timestamp: block.timestamp.as_u64(),
+            base_fee_per_gas: block.base_fee_per_gas.map_or(0, saturating_u128),
+            blob_fee: block
+                .excess_blob_gas
+                .map_or(0, |excess_blob_gas| saturating_u128(blob_fee(excess_blob_gas))),
+        }))
+    }
+}
+
+// The price of a unit of blob gas, given the excess blob gas of the block, as defined in EIP-4844.
+pub(crate) fn blob_fee(excess_blob_gas: U256) -> U256 {
+    fake_exponential(
+        U256::from(MIN_BASE_FEE_PER_BLOB_GAS),
+        excess_blob_gas,
+        U256::from(BLOB_BASE_FEE_UPDATE_FRACTION),
+    )
+}
+
+// Approximates `factor * e ** (numerator / denominator)` using a Taylor expansion, as defined in
+// EIP-4844.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut i = U256::one();
+    let mut output = U256::zero();
+    let mut numerator_accumulation = factor * denominator;
+    while !numerator_accumulation.is_zero() {
+        output = output.saturating_add(numerator_accumulation);
+        numerator_accumulation =
+            numerator_accumulation.saturating_mul(numerator) / (denominator * i);
+        i += U256::one();
+    }
+    output / denominator
+}
+
+fn saturating_u128(value: U256) -> u128 {
+    u128::try_from(value).unwrap_or(u128::MAX)
 }
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
 
 #[cfg(test)]
@@ -17,4 +18,23 @@ pub trait BaseLayerContract {
         &self,
         min_confirmations: Option<u64>,
     ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error>;
+
+    /// Get the number of the latest base layer block, or `None` if there are fewer blocks than
+    /// the required confirmations.
+    async fn latest_l1_block_number(
+        &self,
+        min_confirmations: u64,
+    ) -> Result<Option<u64>, Self::Error>;
+
+    /// Get the gas prices of the given base layer block, or `None` if it doesn't exist yet.
+    async fn get_price_sample(&self, block_number: u64)
+        -> Result<Option<PriceSample>, Self::Error>;
+}
+
+/// The gas prices of a base layer block, in wei.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceSample {
+    pub timestamp: u64,
+    pub base_fee_per_gas: u128,
+    pub blob_fee: u128,
 }
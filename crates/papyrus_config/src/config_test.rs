@@ -19,6 +19,7 @@ use crate::converters::deserialize_milliseconds_to_duration;
 use crate::dumping::{
     append_sub_config_name,
     combine_config_map_and_pointers,
+    ser_dynamic_param,
     ser_generated_param,
     ser_optional_param,
     ser_optional_sub_config,
@@ -36,6 +37,7 @@ use crate::loading::{
     update_optional_values,
 };
 use crate::presentation::get_config_presentation;
+use crate::reloading::{get_param_changes, ParamChanges};
 use crate::{
     ConfigError,
     ParamPath,
@@ -265,6 +267,7 @@ fn test_pointers_flow() {
             description: "This is a.".to_owned(),
             content: SerializedContent::PointerTarget("common_a".to_owned()),
             privacy: ParamPrivacy::Public,
+            is_dynamic: false,
         })
     );
     assert_eq!(
@@ -273,6 +276,7 @@ fn test_pointers_flow() {
             description: "This is a.".to_owned(),
             content: SerializedContent::PointerTarget("common_a".to_owned()),
             privacy: ParamPrivacy::Private,
+            is_dynamic: false,
         })
     );
     assert_eq!(
@@ -281,6 +285,7 @@ fn test_pointers_flow() {
             description: "This is common a".to_owned(),
             content: SerializedContent::DefaultValue(json!(10)),
             privacy: ParamPrivacy::TemporaryValue,
+            is_dynamic: false,
         })
     );
 
@@ -593,3 +598,94 @@ fn deeply_nested_optionals() {
         }
     );
 }
+
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq)]
+struct DynamicConfig {
+    dynamic_param: usize,
+    static_param: usize,
+    opt_dynamic_config: Option<DynamicInnerConfig>,
+}
+
+impl SerializeConfig for DynamicConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        chain!(
+            [
+                ser_dynamic_param(
+                    "dynamic_param",
+                    &self.dynamic_param,
+                    "This is dynamic.",
+                    ParamPrivacyInput::Public,
+                ),
+                ser_param(
+                    "static_param",
+                    &self.static_param,
+                    "This is static.",
+                    ParamPrivacyInput::Public,
+                ),
+            ],
+            ser_optional_sub_config(&self.opt_dynamic_config, "opt_dynamic_config"),
+        )
+        .collect()
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+struct DynamicInnerConfig {
+    o: usize,
+}
+
+impl SerializeConfig for DynamicInnerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from([ser_dynamic_param("o", &self.o, "This is o.", ParamPrivacyInput::Public)])
+    }
+}
+
+#[test]
+fn dynamic_params_are_marked_in_the_dump() {
+    let dumped = DynamicConfig::default().dump();
+    assert!(dumped["dynamic_param"].is_dynamic());
+    assert!(!dumped["static_param"].is_dynamic());
+
+    let stored = json!(dumped);
+    assert_eq!(stored["dynamic_param"]["is_dynamic"], json!(true));
+    assert!(stored["static_param"].get("is_dynamic").is_none());
+    let loaded: SerializedParam = serde_json::from_value(stored["static_param"].clone()).unwrap();
+    assert_eq!(loaded, dumped["static_param"]);
+}
+
+#[test]
+fn param_changes_are_split_by_dynamism() {
+    let current = DynamicConfig {
+        dynamic_param: 1,
+        static_param: 1,
+        opt_dynamic_config: Some(DynamicInnerConfig { o: 1 }),
+    };
+    assert!(get_param_changes(&current, &current.clone()).is_empty());
+
+    let reloaded = DynamicConfig {
+        dynamic_param: 2,
+        static_param: 2,
+        opt_dynamic_config: Some(DynamicInnerConfig { o: 2 }),
+    };
+    assert_eq!(
+        get_param_changes(&current, &reloaded),
+        ParamChanges {
+            dynamic: vec!["dynamic_param".to_owned(), "opt_dynamic_config.o".to_owned()],
+            requiring_restart: vec!["static_param".to_owned()],
+        }
+    );
+
+    // Setting an optional field requires a restart, even if its params are dynamic.
+    let current = DynamicConfig { opt_dynamic_config: None, ..current };
+    assert_eq!(
+        get_param_changes(&current, &reloaded),
+        ParamChanges {
+            dynamic: vec!["dynamic_param".to_owned()],
+            requiring_restart: vec![
+                "opt_dynamic_config.#is_none".to_owned(),
+                "opt_dynamic_config.o".to_owned(),
+                "static_param".to_owned(),
+            ],
+        }
+    );
+}
@@ -33,6 +33,16 @@
 //!     "value": true
 //! }
 //! ```
+//!
+//! Marks the params whose changes can be applied without restarting the node, see
+//! [`crate::reloading`]:
+//! ```json
+//! "conf1.conf2.conf3.param_name": {
+//!     "description": "Param description.",
+//!     "value": json_value,
+//!     "is_dynamic": true
+//! }
+//! ```
 
 use std::collections::BTreeMap;
 use std::fs::File;
@@ -131,7 +141,15 @@ fn common_ser_param(
     description: &str,
     privacy: ParamPrivacy,
 ) -> (String, SerializedParam) {
-    (name.to_owned(), SerializedParam { description: description.to_owned(), content, privacy })
+    (
+        name.to_owned(),
+        SerializedParam {
+            description: description.to_owned(),
+            content,
+            privacy,
+            is_dynamic: false,
+        },
+    )
 }
 
 /// Serializes a single param of a config.
@@ -150,6 +168,20 @@ pub fn ser_param<T: Serialize>(
     )
 }
 
+/// Serializes a single param of a config whose changes can be applied at runtime, without
+/// restarting the node. The components reading the param must pick up its changes.
+/// The returned pair is designed to be an input to a dumped config map.
+pub fn ser_dynamic_param<T: Serialize>(
+    name: &str,
+    value: &T,
+    description: &str,
+    privacy: ParamPrivacyInput,
+) -> (String, SerializedParam) {
+    let (name, mut param) = ser_param(name, value, description, privacy);
+    param.is_dynamic = true;
+    (name, param)
+}
+
 /// Serializes expected type for a single required param of a config.
 /// The returned pair is designed to be an input to a dumped config map.
 pub fn ser_required_param(
@@ -285,6 +317,7 @@ pub(crate) fn combine_config_map_and_pointers(
                     description: pointing_serialized_param.description.clone(),
                     content: SerializedContent::PointerTarget(target_param.to_owned()),
                     privacy: pointing_serialized_param.privacy.clone(),
+                    is_dynamic: pointing_serialized_param.is_dynamic,
                 },
             );
         }
@@ -68,6 +68,7 @@ pub mod converters;
 pub mod dumping;
 pub mod loading;
 pub mod presentation;
+pub mod reloading;
 pub mod validators;
 
 /// The privacy level of a config parameter, that received as input from the configs.
@@ -145,6 +146,20 @@ pub struct SerializedParam {
     #[serde(flatten)]
     pub content: SerializedContent,
     pub(crate) privacy: ParamPrivacy,
+    // Whether a change of the parameter can be applied at runtime, without restarting the node.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) is_dynamic: bool,
+}
+
+impl SerializedParam {
+    /// Returns whether a change of the parameter can be applied without restarting the node.
+    pub fn is_dynamic(&self) -> bool {
+        self.is_dynamic
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
 }
 
 /// A serialized type of a configuration parameter.
@@ -0,0 +1,65 @@
+//! Compares a reloaded configuration with the one in use, to tell the changes that can be applied
+//! at runtime from the changes that require a restart.
+//!
+//! Params whose changes can be applied at runtime are dumped by
+//! [`ser_dynamic_param`](crate::dumping::ser_dynamic_param). Applying the changes is up to the
+//! components reading these params, e.g., by reading them through a shared handle that is updated
+//! once the configuration is reloaded.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dumping::SerializeConfig;
+use crate::{ParamPath, IS_NONE_MARK};
+
+/// The params whose values differ between a configuration in use and a reloaded one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamChanges {
+    /// The changed dynamic params, which can be applied without restarting the node.
+    pub dynamic: Vec<ParamPath>,
+    /// The other changed params, which take effect only once the node is restarted.
+    pub requiring_restart: Vec<ParamPath>,
+}
+
+impl ParamChanges {
+    /// Returns whether no param changed.
+    pub fn is_empty(&self) -> bool {
+        self.dynamic.is_empty() && self.requiring_restart.is_empty()
+    }
+}
+
+/// Returns the params whose values differ between the dumps of the two configurations, sorted by
+/// their paths. The params of an optional field that is set in only one of the configurations
+/// require a restart, even if they are dynamic.
+pub fn get_param_changes<T: SerializeConfig>(current: &T, reloaded: &T) -> ParamChanges {
+    let current_dump = current.dump();
+    let reloaded_dump = reloaded.dump();
+    let changed_paths: Vec<&ParamPath> = current_dump
+        .keys()
+        .chain(reloaded_dump.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|param_path| {
+            current_dump.get(*param_path).map(|param| &param.content)
+                != reloaded_dump.get(*param_path).map(|param| &param.content)
+        })
+        .collect();
+    // The prefixes of the params of the optional fields that are set in only one configuration.
+    let toggled_prefixes: Vec<&str> = changed_paths
+        .iter()
+        .filter_map(|param_path| param_path.strip_suffix(IS_NONE_MARK))
+        .collect();
+
+    let mut changes = ParamChanges::default();
+    for param_path in changed_paths {
+        let is_dynamic = current_dump.get(param_path).is_some_and(|param| param.is_dynamic)
+            && !toggled_prefixes.iter().any(|prefix| param_path.starts_with(prefix));
+        if is_dynamic {
+            changes.dynamic.push(param_path.clone());
+        } else {
+            changes.requiring_restart.push(param_path.clone());
+        }
+    }
+    changes
+}
@@ -11,6 +11,15 @@ pub struct Proposal {
     pub proposer: ContractAddress,
     pub transactions: Vec<Transaction>,
     pub block_hash: BlockHash,
+    pub environment_fingerprint: Option<EnvironmentFingerprint>,
+}
+
+/// The fingerprint of the execution environment a proposal was generated in, computed for the
+/// random seed of the proposal.
+#[derive(Debug, Default, Hash, Clone, Copy, Eq, PartialEq)]
+pub struct EnvironmentFingerprint {
+    pub seed: u64,
+    pub digest: [u8; 32],
 }
 
 #[derive(Debug, Default, Hash, Clone, Eq, PartialEq)]
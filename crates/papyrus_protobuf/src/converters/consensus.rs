@@ -8,7 +8,14 @@ use starknet_api::block::BlockHash;
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::Transaction;
 
-use crate::consensus::{ConsensusMessage, Proposal, StreamMessage, Vote, VoteType};
+use crate::consensus::{
+    ConsensusMessage,
+    EnvironmentFingerprint,
+    Proposal,
+    StreamMessage,
+    Vote,
+    VoteType,
+};
 use crate::converters::ProtobufConversionError;
 use crate::{auto_impl_into_and_try_from_vec_u8, protobuf};
 
@@ -33,8 +40,10 @@ impl TryFrom<protobuf::Proposal> for Proposal {
             .ok_or(ProtobufConversionError::MissingField { field_description: "block_hash" })?
             .try_into()?;
         let block_hash = BlockHash(block_hash);
+        let environment_fingerprint =
+            value.environment_fingerprint.map(TryInto::try_into).transpose()?;
 
-        Ok(Proposal { height, round, proposer, transactions, block_hash })
+        Ok(Proposal { height, round, proposer, transactions, block_hash, environment_fingerprint })
     }
 }
 
@@ -48,10 +57,32 @@ impl From<Proposal> for protobuf::Proposal {
             proposer: Some(value.proposer.into()),
             transactions,
             block_hash: Some(value.block_hash.0.into()),
+            environment_fingerprint: value.environment_fingerprint.map(Into::into),
         }
     }
 }
 
+impl TryFrom<protobuf::EnvironmentFingerprint> for EnvironmentFingerprint {
+    type Error = ProtobufConversionError;
+
+    fn try_from(value: protobuf::EnvironmentFingerprint) -> Result<Self, Self::Error> {
+        let digest = value.digest.try_into().map_err(|digest| {
+            ProtobufConversionError::BytesDataLengthMismatch {
+                type_description: "EnvironmentFingerprint",
+                num_expected: 32,
+                value: digest,
+            }
+        })?;
+        Ok(EnvironmentFingerprint { seed: value.seed, digest })
+    }
+}
+
+impl From<EnvironmentFingerprint> for protobuf::EnvironmentFingerprint {
+    fn from(value: EnvironmentFingerprint) -> Self {
+        protobuf::EnvironmentFingerprint { seed: value.seed, digest: value.digest.to_vec() }
+    }
+}
+
 impl TryFrom<protobuf::vote::VoteType> for VoteType {
     type Error = ProtobufConversionError;
 
@@ -1,7 +1,7 @@
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use rand::Rng;
 
-use crate::consensus::{ConsensusMessage, Proposal, StreamMessage};
+use crate::consensus::{ConsensusMessage, EnvironmentFingerprint, Proposal, StreamMessage};
 
 // TODO(guyn): add tests for other serializable objects in consensus
 
@@ -29,3 +29,14 @@ fn convert_stream_message_to_vec_u8_and_back() {
     let res_data = StreamMessage::try_from(bytes_data).unwrap();
     assert_eq!(stream_message, res_data);
 }
+
+#[test]
+fn convert_proposal_with_environment_fingerprint_to_vec_u8_and_back() {
+    let proposal = ConsensusMessage::Proposal(Proposal {
+        environment_fingerprint: Some(EnvironmentFingerprint { seed: 7, digest: [1; 32] }),
+        ..Default::default()
+    });
+
+    let bytes_data: Vec<u8> = proposal.clone().into();
+    assert_eq!(ConsensusMessage::try_from(bytes_data).unwrap(), proposal);
+}
@@ -89,6 +89,7 @@ pub mod db;
 pub mod header;
 pub mod mmap_file;
 mod serialization;
+pub mod skipped_height;
 pub mod state;
 mod version;
 
@@ -649,6 +650,7 @@ pub struct DbStats {
 // - CompiledClass <= Class <= State <= Header
 // - Body <= Header
 // - BaseLayerBlock <= Header
+// - SkippedHeight <= Header
 // Event is currently unsupported.
 pub(crate) enum MarkerKind {
     Header,
@@ -658,6 +660,7 @@ pub(crate) enum MarkerKind {
     Class,
     CompiledClass,
     BaseLayerBlock,
+    SkippedHeight,
 }
 
 pub(crate) type MarkersTable<'env> =
@@ -320,6 +320,7 @@ auto_storage_serde! {
         Class = 4,
         CompiledClass = 5,
         BaseLayerBlock = 6,
+        SkippedHeight = 7,
     }
     pub struct MessageToL1 {
         pub to_address: EthAddress,
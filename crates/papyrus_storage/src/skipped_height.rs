@@ -0,0 +1,88 @@
+//! Interface for handling the heights consensus decided on no block for.
+//!
+//! A skipped height has no header and an empty state diff, so the state after it is the state
+//! after the previous block, and the block of the next height follows it in the storage.
+//!
+//! Import [`SkippedHeightStorageReader`] and [`SkippedHeightStorageWriter`] to read and write the
+//! skipped heights using a [`StorageTxn`].
+//! # Example
+//! ```
+//! use papyrus_storage::header::HeaderStorageReader;
+//! use papyrus_storage::open_storage;
+//! use papyrus_storage::skipped_height::{SkippedHeightStorageReader, SkippedHeightStorageWriter};
+//! # use papyrus_storage::{db::DbConfig, StorageConfig};
+//! # use starknet_api::core::ChainId;
+//! use starknet_api::block::BlockNumber;
+//!
+//! # let dir_handle = tempfile::tempdir().unwrap();
+//! # let dir = dir_handle.path().to_path_buf();
+//! # let db_config = DbConfig {
+//! #     path_prefix: dir,
+//! #     chain_id: ChainId::Mainnet,
+//! #     enforce_file_exists: false,
+//! #     min_size: 1 << 20,    // 1MB
+//! #     max_size: 1 << 35,    // 32GB
+//! #     growth_step: 1 << 26, // 64MB
+//! # };
+//! # let storage_config = StorageConfig{db_config, ..Default::default()};
+//! let (reader, mut writer) = open_storage(storage_config)?;
+//! writer
+//!     .begin_rw_txn()?                            // Start a RW transaction.
+//!     .append_skipped_height(BlockNumber(0))?     // Skip the first height.
+//!     .commit()?; // Commit the transaction.
+//! let txn = reader.begin_ro_txn()?;
+//! assert_eq!(txn.get_skipped_height_marker()?, BlockNumber(1));
+//! assert_eq!(txn.get_header_marker()?, BlockNumber(1));
+//! assert_eq!(txn.get_block_header(BlockNumber(0))?, None);
+//! # Ok::<(), papyrus_storage::StorageError>(())
+//! ```
+#[cfg(test)]
+#[path = "skipped_height_test.rs"]
+mod skipped_height_test;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::state::ThinStateDiff;
+
+use crate::db::table_types::Table;
+use crate::db::{TransactionKind, RW};
+use crate::state::StateStorageWriter;
+use crate::{MarkerKind, StorageError, StorageResult, StorageTxn};
+
+/// Interface for reading the heights consensus decided on no block for.
+pub trait SkippedHeightStorageReader {
+    /// The skipped height marker is the height following the latest skipped height.
+    fn get_skipped_height_marker(&self) -> StorageResult<BlockNumber>;
+}
+
+/// Interface for writing the heights consensus decided on no block for.
+pub trait SkippedHeightStorageWriter
+where
+    Self: Sized,
+{
+    /// Skips the given height, which must be the next height of the storage: advances the header
+    /// marker past it without a header, and appends an empty state diff for it.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn append_skipped_height(self, height: BlockNumber) -> StorageResult<Self>;
+}
+
+impl<'env, Mode: TransactionKind> SkippedHeightStorageReader for StorageTxn<'env, Mode> {
+    fn get_skipped_height_marker(&self) -> StorageResult<BlockNumber> {
+        let markers_table = self.open_table(&self.tables.markers)?;
+        Ok(markers_table.get(&self.txn, &MarkerKind::SkippedHeight)?.unwrap_or_default())
+    }
+}
+
+impl<'env> SkippedHeightStorageWriter for StorageTxn<'env, RW> {
+    fn append_skipped_height(self, height: BlockNumber) -> StorageResult<Self> {
+        let markers_table = self.open_table(&self.tables.markers)?;
+        let header_marker = markers_table.get(&self.txn, &MarkerKind::Header)?.unwrap_or_default();
+        if header_marker != height {
+            return Err(StorageError::MarkerMismatch { expected: header_marker, found: height });
+        }
+
+        let next_height = height.unchecked_next();
+        markers_table.upsert(&self.txn, &MarkerKind::Header, &next_height)?;
+        markers_table.upsert(&self.txn, &MarkerKind::SkippedHeight, &next_height)?;
+        self.append_state_diff(height, ThinStateDiff::default())
+    }
+}
@@ -0,0 +1,49 @@
+use assert_matches::assert_matches;
+use starknet_api::block::{BlockHeader, BlockNumber};
+use starknet_api::state::ThinStateDiff;
+
+use crate::header::{HeaderStorageReader, HeaderStorageWriter};
+use crate::skipped_height::{SkippedHeightStorageReader, SkippedHeightStorageWriter};
+use crate::state::{StateStorageReader, StateStorageWriter};
+use crate::test_utils::get_test_storage;
+use crate::StorageError;
+
+#[test]
+fn append_skipped_height() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+
+    writer.begin_rw_txn().unwrap().append_skipped_height(BlockNumber(0)).unwrap().commit().unwrap();
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_skipped_height_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_block_header(BlockNumber(0)).unwrap(), None);
+    assert_eq!(txn.get_state_diff(BlockNumber(0)).unwrap(), Some(ThinStateDiff::default()));
+
+    // The block of the next height follows the skipped height.
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(
+            BlockNumber(1),
+            &BlockHeader { block_number: BlockNumber(1), ..Default::default() },
+        )
+        .unwrap()
+        .append_state_diff(BlockNumber(1), ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_skipped_height_marker().unwrap(), BlockNumber(1));
+}
+
+#[test]
+fn append_skipped_height_other_than_next_height_fails() {
+    let ((_reader, mut writer), _temp_dir) = get_test_storage();
+
+    assert_matches!(
+        writer.begin_rw_txn().unwrap().append_skipped_height(BlockNumber(1)),
+        Err(StorageError::MarkerMismatch { expected: BlockNumber(0), found: BlockNumber(1) })
+    );
+}
@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::reloading::ParamChanges;
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher_types::batcher_types::{
+    ForceAbortAndResyncInput,
+    ForceAbortAndResyncReturnValue,
+    ProposalState,
+};
+use starknet_batcher_types::communication::SharedBatcherClient;
+use starknet_gateway_types::communication::SharedGatewayClient;
+use starknet_mempool_infra::auth::constant_time_eq;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use starknet_mempool_types::communication::SharedMempoolClient;
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::config_reloader::ConfigReloader;
+
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+pub const PAUSE_GATEWAY: &str = "/admin/gateway/pause";
+pub const RESUME_GATEWAY: &str = "/admin/gateway/resume";
+pub const RELOAD_GATEWAY_CONFIG: &str = "/admin/gateway/reload_config";
+pub const FLUSH_MEMPOOL: &str = "/admin/mempool/flush";
+pub const PROPOSAL_STATE: &str = "/admin/batcher/proposal_state";
+pub const FORCE_ABORT_AND_RESYNC: &str = "/admin/batcher/force_abort_and_resync";
+pub const RELOAD_NODE_CONFIG: &str = "/admin/config/reload";
+
+/// The configuration of the endpoint through which operators control the components of the node
+/// at runtime.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct AdminEndpointConfig {
+    pub enable: bool,
+    pub ip: IpAddr,
+    pub port: u16,
+    pub admin_token: String,
+}
+
+impl SerializeConfig for AdminEndpointConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, operators can control the components of the node over HTTP.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "ip",
+                &self.ip.to_string(),
+                "The admin endpoint ip.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param("port", &self.port, "The admin endpoint port.", ParamPrivacyInput::Public),
+            ser_param(
+                "admin_token",
+                &self.admin_token,
+                "The token authenticating admin requests. Admin requests are disabled if empty.",
+                ParamPrivacyInput::Private,
+            ),
+        ])
+    }
+}
+
+// Only reachable from the host of the node by default.
+impl Default for AdminEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            ip: "127.0.0.1".parse().unwrap(),
+            port: 8083,
+            admin_token: String::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct AdminState {
+    admin_token: Arc<String>,
+    gateway_client: Option<SharedGatewayClient>,
+    mempool_client: Option<SharedMempoolClient>,
+    batcher_client: Option<SharedBatcherClient>,
+    config_reloader: Option<ConfigReloader>,
+}
+
+/// Serves the admin operations of the node on a port of its own, each forwarded as a request to
+/// the component it controls. Requests must carry the configured admin token in the
+/// `x-admin-token` header.
+pub struct AdminEndpoint {
+    config: AdminEndpointConfig,
+    state: AdminState,
+}
+
+impl AdminEndpoint {
+    pub fn new(
+        config: AdminEndpointConfig,
+        gateway_client: Option<SharedGatewayClient>,
+        mempool_client: Option<SharedMempoolClient>,
+        batcher_client: Option<SharedBatcherClient>,
+        config_reloader: Option<ConfigReloader>,
+    ) -> Self {
+        let state = AdminState {
+            admin_token: Arc::new(config.admin_token.clone()),
+            gateway_client,
+            mempool_client,
+            batcher_client,
+            config_reloader,
+        };
+        Self { config, state }
+    }
+
+    pub fn app(&self) -> Router {
+        Router::new()
+            .route(PAUSE_GATEWAY, post(pause_gateway))
+            .route(RESUME_GATEWAY, post(resume_gateway))
+            .route(RELOAD_GATEWAY_CONFIG, post(reload_gateway_config))
+            .route(FLUSH_MEMPOOL, post(flush_mempool))
+            .route(PROPOSAL_STATE, get(proposal_state))
+            .route(FORCE_ABORT_AND_RESYNC, post(force_abort_and_resync))
+            .route(RELOAD_NODE_CONFIG, post(reload_node_config))
+            .with_state(self.state.clone())
+    }
+}
+
+#[async_trait]
+impl ComponentServerStarter for AdminEndpoint {
+    async fn start(&mut self) {
+        let AdminEndpointConfig { ip, port, .. } = self.config;
+        let addr = SocketAddr::new(ip, port);
+        info!("Serving the admin operations at {}.", addr);
+        if let Err(e) = axum::Server::bind(&addr).serve(self.app().into_make_service()).await {
+            error!("Admin endpoint stopped: {}", e);
+        }
+    }
+}
+
+enum AdminError {
+    Disabled,
+    Unauthorized,
+    ComponentNotRun(&'static str),
+    ComponentFailure(String),
+}
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> Response {
+        match self {
+            AdminError::Disabled => (
+                StatusCode::FORBIDDEN,
+                "Admin requests are disabled, no admin token is configured.".to_owned(),
+            ),
+            AdminError::Unauthorized => {
+                (StatusCode::UNAUTHORIZED, "Unauthorized admin request.".to_owned())
+            }
+            AdminError::ComponentNotRun(component) => {
+                (StatusCode::NOT_FOUND, format!("The {component} is not run by this node."))
+            }
+            AdminError::ComponentFailure(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        }
+        .into_response()
+    }
+}
+
+type AdminResult<T> = Result<T, AdminError>;
+
+fn authenticate(state: &AdminState, headers: &HeaderMap) -> AdminResult<()> {
+    if state.admin_token.is_empty() {
+        return Err(AdminError::Disabled);
+    }
+    let is_authorized = headers
+        .get(ADMIN_TOKEN_HEADER)
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), state.admin_token.as_bytes()));
+    if !is_authorized {
+        warn!("Rejected an admin request with an invalid token.");
+        return Err(AdminError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn component_client<T: Clone>(client: &Option<T>, component: &'static str) -> AdminResult<T> {
+    client.clone().ok_or(AdminError::ComponentNotRun(component))
+}
+
+fn component_failure(error: impl std::fmt::Display) -> AdminError {
+    AdminError::ComponentFailure(error.to_string())
+}
+
+async fn pause_gateway(State(state): State<AdminState>, headers: HeaderMap) -> AdminResult<()> {
+    set_gateway_ingestion_paused(&state, &headers, true).await
+}
+
+async fn resume_gateway(State(state): State<AdminState>, headers: HeaderMap) -> AdminResult<()> {
+    set_gateway_ingestion_paused(&state, &headers, false).await
+}
+
+async fn set_gateway_ingestion_paused(
+    state: &AdminState,
+    headers: &HeaderMap,
+    paused: bool,
+) -> AdminResult<()> {
+    authenticate(state, headers)?;
+    let gateway_client = component_client(&state.gateway_client, "gateway")?;
+    gateway_client.set_ingestion_paused(paused).await.map_err(component_failure)?;
+    info!(target: "audit", paused, "Admin request: set the gateway ingestion paused.");
+    Ok(())
+}
+
+async fn reload_gateway_config(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> AdminResult<()> {
+    authenticate(&state, &headers)?;
+    let gateway_client = component_client(&state.gateway_client, "gateway")?;
+    gateway_client.reload_config().await.map_err(component_failure)?;
+    info!(target: "audit", "Admin request: reloaded the gateway configuration.");
+    Ok(())
+}
+
+async fn flush_mempool(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> AdminResult<Json<Vec<TransactionHash>>> {
+    authenticate(&state, &headers)?;
+    let mempool_client = component_client(&state.mempool_client, "mempool")?;
+    let dropped_tx_hashes = mempool_client.flush().await.map_err(component_failure)?;
+    info!(
+        target: "audit",
+        n_dropped_txs = dropped_tx_hashes.len(),
+        "Admin request: flushed the mempool."
+    );
+    Ok(Json(dropped_tx_hashes))
+}
+
+async fn proposal_state(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> AdminResult<Json<ProposalState>> {
+    authenticate(&state, &headers)?;
+    let batcher_client = component_client(&state.batcher_client, "batcher")?;
+    let proposal_state = batcher_client.get_proposal_state().await.map_err(component_failure)?;
+    Ok(Json(proposal_state))
+}
+
+async fn force_abort_and_resync(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> AdminResult<Json<ForceAbortAndResyncReturnValue>> {
+    authenticate(&state, &headers)?;
+    let batcher_client = component_client(&state.batcher_client, "batcher")?;
+    let return_value = batcher_client
+        .force_abort_and_resync(ForceAbortAndResyncInput)
+        .await
+        .map_err(component_failure)?;
+    info!(
+        target: "audit",
+        aborted_proposal_id = ?return_value.aborted_proposal_id,
+        "Admin request: force-aborted the active proposal and discarded the proposals of the \
+         height."
+    );
+    Ok(Json(return_value))
+}
+
+async fn reload_node_config(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> AdminResult<Json<ParamChanges>> {
+    authenticate(&state, &headers)?;
+    let config_reloader = component_client(&state.config_reloader, "config reloader")?;
+    let changes = config_reloader.reload().map_err(component_failure)?;
+    info!(
+        target: "audit",
+        n_dynamic_changes = changes.dynamic.len(),
+        n_changes_requiring_restart = changes.requiring_restart.len(),
+        "Admin request: reloaded the node configuration."
+    );
+    Ok(Json(changes))
+}
@@ -1,10 +1,10 @@
 use papyrus_config::dumping::SerializeConfig;
-use starknet_mempool_node::config::{MempoolNodeConfig, DEFAULT_CONFIG_PATH};
+use starknet_sequencer_node::config::{SequencerNodeConfig, DEFAULT_CONFIG_PATH};
 
 /// Updates the default config file by:
-/// cargo run --bin mempool_dump_config -q
+/// cargo run --bin sequencer_dump_config -q
 fn main() {
-    MempoolNodeConfig::default()
+    SequencerNodeConfig::default()
         .dump_to_file(&vec![], DEFAULT_CONFIG_PATH)
         .expect("dump to file error");
 }
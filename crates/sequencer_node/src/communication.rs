@@ -0,0 +1,462 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use starknet_api::executable_transaction::Transaction;
+use starknet_batcher_types::communication::{
+    BatcherRequestAndResponseSender,
+    LocalBatcherClientImpl,
+    RemoteBatcherClientImpl,
+    SharedBatcherClient,
+};
+use starknet_class_manager_types::communication::{
+    ClassManagerRequestAndResponseSender,
+    LocalClassManagerClientImpl,
+    RemoteClassManagerClientImpl,
+    SharedClassManagerClient,
+};
+use starknet_consensus_manager_types::communication::{
+    ConsensusManagerRequestAndResponseSender,
+    LocalConsensusManagerClientImpl,
+    RemoteConsensusManagerClientImpl,
+    SharedConsensusManagerClient,
+};
+use starknet_gateway_types::communication::{
+    GatewayRequestAndResponseSender,
+    LocalGatewayClientImpl,
+    RemoteGatewayClientImpl,
+    SharedGatewayClient,
+};
+use starknet_l1_gas_price_types::communication::{
+    L1GasPriceProviderRequestAndResponseSender,
+    LocalL1GasPriceProviderClientImpl,
+    RemoteL1GasPriceProviderClientImpl,
+    SharedL1GasPriceProviderClient,
+};
+use starknet_l1_provider_types::communication::{
+    L1ProviderRequestAndResponseSender,
+    LocalL1ProviderClientImpl,
+    RemoteL1ProviderClientImpl,
+    SharedL1ProviderClient,
+};
+use starknet_mempool_infra::channel_metrics::monitored_channel;
+use starknet_mempool_infra::component_client::SharedComponentHealthClient;
+use starknet_mempool_infra::component_definitions::{
+    ComponentCommunication,
+    LocalComponentCommunicationConfig,
+};
+use starknet_mempool_p2p::sender::{
+    LocalMempoolP2pSenderClientImpl,
+    MempoolP2pSenderRequestAndResponseSender,
+    SharedMempoolP2pSenderClient,
+};
+use starknet_mempool_types::communication::{
+    LocalMempoolClientImpl,
+    MempoolRequestAndResponseSender,
+    RemoteMempoolClientImpl,
+    SharedMempoolClient,
+};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::config::{ComponentExecutionConfig, LocationType, SequencerNodeConfig};
+
+const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 32;
+
+pub struct SequencerNodeCommunication {
+    batcher_channel: ComponentCommunication<BatcherRequestAndResponseSender>,
+    class_manager_channel: ComponentCommunication<ClassManagerRequestAndResponseSender>,
+    consensus_manager_channel: ComponentCommunication<ConsensusManagerRequestAndResponseSender>,
+    gateway_channel: ComponentCommunication<GatewayRequestAndResponseSender>,
+    l1_provider_channel: ComponentCommunication<L1ProviderRequestAndResponseSender>,
+    l1_gas_price_provider_channel:
+        ComponentCommunication<L1GasPriceProviderRequestAndResponseSender>,
+    mempool_channel: ComponentCommunication<MempoolRequestAndResponseSender>,
+    mempool_p2p_sender_channel: ComponentCommunication<MempoolP2pSenderRequestAndResponseSender>,
+    mempool_tx_stream_channel: ComponentCommunication<Transaction>,
+}
+
+impl SequencerNodeCommunication {
+    pub fn take_batcher_tx(&mut self) -> Sender<BatcherRequestAndResponseSender> {
+        self.batcher_channel.take_tx()
+    }
+
+    pub fn take_batcher_rx(&mut self) -> Receiver<BatcherRequestAndResponseSender> {
+        self.batcher_channel.take_rx()
+    }
+
+    pub fn take_class_manager_tx(&mut self) -> Sender<ClassManagerRequestAndResponseSender> {
+        self.class_manager_channel.take_tx()
+    }
+
+    pub fn take_class_manager_rx(&mut self) -> Receiver<ClassManagerRequestAndResponseSender> {
+        self.class_manager_channel.take_rx()
+    }
+
+    pub fn take_consensus_manager_tx(
+        &mut self,
+    ) -> Sender<ConsensusManagerRequestAndResponseSender> {
+        self.consensus_manager_channel.take_tx()
+    }
+
+    pub fn take_consensus_manager_rx(
+        &mut self,
+    ) -> Receiver<ConsensusManagerRequestAndResponseSender> {
+        self.consensus_manager_channel.take_rx()
+    }
+
+    pub fn take_gateway_tx(&mut self) -> Sender<GatewayRequestAndResponseSender> {
+        self.gateway_channel.take_tx()
+    }
+
+    pub fn take_gateway_rx(&mut self) -> Receiver<GatewayRequestAndResponseSender> {
+        self.gateway_channel.take_rx()
+    }
+
+    pub fn take_l1_provider_tx(&mut self) -> Sender<L1ProviderRequestAndResponseSender> {
+        self.l1_provider_channel.take_tx()
+    }
+
+    pub fn take_l1_provider_rx(&mut self) -> Receiver<L1ProviderRequestAndResponseSender> {
+        self.l1_provider_channel.take_rx()
+    }
+
+    pub fn take_l1_gas_price_provider_tx(
+        &mut self,
+    ) -> Sender<L1GasPriceProviderRequestAndResponseSender> {
+        self.l1_gas_price_provider_channel.take_tx()
+    }
+
+    pub fn take_l1_gas_price_provider_rx(
+        &mut self,
+    ) -> Receiver<L1GasPriceProviderRequestAndResponseSender> {
+        self.l1_gas_price_provider_channel.take_rx()
+    }
+
+    pub fn take_mempool_tx(&mut self) -> Sender<MempoolRequestAndResponseSender> {
+        self.mempool_channel.take_tx()
+    }
+
+    pub fn take_mempool_rx(&mut self) -> Receiver<MempoolRequestAndResponseSender> {
+        self.mempool_channel.take_rx()
+    }
+
+    pub fn take_mempool_p2p_sender_tx(
+        &mut self,
+    ) -> Sender<MempoolP2pSenderRequestAndResponseSender> {
+        self.mempool_p2p_sender_channel.take_tx()
+    }
+
+    pub fn take_mempool_p2p_sender_rx(
+        &mut self,
+    ) -> Receiver<MempoolP2pSenderRequestAndResponseSender> {
+        self.mempool_p2p_sender_channel.take_rx()
+    }
+
+    pub fn take_mempool_tx_stream_tx(&mut self) -> Sender<Transaction> {
+        self.mempool_tx_stream_channel.take_tx()
+    }
+
+    pub fn take_mempool_tx_stream_rx(&mut self) -> Receiver<Transaction> {
+        self.mempool_tx_stream_channel.take_rx()
+    }
+}
+
+/// Creates the channels of the components, each buffering as many requests as configured for the
+/// local execution of its component.
+pub fn create_node_channels(config: &SequencerNodeConfig) -> SequencerNodeCommunication {
+    let components = &config.components;
+    let (tx_mempool, rx_mempool) = monitored_channel::<MempoolRequestAndResponseSender>(
+        "mempool_requests",
+        channel_buffer_size(&components.mempool),
+    );
+
+    let (tx_batcher, rx_batcher) = monitored_channel::<BatcherRequestAndResponseSender>(
+        "batcher_requests",
+        channel_buffer_size(&components.batcher),
+    );
+
+    let (tx_class_manager, rx_class_manager) =
+        monitored_channel::<ClassManagerRequestAndResponseSender>(
+            "class_manager_requests",
+            channel_buffer_size(&components.class_manager),
+        );
+
+    let (tx_consensus_manager, rx_consensus_manager) =
+        monitored_channel::<ConsensusManagerRequestAndResponseSender>(
+            "consensus_manager_requests",
+            channel_buffer_size(&components.consensus_manager),
+        );
+
+    let (tx_gateway, rx_gateway) = monitored_channel::<GatewayRequestAndResponseSender>(
+        "gateway_requests",
+        channel_buffer_size(&components.gateway),
+    );
+
+    let (tx_l1_provider, rx_l1_provider) = monitored_channel::<L1ProviderRequestAndResponseSender>(
+        "l1_provider_requests",
+        channel_buffer_size(&components.l1_provider),
+    );
+
+    let (tx_l1_gas_price_provider, rx_l1_gas_price_provider) =
+        monitored_channel::<L1GasPriceProviderRequestAndResponseSender>(
+            "l1_gas_price_provider_requests",
+            channel_buffer_size(&components.l1_gas_price_provider),
+        );
+
+    let (tx_mempool_p2p_sender, rx_mempool_p2p_sender) =
+        monitored_channel::<MempoolP2pSenderRequestAndResponseSender>(
+            "mempool_p2p_sender_requests",
+            channel_buffer_size(&components.mempool_p2p),
+        );
+
+    const DEFAULT_MEMPOOL_TX_STREAM_BUFFER_SIZE: usize = 1000;
+    let (tx_mempool_tx_stream, rx_mempool_tx_stream) = monitored_channel::<Transaction>(
+        "mempool_tx_stream",
+        DEFAULT_MEMPOOL_TX_STREAM_BUFFER_SIZE,
+    );
+
+    SequencerNodeCommunication {
+        mempool_channel: ComponentCommunication::new(Some(tx_mempool), Some(rx_mempool)),
+        consensus_manager_channel: ComponentCommunication::new(
+            Some(tx_consensus_manager),
+            Some(rx_consensus_manager),
+        ),
+        batcher_channel: ComponentCommunication::new(Some(tx_batcher), Some(rx_batcher)),
+        class_manager_channel: ComponentCommunication::new(
+            Some(tx_class_manager),
+            Some(rx_class_manager),
+        ),
+        gateway_channel: ComponentCommunication::new(Some(tx_gateway), Some(rx_gateway)),
+        l1_provider_channel: ComponentCommunication::new(
+            Some(tx_l1_provider),
+            Some(rx_l1_provider),
+        ),
+        l1_gas_price_provider_channel: ComponentCommunication::new(
+            Some(tx_l1_gas_price_provider),
+            Some(rx_l1_gas_price_provider),
+        ),
+        mempool_p2p_sender_channel: ComponentCommunication::new(
+            Some(tx_mempool_p2p_sender),
+            Some(rx_mempool_p2p_sender),
+        ),
+        mempool_tx_stream_channel: ComponentCommunication::new(
+            Some(tx_mempool_tx_stream),
+            Some(rx_mempool_tx_stream),
+        ),
+    }
+}
+
+// The channels of components located remotely aren't used, and keep the default size.
+fn channel_buffer_size(component_config: &ComponentExecutionConfig) -> usize {
+    component_config
+        .local_config
+        .as_ref()
+        .map_or(DEFAULT_CHANNEL_BUFFER_SIZE, |local_config| local_config.channel_buffer_size)
+}
+
+fn request_timeout(component_config: &ComponentExecutionConfig) -> Duration {
+    component_config.local_config.as_ref().map_or_else(
+        || LocalComponentCommunicationConfig::default().request_timeout,
+        |local_config| local_config.request_timeout,
+    )
+}
+
+pub struct SequencerNodeClients {
+    batcher_client: Option<SharedBatcherClient>,
+    class_manager_client: Option<SharedClassManagerClient>,
+    consensus_manager_client: Option<SharedConsensusManagerClient>,
+    gateway_client: Option<SharedGatewayClient>,
+    l1_provider_client: Option<SharedL1ProviderClient>,
+    l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient>,
+    mempool_client: Option<SharedMempoolClient>,
+    mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient>,
+    // TODO (Lev): Change to Option<Box<dyn MemPoolClient>>.
+    // The clients of the executed components answering health probes, by component name.
+    health_clients: Vec<(&'static str, SharedComponentHealthClient)>,
+}
+
+impl SequencerNodeClients {
+    pub fn get_batcher_client(&self) -> Option<SharedBatcherClient> {
+        self.batcher_client.clone()
+    }
+
+    pub fn get_class_manager_client(&self) -> Option<SharedClassManagerClient> {
+        self.class_manager_client.clone()
+    }
+
+    pub fn get_consensus_manager_client(&self) -> Option<SharedConsensusManagerClient> {
+        self.consensus_manager_client.clone()
+    }
+
+    pub fn get_gateway_client(&self) -> Option<SharedGatewayClient> {
+        self.gateway_client.clone()
+    }
+
+    pub fn get_l1_provider_client(&self) -> Option<SharedL1ProviderClient> {
+        self.l1_provider_client.clone()
+    }
+
+    pub fn get_l1_gas_price_provider_client(&self) -> Option<SharedL1GasPriceProviderClient> {
+        self.l1_gas_price_provider_client.clone()
+    }
+
+    pub fn get_mempool_client(&self) -> Option<SharedMempoolClient> {
+        self.mempool_client.clone()
+    }
+
+    pub fn get_mempool_p2p_sender_client(&self) -> Option<SharedMempoolP2pSenderClient> {
+        self.mempool_p2p_sender_client.clone()
+    }
+
+    pub fn get_health_clients(&self) -> Vec<(&'static str, SharedComponentHealthClient)> {
+        self.health_clients.clone()
+    }
+}
+
+/// Creates the clients of the components: through the local channel of a component executed
+/// locally, or at the address of the server of a component located remotely.
+pub fn create_node_clients(
+    config: &SequencerNodeConfig,
+    channels: &mut SequencerNodeCommunication,
+) -> SequencerNodeClients {
+    let components = &config.components;
+
+    let local_batcher_client = components.batcher.is_executed_locally().then(|| {
+        Arc::new(
+            LocalBatcherClientImpl::new(channels.take_batcher_tx())
+                .with_timeout(request_timeout(&components.batcher)),
+        )
+    });
+    let batcher_client: Option<SharedBatcherClient> =
+        match components.batcher.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteBatcherClientImpl::from_config(remote))),
+            None => local_batcher_client.clone().map(|client| client as SharedBatcherClient),
+        };
+
+    let local_class_manager_client = components.class_manager.is_executed_locally().then(|| {
+        Arc::new(
+            LocalClassManagerClientImpl::new(channels.take_class_manager_tx())
+                .with_timeout(request_timeout(&components.class_manager)),
+        )
+    });
+    let class_manager_client: Option<SharedClassManagerClient> = match components
+        .class_manager
+        .remote_server_config()
+    {
+        Some(remote) => Some(Arc::new(RemoteClassManagerClientImpl::from_config(remote))),
+        None => local_class_manager_client.clone().map(|client| client as SharedClassManagerClient),
+    };
+
+    let consensus_manager_client: Option<SharedConsensusManagerClient> =
+        match components.consensus_manager.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteConsensusManagerClientImpl::from_config(remote))),
+            None => components.consensus_manager.is_executed_locally().then(|| {
+                Arc::new(
+                    LocalConsensusManagerClientImpl::new(channels.take_consensus_manager_tx())
+                        .with_timeout(request_timeout(&components.consensus_manager)),
+                ) as SharedConsensusManagerClient
+            }),
+        };
+
+    // The mempool and the gateway are also called by the monitoring endpoint, when executed.
+    let local_mempool_client = (components.mempool.location == LocationType::Local
+        && (components.gateway.execute || components.mempool.execute))
+        .then(|| {
+            Arc::new(
+                LocalMempoolClientImpl::new(channels.take_mempool_tx())
+                    .with_timeout(request_timeout(&components.mempool)),
+            )
+        });
+    let mempool_client: Option<SharedMempoolClient> =
+        match components.mempool.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteMempoolClientImpl::from_config(remote))),
+            None => local_mempool_client.clone().map(|client| client as SharedMempoolClient),
+        };
+
+    // The gateway is called by the mempool p2p receiver, with the transactions of peers, by the
+    // mempool, with the statuses of transactions, and by the admin endpoint.
+    let local_gateway_client = (components.gateway.location == LocationType::Local
+        && (components.mempool_p2p.execute
+            || components.gateway.execute
+            || components.mempool.execute))
+        .then(|| {
+            Arc::new(
+                LocalGatewayClientImpl::new(channels.take_gateway_tx())
+                    .with_timeout(request_timeout(&components.gateway)),
+            )
+        });
+    let gateway_client: Option<SharedGatewayClient> =
+        match components.gateway.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteGatewayClientImpl::from_config(remote))),
+            None => local_gateway_client.clone().map(|client| client as SharedGatewayClient),
+        };
+
+    let local_l1_provider_client = components.l1_provider.is_executed_locally().then(|| {
+        Arc::new(
+            LocalL1ProviderClientImpl::new(channels.take_l1_provider_tx())
+                .with_timeout(request_timeout(&components.l1_provider)),
+        )
+    });
+    let l1_provider_client: Option<SharedL1ProviderClient> =
+        match components.l1_provider.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteL1ProviderClientImpl::from_config(remote))),
+            None => local_l1_provider_client.clone().map(|client| client as SharedL1ProviderClient),
+        };
+
+    let local_l1_gas_price_provider_client =
+        components.l1_gas_price_provider.is_executed_locally().then(|| {
+            Arc::new(
+                LocalL1GasPriceProviderClientImpl::new(channels.take_l1_gas_price_provider_tx())
+                    .with_timeout(request_timeout(&components.l1_gas_price_provider)),
+            )
+        });
+    let l1_gas_price_provider_client: Option<SharedL1GasPriceProviderClient> =
+        match components.l1_gas_price_provider.remote_server_config() {
+            Some(remote) => Some(Arc::new(RemoteL1GasPriceProviderClientImpl::from_config(remote))),
+            None => local_l1_gas_price_provider_client
+                .clone()
+                .map(|client| client as SharedL1GasPriceProviderClient),
+        };
+
+    // The mempool p2p sender has no remote server, so it's called by the gateway of its node only.
+    let mempool_p2p_sender_client: Option<SharedMempoolP2pSenderClient> =
+        match components.mempool_p2p.execute {
+            true => Some(Arc::new(
+                LocalMempoolP2pSenderClientImpl::new(channels.take_mempool_p2p_sender_tx())
+                    .with_timeout(request_timeout(&components.mempool_p2p)),
+            )),
+            false => None,
+        };
+
+    // Only the components executed locally answer health probes through their channels.
+    let mut health_clients: Vec<(&'static str, SharedComponentHealthClient)> = Vec::new();
+    if let Some(client) = local_batcher_client {
+        health_clients.push(("batcher", client));
+    }
+    if let Some(client) = local_class_manager_client {
+        health_clients.push(("class_manager", client));
+    }
+    if let (true, Some(client)) = (components.gateway.execute, local_gateway_client) {
+        health_clients.push(("gateway", client));
+    }
+    if let Some(client) = local_l1_provider_client {
+        health_clients.push(("l1_provider", client));
+    }
+    if let Some(client) = local_l1_gas_price_provider_client {
+        health_clients.push(("l1_gas_price_provider", client));
+    }
+    if let (true, Some(client)) = (components.mempool.execute, local_mempool_client) {
+        health_clients.push(("mempool", client));
+    }
+
+    SequencerNodeClients {
+        batcher_client,
+        class_manager_client,
+        consensus_manager_client,
+        gateway_client,
+        l1_provider_client,
+        l1_gas_price_provider_client,
+        mempool_client,
+        mempool_p2p_sender_client,
+        health_clients,
+    }
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerContract;
+use starknet_batcher::batcher::{create_batcher, open_batcher_storage, Batcher};
+use starknet_class_manager::class_manager::ClassManager;
+use starknet_consensus_manager::consensus_manager::ConsensusManager;
+use starknet_gateway::gateway::{create_gateway, Gateway};
+use starknet_l1_gas_price::l1_gas_price_provider::L1GasPriceProvider;
+use starknet_l1_gas_price::l1_gas_price_scraper::L1GasPriceScraper;
+use starknet_l1_provider::l1_provider::L1Provider;
+use starknet_mempool::class_availability::{
+    ClassAvailabilityChecker,
+    ClassManagerAvailabilityChecker,
+};
+use starknet_mempool::sharded_mempool::ShardedMempool;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_infra::resource_monitor::SharedResourceStatus;
+use starknet_mempool_p2p::create_p2p_sender_and_receiver;
+use starknet_mempool_p2p::receiver::MempoolP2pReceiver;
+use starknet_mempool_p2p::sender::MempoolP2pSender;
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+
+use crate::admin_endpoint::AdminEndpoint;
+use crate::communication::{SequencerNodeClients, SequencerNodeCommunication};
+use crate::config::SequencerNodeConfig;
+use crate::config_reloader::{ConfigReloader, DynamicConfigs};
+use crate::monitoring_endpoint::MonitoringEndpoint;
+use crate::resource_guard::ResourceGuard;
+
+pub struct Components {
+    pub batcher: Option<Batcher>,
+    pub class_manager: Option<ClassManager>,
+    pub consensus_manager: Option<ConsensusManager>,
+    pub gateway: Option<Gateway>,
+    pub l1_provider: Option<L1Provider>,
+    pub l1_gas_price_provider: Option<L1GasPriceProvider>,
+    pub l1_gas_price_scraper: Option<L1GasPriceScraper<EthereumBaseLayerContract>>,
+    pub mempool: Option<ShardedMempool>,
+    pub mempool_p2p_sender: Option<MempoolP2pSender>,
+    pub mempool_p2p_receiver: Option<MempoolP2pReceiver>,
+    pub resource_guard: Option<ResourceGuard>,
+    pub monitoring_endpoint: Option<MonitoringEndpoint>,
+    pub admin_endpoint: Option<AdminEndpoint>,
+    pub config_reloader: Option<ConfigReloader>,
+}
+
+pub fn create_components(
+    config: &SequencerNodeConfig,
+    clients: &SequencerNodeClients,
+    communication: &mut SequencerNodeCommunication,
+) -> Components {
+    let resource_status = SharedResourceStatus::default();
+    // Set by the fee market of the batcher, and checked by the gateway.
+    let l2_gas_price = SharedL2GasPrice::default();
+    // Updated by the config reloader, and read by the block builder of the batcher.
+    let block_builder_config =
+        SharedDynamicConfig::new(config.batcher_config.block_builder.clone());
+
+    // The gateway validates transactions against the storage of the batcher, if it runs in this
+    // node.
+    let batcher_storage =
+        config.components.batcher.execute.then(|| open_batcher_storage(&config.batcher_config));
+    let batcher_storage_reader =
+        batcher_storage.as_ref().map(|(storage_reader, _)| storage_reader.clone());
+
+    let batcher = if let Some(batcher_storage) = batcher_storage {
+        let mempool_client =
+            clients.get_mempool_client().expect("Mempool Client should be available");
+        Some(create_batcher(
+            config.batcher_config.clone(),
+            batcher_storage,
+            mempool_client,
+            clients.get_l1_provider_client(),
+            clients.get_l1_gas_price_provider_client(),
+            clients.get_class_manager_client(),
+            // Transactions are streamed by a mempool served through its local channel only.
+            config
+                .components
+                .mempool
+                .is_executed_locally()
+                .then(|| communication.take_mempool_tx_stream_rx()),
+            l2_gas_price.clone(),
+            block_builder_config.clone(),
+        ))
+    } else {
+        None
+    };
+
+    let class_manager = if config.components.class_manager.execute {
+        Some(ClassManager::new(config.class_manager_config.clone()))
+    } else {
+        None
+    };
+
+    let consensus_manager = if config.components.consensus_manager.execute {
+        let batcher_client =
+            clients.get_batcher_client().expect("Batcher Client should be available");
+        Some(ConsensusManager::new(config.consensus_manager_config.clone(), batcher_client))
+    } else {
+        None
+    };
+
+    let gateway = if config.components.gateway.execute {
+        let mempool_client =
+            clients.get_mempool_client().expect("Mempool Client should be available");
+
+        Some(create_gateway(
+            config.gateway_config.clone(),
+            config.rpc_state_reader_config.clone(),
+            config.compiler_config.clone(),
+            mempool_client,
+            clients.get_mempool_p2p_sender_client(),
+            resource_status.clone(),
+            l2_gas_price,
+            clients.get_class_manager_client(),
+            clients.get_batcher_client(),
+            batcher_storage_reader,
+        ))
+    } else {
+        None
+    };
+
+    let l1_provider =
+        if config.components.l1_provider.execute { Some(L1Provider::new()) } else { None };
+
+    let l1_gas_price_provider = if config.components.l1_gas_price_provider.execute {
+        Some(L1GasPriceProvider::new(config.l1_gas_price_provider_config.clone()))
+    } else {
+        None
+    };
+
+    let l1_gas_price_scraper =
+        match (&config.l1_gas_price_scraper_config, clients.get_l1_gas_price_provider_client()) {
+            (Some(scraper_config), Some(l1_gas_price_provider_client)) => {
+                let base_layer =
+                    EthereumBaseLayerContract::new(scraper_config.base_layer_config.clone())
+                        .expect("Failed to create the base layer contract.");
+                Some(L1GasPriceScraper::new(
+                    scraper_config.clone(),
+                    l1_gas_price_provider_client,
+                    base_layer,
+                ))
+            }
+            _ => None,
+        };
+
+    let mempool = if config.components.mempool.execute {
+        let class_availability_checker =
+            clients.get_class_manager_client().map(|class_manager_client| {
+                Arc::new(ClassManagerAvailabilityChecker::new(class_manager_client))
+                    as Arc<dyn ClassAvailabilityChecker>
+            });
+        Some(ShardedMempool::new(config.mempool_config.clone(), class_availability_checker, None))
+    } else {
+        None
+    };
+
+    let (mempool_p2p_sender, mempool_p2p_receiver) = if config.components.mempool_p2p.execute {
+        let gateway_client =
+            clients.get_gateway_client().expect("Gateway Client should be available");
+        let (mempool_p2p_sender, mempool_p2p_receiver) =
+            create_p2p_sender_and_receiver(config.mempool_p2p_config.clone(), gateway_client);
+        (Some(mempool_p2p_sender), Some(mempool_p2p_receiver))
+    } else {
+        (None, None)
+    };
+
+    let resource_guard = if config.resource_guard_config.enable {
+        Some(ResourceGuard::new(
+            config.resource_guard_config.clone(),
+            resource_status,
+            clients.get_mempool_client(),
+        ))
+    } else {
+        None
+    };
+
+    let monitoring_endpoint = if config.monitoring_endpoint_config.enable {
+        Some(
+            MonitoringEndpoint::new(
+                config.monitoring_endpoint_config.clone(),
+                clients.get_health_clients(),
+            )
+            .expect("Failed to install the metrics recorder."),
+        )
+    } else {
+        None
+    };
+
+    let config_reloader = config.config_reloader_config.enable.then(|| {
+        let dynamic_configs = DynamicConfigs {
+            rate_limit_config: gateway.as_ref().map(Gateway::rate_limit_config),
+            transaction_type_gate: gateway.as_ref().map(Gateway::transaction_type_gate),
+            mempool_eviction_config: mempool.as_ref().and_then(ShardedMempool::eviction_config),
+            block_builder_config: batcher.is_some().then_some(block_builder_config),
+        };
+        ConfigReloader::new(config.config_reloader_config.clone(), config.clone(), dynamic_configs)
+    });
+
+    let admin_endpoint = config.admin_endpoint_config.enable.then(|| {
+        AdminEndpoint::new(
+            config.admin_endpoint_config.clone(),
+            clients.get_gateway_client(),
+            clients.get_mempool_client(),
+            clients.get_batcher_client(),
+            config_reloader.clone(),
+        )
+    });
+
+    Components {
+        batcher,
+        class_manager,
+        consensus_manager,
+        gateway,
+        l1_provider,
+        l1_gas_price_provider,
+        l1_gas_price_scraper,
+        mempool,
+        mempool_p2p_sender,
+        mempool_p2p_receiver,
+        resource_guard,
+        monitoring_endpoint,
+        admin_endpoint,
+        config_reloader,
+    }
+}
@@ -8,6 +8,7 @@ use mempool_test_utils::get_absolute_path;
 use papyrus_config::dumping::SerializeConfig;
 use papyrus_config::validators::{ParsedValidationError, ParsedValidationErrors};
 use rstest::rstest;
+use starknet_api::core::ChainId;
 use starknet_mempool_infra::component_definitions::{
     LocalComponentCommunicationConfig,
     RemoteComponentCommunicationConfig,
@@ -17,8 +18,9 @@ use validator::{Validate, ValidationErrors};
 use crate::config::{
     ComponentConfig,
     ComponentExecutionConfig,
+    ComponentType,
     LocationType,
-    MempoolNodeConfig,
+    SequencerNodeConfig,
     DEFAULT_CONFIG_PATH,
 };
 
@@ -93,6 +95,36 @@ fn test_invalid_component_execution_config(
     );
 }
 
+#[rstest]
+#[case::independent(ComponentType::IndependentComponent)]
+#[case::asynchronous(ComponentType::AsynchronousComponent)]
+fn test_remotely_served_component_must_be_synchronous(#[case] component_type: ComponentType) {
+    let component_exe_config = ComponentExecutionConfig {
+        component_type,
+        location: LocationType::Remote,
+        local_config: None,
+        remote_config: Some(RemoteComponentCommunicationConfig::default()),
+        ..ComponentExecutionConfig::default()
+    };
+    check_validation_error(
+        component_exe_config.validate(),
+        "Invalid component configuration.",
+        "Only synchronous components can be served remotely.",
+    );
+}
+
+#[test]
+fn test_local_channel_must_buffer_requests() {
+    let component_exe_config = ComponentExecutionConfig {
+        local_config: Some(LocalComponentCommunicationConfig {
+            channel_buffer_size: 0,
+            ..LocalComponentCommunicationConfig::default()
+        }),
+        ..ComponentExecutionConfig::default()
+    };
+    assert!(component_exe_config.validate().is_err());
+}
+
 /// Test the validation of the struct ComponentExecutionConfig.
 /// The validation validates that location of the component and the local/remote config are at sync.
 #[rstest]
@@ -124,12 +156,28 @@ fn test_invalid_components_config() {
     // Initialize an invalid config and check that the validator finds an error.
     let component_config = ComponentConfig {
         batcher: ComponentExecutionConfig { execute: false, ..ComponentExecutionConfig::default() },
+        class_manager: ComponentExecutionConfig {
+            execute: false,
+            ..ComponentExecutionConfig::default()
+        },
         consensus_manager: ComponentExecutionConfig {
             execute: false,
             ..ComponentExecutionConfig::default()
         },
         gateway: ComponentExecutionConfig { execute: false, ..ComponentExecutionConfig::default() },
+        l1_provider: ComponentExecutionConfig {
+            execute: false,
+            ..ComponentExecutionConfig::default()
+        },
+        l1_gas_price_provider: ComponentExecutionConfig {
+            execute: false,
+            ..ComponentExecutionConfig::default()
+        },
         mempool: ComponentExecutionConfig { execute: false, ..ComponentExecutionConfig::default() },
+        mempool_p2p: ComponentExecutionConfig {
+            execute: false,
+            ..ComponentExecutionConfig::default()
+        },
     };
 
     check_validation_error(
@@ -142,15 +190,23 @@ fn test_invalid_components_config() {
 /// Test the validation of the struct ComponentConfig.
 /// The validation validates at least one of the components is set with execute: true.
 #[rstest]
-#[case(true, false, false, false)]
-#[case(false, true, false, false)]
-#[case(false, false, true, false)]
-#[case(false, false, false, true)]
+#[case(true, false, false, false, false, false, false, false)]
+#[case(false, true, false, false, false, false, false, false)]
+#[case(false, false, true, false, false, false, false, false)]
+#[case(false, false, false, true, false, false, false, false)]
+#[case(false, false, false, false, true, false, false, false)]
+#[case(false, false, false, false, false, true, false, false)]
+#[case(false, false, false, false, false, false, true, false)]
+#[case(false, false, false, false, false, false, false, true)]
 fn test_valid_components_config(
     #[case] batcher_component_execute: bool,
+    #[case] class_manager_component_execute: bool,
     #[case] consensus_manager_component_execute: bool,
     #[case] gateway_component_execute: bool,
+    #[case] l1_provider_component_execute: bool,
+    #[case] l1_gas_price_provider_component_execute: bool,
     #[case] mempool_component_execute: bool,
+    #[case] mempool_p2p_component_execute: bool,
 ) {
     // Initialize an invalid config and check that the validator finds an error.
     let component_config = ComponentConfig {
@@ -158,6 +214,10 @@ fn test_valid_components_config(
             execute: batcher_component_execute,
             ..ComponentExecutionConfig::default()
         },
+        class_manager: ComponentExecutionConfig {
+            execute: class_manager_component_execute,
+            ..ComponentExecutionConfig::default()
+        },
         consensus_manager: ComponentExecutionConfig {
             execute: consensus_manager_component_execute,
             ..ComponentExecutionConfig::default()
@@ -166,21 +226,91 @@ fn test_valid_components_config(
             execute: gateway_component_execute,
             ..ComponentExecutionConfig::default()
         },
+        l1_provider: ComponentExecutionConfig {
+            execute: l1_provider_component_execute,
+            ..ComponentExecutionConfig::default()
+        },
+        l1_gas_price_provider: ComponentExecutionConfig {
+            execute: l1_gas_price_provider_component_execute,
+            ..ComponentExecutionConfig::default()
+        },
         mempool: ComponentExecutionConfig {
             execute: mempool_component_execute,
             ..ComponentExecutionConfig::default()
         },
+        mempool_p2p: ComponentExecutionConfig {
+            execute: mempool_p2p_component_execute,
+            ..ComponentExecutionConfig::default()
+        },
     };
 
     assert_matches!(component_config.validate(), Ok(()));
 }
 
-/// Test the validation of the struct MempoolNodeConfig and that the default config file is up to
+#[test]
+fn test_mismatched_chain_ids_are_invalid() {
+    let mut config = SequencerNodeConfig::default();
+    config.gateway_config.stateful_tx_validator_config.chain_info.chain_id =
+        ChainId::Other("SN_OTHER".to_owned());
+
+    check_validation_error(
+        config.validate(),
+        "Invalid chain id configuration.",
+        "The chain id of the gateway, SN_OTHER, differs from the chain id of the batcher, 0x0.",
+    );
+}
+
+#[test]
+fn mismatched_fee_configs_are_invalid() {
+    let mut config = SequencerNodeConfig::default();
+    config.batcher_config.fee_config.max_fee = Some(1000);
+
+    check_validation_error(
+        config.validate(),
+        "Invalid fee configuration.",
+        "The fee policy of the gateway, FeeConfig { min_l1_gas_price: 1, min_l2_gas_price: 1, \
+         max_fee: None }, differs from the fee policy of the batcher, FeeConfig { \
+         min_l1_gas_price: 1, min_l2_gas_price: 1, max_fee: Some(1000) }.",
+    );
+}
+
+#[test]
+fn all_violated_constraints_are_reported() {
+    let mut config = SequencerNodeConfig::default();
+    config.gateway_config.stateful_tx_validator_config.chain_info.chain_id =
+        ChainId::Other("SN_OTHER".to_owned());
+    let proposals_manager_config = &mut config.batcher_config.proposals_manager;
+    proposals_manager_config.outstream_content_buffer_size =
+        proposals_manager_config.max_txs_per_mempool_request - 1;
+    proposals_manager_config.deadline_margin = config.batcher_config.max_time_to_deadline;
+
+    let parsed_errors = ParsedValidationErrors::from(config.validate().unwrap_err());
+
+    let mut error_codes: Vec<_> = parsed_errors
+        .0
+        .iter()
+        .map(|error| (error.param_path.as_str(), error.code.as_str()))
+        .collect();
+    error_codes.sort();
+    assert_eq!(
+        error_codes,
+        [
+            ("__all__", "Invalid chain id configuration."),
+            ("batcher_config.__all__", "Invalid batcher configuration."),
+            (
+                "batcher_config.proposals_manager.__all__",
+                "Invalid proposals manager configuration."
+            ),
+        ]
+    );
+}
+
+/// Test the validation of the struct SequencerNodeConfig and that the default config file is up to
 /// date. To update the default config file, run:
-/// cargo run --bin mempool_dump_config -q
+/// cargo run --bin sequencer_dump_config -q
 #[test]
 fn default_config_file_is_up_to_date() {
-    let default_config = MempoolNodeConfig::default();
+    let default_config = SequencerNodeConfig::default();
     assert_matches!(default_config.validate(), Ok(()));
     let from_code: serde_json::Value = serde_json::to_value(default_config.dump()).unwrap();
 
@@ -191,7 +321,7 @@ fn default_config_file_is_up_to_date() {
     println!(
         "{}",
         "Default config file doesn't match the default NodeConfig implementation. Please update \
-         it using the mempool_dump_config binary."
+         it using the sequencer_dump_config binary."
             .purple()
             .bold()
     );
@@ -0,0 +1,516 @@
+#[cfg(test)]
+mod config_test;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+
+use clap::Command;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
+use papyrus_config::loading::load_and_process_config;
+use papyrus_config::{ConfigError, ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_batcher::config::BatcherConfig;
+use starknet_class_manager::class_manager::ClassManagerConfig;
+use starknet_consensus_manager::config::ConsensusManagerConfig;
+use starknet_gateway::config::{GatewayConfig, RpcStateReaderConfig};
+use starknet_l1_gas_price::l1_gas_price_provider::L1GasPriceProviderConfig;
+use starknet_l1_gas_price::l1_gas_price_scraper::L1GasPriceScraperConfig;
+use starknet_mempool::config::MempoolConfig;
+use starknet_mempool_infra::component_definitions::{
+    LocalComponentCommunicationConfig,
+    RemoteComponentCommunicationConfig,
+};
+use starknet_mempool_infra::log_format::LoggingConfig;
+use starknet_mempool_infra::request_priority::RequestPriorities;
+use starknet_mempool_infra::resource_monitor::ResourceGuardConfig;
+use starknet_mempool_infra::trace_export::TraceExportConfig;
+use starknet_mempool_p2p::config::MempoolP2pConfig;
+use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use validator::{Validate, ValidationError};
+
+use crate::admin_endpoint::AdminEndpointConfig;
+use crate::config_reloader::ConfigReloaderConfig;
+use crate::monitoring_endpoint::MonitoringEndpointConfig;
+use crate::supervisor::SupervisorConfig;
+use crate::version::VERSION_FULL;
+
+// The path of the default configuration file, provided as part of the crate.
+pub const DEFAULT_CONFIG_PATH: &str = "config/sequencer/default_config.json";
+
+// The configuration of the components.
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum LocationType {
+    Local,
+    Remote,
+}
+// TODO(Lev/Tsabary): When papyrus_config will support it, change to include communication config in
+// the enum.
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ComponentType {
+    // A component that does not require an infra server, and has an internal mutating task.
+    IndependentComponent,
+    // A component that requires an infra server, and has no internal mutating task.
+    SynchronousComponent,
+    // A component that requires an infra server, and has an internal mutating task.
+    AsynchronousComponent,
+}
+// TODO(Lev/Tsabary): Change the enum values to more discriptive.
+
+/// The single component configuration, including its placement: a component located locally is
+/// called through its channel, of the configured size, and a component located remotely is called
+/// through a client of its server at the configured address.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_single_component_config", skip_on_field_errors = false))]
+pub struct ComponentExecutionConfig {
+    pub execute: bool,
+    pub component_type: ComponentType,
+    pub location: LocationType,
+    #[validate]
+    pub local_config: Option<LocalComponentCommunicationConfig>,
+    #[validate]
+    pub remote_config: Option<RemoteComponentCommunicationConfig>,
+}
+
+impl SerializeConfig for ComponentExecutionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let config = BTreeMap::from_iter([
+            ser_param(
+                "execute",
+                &self.execute,
+                "The component execution flag.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "location",
+                &self.location,
+                "The component location.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "component_type",
+                &self.component_type,
+                "The component type.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        vec![
+            config,
+            ser_optional_sub_config(&self.local_config, "local_config"),
+            ser_optional_sub_config(&self.remote_config, "remote_config"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Default for ComponentExecutionConfig {
+    fn default() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+}
+
+impl ComponentExecutionConfig {
+    /// Whether the component is executed by this node and served through its local channel.
+    pub fn is_executed_locally(&self) -> bool {
+        self.execute && self.location == LocationType::Local
+    }
+
+    /// The address of the server of the component when it is located remotely, either in this
+    /// node or in another one.
+    pub fn remote_server_config(&self) -> Option<&RemoteComponentCommunicationConfig> {
+        match self.location {
+            LocationType::Local => None,
+            LocationType::Remote => self.remote_config.as_ref(),
+        }
+    }
+}
+
+/// Specific components default configurations.
+impl ComponentExecutionConfig {
+    pub fn gateway_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::IndependentComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    // The requests of the batcher building the block overtake the transactions added through the
+    // gateway.
+    pub fn mempool_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig {
+                request_priorities: RequestPriorities::from([
+                    ("GetTransactions".to_string(), 1),
+                    ("GetTransactionsByBudget".to_string(), 1),
+                    ("GetTransactionsWithin".to_string(), 1),
+                    ("CommitBlock".to_string(), 1),
+                ]),
+                ..LocalComponentCommunicationConfig::default()
+            }),
+            remote_config: None,
+        }
+    }
+
+    pub fn l1_provider_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    pub fn l1_gas_price_provider_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    // The class manager opens a storage of its own, so it's executed only once configured.
+    pub fn class_manager_default_config() -> Self {
+        Self {
+            execute: false,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    pub fn mempool_p2p_default_config() -> Self {
+        Self {
+            execute: false,
+            location: LocationType::Local,
+            component_type: ComponentType::AsynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    pub fn batcher_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::SynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+
+    pub fn consensus_manager_default_config() -> Self {
+        Self {
+            execute: true,
+            location: LocationType::Local,
+            component_type: ComponentType::AsynchronousComponent,
+            local_config: Some(LocalComponentCommunicationConfig::default()),
+            remote_config: None,
+        }
+    }
+}
+
+pub fn validate_single_component_config(
+    component_config: &ComponentExecutionConfig,
+) -> Result<(), ValidationError> {
+    let error_message =
+        if component_config.local_config.is_some() && component_config.remote_config.is_some() {
+            "Local config and Remote config are mutually exclusive, can't be both active."
+        } else if component_config.location == LocationType::Local
+            && component_config.local_config.is_none()
+        {
+            "Local communication config is missing."
+        } else if component_config.location == LocationType::Remote
+            && component_config.remote_config.is_none()
+        {
+            "Remote communication config is missing."
+        } else if component_config.execute
+            && component_config.location == LocationType::Remote
+            && component_config.component_type != ComponentType::SynchronousComponent
+        {
+            // The remote server handles requests only, it doesn't run the tasks of the component.
+            "Only synchronous components can be served remotely."
+        } else {
+            return Ok(());
+        };
+
+    let mut error = ValidationError::new("Invalid component configuration.");
+    error.message = Some(error_message.into());
+    Err(error)
+}
+
+/// The components configuration.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_components_config", skip_on_field_errors = false))]
+pub struct ComponentConfig {
+    #[validate]
+    pub batcher: ComponentExecutionConfig,
+    // Stores the classes declared through the gateway, for the batcher to execute the
+    // transactions that use them.
+    #[validate]
+    pub class_manager: ComponentExecutionConfig,
+    #[validate]
+    pub consensus_manager: ComponentExecutionConfig,
+    #[validate]
+    pub gateway: ComponentExecutionConfig,
+    // Provides the L1 handler transactions of the proposals generated by the batcher.
+    #[validate]
+    pub l1_provider: ComponentExecutionConfig,
+    // Provides the L1 gas prices of the blocks built by the batcher.
+    #[validate]
+    pub l1_gas_price_provider: ComponentExecutionConfig,
+    #[validate]
+    pub mempool: ComponentExecutionConfig,
+    // Propagates transactions between the node and its peers. Requires the gateway, which
+    // validates the transactions received from peers.
+    #[validate]
+    pub mempool_p2p: ComponentExecutionConfig,
+}
+
+impl Default for ComponentConfig {
+    fn default() -> Self {
+        Self {
+            batcher: ComponentExecutionConfig::batcher_default_config(),
+            class_manager: ComponentExecutionConfig::class_manager_default_config(),
+            consensus_manager: ComponentExecutionConfig::consensus_manager_default_config(),
+            gateway: ComponentExecutionConfig::gateway_default_config(),
+            l1_provider: ComponentExecutionConfig::l1_provider_default_config(),
+            l1_gas_price_provider: ComponentExecutionConfig::l1_gas_price_provider_default_config(),
+            mempool: ComponentExecutionConfig::mempool_default_config(),
+            mempool_p2p: ComponentExecutionConfig::mempool_p2p_default_config(),
+        }
+    }
+}
+
+impl SerializeConfig for ComponentConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        #[allow(unused_mut)]
+        let mut sub_configs = vec![
+            append_sub_config_name(self.batcher.dump(), "batcher"),
+            append_sub_config_name(self.class_manager.dump(), "class_manager"),
+            append_sub_config_name(self.consensus_manager.dump(), "consensus_manager"),
+            append_sub_config_name(self.gateway.dump(), "gateway"),
+            append_sub_config_name(self.l1_provider.dump(), "l1_provider"),
+            append_sub_config_name(self.l1_gas_price_provider.dump(), "l1_gas_price_provider"),
+            append_sub_config_name(self.mempool.dump(), "mempool"),
+            append_sub_config_name(self.mempool_p2p.dump(), "mempool_p2p"),
+        ];
+
+        sub_configs.into_iter().flatten().collect()
+    }
+}
+
+pub fn validate_components_config(components: &ComponentConfig) -> Result<(), ValidationError> {
+    if components.gateway.execute
+        || components.mempool.execute
+        || components.batcher.execute
+        || components.class_manager.execute
+        || components.consensus_manager.execute
+        || components.l1_provider.execute
+        || components.l1_gas_price_provider.execute
+        || components.mempool_p2p.execute
+    {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("Invalid components configuration.");
+    error.message = Some("At least one component should be allowed to execute.".into());
+    Err(error)
+}
+
+/// The configurations of the various components of the node.
+///
+/// The constraints between params are validated even if some params are invalid, so that a
+/// misconfigured node fails to start with a report of all its invalid params.
+#[derive(Debug, Deserialize, Default, Serialize, Clone, PartialEq, Validate)]
+#[validate(schema(function = "validate_chain_configs", skip_on_field_errors = false))]
+pub struct SequencerNodeConfig {
+    #[validate]
+    pub components: ComponentConfig,
+    #[validate]
+    pub batcher_config: BatcherConfig,
+    #[validate]
+    pub class_manager_config: ClassManagerConfig,
+    #[validate]
+    pub consensus_manager_config: ConsensusManagerConfig,
+    #[validate]
+    pub gateway_config: GatewayConfig,
+    #[validate]
+    pub rpc_state_reader_config: RpcStateReaderConfig,
+    #[validate]
+    pub compiler_config: SierraToCasmCompilationConfig,
+    #[validate]
+    pub mempool_config: MempoolConfig,
+    #[validate]
+    pub mempool_p2p_config: MempoolP2pConfig,
+    #[validate]
+    pub l1_gas_price_provider_config: L1GasPriceProviderConfig,
+    // Samples the base layer gas prices; disabled when not set.
+    #[validate]
+    pub l1_gas_price_scraper_config: Option<L1GasPriceScraperConfig>,
+    #[validate]
+    pub resource_guard_config: ResourceGuardConfig,
+    #[validate]
+    pub monitoring_endpoint_config: MonitoringEndpointConfig,
+    #[validate]
+    pub admin_endpoint_config: AdminEndpointConfig,
+    #[validate]
+    pub config_reloader_config: ConfigReloaderConfig,
+    #[validate]
+    pub supervisor_config: SupervisorConfig,
+    #[validate]
+    pub trace_export_config: TraceExportConfig,
+    #[validate]
+    pub logging_config: LoggingConfig,
+}
+
+/// The gateway and the batcher must be configured for the same chain.
+pub fn validate_chain_configs(config: &SequencerNodeConfig) -> Result<(), ValidationError> {
+    validate_chain_ids(config)?;
+    validate_fee_configs(config)
+}
+
+/// The gateway validates transactions against their hash on the chain of the batcher, so that
+/// transactions signed for another chain can't be replayed on it.
+pub fn validate_chain_ids(config: &SequencerNodeConfig) -> Result<(), ValidationError> {
+    let gateway_chain_id = &config.gateway_config.stateful_tx_validator_config.chain_info.chain_id;
+    let batcher_chain_id = &config.batcher_config.chain_info.chain_id;
+    if gateway_chain_id == batcher_chain_id {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("Invalid chain id configuration.");
+    error.message = Some(
+        format!(
+            "The chain id of the gateway, {gateway_chain_id}, differs from the chain id of the \
+             batcher, {batcher_chain_id}."
+        )
+        .into(),
+    );
+    Err(error)
+}
+
+/// The gateway admits transactions by the fee tokens and the fee policy the batcher executes them
+/// with, so that the transactions it admits aren't left out of every block.
+pub fn validate_fee_configs(config: &SequencerNodeConfig) -> Result<(), ValidationError> {
+    let gateway_fee_token_addresses =
+        &config.gateway_config.stateful_tx_validator_config.chain_info.fee_token_addresses;
+    let batcher_fee_token_addresses = &config.batcher_config.chain_info.fee_token_addresses;
+    let gateway_fee_config = &config.gateway_config.stateless_tx_validator_config.fee_config;
+    let batcher_fee_config = &config.batcher_config.fee_config;
+    let message = if gateway_fee_token_addresses != batcher_fee_token_addresses {
+        format!(
+            "The fee token addresses of the gateway, {gateway_fee_token_addresses:?}, differ \
+             from the fee token addresses of the batcher, {batcher_fee_token_addresses:?}."
+        )
+    } else if gateway_fee_config != batcher_fee_config {
+        format!(
+            "The fee policy of the gateway, {gateway_fee_config:?}, differs from the fee policy \
+             of the batcher, {batcher_fee_config:?}."
+        )
+    } else {
+        return Ok(());
+    };
+
+    let mut error = ValidationError::new("Invalid fee configuration.");
+    error.message = Some(message.into());
+    Err(error)
+}
+
+impl SerializeConfig for SequencerNodeConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        #[allow(unused_mut)]
+        let mut sub_configs = vec![
+            append_sub_config_name(self.components.dump(), "components"),
+            append_sub_config_name(self.batcher_config.dump(), "batcher_config"),
+            append_sub_config_name(self.class_manager_config.dump(), "class_manager_config"),
+            append_sub_config_name(
+                self.consensus_manager_config.dump(),
+                "consensus_manager_config",
+            ),
+            append_sub_config_name(self.gateway_config.dump(), "gateway_config"),
+            append_sub_config_name(self.rpc_state_reader_config.dump(), "rpc_state_reader_config"),
+            append_sub_config_name(self.compiler_config.dump(), "compiler_config"),
+            append_sub_config_name(self.mempool_config.dump(), "mempool_config"),
+            append_sub_config_name(self.mempool_p2p_config.dump(), "mempool_p2p_config"),
+            append_sub_config_name(
+                self.l1_gas_price_provider_config.dump(),
+                "l1_gas_price_provider_config",
+            ),
+            ser_optional_sub_config(
+                &self.l1_gas_price_scraper_config,
+                "l1_gas_price_scraper_config",
+            ),
+            append_sub_config_name(self.resource_guard_config.dump(), "resource_guard_config"),
+            append_sub_config_name(
+                self.monitoring_endpoint_config.dump(),
+                "monitoring_endpoint_config",
+            ),
+            append_sub_config_name(self.admin_endpoint_config.dump(), "admin_endpoint_config"),
+            append_sub_config_name(self.config_reloader_config.dump(), "config_reloader_config"),
+            append_sub_config_name(self.supervisor_config.dump(), "supervisor_config"),
+            append_sub_config_name(self.trace_export_config.dump(), "trace_export_config"),
+            append_sub_config_name(self.logging_config.dump(), "logging_config"),
+        ];
+
+        sub_configs.into_iter().flatten().collect()
+    }
+}
+
+impl SequencerNodeConfig {
+    /// Creates a config object. Selects the values from the default file and from resources with
+    /// higher priority.
+    fn load_and_process_config_file(
+        args: Vec<String>,
+        config_file_name: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        let config_file_name = match config_file_name {
+            Some(file_name) => file_name,
+            None => DEFAULT_CONFIG_PATH,
+        };
+
+        let default_config_file = File::open(Path::new(config_file_name))?;
+        load_and_process_config(default_config_file, node_command(), args)
+    }
+
+    pub fn load_and_process(args: Vec<String>) -> Result<Self, ConfigError> {
+        Self::load_and_process_config_file(args, None)
+    }
+    pub fn load_and_process_file(
+        args: Vec<String>,
+        config_file_name: &str,
+    ) -> Result<Self, ConfigError> {
+        Self::load_and_process_config_file(args, Some(config_file_name))
+    }
+}
+
+/// The command line interface of this node.
+pub fn node_command() -> Command {
+    Command::new("Sequencer")
+        .version(VERSION_FULL)
+        .about("Sequencer is a Starknet sequencer node written in Rust.")
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::env::args;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::reloading::{get_param_changes, ParamChanges};
+use papyrus_config::validators::config_validate;
+use papyrus_config::{ConfigError, ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_batcher::block_builder::BlockBuilderConfig;
+use starknet_gateway::config::RateLimitConfig;
+use starknet_gateway::transaction_type_gate::TransactionTypeGate;
+use starknet_mempool::config::EvictionConfig;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::config::SequencerNodeConfig;
+
+/// The configuration of the reloading of the node config, which applies the changes of its dynamic
+/// params to the running components.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ConfigReloaderConfig {
+    pub enable: bool,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub reload_interval: Duration,
+}
+
+impl Default for ConfigReloaderConfig {
+    fn default() -> Self {
+        Self { enable: false, reload_interval: Duration::from_secs(30) }
+    }
+}
+
+impl SerializeConfig for ConfigReloaderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the changes of the dynamic params of the node config are applied \
+                 without a restart once the config is reloaded, periodically or by an admin \
+                 request.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "reload_interval",
+                &self.reload_interval.as_secs(),
+                "The time in seconds between reloads of the node config. If 0, the config is \
+                 reloaded only by admin requests.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The handles through which the components of the node read their dynamic params. A handle is
+/// missing if its component isn't run by the node.
+#[derive(Clone, Default)]
+pub struct DynamicConfigs {
+    pub rate_limit_config: Option<SharedDynamicConfig<RateLimitConfig>>,
+    pub transaction_type_gate: Option<Arc<TransactionTypeGate>>,
+    pub mempool_eviction_config: Option<SharedDynamicConfig<EvictionConfig>>,
+    pub block_builder_config: Option<SharedDynamicConfig<BlockBuilderConfig>>,
+}
+
+impl DynamicConfigs {
+    // Sets the dynamic params of the components to their values in the given config, keeping the
+    // values of the other params the components were created with.
+    fn set(&self, config: &SequencerNodeConfig) {
+        if let Some(rate_limit_config) = &self.rate_limit_config {
+            rate_limit_config.set(config.gateway_config.rate_limit_config.clone());
+        }
+        if let Some(transaction_type_gate) = &self.transaction_type_gate {
+            let transaction_type_config = &config.gateway_config.transaction_type_config;
+            transaction_type_gate.set_declare_enabled(transaction_type_config.enable_declare);
+            transaction_type_gate
+                .set_deploy_account_enabled(transaction_type_config.enable_deploy_account);
+        }
+        // Enabling or disabling the eviction requires a restart.
+        if let (Some(eviction_config), Some(reloaded_eviction_config)) =
+            (&self.mempool_eviction_config, &config.mempool_config.eviction)
+        {
+            eviction_config.set(EvictionConfig {
+                tx_ttl: reloaded_eviction_config.tx_ttl,
+                max_n_txs: reloaded_eviction_config.max_n_txs,
+                max_n_bytes: reloaded_eviction_config.max_n_bytes,
+                ..eviction_config.get()
+            });
+        }
+        if let Some(block_builder_config) = &self.block_builder_config {
+            let reloaded_block_builder_config = &config.batcher_config.block_builder;
+            block_builder_config.set(BlockBuilderConfig {
+                max_n_transactions: reloaded_block_builder_config.max_n_transactions,
+                max_tx_execution_time: reloaded_block_builder_config.max_tx_execution_time,
+                ..block_builder_config.get()
+            });
+        }
+    }
+}
+
+/// Reloads the node config from the sources it was loaded from at startup, and applies the changes
+/// of its dynamic params to the running components. Changes of the other params are only reported,
+/// and take effect once the node is restarted.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    config: ConfigReloaderConfig,
+    // The config the changes of the next reload are computed against, so that each change is
+    // reported once.
+    last_loaded_config: Arc<Mutex<SequencerNodeConfig>>,
+    dynamic_configs: DynamicConfigs,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config: ConfigReloaderConfig,
+        node_config: SequencerNodeConfig,
+        dynamic_configs: DynamicConfigs,
+    ) -> Self {
+        Self { config, last_loaded_config: Arc::new(Mutex::new(node_config)), dynamic_configs }
+    }
+
+    /// Reloads the node config and applies the changes of its dynamic params. An invalid config is
+    /// rejected as a whole, keeping the current values.
+    pub fn reload(&self) -> Result<ParamChanges, ConfigError> {
+        let reloaded_config = SequencerNodeConfig::load_and_process(args().collect())?;
+        config_validate(&reloaded_config)?;
+        Ok(self.apply(reloaded_config))
+    }
+
+    fn apply(&self, reloaded_config: SequencerNodeConfig) -> ParamChanges {
+        let mut last_loaded_config =
+            self.last_loaded_config.lock().expect("Config lock should not be poisoned.");
+        let changes = get_param_changes(&*last_loaded_config, &reloaded_config);
+        if !changes.requiring_restart.is_empty() {
+            warn!(
+                params = ?changes.requiring_restart,
+                "The reloaded node config changed params that take effect only after a restart."
+            );
+        }
+        if !changes.dynamic.is_empty() {
+            self.dynamic_configs.set(&reloaded_config);
+            info!(target: "audit", params = ?changes.dynamic, "Applied the reloaded node config.");
+        }
+        *last_loaded_config = reloaded_config;
+        changes
+    }
+}
+
+#[async_trait]
+impl ComponentServerStarter for ConfigReloader {
+    async fn start(&mut self) {
+        let mut interval = tokio::time::interval(self.config.reload_interval);
+        // The config was loaded when the node started.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reload() {
+                error!("Failed to reload the node config, keeping the current one: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod admin_endpoint;
+pub mod communication;
+pub mod components;
+pub mod config;
+pub mod config_reloader;
+pub mod monitoring_endpoint;
+pub mod resource_guard;
+pub mod servers;
+pub mod supervisor;
+pub mod utils;
+pub mod version;
@@ -0,0 +1,35 @@
+use std::env::args;
+use std::process::exit;
+
+use papyrus_config::validators::config_validate;
+use papyrus_config::ConfigError;
+use starknet_mempool_infra::trace_util::configure_tracing;
+use starknet_sequencer_node::config::SequencerNodeConfig;
+use starknet_sequencer_node::servers::run_component_servers;
+use starknet_sequencer_node::utils::create_clients_servers_from_config;
+use tracing::{error, info};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let tracing_handle = configure_tracing();
+
+    let config = SequencerNodeConfig::load_and_process(args().collect());
+    if let Err(ConfigError::CommandInput(clap_err)) = config {
+        clap_err.exit();
+    }
+
+    let config = config?;
+    if let Err(error) = config_validate(&config) {
+        error!("{}", error);
+        exit(1);
+    }
+    tracing_handle.set_log_format(config.logging_config.format);
+    tracing_handle.start_trace_export(&config.trace_export_config);
+
+    let (clients, servers) = create_clients_servers_from_config(&config);
+
+    info!("Starting components!");
+    run_component_servers(&config, &clients, servers).await?;
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::future::join_all;
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_mempool_infra::component_client::SharedComponentHealthClient;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use tracing::{error, info, warn};
+use validator::Validate;
+
+pub const ALIVE: &str = "/monitoring/alive";
+pub const READY: &str = "/monitoring/ready";
+pub const METRICS: &str = "/monitoring/metrics";
+
+/// The configuration of the endpoint answering the liveness and readiness probes of the node, and
+/// exporting its metrics.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct MonitoringEndpointConfig {
+    pub enable: bool,
+    pub ip: IpAddr,
+    pub port: u16,
+    pub collect_metrics: bool,
+}
+
+impl SerializeConfig for MonitoringEndpointConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, the liveness and readiness of the components are served over HTTP.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "ip",
+                &self.ip.to_string(),
+                "The monitoring endpoint ip.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "port",
+                &self.port,
+                "The monitoring endpoint port.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "collect_metrics",
+                &self.collect_metrics,
+                "If true, the metrics of the components are collected and served in the \
+                 Prometheus format.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for MonitoringEndpointConfig {
+    fn default() -> Self {
+        Self { enable: false, ip: "0.0.0.0".parse().unwrap(), port: 8082, collect_metrics: false }
+    }
+}
+
+// The clients of the components to probe, by component name.
+type HealthClients = Arc<Vec<(&'static str, SharedComponentHealthClient)>>;
+
+/// Serves the liveness and readiness of the components of the node, e.g., to k8s probes. Each
+/// probe is answered with the health of every component, with status 200 if all the components are
+/// healthy, and 503 otherwise. If metrics are collected, they are served for Prometheus to scrape.
+pub struct MonitoringEndpoint {
+    config: MonitoringEndpointConfig,
+    health_clients: HealthClients,
+    prometheus_handle: Option<PrometheusHandle>,
+}
+
+impl MonitoringEndpoint {
+    /// Installs the global metrics recorder if metrics are collected, which fails if a recorder is
+    /// already installed.
+    pub fn new(
+        config: MonitoringEndpointConfig,
+        health_clients: Vec<(&'static str, SharedComponentHealthClient)>,
+    ) -> Result<Self, BuildError> {
+        let prometheus_handle = match config.collect_metrics {
+            true => Some(PrometheusBuilder::new().install_recorder()?),
+            false => None,
+        };
+        Ok(Self { config, health_clients: Arc::new(health_clients), prometheus_handle })
+    }
+
+    pub fn app(&self) -> Router {
+        let prometheus_handle = self.prometheus_handle.clone();
+        Router::new()
+            .route(ALIVE, get(alive))
+            .route(READY, get(ready))
+            .route(METRICS, get(move || metrics(prometheus_handle)))
+            .with_state(self.health_clients.clone())
+    }
+}
+
+#[async_trait]
+impl ComponentServerStarter for MonitoringEndpoint {
+    async fn start(&mut self) {
+        let MonitoringEndpointConfig { ip, port, .. } = self.config;
+        let addr = SocketAddr::new(ip, port);
+        info!("Serving the health of the components at {}.", addr);
+        if let Err(e) = axum::Server::bind(&addr).serve(self.app().into_make_service()).await {
+            error!("Monitoring endpoint stopped: {}", e);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Probe {
+    Alive,
+    Ready,
+}
+
+async fn alive(
+    State(health_clients): State<HealthClients>,
+) -> (StatusCode, Json<BTreeMap<&'static str, bool>>) {
+    probe(&health_clients, Probe::Alive).await
+}
+
+async fn ready(
+    State(health_clients): State<HealthClients>,
+) -> (StatusCode, Json<BTreeMap<&'static str, bool>>) {
+    probe(&health_clients, Probe::Ready).await
+}
+
+// Components failing to answer are unhealthy.
+async fn probe(
+    health_clients: &HealthClients,
+    probe: Probe,
+) -> (StatusCode, Json<BTreeMap<&'static str, bool>>) {
+    let answers = join_all(health_clients.iter().map(|(name, client)| async move {
+        let answer = match probe {
+            Probe::Alive => client.is_alive().await,
+            Probe::Ready => client.is_ready().await,
+        };
+        let is_healthy = answer.unwrap_or_else(|e| {
+            warn!("Failed to probe the health of the {}: {}", name, e);
+            false
+        });
+        (*name, is_healthy)
+    }))
+    .await;
+
+    let health: BTreeMap<_, _> = answers.into_iter().collect();
+    let status = match health.values().all(|is_healthy| *is_healthy) {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(health))
+}
+
+// Nodes not collecting metrics answer with 405: method not allowed.
+async fn metrics(prometheus_handle: Option<PrometheusHandle>) -> Response {
+    match prometheus_handle {
+        Some(handle) => handle.render().into_response(),
+        None => StatusCode::METHOD_NOT_ALLOWED.into_response(),
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use starknet_mempool_infra::resource_monitor::{
+    ResourceGuardConfig,
+    ResourcePressure,
+    ResourceStatus,
+    ResourceUsage,
+    SharedResourceStatus,
+};
+use starknet_mempool_types::communication::SharedMempoolClient;
+use tracing::{error, info, warn};
+
+/// Monitors the memory and file descriptor usage of the node against the configured ceilings, so
+/// that load is shed before the OOM killer steps in. While the node is under pressure, the
+/// mempool evicts non-eligible transactions and the gateway rejects new ones.
+pub struct ResourceGuard {
+    config: ResourceGuardConfig,
+    status: SharedResourceStatus,
+    mempool_client: Option<SharedMempoolClient>,
+}
+
+impl ResourceGuard {
+    pub fn new(
+        config: ResourceGuardConfig,
+        status: SharedResourceStatus,
+        mempool_client: Option<SharedMempoolClient>,
+    ) -> Self {
+        Self { config, status, mempool_client }
+    }
+
+    async fn check(&self) {
+        let usage = match ResourceUsage::sample() {
+            Ok(usage) => usage,
+            Err(e) => {
+                warn!("Failed to sample the resource usage of the node: {}", e);
+                return;
+            }
+        };
+        let pressure = self.config.pressure(&usage);
+        if pressure != self.status.get().pressure {
+            warn!(?usage, ?pressure, "Resource pressure of the node changed.");
+        }
+        self.status.set(ResourceStatus { usage, pressure });
+
+        if pressure != ResourcePressure::High {
+            return;
+        }
+        let Some(mempool_client) = &self.mempool_client else {
+            return;
+        };
+        match mempool_client.evict_txs(self.config.eviction_batch_size).await {
+            Ok(n_evicted_txs) => info!("Evicted {} transactions from the mempool.", n_evicted_txs),
+            Err(e) => error!("Failed to evict transactions from the mempool: {}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentServerStarter for ResourceGuard {
+    async fn start(&mut self) {
+        let mut interval = tokio::time::interval(self.config.polling_interval());
+        loop {
+            interval.tick().await;
+            self.check().await;
+        }
+    }
+}
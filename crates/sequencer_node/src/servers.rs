@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+use std::future::pending;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::future::select_all;
+use futures::{Future, FutureExt};
+use starknet_batcher::communication::{create_local_batcher_server, create_remote_batcher_server};
+use starknet_class_manager::communication::{
+    create_local_class_manager_server,
+    create_remote_class_manager_server,
+};
+use starknet_consensus_manager::communication::create_local_consensus_manager_server;
+use starknet_gateway::communication::create_gateway_server;
+use starknet_l1_gas_price::communication::{
+    create_local_l1_gas_price_provider_server,
+    create_remote_l1_gas_price_provider_server,
+};
+use starknet_l1_provider::communication::{
+    create_local_l1_provider_server,
+    create_remote_l1_provider_server,
+};
+use starknet_mempool::communication::{create_mempool_server, create_remote_mempool_server};
+use starknet_mempool_infra::component_client::ComponentHealthClient;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use starknet_mempool_infra::request_priority::RequestPriorities;
+use starknet_mempool_p2p::communication::{
+    create_local_mempool_p2p_sender_server,
+    create_mempool_p2p_receiver_server,
+};
+use tokio::signal::ctrl_c;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::communication::{SequencerNodeClients, SequencerNodeCommunication};
+use crate::components::Components;
+use crate::config::{ComponentExecutionConfig, SequencerNodeConfig};
+use crate::supervisor::supervise;
+
+const READINESS_POLLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A server of a component, or a task of the node run like one.
+pub type ComponentServer = Box<dyn ComponentServerStarter>;
+
+pub struct Servers {
+    pub batcher: Option<ComponentServer>,
+    pub class_manager: Option<ComponentServer>,
+    pub consensus_manager: Option<ComponentServer>,
+    pub gateway: Option<ComponentServer>,
+    pub l1_provider: Option<ComponentServer>,
+    pub l1_gas_price_provider: Option<ComponentServer>,
+    pub l1_gas_price_scraper: Option<ComponentServer>,
+    pub mempool: Option<ComponentServer>,
+    pub mempool_p2p_sender: Option<ComponentServer>,
+    pub mempool_p2p_receiver: Option<ComponentServer>,
+    pub resource_guard: Option<ComponentServer>,
+    pub monitoring_endpoint: Option<ComponentServer>,
+    pub admin_endpoint: Option<ComponentServer>,
+    pub config_reloader: Option<ComponentServer>,
+}
+
+/// Creates the servers of the executed components: a local server reading the channel of the
+/// component, or a remote server at its configured address when the component is located remotely.
+pub fn create_servers(
+    config: &SequencerNodeConfig,
+    communication: &mut SequencerNodeCommunication,
+    clients: &SequencerNodeClients,
+    components: Components,
+) -> Servers {
+    let batcher_server = if config.components.batcher.execute {
+        let batcher = components.batcher.expect("Batcher is not initialized.");
+        let server: ComponentServer = match config.components.batcher.remote_server_config() {
+            Some(remote) => Box::new(
+                create_remote_batcher_server(
+                    batcher,
+                    remote.ip,
+                    remote.port,
+                    remote.serialization_format,
+                )
+                .with_transport(remote.transport)
+                .with_auth_token(&remote.auth_token),
+            ),
+            None => Box::new(
+                create_local_batcher_server(batcher, communication.take_batcher_rx())
+                    .with_request_priorities(request_priorities(&config.components.batcher)),
+            ),
+        };
+        Some(server)
+    } else {
+        None
+    };
+    let class_manager_server = if config.components.class_manager.execute {
+        let class_manager = components.class_manager.expect("Class Manager is not initialized.");
+        let server: ComponentServer = match config.components.class_manager.remote_server_config() {
+            Some(remote) => Box::new(
+                create_remote_class_manager_server(
+                    class_manager,
+                    remote.ip,
+                    remote.port,
+                    remote.serialization_format,
+                )
+                .with_transport(remote.transport)
+                .with_auth_token(&remote.auth_token),
+            ),
+            None => Box::new(
+                create_local_class_manager_server(
+                    class_manager,
+                    communication.take_class_manager_rx(),
+                )
+                .with_request_priorities(request_priorities(&config.components.class_manager)),
+            ),
+        };
+        Some(server)
+    } else {
+        None
+    };
+    // The consensus manager, the gateway and the mempool p2p run tasks of their own, which remote
+    // servers don't start, so they're served locally.
+    let consensus_manager_server = if config.components.consensus_manager.execute {
+        let server: ComponentServer = Box::new(create_local_consensus_manager_server(
+            components.consensus_manager.expect("Consensus Manager is not initialized."),
+            communication.take_consensus_manager_rx(),
+        ));
+        Some(server)
+    } else {
+        None
+    };
+    let gateway_server = if config.components.gateway.execute {
+        let server: ComponentServer = Box::new(create_gateway_server(
+            components.gateway.expect("Gateway is not initialized."),
+            communication.take_gateway_rx(),
+            max_concurrent_requests(&config.components.gateway),
+        ));
+        Some(server)
+    } else {
+        None
+    };
+
+    let l1_provider_server = if config.components.l1_provider.execute {
+        let l1_provider = components.l1_provider.expect("L1 Provider is not initialized.");
+        let server: ComponentServer = match config.components.l1_provider.remote_server_config() {
+            Some(remote) => Box::new(
+                create_remote_l1_provider_server(
+                    l1_provider,
+                    remote.ip,
+                    remote.port,
+                    remote.serialization_format,
+                )
+                .with_transport(remote.transport)
+                .with_auth_token(&remote.auth_token),
+            ),
+            None => Box::new(
+                create_local_l1_provider_server(l1_provider, communication.take_l1_provider_rx())
+                    .with_request_priorities(request_priorities(&config.components.l1_provider)),
+            ),
+        };
+        Some(server)
+    } else {
+        None
+    };
+
+    let l1_gas_price_provider_server = if config.components.l1_gas_price_provider.execute {
+        let l1_gas_price_provider =
+            components.l1_gas_price_provider.expect("L1 Gas Price Provider is not initialized.");
+        let server: ComponentServer =
+            match config.components.l1_gas_price_provider.remote_server_config() {
+                Some(remote) => Box::new(
+                    create_remote_l1_gas_price_provider_server(
+                        l1_gas_price_provider,
+                        remote.ip,
+                        remote.port,
+                        remote.serialization_format,
+                    )
+                    .with_transport(remote.transport)
+                    .with_auth_token(&remote.auth_token),
+                ),
+                None => Box::new(
+                    create_local_l1_gas_price_provider_server(
+                        l1_gas_price_provider,
+                        communication.take_l1_gas_price_provider_rx(),
+                    )
+                    .with_request_priorities(request_priorities(
+                        &config.components.l1_gas_price_provider,
+                    )),
+                ),
+            };
+        Some(server)
+    } else {
+        None
+    };
+
+    let mempool_server = if config.components.mempool.execute {
+        let mempool = components.mempool.expect("Mempool is not initialized.");
+        let server: ComponentServer = match config.components.mempool.remote_server_config() {
+            Some(remote) => Box::new(
+                create_remote_mempool_server(
+                    mempool,
+                    remote.ip,
+                    remote.port,
+                    remote.serialization_format,
+                    clients.get_gateway_client(),
+                )
+                .with_transport(remote.transport)
+                .with_auth_token(&remote.auth_token),
+            ),
+            None => Box::new(
+                create_mempool_server(
+                    mempool,
+                    communication.take_mempool_rx(),
+                    Some(communication.take_mempool_tx_stream_tx()),
+                    clients.get_gateway_client(),
+                    max_concurrent_requests(&config.components.mempool),
+                )
+                .with_request_priorities(request_priorities(&config.components.mempool)),
+            ),
+        };
+        Some(server)
+    } else {
+        None
+    };
+
+    let (mempool_p2p_sender_server, mempool_p2p_receiver_server) =
+        if config.components.mempool_p2p.execute {
+            let sender_server: ComponentServer = Box::new(create_local_mempool_p2p_sender_server(
+                components.mempool_p2p_sender.expect("Mempool P2P Sender is not initialized."),
+                communication.take_mempool_p2p_sender_rx(),
+            ));
+            let receiver_server: ComponentServer = Box::new(create_mempool_p2p_receiver_server(
+                components.mempool_p2p_receiver.expect("Mempool P2P Receiver is not initialized."),
+            ));
+            (Some(sender_server), Some(receiver_server))
+        } else {
+            (None, None)
+        };
+
+    let l1_gas_price_scraper =
+        components.l1_gas_price_scraper.map(|scraper| Box::new(scraper) as ComponentServer);
+    let resource_guard = components.resource_guard.map(|guard| Box::new(guard) as ComponentServer);
+    let monitoring_endpoint =
+        components.monitoring_endpoint.map(|endpoint| Box::new(endpoint) as ComponentServer);
+    let admin_endpoint =
+        components.admin_endpoint.map(|endpoint| Box::new(endpoint) as ComponentServer);
+    let config_reloader =
+        components.config_reloader.map(|reloader| Box::new(reloader) as ComponentServer);
+
+    Servers {
+        batcher: batcher_server,
+        class_manager: class_manager_server,
+        consensus_manager: consensus_manager_server,
+        gateway: gateway_server,
+        l1_provider: l1_provider_server,
+        l1_gas_price_provider: l1_gas_price_provider_server,
+        l1_gas_price_scraper,
+        mempool: mempool_server,
+        mempool_p2p_sender: mempool_p2p_sender_server,
+        mempool_p2p_receiver: mempool_p2p_receiver_server,
+        resource_guard,
+        monitoring_endpoint,
+        admin_endpoint,
+        config_reloader,
+    }
+}
+
+/// Runs the servers until one of them stops or the node is signaled to shut down, and then stops
+/// all of them.
+///
+/// The servers are started such that each starts after the servers of the components it calls; a
+/// server with a local health client is waited for until its component is ready.
+// The priorities of the requests queued for a component served locally.
+fn request_priorities(component_config: &ComponentExecutionConfig) -> RequestPriorities {
+    component_config
+        .local_config
+        .as_ref()
+        .map(|local_config| local_config.request_priorities.clone())
+        .unwrap_or_default()
+}
+
+// The number of requests handled concurrently by a component served locally.
+fn max_concurrent_requests(component_config: &ComponentExecutionConfig) -> usize {
+    component_config
+        .local_config
+        .as_ref()
+        .map_or(1, |local_config| local_config.max_concurrent_requests)
+}
+
+pub async fn run_component_servers(
+    config: &SequencerNodeConfig,
+    clients: &SequencerNodeClients,
+    servers: Servers,
+) -> anyhow::Result<()> {
+    let health_clients: HashMap<_, _> = clients.get_health_clients().into_iter().collect();
+    let components = &config.components;
+    // The name of each server, the name its component answers health probes by, if any, and
+    // whether it's run.
+    let ordered_servers = [
+        (
+            "Class Manager",
+            Some("class_manager"),
+            components.class_manager.execute,
+            servers.class_manager,
+        ),
+        ("Mempool", Some("mempool"), components.mempool.execute, servers.mempool),
+        ("L1 Provider", Some("l1_provider"), components.l1_provider.execute, servers.l1_provider),
+        (
+            "L1 Gas Price Provider",
+            Some("l1_gas_price_provider"),
+            components.l1_gas_price_provider.execute,
+            servers.l1_gas_price_provider,
+        ),
+        (
+            "L1 Gas Price Scraper",
+            None,
+            servers.l1_gas_price_scraper.is_some(),
+            servers.l1_gas_price_scraper,
+        ),
+        ("Batcher", Some("batcher"), components.batcher.execute, servers.batcher),
+        (
+            "Consensus Manager",
+            None,
+            components.consensus_manager.execute,
+            servers.consensus_manager,
+        ),
+        ("Mempool P2P Sender", None, components.mempool_p2p.execute, servers.mempool_p2p_sender),
+        ("Gateway", Some("gateway"), components.gateway.execute, servers.gateway),
+        (
+            "Mempool P2P Receiver",
+            None,
+            components.mempool_p2p.execute,
+            servers.mempool_p2p_receiver,
+        ),
+        ("Resource Guard", None, config.resource_guard_config.enable, servers.resource_guard),
+        (
+            "Monitoring Endpoint",
+            None,
+            config.monitoring_endpoint_config.enable,
+            servers.monitoring_endpoint,
+        ),
+        ("Admin Endpoint", None, config.admin_endpoint_config.enable, servers.admin_endpoint),
+        (
+            "Config Reloader",
+            None,
+            // Without periodic reloads, the config is reloaded only by admin requests.
+            config.config_reloader_config.enable
+                && !config.config_reloader_config.reload_interval.is_zero(),
+            servers.config_reloader,
+        ),
+    ];
+
+    let mut handles: Vec<(&str, JoinHandle<()>)> = Vec::new();
+    for (name, health_client_name, execute_flag, server) in ordered_servers {
+        if !execute_flag {
+            continue;
+        }
+        info!("Starting {}.", name);
+        let server = server.unwrap_or_else(|| panic!("{} component is not initialized.", name));
+        let mut handle = tokio::spawn(supervise(name, server, config.supervisor_config.clone()));
+        if let Some(health_client) =
+            health_client_name.and_then(|health_client_name| health_clients.get(health_client_name))
+        {
+            tokio::select! {
+                _ = wait_until_ready(health_client.as_ref()) => info!("{} is ready.", name),
+                result = &mut handle => {
+                    error!("{} stopped while starting.", name);
+                    stop_servers(handles).await;
+                    result?;
+                    return Ok(());
+                }
+            }
+        }
+        handles.push((name, handle));
+    }
+    if handles.is_empty() {
+        return Ok(());
+    }
+
+    let stopped_server = tokio::select! {
+        (result, index, _) = select_all(handles.iter_mut().map(|(_, handle)| handle)) => {
+            Some((index, result))
+        }
+        _ = shutdown_signal() => None,
+    };
+    let result = match stopped_server {
+        Some((index, result)) => {
+            // The handle of the stopped server is done, so it isn't awaited again.
+            let (name, _) = handles.remove(index);
+            error!("{} stopped.", name);
+            result
+        }
+        None => {
+            info!("Received a shutdown signal.");
+            Ok(())
+        }
+    };
+    stop_servers(handles).await;
+    result?;
+
+    Ok(())
+}
+
+/// Stops the servers in the reverse order of their startup, so that a server stops before the
+/// servers of the components it calls.
+async fn stop_servers(handles: Vec<(&str, JoinHandle<()>)>) {
+    for (name, handle) in handles.into_iter().rev() {
+        handle.abort();
+        // The server either stopped by itself or got aborted.
+        let _ = handle.await;
+        info!("{} stopped.", name);
+    }
+}
+
+async fn wait_until_ready(health_client: &dyn ComponentHealthClient) {
+    while !matches!(health_client.is_ready().await, Ok(true)) {
+        tokio::time::sleep(READINESS_POLLING_INTERVAL).await;
+    }
+}
+
+/// Resolves once the node is interrupted or asked to terminate.
+async fn shutdown_signal() {
+    let mut terminate =
+        signal(SignalKind::terminate()).expect("Failed to listen to the terminate signal.");
+    tokio::select! {
+        _ = ctrl_c() => {}
+        _ = terminate.recv() => {}
+    }
+}
+
+pub fn get_server_future(
+    name: &str,
+    execute_flag: bool,
+    server: Option<Box<impl ComponentServerStarter + ?Sized + 'static>>,
+) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+    let server_future = match execute_flag {
+        true => {
+            let mut server = match server {
+                Some(server) => server,
+                _ => panic!("{} component is not initialized.", name),
+            };
+            async move { server.start().await }.boxed()
+        }
+        false => pending().boxed(),
+    };
+    server_future
+}
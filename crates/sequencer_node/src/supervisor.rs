@@ -0,0 +1,161 @@
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::panic::{resume_unwind, AssertUnwindSafe};
+use std::time::Duration;
+
+use futures::FutureExt;
+use papyrus_config::converters::{
+    deserialize_milliseconds_to_duration,
+    deserialize_seconds_to_duration,
+};
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+use validator::Validate;
+
+use crate::servers::ComponentServer;
+
+#[cfg(test)]
+#[path = "supervisor_test.rs"]
+mod supervisor_test;
+
+/// The restart policy of the component servers. A server that stops, e.g., on a panic in its
+/// request loop, is restarted after a backoff, unless it was restarted too often lately, in which
+/// case the node stops.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct SupervisorConfig {
+    pub enable: bool,
+    pub max_restarts: usize,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub restart_window: Duration,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub initial_backoff: Duration,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub max_backoff: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            max_restarts: 3,
+            restart_window: Duration::from_secs(300),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl SerializeConfig for SupervisorConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable",
+                &self.enable,
+                "If true, a component server that stops or panics is restarted, instead of \
+                 stopping the node.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_restarts",
+                &self.max_restarts,
+                "The maximal number of restarts of a component server within the restart window; \
+                 a server stopping once more stops the node.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "restart_window",
+                &self.restart_window.as_secs(),
+                "The time in seconds over which the restarts of a component server are counted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "initial_backoff",
+                &self.initial_backoff.as_millis(),
+                "The time in milliseconds before the first restart of a component server; the \
+                 backoff doubles with each further restart within the restart window.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_backoff",
+                &self.max_backoff.as_millis(),
+                "The maximal time in milliseconds before a restart of a component server.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Counts the restarts of a server against the restart policy.
+pub(crate) struct RestartPolicy {
+    config: SupervisorConfig,
+    // The times of the restarts within the restart window, oldest first.
+    restarts: VecDeque<Instant>,
+}
+
+impl RestartPolicy {
+    pub(crate) fn new(config: SupervisorConfig) -> Self {
+        Self { config, restarts: VecDeque::new() }
+    }
+
+    /// Records that the server stopped at the given time. Returns the backoff to restart it
+    /// after, or `None` if it may not be restarted.
+    pub(crate) fn on_stop(&mut self, now: Instant) -> Option<Duration> {
+        if !self.config.enable {
+            return None;
+        }
+        while self
+            .restarts
+            .front()
+            .is_some_and(|restart| now.duration_since(*restart) >= self.config.restart_window)
+        {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= self.config.max_restarts {
+            return None;
+        }
+        let n_recent_restarts = u32::try_from(self.restarts.len()).unwrap_or(u32::MAX);
+        let backoff = self
+            .config
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(n_recent_restarts))
+            .min(self.config.max_backoff);
+        self.restarts.push_back(now);
+        Some(backoff)
+    }
+}
+
+/// Runs the server, restarting it by the restart policy whenever it stops or panics. A restarted
+/// local server reads the same channel as before, so the clients of its component reach it again
+/// without being recreated; requests in flight when it stopped fail.
+///
+/// Returns once the server may not be restarted, resuming its panic if it panicked, so that the
+/// node stops as it would without supervision.
+pub async fn supervise(name: &'static str, mut server: ComponentServer, config: SupervisorConfig) {
+    let mut restart_policy = RestartPolicy::new(config);
+    loop {
+        let result = AssertUnwindSafe(server.start()).catch_unwind().await;
+        if let Err(panic) = &result {
+            error!("{} panicked: {}", name, panic_message(panic.as_ref()));
+        }
+        let Some(backoff) = restart_policy.on_stop(Instant::now()) else {
+            if let Err(panic) = result {
+                resume_unwind(panic);
+            }
+            return;
+        };
+        warn!("{} stopped, restarting it in {:?}.", name, backoff);
+        tokio::time::sleep(backoff).await;
+        info!("Restarting {}.", name);
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use starknet_mempool_infra::component_server::ComponentServerStarter;
+use tokio::time::Instant;
+
+use crate::supervisor::{supervise, RestartPolicy, SupervisorConfig};
+
+fn config(max_restarts: usize) -> SupervisorConfig {
+    SupervisorConfig {
+        enable: true,
+        max_restarts,
+        restart_window: Duration::from_secs(60),
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(4),
+    }
+}
+
+// A server that panics on its first starts, and then returns.
+struct PanickingServer {
+    n_panics: usize,
+    n_starts: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ComponentServerStarter for PanickingServer {
+    async fn start(&mut self) {
+        let n_starts = self.n_starts.fetch_add(1, Ordering::SeqCst) + 1;
+        if n_starts <= self.n_panics {
+            panic!("Server panicked on start {n_starts}.");
+        }
+    }
+}
+
+#[test]
+fn backoff_doubles_up_to_its_max() {
+    let mut restart_policy = RestartPolicy::new(config(4));
+    let now = Instant::now();
+
+    let backoffs: Vec<_> = (0..4).map(|_| restart_policy.on_stop(now)).collect();
+
+    assert_eq!(backoffs, [1, 2, 4, 4].map(|millis| Some(Duration::from_millis(millis))).to_vec());
+}
+
+#[test]
+fn restarts_are_limited_within_the_window() {
+    let config = config(2);
+    let restart_window = config.restart_window;
+    let mut restart_policy = RestartPolicy::new(config);
+    let now = Instant::now();
+
+    assert!(restart_policy.on_stop(now).is_some());
+    assert!(restart_policy.on_stop(now).is_some());
+    assert_eq!(restart_policy.on_stop(now), None);
+    // Restarts out of the window no longer count.
+    assert_eq!(restart_policy.on_stop(now + restart_window), Some(Duration::from_millis(1)));
+}
+
+#[test]
+fn servers_are_not_restarted_when_disabled() {
+    let mut restart_policy = RestartPolicy::new(SupervisorConfig::default());
+    assert_eq!(restart_policy.on_stop(Instant::now()), None);
+}
+
+#[tokio::test]
+async fn panicking_server_is_restarted() {
+    let n_starts = Arc::new(AtomicUsize::new(0));
+    let server = Box::new(PanickingServer { n_panics: 2, n_starts: n_starts.clone() });
+
+    supervise("Test Server", server, config(2)).await;
+
+    assert_eq!(n_starts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn server_panicking_past_the_restart_limit_stops() {
+    let n_starts = Arc::new(AtomicUsize::new(0));
+    let server = Box::new(PanickingServer { n_panics: 3, n_starts: n_starts.clone() });
+
+    let result = tokio::spawn(supervise("Test Server", server, config(2))).await;
+
+    assert!(result.unwrap_err().is_panic());
+    assert_eq!(n_starts.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,15 @@
+use crate::communication::{create_node_channels, create_node_clients, SequencerNodeClients};
+use crate::components::create_components;
+use crate::config::SequencerNodeConfig;
+use crate::servers::{create_servers, Servers};
+
+pub fn create_clients_servers_from_config(
+    config: &SequencerNodeConfig,
+) -> (SequencerNodeClients, Servers) {
+    let mut channels = create_node_channels(config);
+    let clients = create_node_clients(config, &mut channels);
+    let components = create_components(config, &clients, &mut channels);
+    let servers = create_servers(config, &mut channels, &clients, components);
+
+    (clients, servers)
+}
@@ -8,6 +8,8 @@ pub mod manager;
 #[allow(missing_docs)]
 pub mod papyrus_consensus_context;
 #[allow(missing_docs)]
+pub mod sequencer_consensus_context;
+#[allow(missing_docs)]
 pub mod simulation_network_receiver;
 #[allow(missing_docs)]
 pub mod single_height_consensus;
@@ -224,6 +224,8 @@ impl ConsensusContext for PapyrusConsensusContext {
                     proposer: init.proposer,
                     transactions,
                     block_hash,
+                    // Papyrus doesn't execute the proposals it builds.
+                    environment_fingerprint: None,
                 };
                 debug!(
                     "Sending proposal: height={:?} id={:?} num_txs={} block_hash={:?}",
@@ -97,6 +97,7 @@ async fn propose() {
         proposer: proposal_init.proposer,
         transactions: block.body.transactions,
         block_hash: block.header.block_hash,
+        environment_fingerprint: None,
     });
 
     assert_eq!(mock_network.messages_to_broadcast_receiver.next().await.unwrap(), expected_message);
@@ -0,0 +1,425 @@
+//! Implementation of the [`ConsensusContext`] of a sequencer, which builds and validates the
+//! proposals with the batcher.
+
+#[cfg(test)]
+#[path = "sequencer_consensus_context_test.rs"]
+mod sequencer_consensus_context_test;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::channel::{mpsc, oneshot};
+use futures::sink::SinkExt;
+use futures::StreamExt;
+use papyrus_network::network_manager::BroadcastTopicSender;
+use papyrus_protobuf::consensus::{
+    ConsensusMessage,
+    EnvironmentFingerprint as ProtobufEnvironmentFingerprint,
+    Proposal,
+    StreamMessage,
+    Vote,
+};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
+use starknet_api::transaction::Transaction;
+use starknet_batcher_types::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    EnvironmentFingerprint,
+    GetProposalContentInput,
+    ProposalCommitment,
+    ProposalContent,
+    ProposalId,
+    ProposalStatus,
+    StartHeightInput,
+    ValidateProposalInput,
+};
+use starknet_batcher_types::communication::SharedBatcherClient;
+use starknet_batcher_types::deadline::Deadline;
+use starknet_class_manager_types::transaction_converter::{
+    executable_tx_to_consensus_tx,
+    TransactionConverter,
+};
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use tracing::{debug, debug_span, info, warn, Instrument};
+
+use crate::types::{
+    ConsensusBlock,
+    ConsensusContext,
+    ConsensusError,
+    ProposalInit,
+    Round,
+    ValidatorId,
+};
+
+const CHANNEL_SIZE: usize = 5000;
+
+// TODO(Matan): Pass the consensus round to the batcher once the context is given the round of
+// the proposals it builds and validates.
+const PROPOSAL_ROUND: Round = 0;
+
+/// A page of the transactions of a proposal, along with the fingerprint of the environment the
+/// proposal was generated in, which its validators compare with their own.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SequencerProposalChunk {
+    pub transactions: Vec<ExecutableTransaction>,
+    pub environment_fingerprint: Option<EnvironmentFingerprint>,
+}
+
+/// A block built by the batcher, out of a proposal generated by this node or received from a
+/// peer.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SequencerConsensusBlock {
+    // The pages of transactions of the proposal, in the order they are streamed.
+    content: Vec<SequencerProposalChunk>,
+    id: BlockHash,
+    // The id the batcher holds the proposal under, to commit it once consensus decides on it.
+    proposal_id: ProposalId,
+}
+
+impl ConsensusBlock for SequencerConsensusBlock {
+    type ProposalChunk = SequencerProposalChunk;
+    type ProposalIter = std::vec::IntoIter<SequencerProposalChunk>;
+
+    fn id(&self) -> BlockHash {
+        self.id
+    }
+
+    // Reproposing a block streams the content the batcher already built, without executing it
+    // again.
+    fn proposal_iter(&self) -> Self::ProposalIter {
+        self.content.clone().into_iter()
+    }
+}
+
+pub struct SequencerConsensusContext {
+    batcher: SharedBatcherClient,
+    validators: Vec<ValidatorId>,
+    // The time the batcher is given to build or validate a proposal.
+    proposal_timeout: Duration,
+    // Proposal ids are never reused, as the batcher may still hold the proposals of earlier
+    // heights.
+    next_proposal_id: AtomicU64,
+    next_stream_id: AtomicU64,
+    network_broadcast_sender: BroadcastTopicSender<ConsensusMessage>,
+    // The content of the proposals of this node is streamed to the peers part by part, as the
+    // batcher builds it.
+    proposal_stream_sender: BroadcastTopicSender<StreamMessage<ConsensusMessage>>,
+    transaction_converter: TransactionConverter,
+}
+
+impl SequencerConsensusContext {
+    pub fn new(
+        batcher: SharedBatcherClient,
+        network_broadcast_sender: BroadcastTopicSender<ConsensusMessage>,
+        proposal_stream_sender: BroadcastTopicSender<StreamMessage<ConsensusMessage>>,
+        num_validators: u64,
+        proposal_timeout: Duration,
+        transaction_converter: TransactionConverter,
+    ) -> Self {
+        Self {
+            batcher,
+            validators: (0..num_validators).map(ValidatorId::from).collect(),
+            proposal_timeout,
+            next_proposal_id: AtomicU64::new(0),
+            next_stream_id: AtomicU64::new(0),
+            network_broadcast_sender,
+            proposal_stream_sender,
+            transaction_converter,
+        }
+    }
+
+    /// Converts a proposal part streamed by a peer to the content the batcher validates,
+    /// computing the hashes of its transactions and reading the classes of the declared ones.
+    // TODO(Matan): Convert the proposal parts once the manager reassembles the proposal streams.
+    pub async fn proposal_chunk(
+        &self,
+        init: &ProposalInit,
+        proposal_part: Proposal,
+    ) -> Result<SequencerProposalChunk, ConsensusError> {
+        let mut transactions = Vec::with_capacity(proposal_part.transactions.len());
+        for tx in proposal_part.transactions {
+            let executable_tx =
+                self.transaction_converter.consensus_tx_to_executable_tx(tx).await.map_err(
+                    |err| {
+                        ConsensusError::InvalidProposal(init.proposer, init.height, err.to_string())
+                    },
+                )?;
+            transactions.push(executable_tx);
+        }
+        let environment_fingerprint = proposal_part.environment_fingerprint.map(
+            |ProtobufEnvironmentFingerprint { seed, digest }| EnvironmentFingerprint {
+                seed,
+                digest,
+            },
+        );
+        Ok(SequencerProposalChunk { transactions, environment_fingerprint })
+    }
+
+    fn next_proposal_id(&self) -> ProposalId {
+        self.next_proposal_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl ConsensusContext for SequencerConsensusContext {
+    type Block = SequencerConsensusBlock;
+
+    async fn build_proposal(
+        &self,
+        height: BlockNumber,
+    ) -> (mpsc::Receiver<SequencerProposalChunk>, oneshot::Receiver<SequencerConsensusBlock>) {
+        let (content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+        let (fin_sender, fin_receiver) = oneshot::channel();
+
+        let input = BuildProposalInput {
+            proposal_id: self.next_proposal_id(),
+            deadline: Deadline::after(self.proposal_timeout),
+            height,
+            round: PROPOSAL_ROUND,
+            // The batcher chooses the timestamp until consensus agrees on one.
+            timestamp: None,
+        };
+        let batcher = self.batcher.clone();
+        tokio::spawn(
+            async move {
+                let proposal_id = input.proposal_id;
+                // The senders are dropped on failure, which consensus treats as a failed build.
+                match stream_built_proposal(batcher, input, content_sender).await {
+                    Ok(block) => {
+                        debug!("Built proposal {proposal_id} into block {:?}.", block.id);
+                        // This can happen as a result of sync interrupting `run_height`.
+                        fin_sender.send(block).unwrap_or_else(|_| {
+                            warn!("Failed to send block to consensus. height={height}");
+                        })
+                    }
+                    Err(err) => warn!("Failed to build proposal {proposal_id}: {err}"),
+                }
+            }
+            .instrument(debug_span!("consensus_build_proposal")),
+        );
+
+        (content_receiver, fin_receiver)
+    }
+
+    async fn validate_proposal(
+        &self,
+        height: BlockNumber,
+        mut content: mpsc::Receiver<SequencerProposalChunk>,
+    ) -> oneshot::Receiver<SequencerConsensusBlock> {
+        let (fin_sender, fin_receiver) = oneshot::channel();
+
+        let proposal_id = self.next_proposal_id();
+        let proposal_timeout = self.proposal_timeout;
+        let batcher = self.batcher.clone();
+        tokio::spawn(
+            async move {
+                // The batcher validates the whole content at once, so it is received first.
+                let mut pages = Vec::new();
+                while let Some(page) = content.next().await {
+                    pages.push(page);
+                }
+                // Every page carries the fingerprint of the proposer, if it is known.
+                let environment_fingerprint =
+                    pages.first().and_then(|page| page.environment_fingerprint);
+                let input = ValidateProposalInput {
+                    proposal_id,
+                    deadline: Deadline::after(proposal_timeout),
+                    height,
+                    round: PROPOSAL_ROUND,
+                    txs: pages.iter().flat_map(|page| page.transactions.clone()).collect(),
+                    environment_fingerprint,
+                    // The block info isn't sent along with the proposal yet.
+                    block_info: None,
+                };
+                let commitment = match validate_with_batcher(batcher, input).await {
+                    Ok(commitment) => commitment,
+                    Err(err) => {
+                        warn!("Proposal {proposal_id} of height {height} is invalid: {err}");
+                        return;
+                    }
+                };
+                let block = SequencerConsensusBlock {
+                    content: pages,
+                    id: block_id(&commitment),
+                    proposal_id,
+                };
+                // This can happen as a result of sync interrupting `run_height`.
+                fin_sender.send(block).unwrap_or_else(|_| {
+                    warn!("Failed to send block to consensus. height={height}");
+                })
+            }
+            .instrument(debug_span!("consensus_validate_proposal")),
+        );
+
+        fin_receiver
+    }
+
+    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
+        self.validators.clone()
+    }
+
+    fn proposer(&self, _height: BlockNumber, _round: Round) -> ValidatorId {
+        *self.validators.first().expect("validators should have at least 2 validators")
+    }
+
+    async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError> {
+        debug!("Broadcasting message: {message:?}");
+        self.network_broadcast_sender.send(message).await?;
+        Ok(())
+    }
+
+    // TODO(Matan): Validate the proposals streamed by peers once the manager reassembles the
+    // streams.
+    async fn propose(
+        &self,
+        init: ProposalInit,
+        mut content_receiver: mpsc::Receiver<SequencerProposalChunk>,
+        fin_receiver: oneshot::Receiver<BlockHash>,
+    ) -> Result<(), ConsensusError> {
+        let mut proposal_stream_sender = self.proposal_stream_sender.clone();
+        let stream_id = self.next_stream_id.fetch_add(1, Ordering::Relaxed);
+
+        tokio::spawn(
+            async move {
+                let proposal_part =
+                    |transactions: Vec<Transaction>,
+                     block_hash: BlockHash,
+                     environment_fingerprint: Option<EnvironmentFingerprint>| {
+                        ConsensusMessage::Proposal(Proposal {
+                            height: init.height.0,
+                            round: init.round,
+                            proposer: init.proposer,
+                            transactions,
+                            block_hash,
+                            environment_fingerprint: environment_fingerprint.map(
+                                |EnvironmentFingerprint { seed, digest }| {
+                                    ProtobufEnvironmentFingerprint { seed, digest }
+                                },
+                            ),
+                        })
+                    };
+                let mut chunk_id = 0;
+                while let Some(page) = content_receiver.next().await {
+                    let transactions =
+                        page.transactions.into_iter().map(executable_tx_to_consensus_tx).collect();
+                    let message = StreamMessage {
+                        message: proposal_part(
+                            transactions,
+                            BlockHash::default(),
+                            page.environment_fingerprint,
+                        ),
+                        stream_id,
+                        chunk_id,
+                        fin: false,
+                    };
+                    proposal_stream_sender.send(message).await.expect("Failed to send proposal");
+                    chunk_id += 1;
+                }
+
+                let Ok(block_hash) = fin_receiver.await else {
+                    // This can occur due to sync interrupting a height.
+                    warn!("Failed to get block hash from fin receiver. {init:?}");
+                    return;
+                };
+                debug!(
+                    "Sending the fin of proposal stream {stream_id}: height={} round={} \
+                     block_hash={block_hash:?}",
+                    init.height, init.round
+                );
+                let message = StreamMessage {
+                    message: proposal_part(Vec::new(), block_hash, None),
+                    stream_id,
+                    chunk_id,
+                    fin: true,
+                };
+                proposal_stream_sender.send(message).await.expect("Failed to send proposal");
+            }
+            .instrument(debug_span!("consensus_propose")),
+        );
+        Ok(())
+    }
+
+    async fn decision_reached(
+        &mut self,
+        block: Self::Block,
+        precommits: Vec<Vote>,
+    ) -> Result<(), ConsensusError> {
+        let height = precommits[0].height;
+        info!(
+            "Finished consensus for height: {height}. Agreed on block with id: {:x}",
+            block.id().0
+        );
+        self.batcher
+            .decision_reached(DecisionReachedInput { proposal_id: block.proposal_id })
+            .await
+            .map_err(|err| ConsensusError::BatcherError(err.to_string()))
+    }
+}
+
+// Builds a proposal with the batcher, streaming its transactions page by page as they are built.
+async fn stream_built_proposal(
+    batcher: SharedBatcherClient,
+    input: BuildProposalInput,
+    mut content_sender: mpsc::Sender<SequencerProposalChunk>,
+) -> Result<SequencerConsensusBlock, String> {
+    let proposal_id = input.proposal_id;
+    batcher
+        .start_height(StartHeightInput { height: input.height })
+        .await
+        .map_err(|err| err.to_string())?;
+    let environment_fingerprint =
+        batcher.build_proposal(input).await.map_err(|err| err.to_string())?.environment_fingerprint;
+
+    let mut content = Vec::new();
+    loop {
+        let page = content.len();
+        let page_content = batcher
+            .get_proposal_content(GetProposalContentInput { proposal_id, page })
+            .await
+            .map_err(|err| err.to_string())?
+            .content;
+        match page_content {
+            ProposalContent::Txs(transactions) => {
+                let chunk = SequencerProposalChunk {
+                    transactions,
+                    environment_fingerprint: Some(environment_fingerprint),
+                };
+                content_sender.send(chunk.clone()).await.map_err(|err| err.to_string())?;
+                content.push(chunk);
+            }
+            ProposalContent::Finished { commitment } => {
+                return Ok(SequencerConsensusBlock {
+                    content,
+                    id: block_id(&commitment),
+                    proposal_id,
+                });
+            }
+        }
+    }
+}
+
+// Returns the commitment of the block the batcher built out of the proposal, if it is valid.
+async fn validate_with_batcher(
+    batcher: SharedBatcherClient,
+    input: ValidateProposalInput,
+) -> Result<ProposalCommitment, String> {
+    batcher
+        .start_height(StartHeightInput { height: input.height })
+        .await
+        .map_err(|err| err.to_string())?;
+    match batcher.validate_proposal(input).await.map_err(|err| err.to_string())? {
+        ProposalStatus::Valid { commitment } => Ok(commitment),
+        status => Err(format!("{status:?}")),
+    }
+}
+
+// The id consensus votes on, which the proposer and the validators of a proposal agree on if they
+// built the same block.
+fn block_id(commitment: &ProposalCommitment) -> BlockHash {
+    BlockHash(Poseidon::hash(
+        &commitment.state_diff_commitment.0 .0,
+        &commitment.transaction_commitment.0,
+    ))
+}
@@ -0,0 +1,291 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::{mpsc, oneshot};
+use futures::StreamExt;
+use papyrus_network::network_manager::test_utils::{
+    mock_register_broadcast_topic,
+    BroadcastNetworkMock,
+};
+use papyrus_protobuf::consensus::{
+    ConsensusMessage,
+    EnvironmentFingerprint as ProtobufEnvironmentFingerprint,
+    Proposal,
+    StreamMessage,
+    Vote,
+};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ChainId, ContractAddress, StateDiffCommitment, TransactionCommitment};
+use starknet_api::executable_transaction::{
+    InvokeTransaction,
+    Transaction as ExecutableTransaction,
+};
+use starknet_api::felt;
+use starknet_api::hash::PoseidonHash;
+use starknet_api::test_utils::invoke::{invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::{Transaction, TransactionHash};
+use starknet_batcher_types::batcher_types::{
+    BuildProposalReturnValue,
+    EnvironmentFingerprint,
+    GetProposalContentReturnValue,
+    ProposalCommitment,
+    ProposalContent,
+    ProposalStatus,
+    ProposalValidationError,
+};
+use starknet_batcher_types::communication::MockBatcherClient;
+use starknet_batcher_types::errors::BatcherError;
+use starknet_class_manager_types::communication::MockClassManagerClient;
+use starknet_class_manager_types::transaction_converter::{
+    executable_tx_to_consensus_tx,
+    TransactionConverter,
+};
+
+use crate::sequencer_consensus_context::{
+    block_id,
+    SequencerConsensusBlock,
+    SequencerConsensusContext,
+    SequencerProposalChunk,
+};
+use crate::types::{ConsensusBlock, ConsensusContext, ProposalInit};
+
+const HEIGHT: BlockNumber = BlockNumber(3);
+
+fn txs(tx_hashes: &[u8]) -> Vec<ExecutableTransaction> {
+    tx_hashes
+        .iter()
+        .map(|&tx_hash| {
+            ExecutableTransaction::Invoke(InvokeTransaction {
+                tx: invoke_tx(InvokeTxArgs::default()),
+                tx_hash: TransactionHash(felt!(tx_hash)),
+            })
+        })
+        .collect()
+}
+
+const ENVIRONMENT_FINGERPRINT: EnvironmentFingerprint =
+    EnvironmentFingerprint { seed: 5, digest: [1; 32] };
+
+fn chunk(tx_hashes: &[u8]) -> SequencerProposalChunk {
+    SequencerProposalChunk {
+        transactions: txs(tx_hashes),
+        environment_fingerprint: Some(ENVIRONMENT_FINGERPRINT),
+    }
+}
+
+fn commitment() -> ProposalCommitment {
+    ProposalCommitment {
+        state_diff_commitment: StateDiffCommitment(PoseidonHash(felt!(1_u8))),
+        transaction_commitment: TransactionCommitment(felt!(2_u8)),
+    }
+}
+
+fn sequencer_context(
+    batcher: MockBatcherClient,
+) -> (SequencerConsensusContext, BroadcastNetworkMock<StreamMessage<ConsensusMessage>>) {
+    let network_channels = mock_register_broadcast_topic().unwrap();
+    let stream_channels = mock_register_broadcast_topic().unwrap();
+    let context = SequencerConsensusContext::new(
+        Arc::new(batcher),
+        network_channels.subscriber_channels.messages_to_broadcast_sender,
+        stream_channels.subscriber_channels.messages_to_broadcast_sender,
+        4,
+        Duration::from_secs(1),
+        TransactionConverter::new(
+            Arc::new(MockClassManagerClient::new()),
+            ChainId::create_for_testing(),
+        ),
+    );
+    (context, stream_channels.mock_network)
+}
+
+#[tokio::test]
+async fn build_proposal_streams_the_batcher_pages() {
+    let mut batcher = MockBatcherClient::new();
+    batcher
+        .expect_start_height()
+        .withf(|input| input.height == HEIGHT)
+        .times(1)
+        .returning(|_| Ok(()));
+    batcher
+        .expect_build_proposal()
+        .withf(|input| input.proposal_id == 0 && input.height == HEIGHT)
+        .times(1)
+        .returning(|_| {
+            Ok(BuildProposalReturnValue {
+                environment_fingerprint: ENVIRONMENT_FINGERPRINT,
+                block_info: Default::default(),
+            })
+        });
+    batcher.expect_get_proposal_content().times(3).returning(|input| {
+        let content = match input.page {
+            0 => ProposalContent::Txs(txs(&[1, 2])),
+            1 => ProposalContent::Txs(txs(&[3])),
+            _ => ProposalContent::Finished { commitment: commitment() },
+        };
+        Ok(GetProposalContentReturnValue { content })
+    });
+    let (context, _stream_network) = sequencer_context(batcher);
+
+    let (content_receiver, fin_receiver) = context.build_proposal(HEIGHT).await;
+
+    let content: Vec<_> = content_receiver.collect().await;
+    assert_eq!(content, vec![chunk(&[1, 2]), chunk(&[3])]);
+    let block = fin_receiver.await.unwrap();
+    assert_eq!(block.id(), block_id(&commitment()));
+    assert_eq!(block.proposal_iter().collect::<Vec<_>>(), content);
+}
+
+#[tokio::test]
+async fn build_proposal_fails_with_the_batcher() {
+    let mut batcher = MockBatcherClient::new();
+    batcher.expect_start_height().returning(|_| Ok(()));
+    batcher.expect_build_proposal().returning(|_| {
+        Ok(BuildProposalReturnValue {
+            environment_fingerprint: Default::default(),
+            block_info: Default::default(),
+        })
+    });
+    batcher.expect_get_proposal_content().returning(|input| {
+        Err(BatcherError::ProposalFailed { proposal_id: input.proposal_id }.into())
+    });
+    let (context, _stream_network) = sequencer_context(batcher);
+
+    let (_content_receiver, fin_receiver) = context.build_proposal(HEIGHT).await;
+
+    assert_eq!(fin_receiver.await, Err(oneshot::Canceled));
+}
+
+#[tokio::test]
+async fn validate_proposal_success() {
+    let mut batcher = MockBatcherClient::new();
+    batcher.expect_start_height().times(1).returning(|_| Ok(()));
+    batcher
+        .expect_validate_proposal()
+        .withf(|input| {
+            input.height == HEIGHT
+                && input.txs == txs(&[1, 2, 3])
+                && input.environment_fingerprint == Some(ENVIRONMENT_FINGERPRINT)
+        })
+        .times(1)
+        .returning(|_| Ok(ProposalStatus::Valid { commitment: commitment() }));
+    let (context, _stream_network) = sequencer_context(batcher);
+
+    let (mut content_sender, content_receiver) = mpsc::channel(2);
+    content_sender.try_send(chunk(&[1, 2])).unwrap();
+    content_sender.try_send(chunk(&[3])).unwrap();
+    content_sender.close_channel();
+    let block = context.validate_proposal(HEIGHT, content_receiver).await.await.unwrap();
+
+    assert_eq!(block.id(), block_id(&commitment()));
+    assert_eq!(block.proposal_iter().collect::<Vec<_>>(), vec![chunk(&[1, 2]), chunk(&[3])]);
+}
+
+#[tokio::test]
+async fn validate_proposal_fail() {
+    let mut batcher = MockBatcherClient::new();
+    batcher.expect_start_height().returning(|_| Ok(()));
+    batcher.expect_validate_proposal().returning(|_| {
+        Ok(ProposalStatus::ContentMismatch {
+            n_proposed_txs: 1,
+            n_executed_txs: 0,
+            error: ProposalValidationError::Incomplete { n_received_txs: 0 },
+        })
+    });
+    let (context, _stream_network) = sequencer_context(batcher);
+
+    let (mut content_sender, content_receiver) = mpsc::channel(1);
+    content_sender.try_send(chunk(&[1])).unwrap();
+    content_sender.close_channel();
+    let fin = context.validate_proposal(HEIGHT, content_receiver).await.await;
+
+    assert_eq!(fin, Err(oneshot::Canceled));
+}
+
+#[tokio::test]
+async fn propose_streams_the_proposal_parts() {
+    let (context, mut stream_network) = sequencer_context(MockBatcherClient::new());
+    let (mut content_sender, content_receiver) = mpsc::channel(2);
+    content_sender.try_send(chunk(&[1, 2])).unwrap();
+    content_sender.try_send(chunk(&[3])).unwrap();
+    content_sender.close_channel();
+    let (fin_sender, fin_receiver) = oneshot::channel();
+    let block_hash = BlockHash(felt!(7_u8));
+    fin_sender.send(block_hash).unwrap();
+    let init = ProposalInit { height: HEIGHT, round: 1, proposer: ContractAddress::default() };
+
+    context.propose(init.clone(), content_receiver, fin_receiver).await.unwrap();
+
+    let proposal_part =
+        |transactions: Vec<Transaction>,
+         block_hash: BlockHash,
+         environment_fingerprint: Option<ProtobufEnvironmentFingerprint>| {
+            ConsensusMessage::Proposal(Proposal {
+                height: init.height.0,
+                round: init.round,
+                proposer: init.proposer,
+                transactions,
+                block_hash,
+                environment_fingerprint,
+            })
+        };
+    let fingerprint = Some(ProtobufEnvironmentFingerprint { seed: 5, digest: [1; 32] });
+    let expected_parts = [
+        (txs(&[1, 2]), BlockHash::default(), fingerprint, false),
+        (txs(&[3]), BlockHash::default(), fingerprint, false),
+        (vec![], block_hash, None, true),
+    ];
+    for (chunk_id, (part_txs, part_block_hash, part_fingerprint, fin)) in (0..).zip(expected_parts)
+    {
+        let transactions = part_txs.into_iter().map(executable_tx_to_consensus_tx).collect();
+        assert_eq!(
+            stream_network.messages_to_broadcast_receiver.next().await.unwrap(),
+            StreamMessage {
+                message: proposal_part(transactions, part_block_hash, part_fingerprint),
+                stream_id: 0,
+                chunk_id,
+                fin,
+            }
+        );
+    }
+}
+
+#[tokio::test]
+async fn proposal_chunk_computes_the_transaction_hashes() {
+    let (context, _stream_network) = sequencer_context(MockBatcherClient::new());
+    let init = ProposalInit { height: HEIGHT, round: 0, proposer: ContractAddress::default() };
+    let tx = invoke_tx(InvokeTxArgs::default());
+    let expected_tx = ExecutableTransaction::Invoke(
+        InvokeTransaction::create(tx.clone(), &ChainId::create_for_testing()).unwrap(),
+    );
+
+    let proposal_part = Proposal {
+        transactions: vec![Transaction::Invoke(tx)],
+        environment_fingerprint: Some(ProtobufEnvironmentFingerprint { seed: 5, digest: [1; 32] }),
+        ..Default::default()
+    };
+
+    let chunk = context.proposal_chunk(&init, proposal_part).await.unwrap();
+
+    assert_eq!(
+        chunk,
+        SequencerProposalChunk {
+            transactions: vec![expected_tx],
+            environment_fingerprint: Some(ENVIRONMENT_FINGERPRINT),
+        }
+    );
+}
+
+#[tokio::test]
+async fn decision_commits_the_proposal() {
+    let mut batcher = MockBatcherClient::new();
+    batcher
+        .expect_decision_reached()
+        .withf(|input| input.proposal_id == 4)
+        .times(1)
+        .returning(|_| Ok(()));
+    let (mut context, _stream_network) = sequencer_context(batcher);
+    let block = SequencerConsensusBlock { proposal_id: 4, ..Default::default() };
+
+    context.decision_reached(block, vec![Vote::default()]).await.unwrap();
+}
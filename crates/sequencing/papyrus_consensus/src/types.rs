@@ -190,4 +190,7 @@ pub enum ConsensusError {
     InternalNetworkError(String),
     #[error("{0}")]
     SyncError(String),
+    // Indicates a failure of the batcher to handle a block consensus decided on.
+    #[error("{0}")]
+    BatcherError(String),
 }
@@ -18,6 +18,15 @@ pub struct TransactionLeafElement {
     pub(crate) transaction_signature: Option<TransactionSignature>,
 }
 
+impl TransactionLeafElement {
+    pub fn new(
+        transaction_hash: TransactionHash,
+        transaction_signature: Option<TransactionSignature>,
+    ) -> Self {
+        Self { transaction_hash, transaction_signature }
+    }
+}
+
 impl From<&TransactionHashingData> for TransactionLeafElement {
     fn from(transaction_data: &TransactionHashingData) -> Self {
         Self {
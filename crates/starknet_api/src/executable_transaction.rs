@@ -15,6 +15,7 @@ use crate::transaction::{
     AccountDeploymentData,
     Calldata,
     ContractAddressSalt,
+    Fee,
     PaymasterData,
     Tip,
     TransactionHash,
@@ -47,6 +48,7 @@ pub enum Transaction {
     Declare(DeclareTransaction),
     DeployAccount(DeployAccountTransaction),
     Invoke(InvokeTransaction),
+    L1Handler(L1HandlerTransaction),
 }
 
 impl Transaction {
@@ -55,6 +57,7 @@ impl Transaction {
             Transaction::Declare(tx_data) => tx_data.tx.sender_address(),
             Transaction::DeployAccount(tx_data) => tx_data.contract_address,
             Transaction::Invoke(tx_data) => tx_data.tx.sender_address(),
+            Transaction::L1Handler(tx_data) => tx_data.tx.contract_address,
         }
     }
 
@@ -63,6 +66,7 @@ impl Transaction {
             Transaction::Declare(tx_data) => tx_data.tx.nonce(),
             Transaction::DeployAccount(tx_data) => tx_data.tx.nonce(),
             Transaction::Invoke(tx_data) => tx_data.tx.nonce(),
+            Transaction::L1Handler(tx_data) => tx_data.tx.nonce,
         }
     }
 
@@ -71,6 +75,17 @@ impl Transaction {
             Transaction::Declare(tx_data) => tx_data.tx_hash,
             Transaction::DeployAccount(tx_data) => tx_data.tx_hash,
             Transaction::Invoke(tx_data) => tx_data.tx_hash,
+            Transaction::L1Handler(tx_data) => tx_data.tx_hash,
+        }
+    }
+
+    /// The signature of the transaction; L1 handler transactions are not signed.
+    pub fn signature(&self) -> Option<TransactionSignature> {
+        match self {
+            Transaction::Declare(tx_data) => Some(tx_data.tx.signature()),
+            Transaction::DeployAccount(tx_data) => Some(tx_data.tx.signature()),
+            Transaction::Invoke(tx_data) => Some(tx_data.signature()),
+            Transaction::L1Handler(_) => None,
         }
     }
 
@@ -89,6 +104,7 @@ impl Transaction {
                 crate::transaction::InvokeTransaction::V3(tx_v3) => Some(tx_v3.tip),
                 _ => None,
             },
+            Transaction::L1Handler(_) => None,
         }
     }
 
@@ -108,6 +124,7 @@ impl Transaction {
                 crate::transaction::InvokeTransaction::V3(tx_v3) => Some(&tx_v3.resource_bounds),
                 _ => None,
             },
+            Transaction::L1Handler(_) => None,
         }
     }
 
@@ -257,3 +274,22 @@ impl InvokeTransaction {
         Self::create(invoke_tx, chain_id)
     }
 }
+
+/// A transaction sent by an L1 contract to an L2 contract, with the fee paid for it on L1.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct L1HandlerTransaction {
+    pub tx: crate::transaction::L1HandlerTransaction,
+    pub tx_hash: TransactionHash,
+    pub paid_fee_on_l1: Fee,
+}
+
+impl L1HandlerTransaction {
+    pub fn create(
+        l1_handler_tx: crate::transaction::L1HandlerTransaction,
+        chain_id: &ChainId,
+        paid_fee_on_l1: Fee,
+    ) -> Result<Self, StarknetApiError> {
+        let tx_hash = l1_handler_tx.calculate_transaction_hash(chain_id, &l1_handler_tx.version)?;
+        Ok(Self { tx: l1_handler_tx, tx_hash, paid_fee_on_l1 })
+    }
+}
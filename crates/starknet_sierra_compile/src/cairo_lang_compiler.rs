@@ -6,6 +6,7 @@ use cairo_lang_starknet_classes::contract_class::ContractClass;
 
 use crate::config::SierraToCasmCompilationConfig;
 use crate::errors::CompilationUtilError;
+use crate::utils::validate_raw_class_size;
 use crate::SierraToCasmCompiler;
 
 /// A compiler that compiles Sierra programs to Casm. Uses the code from the
@@ -37,6 +38,7 @@ impl CairoLangSierraToCasmCompiler {
         &self,
         contract_class: ContractClass,
     ) -> Result<CasmContractClass, CompilationUtilError> {
+        validate_raw_class_size(&serde_json::to_string(&contract_class)?, &self.config)?;
         contract_class.validate_version_compatible(ListSelector::DefaultList)?;
 
         Ok(CasmContractClass::from_contract_class(
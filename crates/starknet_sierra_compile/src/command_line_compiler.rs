@@ -9,6 +9,7 @@ use tempfile::NamedTempFile;
 use crate::build_utils::binary_path;
 use crate::config::SierraToCasmCompilationConfig;
 use crate::errors::CompilationUtilError;
+use crate::utils::validate_raw_class_size;
 use crate::SierraToCasmCompiler;
 
 #[derive(Clone)]
@@ -30,6 +31,7 @@ impl SierraToCasmCompiler for CommandLineCompiler {
     ) -> Result<CasmContractClass, CompilationUtilError> {
         // Create a temporary file to store the Sierra contract class.
         let serialized_contract_class = serde_json::to_string(&contract_class)?;
+        validate_raw_class_size(&serialized_contract_class, &self.config)?;
 
         let mut temp_file = NamedTempFile::new()?;
         temp_file.write_all(serialized_contract_class.as_bytes())?;
@@ -13,7 +13,7 @@ use crate::test_utils::contract_class_from_file;
 use crate::SierraToCasmCompiler;
 
 const SIERRA_TO_CASM_COMPILATION_CONFIG: SierraToCasmCompilationConfig =
-    SierraToCasmCompilationConfig { max_bytecode_size: 81920 };
+    SierraToCasmCompilationConfig { max_bytecode_size: 81920, max_raw_class_size: 4089446 };
 
 fn cairo_lang_compiler() -> CairoLangSierraToCasmCompiler {
     CairoLangSierraToCasmCompiler { config: SIERRA_TO_CASM_COMPILATION_CONFIG }
@@ -53,3 +53,23 @@ fn test_negative_flow_compile_sierra_to_casm(#[case] compiler: impl SierraToCasm
     let result = compiler.compile(contract_class);
     assert_matches!(result, Err(CompilationUtilError::CompilationError(..)));
 }
+
+#[rstest]
+#[case::cairo_lang_compiler(CairoLangSierraToCasmCompiler {
+    config: SierraToCasmCompilationConfig { max_raw_class_size: 1, ..SIERRA_TO_CASM_COMPILATION_CONFIG },
+})]
+#[case::command_line_compiler(CommandLineCompiler::new(SierraToCasmCompilationConfig {
+    max_raw_class_size: 1,
+    ..SIERRA_TO_CASM_COMPILATION_CONFIG
+}))]
+fn test_compile_sierra_to_casm_class_size_limit(#[case] compiler: impl SierraToCasmCompiler) {
+    env::set_current_dir(get_absolute_path(TEST_FILES_FOLDER)).expect("Failed to set current dir.");
+    let sierra_path = Path::new(FAULTY_ACCOUNT_CLASS_FILE);
+
+    let contract_class = contract_class_from_file(sierra_path);
+    let result = compiler.compile(contract_class);
+    assert_matches!(
+        result,
+        Err(CompilationUtilError::ClassSizeLimitExceeded { max_class_size: 1, .. })
+    );
+}
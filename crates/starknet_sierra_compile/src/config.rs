@@ -8,21 +8,30 @@ use validator::Validate;
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct SierraToCasmCompilationConfig {
     pub max_bytecode_size: usize,
+    pub max_raw_class_size: usize,
 }
 
 impl Default for SierraToCasmCompilationConfig {
     fn default() -> Self {
-        Self { max_bytecode_size: 81920 }
+        Self { max_bytecode_size: 81920, max_raw_class_size: 4089446 }
     }
 }
 
 impl SerializeConfig for SierraToCasmCompilationConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
-            "max_bytecode_size",
-            &self.max_bytecode_size,
-            "Limitation of contract bytecode size.",
-            ParamPrivacyInput::Public,
-        )])
+        BTreeMap::from_iter([
+            ser_param(
+                "max_bytecode_size",
+                &self.max_bytecode_size,
+                "Limitation of contract bytecode size.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_raw_class_size",
+                &self.max_raw_class_size,
+                "Limitation of the size of the serialized Sierra contract class, in bytes.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
@@ -4,6 +4,10 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum CompilationUtilError {
+    #[error(
+        "Cannot compile a contract class of size {class_size}; max allowed size: {max_class_size}."
+    )]
+    ClassSizeLimitExceeded { class_size: usize, max_class_size: usize },
     #[error("Starknet Sierra compilation error: {0}")]
     CompilationError(String),
     #[error("Unexpected compilation error: {0}")]
@@ -13,6 +13,9 @@ use starknet_api::rpc_transaction::{
 use starknet_api::state::EntryPoint as StarknetApiEntryPoint;
 use starknet_types_core::felt::Felt;
 
+use crate::config::SierraToCasmCompilationConfig;
+use crate::errors::CompilationUtilError;
+
 /// Retruns a [`CairoLangContractClass`] struct ready for Sierra to Casm compilation. Note the `abi`
 /// field is None as it is not relevant for the compilation.
 pub fn into_contract_class_for_compilation(
@@ -65,3 +68,18 @@ pub fn sierra_program_as_felts_to_big_uint_as_hex(sierra_program: &[Felt]) -> Ve
 fn felt_to_big_uint_as_hex(felt: &Felt) -> BigUintAsHex {
     BigUintAsHex { value: felt.to_biguint() }
 }
+
+/// Rejects serialized contract classes larger than the configured limit, before compiling them.
+pub(crate) fn validate_raw_class_size(
+    serialized_contract_class: &str,
+    config: &SierraToCasmCompilationConfig,
+) -> Result<(), CompilationUtilError> {
+    let class_size = serialized_contract_class.len();
+    if class_size > config.max_raw_class_size {
+        return Err(CompilationUtilError::ClassSizeLimitExceeded {
+            class_size,
+            max_class_size: config.max_raw_class_size,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use blockifier::context::BlockContext;
+use blockifier::test_utils::contracts::FeatureContract;
+use starknet_api::block::BlockNumber;
+use starknet_api::executable_transaction::{L1HandlerTransaction, Transaction};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::TransactionHash;
+use starknet_batcher::batcher::{Batcher, BatcherStorageReaderTrait};
+use starknet_batcher::block_builder::BlockBuilder;
+use starknet_batcher::communication::create_local_batcher_server;
+use starknet_batcher::config::BatcherConfig;
+use starknet_batcher::papyrus_state_reader::PapyrusReaderFactory;
+use starknet_batcher::test_utils::FakeClock;
+use starknet_batcher_types::batcher_types::{
+    BuildProposalInput,
+    DecisionReachedInput,
+    GetProposalContentInput,
+    GetProposalProgressInput,
+    ProposalContent,
+    ProposalId,
+    StartHeightInput,
+};
+use starknet_batcher_types::communication::{LocalBatcherClientImpl, SharedBatcherClient};
+use starknet_batcher_types::deadline::Deadline;
+use starknet_gateway::config::GatewayNetworkConfig;
+use starknet_l1_provider_types::communication::MockL1ProviderClient;
+use starknet_mempool_infra::dynamic_config::SharedDynamicConfig;
+use starknet_mempool_infra::trace_util::configure_tracing;
+use starknet_mempool_types::resource_bounds::SharedL2GasPrice;
+use starknet_sequencer_node::servers::get_server_future;
+use starknet_sequencer_node::utils::create_clients_servers_from_config;
+use tempfile::{tempdir, TempDir};
+use tokio::sync::mpsc::channel;
+use tokio::task::JoinHandle;
+
+use crate::integration_test_utils::{create_config, GatewayClient};
+use crate::state_reader::{initialize_test_state_storage, spawn_test_rpc_state_reader};
+
+// The time from the start of a build to its deadline, on the fake clock of the batcher.
+const BUILD_TIME: Duration = Duration::from_secs(1);
+// The real time the block builder is given to add the expected transactions to a block.
+const EXECUTION_TIMEOUT: Duration = Duration::from_secs(30);
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const BATCHER_CHANNEL_SIZE: usize = 32;
+
+/// Builds an [`InProcessSequencer`].
+pub struct InProcessSequencerBuilder {
+    accounts: Vec<FeatureContract>,
+    batcher_config: BatcherConfig,
+    l1_handler_txs: Vec<L1HandlerTransaction>,
+}
+
+impl InProcessSequencerBuilder {
+    /// The given accounts are initialized and funded in the state of the gateway and the batcher.
+    pub fn new(accounts: impl IntoIterator<Item = FeatureContract>) -> Self {
+        let batcher_config = BatcherConfig {
+            chain_info: BlockContext::create_for_testing().chain_info().clone(),
+            ..Default::default()
+        };
+        Self {
+            accounts: accounts.into_iter().collect(),
+            batcher_config,
+            l1_handler_txs: Vec::new(),
+        }
+    }
+
+    /// The storage path of the given config is replaced by a temporary directory.
+    pub fn with_batcher_config(mut self, batcher_config: BatcherConfig) -> Self {
+        self.batcher_config = batcher_config;
+        self
+    }
+
+    /// The L1 handler transactions the mock L1 provider hands to the batcher, in order, until
+    /// they run out.
+    pub fn with_l1_handler_txs(
+        mut self,
+        l1_handler_txs: impl IntoIterator<Item = L1HandlerTransaction>,
+    ) -> Self {
+        self.l1_handler_txs.extend(l1_handler_txs);
+        self
+    }
+
+    pub async fn build(self) -> InProcessSequencer {
+        let Self { accounts, mut batcher_config, l1_handler_txs } = self;
+
+        // Configure and start tracing
+        configure_tracing();
+
+        // Spawn a papyrus rpc server for the state the gateway validates against.
+        let rpc_server_addr = spawn_test_rpc_state_reader(accounts.iter().copied()).await;
+
+        // The batcher is created below, with a fake clock and a mock L1 provider, and is driven by
+        // the harness instead of consensus.
+        let mut config = create_config(rpc_server_addr).await;
+        config.components.batcher.execute = false;
+        config.components.consensus_manager.execute = false;
+
+        let (clients, servers) = create_clients_servers_from_config(&config);
+
+        let GatewayNetworkConfig { ip, port, .. } = config.gateway_config.network_config;
+        let gateway_client = GatewayClient::new(SocketAddr::from((ip, port)));
+        let gateway_handle = tokio::spawn(get_server_future("Gateway", true, servers.gateway));
+        let mempool_handle = tokio::spawn(get_server_future("Mempool", true, servers.mempool));
+
+        // Wait for server to spin up.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // The batcher executes its blocks on top of its own copy of the state of the gateway.
+        let storage_dir = tempdir().unwrap();
+        batcher_config.storage.db_config.path_prefix = storage_dir.path().to_path_buf();
+        let (storage_reader, storage_writer) =
+            initialize_test_state_storage(accounts, batcher_config.storage.clone());
+        let height = storage_reader.height().unwrap();
+
+        let clock = Arc::new(FakeClock::default());
+        let l2_gas_price = SharedL2GasPrice::default();
+        let block_builder = BlockBuilder::new(
+            SharedDynamicConfig::new(batcher_config.block_builder.clone()),
+            Arc::new(PapyrusReaderFactory {
+                storage_reader: storage_reader.clone(),
+                class_manager_client: None,
+            }),
+            batcher_config.chain_info.clone(),
+            l2_gas_price.clone(),
+            None,
+            clock.clone(),
+        )
+        .with_fee_config(batcher_config.fee_config);
+        let batcher = Batcher::new(
+            batcher_config,
+            clients.get_mempool_client().unwrap(),
+            Some(Arc::new(mock_l1_provider_client(l1_handler_txs))),
+            None,
+            Arc::new(storage_reader),
+            Box::new(storage_writer),
+            Arc::new(block_builder),
+            l2_gas_price,
+            clock.clone(),
+        );
+
+        let (tx_batcher, rx_batcher) = channel(BATCHER_CHANNEL_SIZE);
+        let batcher_client = Arc::new(LocalBatcherClientImpl::new(tx_batcher));
+        let batcher_server = create_local_batcher_server(batcher, rx_batcher);
+        let batcher_handle =
+            tokio::spawn(get_server_future("Batcher", true, Some(Box::new(batcher_server))));
+
+        InProcessSequencer {
+            gateway_client,
+            batcher_client,
+            clock,
+            height,
+            next_proposal_id: 0,
+            gateway_handle,
+            mempool_handle,
+            batcher_handle,
+            _storage_dir: storage_dir,
+        }
+    }
+}
+
+/// A gateway, a mempool and a batcher running in the test process, connected by local clients. The
+/// batcher measures its deadlines by a fake clock, takes its L1 handler transactions from a mock L1
+/// provider, and builds its blocks when the test asks, standing in for consensus.
+pub struct InProcessSequencer {
+    pub gateway_client: GatewayClient,
+    pub batcher_client: SharedBatcherClient,
+    pub clock: Arc<FakeClock>,
+    // The height of the next block.
+    height: BlockNumber,
+    next_proposal_id: ProposalId,
+    pub gateway_handle: JoinHandle<()>,
+    pub mempool_handle: JoinHandle<()>,
+    pub batcher_handle: JoinHandle<()>,
+    // Keeps the storage of the batcher for the lifetime of the harness.
+    _storage_dir: TempDir,
+}
+
+impl InProcessSequencer {
+    pub async fn assert_add_tx_success(&self, tx: &RpcTransaction) -> TransactionHash {
+        self.gateway_client.assert_add_tx_success(tx).await
+    }
+
+    /// Builds a block of the next height and decides on it, committing it. The block is closed
+    /// once `n_txs` transactions were added to it, with the transactions of the mempool and the L1
+    /// handler transactions of the mock L1 provider. Returns the transactions of the block.
+    pub async fn build_block(&mut self, n_txs: usize) -> Vec<Transaction> {
+        let height = self.height;
+        let proposal_id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+
+        self.batcher_client.start_height(StartHeightInput { height }).await.unwrap();
+        self.batcher_client
+            .build_proposal(BuildProposalInput {
+                proposal_id,
+                deadline: Deadline::after(BUILD_TIME),
+                height,
+                round: 0,
+                timestamp: None,
+            })
+            .await
+            .unwrap();
+        self.wait_for_executed_txs(proposal_id, n_txs).await;
+
+        // Reach the deadline of the build, closing the block.
+        self.clock.advance(BUILD_TIME);
+        let txs = self.proposal_txs(proposal_id).await;
+
+        self.batcher_client.decision_reached(DecisionReachedInput { proposal_id }).await.unwrap();
+        self.height = height.unchecked_next();
+        txs
+    }
+
+    /// Builds a block of the next height, as in [`Self::build_block`], and asserts it consists of
+    /// the given transactions, in order.
+    pub async fn assert_next_block_tx_hashes(&mut self, expected_tx_hashes: &[TransactionHash]) {
+        let txs = self.build_block(expected_tx_hashes.len()).await;
+        let tx_hashes: Vec<TransactionHash> = txs.iter().map(|tx| tx.tx_hash()).collect();
+        assert_eq!(tx_hashes, expected_tx_hashes);
+    }
+
+    async fn wait_for_executed_txs(&self, proposal_id: ProposalId, n_txs: usize) {
+        let wait = async {
+            loop {
+                let progress = self
+                    .batcher_client
+                    .get_proposal_progress(GetProposalProgressInput { proposal_id })
+                    .await
+                    .unwrap();
+                if progress.n_executed_txs >= n_txs {
+                    return;
+                }
+                tokio::time::sleep(PROGRESS_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(EXECUTION_TIMEOUT, wait).await.unwrap_or_else(|_| {
+            panic!("Proposal {proposal_id} did not reach {n_txs} transactions in time.")
+        });
+    }
+
+    async fn proposal_txs(&self, proposal_id: ProposalId) -> Vec<Transaction> {
+        let mut txs = Vec::new();
+        let mut page = 0;
+        loop {
+            let content = self
+                .batcher_client
+                .get_proposal_content(GetProposalContentInput { proposal_id, page })
+                .await
+                .unwrap()
+                .content;
+            match content {
+                ProposalContent::Txs(page_txs) => txs.extend(page_txs),
+                ProposalContent::Finished { .. } => return txs,
+            }
+            page += 1;
+        }
+    }
+}
+
+fn mock_l1_provider_client(l1_handler_txs: Vec<L1HandlerTransaction>) -> MockL1ProviderClient {
+    let pending_txs = Mutex::new(l1_handler_txs);
+    let mut l1_provider_client = MockL1ProviderClient::new();
+    l1_provider_client.expect_get_txs().returning(move |n_txs| {
+        let mut pending_txs = pending_txs.lock().unwrap();
+        let n_txs = n_txs.min(pending_txs.len());
+        Ok(pending_txs.drain(..n_txs).collect())
+    });
+    l1_provider_client.expect_commit_block().returning(|_| Ok(()));
+    l1_provider_client
+}
@@ -8,8 +8,8 @@ use starknet_api::transaction::TransactionHash;
 use starknet_gateway::config::GatewayNetworkConfig;
 use starknet_gateway::errors::GatewaySpecError;
 use starknet_mempool_infra::trace_util::configure_tracing;
-use starknet_mempool_node::servers::get_server_future;
-use starknet_mempool_node::utils::create_clients_servers_from_config;
+use starknet_sequencer_node::servers::get_server_future;
+use starknet_sequencer_node::utils::create_clients_servers_from_config;
 use starknet_task_executor::tokio_executor::TokioExecutor;
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
@@ -46,12 +46,12 @@ impl IntegrationTestSetup {
         // Spawn a papyrus rpc server for a papyrus storage reader.
         let rpc_server_addr = spawn_test_rpc_state_reader(accounts).await;
 
-        // Derive the configuration for the mempool node.
+        // Derive the configuration for the sequencer node.
         let config = create_config(rpc_server_addr).await;
 
         let (clients, servers) = create_clients_servers_from_config(&config);
 
-        let GatewayNetworkConfig { ip, port } = config.gateway_config.network_config;
+        let GatewayNetworkConfig { ip, port, .. } = config.gateway_config.network_config;
         let gateway_client = GatewayClient::new(SocketAddr::from((ip, port)));
 
         let gateway_future = get_server_future("Gateway", true, servers.gateway);
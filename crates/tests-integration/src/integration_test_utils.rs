@@ -10,37 +10,77 @@ use reqwest::{Client, Response};
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_gateway::config::{
+    ApiVersionsConfig,
+    ArrivalJournalConfig,
+    ClassCacheConfig,
+    CompiledClassCacheConfig,
+    DeclareGatingConfig,
+    DuplicateDetectionConfig,
     GatewayConfig,
     GatewayNetworkConfig,
+    IdempotencyConfig,
+    LoadSheddingConfig,
+    RateLimitConfig,
+    RequestLoggingConfig,
     RpcStateReaderConfig,
+    SenderAccessConfig,
+    SimulationConfig,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
+    TenantsConfig,
+    TransactionEventsConfig,
+    TransactionStatusConfig,
+    TransactionTypeConfig,
 };
 use starknet_gateway::errors::GatewaySpecError;
-use starknet_mempool_node::config::MempoolNodeConfig;
+use starknet_sequencer_node::config::SequencerNodeConfig;
 use tokio::net::TcpListener;
 
 use crate::integration_test_setup::IntegrationTestSetup;
 
 async fn create_gateway_config() -> GatewayConfig {
     let stateless_tx_validator_config = StatelessTransactionValidatorConfig {
-        validate_non_zero_l1_gas_fee: true,
         max_calldata_length: 10,
         max_signature_length: 2,
         ..Default::default()
     };
 
     let socket = get_available_socket().await;
-    let network_config = GatewayNetworkConfig { ip: socket.ip(), port: socket.port() };
+    let network_config =
+        GatewayNetworkConfig { ip: socket.ip(), port: socket.port(), ..Default::default() };
     let stateful_tx_validator_config = StatefulTransactionValidatorConfig::create_for_testing();
 
-    GatewayConfig { network_config, stateless_tx_validator_config, stateful_tx_validator_config }
+    GatewayConfig {
+        network_config,
+        stateless_tx_validator_config,
+        stateful_tx_validator_config,
+        transaction_type_config: TransactionTypeConfig::default(),
+        arrival_journal_config: ArrivalJournalConfig::default(),
+        request_logging_config: RequestLoggingConfig::default(),
+        class_cache_config: ClassCacheConfig::default(),
+        compiled_class_cache_config: CompiledClassCacheConfig::default(),
+        idempotency_config: IdempotencyConfig::default(),
+        duplicate_detection_config: DuplicateDetectionConfig::default(),
+        rate_limit_config: RateLimitConfig::default(),
+        sender_access_config: SenderAccessConfig::default(),
+        transaction_status_config: TransactionStatusConfig::default(),
+        transaction_events_config: TransactionEventsConfig::default(),
+        tenants_config: TenantsConfig::default(),
+        simulation_config: SimulationConfig::default(),
+        load_shedding_config: LoadSheddingConfig::default(),
+        api_versions_config: ApiVersionsConfig::default(),
+        declare_gating_config: DeclareGatingConfig::default(),
+    }
 }
 
-pub async fn create_config(rpc_server_addr: SocketAddr) -> MempoolNodeConfig {
+pub async fn create_config(rpc_server_addr: SocketAddr) -> SequencerNodeConfig {
     let gateway_config = create_gateway_config().await;
     let rpc_state_reader_config = test_rpc_state_reader_config(rpc_server_addr);
-    MempoolNodeConfig { gateway_config, rpc_state_reader_config, ..MempoolNodeConfig::default() }
+    SequencerNodeConfig {
+        gateway_config,
+        rpc_state_reader_config,
+        ..SequencerNodeConfig::default()
+    }
 }
 
 /// A test utility client for interacting with a gateway server.
@@ -87,6 +127,7 @@ fn test_rpc_state_reader_config(rpc_server_addr: SocketAddr) -> RpcStateReaderCo
     RpcStateReaderConfig {
         url: format!("http://{rpc_server_addr:?}/rpc/{RPC_SPEC_VERION}"),
         json_rpc_version: JSON_RPC_VERSION.to_string(),
+        strk_per_eth_rate: None,
     }
 }
 
@@ -1,3 +1,4 @@
+pub mod in_process_sequencer;
 pub mod integration_test_setup;
 pub mod integration_test_utils;
 pub mod mock_batcher;
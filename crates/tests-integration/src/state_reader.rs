@@ -24,7 +24,7 @@ use papyrus_storage::class::ClassStorageWriter;
 use papyrus_storage::compiled_class::CasmStorageWriter;
 use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::state::StateStorageWriter;
-use papyrus_storage::{open_storage, StorageConfig, StorageReader};
+use papyrus_storage::{open_storage, StorageConfig, StorageReader, StorageWriter};
 use starknet_api::block::{
     BlockBody,
     BlockHeader,
@@ -64,6 +64,19 @@ fn deploy_account_tx_contract_address() -> &'static ContractAddress {
 pub async fn spawn_test_rpc_state_reader(
     accounts: impl IntoIterator<Item = FeatureContract>,
 ) -> SocketAddr {
+    let mut storage_config = StorageConfig::default();
+    let tempdir = tempdir().unwrap();
+    storage_config.db_config.path_prefix = tempdir.path().to_path_buf();
+    let (storage_reader, _) = initialize_test_state_storage(accounts, storage_config);
+    run_papyrus_rpc_server(storage_reader).await
+}
+
+/// Opens a papyrus storage by the given config and writes the test state to it, as the genesis
+/// block: a variable number of identical accounts and test contracts are initialized and funded.
+pub fn initialize_test_state_storage(
+    accounts: impl IntoIterator<Item = FeatureContract>,
+    storage_config: StorageConfig,
+) -> (StorageReader, StorageWriter) {
     let block_context = BlockContext::create_for_testing();
 
     // Map feature contracts to their number of instances inside the account array.
@@ -82,13 +95,13 @@ pub async fn spawn_test_rpc_state_reader(
 
     let fund_accounts = vec![*deploy_account_tx_contract_address()];
 
-    let storage_reader = initialize_papyrus_test_state(
+    initialize_papyrus_test_state(
         block_context.chain_info(),
         BALANCE,
         account_to_n_instances,
         fund_accounts,
-    );
-    run_papyrus_rpc_server(storage_reader).await
+        storage_config,
+    )
 }
 
 fn initialize_papyrus_test_state(
@@ -96,7 +109,8 @@ fn initialize_papyrus_test_state(
     initial_balances: u128,
     contract_instances: IndexMap<FeatureContract, usize>,
     fund_additional_accounts: Vec<ContractAddress>,
-) -> StorageReader {
+    storage_config: StorageConfig,
+) -> (StorageReader, StorageWriter) {
     let state_diff = prepare_state_diff(
         chain_info,
         &contract_instances,
@@ -107,7 +121,12 @@ fn initialize_papyrus_test_state(
     let (cairo0_contract_classes, cairo1_contract_classes) =
         prepare_compiled_contract_classes(contract_instances.into_keys());
 
-    write_state_to_papyrus_storage(state_diff, &cairo0_contract_classes, &cairo1_contract_classes)
+    write_state_to_papyrus_storage(
+        state_diff,
+        &cairo0_contract_classes,
+        &cairo1_contract_classes,
+        storage_config,
+    )
 }
 
 fn prepare_state_diff(
@@ -193,13 +212,11 @@ fn write_state_to_papyrus_storage(
     state_diff: ThinStateDiff,
     cairo0_contract_classes: &[(ClassHash, DeprecatedContractClass)],
     cairo1_contract_classes: &[(ClassHash, CasmContractClass)],
-) -> StorageReader {
+    storage_config: StorageConfig,
+) -> (StorageReader, StorageWriter) {
     let block_number = BlockNumber(0);
     let block_header = test_block_header(block_number);
 
-    let mut storage_config = StorageConfig::default();
-    let tempdir = tempdir().unwrap();
-    storage_config.db_config.path_prefix = tempdir.path().to_path_buf();
     let (storage_reader, mut storage_writer) = open_storage(storage_config).unwrap();
 
     let cairo0_contract_classes =
@@ -221,7 +238,7 @@ fn write_state_to_papyrus_storage(
         .commit()
         .unwrap();
 
-    storage_reader
+    (storage_reader, storage_writer)
 }
 
 fn test_block_header(block_number: BlockNumber) -> BlockHeader {
@@ -0,0 +1,35 @@
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::CairoVersion;
+use mempool_test_utils::starknet_api_test_utils::MultiAccountTransactionGenerator;
+use starknet_mempool_integration_tests::in_process_sequencer::InProcessSequencerBuilder;
+
+#[tokio::test]
+async fn test_submitted_txs_are_built_into_block() {
+    // Setup.
+    let accounts = [
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo1),
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0),
+    ];
+    let mut sequencer = InProcessSequencerBuilder::new(accounts).build().await;
+    let mut tx_generator = MultiAccountTransactionGenerator::new_for_account_contracts(accounts);
+
+    let account0_deploy_nonce0 = &tx_generator.account_with_id(0).generate_default_deploy_account();
+    let account1_invoke_nonce0 = tx_generator.account_with_id(1).generate_default_invoke();
+
+    // Test.
+
+    let account0_deploy_nonce0_tx_hash =
+        sequencer.assert_add_tx_success(account0_deploy_nonce0).await;
+    let account1_invoke_nonce0_tx_hash =
+        sequencer.assert_add_tx_success(&account1_invoke_nonce0).await;
+
+    // Assert.
+
+    // The block takes the transactions in the order the mempool hands them out.
+    sequencer
+        .assert_next_block_tx_hashes(&[
+            account1_invoke_nonce0_tx_hash,
+            account0_deploy_nonce0_tx_hash,
+        ])
+        .await;
+}